@@ -0,0 +1,231 @@
+//! Content-hash incremental build manifest.
+//!
+//! Persists a `source path -> (fingerprint, output path)` map under the site's
+//! output directory as JSON, so a rebuild can skip reprocessing any file whose
+//! fingerprint hasn't changed since the last run. The fingerprint hashes a
+//! file's bytes together with its modification time, mirroring Deno's
+//! `calculate_fs_version` approach of fingerprinting source content.
+
+use crate::hashing::content_fingerprint;
+use crate::parsers::{parse_json, JsonValue};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Name of the manifest file, written under the site's output directory.
+pub const MANIFEST_FILE_NAME: &str = ".build-manifest.json";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ManifestEntry {
+    fingerprint: String,
+    output_path: String,
+}
+
+/// Maps a source file path to the fingerprint and output path it produced
+/// the last time it was processed.
+#[derive(Debug, Default)]
+pub struct BuildManifest {
+    entries: HashMap<String, ManifestEntry>,
+}
+
+impl BuildManifest {
+    /// Loads the manifest from `manifest_path`, returning an empty manifest if
+    /// the file is missing or can't be parsed (e.g. it's from an older,
+    /// incompatible format).
+    pub fn load(manifest_path: &Path) -> Self {
+        let Ok(raw) = fs::read_to_string(manifest_path) else {
+            return Self::default();
+        };
+        let Ok(JsonValue::Object(source_paths)) = parse_json(&raw) else {
+            return Self::default();
+        };
+
+        let mut entries = HashMap::new();
+        for (source_path, value) in source_paths {
+            let JsonValue::Object(fields) = value else {
+                continue;
+            };
+            let fingerprint = fields.get("fingerprint").and_then(as_str);
+            let output_path = fields.get("output_path").and_then(as_str);
+            if let (Some(fingerprint), Some(output_path)) = (fingerprint, output_path) {
+                entries.insert(
+                    source_path,
+                    ManifestEntry {
+                        fingerprint,
+                        output_path,
+                    },
+                );
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Writes the manifest to `manifest_path` as JSON, creating parent
+    /// directories as needed.
+    pub fn save(&self, manifest_path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = manifest_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut source_paths = HashMap::new();
+        for (source_path, entry) in &self.entries {
+            let mut fields = HashMap::new();
+            fields.insert(
+                "fingerprint".to_string(),
+                JsonValue::String(entry.fingerprint.clone()),
+            );
+            fields.insert(
+                "output_path".to_string(),
+                JsonValue::String(entry.output_path.clone()),
+            );
+            source_paths.insert(source_path.clone(), JsonValue::Object(fields));
+        }
+
+        fs::write(
+            manifest_path,
+            JsonValue::Object(source_paths).to_string_pretty(2),
+        )
+    }
+
+    /// Returns the previously recorded output path for `source_path` if its
+    /// fingerprint is unchanged, meaning processing it can be skipped.
+    pub fn unchanged_output_path(&self, source_path: &str, fingerprint: &str) -> Option<&str> {
+        self.entries
+            .get(source_path)
+            .filter(|entry| entry.fingerprint == fingerprint)
+            .map(|entry| entry.output_path.as_str())
+    }
+
+    /// Records (or updates) the fingerprint and output path produced for `source_path`.
+    pub fn record(&mut self, source_path: &str, fingerprint: String, output_path: String) {
+        self.entries.insert(
+            source_path.to_string(),
+            ManifestEntry {
+                fingerprint,
+                output_path,
+            },
+        );
+    }
+}
+
+fn as_str(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Fingerprints a file's bytes together with its modification time. A file
+/// whose bytes are untouched keeps the same fingerprint even if its mtime is
+/// unreadable (e.g. on filesystems that don't report one); a byte change is
+/// always detected regardless of mtime.
+pub fn fingerprint_file_contents(content: &[u8], mtime: Option<SystemTime>) -> String {
+    let mut buffer = Vec::with_capacity(content.len() + 16);
+    buffer.extend_from_slice(content);
+    if let Some(duration) = mtime.and_then(|mtime| mtime.duration_since(SystemTime::UNIX_EPOCH).ok()) {
+        buffer.extend_from_slice(&duration.as_nanos().to_le_bytes());
+    }
+    content_fingerprint(&buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn fingerprint_changes_when_content_changes() {
+        let mtime = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000));
+        assert_ne!(
+            fingerprint_file_contents(b"hello", mtime),
+            fingerprint_file_contents(b"world", mtime)
+        );
+    }
+
+    #[test]
+    fn fingerprint_changes_when_mtime_changes() {
+        let earlier = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000));
+        let later = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(2_000));
+        assert_ne!(
+            fingerprint_file_contents(b"hello", earlier),
+            fingerprint_file_contents(b"hello", later)
+        );
+    }
+
+    #[test]
+    fn fingerprint_is_stable_without_an_mtime() {
+        assert_eq!(
+            fingerprint_file_contents(b"hello", None),
+            fingerprint_file_contents(b"hello", None)
+        );
+    }
+
+    #[test]
+    fn round_trips_through_save_and_load() {
+        let dir = tempdir().unwrap();
+        let manifest_path = dir.path().join("out/.build-manifest.json");
+
+        let mut manifest = BuildManifest::default();
+        manifest.record(
+            "sites/test/assets/style.css",
+            "abc123".to_string(),
+            "out/test/assets/style-abc123.css".to_string(),
+        );
+        manifest.save(&manifest_path).unwrap();
+
+        let loaded = BuildManifest::load(&manifest_path);
+        assert_eq!(
+            loaded.unchanged_output_path("sites/test/assets/style.css", "abc123"),
+            Some("out/test/assets/style-abc123.css")
+        );
+    }
+
+    #[test]
+    fn unchanged_output_path_is_none_when_fingerprint_differs() {
+        let mut manifest = BuildManifest::default();
+        manifest.record(
+            "sites/test/assets/style.css",
+            "abc123".to_string(),
+            "out/test/assets/style-abc123.css".to_string(),
+        );
+        assert_eq!(
+            manifest.unchanged_output_path("sites/test/assets/style.css", "def456"),
+            None
+        );
+    }
+
+    #[test]
+    fn unchanged_output_path_is_none_for_unknown_source() {
+        let manifest = BuildManifest::default();
+        assert_eq!(
+            manifest.unchanged_output_path("sites/test/assets/unknown.css", "abc123"),
+            None
+        );
+    }
+
+    #[test]
+    fn load_returns_empty_manifest_for_missing_file() {
+        let dir = tempdir().unwrap();
+        let manifest_path = dir.path().join("does-not-exist.json");
+        let manifest = BuildManifest::load(&manifest_path);
+        assert_eq!(
+            manifest.unchanged_output_path("anything", "anything"),
+            None
+        );
+    }
+
+    #[test]
+    fn load_returns_empty_manifest_for_malformed_json() {
+        let dir = tempdir().unwrap();
+        let manifest_path = dir.path().join("manifest.json");
+        fs::write(&manifest_path, "not json").unwrap();
+        let manifest = BuildManifest::load(&manifest_path);
+        assert_eq!(
+            manifest.unchanged_output_path("anything", "anything"),
+            None
+        );
+    }
+}
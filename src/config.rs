@@ -1,6 +1,9 @@
 //! Application-wide configuration values.
 
+use crate::parsers::{parse_content_with_front_matter, parse_toml, JsonValue};
+use std::collections::HashMap;
 use std::env;
+use std::path::{Path, PathBuf};
 
 /// Site configuration structure containing all configurable options
 #[derive(Debug, Clone)]
@@ -31,6 +34,153 @@ pub struct SiteConfig {
     pub server_host: String,
     /// Server port
     pub server_port: u16,
+    /// Whether to emit `.map` Source Map v3 files alongside minified CSS/JS assets
+    pub source_maps: bool,
+    /// Browserslist-style CSS target query (e.g. `"last 2 versions, ie 11"`)
+    /// driving vendor prefixing and legacy syntax lowering. `None` disables
+    /// the transform entirely.
+    pub css_targets: Option<String>,
+    /// Whether `minify_html` also runs the CSS/JS minifiers over `<style>`
+    /// and `<script>` block bodies. Enabled by default; disable it to keep
+    /// inline script/style content verbatim for debugging.
+    pub minify_inline_assets: bool,
+    /// Leading markers that make `minify_html` keep an HTML comment
+    /// (verbatim, but with its inner markup still minified) instead of
+    /// stripping it. Defaults to `[if` (IE conditional comments) and `!`
+    /// (the "preserve this" convention, e.g. for license/legal notices).
+    pub preserved_comment_markers: Vec<String>,
+    /// Bypasses the incremental build manifest, forcing every asset to be
+    /// reprocessed even if its content fingerprint is unchanged. Set via the
+    /// `generate` command's `--force` flag.
+    pub force_rebuild: bool,
+    /// Taxonomies (category, tags, ...) to group posts by and generate
+    /// paginated term pages for. See [`TaxonomyConfig`].
+    pub taxonomies: Vec<TaxonomyConfig>,
+    /// Output path segment category listing pages are nested under, e.g.
+    /// `/category/travel/page/2/`. Lets a site use `/topics/...` instead.
+    pub taxonomy_path_prefix: String,
+    /// Output path segment a pagination page number is nested under, e.g.
+    /// `/category/travel/page/2/`. Lets a site use `/p/2/` instead.
+    pub paginate_path: String,
+    /// How posts are ordered within a term page before being sliced into
+    /// pages, unless a [`TaxonomyConfig`] overrides it. See [`SortMode`].
+    pub default_sort_mode: SortMode,
+    /// When enabled, a pagination listing's first page is served at its own
+    /// root (e.g. `/category/travel/`) with `index.html` written directly
+    /// into that directory, instead of `/category/travel/page/1/`. Every
+    /// other page keeps the `.../{paginate_path}/{page_num}/` form. Acts as
+    /// the site-wide default for the per-listing `pagination_index_first_page`
+    /// variable.
+    pub pagination_index_first_page: bool,
+    /// The deployed site's base URL (e.g. `"https://example.com"`), used by
+    /// [`Self::make_permalink`] to turn a site-relative path into an
+    /// absolute link.
+    pub base_url: String,
+    /// Whether [`Self::make_permalink`] appends a trailing slash to
+    /// permalinks that don't already end in one.
+    pub trailing_slash: bool,
+    /// Whether `serve` watches the site's content directories and rebuilds
+    /// on change, in the spirit of `zola serve`.
+    pub watch: bool,
+    /// How long to wait after a filesystem change before rebuilding, so a
+    /// burst of edits (e.g. a save-all, or an editor writing a temp file
+    /// then renaming it over the real one) coalesces into a single rebuild.
+    pub watch_debounce_ms: u64,
+    /// How the main post listing orders posts before pagination. See
+    /// [`PostSortOrder`].
+    pub post_sort_order: PostSortOrder,
+    /// Whether posts and pages are rendered concurrently (via rayon) rather
+    /// than one at a time. Each item writes to its own output path and only
+    /// reads shared, read-only state, so this is safe by default; disable it
+    /// for snapshot tests that expect deterministic output ordering.
+    pub parallel_content_generation: bool,
+    /// Words-per-minute rate used to estimate a content item's
+    /// `reading_time` template variable from its `word_count`.
+    pub reading_time_wpm: usize,
+}
+
+/// How posts within a taxonomy term are ordered before pagination, mirroring
+/// Zola's `sort_by` taxonomy option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortMode {
+    /// Newest `date` first. Posts with a missing or unparseable `date` keep
+    /// their position relative to one another.
+    Date,
+    /// Ascending by an integer `order` field. Posts with a missing or
+    /// non-numeric `order` keep their position relative to one another.
+    Order,
+    /// Preserve input order as-is.
+    None,
+}
+
+/// How the main post listing orders posts before pagination chunks them
+/// into pages. Unlike [`SortMode`] (used for taxonomy term pages), date
+/// sorting direction is explicit rather than always descending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostSortOrder {
+    /// Newest `date` first. Posts with a missing or unparseable `date` sort
+    /// after every post that has one.
+    DateDesc,
+    /// Oldest `date` first. Posts with a missing or unparseable `date` sort
+    /// after every post that has one.
+    DateAsc,
+    /// Ascending by an integer `order` front-matter key. Posts with a
+    /// missing or non-numeric `order` sort after every post that has one.
+    Order,
+    /// Preserve input order as-is.
+    None,
+}
+
+impl std::str::FromStr for PostSortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "date_desc" => Ok(PostSortOrder::DateDesc),
+            "date_asc" => Ok(PostSortOrder::DateAsc),
+            "order" => Ok(PostSortOrder::Order),
+            "none" => Ok(PostSortOrder::None),
+            _ => Err(format!(
+                "Invalid post sort order `{s}`; expected one of: date_desc, date_asc, order, none"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for PostSortOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            PostSortOrder::DateDesc => "date_desc",
+            PostSortOrder::DateAsc => "date_asc",
+            PostSortOrder::Order => "order",
+            PostSortOrder::None => "none",
+        };
+        f.write_str(s)
+    }
+}
+
+/// One taxonomy to group posts by: a front-matter field whose distinct
+/// values each become a term with its own listing page, e.g. `category` or
+/// `tags`. Modeled on Zola's taxonomy configuration.
+#[derive(Debug, Clone)]
+pub struct TaxonomyConfig {
+    /// Taxonomy name, used as the output path segment (`/<name>/<slug>/`)
+    /// and exposed to templates as the `taxonomy_name` variable.
+    pub name: String,
+    /// Front-matter field a post's term value(s) are read from, e.g.
+    /// `"category"` or `"tags"`.
+    pub front_matter_key: String,
+    /// Whether the field holds several comma-separated values (e.g.
+    /// `tags: rust, web`) rather than a single value. Multi-valued fields
+    /// are split on commas, trimmed, and the post is added to every
+    /// resulting term.
+    pub multi_valued: bool,
+    /// Whether term pages are paginated into `posts_per_page`-sized chunks.
+    /// Terms with few posts usually don't need it, but most do.
+    pub paginated: bool,
+    /// Overrides [`SiteConfig::default_sort_mode`] for this taxonomy's term
+    /// pages. `None` inherits the site-wide default.
+    pub sort_mode: Option<SortMode>,
 }
 
 impl Default for SiteConfig {
@@ -49,6 +199,29 @@ impl Default for SiteConfig {
             default_posts_per_page: 5,
             server_host: "localhost".to_string(),
             server_port: 2030,
+            source_maps: false,
+            css_targets: None,
+            minify_inline_assets: true,
+            preserved_comment_markers: vec!["[if".to_string(), "!".to_string()],
+            force_rebuild: false,
+            taxonomies: vec![TaxonomyConfig {
+                name: "category".to_string(),
+                front_matter_key: "category".to_string(),
+                multi_valued: false,
+                paginated: true,
+                sort_mode: None,
+            }],
+            taxonomy_path_prefix: "category".to_string(),
+            paginate_path: "page".to_string(),
+            default_sort_mode: SortMode::Date,
+            pagination_index_first_page: false,
+            base_url: "https://example.com".to_string(),
+            trailing_slash: true,
+            watch: false,
+            watch_debounce_ms: 300,
+            post_sort_order: PostSortOrder::DateDesc,
+            parallel_content_generation: true,
+            reading_time_wpm: 200,
         }
     }
 }
@@ -60,9 +233,337 @@ impl SiteConfig {
         Self::default()
     }
 
+    /// Loads configuration from `path` layered over built-in defaults and
+    /// environment overrides: built-in defaults < file values < environment
+    /// overrides. `path` is parsed as TOML if it has a `.toml` extension,
+    /// otherwise as the same front matter format a site's `config.md` uses
+    /// (see [`crate::file_readers::load_site_config`]). Every field is
+    /// optional in the file; keys it doesn't set are left at their default
+    /// (and still overridable by environment variables). If `path` doesn't
+    /// exist, file values are simply skipped.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read config file {}: {e}", path.display()))?;
+            let values = Self::read_file_values(path, &content)?;
+            config.apply_file_values(&values)?;
+        }
+
+        config.apply_environment_overlay();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Produces a per-site config by overriding `self` (the global base)
+    /// with whichever fields `sites_base_dir/site_name/config.md` (or
+    /// `config.toml`, tried if the former doesn't exist) sets - the same
+    /// fields [`Self::apply_file_values`] recognizes, e.g. `base_url`,
+    /// `default_posts_per_page`, or `main_layout`. Fields the site file
+    /// doesn't mention are inherited from `self` unchanged. If neither file
+    /// exists, the result is just a clone of `self`. The merged result is
+    /// validated before being returned, and any error - a malformed file or
+    /// a validation failure - names `site_name`.
+    pub fn for_site(&self, site_name: &str) -> Result<SiteConfig, String> {
+        let mut config = self.clone();
+        let site_dir = PathBuf::from(&self.sites_base_dir).join(site_name);
+
+        for path in [site_dir.join(&self.config_file), site_dir.join("config.toml")] {
+            if !path.exists() {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                format!("Failed to read config for site `{site_name}` ({}): {e}", path.display())
+            })?;
+            let values = Self::read_file_values(&path, &content)
+                .map_err(|e| format!("Invalid config for site `{site_name}`: {e}"))?;
+            config
+                .apply_file_values(&values)
+                .map_err(|e| format!("Invalid config for site `{site_name}`: {e}"))?;
+            break;
+        }
+
+        config
+            .validate()
+            .map_err(|e| format!("Invalid config for site `{site_name}`: {e}"))?;
+        Ok(config)
+    }
+
+    /// Renders every field [`Self::apply_file_values`] recognizes as a
+    /// commented `key: value` line, fenced as the YAML front matter
+    /// `config.md` expects, so the output both documents each setting and
+    /// round-trips through [`Self::load`] back to an equal config. Intended
+    /// for scaffolding a new site's config file pre-filled with its current
+    /// (often default) values, ready to edit.
+    ///
+    /// `preserved_comment_markers` and `taxonomies` are left out: they're
+    /// structured values [`Self::apply_file_values`] doesn't load from a
+    /// file either (see its doc comment), so there's no key that would
+    /// round-trip them.
+    pub fn to_config_string(&self) -> String {
+        let mut out = String::from("---\n");
+        out.push_str("# Site configuration. Edit any value below; remove a line to fall\n");
+        out.push_str("# back to its built-in default (shown here) or an environment override.\n");
+
+        push_field(&mut out, "Output directory for generated site", "output_dir", &self.output_dir);
+        push_field(&mut out, "Base directory for sites", "sites_base_dir", &self.sites_base_dir);
+        push_field(&mut out, "Posts subdirectory name", "posts_subdir", &self.posts_subdir);
+        push_field(&mut out, "Pages subdirectory name", "pages_subdir", &self.pages_subdir);
+        push_field(&mut out, "Includes subdirectory name", "includes_subdir", &self.includes_subdir);
+        push_field(&mut out, "Layouts subdirectory name", "layouts_subdir", &self.layouts_subdir);
+        push_field(&mut out, "Assets subdirectory name", "assets_subdir", &self.assets_subdir);
+        push_field(&mut out, "Data subdirectory name", "data_subdir", &self.data_subdir);
+        push_field(&mut out, "Main layout file name", "main_layout", &self.main_layout);
+        push_field(&mut out, "Configuration file name", "config_file", &self.config_file);
+        push_field(
+            &mut out,
+            "Default posts per page for pagination",
+            "default_posts_per_page",
+            self.default_posts_per_page,
+        );
+        push_field(&mut out, "Server host", "server_host", &self.server_host);
+        push_field(&mut out, "Server port", "server_port", self.server_port);
+        push_field(
+            &mut out,
+            "Whether to emit .map Source Map v3 files alongside minified CSS/JS assets",
+            "source_maps",
+            self.source_maps,
+        );
+        push_field(
+            &mut out,
+            "Browserslist-style CSS target query, e.g. \"last 2 versions, ie 11\"; blank disables the transform",
+            "css_targets",
+            self.css_targets.as_deref().unwrap_or(""),
+        );
+        push_field(
+            &mut out,
+            "Whether minify_html also minifies <style>/<script> block bodies",
+            "minify_inline_assets",
+            self.minify_inline_assets,
+        );
+        push_field(
+            &mut out,
+            "Bypasses the incremental build manifest, forcing every asset to be reprocessed",
+            "force_rebuild",
+            self.force_rebuild,
+        );
+        push_field(
+            &mut out,
+            "Output path segment category listing pages are nested under",
+            "taxonomy_path_prefix",
+            &self.taxonomy_path_prefix,
+        );
+        push_field(
+            &mut out,
+            "Output path segment a pagination page number is nested under",
+            "paginate_path",
+            &self.paginate_path,
+        );
+        push_field(
+            &mut out,
+            "Serve a pagination listing's first page at its own root instead of page/1/",
+            "pagination_index_first_page",
+            self.pagination_index_first_page,
+        );
+        push_field(&mut out, "The deployed site's base URL", "base_url", &self.base_url);
+        push_field(
+            &mut out,
+            "Whether make_permalink appends a trailing slash",
+            "trailing_slash",
+            self.trailing_slash,
+        );
+        push_field(
+            &mut out,
+            "Whether serve watches the site's content directories and rebuilds on change",
+            "watch",
+            self.watch,
+        );
+        push_field(
+            &mut out,
+            "How long to wait after a filesystem change before rebuilding",
+            "watch_debounce_ms",
+            self.watch_debounce_ms,
+        );
+        push_field(
+            &mut out,
+            "How the main post listing orders posts: date_desc, date_asc, order, or none",
+            "post_sort_order",
+            self.post_sort_order,
+        );
+        push_field(
+            &mut out,
+            "Whether posts and pages are rendered concurrently instead of one at a time",
+            "parallel_content_generation",
+            self.parallel_content_generation,
+        );
+        push_field(
+            &mut out,
+            "Words-per-minute rate used to estimate a content item's reading time",
+            "reading_time_wpm",
+            self.reading_time_wpm,
+        );
+
+        out.push_str("---\n");
+        out
+    }
+
+    /// Writes [`Self::to_config_string`] to `path`, for scaffolding a new
+    /// site's config file (or regenerating the global one) pre-filled with
+    /// every round-trippable field at its current value.
+    pub fn write_config_file(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_config_string())
+            .map_err(|e| format!("Failed to write config file {}: {e}", path.display()))
+    }
+
+    /// Normalizes a config file's content into a flat string map, regardless
+    /// of whether it's TOML or `config.md`-style front matter, so
+    /// [`Self::apply_file_values`] only has one shape to deal with. Nested
+    /// values (tables, arrays) are skipped; none of the fields
+    /// [`Self::apply_file_values`] sets are structured.
+    fn read_file_values(path: &Path, content: &str) -> Result<HashMap<String, String>, String> {
+        if path.extension().and_then(|e| e.to_str()) == Some("toml") {
+            let parsed =
+                parse_toml(content).map_err(|e| format!("Failed to parse {}: {e}", path.display()))?;
+            let JsonValue::Object(map) = parsed else {
+                return Err(format!("Expected a table at the top level of {}", path.display()));
+            };
+            Ok(map
+                .into_iter()
+                .filter_map(|(key, value)| scalar_to_string(&value).map(|s| (key, s)))
+                .collect())
+        } else {
+            parse_content_with_front_matter(content)
+                .map(|content_item| content_item.into_iter().filter(|(key, _)| key != "content").collect())
+                .map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+        }
+    }
+
+    /// Applies each recognized key present in `values` onto `self`, leaving
+    /// fields the file doesn't mention untouched. Unknown keys are ignored.
+    /// Numeric and boolean fields return a descriptive error if their value
+    /// can't be parsed, rather than silently keeping the old value.
+    fn apply_file_values(&mut self, values: &HashMap<String, String>) -> Result<(), String> {
+        if let Some(v) = values.get("output_dir") {
+            self.output_dir = v.clone();
+        }
+        if let Some(v) = values.get("sites_base_dir") {
+            self.sites_base_dir = v.clone();
+        }
+        if let Some(v) = values.get("posts_subdir") {
+            self.posts_subdir = v.clone();
+        }
+        if let Some(v) = values.get("pages_subdir") {
+            self.pages_subdir = v.clone();
+        }
+        if let Some(v) = values.get("includes_subdir") {
+            self.includes_subdir = v.clone();
+        }
+        if let Some(v) = values.get("layouts_subdir") {
+            self.layouts_subdir = v.clone();
+        }
+        if let Some(v) = values.get("assets_subdir") {
+            self.assets_subdir = v.clone();
+        }
+        if let Some(v) = values.get("data_subdir") {
+            self.data_subdir = v.clone();
+        }
+        if let Some(v) = values.get("main_layout") {
+            self.main_layout = v.clone();
+        }
+        if let Some(v) = values.get("config_file") {
+            self.config_file = v.clone();
+        }
+        if let Some(v) = values.get("default_posts_per_page") {
+            self.default_posts_per_page = v
+                .parse()
+                .map_err(|_| format!("Invalid `default_posts_per_page` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("server_host") {
+            self.server_host = v.clone();
+        }
+        if let Some(v) = values.get("server_port") {
+            self.server_port = v
+                .parse()
+                .map_err(|_| format!("Invalid `server_port` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("source_maps") {
+            self.source_maps = v
+                .parse()
+                .map_err(|_| format!("Invalid `source_maps` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("css_targets") {
+            self.css_targets = if v.trim().is_empty() { None } else { Some(v.clone()) };
+        }
+        if let Some(v) = values.get("minify_inline_assets") {
+            self.minify_inline_assets = v
+                .parse()
+                .map_err(|_| format!("Invalid `minify_inline_assets` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("force_rebuild") {
+            self.force_rebuild = v
+                .parse()
+                .map_err(|_| format!("Invalid `force_rebuild` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("taxonomy_path_prefix") {
+            self.taxonomy_path_prefix = v.clone();
+        }
+        if let Some(v) = values.get("paginate_path") {
+            self.paginate_path = v.clone();
+        }
+        if let Some(v) = values.get("pagination_index_first_page") {
+            self.pagination_index_first_page = v
+                .parse()
+                .map_err(|_| format!("Invalid `pagination_index_first_page` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("base_url") {
+            self.base_url = v.clone();
+        }
+        if let Some(v) = values.get("trailing_slash") {
+            self.trailing_slash = v
+                .parse()
+                .map_err(|_| format!("Invalid `trailing_slash` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("watch") {
+            self.watch = v.parse().map_err(|_| format!("Invalid `watch` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("watch_debounce_ms") {
+            self.watch_debounce_ms = v
+                .parse()
+                .map_err(|_| format!("Invalid `watch_debounce_ms` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("post_sort_order") {
+            self.post_sort_order = v.parse().map_err(|e| format!("Invalid `post_sort_order` in config file: {e}"))?;
+        }
+        if let Some(v) = values.get("parallel_content_generation") {
+            self.parallel_content_generation = v
+                .parse()
+                .map_err(|_| format!("Invalid `parallel_content_generation` in config file: `{v}`"))?;
+        }
+        if let Some(v) = values.get("reading_time_wpm") {
+            self.reading_time_wpm = v
+                .parse()
+                .map_err(|_| format!("Invalid `reading_time_wpm` in config file: `{v}`"))?;
+        }
+
+        Ok(())
+    }
+
     /// Load configuration from environment variables, falling back to defaults
     pub fn from_environment() -> Self {
         let mut config = Self::default();
+        config.apply_environment_overlay();
+        config
+    }
+
+    /// Applies `LEPKEFING_*` environment variable overrides onto `self`,
+    /// leaving any field without a set variable untouched. Shared by
+    /// [`Self::from_environment`] and [`Self::load`], which calls this after
+    /// applying file values so environment variables remain the
+    /// highest-precedence layer.
+    fn apply_environment_overlay(&mut self) {
+        let config = self;
 
         if let Ok(value) = env::var("LEPKEFING_OUTPUT_DIR") {
             config.output_dir = value;
@@ -107,8 +608,121 @@ impl SiteConfig {
                 config.server_port = parsed;
             }
         }
+        if let Ok(value) = env::var("LEPKEFING_SOURCE_MAPS") {
+            if let Ok(parsed) = value.parse::<bool>() {
+                config.source_maps = parsed;
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_CSS_TARGETS") {
+            if !value.trim().is_empty() {
+                config.css_targets = Some(value);
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_MINIFY_INLINE_ASSETS") {
+            if let Ok(parsed) = value.parse::<bool>() {
+                config.minify_inline_assets = parsed;
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_PRESERVED_COMMENT_MARKERS") {
+            config.preserved_comment_markers = value
+                .split(',')
+                .map(str::trim)
+                .filter(|marker| !marker.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Ok(value) = env::var("LEPKEFING_FORCE_REBUILD") {
+            if let Ok(parsed) = value.parse::<bool>() {
+                config.force_rebuild = parsed;
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_TAXONOMY_PATH_PREFIX") {
+            config.taxonomy_path_prefix = value;
+        }
+        if let Ok(value) = env::var("LEPKEFING_PAGINATE_PATH") {
+            config.paginate_path = value;
+        }
+        if let Ok(value) = env::var("LEPKEFING_PAGINATION_INDEX_FIRST_PAGE") {
+            if let Ok(parsed) = value.parse::<bool>() {
+                config.pagination_index_first_page = parsed;
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_BASE_URL") {
+            config.base_url = value;
+        }
+        if let Ok(value) = env::var("LEPKEFING_TRAILING_SLASH") {
+            if let Ok(parsed) = value.parse::<bool>() {
+                config.trailing_slash = parsed;
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_WATCH") {
+            if let Ok(parsed) = value.parse::<bool>() {
+                config.watch = parsed;
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_WATCH_DEBOUNCE_MS") {
+            if let Ok(parsed) = value.parse::<u64>() {
+                config.watch_debounce_ms = parsed;
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_POST_SORT_ORDER") {
+            if let Ok(parsed) = value.parse::<PostSortOrder>() {
+                config.post_sort_order = parsed;
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_PARALLEL_CONTENT_GENERATION") {
+            if let Ok(parsed) = value.parse::<bool>() {
+                config.parallel_content_generation = parsed;
+            }
+        }
+        if let Ok(value) = env::var("LEPKEFING_READING_TIME_WPM") {
+            if let Ok(parsed) = value.parse::<usize>() {
+                config.reading_time_wpm = parsed;
+            }
+        }
+    }
 
-        config
+    /// Composes [`Self::base_url`] and `path` into an absolute permalink,
+    /// mirroring Zola's `make_permalink`. Honors [`Self::trailing_slash`] by
+    /// omitting the trailing slash it would otherwise add to a path that
+    /// doesn't already end in one.
+    pub fn make_permalink(&self, path: &str) -> String {
+        let trailing_bit = if path.ends_with('/') || !self.trailing_slash {
+            ""
+        } else {
+            "/"
+        };
+
+        if self.base_url.ends_with('/') && path == "/" {
+            self.base_url.clone()
+        } else if path == "/" {
+            format!("{}/", self.base_url)
+        } else if self.base_url.ends_with('/') && path.starts_with('/') {
+            format!("{}{}{}", self.base_url, &path[1..], trailing_bit)
+        } else if self.base_url.ends_with('/') {
+            format!("{}{}{}", self.base_url, path, trailing_bit)
+        } else {
+            format!("{}/{}{}", self.base_url, path.trim_start_matches('/'), trailing_bit)
+        }
+    }
+
+    /// The site's content directories the dev server's watcher watches for
+    /// changes, so the watcher and the builder always agree on what counts
+    /// as a source path: every `*_subdir` field, rooted under
+    /// `sites_base_dir/site_name`.
+    pub fn watched_paths(&self, site_name: &str) -> Vec<PathBuf> {
+        let site_dir = PathBuf::from(&self.sites_base_dir).join(site_name);
+        [
+            &self.posts_subdir,
+            &self.pages_subdir,
+            &self.includes_subdir,
+            &self.layouts_subdir,
+            &self.assets_subdir,
+            &self.data_subdir,
+        ]
+        .into_iter()
+        .map(|subdir| site_dir.join(subdir))
+        .collect()
     }
 
     /// Validate the configuration values
@@ -152,11 +766,51 @@ impl SiteConfig {
         if self.server_port == 0 {
             return Err("Server port must be greater than 0".to_string());
         }
+        if self.taxonomy_path_prefix.is_empty() {
+            return Err("Taxonomy path prefix cannot be empty".to_string());
+        }
+        if self.paginate_path.is_empty() {
+            return Err("Paginate path cannot be empty".to_string());
+        }
+        if self.base_url.is_empty() {
+            return Err("Base URL cannot be empty".to_string());
+        }
+        if !self.base_url.starts_with("http://") && !self.base_url.starts_with("https://") {
+            return Err("Base URL must start with http:// or https://".to_string());
+        }
+        if self.watch_debounce_ms == 0 {
+            return Err("Watch debounce must be greater than 0".to_string());
+        }
 
         Ok(())
     }
 }
 
+/// Appends a `# {comment}` line followed by a `{key}: {value}` line to `out`,
+/// the unit [`SiteConfig::to_config_string`] repeats for every field.
+fn push_field(out: &mut String, comment: &str, key: &str, value: impl std::fmt::Display) {
+    out.push_str("# ");
+    out.push_str(comment);
+    out.push('\n');
+    out.push_str(key);
+    out.push_str(": ");
+    out.push_str(&value.to_string());
+    out.push('\n');
+}
+
+/// Converts a TOML scalar to the string representation [`SiteConfig::apply_file_values`]
+/// parses, returning `None` for values (arrays, tables, null) that don't map to any
+/// known field.
+fn scalar_to_string(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Integer(n) => Some(n.to_string()),
+        JsonValue::Float(n) => Some(n.to_string()),
+        JsonValue::Bool(b) => Some(b.to_string()),
+        JsonValue::Null | JsonValue::Array(_) | JsonValue::Object(_) => None,
+    }
+}
+
 // Keep the old constants for backward compatibility during transition
 #[allow(dead_code)]
 pub const OUTPUT_DIR: &str = "out";
@@ -254,4 +908,477 @@ mod tests {
         assert_eq!(config.output_dir, "out");
         assert_eq!(config.server_port, 2030);
     }
+
+    #[test]
+    fn test_site_config_default_source_maps_disabled() {
+        let config = SiteConfig::default();
+        assert!(!config.source_maps);
+    }
+
+    #[test]
+    fn test_site_config_default_css_targets_disabled() {
+        let config = SiteConfig::default();
+        assert_eq!(config.css_targets, None);
+    }
+
+    #[test]
+    fn test_site_config_default_minify_inline_assets_enabled() {
+        let config = SiteConfig::default();
+        assert!(config.minify_inline_assets);
+    }
+
+    #[test]
+    fn test_site_config_default_preserved_comment_markers() {
+        let config = SiteConfig::default();
+        assert_eq!(config.preserved_comment_markers, vec!["[if", "!"]);
+    }
+
+    #[test]
+    fn test_site_config_default_taxonomy_path_prefix_and_paginate_path() {
+        let config = SiteConfig::default();
+        assert_eq!(config.taxonomy_path_prefix, "category");
+        assert_eq!(config.paginate_path, "page");
+    }
+
+    #[test]
+    fn test_site_config_validation_empty_taxonomy_path_prefix() {
+        let mut config = SiteConfig::default();
+        config.taxonomy_path_prefix = String::new();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_site_config_validation_empty_paginate_path() {
+        let mut config = SiteConfig::default();
+        config.paginate_path = String::new();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_site_config_default_taxonomies_is_category_only() {
+        let config = SiteConfig::default();
+        assert_eq!(config.taxonomies.len(), 1);
+        assert_eq!(config.taxonomies[0].name, "category");
+        assert_eq!(config.taxonomies[0].front_matter_key, "category");
+        assert!(!config.taxonomies[0].multi_valued);
+        assert!(config.taxonomies[0].paginated);
+        assert_eq!(config.taxonomies[0].sort_mode, None);
+    }
+
+    #[test]
+    fn test_site_config_default_sort_mode_is_date() {
+        let config = SiteConfig::default();
+        assert_eq!(config.default_sort_mode, SortMode::Date);
+    }
+
+    #[test]
+    fn test_site_config_default_pagination_index_first_page_is_false() {
+        let config = SiteConfig::default();
+        assert!(!config.pagination_index_first_page);
+    }
+
+    #[test]
+    fn test_load_missing_file_returns_defaults() {
+        let config = SiteConfig::load(Path::new("/definitely/not/a/real/config.toml")).unwrap();
+        assert_eq!(config.output_dir, "out");
+        assert_eq!(config.server_port, 2030);
+    }
+
+    #[test]
+    fn test_load_applies_toml_values_over_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lepkefing.toml");
+        std::fs::write(&path, "output_dir = \"dist\"\nserver_port = 8080\nsource_maps = true\n").unwrap();
+
+        let config = SiteConfig::load(&path).unwrap();
+        assert_eq!(config.output_dir, "dist");
+        assert_eq!(config.server_port, 8080);
+        assert!(config.source_maps);
+        // Untouched fields keep their defaults.
+        assert_eq!(config.server_host, "localhost");
+    }
+
+    #[test]
+    fn test_load_applies_front_matter_style_config_md() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.md");
+        std::fs::write(&path, "---\noutput_dir: dist\nserver_port: 8080\n---\n").unwrap();
+
+        let config = SiteConfig::load(&path).unwrap();
+        assert_eq!(config.output_dir, "dist");
+        assert_eq!(config.server_port, 8080);
+    }
+
+    #[test]
+    fn test_load_ignores_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lepkefing.toml");
+        std::fs::write(&path, "not_a_real_field = \"whatever\"\noutput_dir = \"dist\"\n").unwrap();
+
+        let config = SiteConfig::load(&path).unwrap();
+        assert_eq!(config.output_dir, "dist");
+    }
+
+    #[test]
+    fn test_load_invalid_numeric_field_is_a_descriptive_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lepkefing.toml");
+        std::fs::write(&path, "server_port = \"not-a-number\"\n").unwrap();
+
+        let err = SiteConfig::load(&path).unwrap_err();
+        assert!(err.contains("server_port"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_site_config_default_base_url_and_trailing_slash() {
+        let config = SiteConfig::default();
+        assert_eq!(config.base_url, "https://example.com");
+        assert!(config.trailing_slash);
+    }
+
+    #[test]
+    fn test_site_config_validation_empty_base_url() {
+        let mut config = SiteConfig::default();
+        config.base_url = String::new();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_site_config_validation_base_url_without_scheme() {
+        let mut config = SiteConfig::default();
+        config.base_url = "example.com".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_make_permalink_root_path() {
+        let mut config = SiteConfig::default();
+        config.base_url = "https://example.com".to_string();
+        assert_eq!(config.make_permalink("/"), "https://example.com/");
+
+        config.base_url = "https://example.com/".to_string();
+        assert_eq!(config.make_permalink("/"), "https://example.com/");
+    }
+
+    #[test]
+    fn test_make_permalink_joins_base_url_and_path() {
+        let config = SiteConfig {
+            base_url: "https://example.com".to_string(),
+            ..SiteConfig::default()
+        };
+        assert_eq!(
+            config.make_permalink("/posts/hello"),
+            "https://example.com/posts/hello/"
+        );
+        assert_eq!(
+            config.make_permalink("posts/hello"),
+            "https://example.com/posts/hello/"
+        );
+    }
+
+    #[test]
+    fn test_make_permalink_base_url_with_trailing_slash() {
+        let config = SiteConfig {
+            base_url: "https://example.com/".to_string(),
+            ..SiteConfig::default()
+        };
+        assert_eq!(
+            config.make_permalink("/posts/hello"),
+            "https://example.com/posts/hello/"
+        );
+        assert_eq!(
+            config.make_permalink("posts/hello"),
+            "https://example.com/posts/hello/"
+        );
+    }
+
+    #[test]
+    fn test_make_permalink_honors_trailing_slash_disabled() {
+        let config = SiteConfig {
+            base_url: "https://example.com".to_string(),
+            trailing_slash: false,
+            ..SiteConfig::default()
+        };
+        assert_eq!(
+            config.make_permalink("/posts/hello"),
+            "https://example.com/posts/hello"
+        );
+    }
+
+    #[test]
+    fn test_make_permalink_path_already_ending_in_slash() {
+        let config = SiteConfig::default();
+        assert_eq!(
+            config.make_permalink("/posts/hello/"),
+            "https://example.com/posts/hello/"
+        );
+    }
+
+    #[test]
+    fn test_load_applies_base_url_and_trailing_slash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lepkefing.toml");
+        std::fs::write(&path, "base_url = \"https://example.org\"\ntrailing_slash = false\n").unwrap();
+
+        let config = SiteConfig::load(&path).unwrap();
+        assert_eq!(config.base_url, "https://example.org");
+        assert!(!config.trailing_slash);
+    }
+
+    #[test]
+    fn test_site_config_default_watch_disabled_with_300ms_debounce() {
+        let config = SiteConfig::default();
+        assert!(!config.watch);
+        assert_eq!(config.watch_debounce_ms, 300);
+    }
+
+    #[test]
+    fn test_site_config_validation_zero_watch_debounce() {
+        let mut config = SiteConfig::default();
+        config.watch_debounce_ms = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_watched_paths_covers_every_content_subdir() {
+        let config = SiteConfig::default();
+        let paths = config.watched_paths("blog");
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("./sites/blog/posts"),
+                Path::new("./sites/blog/pages"),
+                Path::new("./sites/blog/includes"),
+                Path::new("./sites/blog/layouts"),
+                Path::new("./sites/blog/assets"),
+                Path::new("./sites/blog/data"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_applies_watch_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lepkefing.toml");
+        std::fs::write(&path, "watch = true\nwatch_debounce_ms = 500\n").unwrap();
+
+        let config = SiteConfig::load(&path).unwrap();
+        assert!(config.watch);
+        assert_eq!(config.watch_debounce_ms, 500);
+    }
+
+    #[test]
+    fn test_for_site_without_a_config_file_inherits_the_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = SiteConfig {
+            sites_base_dir: dir.path().to_string_lossy().to_string(),
+            base_url: "https://global.example.com".to_string(),
+            ..SiteConfig::default()
+        };
+
+        let config = base.for_site("blog").unwrap();
+        assert_eq!(config.base_url, "https://global.example.com");
+    }
+
+    #[test]
+    fn test_for_site_overrides_only_the_fields_the_site_sets() {
+        let dir = tempfile::tempdir().unwrap();
+        let site_dir = dir.path().join("blog");
+        std::fs::create_dir_all(&site_dir).unwrap();
+        std::fs::write(
+            site_dir.join("config.md"),
+            "---\nbase_url: https://blog.example.com\ndefault_posts_per_page: 3\n---\n",
+        )
+        .unwrap();
+
+        let base = SiteConfig {
+            sites_base_dir: dir.path().to_string_lossy().to_string(),
+            base_url: "https://global.example.com".to_string(),
+            main_layout: "main.html".to_string(),
+            ..SiteConfig::default()
+        };
+
+        let config = base.for_site("blog").unwrap();
+        assert_eq!(config.base_url, "https://blog.example.com");
+        assert_eq!(config.default_posts_per_page, 3);
+        // Untouched fields are inherited from the global base.
+        assert_eq!(config.main_layout, "main.html");
+    }
+
+    #[test]
+    fn test_for_site_falls_back_to_toml_when_config_md_is_absent() {
+        let dir = tempfile::tempdir().unwrap();
+        let site_dir = dir.path().join("blog");
+        std::fs::create_dir_all(&site_dir).unwrap();
+        std::fs::write(site_dir.join("config.toml"), "base_url = \"https://blog.example.com\"\n").unwrap();
+
+        let base = SiteConfig {
+            sites_base_dir: dir.path().to_string_lossy().to_string(),
+            ..SiteConfig::default()
+        };
+
+        let config = base.for_site("blog").unwrap();
+        assert_eq!(config.base_url, "https://blog.example.com");
+    }
+
+    #[test]
+    fn test_for_site_names_the_site_in_a_malformed_config_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let site_dir = dir.path().join("blog");
+        std::fs::create_dir_all(&site_dir).unwrap();
+        std::fs::write(site_dir.join("config.md"), "---\ndefault_posts_per_page: not-a-number\n---\n").unwrap();
+
+        let base = SiteConfig {
+            sites_base_dir: dir.path().to_string_lossy().to_string(),
+            ..SiteConfig::default()
+        };
+
+        let err = base.for_site("blog").unwrap_err();
+        assert!(err.contains("blog"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_for_site_validates_the_merged_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let site_dir = dir.path().join("blog");
+        std::fs::create_dir_all(&site_dir).unwrap();
+        std::fs::write(site_dir.join("config.md"), "---\noutput_dir: \"\"\n---\n").unwrap();
+
+        let base = SiteConfig {
+            sites_base_dir: dir.path().to_string_lossy().to_string(),
+            ..SiteConfig::default()
+        };
+
+        let err = base.for_site("blog").unwrap_err();
+        assert!(err.contains("blog"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_to_config_string_is_fenced_yaml_front_matter() {
+        let config = SiteConfig::default();
+        let rendered = config.to_config_string();
+        assert!(rendered.starts_with("---\n"));
+        assert!(rendered.trim_end().ends_with("---"));
+        assert!(rendered.contains("output_dir: out"));
+        assert!(rendered.contains("base_url: https://example.com"));
+    }
+
+    #[test]
+    fn test_to_config_string_round_trips_through_load() {
+        let config = SiteConfig {
+            output_dir: "dist".to_string(),
+            server_port: 9090,
+            base_url: "https://example.org".to_string(),
+            trailing_slash: false,
+            watch: true,
+            watch_debounce_ms: 500,
+            css_targets: Some("last 2 versions".to_string()),
+            ..SiteConfig::default()
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.md");
+        std::fs::write(&path, config.to_config_string()).unwrap();
+
+        let loaded = SiteConfig::load(&path).unwrap();
+        assert_eq!(loaded.output_dir, config.output_dir);
+        assert_eq!(loaded.server_port, config.server_port);
+        assert_eq!(loaded.base_url, config.base_url);
+        assert_eq!(loaded.trailing_slash, config.trailing_slash);
+        assert_eq!(loaded.watch, config.watch);
+        assert_eq!(loaded.watch_debounce_ms, config.watch_debounce_ms);
+        assert_eq!(loaded.css_targets, config.css_targets);
+    }
+
+    #[test]
+    fn test_to_config_string_round_trips_a_blank_css_targets() {
+        let config = SiteConfig::default();
+        assert_eq!(config.css_targets, None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.md");
+        std::fs::write(&path, config.to_config_string()).unwrap();
+
+        let loaded = SiteConfig::load(&path).unwrap();
+        assert_eq!(loaded.css_targets, None);
+    }
+
+    #[test]
+    fn test_write_config_file_writes_to_config_string() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.md");
+        let config = SiteConfig::default();
+
+        config.write_config_file(&path).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), config.to_config_string());
+    }
+
+    #[test]
+    fn test_site_config_default_post_sort_order_is_date_desc() {
+        let config = SiteConfig::default();
+        assert_eq!(config.post_sort_order, PostSortOrder::DateDesc);
+    }
+
+    #[test]
+    fn test_post_sort_order_from_str_accepts_every_variant() {
+        assert_eq!("date_desc".parse(), Ok(PostSortOrder::DateDesc));
+        assert_eq!("date_asc".parse(), Ok(PostSortOrder::DateAsc));
+        assert_eq!("order".parse(), Ok(PostSortOrder::Order));
+        assert_eq!("none".parse(), Ok(PostSortOrder::None));
+    }
+
+    #[test]
+    fn test_post_sort_order_from_str_rejects_unknown_value_listing_accepted_ones() {
+        let err = "whenever".parse::<PostSortOrder>().unwrap_err();
+        assert!(err.contains("date_desc"));
+        assert!(err.contains("date_asc"));
+        assert!(err.contains("order"));
+        assert!(err.contains("none"));
+    }
+
+    #[test]
+    fn test_load_applies_post_sort_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lepkefing.toml");
+        std::fs::write(&path, "post_sort_order = \"order\"\n").unwrap();
+
+        let config = SiteConfig::load(&path).unwrap();
+        assert_eq!(config.post_sort_order, PostSortOrder::Order);
+    }
+
+    #[test]
+    fn test_load_invalid_post_sort_order_is_a_descriptive_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lepkefing.toml");
+        std::fs::write(&path, "post_sort_order = \"whenever\"\n").unwrap();
+
+        let err = SiteConfig::load(&path).unwrap_err();
+        assert!(err.contains("post_sort_order"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_to_config_string_round_trips_post_sort_order() {
+        let config = SiteConfig {
+            post_sort_order: PostSortOrder::Order,
+            ..SiteConfig::default()
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.md");
+        std::fs::write(&path, config.to_config_string()).unwrap();
+
+        let loaded = SiteConfig::load(&path).unwrap();
+        assert_eq!(loaded.post_sort_order, PostSortOrder::Order);
+    }
+
+    #[test]
+    fn test_load_validates_after_applying_file_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lepkefing.toml");
+        std::fs::write(&path, "output_dir = \"\"\n").unwrap();
+
+        let err = SiteConfig::load(&path).unwrap_err();
+        assert_eq!(err, "Output directory cannot be empty");
+    }
 }
@@ -1,12 +1,15 @@
+use crate::converters::org::org_to_html;
 use crate::error::Result;
 use crate::template_processors::liquid::process_liquid_tags;
-use crate::template_processors::markdown::markdown_to_html;
+use crate::template_processors::markdown::{
+    build_table_of_contents, flatten_table_of_contents, markdown_to_html_with_headings,
+};
 use crate::template_processors::process_template_tags;
 use crate::types::{ContentItem, TemplateIncludes, Variables};
 
 /// Centralized content processing function that handles the complete pipeline:
 /// 1. Processes liquid includes
-/// 2. Converts markdown to HTML (if needed)
+/// 2. Converts markdown or Org content to HTML (if needed)
 /// 3. Processes template variables
 ///
 /// This ensures consistent processing across all content generation functions.
@@ -26,12 +29,31 @@ pub fn process_content(
     // Step 1: Process liquid includes first
     let content_with_includes = process_liquid_tags(content, &keys, includes)?;
 
-    // Step 2: Convert markdown to HTML if needed
-    let is_markdown = content_item.get("file_type").map_or(true, |ft| ft == "md");
-    let html_content = if is_markdown {
-        markdown_to_html(&content_with_includes)
-    } else {
-        content_with_includes
+    // Step 2: Convert markdown or Org content to HTML if needed, injecting a
+    // table of contents when the content item or content requests one
+    // (markdown only). The same request also flattens the heading tree into
+    // `toc.N.*` variables, so a template can walk it with `{% for %}`
+    // instead of relying only on the pre-rendered HTML.
+    let html_content = match content_item.get("file_type").map(String::as_str) {
+        Some("org") => org_to_html(&content_with_includes),
+        None | Some("md") => {
+            let (html, headings) = markdown_to_html_with_headings(&content_with_includes);
+            let has_toc_marker = html.contains("{% toc %}");
+            let wants_toc =
+                content_item.get("toc").is_some_and(|toc| toc == "true") || has_toc_marker;
+            if wants_toc {
+                flatten_table_of_contents(&headings, &mut combined_variables);
+                let toc = build_table_of_contents(&headings);
+                if has_toc_marker {
+                    html.replace("{% toc %}", &toc)
+                } else {
+                    format!("{toc}{html}")
+                }
+            } else {
+                html
+            }
+        }
+        _ => content_with_includes,
     };
 
     // Step 3: Process template variables
@@ -71,8 +93,26 @@ mod tests {
 
         let content = "# Test Heading\n\nThis is a paragraph.";
         let result = process_content(content, &content_item, &includes, &variables).unwrap();
-        // The markdown processor strips line breaks between non-list lines
-        assert_eq!(result, "# Test HeadingThis is a paragraph.");
+        // Headings gain an anchor id, and line breaks between non-list
+        // lines are still stripped.
+        assert_eq!(
+            result,
+            "<h1 id=\"test-heading\">Test Heading</h1>This is a paragraph."
+        );
+    }
+
+    #[test]
+    fn test_process_content_with_toc_exposes_flattened_variables_to_for_loop() {
+        let includes = HashMap::new();
+        let mut content_item = HashMap::new();
+        content_item.insert("file_type".to_string(), "md".to_string());
+        content_item.insert("toc".to_string(), "true".to_string());
+        let variables = HashMap::new();
+
+        let content =
+            "# One\n\n# Two\n\n{% for item in toc %}[{{ item.level }}:{{ item.title }}]{% endfor %}";
+        let result = process_content(content, &content_item, &includes, &variables).unwrap();
+        assert!(result.ends_with("[1:One][1:Two]"));
     }
 
     #[test]
@@ -87,6 +127,18 @@ mod tests {
         assert_eq!(result, "<p>Already HTML</p>");
     }
 
+    #[test]
+    fn test_process_content_org_only() {
+        let includes = HashMap::new();
+        let mut content_item = HashMap::new();
+        content_item.insert("file_type".to_string(), "org".to_string());
+        let variables = HashMap::new();
+
+        let content = "* Test Heading\n\n- an item";
+        let result = process_content(content, &content_item, &includes, &variables).unwrap();
+        assert_eq!(result, "<h1>Test Heading</h1><ul><li>an item</li></ul>");
+    }
+
     #[test]
     fn test_process_content_with_template_variables() {
         let includes = HashMap::new();
@@ -98,7 +150,7 @@ mod tests {
 
         let content = "# {{title}}\n\nContent here.";
         let result = process_content(content, &content_item, &includes, &variables).unwrap();
-        // The markdown processor strips line breaks between non-list lines
-        assert_eq!(result, "# Test TitleContent here.");
+        // The heading id is derived before variable substitution runs
+        assert_eq!(result, "<h1 id=\"title\">Test Title</h1>Content here.");
     }
 }
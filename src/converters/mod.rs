@@ -1,6 +1,8 @@
+pub mod org;
 pub mod scss;
 pub mod typescript;
 
 // Re-export the trait implementations
+pub use org::OrgConverter;
 pub use scss::ScssConverter;
 pub use typescript::TypeScriptConverter;
@@ -0,0 +1,190 @@
+//! Parses Org source into a flat stream of block-level [`Node`]s. Keyword
+//! lines (`#+TITLE:`, `#+DATE:`, ...) are kept distinct from renderable
+//! content here so [`super::render_with_handler`] can split them off into
+//! metadata instead of feeding them to a handler.
+
+/// A single block-level construct (or keyword line) parsed from an Org
+/// document, in document order. Consecutive `ListItem`s of the same
+/// `ordered`-ness are grouped into one `<ul>`/`<ol>` by the renderer; a
+/// [`Blank`](Node::Blank) between them starts a new list instead.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Heading { level: usize, text: String },
+    Paragraph(String),
+    ListItem { ordered: bool, text: String },
+    CodeBlock { lang: String, code: String },
+    /// A `#+KEY: value` line, e.g. `#+TITLE:` or `#+DATE:`. `key` is
+    /// lowercased so callers can match on it without worrying about case.
+    Keyword { key: String, value: String },
+    /// A blank source line, kept only so the renderer can tell two list
+    /// blocks separated by a blank line apart from one continuous list.
+    Blank,
+}
+
+/// Returns `(heading_level, heading_text)` if `line` is an Org heading (1 or
+/// more leading `*` characters followed by a space).
+fn parse_org_heading(line: &str) -> Option<(usize, &str)> {
+    let stars = line.chars().take_while(|&c| c == '*').count();
+    if stars == 0 {
+        return None;
+    }
+
+    let rest = &line[stars..];
+    let mut chars = rest.chars();
+    if chars.next() != Some(' ') {
+        return None;
+    }
+
+    Some((stars, chars.as_str().trim()))
+}
+
+/// Returns `(ordered, item_text)` if `line` starts an Org list item: `- `
+/// and `+ ` for unordered items, `N. ` for ordered ones.
+fn parse_list_item(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim_start();
+
+    if let Some(rest) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("+ ")) {
+        return Some((false, rest));
+    }
+
+    let digits: &str = trimmed.trim_start_matches(|c: char| c.is_ascii_digit());
+    if digits.len() != trimmed.len() {
+        if let Some(rest) = digits.strip_prefix(". ") {
+            return Some((true, rest));
+        }
+    }
+
+    None
+}
+
+/// Returns the language (possibly empty) if `line` opens a `#+BEGIN_SRC` block.
+fn parse_src_block_open(line: &str) -> Option<&str> {
+    line.trim().strip_prefix("#+BEGIN_SRC").map(str::trim)
+}
+
+/// Returns true if `line` closes an open `#+BEGIN_SRC` block.
+fn is_src_block_close(line: &str) -> bool {
+    line.trim() == "#+END_SRC"
+}
+
+/// Returns `(key, value)` if `line` is a `#+KEY: value` keyword line.
+fn parse_keyword(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim().strip_prefix("#+")?;
+    let colon = rest.find(':')?;
+    let key = &rest[..colon];
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    Some((key, rest[colon + 1..].trim()))
+}
+
+/// Scans `input` line by line into a flat [`Node`] stream.
+pub fn parse(input: &str) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut src_lang: Option<&str> = None;
+    let mut src_lines: Vec<&str> = Vec::new();
+
+    for line in input.lines() {
+        if let Some(lang) = src_lang {
+            if is_src_block_close(line) {
+                nodes.push(Node::CodeBlock {
+                    lang: lang.to_string(),
+                    code: src_lines.join("\n"),
+                });
+                src_lang = None;
+                src_lines.clear();
+            } else {
+                src_lines.push(line);
+            }
+            continue;
+        }
+
+        if let Some(lang) = parse_src_block_open(line) {
+            src_lang = Some(lang);
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            nodes.push(Node::Blank);
+            continue;
+        }
+
+        if let Some((key, value)) = parse_keyword(line) {
+            nodes.push(Node::Keyword {
+                key: key.to_ascii_lowercase(),
+                value: value.to_string(),
+            });
+        } else if let Some((level, text)) = parse_org_heading(line) {
+            nodes.push(Node::Heading { level, text: text.to_string() });
+        } else if let Some((ordered, text)) = parse_list_item(line) {
+            nodes.push(Node::ListItem { ordered, text: text.to_string() });
+        } else {
+            nodes.push(Node::Paragraph(line.to_string()));
+        }
+    }
+
+    // An unterminated block still renders the code collected so far.
+    if let Some(lang) = src_lang {
+        nodes.push(Node::CodeBlock {
+            lang: lang.to_string(),
+            code: src_lines.join("\n"),
+        });
+    }
+
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_heading() {
+        assert_eq!(
+            parse("* Title"),
+            vec![Node::Heading { level: 1, text: "Title".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_parse_unordered_and_ordered_list_items() {
+        assert_eq!(
+            parse("- one\n1. two"),
+            vec![
+                Node::ListItem { ordered: false, text: "one".to_string() },
+                Node::ListItem { ordered: true, text: "two".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_keyword_lines() {
+        assert_eq!(
+            parse("#+TITLE: My Post\n#+DATE: 2024-01-01"),
+            vec![
+                Node::Keyword { key: "title".to_string(), value: "My Post".to_string() },
+                Node::Keyword { key: "date".to_string(), value: "2024-01-01".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_src_block() {
+        assert_eq!(
+            parse("#+BEGIN_SRC rust\nfn main() {}\n#+END_SRC"),
+            vec![Node::CodeBlock { lang: "rust".to_string(), code: "fn main() {}".to_string() }]
+        );
+    }
+
+    #[test]
+    fn test_parse_blank_line_between_content() {
+        assert_eq!(
+            parse("* A\n\n* B"),
+            vec![
+                Node::Heading { level: 1, text: "A".to_string() },
+                Node::Blank,
+                Node::Heading { level: 1, text: "B".to_string() },
+            ]
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! The HTML-rendering side of the Org pipeline. [`Element`] names each
+//! block-level construct [`super::block::parse`] produces; [`OrgHtmlHandler`]
+//! is the `start_element`/`end_element` callback pair that turns one into
+//! markup. [`DefaultHtmlHandler`] is the renderer's built-in implementation -
+//! swapping in a different handler (e.g. to syntax-highlight code blocks)
+//! doesn't require touching the parser.
+//!
+//! Modeled on orgize's `Render`/`HtmlHandler` split.
+
+/// A block-level construct a handler is asked to open and close. Inline
+/// text (heading/paragraph/list-item content) is applied by the renderer
+/// between the `start_element`/`end_element` calls, not by the handler.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Element {
+    Heading(usize),
+    Paragraph,
+    List { ordered: bool },
+    ListItem,
+    /// A complete `#+BEGIN_SRC`/`#+END_SRC` block. Unlike the other variants
+    /// there's no text emitted between `start_element` and `end_element`;
+    /// handlers render the whole thing, `code` included, in `start_element`.
+    CodeBlock { lang: String, code: String },
+}
+
+/// Turns [`Element`]s into HTML. `start_element`/`end_element` each append
+/// to `out` rather than returning a string, so a handler can skip emitting
+/// anything for elements it doesn't care about (see [`DefaultHtmlHandler`]'s
+/// `Paragraph` arms, which emit no wrapper tag at all).
+pub trait OrgHtmlHandler {
+    fn start_element(&mut self, element: &Element, out: &mut String);
+    fn end_element(&mut self, element: &Element, out: &mut String);
+}
+
+/// Renders the same HTML the Org converter has always produced: headings as
+/// `<hN>`, `-`/`+`/`N.` lists as `<ul>`/`<ol>` of `<li>`, code blocks as
+/// `<pre><code class="language-...">`, and paragraphs with no wrapper tag.
+pub struct DefaultHtmlHandler;
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn render_src_block(lang: &str, code: &str) -> String {
+    let class_attr = if lang.is_empty() {
+        String::new()
+    } else {
+        format!(" class=\"language-{lang}\"")
+    };
+    let escaped: Vec<String> = code.lines().map(escape_html).collect();
+    format!("<pre><code{class_attr}>{}</code></pre>", escaped.join("\n"))
+}
+
+impl OrgHtmlHandler for DefaultHtmlHandler {
+    fn start_element(&mut self, element: &Element, out: &mut String) {
+        match element {
+            Element::Heading(level) => out.push_str(&format!("<h{level}>")),
+            Element::Paragraph => {}
+            Element::List { ordered } => out.push_str(if *ordered { "<ol>" } else { "<ul>" }),
+            Element::ListItem => out.push_str("<li>"),
+            Element::CodeBlock { lang, code } => out.push_str(&render_src_block(lang, code)),
+        }
+    }
+
+    fn end_element(&mut self, element: &Element, out: &mut String) {
+        match element {
+            Element::Heading(level) => out.push_str(&format!("</h{level}>")),
+            Element::Paragraph => {}
+            Element::List { ordered } => out.push_str(if *ordered { "</ol>" } else { "</ul>" }),
+            Element::ListItem => out.push_str("</li>"),
+            Element::CodeBlock { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_handler_renders_heading() {
+        let mut handler = DefaultHtmlHandler;
+        let mut out = String::new();
+        let element = Element::Heading(2);
+        handler.start_element(&element, &mut out);
+        out.push_str("Title");
+        handler.end_element(&element, &mut out);
+        assert_eq!(out, "<h2>Title</h2>");
+    }
+
+    #[test]
+    fn test_default_handler_renders_ordered_and_unordered_lists() {
+        let mut handler = DefaultHtmlHandler;
+
+        let mut out = String::new();
+        handler.start_element(&Element::List { ordered: false }, &mut out);
+        handler.end_element(&Element::List { ordered: false }, &mut out);
+        assert_eq!(out, "<ul></ul>");
+
+        let mut out = String::new();
+        handler.start_element(&Element::List { ordered: true }, &mut out);
+        handler.end_element(&Element::List { ordered: true }, &mut out);
+        assert_eq!(out, "<ol></ol>");
+    }
+
+    #[test]
+    fn test_default_handler_renders_code_block_with_escaping() {
+        let mut handler = DefaultHtmlHandler;
+        let mut out = String::new();
+        let element = Element::CodeBlock {
+            lang: "rust".to_string(),
+            code: "a < b && b > c".to_string(),
+        };
+        handler.start_element(&element, &mut out);
+        handler.end_element(&element, &mut out);
+        assert_eq!(
+            out,
+            "<pre><code class=\"language-rust\">a &lt; b &amp;&amp; b &gt; c</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_default_handler_paragraph_has_no_wrapper() {
+        let mut handler = DefaultHtmlHandler;
+        let mut out = String::new();
+        let element = Element::Paragraph;
+        handler.start_element(&element, &mut out);
+        out.push_str("plain text");
+        handler.end_element(&element, &mut out);
+        assert_eq!(out, "plain text");
+    }
+}
@@ -0,0 +1,133 @@
+//! Inline Org markup: `*bold*`, `/italic/`, `=code=`, and `[[url][description]]`
+//! / `[[url]]` links, each mapped to their HTML equivalent. Delimiters are
+//! matched non-greedily on the same line; an opener with no matching closer
+//! is left in the output as a literal character.
+
+/// Applies inline Org formatting to a line (or heading/list item text),
+/// recursing into matched spans so markers can nest, e.g. `*/both/*`.
+pub fn apply_inline_formatting(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < text.len() {
+        if text[i..].starts_with("[[") {
+            if let Some((html, end)) = try_parse_link(text, i) {
+                out.push_str(&html);
+                i = end;
+                continue;
+            }
+        }
+
+        let byte = text.as_bytes()[i];
+        let wrapped = match byte {
+            b'*' => find_closing(text, i, b'*').map(|(inner, end)| {
+                (format!("<strong>{}</strong>", apply_inline_formatting(inner)), end)
+            }),
+            b'/' => find_closing(text, i, b'/').map(|(inner, end)| {
+                (format!("<em>{}</em>", apply_inline_formatting(inner)), end)
+            }),
+            b'=' => {
+                find_closing(text, i, b'=').map(|(inner, end)| (format!("<code>{inner}</code>"), end))
+            }
+            _ => None,
+        };
+
+        if let Some((html, end)) = wrapped {
+            out.push_str(&html);
+            i = end;
+            continue;
+        }
+
+        let ch = text[i..].chars().next().expect("i is a char boundary");
+        out.push(ch);
+        i += ch.len_utf8();
+    }
+
+    out
+}
+
+/// Finds the next occurrence of `delim` after `open_idx`, returning the text
+/// between the two delimiters and the byte offset just past the closer.
+/// Returns `None` for an empty span (`**`) or an unmatched opener.
+fn find_closing(text: &str, open_idx: usize, delim: u8) -> Option<(&str, usize)> {
+    let bytes = text.as_bytes();
+    let content_start = open_idx + 1;
+    let close = (content_start..bytes.len()).find(|&j| bytes[j] == delim)?;
+    if close == content_start {
+        return None;
+    }
+    Some((&text[content_start..close], close + 1))
+}
+
+/// Parses a `[[url][description]]` or `[[url]]` link starting at `open_idx`
+/// (which must point at the first `[` of a `[[` pair).
+fn try_parse_link(text: &str, open_idx: usize) -> Option<(String, usize)> {
+    let rest = &text[open_idx + 2..];
+    let close = rest.find("]]")?;
+    let inner = &rest[..close];
+    let end = open_idx + 2 + close + 2;
+
+    let html = if let Some(sep) = inner.find("][") {
+        let url = &inner[..sep];
+        let description = &inner[sep + 2..];
+        format!("<a href=\"{url}\">{description}</a>")
+    } else {
+        format!("<a href=\"{inner}\">{inner}</a>")
+    };
+
+    Some((html, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_inline_formatting_bold() {
+        assert_eq!(apply_inline_formatting("*bold*"), "<strong>bold</strong>");
+    }
+
+    #[test]
+    fn test_apply_inline_formatting_italic() {
+        assert_eq!(apply_inline_formatting("/italic/"), "<em>italic</em>");
+    }
+
+    #[test]
+    fn test_apply_inline_formatting_code() {
+        assert_eq!(apply_inline_formatting("=code="), "<code>code</code>");
+    }
+
+    #[test]
+    fn test_apply_inline_formatting_link_with_description() {
+        assert_eq!(
+            apply_inline_formatting("[[https://example.com][Example]]"),
+            "<a href=\"https://example.com\">Example</a>"
+        );
+    }
+
+    #[test]
+    fn test_apply_inline_formatting_link_without_description() {
+        assert_eq!(
+            apply_inline_formatting("[[https://example.com]]"),
+            "<a href=\"https://example.com\">https://example.com</a>"
+        );
+    }
+
+    #[test]
+    fn test_apply_inline_formatting_mixed_and_surrounding_text() {
+        assert_eq!(
+            apply_inline_formatting("See *bold* and /italic/ and =code=."),
+            "See <strong>bold</strong> and <em>italic</em> and <code>code</code>."
+        );
+    }
+
+    #[test]
+    fn test_apply_inline_formatting_unmatched_delimiter_is_literal() {
+        assert_eq!(apply_inline_formatting("price: $5 * 2"), "price: $5 * 2");
+    }
+
+    #[test]
+    fn test_apply_inline_formatting_empty_span_is_literal() {
+        assert_eq!(apply_inline_formatting("**"), "**");
+    }
+}
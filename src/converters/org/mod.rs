@@ -0,0 +1,265 @@
+//! Org-mode to HTML conversion.
+//!
+//! A focused subset of Org syntax is supported: `*` through `******` lines
+//! become `<h1>`-`<h6>` headings, `#+BEGIN_SRC lang` / `#+END_SRC` blocks
+//! become `<pre><code class="language-lang">`, `- ` / `+ ` / `N. ` lines
+//! become `<ul>`/`<ol>` items, `#+TITLE:`/`#+DATE:`-style keyword lines are
+//! pulled out as front-matter-like metadata instead of being rendered, and
+//! inline markup (see [`inline`]) is applied to every heading, list item,
+//! and plain line.
+//!
+//! [`block::parse`] scans the source into a flat stream of block-level
+//! [`block::Node`]s, and [`render_with_handler`] drives that stream through
+//! an [`handler::OrgHtmlHandler`] to produce HTML - the same split orgize
+//! uses between its parser and its `HtmlHandler`, so a caller that needs
+//! different markup (e.g. syntax-highlighted code blocks) can supply its
+//! own handler without forking the parser.
+
+mod block;
+mod handler;
+mod inline;
+
+use crate::error::Result;
+use crate::traits::AssetConverter;
+use block::Node;
+use handler::{DefaultHtmlHandler, Element, OrgHtmlHandler};
+use inline::apply_inline_formatting;
+use std::collections::HashMap;
+use std::path::Path;
+
+fn close_open_list(open_list: &mut Option<bool>, handler: &mut dyn OrgHtmlHandler, out: &mut String) {
+    if let Some(ordered) = open_list.take() {
+        handler.end_element(&Element::List { ordered }, out);
+    }
+}
+
+/// Parses `input` and drives the resulting nodes through `handler`,
+/// returning the rendered HTML together with any `#+KEY: value` keyword
+/// lines found (key lowercased), e.g. `#+TITLE:`/`#+DATE:`.
+pub fn render_with_handler(input: &str, handler: &mut dyn OrgHtmlHandler) -> (String, HashMap<String, String>) {
+    let mut out = String::with_capacity(input.len());
+    let mut metadata = HashMap::new();
+    let mut open_list: Option<bool> = None;
+
+    for node in block::parse(input) {
+        match node {
+            Node::Keyword { key, value } => {
+                close_open_list(&mut open_list, handler, &mut out);
+                metadata.insert(key, value);
+            }
+            Node::Blank => close_open_list(&mut open_list, handler, &mut out),
+            Node::Heading { level, text } => {
+                close_open_list(&mut open_list, handler, &mut out);
+                let element = Element::Heading(level);
+                handler.start_element(&element, &mut out);
+                out.push_str(&apply_inline_formatting(&text));
+                handler.end_element(&element, &mut out);
+            }
+            Node::ListItem { ordered, text } => {
+                if open_list != Some(ordered) {
+                    close_open_list(&mut open_list, handler, &mut out);
+                    handler.start_element(&Element::List { ordered }, &mut out);
+                    open_list = Some(ordered);
+                }
+                handler.start_element(&Element::ListItem, &mut out);
+                out.push_str(&apply_inline_formatting(&text));
+                handler.end_element(&Element::ListItem, &mut out);
+            }
+            Node::CodeBlock { lang, code } => {
+                close_open_list(&mut open_list, handler, &mut out);
+                let element = Element::CodeBlock { lang, code };
+                handler.start_element(&element, &mut out);
+                handler.end_element(&element, &mut out);
+            }
+            Node::Paragraph(text) => {
+                close_open_list(&mut open_list, handler, &mut out);
+                let element = Element::Paragraph;
+                handler.start_element(&element, &mut out);
+                out.push_str(&apply_inline_formatting(&text));
+                handler.end_element(&element, &mut out);
+            }
+        }
+    }
+
+    close_open_list(&mut open_list, handler, &mut out);
+
+    (out, metadata)
+}
+
+/// Converts Org markup to HTML and also returns any `#+TITLE:`/`#+DATE:`
+/// keyword lines as metadata, mirroring how
+/// [`markdown_to_html_with_headings`](crate::template_processors::markdown::markdown_to_html_with_headings)
+/// exposes extra information alongside the markdown converter's plain HTML.
+pub fn org_to_html_with_metadata(input: &str) -> (String, HashMap<String, String>) {
+    render_with_handler(input, &mut DefaultHtmlHandler)
+}
+
+/// Converts a focused subset of Org markup to HTML: headings, `#+BEGIN_SRC`
+/// blocks, `-`/`+`/`N.` list items, and inline `*bold*`/`/italic/`/`=code=`/
+/// link markup. Blank lines are dropped and `#+KEY:` keyword lines are
+/// omitted, mirroring how the markdown converter strips blank lines between
+/// non-list lines.
+pub fn org_to_html(input: &str) -> String {
+    org_to_html_with_metadata(input).0
+}
+
+/// Org-mode to HTML converter implementation
+pub struct OrgConverter;
+
+impl OrgConverter {
+    /// Create a new Org converter
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for OrgConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AssetConverter for OrgConverter {
+    fn convert(&self, input: &str, _source_path: Option<&Path>) -> Result<String> {
+        Ok(org_to_html(input))
+    }
+
+    fn supported_extensions(&self) -> Vec<&str> {
+        vec!["org"]
+    }
+
+    fn output_extension(&self) -> &str {
+        "html"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_org_to_html_converts_heading() {
+        assert_eq!(org_to_html("* Test Heading"), "<h1>Test Heading</h1>");
+    }
+
+    #[test]
+    fn test_org_to_html_converts_nested_heading_level() {
+        assert_eq!(org_to_html("** Sub Heading"), "<h2>Sub Heading</h2>");
+    }
+
+    #[test]
+    fn test_org_to_html_strips_blank_lines_between_plain_lines() {
+        let result = org_to_html("* Test Heading\n\nThis is a paragraph.");
+        assert_eq!(result, "<h1>Test Heading</h1>This is a paragraph.");
+    }
+
+    #[test]
+    fn test_org_to_html_ignores_star_without_following_space() {
+        assert_eq!(org_to_html("*nospace"), "*nospace");
+    }
+
+    #[test]
+    fn test_org_to_html_renders_list_items() {
+        let result = org_to_html("- one\n- two\n+ three");
+        assert_eq!(result, "<ul><li>one</li><li>two</li><li>three</li></ul>");
+    }
+
+    #[test]
+    fn test_org_to_html_renders_ordered_list_items() {
+        let result = org_to_html("1. one\n2. two");
+        assert_eq!(result, "<ol><li>one</li><li>two</li></ol>");
+    }
+
+    #[test]
+    fn test_org_to_html_switches_list_kind_without_blank_line() {
+        let result = org_to_html("- one\n1. two");
+        assert_eq!(result, "<ul><li>one</li></ul><ol><li>two</li></ol>");
+    }
+
+    #[test]
+    fn test_org_to_html_closes_list_before_heading() {
+        let result = org_to_html("- one\n* Heading");
+        assert_eq!(result, "<ul><li>one</li></ul><h1>Heading</h1>");
+    }
+
+    #[test]
+    fn test_org_to_html_renders_src_block() {
+        let result = org_to_html("#+BEGIN_SRC rust\nfn main() {}\n#+END_SRC");
+        assert_eq!(
+            result,
+            "<pre><code class=\"language-rust\">fn main() {}</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_org_to_html_src_block_preserves_line_breaks_and_escapes() {
+        let result = org_to_html("#+BEGIN_SRC html\n<p>hi & bye</p>\n\nstill code\n#+END_SRC");
+        assert_eq!(
+            result,
+            "<pre><code class=\"language-html\">&lt;p&gt;hi &amp; bye&lt;/p&gt;\n\n\
+             still code</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_org_to_html_src_block_without_language() {
+        let result = org_to_html("#+BEGIN_SRC\nraw text\n#+END_SRC");
+        assert_eq!(result, "<pre><code>raw text</code></pre>");
+    }
+
+    #[test]
+    fn test_org_to_html_applies_inline_formatting_in_headings_and_items() {
+        let result = org_to_html("* A *bold* title\n- an /italic/ item");
+        assert_eq!(
+            result,
+            "<h1>A <strong>bold</strong> title</h1><ul><li>an <em>italic</em> item</li></ul>"
+        );
+    }
+
+    #[test]
+    fn test_org_to_html_renders_link() {
+        let result = org_to_html("See [[https://example.com][Example]] for more.");
+        assert_eq!(
+            result,
+            "See <a href=\"https://example.com\">Example</a> for more."
+        );
+    }
+
+    #[test]
+    fn test_org_to_html_omits_keyword_lines() {
+        let result = org_to_html("#+TITLE: My Post\n#+DATE: 2024-01-01\n* Heading");
+        assert_eq!(result, "<h1>Heading</h1>");
+    }
+
+    #[test]
+    fn test_org_to_html_with_metadata_extracts_title_and_date() {
+        let (html, metadata) = org_to_html_with_metadata("#+TITLE: My Post\n#+DATE: 2024-01-01\n* Heading");
+        assert_eq!(html, "<h1>Heading</h1>");
+        assert_eq!(metadata.get("title"), Some(&"My Post".to_string()));
+        assert_eq!(metadata.get("date"), Some(&"2024-01-01".to_string()));
+    }
+
+    #[test]
+    fn test_org_to_html_with_metadata_returns_empty_map_without_keywords() {
+        let (html, metadata) = org_to_html_with_metadata("* Heading");
+        assert_eq!(html, "<h1>Heading</h1>");
+        assert!(metadata.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod trait_tests {
+    use super::*;
+    use crate::traits::AssetConverter;
+
+    #[test]
+    fn test_org_converter_trait() {
+        let converter = OrgConverter::new();
+        assert_eq!(converter.supported_extensions(), vec!["org"]);
+        assert_eq!(converter.output_extension(), "html");
+
+        let input = "* Title\n\n- item";
+        let result = converter.convert(input, None).expect("Conversion failed");
+        assert_eq!(result, "<h1>Title</h1><ul><li>item</li></ul>");
+    }
+}
@@ -0,0 +1,319 @@
+use super::prefixes::{add_vendor_prefixes, PrefixConfig};
+
+/// Configuration for [`minify_css`].
+#[derive(Debug, Clone, Copy)]
+pub struct MinifyConfig {
+    /// Whether to strip `/* ... */` comments. Banner comments starting with
+    /// `/*!` (the convention for license/attribution text) are always kept
+    /// regardless of this toggle.
+    pub strip_comments: bool,
+}
+
+impl Default for MinifyConfig {
+    fn default() -> Self {
+        Self {
+            strip_comments: true,
+        }
+    }
+}
+
+/// Minifies CSS the way a bundler's production codegen step would: strips
+/// comments, collapses runs of whitespace to a single space, removes
+/// whitespace around `{`, `}`, `:`, `;`, `,`, drops a rule's trailing `;`
+/// before its closing `}`, and removes rules left with an empty body.
+///
+/// String literals and `url(...)` contents are copied through untouched, so
+/// this never corrupts a value like `content: "  "` or `url(a b.png)`.
+pub fn minify_css(css: &str, config: &MinifyConfig) -> String {
+    let without_comments = if config.strip_comments {
+        strip_comments(css)
+    } else {
+        css.to_string()
+    };
+    let collapsed = collapse_whitespace(&without_comments);
+    collapse_empty_rules(&collapsed)
+}
+
+/// Adds vendor prefixes and then minifies, so the build pipeline can emit one
+/// compact, cross-browser stylesheet in a single call.
+pub fn prefix_and_minify(
+    css: &str,
+    prefix_config: &PrefixConfig,
+    minify_config: &MinifyConfig,
+) -> String {
+    let prefixed = add_vendor_prefixes(css, prefix_config);
+    minify_css(&prefixed, minify_config)
+}
+
+/// Removes `/* ... */` comments, leaving `/*! ... */` banner comments (the
+/// convention for preserved license/attribution text) untouched. Comment-like
+/// text inside a quoted string is left alone.
+fn strip_comments(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut chars = css.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(ch) = chars.next() {
+        if let Some(q) = quote {
+            result.push(ch);
+            if ch == '\\' {
+                if let Some(escaped) = chars.next() {
+                    result.push(escaped);
+                }
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        if ch == '"' || ch == '\'' {
+            quote = Some(ch);
+            result.push(ch);
+            continue;
+        }
+
+        if ch == '/' && chars.peek() == Some(&'*') {
+            chars.next(); // consume the '*'
+            let is_banner = chars.peek() == Some(&'!');
+            if is_banner {
+                result.push_str("/*");
+            }
+
+            let mut prev = '\0';
+            for c in chars.by_ref() {
+                if is_banner {
+                    result.push(c);
+                }
+                if prev == '*' && c == '/' {
+                    break;
+                }
+                prev = c;
+            }
+            continue;
+        }
+
+        result.push(ch);
+    }
+
+    result
+}
+
+/// Collapses whitespace to a single space, drops whitespace that sits next to
+/// `{`, `}`, `:`, `;` or `,`, and drops a `;` that's immediately followed (once
+/// its own trailing whitespace is skipped) by a rule's closing `}`. Quoted
+/// strings and `url(...)` contents are copied through verbatim.
+fn collapse_whitespace(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut chars = css.chars().peekable();
+    let mut quote: Option<char> = None;
+    let mut in_url = false;
+
+    while let Some(ch) = chars.next() {
+        if let Some(q) = quote {
+            result.push(ch);
+            if ch == '\\' {
+                if let Some(escaped) = chars.next() {
+                    result.push(escaped);
+                }
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        if in_url {
+            result.push(ch);
+            if ch == '"' || ch == '\'' {
+                quote = Some(ch);
+            } else if ch == ')' {
+                in_url = false;
+            }
+            continue;
+        }
+
+        if ch == '"' || ch == '\'' {
+            quote = Some(ch);
+            result.push(ch);
+            continue;
+        }
+
+        if ch.is_whitespace() {
+            while chars.peek().map(|c| c.is_whitespace()).unwrap_or(false) {
+                chars.next();
+            }
+
+            let prev = result.chars().last();
+            let next = chars.peek().copied();
+            let touches_punctuation = |c: Option<char>| {
+                matches!(c, None | Some('{') | Some('}') | Some(';') | Some(':') | Some(','))
+            };
+
+            if !touches_punctuation(prev) && !touches_punctuation(next) {
+                result.push(' ');
+            }
+            continue;
+        }
+
+        if ch == ';' {
+            let mut lookahead = chars.clone();
+            while lookahead.peek().map(|c| c.is_whitespace()).unwrap_or(false) {
+                lookahead.next();
+            }
+            if lookahead.peek() == Some(&'}') {
+                continue; // drop the redundant trailing semicolon
+            }
+            result.push(ch);
+            continue;
+        }
+
+        result.push(ch);
+
+        if ch == '(' && result.len() >= 4 && result[result.len() - 4..].eq_ignore_ascii_case("url(")
+        {
+            in_url = true;
+        }
+    }
+
+    result
+}
+
+/// Removes rules left with an empty body (`selector{}`) after whitespace
+/// collapsing, including their selector. A `{}` that appears inside a quoted
+/// string is left alone.
+fn collapse_empty_rules(css: &str) -> String {
+    let chars: Vec<char> = css.chars().collect();
+    let mut keep = vec![true; chars.len()];
+    let mut quote: Option<char> = None;
+    let mut last_boundary = 0usize;
+
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+
+        if let Some(q) = quote {
+            if ch == '\\' {
+                i += 2;
+                continue;
+            }
+            if ch == q {
+                quote = None;
+            }
+            i += 1;
+            continue;
+        }
+
+        match ch {
+            '"' | '\'' => {
+                quote = Some(ch);
+                i += 1;
+            }
+            '}' => {
+                last_boundary = i + 1;
+                i += 1;
+            }
+            '{' if chars.get(i + 1) == Some(&'}') => {
+                for slot in keep.iter_mut().take(i + 2).skip(last_boundary) {
+                    *slot = false;
+                }
+                last_boundary = i + 2;
+                i += 2;
+            }
+            _ => {
+                i += 1;
+            }
+        }
+    }
+
+    chars
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(c, k)| k.then_some(c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_comments() {
+        let css = ".a { color: red; /* note */ margin: 0; }";
+        let result = minify_css(css, &MinifyConfig::default());
+        assert!(!result.contains("note"));
+    }
+
+    #[test]
+    fn test_preserves_banner_comments() {
+        let css = "/*! keep me */.a{color:red;}";
+        let result = minify_css(css, &MinifyConfig::default());
+        assert!(result.contains("/*! keep me */"));
+    }
+
+    #[test]
+    fn test_strip_comments_toggle_off_keeps_comments() {
+        let css = ".a { /* keep */ color: red; }";
+        let mut config = MinifyConfig::default();
+        config.strip_comments = false;
+        let result = minify_css(css, &config);
+        assert!(result.contains("/* keep */"));
+    }
+
+    #[test]
+    fn test_collapses_whitespace_around_punctuation() {
+        let css = ".a , .b {\n  color : red ;\n  margin : 0 ;\n}";
+        let result = minify_css(css, &MinifyConfig::default());
+        assert_eq!(result, ".a,.b{color:red;margin:0}");
+    }
+
+    #[test]
+    fn test_collapses_whitespace_runs_in_values() {
+        let css = ".a{border:1px   solid   red;}";
+        let result = minify_css(css, &MinifyConfig::default());
+        assert_eq!(result, ".a{border:1px solid red}");
+    }
+
+    #[test]
+    fn test_drops_only_trailing_semicolon() {
+        let css = ".a{color:red;margin:0;padding:0;}";
+        let result = minify_css(css, &MinifyConfig::default());
+        assert_eq!(result, ".a{color:red;margin:0;padding:0}");
+    }
+
+    #[test]
+    fn test_does_not_collapse_whitespace_inside_quoted_string() {
+        let css = ".a{content:\"  spaced  out  \";}";
+        let result = minify_css(css, &MinifyConfig::default());
+        assert_eq!(result, ".a{content:\"  spaced  out  \"}");
+    }
+
+    #[test]
+    fn test_does_not_collapse_whitespace_inside_url() {
+        let css = ".a{background:url(my image.png);}";
+        let result = minify_css(css, &MinifyConfig::default());
+        assert_eq!(result, ".a{background:url(my image.png)}");
+    }
+
+    #[test]
+    fn test_collapses_empty_rules() {
+        let css = ".a{} .b{color:red;}";
+        let result = minify_css(css, &MinifyConfig::default());
+        assert_eq!(result, ".b{color:red}");
+    }
+
+    #[test]
+    fn test_does_not_collapse_brace_pair_inside_string() {
+        let css = ".a{content:\"{}\";}";
+        let result = minify_css(css, &MinifyConfig::default());
+        assert_eq!(result, ".a{content:\"{}\"}");
+    }
+
+    #[test]
+    fn test_prefix_and_minify_combines_both_passes() {
+        let css = ".a {\n  user-select: none;\n}";
+        let result = prefix_and_minify(css, &PrefixConfig::default(), &MinifyConfig::default());
+
+        assert!(result.contains("-webkit-user-select:none"));
+        assert!(result.contains(".a{"));
+        assert!(!result.contains('\n'));
+    }
+}
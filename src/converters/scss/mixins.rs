@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+
+use crate::lexer::{self, TokenKind};
+
+/// A mixin parameter's name and optional default value, e.g. `$color: blue`.
+type Params = Vec<(String, Option<String>)>;
+/// A `@include` call's resolved arguments: `Some(name)` for a named
+/// (`$name: value`) argument, `None` for a positional one.
+type Args = Vec<(Option<String>, String)>;
+
+/// A parsed `@mixin name(params) { body }` definition: its (unsubstituted)
+/// parameter names with optional default values, and its raw body text.
+struct MixinDef {
+    params: Params,
+    body: String,
+}
+
+/// Resolves `@mixin`/`@include` against each other: every `@mixin` body is
+/// recorded and stripped from the output, then every `@include name(...)`
+/// call site is replaced by its mixin's body with `$param` references
+/// substituted by the call's positional or named arguments.
+///
+/// Runs before [`super::variables::substitute_variables`] so a mixin's own
+/// `$param` placeholders are resolved to literal argument text first - by
+/// the time the variables pass runs, only genuine `$global-variable`
+/// references (used as argument values, or anywhere else in the sheet)
+/// remain to be substituted.
+pub fn expand_mixins(input: &str) -> String {
+    let (without_defs, mixins) = extract_mixin_definitions(input);
+    if mixins.is_empty() {
+        return without_defs;
+    }
+    substitute_includes(&without_defs, &mixins)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Whether the char at `pos` actually starts a comment/string token (as
+/// opposed to e.g. a lone `/` that's just division-like text in this
+/// context).
+fn has_lexer_token(input: &str, pos: usize, c: char) -> bool {
+    let token = lexer::first_token(&input[pos..]);
+    match c {
+        '/' => matches!(
+            token.kind,
+            TokenKind::LineComment | TokenKind::BlockComment { .. }
+        ),
+        _ => matches!(
+            token.kind,
+            TokenKind::DoubleQuotedString { .. } | TokenKind::SingleQuotedString { .. }
+        ),
+    }
+}
+
+/// Finds the index just past the `{`...`}` block opened at `open_idx` (which
+/// must point at the `{`), skipping braces inside comments or strings.
+fn find_matching_brace_end(input: &str, open_idx: usize) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = open_idx + 1;
+    let mut depth = 1i32;
+    while i < len {
+        let c = bytes[i] as char;
+        if (c == '/' || c == '\'' || c == '"') && has_lexer_token(input, i, c) {
+            let token = lexer::first_token(&input[i..]);
+            i += token.len;
+            continue;
+        }
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Parses a comma-separated `(param, param: default, ...)` list starting at
+/// the `(` at `start`. Returns the parsed params and the index just past
+/// the closing `)`.
+fn parse_param_list(input: &str, start: usize) -> Option<(Params, usize)> {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = start + 1; // skip '('
+    let mut params = Vec::new();
+    let mut depth = 1i32;
+    let mut current_start = i;
+
+    while i < len {
+        let c = bytes[i] as char;
+        if (c == '/' || c == '\'' || c == '"') && has_lexer_token(input, i, c) {
+            let token = lexer::first_token(&input[i..]);
+            i += token.len;
+            continue;
+        }
+        match c {
+            '(' => {
+                depth += 1;
+                i += 1;
+            }
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    push_param(&mut params, input[current_start..i].trim());
+                    return Some((params, i + 1));
+                }
+                i += 1;
+            }
+            ',' if depth == 1 => {
+                push_param(&mut params, input[current_start..i].trim());
+                current_start = i + 1;
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+fn push_param(params: &mut Params, raw: &str) {
+    let raw = raw.trim().trim_start_matches('$');
+    if raw.is_empty() {
+        return;
+    }
+    match raw.split_once(':') {
+        Some((name, default)) => {
+            params.push((name.trim().to_string(), Some(default.trim().to_string())))
+        }
+        None => params.push((raw.to_string(), None)),
+    }
+}
+
+/// Parses a `@mixin name(params) { body }` definition starting at the `@`
+/// of `@mixin` at `start`. Returns the name, params, body, and the index
+/// just past the closing `}`.
+fn parse_mixin_def(input: &str, start: usize) -> Option<(String, Params, String, usize)> {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = start + "@mixin".len();
+    while i < len && (bytes[i] as char).is_whitespace() {
+        i += 1;
+    }
+    let name_start = i;
+    while i < len && is_name_char(bytes[i] as char) {
+        i += 1;
+    }
+    if i == name_start {
+        return None;
+    }
+    let name = input[name_start..i].to_string();
+
+    while i < len && (bytes[i] as char).is_whitespace() {
+        i += 1;
+    }
+
+    let params = if i < len && bytes[i] == b'(' {
+        let (params, end) = parse_param_list(input, i)?;
+        i = end;
+        while i < len && (bytes[i] as char).is_whitespace() {
+            i += 1;
+        }
+        params
+    } else {
+        Vec::new()
+    };
+
+    if i >= len || bytes[i] != b'{' {
+        return None;
+    }
+    let body_start = i + 1;
+    let block_end = find_matching_brace_end(input, i)?;
+    let body = input[body_start..block_end - 1].trim().to_string();
+
+    Some((name, params, body, block_end))
+}
+
+/// Scans `input` for `@mixin` definitions, stripping them from the output
+/// and recording them keyed by name.
+fn extract_mixin_definitions(input: &str) -> (String, HashMap<String, MixinDef>) {
+    let mut mixins = HashMap::new();
+    let mut out = String::with_capacity(input.len());
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = 0;
+
+    while i < len {
+        let c = bytes[i] as char;
+        if (c == '/' || c == '\'' || c == '"') && has_lexer_token(input, i, c) {
+            let token = lexer::first_token(&input[i..]);
+            out.push_str(&input[i..i + token.len]);
+            i += token.len;
+            continue;
+        }
+        if c == '@' && input[i..].starts_with("@mixin") {
+            match parse_mixin_def(input, i) {
+                Some((name, params, body, end)) => {
+                    mixins.insert(name, MixinDef { params, body });
+                    i = end;
+                    continue;
+                }
+                None => {
+                    out.push(c);
+                    i += 1;
+                    continue;
+                }
+            }
+        }
+        out.push(c);
+        i += 1;
+    }
+
+    (out, mixins)
+}
+
+/// Parses a comma-separated `@include` argument list the same way
+/// [`parse_param_list`] parses a mixin's parameter list, but values are
+/// either bare (positional) or `$name: value` (named).
+fn parse_arg_list(input: &str, start: usize) -> Option<(Args, usize)> {
+    let (raw_params, end) = parse_param_list(input, start)?;
+    let args = raw_params
+        .into_iter()
+        .map(|(first, rest)| match rest {
+            Some(value) => (Some(first), value),
+            None => (None, first),
+        })
+        .collect();
+    Some((args, end))
+}
+
+/// Resolves a mixin call's arguments (positional, then named overrides)
+/// against its declared params, falling back to each param's default.
+fn resolve_args(params: &Params, args: &Args) -> HashMap<String, String> {
+    let mut resolved = HashMap::new();
+    let mut positional = args.iter().filter(|(name, _)| name.is_none());
+
+    for (param_name, default) in params {
+        if let Some((_, value)) = args
+            .iter()
+            .find(|(name, _)| name.as_deref() == Some(param_name.as_str()))
+        {
+            resolved.insert(param_name.clone(), value.clone());
+        } else if let Some((_, value)) = positional.next() {
+            resolved.insert(param_name.clone(), value.clone());
+        } else if let Some(default_value) = default {
+            resolved.insert(param_name.clone(), default_value.clone());
+        }
+    }
+
+    resolved
+}
+
+/// Replaces every `$param` reference in `body` with its resolved argument
+/// value, leaving unresolved names (no argument and no default) untouched.
+fn substitute_params(body: &str, resolved: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(body.len());
+    let bytes = body.as_bytes();
+    let len = bytes.len();
+    let mut i = 0;
+    while i < len {
+        let c = bytes[i] as char;
+        if (c == '/' || c == '\'' || c == '"') && has_lexer_token(body, i, c) {
+            let token = lexer::first_token(&body[i..]);
+            out.push_str(&body[i..i + token.len]);
+            i += token.len;
+            continue;
+        }
+        if c == '$' {
+            let name_start = i + 1;
+            let mut j = name_start;
+            while j < len && is_name_char(bytes[j] as char) {
+                j += 1;
+            }
+            if j == name_start {
+                out.push(c);
+                i += 1;
+                continue;
+            }
+            if let Some(value) = resolved.get(&body[name_start..j]) {
+                out.push_str(value);
+                i = j;
+                continue;
+            }
+        }
+        out.push(c);
+        i += 1;
+    }
+    out
+}
+
+/// Parses a `@include name(args);` call starting at the `@` of `@include`
+/// at `start`. Returns the name, raw args, and the index just past the
+/// terminating `;`.
+fn parse_include_call(input: &str, start: usize) -> Option<(String, Args, usize)> {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = start + "@include".len();
+    while i < len && (bytes[i] as char).is_whitespace() {
+        i += 1;
+    }
+    let name_start = i;
+    while i < len && is_name_char(bytes[i] as char) {
+        i += 1;
+    }
+    if i == name_start {
+        return None;
+    }
+    let name = input[name_start..i].to_string();
+
+    while i < len && (bytes[i] as char).is_whitespace() {
+        i += 1;
+    }
+
+    let args = if i < len && bytes[i] == b'(' {
+        let (args, end) = parse_arg_list(input, i)?;
+        i = end;
+        args
+    } else {
+        Vec::new()
+    };
+
+    while i < len && (bytes[i] as char).is_whitespace() {
+        i += 1;
+    }
+    if i >= len || bytes[i] != b';' {
+        return None;
+    }
+
+    Some((name, args, i + 1))
+}
+
+fn substitute_includes(input: &str, mixins: &HashMap<String, MixinDef>) -> String {
+    let mut out = String::with_capacity(input.len());
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = 0;
+
+    while i < len {
+        let c = bytes[i] as char;
+        if (c == '/' || c == '\'' || c == '"') && has_lexer_token(input, i, c) {
+            let token = lexer::first_token(&input[i..]);
+            out.push_str(&input[i..i + token.len]);
+            i += token.len;
+            continue;
+        }
+        if c == '@' && input[i..].starts_with("@include") {
+            let Some((name, args, end)) = parse_include_call(input, i) else {
+                out.push(c);
+                i += 1;
+                continue;
+            };
+            match mixins.get(&name) {
+                Some(mixin) => {
+                    let resolved = resolve_args(&mixin.params, &args);
+                    out.push_str(&substitute_params(&mixin.body, &resolved));
+                }
+                None => {
+                    eprintln!("[scss] Warning: undefined mixin '{name}' referenced");
+                    out.push_str(&input[i..end]);
+                }
+            }
+            i = end;
+            continue;
+        }
+        out.push(c);
+        i += 1;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_input_unchanged_without_mixins() {
+        let input = ".foo { color: red; }";
+        assert_eq!(expand_mixins(input), input);
+    }
+
+    #[test]
+    fn expands_an_include_with_positional_arguments() {
+        let input = "@mixin button($color, $size) { color: $color; font-size: $size; }\n.btn { @include button(red, 12px); }";
+        let result = expand_mixins(input);
+        assert!(!result.contains("@mixin"));
+        assert!(result.contains(".btn { color: red; font-size: 12px; }"));
+    }
+
+    #[test]
+    fn expands_an_include_with_named_arguments_in_any_order() {
+        let input = "@mixin button($color, $size) { color: $color; font-size: $size; }\n.btn { @include button($size: 12px, $color: red); }";
+        let result = expand_mixins(input);
+        assert!(result.contains(".btn { color: red; font-size: 12px; }"));
+    }
+
+    #[test]
+    fn falls_back_to_a_default_value_when_no_argument_is_given() {
+        let input = "@mixin button($color: blue) { color: $color; }\n.btn { @include button(); }";
+        let result = expand_mixins(input);
+        assert!(result.contains(".btn { color: blue; }"));
+    }
+
+    #[test]
+    fn an_explicit_argument_overrides_the_default() {
+        let input =
+            "@mixin button($color: blue) { color: $color; }\n.btn { @include button(red); }";
+        let result = expand_mixins(input);
+        assert!(result.contains(".btn { color: red; }"));
+    }
+
+    #[test]
+    fn undefined_mixin_reference_is_left_untouched() {
+        let input = ".btn { @include missing(red); }";
+        let result = expand_mixins(input);
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn mixin_with_no_parameters_expands_as_is() {
+        let input = "@mixin clearfix { content: \"\"; display: table; clear: both; }\n.group { @include clearfix(); }";
+        let result = expand_mixins(input);
+        assert!(result.contains(".group { content: \"\"; display: table; clear: both; }"));
+    }
+}
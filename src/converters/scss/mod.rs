@@ -1,23 +1,119 @@
+use crate::error::{Error, Result};
+use crate::hashing::content_fingerprint;
+use crate::traits::AssetConverter;
 use std::collections::HashSet;
+use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 mod imports;
+mod minify;
+mod mixins;
 mod nesting;
+mod prefixes;
+mod targets;
+mod variables;
+
+pub use minify::{minify_css, prefix_and_minify, MinifyConfig};
+pub use prefixes::PrefixConfig;
+pub use targets::{apply_browser_targets, BrowserTargets};
 
 /// Very primitive SCSS to CSS converter that only supports inlining of `@use` and `@import`.
 /// - Only local relative imports ("./" or "../") are supported.
 /// - Supports optional quotes and optional trailing semicolon.
 /// - Ignores media queries or import options; just inlines raw content.
 /// - Prevents infinite recursion by tracking visited absolute paths.
-/// - Does NOT process variables, mixins, etc.
-/// - Adds minimal support for flattening simple nested selectors like `.foo { .bar { ... } }` → `.foo .bar { ... }`.
+/// - Supports simple top-level `$name: value;` variables (including `!default`),
+///   and `@mixin`/`@include` with positional or named arguments and
+///   parameter defaults, but not functions or other richer Sass features.
+/// - Adds minimal support for flattening simple nested selectors like `.foo { .bar { ... } }` → `.foo .bar { ... }`,
+///   including `&` parent-selector references (`.btn { &:hover { ... } }` → `.btn:hover { ... }`).
 pub fn scss_to_css_with_inline_imports(entry_path: &Path) -> std::io::Result<String> {
     let mut visited: HashSet<PathBuf> = HashSet::new();
     let mut inlined = String::new();
     imports::inline_scss_file(entry_path, &mut visited, &mut inlined)?;
-    let flattened = nesting::flatten_basic_nesting(&inlined);
+    // Mixins are expanded before variables so a mixin's own `$param`
+    // placeholders are resolved from the call site's arguments first,
+    // leaving only genuine `$global-variable` references for the
+    // variables pass - which runs over the whole inlined buffer, so
+    // definitions from an included partial are already visible here.
+    let with_mixins = mixins::expand_mixins(&inlined);
+    let with_variables = variables::substitute_variables(&with_mixins);
+    let flattened = nesting::flatten_basic_nesting(&with_variables);
     Ok(flattened)
 }
 
+/// Fingerprints [`scss_to_css_with_inline_imports`]'s combined CSS output
+/// for cache-busting (see the `hashing` module docs): hashes the final CSS
+/// bytes and appends the result to the source file's stem, producing a
+/// `<name>-<hash>.css` filename in the same style
+/// [`crate::file_copier::copy_file_with_versioning`] already uses for every
+/// other asset type. Returns the fingerprinted filename together with the
+/// CSS itself, so a caller can expose a stable `logical_name ->
+/// fingerprinted_name` mapping - e.g. as a template variable for
+/// `replace_template_variables` to inject into `<link>` tags, or into a
+/// future asset manifest - the same way `generate::copy_assets` already
+/// does for assets routed through the generic pipeline.
+pub fn scss_to_fingerprinted_css(entry_path: &Path) -> io::Result<(String, String)> {
+    let css = scss_to_css_with_inline_imports(entry_path)?;
+    let fingerprint = content_fingerprint(css.as_bytes());
+    let stem = entry_path
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .unwrap_or("style");
+    let fingerprinted_name = format!("{stem}-{fingerprint}.css");
+    Ok((fingerprinted_name, css))
+}
+
+/// Writes [`scss_to_fingerprinted_css`]'s CSS output to `destination_dir`
+/// under its fingerprinted name, mirroring how
+/// [`crate::file_copier::copy_file_with_versioning`] persists other
+/// fingerprinted assets. Returns the fingerprinted filename.
+pub fn write_fingerprinted_css(entry_path: &Path, destination_dir: &Path) -> io::Result<String> {
+    let (fingerprinted_name, css) = scss_to_fingerprinted_css(entry_path)?;
+    fs::create_dir_all(destination_dir)?;
+    fs::write(destination_dir.join(&fingerprinted_name), css)?;
+    Ok(fingerprinted_name)
+}
+
+/// SCSS to CSS converter implementation
+pub struct ScssConverter;
+
+impl ScssConverter {
+    /// Create a new SCSS converter
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ScssConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AssetConverter for ScssConverter {
+    fn convert(&self, _input: &str, source_path: Option<&Path>) -> Result<String> {
+        // Inlining `@use`/`@import` needs the source file's location to
+        // resolve relative paths, so the converter reads from disk rather
+        // than working from the already-loaded `input` string.
+        let source_path = source_path.ok_or_else(|| {
+            Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "ScssConverter::convert requires a source path",
+            ))
+        })?;
+        Ok(scss_to_css_with_inline_imports(source_path)?)
+    }
+
+    fn supported_extensions(&self) -> Vec<&str> {
+        vec!["scss"]
+    }
+
+    fn output_extension(&self) -> &str {
+        "css"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,4 +138,53 @@ mod tests {
         assert!(css.contains("article img.loaded{background: initial;"));
         assert!(css.contains("p{margin: 0;"));
     }
+
+    #[test]
+    fn test_scss_converter_trait() {
+        let converter = ScssConverter::new();
+        assert_eq!(converter.supported_extensions(), vec!["scss"]);
+        assert_eq!(converter.output_extension(), "css");
+
+        let path = Path::new("sites/polgarand.org/assets/style.scss");
+        let result = converter
+            .convert("", Some(path))
+            .expect("Conversion failed");
+        assert!(result.contains("p{margin: 0;"));
+    }
+
+    #[test]
+    fn test_scss_converter_requires_source_path() {
+        let converter = ScssConverter::new();
+        assert!(converter.convert("", None).is_err());
+    }
+
+    #[test]
+    fn test_scss_to_fingerprinted_css_appends_hash_to_stem() {
+        let path = Path::new("sites/polgarand.org/assets/style.scss");
+        let (fingerprinted_name, css) = scss_to_fingerprinted_css(path).expect("fingerprint scss");
+
+        assert!(fingerprinted_name.starts_with("style-"));
+        assert!(fingerprinted_name.ends_with(".css"));
+        assert_eq!(css, scss_to_css_with_inline_imports(path).unwrap());
+    }
+
+    #[test]
+    fn test_scss_to_fingerprinted_css_is_deterministic() {
+        let path = Path::new("sites/polgarand.org/assets/style.scss");
+        let (first, _) = scss_to_fingerprinted_css(path).unwrap();
+        let (second, _) = scss_to_fingerprinted_css(path).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_write_fingerprinted_css_writes_the_hashed_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = Path::new("sites/polgarand.org/assets/style.scss");
+
+        let fingerprinted_name =
+            write_fingerprinted_css(path, temp_dir.path()).expect("write fingerprinted css");
+
+        let written = std::fs::read_to_string(temp_dir.path().join(&fingerprinted_name)).unwrap();
+        assert_eq!(written, scss_to_css_with_inline_imports(path).unwrap());
+    }
 }
@@ -1,3 +1,5 @@
+use crate::lexer;
+
 pub fn flatten_basic_nesting(input: &str) -> String {
     let mut parser = Parser::new(input);
     let mut output = String::with_capacity(input.len());
@@ -119,7 +121,7 @@ impl<'a> Parser<'a> {
                     // orphaned block; copy raw
                     items.push(Content::Raw(self.next_char()));
                 }
-                '.' | '#' | '[' | '*' | ':' | '@' => {
+                '.' | '#' | '[' | '*' | ':' | '@' | '&' => {
                     let save = self.pos;
                     if let Some(rule) = self.parse_rule() {
                         items.push(Content::Rule(rule));
@@ -138,40 +140,21 @@ impl<'a> Parser<'a> {
 
     fn parse_declaration_like(&mut self) -> String {
         let start = self.pos;
-        let mut in_string: Option<char> = None;
         while let Some(c) = self.peek() {
-            if let Some(q) = in_string {
-                if c == q {
-                    in_string = None;
-                }
-                self.pos += 1;
+            // Comments and strings are scanned as whole tokens via the
+            // shared lexer, so a `}`/`;` inside either is never mistaken
+            // for the end of this declaration.
+            if c == '/' && matches!(self.bytes.get(self.pos + 1), Some(b'*')) {
+                let token = lexer::first_token(self.slice(self.pos, self.len));
+                self.pos += token.len;
                 continue;
             }
-            // Handle block comments to avoid misinterpreting braces inside comments
-            if c == '/' {
-                if let Some(next) = self.bytes.get(self.pos + 1).map(|b| *b as char) {
-                    if next == '*' {
-                        // Skip '/*'
-                        self.pos += 2;
-                        // Advance until '*/' or EOF
-                        while self.pos + 1 < self.len {
-                            let a = self.bytes[self.pos] as char;
-                            let b = self.bytes[self.pos + 1] as char;
-                            self.pos += 1;
-                            if a == '*' && b == '/' {
-                                self.pos += 1;
-                                break;
-                            }
-                        }
-                        continue;
-                    }
-                }
+            if c == '\'' || c == '"' {
+                let token = lexer::first_token(self.slice(self.pos, self.len));
+                self.pos += token.len;
+                continue;
             }
             match c {
-                '\'' | '"' => {
-                    in_string = Some(c);
-                    self.pos += 1;
-                }
                 ';' => {
                     self.pos += 1;
                     break;
@@ -190,7 +173,7 @@ impl<'a> Parser<'a> {
 
 fn emit_rule(out: &mut String, rule: &Rule, parent: &str) {
     let selector = rule.selector.trim();
-    if selector.is_empty() || selector.contains('&') {
+    if selector.is_empty() {
         // unsupported, emit as-is roughly
         out.push_str(selector);
         out.push('{');
@@ -200,7 +183,9 @@ fn emit_rule(out: &mut String, rule: &Rule, parent: &str) {
         return;
     }
 
-    // Preserve at-rules (e.g., @media) as blocks and emit children inside without combining selectors
+    // Preserve at-rules (e.g., @media) as blocks, passing the enclosing
+    // parent through unchanged so a selector nested inside the at-rule
+    // (plain or using `&`) still combines against it instead of losing it.
     if selector.starts_with('@') {
         out.push_str(selector);
         out.push('{');
@@ -210,7 +195,7 @@ fn emit_rule(out: &mut String, rule: &Rule, parent: &str) {
                     out.push_str(s);
                 }
                 Content::Rule(r) => {
-                    emit_rule(out, r, "");
+                    emit_rule(out, r, parent);
                 }
                 Content::Raw(ch) => {
                     out.push(*ch);
@@ -222,11 +207,7 @@ fn emit_rule(out: &mut String, rule: &Rule, parent: &str) {
         return;
     }
 
-    let combined_selector = if parent.is_empty() {
-        selector.to_string()
-    } else {
-        format!("{} {}", parent, selector)
-    };
+    let combined_selector = combine_selectors(parent, selector);
     let mut decls = String::new();
     for c in &rule.content {
         match c {
@@ -247,6 +228,44 @@ fn emit_rule(out: &mut String, rule: &Rule, parent: &str) {
     }
 }
 
+/// Combines a (possibly comma-separated) `parent` selector list with a
+/// (possibly comma-separated) nested `selector`, distributing every child
+/// over every parent. A child containing `&` is concatenated directly onto
+/// its parent with no intervening space (`.btn` + `&:hover` → `.btn:hover`);
+/// any other child keeps the plain descendant-combinator space
+/// (`.btn` + `.icon` → `.btn .icon`). With no parent, `selector` is returned
+/// untouched so top-level selector lists keep their original formatting.
+fn combine_selectors(parent: &str, selector: &str) -> String {
+    if parent.is_empty() && !selector.contains('&') {
+        return selector.to_string();
+    }
+
+    let parents: Vec<&str> = if parent.is_empty() {
+        vec![""]
+    } else {
+        parent.split(',').map(str::trim).collect()
+    };
+    let children: Vec<&str> = selector.split(',').map(str::trim).collect();
+
+    let mut combined = Vec::with_capacity(parents.len() * children.len());
+    for p in &parents {
+        for c in &children {
+            combined.push(combine_one_selector(p, c));
+        }
+    }
+    combined.join(", ")
+}
+
+fn combine_one_selector(parent: &str, child: &str) -> String {
+    if child.contains('&') {
+        child.replace('&', parent)
+    } else if parent.is_empty() {
+        child.to_string()
+    } else {
+        format!("{} {}", parent, child)
+    }
+}
+
 fn emit_content(out: &mut String, c: &Content, parent: &str) {
     match c {
         Content::Decl(s) => out.push_str(s),
@@ -283,6 +302,20 @@ mod tests {
         assert_eq!(flattened, ".foo .bar{color: #000000;}");
     }
 
+    #[test]
+    fn test_brace_inside_declaration_string_does_not_close_block() {
+        let input = r#".foo { content: "}"; color: red; }"#;
+        let flattened = flatten_basic_nesting(input);
+        assert_eq!(flattened, r#".foo{content: "}";color: red;}"#);
+    }
+
+    #[test]
+    fn test_brace_inside_declaration_comment_does_not_close_block() {
+        let input = ".foo { /* a } b */ color: red; }";
+        let flattened = flatten_basic_nesting(input);
+        assert_eq!(flattened, ".foo{/* a } b */ color: red;}");
+    }
+
     #[test]
     fn test_element_selector_preserved() {
         let input = "p { margin: 0; padding: 0 0 20px 0; }";
@@ -300,6 +333,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ampersand_parent_reference_concatenated_without_space() {
+        let input = ".btn { &:hover { color: blue; } }";
+        let flattened = flatten_basic_nesting(input);
+        assert_eq!(flattened, ".btn:hover{color: blue;}");
+    }
+
+    #[test]
+    fn test_ampersand_distributes_over_comma_separated_parents() {
+        let input = ".a, .b { &:hover { color: blue; } }";
+        let flattened = flatten_basic_nesting(input);
+        assert_eq!(flattened, ".a:hover, .b:hover{color: blue;}");
+    }
+
+    #[test]
+    fn test_bare_nested_selector_keeps_descendant_space_alongside_ampersand_sibling() {
+        let input = ".btn { &.is-active { color: green; } .icon { width: 1px; } }";
+        let flattened = flatten_basic_nesting(input);
+        assert_eq!(
+            flattened,
+            ".btn.is-active{color: green;}.btn .icon{width: 1px;}"
+        );
+    }
+
+    #[test]
+    fn test_ampersand_combines_with_cartesian_product_on_both_sides() {
+        let input = ".a, .b { &:hover, &:focus { color: blue; } }";
+        let flattened = flatten_basic_nesting(input);
+        assert_eq!(
+            flattened,
+            ".a:hover, .a:focus, .b:hover, .b:focus{color: blue;}"
+        );
+    }
+
+    #[test]
+    fn test_ampersand_occurring_multiple_times_in_one_child_selector() {
+        let input = ".a, .b { &.foo & { color: blue; } }";
+        let flattened = flatten_basic_nesting(input);
+        assert_eq!(flattened, ".a.foo .a, .b.foo .b{color: blue;}");
+    }
+
+    #[test]
+    fn test_ampersand_inside_nested_media_query_keeps_parent_selector() {
+        let input = ".btn { @media (max-width: 500px) { &:hover { color: blue; } } }";
+        let flattened = flatten_basic_nesting(input);
+        assert_eq!(
+            flattened,
+            "@media (max-width: 500px){.btn:hover{color: blue;}}"
+        );
+    }
+
     #[test]
     fn test_multiple_top_level_rules() {
         let input = ".btn { color: red; }\nh1 { font-weight: 700; }\nbody { margin: 0; }\n";
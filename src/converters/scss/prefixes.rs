@@ -1,3 +1,77 @@
+/// Minimum supported version per browser engine, used to decide whether a
+/// given vendor prefix is still worth emitting. `None` means that engine
+/// isn't targeted at all, so prefixes are never emitted on its account.
+///
+/// Versions are plain major-version numbers (`Some(11)` for "IE 11"), not
+/// resolved against real caniuse/browserslist usage-share data - this
+/// generator has no access to that, the same limitation noted on
+/// [`super::targets::BrowserTargets`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TargetBrowsers {
+    pub chrome: Option<u32>,
+    pub firefox: Option<u32>,
+    pub safari: Option<u32>,
+    pub ios: Option<u32>,
+    pub android: Option<u32>,
+    pub edge: Option<u32>,
+    pub ie: Option<u32>,
+    pub samsung: Option<u32>,
+}
+
+impl TargetBrowsers {
+    /// A target set that always needs every known prefix: every engine is
+    /// "supported" starting from version 0, so it satisfies any prefix's
+    /// [`RequiredUpTo`] floor. This is what [`PrefixConfig::default`] uses to
+    /// keep its boolean toggles' historical behavior.
+    pub fn support_everything() -> Self {
+        Self {
+            chrome: Some(0),
+            firefox: Some(0),
+            safari: Some(0),
+            ios: Some(0),
+            android: Some(0),
+            edge: Some(0),
+            ie: Some(0),
+            samsung: Some(0),
+        }
+    }
+}
+
+/// The highest version of each engine that still needs a particular vendor
+/// prefix. `None` for an engine means that engine never needed this prefix
+/// form, so targeting it can never bring the prefix back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct RequiredUpTo {
+    chrome: Option<u32>,
+    firefox: Option<u32>,
+    safari: Option<u32>,
+    ios: Option<u32>,
+    android: Option<u32>,
+    edge: Option<u32>,
+    ie: Option<u32>,
+    samsung: Option<u32>,
+}
+
+/// Whether `targets` includes at least one engine whose configured minimum
+/// version is still at or below `required_up_to`'s floor for that engine -
+/// i.e. whether some targeted browser still needs the prefix.
+fn needs_prefix(targets: &TargetBrowsers, required_up_to: &RequiredUpTo) -> bool {
+    let pairs = [
+        (targets.chrome, required_up_to.chrome),
+        (targets.firefox, required_up_to.firefox),
+        (targets.safari, required_up_to.safari),
+        (targets.ios, required_up_to.ios),
+        (targets.android, required_up_to.android),
+        (targets.edge, required_up_to.edge),
+        (targets.ie, required_up_to.ie),
+        (targets.samsung, required_up_to.samsung),
+    ];
+
+    pairs
+        .iter()
+        .any(|&(min, up_to)| matches!((min, up_to), (Some(min), Some(up_to)) if min <= up_to))
+}
+
 /// Configuration for vendor prefix generation
 #[derive(Debug, Clone)]
 pub struct PrefixConfig {
@@ -5,8 +79,15 @@ pub struct PrefixConfig {
     pub flexbox: bool,
     /// Whether to add user interaction prefixes (-webkit-, -moz-)
     pub user_interaction: bool,
-    /// Whether to add effect prefixes like backdrop-filter (-webkit-)
+    /// Whether to add effect prefixes like backdrop-filter/mask (-webkit-)
     pub effects: bool,
+    /// Whether to add layout prefixes like position: sticky (-webkit-)
+    pub layout: bool,
+    /// Browser engines to target. A prefix is only emitted when both its
+    /// category toggle above is on AND some targeted engine's minimum
+    /// version still falls at or below the prefix's own version floor -
+    /// e.g. dropping `ie` from the targets drops `-ms-flexbox`.
+    pub targets: TargetBrowsers,
 }
 
 impl Default for PrefixConfig {
@@ -15,6 +96,8 @@ impl Default for PrefixConfig {
             flexbox: true,
             user_interaction: true,
             effects: true,
+            layout: true,
+            targets: TargetBrowsers::support_everything(),
         }
     }
 }
@@ -35,6 +118,38 @@ pub fn add_vendor_prefixes(css: &str, config: &PrefixConfig) -> String {
     let mut pos = 0;
     while let Some(open_brace) = result[pos..].find('{') {
         let absolute_open = pos + open_brace;
+
+        // The selector/at-rule text immediately preceding this brace.
+        let selector_start = result[..absolute_open]
+            .rfind('}')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let selector = result[selector_start..absolute_open].trim();
+
+        // `@keyframes` blocks are cloned wholesale under a `-webkit-` name
+        // rather than having their individual declarations walked, since the
+        // prefix here lives on the at-rule, not on any declaration inside it.
+        if let Some(name) = selector.strip_prefix("@keyframes").map(str::trim) {
+            let Some(absolute_close) = find_matching_brace(&result, absolute_open) else {
+                break;
+            };
+
+            let webkit_marker = format!("@-webkit-keyframes {name}");
+            if config.effects
+                && needs_prefix(&config.targets, &WEBKIT_ANIMATION)
+                && !result.contains(&webkit_marker)
+            {
+                let block = result[selector_start..=absolute_close].to_string();
+                let webkit_block = block.replacen("@keyframes", "@-webkit-keyframes", 1);
+                result.insert_str(absolute_close + 1, &webkit_block);
+                pos = absolute_close + 1 + webkit_block.len();
+                continue;
+            }
+
+            pos = absolute_close + 1;
+            continue;
+        }
+
         if let Some(close_brace) = result[absolute_open..].find('}') {
             let absolute_close = absolute_open + close_brace;
 
@@ -57,6 +172,27 @@ pub fn add_vendor_prefixes(css: &str, config: &PrefixConfig) -> String {
     result
 }
 
+/// Finds the index of the `}` that closes the `{` at `open_brace`, accounting
+/// for braces nested inside - unlike the declaration scan above, an
+/// `@keyframes` block's per-keyframe selectors (`0% { ... }`) nest another
+/// level of braces that a first-`}`-found search would stop at too early.
+fn find_matching_brace(css: &str, open_brace: usize) -> Option<usize> {
+    let mut depth = 0i32;
+    for (i, ch) in css.char_indices().skip(open_brace) {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 /// Add vendor prefixes to declarations within a CSS rule
 fn add_prefixes_to_declarations(declarations: &str, config: &PrefixConfig) -> String {
     let mut result = String::new();
@@ -144,38 +280,285 @@ fn parse_declaration_from_text(text: &str) -> Option<Declaration> {
     Some(Declaration { property, value })
 }
 
+/// Needed through Chrome 28 / Safari 8 / iOS 8 / Android 4 / Samsung 4 - the
+/// pre-2015 engines that only understood the 2012 flexbox syntax.
+const WEBKIT_FLEXBOX: RequiredUpTo = RequiredUpTo {
+    chrome: Some(28),
+    firefox: None,
+    safari: Some(8),
+    ios: Some(8),
+    android: Some(4),
+    edge: None,
+    ie: None,
+    samsung: Some(4),
+};
+
+/// Needed through IE 10, the only engine that shipped the 2012 `-ms-`
+/// flexbox syntax.
+const MS_FLEXBOX: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: None,
+    ios: None,
+    android: None,
+    edge: None,
+    ie: Some(10),
+    samsung: None,
+};
+
+/// Needed through Safari 14 / iOS 14, which never shipped unprefixed
+/// `user-select`.
+const WEBKIT_USER_SELECT: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: Some(14),
+    ios: Some(14),
+    android: None,
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through Firefox 68, before `user-select` was unprefixed there.
+const MOZ_USER_SELECT: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: Some(68),
+    safari: None,
+    ios: None,
+    android: None,
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through IE 11 and legacy (pre-Chromium) Edge 18.
+const MS_USER_SELECT: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: None,
+    ios: None,
+    android: None,
+    edge: Some(18),
+    ie: Some(11),
+    samsung: None,
+};
+
+/// Needed through Safari 14 / iOS 14 for `appearance`.
+const WEBKIT_APPEARANCE: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: Some(14),
+    ios: Some(14),
+    android: None,
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through Firefox 80 for `appearance`.
+const MOZ_APPEARANCE: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: Some(80),
+    safari: None,
+    ios: None,
+    android: None,
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through Safari 17 / iOS 17, which still requires `-webkit-` for
+/// `backdrop-filter`.
+const WEBKIT_BACKDROP_FILTER: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: Some(17),
+    ios: Some(17),
+    android: None,
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through Safari 15 / iOS 15 for `mask`.
+const WEBKIT_MASK: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: Some(15),
+    ios: Some(15),
+    android: None,
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through Safari 12 / iOS 12, before `position: sticky` shipped
+/// unprefixed there.
+const WEBKIT_STICKY: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: Some(12),
+    ios: Some(12),
+    android: None,
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through Chrome 35 / Firefox 15 / Safari 8 / iOS 8 / Android 4 /
+/// Samsung 4 for `transform`.
+const WEBKIT_TRANSFORM: RequiredUpTo = RequiredUpTo {
+    chrome: Some(35),
+    firefox: Some(15),
+    safari: Some(8),
+    ios: Some(8),
+    android: Some(4),
+    edge: None,
+    ie: None,
+    samsung: Some(4),
+};
+
+/// Needed through IE 9, the only engine that shipped a `-ms-transform`.
+const MS_TRANSFORM: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: None,
+    ios: None,
+    android: None,
+    edge: None,
+    ie: Some(9),
+    samsung: None,
+};
+
+/// Needed through Chrome 25 / Safari 6 / iOS 6 / Android 4 / Samsung 4 for
+/// `transition`.
+const WEBKIT_TRANSITION: RequiredUpTo = RequiredUpTo {
+    chrome: Some(25),
+    firefox: None,
+    safari: Some(6),
+    ios: Some(6),
+    android: Some(4),
+    edge: None,
+    ie: None,
+    samsung: Some(4),
+};
+
+/// Needed through Chrome 42 / Safari 8 / iOS 8 / Android 4 / Samsung 4 for
+/// `animation`.
+const WEBKIT_ANIMATION: RequiredUpTo = RequiredUpTo {
+    chrome: Some(42),
+    firefox: None,
+    safari: Some(8),
+    ios: Some(8),
+    android: Some(4),
+    edge: None,
+    ie: None,
+    samsung: Some(4),
+};
+
+/// Needed through Safari 4 / iOS 4 / Android 2, the last engines that hadn't
+/// unprefixed `box-shadow`.
+const WEBKIT_BOX_SHADOW: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: Some(4),
+    ios: Some(4),
+    android: Some(2),
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through Safari 15 / iOS 15, which never shipped unprefixed
+/// `break-inside` and still wants the old multicol spelling.
+const WEBKIT_COLUMN_BREAK_INSIDE: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: Some(15),
+    ios: Some(15),
+    android: None,
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through Safari 16 / iOS 16 for `cursor: grab`.
+const WEBKIT_GRAB: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: Some(16),
+    ios: Some(16),
+    android: None,
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
+/// Needed through Safari 10 / iOS 10 / Android 4 for `max-content`.
+const WEBKIT_MAX_CONTENT: RequiredUpTo = RequiredUpTo {
+    chrome: None,
+    firefox: None,
+    safari: Some(10),
+    ios: Some(10),
+    android: Some(4),
+    edge: None,
+    ie: None,
+    samsung: None,
+};
+
 /// Get required vendor prefixes for a declaration
 fn get_required_prefixes(decl: &Declaration, config: &PrefixConfig) -> Vec<String> {
     let mut prefixes = Vec::new();
+    let targets = &config.targets;
 
     // Flexbox properties
     if config.flexbox {
         match decl.property.as_str() {
             "display" if decl.value == "flex" => {
-                prefixes.push(format!("display: -webkit-flex;"));
-                prefixes.push(format!("display: -ms-flexbox;"));
+                if needs_prefix(targets, &WEBKIT_FLEXBOX) {
+                    prefixes.push("display: -webkit-flex;".to_string());
+                }
+                if needs_prefix(targets, &MS_FLEXBOX) {
+                    prefixes.push("display: -ms-flexbox;".to_string());
+                }
             }
             "flex-direction" => {
-                prefixes.push(format!("-webkit-flex-direction: {};", decl.value));
-                prefixes.push(format!("-ms-flex-direction: {};", decl.value));
+                if needs_prefix(targets, &WEBKIT_FLEXBOX) {
+                    prefixes.push(format!("-webkit-flex-direction: {};", decl.value));
+                }
+                if needs_prefix(targets, &MS_FLEXBOX) {
+                    prefixes.push(format!("-ms-flex-direction: {};", decl.value));
+                }
             }
             "justify-content" => {
-                prefixes.push(format!("-webkit-justify-content: {};", decl.value));
-                prefixes.push(format!(
-                    "-ms-flex-pack: {};",
-                    map_justify_content_to_ms(&decl.value)
-                ));
+                if needs_prefix(targets, &WEBKIT_FLEXBOX) {
+                    prefixes.push(format!("-webkit-justify-content: {};", decl.value));
+                }
+                if needs_prefix(targets, &MS_FLEXBOX) {
+                    prefixes.push(format!(
+                        "-ms-flex-pack: {};",
+                        map_justify_content_to_ms(&decl.value)
+                    ));
+                }
             }
             "align-items" => {
-                prefixes.push(format!("-webkit-align-items: {};", decl.value));
-                prefixes.push(format!(
-                    "-ms-flex-align: {};",
-                    map_align_items_to_ms(&decl.value)
-                ));
+                if needs_prefix(targets, &WEBKIT_FLEXBOX) {
+                    prefixes.push(format!("-webkit-align-items: {};", decl.value));
+                }
+                if needs_prefix(targets, &MS_FLEXBOX) {
+                    prefixes.push(format!(
+                        "-ms-flex-align: {};",
+                        map_align_items_to_ms(&decl.value)
+                    ));
+                }
             }
             "flex" => {
-                prefixes.push(format!("-webkit-flex: {};", decl.value));
-                prefixes.push(format!("-ms-flex: {};", decl.value));
+                if needs_prefix(targets, &WEBKIT_FLEXBOX) {
+                    prefixes.push(format!("-webkit-flex: {};", decl.value));
+                }
+                if needs_prefix(targets, &MS_FLEXBOX) {
+                    prefixes.push(format!("-ms-flex: {};", decl.value));
+                }
             }
             _ => {}
         }
@@ -185,13 +568,23 @@ fn get_required_prefixes(decl: &Declaration, config: &PrefixConfig) -> Vec<Strin
     if config.user_interaction {
         match decl.property.as_str() {
             "user-select" => {
-                prefixes.push(format!("-webkit-user-select: {};", decl.value));
-                prefixes.push(format!("-moz-user-select: {};", decl.value));
-                prefixes.push(format!("-ms-user-select: {};", decl.value));
+                if needs_prefix(targets, &WEBKIT_USER_SELECT) {
+                    prefixes.push(format!("-webkit-user-select: {};", decl.value));
+                }
+                if needs_prefix(targets, &MOZ_USER_SELECT) {
+                    prefixes.push(format!("-moz-user-select: {};", decl.value));
+                }
+                if needs_prefix(targets, &MS_USER_SELECT) {
+                    prefixes.push(format!("-ms-user-select: {};", decl.value));
+                }
             }
             "appearance" => {
-                prefixes.push(format!("-webkit-appearance: {};", decl.value));
-                prefixes.push(format!("-moz-appearance: {};", decl.value));
+                if needs_prefix(targets, &WEBKIT_APPEARANCE) {
+                    prefixes.push(format!("-webkit-appearance: {};", decl.value));
+                }
+                if needs_prefix(targets, &MOZ_APPEARANCE) {
+                    prefixes.push(format!("-moz-appearance: {};", decl.value));
+                }
             }
             _ => {}
         }
@@ -201,7 +594,65 @@ fn get_required_prefixes(decl: &Declaration, config: &PrefixConfig) -> Vec<Strin
     if config.effects {
         match decl.property.as_str() {
             "backdrop-filter" => {
-                prefixes.push(format!("-webkit-backdrop-filter: {};", decl.value));
+                if needs_prefix(targets, &WEBKIT_BACKDROP_FILTER) {
+                    prefixes.push(format!("-webkit-backdrop-filter: {};", decl.value));
+                }
+            }
+            "mask" => {
+                if needs_prefix(targets, &WEBKIT_MASK) {
+                    prefixes.push(format!("-webkit-mask: {};", decl.value));
+                }
+            }
+            "transform" => {
+                if needs_prefix(targets, &WEBKIT_TRANSFORM) {
+                    prefixes.push(format!("-webkit-transform: {};", decl.value));
+                }
+                if needs_prefix(targets, &MS_TRANSFORM) {
+                    prefixes.push(format!("-ms-transform: {};", decl.value));
+                }
+            }
+            "transition" => {
+                if needs_prefix(targets, &WEBKIT_TRANSITION) {
+                    prefixes.push(format!("-webkit-transition: {};", decl.value));
+                }
+            }
+            "animation" => {
+                if needs_prefix(targets, &WEBKIT_ANIMATION) {
+                    prefixes.push(format!("-webkit-animation: {};", decl.value));
+                }
+            }
+            "box-shadow" => {
+                if needs_prefix(targets, &WEBKIT_BOX_SHADOW) {
+                    prefixes.push(format!("-webkit-box-shadow: {};", decl.value));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Layout properties, including value-level prefixing where the prefix
+    // belongs on the value rather than (or in addition to) the property.
+    if config.layout {
+        match (decl.property.as_str(), decl.value.as_str()) {
+            ("position", "sticky") => {
+                if needs_prefix(targets, &WEBKIT_STICKY) {
+                    prefixes.push("position: -webkit-sticky;".to_string());
+                }
+            }
+            ("cursor", "grab") => {
+                if needs_prefix(targets, &WEBKIT_GRAB) {
+                    prefixes.push("cursor: -webkit-grab;".to_string());
+                }
+            }
+            ("page-break-inside", _) | ("break-inside", _) => {
+                if needs_prefix(targets, &WEBKIT_COLUMN_BREAK_INSIDE) {
+                    prefixes.push(format!("-webkit-column-break-inside: {};", decl.value));
+                }
+            }
+            (property, "max-content") => {
+                if needs_prefix(targets, &WEBKIT_MAX_CONTENT) {
+                    prefixes.push(format!("{property}: -webkit-max-content;"));
+                }
             }
             _ => {}
         }
@@ -304,6 +755,36 @@ mod tests {
         assert!(result.contains("-webkit-backdrop-filter: blur(5px);"));
     }
 
+    #[test]
+    fn test_mask_prefixes() {
+        let config = PrefixConfig::default();
+        let css = ".test{mask:url(#mask);}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("mask:url(#mask);"));
+        assert!(result.contains("-webkit-mask: url(#mask);"));
+    }
+
+    #[test]
+    fn test_position_sticky_prefixes() {
+        let config = PrefixConfig::default();
+        let css = ".test{position:sticky;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("position:sticky;"));
+        assert!(result.contains("position: -webkit-sticky;"));
+    }
+
+    #[test]
+    fn test_layout_toggle_disables_sticky_prefix() {
+        let mut config = PrefixConfig::default();
+        config.layout = false;
+        let css = ".test{position:sticky;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(!result.contains("-webkit-sticky"));
+    }
+
     #[test]
     fn test_configuration_toggles() {
         let mut config = PrefixConfig::default();
@@ -349,4 +830,182 @@ mod tests {
         assert_eq!(map_align_items_to_ms("stretch"), "stretch");
         assert_eq!(map_align_items_to_ms("baseline"), "baseline");
     }
+
+    #[test]
+    fn test_no_targets_drops_every_prefix() {
+        let mut config = PrefixConfig::default();
+        config.targets = TargetBrowsers::default();
+        let css = ".test{display:flex;user-select:none;position:sticky;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(!result.contains("-webkit-"));
+        assert!(!result.contains("-moz-"));
+        assert!(!result.contains("-ms-"));
+    }
+
+    #[test]
+    fn test_dropping_ie_drops_only_ms_flexbox_prefix() {
+        let mut config = PrefixConfig::default();
+        let mut targets = TargetBrowsers::support_everything();
+        targets.ie = None;
+        config.targets = targets;
+        let css = ".test{display:flex;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("display: -webkit-flex;"));
+        assert!(!result.contains("-ms-flexbox"));
+    }
+
+    #[test]
+    fn test_modern_only_targets_drop_ms_and_legacy_webkit_flexbox() {
+        let mut config = PrefixConfig::default();
+        config.targets = TargetBrowsers {
+            chrome: Some(120),
+            firefox: Some(120),
+            safari: Some(17),
+            ios: None,
+            android: None,
+            edge: None,
+            ie: None,
+            samsung: None,
+        };
+        let css = ".test{display:flex;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        // Chrome/Firefox/Safari 120/120/17 are all past the legacy webkit-flexbox
+        // floor, and ie/edge aren't targeted at all.
+        assert!(!result.contains("-webkit-flex"));
+        assert!(!result.contains("-ms-flexbox"));
+    }
+
+    #[test]
+    fn test_old_safari_still_needs_webkit_backdrop_filter() {
+        let mut config = PrefixConfig::default();
+        let mut targets = TargetBrowsers::default();
+        targets.safari = Some(15);
+        config.targets = targets;
+        let css = ".test{backdrop-filter:blur(5px);}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("-webkit-backdrop-filter: blur(5px);"));
+    }
+
+    #[test]
+    fn test_targets_cannot_revive_a_prefix_the_category_toggle_disabled() {
+        let mut config = PrefixConfig::default();
+        config.flexbox = false;
+        let css = ".test{display:flex;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(!result.contains("-webkit-flex"));
+        assert!(!result.contains("-ms-flexbox"));
+    }
+
+    #[test]
+    fn test_transform_transition_animation_prefixes() {
+        let config = PrefixConfig::default();
+        let css = ".test{transform:rotate(5deg);transition:all 1s;animation:spin 2s;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("-webkit-transform: rotate(5deg);"));
+        assert!(result.contains("-ms-transform: rotate(5deg);"));
+        assert!(result.contains("-webkit-transition: all 1s;"));
+        assert!(result.contains("-webkit-animation: spin 2s;"));
+    }
+
+    #[test]
+    fn test_box_shadow_prefix() {
+        let config = PrefixConfig::default();
+        let css = ".test{box-shadow:0 0 5px #000;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("-webkit-box-shadow: 0 0 5px #000;"));
+    }
+
+    #[test]
+    fn test_page_break_inside_maps_to_webkit_column_break_inside() {
+        let config = PrefixConfig::default();
+        let css = ".test{page-break-inside:avoid;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("-webkit-column-break-inside: avoid;"));
+    }
+
+    #[test]
+    fn test_break_inside_maps_to_webkit_column_break_inside() {
+        let config = PrefixConfig::default();
+        let css = ".test{break-inside:avoid;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("-webkit-column-break-inside: avoid;"));
+    }
+
+    #[test]
+    fn test_cursor_grab_value_prefix() {
+        let config = PrefixConfig::default();
+        let css = ".test{cursor:grab;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("cursor: -webkit-grab;"));
+    }
+
+    #[test]
+    fn test_max_content_value_prefix_is_generic_over_property() {
+        let config = PrefixConfig::default();
+        let css = ".test{width:max-content;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("width: -webkit-max-content;"));
+    }
+
+    #[test]
+    fn test_value_prefix_dedupe_when_already_present() {
+        let config = PrefixConfig::default();
+        let css = ".test{cursor:-webkit-grab;cursor:grab;}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert_eq!(result.matches("cursor: -webkit-grab;").count(), 0);
+    }
+
+    #[test]
+    fn test_effects_toggle_disables_transform_prefixes() {
+        let mut config = PrefixConfig::default();
+        config.effects = false;
+        let css = ".test{transform:rotate(5deg);}";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(!result.contains("-webkit-transform"));
+        assert!(!result.contains("-ms-transform"));
+    }
+
+    #[test]
+    fn test_keyframes_block_is_duplicated_with_webkit_prefix() {
+        let config = PrefixConfig::default();
+        let css = "@keyframes spin { 0% { transform: rotate(0deg); } 100% { transform: rotate(360deg); } }";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(result.contains("@keyframes spin"));
+        assert!(result.contains("@-webkit-keyframes spin"));
+        // The cloned block keeps the same body as the original.
+        assert_eq!(result.matches("rotate(360deg)").count(), 2);
+    }
+
+    #[test]
+    fn test_keyframes_block_not_duplicated_when_already_prefixed() {
+        let config = PrefixConfig::default();
+        let css = "@keyframes spin { 0% { opacity: 0; } } @-webkit-keyframes spin { 0% { opacity: 0; } }";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert_eq!(result.matches("@-webkit-keyframes spin").count(), 1);
+    }
+
+    #[test]
+    fn test_keyframes_not_duplicated_when_effects_toggle_disabled() {
+        let mut config = PrefixConfig::default();
+        config.effects = false;
+        let css = "@keyframes spin { 0% { opacity: 0; } }";
+        let result = add_vendor_prefixes(css, &config);
+
+        assert!(!result.contains("@-webkit-keyframes"));
+    }
 }
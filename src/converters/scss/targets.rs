@@ -0,0 +1,150 @@
+use super::prefixes::{add_vendor_prefixes, PrefixConfig};
+
+/// Parsed browserslist-style target configuration, e.g. `"last 2 versions,
+/// >1%, ie 11"`. Each comma-separated clause is kept verbatim (lowercased
+/// and trimmed) rather than resolved against real usage-share data, since
+/// this generator has no access to caniuse/browserslist datasets - it only
+/// recognizes a couple of clauses well enough to decide whether legacy
+/// transforms are worth running at all.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BrowserTargets {
+    entries: Vec<String>,
+}
+
+impl BrowserTargets {
+    /// Parses a comma-separated browserslist-style query string.
+    pub fn parse(spec: &str) -> Self {
+        let entries = spec
+            .split(',')
+            .map(|clause| clause.trim().to_lowercase())
+            .filter(|clause| !clause.is_empty())
+            .collect();
+        Self { entries }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether Internet Explorer is named explicitly, with or without a
+    /// version (`"ie"`, `"ie 11"`). IE is the one browser this pipeline
+    /// knows lacks support for 8-digit hex colors with an alpha channel.
+    fn targets_ie(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|clause| clause == "ie" || clause.starts_with("ie "))
+    }
+}
+
+/// Applies target-aware CSS transforms: vendor prefixing for properties that
+/// need it, and (only when Internet Explorer is targeted) lowering 8-digit
+/// hex colors with an alpha channel to `rgba()`. A no-op when `targets` is
+/// empty, so sites that don't configure targets keep their current output.
+pub fn apply_browser_targets(css: &str, targets: &BrowserTargets) -> String {
+    if targets.is_empty() {
+        return css.to_string();
+    }
+
+    let prefixed = add_vendor_prefixes(css, &PrefixConfig::default());
+
+    if targets.targets_ie() {
+        lower_alpha_hex_colors(&prefixed)
+    } else {
+        prefixed
+    }
+}
+
+/// Expands 8-digit `#rrggbbaa` hex colors to `rgba(r, g, b, a)`. Shorter hex
+/// forms (3/4/6 digits) are left untouched since they're already supported
+/// everywhere IE is still relevant.
+fn lower_alpha_hex_colors(css: &str) -> String {
+    let chars: Vec<char> = css.chars().collect();
+    let mut result = String::with_capacity(css.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == '#' {
+            let hex: String = chars[i + 1..]
+                .iter()
+                .take_while(|c| c.is_ascii_hexdigit())
+                .collect();
+            if hex.len() == 8 {
+                if let Some(rgba) = hex_to_rgba(&hex) {
+                    result.push_str(&rgba);
+                    i += 1 + hex.len();
+                    continue;
+                }
+            }
+        }
+        result.push(chars[i]);
+        i += 1;
+    }
+
+    result
+}
+
+/// Converts an 8-digit `rrggbbaa` hex string into a `rgba(...)` function call.
+fn hex_to_rgba(hex: &str) -> Option<String> {
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    let a = u8::from_str_radix(&hex[6..8], 16).ok()?;
+    let alpha = f64::from(a) / 255.0;
+    Some(format!("rgba({r}, {g}, {b}, {alpha:.2})"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_targets_is_noop() {
+        let css = ".test{user-select:none;}";
+        let result = apply_browser_targets(css, &BrowserTargets::parse(""));
+        assert_eq!(result, css);
+    }
+
+    #[test]
+    fn test_last_versions_adds_prefixes_without_hex_lowering() {
+        let css = ".test{user-select:none;background:#ff000080;}";
+        let targets = BrowserTargets::parse("last 2 versions, >1%");
+        let result = apply_browser_targets(css, &targets);
+
+        assert!(result.contains("-webkit-user-select: none;"));
+        assert!(result.contains("background:#ff000080;"));
+    }
+
+    #[test]
+    fn test_ie_target_lowers_alpha_hex_colors() {
+        let css = ".test{background:#ff000080;}";
+        let targets = BrowserTargets::parse("ie 11");
+        let result = apply_browser_targets(css, &targets);
+
+        assert!(!result.contains("#ff000080"));
+        assert!(result.contains("rgba(255, 0, 0, 0.50)"));
+    }
+
+    #[test]
+    fn test_ie_target_also_adds_prefixes() {
+        let css = ".test{backdrop-filter:blur(2px);}";
+        let targets = BrowserTargets::parse("ie");
+        let result = apply_browser_targets(css, &targets);
+
+        assert!(result.contains("-webkit-backdrop-filter: blur(2px);"));
+    }
+
+    #[test]
+    fn test_hex_to_rgba_conversion() {
+        assert_eq!(hex_to_rgba("ff000080"), Some("rgba(255, 0, 0, 0.50)".to_string()));
+        assert_eq!(hex_to_rgba("00000000"), Some("rgba(0, 0, 0, 0.00)".to_string()));
+        assert_eq!(hex_to_rgba("zzzzzzzz"), None);
+    }
+
+    #[test]
+    fn test_six_digit_hex_is_left_untouched() {
+        let css = ".test{color:#ff0000;}";
+        let targets = BrowserTargets::parse("ie 11");
+        let result = apply_browser_targets(css, &targets);
+        assert_eq!(result, css);
+    }
+}
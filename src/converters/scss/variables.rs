@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use crate::lexer::{self, TokenKind};
+
+/// Resolves `$name` variable references against the top-level
+/// `$name: value;` declarations found in `input`, then strips those
+/// declarations (they aren't valid CSS on their own) from the output.
+///
+/// Only top-level (depth 0, i.e. outside any `{ }` block) declarations are
+/// collected - a nested `$name: value;` is a local override that the rest
+/// of this primitive converter doesn't attempt to scope. A `!default`
+/// suffix only takes effect if the name hasn't already been set by an
+/// earlier (plain or `!default`) declaration, matching Sass's own
+/// first-wins semantics for that flag.
+pub fn substitute_variables(input: &str) -> String {
+    let vars = collect_top_level_variables(input);
+    if vars.is_empty() {
+        return input.to_string();
+    }
+    rewrite_with_variables(input, &vars)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Parses a `$name: value;` declaration starting at the `$` at `start`.
+/// Returns the name, the resolved value (with any `!default` suffix
+/// stripped), whether `!default` was present, and the index just past the
+/// terminating `;`.
+fn parse_variable_decl(input: &str, start: usize) -> Option<(String, String, bool, usize)> {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = start + 1; // skip '$'
+    let name_start = i;
+    while i < len && is_name_char(bytes[i] as char) {
+        i += 1;
+    }
+    if i == name_start {
+        return None;
+    }
+    let name = input[name_start..i].to_string();
+
+    let mut j = i;
+    while j < len && (bytes[j] as char).is_whitespace() {
+        j += 1;
+    }
+    if j >= len || bytes[j] != b':' {
+        return None;
+    }
+    j += 1;
+
+    let value_start = j;
+    while j < len && bytes[j] != b';' {
+        j += 1;
+    }
+    if j >= len {
+        return None;
+    }
+    let raw_value = input[value_start..j].trim();
+    let (value, is_default) = match raw_value.strip_suffix("!default") {
+        Some(v) => (v.trim().to_string(), true),
+        None => (raw_value.to_string(), false),
+    };
+    Some((name, value, is_default, j + 1))
+}
+
+fn collect_top_level_variables(input: &str) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = 0;
+    let mut depth = 0i32;
+
+    while i < len {
+        let c = bytes[i] as char;
+        if (c == '/' || c == '\'' || c == '"') && has_lexer_token(input, i, c) {
+            let token = lexer::first_token(&input[i..]);
+            i += token.len;
+            continue;
+        }
+        match c {
+            '{' => {
+                depth += 1;
+                i += 1;
+            }
+            '}' => {
+                depth -= 1;
+                i += 1;
+            }
+            '$' if depth == 0 => {
+                if let Some((name, value, is_default, end)) = parse_variable_decl(input, i) {
+                    match vars.get(&name) {
+                        Some(_) if is_default => {}
+                        _ => {
+                            vars.insert(name, value);
+                        }
+                    }
+                    i = end;
+                } else {
+                    i += 1;
+                }
+            }
+            _ => {
+                i += 1;
+            }
+        }
+    }
+
+    vars
+}
+
+/// Whether the char at `pos` actually starts a comment/string token (as
+/// opposed to e.g. a lone `/` that's just division-like text in this
+/// context).
+fn has_lexer_token(input: &str, pos: usize, c: char) -> bool {
+    let token = lexer::first_token(&input[pos..]);
+    match c {
+        '/' => matches!(
+            token.kind,
+            TokenKind::LineComment | TokenKind::BlockComment { .. }
+        ),
+        _ => matches!(
+            token.kind,
+            TokenKind::DoubleQuotedString { .. } | TokenKind::SingleQuotedString { .. }
+        ),
+    }
+}
+
+fn substitute_reference(
+    input: &str,
+    start: usize,
+    vars: &HashMap<String, String>,
+    out: &mut String,
+) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = start + 1; // skip '$'
+    let name_start = i;
+    while i < len && is_name_char(bytes[i] as char) {
+        i += 1;
+    }
+    if i == name_start {
+        return None;
+    }
+    let name = &input[name_start..i];
+    match vars.get(name) {
+        Some(value) => out.push_str(value),
+        None => {
+            eprintln!("[scss] Warning: undefined variable '${name}' referenced");
+            return None;
+        }
+    }
+    Some(i)
+}
+
+fn rewrite_with_variables(input: &str, vars: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(input.len());
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut i = 0;
+    let mut depth = 0i32;
+
+    while i < len {
+        let c = bytes[i] as char;
+        if (c == '/' || c == '\'' || c == '"') && has_lexer_token(input, i, c) {
+            let token = lexer::first_token(&input[i..]);
+            out.push_str(&input[i..i + token.len]);
+            i += token.len;
+            continue;
+        }
+        if c == '{' {
+            depth += 1;
+            out.push(c);
+            i += 1;
+        } else if c == '}' {
+            depth -= 1;
+            out.push(c);
+            i += 1;
+        } else if c == '$' && depth == 0 {
+            match parse_variable_decl(input, i) {
+                Some((_, _, _, end)) => i = end,
+                None => {
+                    out.push(c);
+                    i += 1;
+                }
+            }
+        } else if c == '$' {
+            if let Some(end) = substitute_reference(input, i, vars, &mut out) {
+                i = end;
+            } else {
+                out.push(c);
+                i += 1;
+            }
+        } else {
+            out.push(c);
+            i += 1;
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_input_unchanged_without_variables() {
+        let input = ".foo { color: red; }";
+        assert_eq!(substitute_variables(input), input);
+    }
+
+    #[test]
+    fn substitutes_a_simple_variable_and_drops_the_declaration() {
+        let input = "$brand: #336699;\n.foo { color: $brand; }";
+        let result = substitute_variables(input);
+        assert_eq!(result, "\n.foo { color: #336699; }");
+    }
+
+    #[test]
+    fn later_plain_declaration_overrides_an_earlier_one() {
+        let input = "$brand: red;\n$brand: blue;\n.foo { color: $brand; }";
+        let result = substitute_variables(input);
+        assert!(result.contains("color: blue;"));
+    }
+
+    #[test]
+    fn default_declaration_does_not_override_an_already_set_name() {
+        let input = "$brand: red;\n$brand: blue !default;\n.foo { color: $brand; }";
+        let result = substitute_variables(input);
+        assert!(result.contains("color: red;"));
+    }
+
+    #[test]
+    fn default_declaration_sets_the_value_when_not_already_set() {
+        let input = "$brand: blue !default;\n.foo { color: $brand; }";
+        let result = substitute_variables(input);
+        assert!(result.contains("color: blue;"));
+    }
+
+    #[test]
+    fn unknown_variable_reference_is_left_untouched() {
+        let input = "$brand: blue;\n.foo { color: $unknown; }";
+        let result = substitute_variables(input);
+        assert!(result.contains("color: $unknown;"));
+    }
+}
@@ -77,33 +77,26 @@ pub fn remove_as_casts(input: &str) -> String {
             continue;
         }
 
-        if i + 2 < len
-            && bytes[i].is_ascii_whitespace()
-            && input.get(i + 1..).is_some_and(|s| s.starts_with("as "))
-        {
-            // Found " as ": remove until a terminator character
-            i += 1 + 3; // skip space + "as "
+        if input.get(i..).is_some_and(|s| s.starts_with(" as ")) {
+            // Found " as ": remove the keyword and the type expression that
+            // follows it, up to a top-level terminator. Depth-tracks
+            // `{}`/`()`/`[]`/`<>` so a comma or brace inside a generic
+            // argument list or object type literal doesn't end the type
+            // expression early.
+            i += " as ".len();
+            let mut depth: i32 = 0;
             while i < len {
                 let ch = bytes[i] as char;
-                if ch == ')' || ch == ';' || ch == ',' || ch == '\n' || ch == '.' || ch == ']' {
-                    break;
+                match ch {
+                    '{' | '(' | '[' | '<' => depth += 1,
+                    '}' | ')' | ']' | '>' if depth > 0 => depth -= 1,
+                    ')' | '}' | ';' | ',' | '\n' | ']' if depth == 0 => break,
+                    _ => {}
                 }
                 i += 1;
             }
             continue; // do not copy the removed type
         }
-        // Handle "(ident as Type)" where there might not be leading space before 'as'
-        if i + 4 < len && input.get(i..).is_some_and(|s| s.starts_with(" as ")) {
-            i += 4;
-            while i < len {
-                let ch = bytes[i] as char;
-                if ch == ')' || ch == ';' || ch == ',' || ch == '\n' || ch == '.' || ch == ']' {
-                    break;
-                }
-                i += 1;
-            }
-            continue;
-        }
         push_char_from(input, &mut i, &mut out);
     }
 
@@ -128,4 +121,18 @@ mod tests {
         assert!(js.contains("(style).onload"));
         assert!(!js.contains("as HTMLLinkElement"));
     }
+
+    #[test]
+    fn removes_as_cast_with_generic_type_argument() {
+        let ts = "const x = y as Map<string, number>;";
+        let js = remove_as_casts(ts);
+        assert_eq!(js, "const x = y;");
+    }
+
+    #[test]
+    fn removes_as_cast_inside_object_literal() {
+        let ts = "const o = { x: y as Type };";
+        let js = remove_as_casts(ts);
+        assert_eq!(js, "const o = { x: y };");
+    }
 }
@@ -1,238 +1,101 @@
-use crate::converters::typescript::utils::{is_identifier_char, push_char_from};
-
-/// Represents the state of string and comment parsing
-#[allow(clippy::struct_excessive_bools)]
-struct ParseState {
-    in_single: bool,
-    in_double: bool,
-    in_backtick: bool,
-    in_line_comment: bool,
-    in_block_comment: bool,
-}
-
-impl ParseState {
-    fn new() -> Self {
-        Self {
-            in_single: false,
-            in_double: false,
-            in_backtick: false,
-            in_line_comment: false,
-            in_block_comment: false,
-        }
-    }
-
-    fn is_in_string(&self) -> bool {
-        self.in_single || self.in_double || self.in_backtick
-    }
-}
-
-/// Handles comment parsing and state updates
-fn handle_comments(
-    input: &str,
-    b: &[u8],
-    len: usize,
-    i: &mut usize,
-    c: char,
-    state: &mut ParseState,
-    out: &mut String,
-) -> bool {
-    // Handle exiting comments
-    if state.in_line_comment {
-        push_char_from(input, i, out);
-        if c == '\n' {
-            state.in_line_comment = false;
-        }
-        return true;
-    }
-    if state.in_block_comment {
-        push_char_from(input, i, out);
-        if c == '*' && *i < len && b[*i] as char == '/' {
-            out.push('/');
-            *i += 1;
-            state.in_block_comment = false;
-        }
-        return true;
-    }
-
-    // Enter comments when not in strings
-    if !state.is_in_string() && c == '/' && *i + 1 < len {
-        let n = b[*i + 1] as char;
-        if n == '/' {
-            state.in_line_comment = true;
-            out.push(c);
-            out.push(n);
-            *i += 2;
-            return true;
-        }
-        if n == '*' {
-            state.in_block_comment = true;
-            out.push(c);
-            out.push(n);
-            *i += 2;
-            return true;
+use crate::converters::typescript::tokenizer::{self, tokenize, Token, TokenKind};
+
+/// Finds the index just past the token closing the balanced `<...>` block
+/// starting at `tokens[start]` (which must be a `Punct('<')`), counting
+/// nested `<`/`>` punctuation tokens. Returns `None` if the block is never
+/// closed.
+fn find_generic_block_end(tokens: &[Token], start: usize) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut i = start;
+
+    while i < tokens.len() {
+        match tokens[i].kind {
+            TokenKind::Punct('<') => depth += 1,
+            TokenKind::Punct('>') => {
+                depth -= 1;
+                i += 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+                continue;
+            }
+            _ => {}
         }
+        i += 1;
     }
-    false
-}
 
-/// Handles string literal parsing and state updates
-fn handle_strings(
-    input: &str,
-    i: &mut usize,
-    c: char,
-    state: &mut ParseState,
-    out: &mut String,
-) -> bool {
-    if !state.in_double && !state.in_backtick && c == '\'' {
-        state.in_single = !state.in_single;
-        push_char_from(input, i, out);
-        return true;
-    }
-    if !state.in_single && !state.in_backtick && c == '"' {
-        state.in_double = !state.in_double;
-        push_char_from(input, i, out);
-        return true;
-    }
-    if !state.in_single && !state.in_double && c == '`' {
-        state.in_backtick = !state.in_backtick;
-        push_char_from(input, i, out);
-        return true;
-    }
-    false
+    None
 }
 
-/// Skips whitespace and returns the final position
-fn skip_whitespace(b: &[u8], len: usize, start: usize) -> usize {
-    let mut pos = start;
-    while pos < len && (b[pos] as char).is_ascii_whitespace() {
-        pos += 1;
+fn skip_whitespace(tokens: &[Token], start: usize) -> usize {
+    let mut i = start;
+    while i < tokens.len() && matches!(tokens[i].kind, TokenKind::Whitespace) {
+        i += 1;
     }
-    pos
+    i
 }
 
-/// Tries to parse and skip a balanced generic block, returns end position if valid
-fn try_parse_generic_block(b: &[u8], len: usize, start: usize) -> Option<usize> {
-    let mut pos = start;
-    let mut depth = 0;
-
-    while pos < len {
-        let ch = b[pos] as char;
-        if ch == '<' {
-            depth += 1;
-        } else if ch == '>' {
-            depth -= 1;
-            pos += 1;
-            if depth == 0 {
-                return Some(pos);
+pub fn remove_generics_before_calls(input: &str) -> String {
+    let tokens = tokenize(input);
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if let TokenKind::TemplateStr { terminated } = tokens[i].kind {
+            out.push('`');
+            for segment in tokenizer::split_template_segments(tokens[i].text) {
+                match segment {
+                    tokenizer::TemplateSegment::Text(text) => out.push_str(text),
+                    tokenizer::TemplateSegment::Interpolation(expr) => {
+                        out.push_str("${");
+                        out.push_str(&remove_generics_before_calls(expr));
+                        out.push('}');
+                    }
+                }
             }
+            if terminated {
+                out.push('`');
+            }
+            i += 1;
             continue;
         }
-        pos += 1;
-    }
-    None
-}
 
-/// Processes identifier and handles generic removal for function calls
-fn handle_identifier(
-    _input: &str,
-    bytes: &[u8],
-    length: usize,
-    position: &mut usize,
-    current_char: char,
-    output: &mut String,
-) -> bool {
-    // Detect start of identifier (and ensure previous is not identifier char)
-    if (current_char.is_ascii_alphabetic() || current_char == '_' || current_char == '$')
-        && (*position == 0 || !is_identifier_char(bytes[*position - 1] as char))
-    {
-        // Read identifier
-        let start_ident = *position;
-        *position += 1;
-        while *position < length && is_identifier_char(bytes[*position] as char) {
-            *position += 1;
-        }
-
-        // Copy identifier to output
-        if let Ok(ident_str) = std::str::from_utf8(&bytes[start_ident..*position]) {
-            output.push_str(ident_str);
+        if !matches!(tokens[i].kind, TokenKind::Ident) {
+            out.push_str(tokens[i].text);
+            i += 1;
+            continue;
         }
 
-        // Skip whitespace after identifier
-        let whitespace_end = skip_whitespace(bytes, length, *position);
+        out.push_str(tokens[i].text);
+        let after_ident = i + 1;
 
-        // If next is '<', try to parse generic and remove it only if next non-space after generic is '('
-        if whitespace_end < length && bytes[whitespace_end] as char == '<' {
-            if let Some(generic_end) = try_parse_generic_block(bytes, length, whitespace_end) {
-                // Check if next non-space character is '(' (function call)
-                let after_generic = skip_whitespace(bytes, length, generic_end);
-                if after_generic < length && bytes[after_generic] as char == '(' {
-                    // Drop the generic by advancing position to generic_end (after '>')
-                    *position = generic_end;
-                    return true;
-                }
-                // Not a call context, keep original including whitespace
-                if let Ok(orig) = std::str::from_utf8(&bytes[*position..generic_end]) {
-                    output.push_str(orig);
-                }
-                *position = generic_end;
-                return true;
-            }
-        }
-        return true;
-    }
-    false
-}
+        let before_generic = skip_whitespace(&tokens, after_ident);
+        let generic_end = if matches!(tokens.get(before_generic).map(|t| t.kind), Some(TokenKind::Punct('<'))) {
+            find_generic_block_end(&tokens, before_generic)
+        } else {
+            None
+        };
 
-pub fn remove_generics_before_calls(input: &str) -> String {
-    let mut output = String::with_capacity(input.len());
-    let mut position = 0;
-    let bytes = input.as_bytes();
-    let length = bytes.len();
-    let mut state = ParseState::new();
-
-    while position < length {
-        let current_char = bytes[position] as char;
-
-        // Handle comments first
-        if handle_comments(
-            input,
-            bytes,
-            length,
-            &mut position,
-            current_char,
-            &mut state,
-            &mut output,
-        ) {
-            continue;
-        }
-
-        // Handle strings
-        if handle_strings(input, &mut position, current_char, &mut state, &mut output) {
+        let Some(generic_end) = generic_end else {
+            i = after_ident;
             continue;
-        }
+        };
 
-        // If inside strings, just copy
-        if state.is_in_string() {
-            push_char_from(input, &mut position, &mut output);
-            continue;
-        }
+        let after_generic = skip_whitespace(&tokens, generic_end);
+        let is_call = matches!(tokens.get(after_generic).map(|t| t.kind), Some(TokenKind::Punct('(')));
 
-        // Handle identifiers with potential generics
-        if handle_identifier(
-            input,
-            bytes,
-            length,
-            &mut position,
-            current_char,
-            &mut output,
-        ) {
-            continue;
+        if !is_call {
+            // Not a call - keep the whitespace and generic block verbatim.
+            for token in &tokens[after_ident..generic_end] {
+                out.push_str(token.text);
+            }
         }
+        // A call: drop the whitespace and generic block entirely.
 
-        push_char_from(input, &mut position, &mut output);
+        i = generic_end;
     }
 
-    output
+    out
 }
 
 #[cfg(test)]
@@ -253,4 +116,53 @@ mod tests {
         let js = remove_generics_before_calls(ts);
         assert!(js.contains("Promise<void>"));
     }
+
+    #[test]
+    fn strips_generics_from_function_declarations() {
+        let ts = "function identity<T>(x) { return x; }";
+        let js = remove_generics_before_calls(ts);
+        assert_eq!(js, "function identity(x) { return x; }");
+    }
+
+    #[test]
+    fn strips_generics_with_nested_angle_brackets() {
+        let ts = "new Map<string, Array<number>>();";
+        let js = remove_generics_before_calls(ts);
+        assert_eq!(js, "new Map();");
+    }
+
+    #[test]
+    fn ignores_angle_brackets_inside_strings() {
+        let ts = "compare('a<b>c', x)";
+        let js = remove_generics_before_calls(ts);
+        assert_eq!(js, "compare('a<b>c', x)");
+    }
+
+    #[test]
+    fn ignores_angle_brackets_inside_comments() {
+        let ts = "call(/* a<b> */x)";
+        let js = remove_generics_before_calls(ts);
+        assert_eq!(js, "call(/* a<b> */x)");
+    }
+
+    #[test]
+    fn strips_generics_inside_template_interpolation() {
+        let ts = "const s = `computed: ${helper<number>(x)}`;";
+        let js = remove_generics_before_calls(ts);
+        assert_eq!(js, "const s = `computed: ${helper(x)}`;");
+    }
+
+    #[test]
+    fn leaves_nested_template_literal_interpolation_untouched() {
+        let ts = "const s = `outer ${`inner ${1 + 1}`}`;";
+        let js = remove_generics_before_calls(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn leaves_call_with_no_generics_untouched() {
+        let ts = "identity(x);";
+        let js = remove_generics_before_calls(ts);
+        assert_eq!(js, "identity(x);");
+    }
 }
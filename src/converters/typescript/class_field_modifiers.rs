@@ -0,0 +1,230 @@
+use crate::converters::typescript::utils::{is_identifier_char, push_char_from};
+
+/// Represents the state of string and comment parsing
+struct ParseState {
+    in_single: bool,
+    in_double: bool,
+    in_backtick: bool,
+    in_line_comment: bool,
+    in_block_comment: bool,
+}
+
+impl ParseState {
+    fn new() -> Self {
+        Self {
+            in_single: false,
+            in_double: false,
+            in_backtick: false,
+            in_line_comment: false,
+            in_block_comment: false,
+        }
+    }
+
+    fn is_in_string(&self) -> bool {
+        self.in_single || self.in_double || self.in_backtick
+    }
+}
+
+/// Handles comment parsing and state updates
+fn handle_comments(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &mut ParseState,
+    out: &mut String,
+) -> bool {
+    if state.in_line_comment {
+        push_char_from(input, i, out);
+        if c == '\n' {
+            state.in_line_comment = false;
+        }
+        return true;
+    }
+    if state.in_block_comment {
+        push_char_from(input, i, out);
+        if c == '*' && *i < len && bytes[*i] as char == '/' {
+            out.push('/');
+            *i += 1;
+            state.in_block_comment = false;
+        }
+        return true;
+    }
+    if !state.is_in_string() && c == '/' && *i + 1 < len {
+        let n = bytes[*i + 1] as char;
+        if n == '/' {
+            state.in_line_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+        if n == '*' {
+            state.in_block_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+    }
+    false
+}
+
+/// Handles string literal parsing and state updates
+fn handle_strings(input: &str, i: &mut usize, c: char, state: &mut ParseState, out: &mut String) -> bool {
+    if !state.in_double && !state.in_backtick && c == '\'' {
+        state.in_single = !state.in_single;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_backtick && c == '"' {
+        state.in_double = !state.in_double;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_double && c == '`' {
+        state.in_backtick = !state.in_backtick;
+        push_char_from(input, i, out);
+        return true;
+    }
+    false
+}
+
+const MODIFIERS: &[&str] = &["public", "private", "protected", "readonly"];
+
+/// Finds the modifier keyword starting at `i`, if any, requiring it be
+/// followed by whitespace so e.g. `privateKey` isn't mistaken for `private`.
+fn starts_with_modifier(input: &str, i: usize) -> Option<&'static str> {
+    MODIFIERS.iter().copied().find(|modifier| {
+        input.get(i..).is_some_and(|s| {
+            s.strip_prefix(modifier)
+                .is_some_and(|after| after.starts_with(char::is_whitespace))
+        })
+    })
+}
+
+/// A modifier only strips where it could actually start a class member
+/// declaration: right after `{`, `}`, `;`, the start of input, or another
+/// modifier/`static` already emitted - never, say, after a `:` inside a
+/// type annotation (`x: readonly string[]`), which is left for
+/// `remove_type_annotations` to delete wholesale.
+fn is_modifier_position(out: &str) -> bool {
+    let trimmed = out.trim_end();
+    if trimmed.is_empty() || trimmed.ends_with(['{', '}', ';']) {
+        return true;
+    }
+    let word_start = trimmed
+        .rfind(|c: char| !is_identifier_char(c))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let last_word = &trimmed[word_start..];
+    last_word == "static" || MODIFIERS.contains(&last_word)
+}
+
+/// Strips a leading class-member modifier at `i`, if one is there, along
+/// with the whitespace that follows it.
+fn handle_modifier(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &ParseState,
+    out: &str,
+) -> bool {
+    let preceded_by_identifier = *i > 0 && is_identifier_char(bytes[*i - 1] as char);
+    if state.is_in_string() || preceded_by_identifier || c != bytes[*i] as char {
+        return false;
+    }
+    let Some(modifier) = starts_with_modifier(input, *i) else {
+        return false;
+    };
+    if !is_modifier_position(out) {
+        return false;
+    }
+
+    *i += modifier.len();
+    while *i < len && (bytes[*i] as char).is_ascii_whitespace() {
+        *i += 1;
+    }
+    true
+}
+
+/// Drops `public`/`private`/`protected`/`readonly` modifiers from class
+/// field declarations, e.g. `private x: number;` -> `x: number;`.
+/// Constructor parameter properties (`constructor(private x: T)`) are
+/// handled separately by [`super::convert_parameter_properties`], which
+/// runs first.
+pub fn remove_class_field_modifiers(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut state = ParseState::new();
+
+    while i < len {
+        let c = bytes[i] as char;
+
+        if handle_comments(input, bytes, len, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+        if handle_strings(input, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+        if handle_modifier(input, bytes, len, &mut i, c, &state, &out) {
+            continue;
+        }
+
+        push_char_from(input, &mut i, &mut out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remove_class_field_modifiers;
+
+    #[test]
+    fn strips_single_modifier_from_class_field() {
+        let ts = "class Point { private x: number; }";
+        let js = remove_class_field_modifiers(ts);
+        assert_eq!(js, "class Point { x: number; }");
+    }
+
+    #[test]
+    fn strips_multiple_modifiers_in_declaration_order() {
+        let ts = "class Point { public static readonly origin: Point; }";
+        let js = remove_class_field_modifiers(ts);
+        assert_eq!(js, "class Point { static origin: Point; }");
+    }
+
+    #[test]
+    fn leaves_object_literal_property_named_like_a_modifier_untouched() {
+        let ts = "const o = { private: 1 };";
+        let js = remove_class_field_modifiers(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn leaves_readonly_array_type_inside_type_annotation_untouched() {
+        let ts = "class C { x: readonly string[]; }";
+        let js = remove_class_field_modifiers(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn keeps_modifier_word_in_strings() {
+        let ts = "console.log('private x');";
+        let js = remove_class_field_modifiers(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn does_not_mistake_privatekey_identifier_for_modifier() {
+        let ts = "class C { privateKey: string; }";
+        let js = remove_class_field_modifiers(ts);
+        assert_eq!(js, ts);
+    }
+}
@@ -0,0 +1,309 @@
+use crate::converters::typescript::utils::{is_identifier_char, push_char_from};
+
+/// Represents the state of string and comment parsing
+#[allow(clippy::struct_excessive_bools)]
+struct ParseState {
+    in_single: bool,
+    in_double: bool,
+    in_backtick: bool,
+    in_line_comment: bool,
+    in_block_comment: bool,
+}
+
+impl ParseState {
+    fn new() -> Self {
+        Self {
+            in_single: false,
+            in_double: false,
+            in_backtick: false,
+            in_line_comment: false,
+            in_block_comment: false,
+        }
+    }
+
+    fn is_in_string(&self) -> bool {
+        self.in_single || self.in_double || self.in_backtick
+    }
+}
+
+/// Handles comment parsing and state updates
+fn handle_comments(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &mut ParseState,
+    out: &mut String,
+) -> bool {
+    // Handle exiting comments
+    if state.in_line_comment {
+        push_char_from(input, i, out);
+        if c == '\n' {
+            state.in_line_comment = false;
+        }
+        return true;
+    }
+    if state.in_block_comment {
+        push_char_from(input, i, out);
+        if c == '*' && *i < len && bytes[*i] as char == '/' {
+            out.push('/');
+            *i += 1;
+            state.in_block_comment = false;
+        }
+        return true;
+    }
+
+    // Enter comments when not in strings
+    if !state.is_in_string() && c == '/' && *i + 1 < len {
+        let n = bytes[*i + 1] as char;
+        if n == '/' {
+            state.in_line_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+        if n == '*' {
+            state.in_block_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+    }
+    false
+}
+
+/// Handles string literal parsing and state updates
+fn handle_strings(
+    input: &str,
+    i: &mut usize,
+    c: char,
+    state: &mut ParseState,
+    out: &mut String,
+) -> bool {
+    if !state.in_double && !state.in_backtick && c == '\'' {
+        state.in_single = !state.in_single;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_backtick && c == '"' {
+        state.in_double = !state.in_double;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_double && c == '`' {
+        state.in_backtick = !state.in_backtick;
+        push_char_from(input, i, out);
+        return true;
+    }
+    false
+}
+
+/// Checks whether `word` starts at `i` and is followed by a non-identifier character,
+/// so e.g. matching `class` doesn't also match `classify`.
+fn starts_with_word(input: &str, i: usize, word: &str) -> bool {
+    input.get(i..).is_some_and(|s| {
+        s.starts_with(word)
+            && s[word.len()..]
+                .chars()
+                .next()
+                .is_some_and(|next| next.is_ascii_whitespace() || next == '<' || next == '{')
+    })
+}
+
+/// Copies whitespace characters from `input` to `out`
+fn copy_whitespace(input: &str, bytes: &[u8], len: usize, i: &mut usize, out: &mut String) {
+    while *i < len && (bytes[*i] as char).is_ascii_whitespace() {
+        push_char_from(input, i, out);
+    }
+}
+
+/// Copies identifier characters from `input` to `out`
+fn copy_identifier(input: &str, bytes: &[u8], len: usize, i: &mut usize, out: &mut String) {
+    while *i < len && is_identifier_char(bytes[*i] as char) {
+        push_char_from(input, i, out);
+    }
+}
+
+/// Attempts to skip a balanced `<...>` generic parameter list starting at a `<`.
+/// Bails out (returns `None`, leaving `i` untouched) if a `;`, `)` or newline is seen
+/// before the brackets balance, so a real `<`/`>` comparison or shift never gets eaten.
+fn try_skip_generic_with_bailout(bytes: &[u8], len: usize, start: usize) -> Option<usize> {
+    let mut pos = start;
+    let mut depth: i32 = 0;
+    while pos < len {
+        let ch = bytes[pos] as char;
+        match ch {
+            '<' => depth += 1,
+            '>' => {
+                depth -= 1;
+                pos += 1;
+                if depth == 0 {
+                    return Some(pos);
+                }
+                continue;
+            }
+            ';' | ')' | '\n' => return None,
+            _ => {}
+        }
+        pos += 1;
+    }
+    None
+}
+
+/// Drops a `<...>` generic parameter list at `i`, if one is present, advancing past it
+fn skip_generic_if_present(bytes: &[u8], len: usize, i: &mut usize) {
+    if *i < len && bytes[*i] as char == '<' {
+        if let Some(end) = try_skip_generic_with_bailout(bytes, len, *i) {
+            *i = end;
+        }
+    }
+}
+
+/// Drops an `implements X, Y` clause entirely, stopping right before the class body's `{`
+fn skip_implements_clause(bytes: &[u8], len: usize, i: &mut usize) {
+    *i += "implements".len();
+    let mut depth: i32 = 0;
+    while *i < len {
+        let ch = bytes[*i] as char;
+        match ch {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            '{' if depth <= 0 => return,
+            _ => {}
+        }
+        *i += 1;
+    }
+}
+
+/// Processes a `class Name<T> extends Base<T> implements X, Y {` header, dropping the
+/// generic parameter lists and the `implements` clause
+fn handle_class_header(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &ParseState,
+    out: &mut String,
+) -> bool {
+    if state.is_in_string() || c != 'c' || !starts_with_word(input, *i, "class") {
+        return false;
+    }
+
+    for _ in 0.."class".len() {
+        push_char_from(input, i, out);
+    }
+    copy_whitespace(input, bytes, len, i, out);
+    copy_identifier(input, bytes, len, i, out);
+    skip_generic_if_present(bytes, len, i);
+    copy_whitespace(input, bytes, len, i, out);
+
+    if starts_with_word(input, *i, "extends") {
+        for _ in 0.."extends".len() {
+            push_char_from(input, i, out);
+        }
+        copy_whitespace(input, bytes, len, i, out);
+        copy_identifier(input, bytes, len, i, out);
+        skip_generic_if_present(bytes, len, i);
+        copy_whitespace(input, bytes, len, i, out);
+    }
+
+    if starts_with_word(input, *i, "implements") {
+        skip_implements_clause(bytes, len, i);
+        if !out.ends_with(' ') && !out.ends_with('\n') {
+            out.push(' ');
+        }
+    }
+
+    true
+}
+
+/// Removes generic type-parameter lists (`<T>`) from `class` and `extends` clauses and
+/// drops `implements X, Y` clauses entirely, e.g. turning
+/// `class Box<T> extends Container<T> implements Comparable<T> {` into
+/// `class Box extends Container {`.
+pub fn simplify_class_headers(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut state = ParseState::new();
+
+    while i < len {
+        let c = bytes[i] as char;
+
+        if handle_comments(input, bytes, len, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+
+        if handle_strings(input, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+
+        if handle_class_header(input, bytes, len, &mut i, c, &state, &mut out) {
+            continue;
+        }
+
+        push_char_from(input, &mut i, &mut out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::simplify_class_headers;
+
+    #[test]
+    fn strips_class_generic_type_parameters() {
+        let ts = "class Box<T> { get(): T { return this.value; } }";
+        let js = simplify_class_headers(ts);
+        assert!(js.contains("class Box {"));
+        assert!(!js.contains("<T>"));
+    }
+
+    #[test]
+    fn strips_extends_generic_argument() {
+        let ts = "class Box<T> extends Container<T> {}";
+        let js = simplify_class_headers(ts);
+        assert!(js.contains("class Box extends Container {}"));
+    }
+
+    #[test]
+    fn strips_nested_generic_bounds() {
+        let ts = "class Box<T extends Comparable<T>> {}";
+        let js = simplify_class_headers(ts);
+        assert_eq!(js, "class Box {}");
+    }
+
+    #[test]
+    fn removes_implements_clause() {
+        let ts = "class Foo implements Bar, Baz {}";
+        let js = simplify_class_headers(ts);
+        assert_eq!(js, "class Foo {}");
+    }
+
+    #[test]
+    fn removes_implements_clause_with_generic_interface() {
+        let ts = "class Foo<T> implements Comparable<T>, Serializable {}";
+        let js = simplify_class_headers(ts);
+        assert_eq!(js, "class Foo {}");
+    }
+
+    #[test]
+    fn keeps_class_keyword_in_strings() {
+        let ts = "console.log('class Foo<T> implements Bar {}');";
+        let js = simplify_class_headers(ts);
+        assert!(js.contains("'class Foo<T> implements Bar {}'"));
+    }
+
+    #[test]
+    fn leaves_plain_class_untouched() {
+        let ts = "class Foo { constructor() {} }";
+        let js = simplify_class_headers(ts);
+        assert_eq!(js, ts);
+    }
+}
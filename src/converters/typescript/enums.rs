@@ -0,0 +1,309 @@
+use crate::converters::typescript::utils::{is_identifier_char, push_char_from, split_top_level_commas};
+
+/// Represents the state of string and comment parsing
+struct ParseState {
+    in_single: bool,
+    in_double: bool,
+    in_backtick: bool,
+    in_line_comment: bool,
+    in_block_comment: bool,
+}
+
+impl ParseState {
+    fn new() -> Self {
+        Self {
+            in_single: false,
+            in_double: false,
+            in_backtick: false,
+            in_line_comment: false,
+            in_block_comment: false,
+        }
+    }
+
+    fn is_in_string(&self) -> bool {
+        self.in_single || self.in_double || self.in_backtick
+    }
+}
+
+/// Handles comment parsing and state updates
+fn handle_comments(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &mut ParseState,
+    out: &mut String,
+) -> bool {
+    if state.in_line_comment {
+        push_char_from(input, i, out);
+        if c == '\n' {
+            state.in_line_comment = false;
+        }
+        return true;
+    }
+    if state.in_block_comment {
+        push_char_from(input, i, out);
+        if c == '*' && *i < len && bytes[*i] as char == '/' {
+            out.push('/');
+            *i += 1;
+            state.in_block_comment = false;
+        }
+        return true;
+    }
+    if !state.is_in_string() && c == '/' && *i + 1 < len {
+        let n = bytes[*i + 1] as char;
+        if n == '/' {
+            state.in_line_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+        if n == '*' {
+            state.in_block_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+    }
+    false
+}
+
+/// Handles string literal parsing and state updates
+fn handle_strings(input: &str, i: &mut usize, c: char, state: &mut ParseState, out: &mut String) -> bool {
+    if !state.in_double && !state.in_backtick && c == '\'' {
+        state.in_single = !state.in_single;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_backtick && c == '"' {
+        state.in_double = !state.in_double;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_double && c == '`' {
+        state.in_backtick = !state.in_backtick;
+        push_char_from(input, i, out);
+        return true;
+    }
+    false
+}
+
+fn starts_with_enum_keyword(input: &str, i: usize) -> bool {
+    input.get(i..).is_some_and(|s| s.starts_with("enum "))
+        || input.get(i..).is_some_and(|s| s.starts_with("enum\t"))
+}
+
+/// Removes a `const` keyword already written to `out` (with whitespace after
+/// it but nothing else) if one is there. `const enum` inlining is out of
+/// scope, so a `const enum` is lowered exactly like a regular `enum` and the
+/// leading `const` keyword is simply dropped.
+fn strip_preceding_const_keyword(out: &mut String) {
+    let trimmed_end = out.trim_end().len();
+    if trimmed_end < "const".len() || &out[trimmed_end - "const".len()..trimmed_end] != "const" {
+        return;
+    }
+    let const_start = trimmed_end - "const".len();
+    let preceded_by_identifier =
+        const_start > 0 && is_identifier_char(out.as_bytes()[const_start - 1] as char);
+    if preceded_by_identifier {
+        return;
+    }
+    out.truncate(const_start);
+}
+
+fn skip_whitespace(bytes: &[u8], len: usize, i: &mut usize) {
+    while *i < len && (bytes[*i] as char).is_ascii_whitespace() {
+        *i += 1;
+    }
+}
+
+fn skip_identifier(bytes: &[u8], len: usize, i: &mut usize) -> usize {
+    let start = *i;
+    while *i < len && is_identifier_char(bytes[*i] as char) {
+        *i += 1;
+    }
+    *i - start
+}
+
+/// Finds the `}` matching the `{` at `start` (which must point at `{`).
+fn find_matching_brace(bytes: &[u8], len: usize, start: usize) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut i = start;
+    while i < len {
+        match bytes[i] as char {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Builds the runtime object a TypeScript compiler emits for an enum body:
+/// `var Name; (function (Name) { Name[Name["A"] = 0] = "A"; ... })(Name || (Name = {}));`.
+/// Members without an explicit initializer get the next integer after the
+/// last explicit numeric value, starting at 0. Numeric members get a reverse
+/// mapping (`Name[Name["A"] = 0] = "A";`); string-initialized members only
+/// get the forward mapping (`Name["A"] = "x";`), matching real TS codegen.
+fn build_enum_object(name: &str, body: &str) -> String {
+    let mut next_value: i64 = 0;
+    let mut statements = Vec::new();
+
+    for raw_member in split_top_level_commas(body) {
+        let member = raw_member.trim();
+        if member.is_empty() {
+            continue;
+        }
+        if let Some((member_name, value)) = member.split_once('=') {
+            let member_name = member_name.trim();
+            let value = value.trim();
+            if let Ok(n) = value.parse::<i64>() {
+                statements.push(format!("{name}[{name}[\"{member_name}\"] = {n}] = \"{member_name}\";"));
+                next_value = n + 1;
+            } else {
+                statements.push(format!("{name}[\"{member_name}\"] = {value};"));
+            }
+        } else {
+            statements.push(format!(
+                "{name}[{name}[\"{member}\"] = {next_value}] = \"{member}\";"
+            ));
+            next_value += 1;
+        }
+    }
+
+    format!(
+        "var {name}; (function ({name}) {{ {} }})({name} || ({name} = {{}}));",
+        statements.join(" ")
+    )
+}
+
+/// Converts an `enum Name { ... }` declaration starting at `i` into its
+/// equivalent runtime object, if one is there. A preceding `const` keyword
+/// (from `const enum Name { ... }`) is dropped, since inlining `const enum`
+/// is out of scope and it's otherwise lowered exactly like a regular enum.
+fn handle_enum(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &ParseState,
+    out: &mut String,
+) -> bool {
+    let preceded_by_identifier = *i > 0 && is_identifier_char(bytes[*i - 1] as char);
+    if state.is_in_string() || preceded_by_identifier || c != 'e' || !starts_with_enum_keyword(input, *i) {
+        return false;
+    }
+
+    let mut cursor = *i + "enum".len();
+    skip_whitespace(bytes, len, &mut cursor);
+    let name_start = cursor;
+    let name_len = skip_identifier(bytes, len, &mut cursor);
+    if name_len == 0 {
+        return false;
+    }
+    let name = &input[name_start..cursor];
+    skip_whitespace(bytes, len, &mut cursor);
+
+    if cursor >= len || bytes[cursor] as char != '{' {
+        return false;
+    }
+    let Some(close) = find_matching_brace(bytes, len, cursor) else {
+        return false;
+    };
+
+    let body = &input[cursor + 1..close];
+    strip_preceding_const_keyword(out);
+    out.push_str(&build_enum_object(name, body));
+    *i = close + 1;
+    true
+}
+
+/// Converts TypeScript `enum` declarations into the runtime object a TS
+/// compiler would emit; JavaScript has no native enum construct.
+pub fn convert_enums(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut state = ParseState::new();
+
+    while i < len {
+        let c = bytes[i] as char;
+
+        if handle_comments(input, bytes, len, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+        if handle_strings(input, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+        if handle_enum(input, bytes, len, &mut i, c, &state, &mut out) {
+            continue;
+        }
+
+        push_char_from(input, &mut i, &mut out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert_enums;
+
+    #[test]
+    fn converts_auto_incrementing_enum() {
+        let ts = "enum Direction { Up, Down, Left, Right }";
+        let js = convert_enums(ts);
+        assert_eq!(
+            js,
+            "var Direction; (function (Direction) { Direction[Direction[\"Up\"] = 0] = \"Up\"; Direction[Direction[\"Down\"] = 1] = \"Down\"; Direction[Direction[\"Left\"] = 2] = \"Left\"; Direction[Direction[\"Right\"] = 3] = \"Right\"; })(Direction || (Direction = {}));"
+        );
+    }
+
+    #[test]
+    fn converts_enum_with_explicit_values() {
+        let ts = "enum E { A, B = 2, C }";
+        let js = convert_enums(ts);
+        assert_eq!(
+            js,
+            "var E; (function (E) { E[E[\"A\"] = 0] = \"A\"; E[E[\"B\"] = 2] = \"B\"; E[E[\"C\"] = 3] = \"C\"; })(E || (E = {}));"
+        );
+    }
+
+    #[test]
+    fn converts_string_enum_without_reverse_mapping() {
+        let ts = r#"enum Color { Red = "red", Blue = "blue" }"#;
+        let js = convert_enums(ts);
+        assert_eq!(
+            js,
+            "var Color; (function (Color) { Color[\"Red\"] = \"red\"; Color[\"Blue\"] = \"blue\"; })(Color || (Color = {}));"
+        );
+    }
+
+    #[test]
+    fn keeps_enum_word_in_strings() {
+        let ts = "console.log('enum Foo { A }');";
+        let js = convert_enums(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn const_enum_is_treated_as_a_regular_enum() {
+        let ts = "const enum E { A, B }";
+        let js = convert_enums(ts);
+        assert_eq!(
+            js,
+            "var E; (function (E) { E[E[\"A\"] = 0] = \"A\"; E[E[\"B\"] = 1] = \"B\"; })(E || (E = {}));"
+        );
+    }
+}
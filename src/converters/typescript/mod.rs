@@ -2,50 +2,100 @@ mod is_identifier_char;
 
 mod as_casts;
 mod call_generics;
+mod class_field_modifiers;
+mod class_headers;
+mod enums;
 mod interface_blocks;
+mod parameter_properties;
 mod postfix_non_null;
 mod query_selector_generics;
+mod satisfies;
+mod source_map;
+pub(crate) mod tokenizer;
+mod type_aliases;
 mod type_annotations;
+mod type_only_statements;
 mod utils;
 
 use as_casts::remove_as_casts;
 use call_generics::remove_generics_before_calls;
+use class_field_modifiers::remove_class_field_modifiers;
+use class_headers::simplify_class_headers;
+use enums::convert_enums;
 use interface_blocks::remove_interface_blocks;
+use parameter_properties::convert_parameter_properties;
 use postfix_non_null::remove_postfix_non_null;
 use query_selector_generics::remove_query_selector_generics;
+use satisfies::remove_satisfies;
+pub use source_map::{strip_typescript_types_with_inline_source_map, strip_typescript_types_with_source_map};
+use type_aliases::remove_type_aliases;
 use type_annotations::remove_type_annotations;
+use type_only_statements::elide_type_declarations;
 use crate::traits::AssetConverter;
 use crate::error::Result;
 use std::path::Path;
 
-/// Minimal TypeScript-to-JavaScript stripper tailored for constructs used in router.ts.
-/// This does not fully parse TS; it heuristically removes:
+/// Multi-stage TypeScript-to-JavaScript stripper tailored for constructs used in router.ts.
+/// This does not fully parse TS; it heuristically removes, in order:
 /// - `interface ... { ... }` blocks
+/// - `type Name = ...;` alias statements
+/// - `declare ...` statements and `import type`/`export type` statements
+/// - `enum Name { ... }` declarations, converted to the runtime object a TS compiler
+///   would emit (`const enum` is lowered the same way; inlining it is out of scope)
 /// - Generic annotations after `querySelector`/`querySelectorAll`, e.g. `<HTMLElement>`
 /// - Generic arguments after identifiers when directly followed by a call, e.g. `Promise<void>(...)`
+/// - Generic type-parameter lists and `implements` clauses on `class` headers, e.g.
+///   `class Box<T> extends Container<T> implements Comparable<T> {`
+/// - `as Type` casts (e.g., `(style as HTMLLinkElement)` -> `(style)`)
+/// - `satisfies Type` expressions
+/// - Constructor parameter properties (`constructor(private x: T)`), converted to a
+///   plain parameter plus a `this.x = x;` assignment in the constructor body
+/// - `public`/`private`/`protected`/`readonly` modifiers on class field declarations,
+///   e.g. `private x: number;` -> `x: number;`
 /// - Parameter and return type annotations in functions and arrow functions
 /// - Variable type annotations in `const`/`let`/`var` declarations
-/// - `as Type` casts (e.g., `(style as HTMLLinkElement)` -> `(style)`)
 /// - Postfix non-null assertions like `value!` or `call()`
-///
-/// It intentionally does NOT implement enums or other TS features.
 pub fn strip_typescript_types(input: &str) -> String {
     let without_interfaces = remove_interface_blocks(input);
-    let without_generics = remove_query_selector_generics(&without_interfaces);
+    let without_type_aliases = remove_type_aliases(&without_interfaces);
+    let without_type_only_statements = elide_type_declarations(&without_type_aliases);
+    let without_enums = convert_enums(&without_type_only_statements);
+    let without_class_headers = simplify_class_headers(&without_enums);
+    let without_generics = remove_query_selector_generics(&without_class_headers);
     let without_call_generics = remove_generics_before_calls(&without_generics);
     let without_casts = remove_as_casts(&without_call_generics);
-    let without_types = remove_type_annotations(&without_casts);
-    
+    let without_satisfies = remove_satisfies(&without_casts);
+    let without_parameter_properties = convert_parameter_properties(&without_satisfies);
+    let without_field_modifiers = remove_class_field_modifiers(&without_parameter_properties);
+    let without_types = remove_type_annotations(&without_field_modifiers);
+
     remove_postfix_non_null(&without_types)
 }
 
 /// TypeScript to JavaScript converter implementation
-pub struct TypeScriptConverter;
+pub struct TypeScriptConverter {
+    /// When set (see [`Self::with_source_maps`]), `convert` appends an
+    /// inline Source Map v3 `data:` URI comment using this as the map's
+    /// `sources` entry, via [`strip_typescript_types_with_inline_source_map`].
+    source_name: Option<String>,
+}
 
 impl TypeScriptConverter {
     /// Create a new TypeScript converter
     pub fn new() -> Self {
-        Self
+        Self { source_name: None }
+    }
+
+    /// Create a TypeScript converter that appends an inline Source Map v3
+    /// `data:` URI comment to its output, pointing back at `source_name`
+    /// (typically the original `.ts` file's name). Unlike `file_copier`'s
+    /// pipeline, this trait's `convert` can only return a single `String`,
+    /// so there's no sibling `.map` file to write - the map travels with
+    /// the output instead.
+    pub fn with_source_maps(source_name: impl Into<String>) -> Self {
+        Self {
+            source_name: Some(source_name.into()),
+        }
     }
 }
 
@@ -58,7 +108,10 @@ impl Default for TypeScriptConverter {
 impl AssetConverter for TypeScriptConverter {
     fn convert(&self, input: &str, _source_path: Option<&Path>) -> Result<String> {
         // TypeScript conversion doesn't need the source path
-        Ok(strip_typescript_types(input))
+        Ok(match &self.source_name {
+            Some(source_name) => strip_typescript_types_with_inline_source_map(input, source_name),
+            None => strip_typescript_types(input),
+        })
     }
 
     fn supported_extensions(&self) -> Vec<&str> {
@@ -102,4 +155,14 @@ mod trait_tests {
         // Should remove generic type parameter
         assert_eq!(result, "document.querySelector('.test')");
     }
+
+    #[test]
+    fn test_typescript_converter_with_source_maps_appends_source_mapping_url() {
+        let converter = TypeScriptConverter::with_source_maps("router.ts");
+        let input = "const a: number = 1;";
+        let result = converter.convert(input, None).expect("Conversion failed");
+
+        assert!(result.starts_with("const a = 1;\n"));
+        assert!(result.contains("//# sourceMappingURL=data:application/json;charset=utf-8;base64,"));
+    }
 }
@@ -0,0 +1,310 @@
+use crate::converters::typescript::utils::{is_identifier_char, push_char_from, split_top_level_commas};
+
+/// Represents the state of string and comment parsing
+struct ParseState {
+    in_single: bool,
+    in_double: bool,
+    in_backtick: bool,
+    in_line_comment: bool,
+    in_block_comment: bool,
+}
+
+impl ParseState {
+    fn new() -> Self {
+        Self {
+            in_single: false,
+            in_double: false,
+            in_backtick: false,
+            in_line_comment: false,
+            in_block_comment: false,
+        }
+    }
+
+    fn is_in_string(&self) -> bool {
+        self.in_single || self.in_double || self.in_backtick
+    }
+}
+
+/// Handles comment parsing and state updates
+fn handle_comments(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &mut ParseState,
+    out: &mut String,
+) -> bool {
+    if state.in_line_comment {
+        push_char_from(input, i, out);
+        if c == '\n' {
+            state.in_line_comment = false;
+        }
+        return true;
+    }
+    if state.in_block_comment {
+        push_char_from(input, i, out);
+        if c == '*' && *i < len && bytes[*i] as char == '/' {
+            out.push('/');
+            *i += 1;
+            state.in_block_comment = false;
+        }
+        return true;
+    }
+    if !state.is_in_string() && c == '/' && *i + 1 < len {
+        let n = bytes[*i + 1] as char;
+        if n == '/' {
+            state.in_line_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+        if n == '*' {
+            state.in_block_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+    }
+    false
+}
+
+/// Handles string literal parsing and state updates
+fn handle_strings(input: &str, i: &mut usize, c: char, state: &mut ParseState, out: &mut String) -> bool {
+    if !state.in_double && !state.in_backtick && c == '\'' {
+        state.in_single = !state.in_single;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_backtick && c == '"' {
+        state.in_double = !state.in_double;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_double && c == '`' {
+        state.in_backtick = !state.in_backtick;
+        push_char_from(input, i, out);
+        return true;
+    }
+    false
+}
+
+fn starts_with_constructor_keyword(input: &str, i: usize) -> bool {
+    input.get(i..).is_some_and(|s| {
+        s.strip_prefix("constructor")
+            .is_some_and(|after| after.starts_with(|c: char| c == '(' || c.is_whitespace()))
+    })
+}
+
+fn skip_whitespace(bytes: &[u8], len: usize, i: &mut usize) {
+    while *i < len && (bytes[*i] as char).is_ascii_whitespace() {
+        *i += 1;
+    }
+}
+
+/// Finds the `)` matching the `(` at `start`, skipping over nested
+/// `()`/`{}`/`[]` and string literals.
+fn find_matching_paren(bytes: &[u8], len: usize, start: usize) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut in_backtick = false;
+    let mut i = start;
+
+    while i < len {
+        let c = bytes[i] as char;
+        if in_single {
+            if c == '\'' {
+                in_single = false;
+            }
+        } else if in_double {
+            if c == '"' {
+                in_double = false;
+            }
+        } else if in_backtick {
+            if c == '`' {
+                in_backtick = false;
+            }
+        } else {
+            match c {
+                '\'' => in_single = true,
+                '"' => in_double = true,
+                '`' => in_backtick = true,
+                '(' | '{' | '[' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                '}' | ']' => depth -= 1,
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+const MODIFIERS: &[&str] = &["public", "private", "protected", "readonly"];
+
+/// Strips leading `public`/`private`/`protected`/`readonly` modifiers from a
+/// parameter, returning the cleaned parameter text and whether it was a
+/// parameter property (had at least one modifier).
+fn strip_modifiers(param: &str) -> (String, bool) {
+    let mut rest = param.trim();
+    let mut had_modifier = false;
+
+    loop {
+        let Some(matched) = MODIFIERS.iter().find(|m| {
+            rest.strip_prefix(**m)
+                .is_some_and(|after| after.starts_with(char::is_whitespace))
+        }) else {
+            break;
+        };
+        rest = rest[matched.len()..].trim_start();
+        had_modifier = true;
+    }
+
+    (rest.to_string(), had_modifier)
+}
+
+/// Extracts the bare identifier name a (possibly typed/defaulted) parameter
+/// binds, e.g. `"x: string = 1"` -> `"x"`.
+fn parameter_identifier(param: &str) -> &str {
+    let end = param
+        .find(|c: char| matches!(c, ':' | '=' | '?'))
+        .unwrap_or(param.len());
+    param[..end].trim()
+}
+
+/// Rewrites a `constructor(...)` parameter list: strips TypeScript parameter
+/// property modifiers, and returns the cleaned parameter list plus the
+/// `this.x = x;` assignments to inject at the top of the constructor body.
+fn rewrite_constructor_params(raw_params: &str) -> (String, String) {
+    let mut cleaned_params = Vec::new();
+    let mut assignments = String::new();
+
+    for raw_param in split_top_level_commas(raw_params) {
+        let trimmed = raw_param.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let (cleaned, is_property) = strip_modifiers(trimmed);
+        if is_property {
+            let name = parameter_identifier(&cleaned);
+            assignments.push_str("this.");
+            assignments.push_str(name);
+            assignments.push_str(" = ");
+            assignments.push_str(name);
+            assignments.push_str("; ");
+        }
+        cleaned_params.push(cleaned);
+    }
+
+    (cleaned_params.join(", "), assignments)
+}
+
+/// Translates TypeScript constructor parameter properties
+/// (`constructor(private x: T)`) into a plain parameter plus a `this.x = x;`
+/// assignment injected at the top of the constructor body, the way compiled
+/// TypeScript does.
+pub fn convert_parameter_properties(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut state = ParseState::new();
+
+    while i < len {
+        let c = bytes[i] as char;
+
+        if handle_comments(input, bytes, len, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+        if handle_strings(input, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+
+        let preceded_by_identifier = i > 0 && is_identifier_char(bytes[i - 1] as char);
+        if !state.is_in_string()
+            && c == 'c'
+            && !preceded_by_identifier
+            && starts_with_constructor_keyword(input, i)
+        {
+            let mut cursor = i + "constructor".len();
+            skip_whitespace(bytes, len, &mut cursor);
+            if cursor < len && bytes[cursor] as char == '(' {
+                if let Some(close) = find_matching_paren(bytes, len, cursor) {
+                    let raw_params = &input[cursor + 1..close];
+                    let (cleaned_params, assignments) = rewrite_constructor_params(raw_params);
+
+                    let mut after_close = close + 1;
+                    skip_whitespace(bytes, len, &mut after_close);
+                    if !assignments.is_empty() && after_close < len && bytes[after_close] as char == '{' {
+                        let mut body_start = after_close + 1;
+                        skip_whitespace(bytes, len, &mut body_start);
+                        out.push_str("constructor(");
+                        out.push_str(&cleaned_params);
+                        out.push_str(") { ");
+                        out.push_str(assignments.trim_end());
+                        out.push(' ');
+                        i = body_start;
+                        continue;
+                    } else if assignments.is_empty() {
+                        out.push_str("constructor(");
+                        out.push_str(&cleaned_params);
+                        out.push(')');
+                        i = close + 1;
+                        continue;
+                    }
+                }
+            }
+        }
+
+        push_char_from(input, &mut i, &mut out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert_parameter_properties;
+
+    #[test]
+    fn converts_single_parameter_property() {
+        let ts = "class Point { constructor(private x: number) {} }";
+        let js = convert_parameter_properties(ts);
+        assert_eq!(
+            js,
+            "class Point { constructor(x: number) { this.x = x; } }"
+        );
+    }
+
+    #[test]
+    fn converts_multiple_parameter_properties_mixing_plain_params() {
+        let ts = "class P { constructor(public x: number, y: number, readonly z = 1) { doStuff(); } }";
+        let js = convert_parameter_properties(ts);
+        assert_eq!(
+            js,
+            "class P { constructor(x: number, y: number, z = 1) { this.x = x; this.z = z; doStuff(); } }"
+        );
+    }
+
+    #[test]
+    fn leaves_constructors_without_modifiers_unchanged() {
+        let ts = "class P { constructor(x: number) { this.x = x; } }";
+        let js = convert_parameter_properties(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn keeps_constructor_word_in_strings() {
+        let ts = "console.log('constructor(private x) {}');";
+        let js = convert_parameter_properties(ts);
+        assert_eq!(js, ts);
+    }
+}
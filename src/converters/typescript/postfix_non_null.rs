@@ -23,7 +23,12 @@ pub fn remove_postfix_non_null(input: &str) -> String {
             }
             let next_char = if next_index < length { bytes[next_index] as char } else { '\0' };
 
-            let prev_allows_postfix = prev_char == ')' || prev_char == ']' || is_identifier_char(prev_char);
+            let prev_allows_postfix = prev_char == ')'
+                || prev_char == ']'
+                || prev_char == '"'
+                || prev_char == '\''
+                || prev_char == '`'
+                || is_identifier_char(prev_char);
             let next_is_terminator = next_char == '.'
                 || next_char == ';'
                 || next_char == ','
@@ -60,4 +65,18 @@ mod tests {
         let js = remove_postfix_non_null(ts);
         assert_eq!(js, "x.y");
     }
+
+    #[test]
+    fn removes_postfix_non_null_after_string_literal() {
+        let ts = "const len = 'literal'!.length;";
+        let js = remove_postfix_non_null(ts);
+        assert_eq!(js, "const len = 'literal'.length;");
+    }
+
+    #[test]
+    fn keeps_not_equal_operator_untouched() {
+        let ts = "if (x !== null) { y; }";
+        let js = remove_postfix_non_null(ts);
+        assert_eq!(js, ts);
+    }
 }
@@ -0,0 +1,129 @@
+use crate::converters::typescript::utils::push_char_from;
+
+/// Removes ` satisfies Type` expressions; `satisfies` is a type-checking-only
+/// operator and has no JavaScript runtime equivalent.
+pub fn remove_satisfies(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut in_backtick = false;
+    let mut in_line_comment = false;
+    let mut in_block_comment = false;
+
+    while i < len {
+        let c = bytes[i] as char;
+
+        if in_line_comment {
+            push_char_from(input, &mut i, &mut out);
+            if c == '\n' {
+                in_line_comment = false;
+            }
+            continue;
+        }
+        if in_block_comment {
+            push_char_from(input, &mut i, &mut out);
+            if c == '*' && i < len && bytes[i] as char == '/' {
+                out.push('/');
+                i += 1;
+                in_block_comment = false;
+            }
+            continue;
+        }
+
+        if !in_single && !in_double && !in_backtick && c == '/' && i + 1 < len {
+            let n = bytes[i + 1] as char;
+            if n == '/' {
+                in_line_comment = true;
+                out.push(c);
+                out.push(n);
+                i += 2;
+                continue;
+            }
+            if n == '*' {
+                in_block_comment = true;
+                out.push(c);
+                out.push(n);
+                i += 2;
+                continue;
+            }
+        }
+
+        if !in_double && !in_backtick && c == '\'' {
+            in_single = !in_single;
+            push_char_from(input, &mut i, &mut out);
+            continue;
+        }
+        if !in_single && !in_backtick && c == '"' {
+            in_double = !in_double;
+            push_char_from(input, &mut i, &mut out);
+            continue;
+        }
+        if !in_single && !in_double && c == '`' {
+            in_backtick = !in_backtick;
+            push_char_from(input, &mut i, &mut out);
+            continue;
+        }
+
+        if in_single || in_double || in_backtick {
+            push_char_from(input, &mut i, &mut out);
+            continue;
+        }
+
+        if input.get(i..).is_some_and(|s| s.starts_with(" satisfies ")) {
+            i += " satisfies ".len();
+            let mut depth: i32 = 0;
+            while i < len {
+                let ch = bytes[i] as char;
+                match ch {
+                    '{' | '(' | '[' | '<' => depth += 1,
+                    '}' | ')' | ']' | '>' if depth > 0 => depth -= 1,
+                    ')' | '}' | ';' | ',' | '\n' | ']' if depth == 0 => break,
+                    _ => {}
+                }
+                i += 1;
+            }
+            continue;
+        }
+
+        push_char_from(input, &mut i, &mut out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remove_satisfies;
+
+    #[test]
+    fn removes_satisfies_expression() {
+        let ts = "const config = { a: 1 } satisfies Config;";
+        let js = remove_satisfies(ts);
+        assert_eq!(js, "const config = { a: 1 };");
+    }
+
+    #[test]
+    fn removes_satisfies_inside_call_arguments() {
+        let ts = "render({ a: 1 } satisfies Props, target);";
+        let js = remove_satisfies(ts);
+        assert_eq!(js, "render({ a: 1 }, target);");
+    }
+
+    #[test]
+    fn keeps_satisfies_word_in_strings() {
+        let ts = "console.log('a satisfies b');";
+        let js = remove_satisfies(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn removes_satisfies_inside_wrapping_object_literal() {
+        let ts = "const o = { config: x satisfies Schema };";
+        let js = remove_satisfies(ts);
+        assert_eq!(js, "const o = { config: x };");
+    }
+}
@@ -0,0 +1,194 @@
+//! Line-granular Source Map v3 support for [`super::strip_typescript_types`],
+//! matching the "one segment per output line" contract already used by
+//! [`crate::minifier::css::minify_css_with_source_map`] and
+//! [`crate::minifier::js::minify_js_with_source_map`]. Those minifiers
+//! thread a running `source_line` counter through a single pass over the
+//! input; the TypeScript stripper is instead a pipeline of whole-string
+//! transforms (see [`super::strip_typescript_types`]) with no single point
+//! to thread a counter through, so the mapping here is built after the
+//! fact by aligning the stripped output's lines back to the original
+//! input's lines, which is possible because no stage reorders lines or
+//! rewrites one beyond recognition - they only delete spans or whole
+//! lines.
+
+use super::strip_typescript_types;
+use crate::minifier::source_map::build_source_map_json;
+
+/// Aligns `output_lines` to `source_lines` with a line-level LCS: a line in
+/// `output_lines` that exactly matches (ignoring leading/trailing
+/// whitespace) a later, not-yet-claimed line in `source_lines` is mapped to
+/// it (1-based); any output line in between - blank lines left behind by a
+/// deleted multi-line block, or a line rewritten beyond an exact match -
+/// inherits the most recently matched source line.
+///
+/// Builds the alignment with an O(source_len * output_len) dynamic-programming
+/// table, which is fine for the handful of client-side TypeScript files a
+/// static site typically ships, but isn't meant to scale to huge sources.
+fn map_output_lines_to_source(source_lines: &[&str], output_lines: &[&str]) -> Vec<usize> {
+    let source_len = source_lines.len();
+    let output_len = output_lines.len();
+
+    let is_match = |i: usize, j: usize| {
+        let line = output_lines[j].trim();
+        if line.is_empty() {
+            // A genuinely empty line only counts as a match at the final
+            // sentinel segment `split('\n')` produces when the text ends in
+            // a newline - otherwise any two blank lines would match and
+            // the alignment could anchor on an unrelated pair of them.
+            i == source_len - 1 && j == output_len - 1 && source_lines[i].trim().is_empty()
+        } else {
+            source_lines[i].trim() == line
+        }
+    };
+
+    let mut lcs = vec![vec![0u32; output_len + 1]; source_len + 1];
+    for i in (0..source_len).rev() {
+        for j in (0..output_len).rev() {
+            lcs[i][j] = if is_match(i, j) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut mapping = Vec::with_capacity(output_len);
+    let (mut i, mut j) = (0usize, 0usize);
+    let mut last_matched = 1usize;
+    while j < output_len {
+        if i < source_len && is_match(i, j) {
+            last_matched = i + 1;
+            mapping.push(last_matched);
+            i += 1;
+            j += 1;
+        } else if i < source_len && lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            mapping.push(last_matched);
+            j += 1;
+        }
+    }
+
+    mapping
+}
+
+/// Strips TypeScript types like [`strip_typescript_types`], additionally
+/// returning a line-granular source map: entry `i` is the 1-based source
+/// line that output line `i` (0-based) originated from.
+pub fn strip_typescript_types_with_source_map(input: &str) -> (String, Vec<usize>) {
+    let output = strip_typescript_types(input);
+    let source_lines: Vec<&str> = input.split('\n').collect();
+    let output_lines: Vec<&str> = output.split('\n').collect();
+    let mapping = map_output_lines_to_source(&source_lines, &output_lines);
+    (output, mapping)
+}
+
+const BASE64_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard (padded) base64 encoding, used to embed the Source Map v3
+/// document as a `data:` URI below - duplicated rather than shared with
+/// [`crate::integrity::sri_hash_sha384`]'s encoder, matching how
+/// `minifier::source_map` already keeps its own copy of the (differently
+/// shaped) VLQ base64 alphabet rather than reaching across modules for it.
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied();
+        let b2 = chunk.get(2).copied();
+
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0b11) << 4) | (b1.unwrap_or(0) >> 4)) as usize] as char);
+        out.push(match b1 {
+            Some(b1) => {
+                BASE64_ALPHABET[(((b1 & 0b1111) << 2) | (b2.unwrap_or(0) >> 6)) as usize] as char
+            }
+            None => '=',
+        });
+        out.push(match b2 {
+            Some(b2) => BASE64_ALPHABET[(b2 & 0b0011_1111) as usize] as char,
+            None => '=',
+        });
+    }
+    out
+}
+
+/// Strips TypeScript types like [`strip_typescript_types`], additionally
+/// appending a `//# sourceMappingURL=` comment pointing at an inline
+/// (base64 `data:` URI) Source Map v3 document. For callers that only get
+/// to return a single `String` - e.g. [`super::TypeScriptConverter`] via the
+/// `AssetConverter` trait - there's no sibling file to write the way
+/// `file_copier`'s pipeline writes a `.map` file, so the map travels with
+/// the output instead.
+pub fn strip_typescript_types_with_inline_source_map(input: &str, source_name: &str) -> String {
+    let (output, source_lines) = strip_typescript_types_with_source_map(input);
+    let map_json = build_source_map_json(source_name, &source_lines);
+    let encoded = base64_encode(map_json.as_bytes());
+    format!("{output}\n//# sourceMappingURL=data:application/json;charset=utf-8;base64,{encoded}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_plain_output_line_for_line() {
+        let ts = "const a = 1;\nconst b = 2;\n";
+        let (js, mapping) = strip_typescript_types_with_source_map(ts);
+        assert_eq!(js, ts);
+        assert_eq!(mapping, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn carries_source_line_forward_across_a_deleted_interface_block() {
+        let ts = "interface User {\n\tname: string;\n}\nconst user = 1;\n";
+        let (js, mapping) = strip_typescript_types_with_source_map(ts);
+        assert_eq!(js, "const user = 1;\n");
+        // The trailing empty segment from the final newline lines up with
+        // the source's own trailing empty segment, one past the statement.
+        assert_eq!(mapping, vec![4, 5]);
+    }
+
+    #[test]
+    fn inline_type_annotation_removal_keeps_same_line_mapped() {
+        let ts = "function f(a: number) {\n\treturn a;\n}\n";
+        let (js, mapping) = strip_typescript_types_with_source_map(ts);
+        assert_eq!(js, "function f(a) {\n\treturn a;\n}\n");
+        assert_eq!(mapping, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn inline_source_map_appends_base64_data_uri_comment() {
+        let ts = "const a: number = 1;";
+        let js = strip_typescript_types_with_inline_source_map(ts, "script.ts");
+        assert!(js.starts_with("const a = 1;\n"));
+        assert!(js.contains("//# sourceMappingURL=data:application/json;charset=utf-8;base64,"));
+
+        let encoded = js.rsplit(',').next().unwrap();
+        let decoded = String::from_utf8(decode_base64_for_test(encoded)).unwrap();
+        assert!(decoded.contains("\"version\":3"));
+        assert!(decoded.contains("\"sources\":[\"script.ts\"]"));
+    }
+
+    /// Minimal standard-base64 decoder used only to unwrap the encoded map
+    /// in the test above; production code never needs to decode base64.
+    fn decode_base64_for_test(input: &str) -> Vec<u8> {
+        let value_of = |b: u8| BASE64_ALPHABET.iter().position(|&c| c == b).unwrap() as u32;
+        let mut bits = 0u32;
+        let mut bit_count = 0u32;
+        let mut out = Vec::new();
+        for b in input.bytes() {
+            if b == b'=' {
+                break;
+            }
+            bits = (bits << 6) | value_of(b);
+            bit_count += 6;
+            if bit_count >= 8 {
+                bit_count -= 8;
+                out.push((bits >> bit_count) as u8);
+            }
+        }
+        out
+    }
+}
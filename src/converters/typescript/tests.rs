@@ -93,3 +93,33 @@ fn does_not_strip_type_like_sequences_inside_strings_and_templates() {
     assert!(js.contains("querySelector<HTMLElement> as Type : string !"));
     assert!(js.contains("template keeps as Cast<T> : number and bang!"));
 }
+
+#[test]
+fn strips_type_aliases_enums_satisfies_and_parameter_properties() {
+    let ts = r#"
+type PageData = {
+	content: string;
+	css?: string;
+};
+
+enum Status { Idle, Loading, Done = 5 }
+
+class Renderer {
+	constructor(private target: HTMLElement, readonly status: Status = Status.Idle) {
+		this.render();
+	}
+
+	render() {
+		const config = { retries: 3 } satisfies PageData;
+		return config!;
+	}
+}
+		"#;
+    let js = strip_typescript_types(ts);
+    assert!(!js.contains("type PageData"));
+    assert!(js.contains("const Status = { Idle: 0, Loading: 1, Done: 5 }"));
+    assert!(js.contains("constructor(target, status = Status.Idle) {"));
+    assert!(js.contains("this.target = target; this.status = status;"));
+    assert!(!js.contains("satisfies PageData"));
+    assert!(js.contains("return config;"));
+}
@@ -0,0 +1,588 @@
+//! A TypeScript-specific token scanner, modeled on [`crate::lexer`] (the
+//! pure-lexing, tag-plus-text [`Cursor`] shared by the CSS/SCSS scanners)
+//! but with the richer token kinds the transforms in this module need:
+//! whole identifiers and numeric literals, single punctuation characters,
+//! and backtick template literals - none of which `crate::lexer` models,
+//! since CSS/SCSS have no use for them. `remove_type_annotations` and
+//! `remove_generics_before_calls` both used to re-derive their own
+//! in-string/in-comment state machine by walking raw bytes; this is the one
+//! place that bookkeeping lives now, so a new TypeScript transform can be
+//! added as a pass over the token stream instead of another byte-scanner.
+
+use crate::converters::typescript::utils::is_identifier_char;
+use std::str::Chars;
+
+/// The kind of a single token produced by [`Cursor::advance_token`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A run of identifier characters (see [`is_identifier_char`]), e.g.
+    /// `foo`, `_bar`, `$baz`.
+    Ident,
+    /// A run of ASCII digits, with an optional single `.` and more digits,
+    /// e.g. `42`, `0.5`.
+    Num,
+    /// `"..."` or `'...'`, honoring `\`-escaped characters so an escaped
+    /// quote doesn't end the string early. `terminated` is false if the
+    /// input ended before the closing quote.
+    Str { terminated: bool },
+    /// `` `...` ``, honoring `\`-escaped characters like [`TokenKind::Str`].
+    /// A `${` switches to scanning a nested expression - tracked with its
+    /// own brace depth so `{`/`}` inside it (object literals, nested
+    /// blocks) don't prematurely end the interpolation - until the
+    /// matching `}` returns to template text; nested template literals
+    /// inside an interpolation are skipped the same recursive way, so the
+    /// whole token always spans a single, correctly balanced literal.
+    /// `terminated` is false if the input ended before the closing
+    /// backtick (or before an interpolation's closing `}`).
+    TemplateStr { terminated: bool },
+    /// `// ...`, up to (but not including) the next newline or end of input.
+    LineComment,
+    /// `/* ... */`. `terminated` is false if the input ended before `*/`.
+    BlockComment { terminated: bool },
+    /// `/pattern/flags`, honoring `\`-escaped characters and a `[...]`
+    /// character class in which `/` is literal. `terminated` is false if
+    /// the input ended before the closing `/`.
+    Regex { terminated: bool },
+    /// A run of ASCII whitespace.
+    Whitespace,
+    /// A single punctuation character not covered by any other kind, e.g.
+    /// `<`, `(`, `:`.
+    Punct(char),
+    /// A single character that isn't ASCII - none of this module's
+    /// transforms need to look inside these, so they're never merged into
+    /// a run.
+    Unknown,
+}
+
+/// A [`TokenKind`] paired with the slice of the input it was scanned from.
+/// Concatenating every token's `text` in order reproduces the input
+/// exactly, so callers can drop, keep, or rewrite tokens and join the
+/// result back into a `String` without losing any bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+}
+
+/// Walks a `&str` one [`Token`] at a time without allocating.
+#[derive(Clone)]
+pub struct Cursor<'a> {
+    chars: Chars<'a>,
+    /// The last non-whitespace, non-comment token kind seen so far, used
+    /// to disambiguate a `/` as division vs. the start of a regex literal.
+    /// `None` means "nothing significant yet" - the start of input, which
+    /// is regex-literal context, same as following any other operator.
+    prev_significant: Option<TokenKind>,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { chars: input.chars(), prev_significant: None }
+    }
+
+    /// Whether the cursor has reached the end of input.
+    pub fn is_eof(&self) -> bool {
+        self.chars.as_str().is_empty()
+    }
+
+    fn first(&self) -> char {
+        self.chars.clone().next().unwrap_or('\0')
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        self.chars.next()
+    }
+
+    /// Consumes and returns the next token. Only call this when
+    /// [`Cursor::is_eof`] is false.
+    pub fn advance_token(&mut self) -> Token<'a> {
+        let start = self.chars.as_str();
+        let first = self.bump().expect("advance_token called at end of input");
+
+        let kind = match first {
+            '/' if self.first() == '/' => self.line_comment(),
+            '/' if self.first() == '*' => self.block_comment(),
+            '/' if self.regex_allowed() => self.regex_body(),
+            '"' => self.quoted(first, |terminated| TokenKind::Str { terminated }),
+            '\'' => self.quoted(first, |terminated| TokenKind::Str { terminated }),
+            '`' => self.template_literal(),
+            c if c.is_ascii_whitespace() => self.whitespace(),
+            c if c.is_ascii_digit() => self.number(),
+            c if is_identifier_char(c) => self.identifier(),
+            c if c.is_ascii() => TokenKind::Punct(c),
+            _ => TokenKind::Unknown,
+        };
+
+        if !matches!(kind, TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment { .. }) {
+            self.prev_significant = Some(kind);
+        }
+
+        let len = start.len() - self.chars.as_str().len();
+        Token { kind, text: &start[..len] }
+    }
+
+    /// The standard preceding-token heuristic: a `/` begins a regex only
+    /// when the last significant token was not value-producing - not an
+    /// identifier, not a numeric/string/regex literal, and not one of
+    /// `)`, `]`, `}` (which close an expression that division would
+    /// follow). Anything else, including the start of input, is treated
+    /// as regex-literal context.
+    fn regex_allowed(&self) -> bool {
+        !matches!(
+            self.prev_significant,
+            Some(
+                TokenKind::Ident
+                    | TokenKind::Num
+                    | TokenKind::Str { .. }
+                    | TokenKind::TemplateStr { .. }
+                    | TokenKind::Regex { .. }
+                    | TokenKind::Punct(')')
+                    | TokenKind::Punct(']')
+                    | TokenKind::Punct('}')
+            )
+        )
+    }
+
+    fn identifier(&mut self) -> TokenKind {
+        while is_identifier_char(self.first()) {
+            self.bump();
+        }
+        TokenKind::Ident
+    }
+
+    fn number(&mut self) -> TokenKind {
+        while self.first().is_ascii_digit() {
+            self.bump();
+        }
+        if self.first() == '.' {
+            self.bump();
+            while self.first().is_ascii_digit() {
+                self.bump();
+            }
+        }
+        TokenKind::Num
+    }
+
+    fn whitespace(&mut self) -> TokenKind {
+        while self.first().is_ascii_whitespace() {
+            self.bump();
+        }
+        TokenKind::Whitespace
+    }
+
+    fn line_comment(&mut self) -> TokenKind {
+        self.bump(); // second '/'
+        while !self.is_eof() && self.first() != '\n' {
+            self.bump();
+        }
+        TokenKind::LineComment
+    }
+
+    fn block_comment(&mut self) -> TokenKind {
+        self.bump(); // '*'
+        loop {
+            if self.is_eof() {
+                return TokenKind::BlockComment { terminated: false };
+            }
+            let c = self.bump().unwrap();
+            if c == '*' && self.first() == '/' {
+                self.bump();
+                return TokenKind::BlockComment { terminated: true };
+            }
+        }
+    }
+
+    /// Consumes a regex literal's body and flags, assuming the leading `/`
+    /// has already been bumped. Honors `\`-escaped characters like
+    /// [`Cursor::quoted`], and treats `/` as literal while inside a
+    /// `[...]` character class.
+    fn regex_body(&mut self) -> TokenKind {
+        let mut in_class = false;
+        loop {
+            if self.is_eof() {
+                return TokenKind::Regex { terminated: false };
+            }
+            let c = self.bump().unwrap();
+            match c {
+                '\\' => {
+                    self.bump();
+                }
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '/' if !in_class => break,
+                _ => {}
+            }
+        }
+
+        while self.first().is_ascii_lowercase() {
+            self.bump();
+        }
+        TokenKind::Regex { terminated: true }
+    }
+
+    /// Consumes a template literal's body, assuming the opening backtick
+    /// has already been bumped. A `${` hands off to
+    /// [`Cursor::skip_interpolation`] to skip the nested expression as a
+    /// bracketed sub-scan rather than more template text.
+    fn template_literal(&mut self) -> TokenKind {
+        loop {
+            if self.is_eof() {
+                return TokenKind::TemplateStr { terminated: false };
+            }
+            let c = self.bump().unwrap();
+            match c {
+                '\\' => {
+                    self.bump();
+                }
+                '`' => return TokenKind::TemplateStr { terminated: true },
+                '$' if self.first() == '{' => {
+                    self.bump(); // '{'
+                    if !self.skip_interpolation() {
+                        return TokenKind::TemplateStr { terminated: false };
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Skips a `${ ... }` interpolation's body, already positioned just
+    /// past the opening `{`. Returns `false` if the input ends before the
+    /// matching `}`. Each inner token is scanned via [`Cursor::advance_token`]
+    /// itself, so nested strings, comments, regex literals, and template
+    /// literals (with their own interpolations) are skipped as atomic
+    /// units instead of having their own `{`/`}` characters miscounted -
+    /// the same stack-of-modes structure a real lexer uses for
+    /// interpolation, expressed here as recursive calls rather than an
+    /// explicit mode stack.
+    fn skip_interpolation(&mut self) -> bool {
+        let mut depth = 1u32;
+        while depth > 0 {
+            if self.is_eof() {
+                return false;
+            }
+            match self.advance_token().kind {
+                TokenKind::Punct('{') => depth += 1,
+                TokenKind::Punct('}') => depth -= 1,
+                _ => {}
+            }
+        }
+        true
+    }
+
+    fn quoted(&mut self, quote: char, make_kind: impl Fn(bool) -> TokenKind) -> TokenKind {
+        loop {
+            if self.is_eof() {
+                return make_kind(false);
+            }
+            let c = self.bump().unwrap();
+            if c == '\\' {
+                // An escaped character never ends the string, no matter
+                // what it is - consume it as part of the escape pair
+                // unconditionally.
+                self.bump();
+                continue;
+            }
+            if c == quote {
+                return make_kind(true);
+            }
+        }
+    }
+}
+
+/// Tokenizes just the single token at the start of `input` - a convenience
+/// for callers that only need to classify what starts at a position and
+/// learn how many bytes it spans, without keeping a [`Cursor`] around
+/// themselves.
+pub fn first_token(input: &str) -> Token<'_> {
+    Cursor::new(input).advance_token()
+}
+
+/// Scans a regex literal assuming `input` starts with `/` and the caller
+/// has already used the preceding-token heuristic (see
+/// [`Cursor::regex_allowed`]) to decide this `/` is not division. Useful
+/// for callers like `type_annotations` that dispatch on a single
+/// character at a time and so can't lean on [`Cursor`]'s own
+/// continuous tracking of the previous significant token.
+pub fn scan_regex_literal(input: &str) -> Token<'_> {
+    let mut cursor = Cursor::new(input);
+    cursor.bump();
+    let kind = cursor.regex_body();
+    let len = input.len() - cursor.chars.as_str().len();
+    Token { kind, text: &input[..len] }
+}
+
+/// One piece of a template literal's source, as produced by
+/// [`split_template_segments`]: either verbatim template text, or the
+/// source of a `${ ... }` interpolation with the delimiters stripped off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateSegment<'a> {
+    Text(&'a str),
+    Interpolation(&'a str),
+}
+
+fn cursor_pos(cursor: &Cursor, total_len: usize) -> usize {
+    total_len - cursor.chars.as_str().len()
+}
+
+/// Splits a template literal's full source - including the opening and
+/// closing backtick - into its alternating text and interpolation
+/// segments, so a caller can recursively transform each interpolated
+/// expression while leaving the surrounding template text untouched.
+/// Reassembling the original requires re-wrapping each
+/// [`TemplateSegment::Interpolation`] in `${` / `}` and the whole thing in
+/// backticks. Nested interpolations and nested template literals are
+/// skipped as atomic units by the same scan [`Cursor::template_literal`]
+/// uses, so a `}` inside a nested string, comment, or template literal is
+/// never mistaken for the interpolation's closing brace.
+pub fn split_template_segments(literal: &str) -> Vec<TemplateSegment<'_>> {
+    let mut segments = Vec::new();
+    if !literal.starts_with('`') {
+        return segments;
+    }
+
+    let mut cursor = Cursor::new(literal);
+    cursor.bump(); // opening backtick
+    let mut text_start = cursor_pos(&cursor, literal.len());
+
+    loop {
+        if cursor.is_eof() {
+            segments.push(TemplateSegment::Text(&literal[text_start..]));
+            return segments;
+        }
+
+        let before = cursor_pos(&cursor, literal.len());
+        let c = cursor.bump().unwrap();
+        match c {
+            '\\' => {
+                cursor.bump();
+            }
+            '`' => {
+                segments.push(TemplateSegment::Text(&literal[text_start..before]));
+                return segments;
+            }
+            '$' if cursor.first() == '{' => {
+                segments.push(TemplateSegment::Text(&literal[text_start..before]));
+                cursor.bump(); // '{'
+                let expr_start = cursor_pos(&cursor, literal.len());
+                if !cursor.skip_interpolation() {
+                    segments.push(TemplateSegment::Interpolation(&literal[expr_start..]));
+                    return segments;
+                }
+                let expr_end = cursor_pos(&cursor, literal.len()) - 1; // exclude the closing '}'
+                segments.push(TemplateSegment::Interpolation(&literal[expr_start..expr_end]));
+                text_start = cursor_pos(&cursor, literal.len());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Tokenizes all of `input` into a flat token stream.
+pub fn tokenize(input: &str) -> Vec<Token<'_>> {
+    let mut cursor = Cursor::new(input);
+    let mut tokens = Vec::new();
+    while !cursor.is_eof() {
+        tokens.push(cursor.advance_token());
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizes_identifier() {
+        let token = first_token("a1b2 rest");
+        assert_eq!(token.kind, TokenKind::Ident);
+        assert_eq!(token.text, "a1b2");
+    }
+
+    #[test]
+    fn tokenizes_decimal_number() {
+        let token = first_token("0.5rest");
+        assert_eq!(token.kind, TokenKind::Num);
+        assert_eq!(token.text, "0.5");
+    }
+
+    #[test]
+    fn tokenizes_integer_number() {
+        let token = first_token("42, next");
+        assert_eq!(token.kind, TokenKind::Num);
+        assert_eq!(token.text, "42");
+    }
+
+    #[test]
+    fn double_quoted_string_does_not_close_on_escaped_quote() {
+        let token = first_token(r#""a\":b" rest"#);
+        assert_eq!(token.kind, TokenKind::Str { terminated: true });
+        assert_eq!(token.text, r#""a\":b""#);
+    }
+
+    #[test]
+    fn single_quoted_string_does_not_close_on_escaped_quote() {
+        let token = first_token(r"'a\'b' rest");
+        assert_eq!(token.kind, TokenKind::Str { terminated: true });
+        assert_eq!(token.text, r"'a\'b'");
+    }
+
+    #[test]
+    fn template_string_does_not_close_on_escaped_backtick() {
+        let token = first_token(r"`a\`b` rest");
+        assert_eq!(token.kind, TokenKind::TemplateStr { terminated: true });
+        assert_eq!(token.text, r"`a\`b`");
+    }
+
+    #[test]
+    fn unterminated_string_is_flagged() {
+        let token = first_token(r#""unterminated"#);
+        assert_eq!(token.kind, TokenKind::Str { terminated: false });
+    }
+
+    #[test]
+    fn tokenizes_line_comment() {
+        let token = first_token("// comment\nrest");
+        assert_eq!(token.kind, TokenKind::LineComment);
+        assert_eq!(token.text, "// comment");
+    }
+
+    #[test]
+    fn tokenizes_block_comment() {
+        let token = first_token("/* comment */rest");
+        assert_eq!(token.kind, TokenKind::BlockComment { terminated: true });
+        assert_eq!(token.text, "/* comment */");
+    }
+
+    #[test]
+    fn tokenizes_whitespace_run() {
+        let token = first_token("   \t\nrest");
+        assert_eq!(token.kind, TokenKind::Whitespace);
+        assert_eq!(token.text, "   \t\n");
+    }
+
+    #[test]
+    fn tokenizes_single_punct_char() {
+        let token = first_token("<rest");
+        assert_eq!(token.kind, TokenKind::Punct('<'));
+        assert_eq!(token.text, "<");
+    }
+
+    #[test]
+    fn tokenizes_regex_literal_at_start_of_input() {
+        let token = first_token("/ab:cd/g; rest");
+        assert_eq!(token.kind, TokenKind::Regex { terminated: true });
+        assert_eq!(token.text, "/ab:cd/g");
+    }
+
+    #[test]
+    fn tokenizes_division_after_identifier() {
+        let tokens = tokenize("a / b");
+        assert_eq!(tokens[0].kind, TokenKind::Ident);
+        assert_eq!(tokens[2].kind, TokenKind::Punct('/'));
+    }
+
+    #[test]
+    fn tokenizes_regex_literal_after_assignment() {
+        let tokens = tokenize("x = /ab/;");
+        let regex = tokens.iter().find(|t| matches!(t.kind, TokenKind::Regex { .. })).expect("expected a regex token");
+        assert_eq!(regex.text, "/ab/");
+    }
+
+    #[test]
+    fn regex_does_not_close_on_escaped_slash() {
+        let token = first_token(r"/a\/b/ rest");
+        assert_eq!(token.kind, TokenKind::Regex { terminated: true });
+        assert_eq!(token.text, r"/a\/b/");
+    }
+
+    #[test]
+    fn regex_treats_slash_inside_character_class_as_literal() {
+        let token = first_token("/[a/b]/ rest");
+        assert_eq!(token.kind, TokenKind::Regex { terminated: true });
+        assert_eq!(token.text, "/[a/b]/");
+    }
+
+    #[test]
+    fn division_after_closing_paren_is_not_a_regex() {
+        let tokens = tokenize("(a) / b");
+        let slash = tokens.iter().find(|t| matches!(t.kind, TokenKind::Punct('/'))).expect("expected a division token");
+        assert_eq!(slash.text, "/");
+    }
+
+    #[test]
+    fn scan_regex_literal_consumes_body_and_flags() {
+        let token = scan_regex_literal("/ab:cd/gi rest");
+        assert_eq!(token.kind, TokenKind::Regex { terminated: true });
+        assert_eq!(token.text, "/ab:cd/gi");
+    }
+
+    #[test]
+    fn template_literal_interpolation_brace_does_not_close_it_early() {
+        let token = first_token("`total: ${ { a: 1 }.a }` rest");
+        assert_eq!(token.kind, TokenKind::TemplateStr { terminated: true });
+        assert_eq!(token.text, "`total: ${ { a: 1 }.a }`");
+    }
+
+    #[test]
+    fn template_literal_handles_nested_template_literal_in_interpolation() {
+        let token = first_token("`outer ${ `inner ${ 1 + 1 }` } end` rest");
+        assert_eq!(token.kind, TokenKind::TemplateStr { terminated: true });
+        assert_eq!(token.text, "`outer ${ `inner ${ 1 + 1 }` } end`");
+    }
+
+    #[test]
+    fn template_literal_unterminated_interpolation_is_flagged() {
+        let token = first_token("`total: ${ 1 + 1 ");
+        assert_eq!(token.kind, TokenKind::TemplateStr { terminated: false });
+    }
+
+    #[test]
+    fn split_template_segments_splits_text_and_interpolation() {
+        let segments = split_template_segments("`value: ${ x + 1 } done`");
+        assert_eq!(
+            segments,
+            vec![
+                TemplateSegment::Text("value: "),
+                TemplateSegment::Interpolation(" x + 1 "),
+                TemplateSegment::Text(" done"),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_template_segments_handles_nested_braces_in_interpolation() {
+        let segments = split_template_segments("`${ { a: 1 }.a }`");
+        assert_eq!(
+            segments,
+            vec![TemplateSegment::Text(""), TemplateSegment::Interpolation(" { a: 1 }.a "), TemplateSegment::Text("")]
+        );
+    }
+
+    #[test]
+    fn split_template_segments_handles_nested_template_literal() {
+        let segments = split_template_segments("`outer ${ `inner` } end`");
+        assert_eq!(
+            segments,
+            vec![
+                TemplateSegment::Text("outer "),
+                TemplateSegment::Interpolation(" `inner` "),
+                TemplateSegment::Text(" end"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_rejoins_to_original_input() {
+        let input = "function identity<T>(x: T): T { return x; } // done";
+        let rejoined: String = tokenize(input).into_iter().map(|t| t.text).collect();
+        assert_eq!(rejoined, input);
+    }
+
+    #[test]
+    fn tokenize_rejoins_template_literal_with_nested_interpolation() {
+        let input = "const s = `outer ${ `inner ${ 1 + 1 }` } end`;";
+        let rejoined: String = tokenize(input).into_iter().map(|t| t.text).collect();
+        assert_eq!(rejoined, input);
+    }
+}
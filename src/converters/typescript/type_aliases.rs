@@ -0,0 +1,300 @@
+use crate::converters::typescript::utils::{is_identifier_char, push_char_from};
+
+/// Represents the state of string and comment parsing
+struct ParseState {
+    in_single: bool,
+    in_double: bool,
+    in_backtick: bool,
+    in_line_comment: bool,
+    in_block_comment: bool,
+}
+
+impl ParseState {
+    fn new() -> Self {
+        Self {
+            in_single: false,
+            in_double: false,
+            in_backtick: false,
+            in_line_comment: false,
+            in_block_comment: false,
+        }
+    }
+
+    fn is_in_string(&self) -> bool {
+        self.in_single || self.in_double || self.in_backtick
+    }
+}
+
+/// Handles comment parsing and state updates
+fn handle_comments(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &mut ParseState,
+    out: &mut String,
+) -> bool {
+    if state.in_line_comment {
+        push_char_from(input, i, out);
+        if c == '\n' {
+            state.in_line_comment = false;
+        }
+        return true;
+    }
+    if state.in_block_comment {
+        push_char_from(input, i, out);
+        if c == '*' && *i < len && bytes[*i] as char == '/' {
+            out.push('/');
+            *i += 1;
+            state.in_block_comment = false;
+        }
+        return true;
+    }
+    if !state.is_in_string() && c == '/' && *i + 1 < len {
+        let n = bytes[*i + 1] as char;
+        if n == '/' {
+            state.in_line_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+        if n == '*' {
+            state.in_block_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+    }
+    false
+}
+
+/// Handles string literal parsing and state updates
+fn handle_strings(input: &str, i: &mut usize, c: char, state: &mut ParseState, out: &mut String) -> bool {
+    if !state.in_double && !state.in_backtick && c == '\'' {
+        state.in_single = !state.in_single;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_backtick && c == '"' {
+        state.in_double = !state.in_double;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_double && c == '`' {
+        state.in_backtick = !state.in_backtick;
+        push_char_from(input, i, out);
+        return true;
+    }
+    false
+}
+
+fn starts_with_type_keyword(input: &str, i: usize) -> bool {
+    input.get(i..).is_some_and(|s| s.starts_with("type "))
+        || input.get(i..).is_some_and(|s| s.starts_with("type\t"))
+}
+
+fn skip_whitespace(bytes: &[u8], len: usize, i: &mut usize) {
+    while *i < len && (bytes[*i] as char).is_ascii_whitespace() {
+        *i += 1;
+    }
+}
+
+fn skip_identifier(bytes: &[u8], len: usize, i: &mut usize) {
+    while *i < len && is_identifier_char(bytes[*i] as char) {
+        *i += 1;
+    }
+}
+
+/// True if `type` at position `i` is being used as the declaration keyword
+/// (statement start, or right after `export`) rather than as an ordinary
+/// identifier (e.g. the variable in `const type = 'value';`).
+fn type_keyword_is_a_declaration(bytes: &[u8], i: usize) -> bool {
+    let mut j = i;
+    while j > 0 && (bytes[j - 1] as char).is_ascii_whitespace() {
+        j -= 1;
+    }
+    let mut word_start = j;
+    while word_start > 0 && is_identifier_char(bytes[word_start - 1] as char) {
+        word_start -= 1;
+    }
+    let preceding_word = std::str::from_utf8(&bytes[word_start..j]).unwrap_or("");
+    preceding_word.is_empty() || preceding_word == "export"
+}
+
+/// Skips the value of a `type X = ...` alias up to (and including) the
+/// terminating `;` at depth zero, respecting `{}`/`<>`/`()`/`[]` nesting and
+/// string literals so that e.g. `type T = "a;b"` doesn't terminate early.
+fn skip_type_alias_value(bytes: &[u8], len: usize, i: &mut usize) {
+    let mut depth: i32 = 0;
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut in_backtick = false;
+
+    while *i < len {
+        let ch = bytes[*i] as char;
+        if in_single {
+            if ch == '\'' {
+                in_single = false;
+            }
+            *i += 1;
+            continue;
+        }
+        if in_double {
+            if ch == '"' {
+                in_double = false;
+            }
+            *i += 1;
+            continue;
+        }
+        if in_backtick {
+            if ch == '`' {
+                in_backtick = false;
+            }
+            *i += 1;
+            continue;
+        }
+        match ch {
+            '\'' => in_single = true,
+            '"' => in_double = true,
+            '`' => in_backtick = true,
+            '{' | '(' | '<' | '[' => depth += 1,
+            '}' | ')' | '>' | ']' => depth -= 1,
+            ';' if depth <= 0 => {
+                *i += 1;
+                return;
+            }
+            '\n' if depth <= 0 => return,
+            _ => {}
+        }
+        *i += 1;
+    }
+}
+
+/// Removes a `type Name = ...;` alias statement starting at `i`, if one is there.
+fn handle_type_alias(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &ParseState,
+) -> bool {
+    let preceded_by_identifier = *i > 0 && is_identifier_char(bytes[*i - 1] as char);
+    if state.is_in_string()
+        || preceded_by_identifier
+        || c != 't'
+        || !starts_with_type_keyword(input, *i)
+        || !type_keyword_is_a_declaration(bytes, *i)
+    {
+        return false;
+    }
+
+    let save = *i;
+    *i += "type".len();
+    skip_whitespace(bytes, len, i);
+    skip_identifier(bytes, len, i);
+    skip_whitespace(bytes, len, i);
+
+    // Optional generic parameters, e.g. `type Box<T> = ...`
+    if *i < len && bytes[*i] as char == '<' {
+        let mut depth = 0;
+        while *i < len {
+            let ch = bytes[*i] as char;
+            *i += 1;
+            if ch == '<' {
+                depth += 1;
+            } else if ch == '>' {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+        }
+        skip_whitespace(bytes, len, i);
+    }
+
+    if *i >= len || bytes[*i] as char != '=' {
+        // Not actually a type alias (e.g. a variable named `type`); bail out.
+        *i = save;
+        return false;
+    }
+    *i += 1; // skip '='
+
+    skip_type_alias_value(bytes, len, i);
+    skip_whitespace(bytes, len, i);
+    true
+}
+
+/// Removes `type Name = ...;` alias statements; they have no JavaScript
+/// runtime equivalent. Respects string/comment context via [`ParseState`].
+pub fn remove_type_aliases(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut state = ParseState::new();
+
+    while i < len {
+        let c = bytes[i] as char;
+
+        if handle_comments(input, bytes, len, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+        if handle_strings(input, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+        if handle_type_alias(input, bytes, len, &mut i, c, &state) {
+            continue;
+        }
+
+        push_char_from(input, &mut i, &mut out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remove_type_aliases;
+
+    #[test]
+    fn removes_simple_type_alias() {
+        let ts = "type ID = string;\nconst a = 1;";
+        let js = remove_type_aliases(ts);
+        assert!(!js.contains("type ID"));
+        assert!(js.contains("const a = 1;"));
+    }
+
+    #[test]
+    fn removes_object_type_alias_with_nested_braces() {
+        let ts = "type User = { name: string; meta: { age: number } };\nconst a = 1;";
+        let js = remove_type_aliases(ts);
+        assert!(!js.contains("type User"));
+        assert!(js.contains("const a = 1;"));
+    }
+
+    #[test]
+    fn removes_generic_type_alias() {
+        let ts = "type Box<T> = { value: T };\nconst a = 1;";
+        let js = remove_type_aliases(ts);
+        assert!(!js.contains("type Box"));
+        assert!(js.contains("const a = 1;"));
+    }
+
+    #[test]
+    fn keeps_type_word_in_strings() {
+        let ts = "console.log('type X = string;');";
+        let js = remove_type_aliases(ts);
+        assert!(js.contains("'type X = string;'"));
+    }
+
+    #[test]
+    fn leaves_variable_named_type_alone() {
+        let ts = "const type = 'value';";
+        let js = remove_type_aliases(ts);
+        assert_eq!(js, ts);
+    }
+}
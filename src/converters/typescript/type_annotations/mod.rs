@@ -1,16 +1,26 @@
+//! Strips TypeScript type annotations (parameter/property/return types,
+//! `?:` optional markers) down to plain JavaScript, leaving comments and
+//! string/template-literal contents untouched. Comment and quoted-string
+//! recognition is delegated to [`crate::converters::typescript::tokenizer`],
+//! which consumes each comment or string (including backtick template
+//! literals) in one shot, so no state needs to persist across scan
+//! positions to track whether a position is "inside" one.
+
 use crate::converters::typescript::utils::push_char_from;
 
 mod colon;
 mod comments;
 mod depth_counters;
-mod parse_state;
+mod optional_markers;
 mod property_detection;
+mod regex_literals;
 mod strings;
 mod type_skipping;
 
 use colon::handle_colon;
 use comments::handle_comments;
-use parse_state::ParseState;
+use optional_markers::handle_bare_optional_marker;
+use regex_literals::handle_regex;
 use strings::handle_strings;
 
 pub fn remove_type_annotations(input: &str) -> String {
@@ -18,24 +28,23 @@ pub fn remove_type_annotations(input: &str) -> String {
     let mut i = 0;
     let b = input.as_bytes();
     let len = b.len();
-    let mut state = ParseState::new();
 
     while i < len {
         let c = b[i] as char;
 
         // Handle comments first
-        if handle_comments(input, b, len, &mut i, c, &mut state, &mut out) {
+        if handle_comments(input, &mut i, c, &mut out) {
             continue;
         }
 
-        // Handle strings
-        if handle_strings(input, &mut i, c, &mut state, &mut out) {
+        // Handle regex literals before strings, since a `/` is never a
+        // quote character but its body can contain one.
+        if handle_regex(input, b, &mut i, c, &mut out) {
             continue;
         }
 
-        // If inside any string, just copy
-        if state.is_in_string() {
-            push_char_from(input, &mut i, &mut out);
+        // Handle strings
+        if handle_strings(input, &mut i, c, &mut out) {
             continue;
         }
 
@@ -44,6 +53,11 @@ pub fn remove_type_annotations(input: &str) -> String {
             continue;
         }
 
+        // Handle a bare optional-parameter marker, e.g. `b?)` with no type.
+        if handle_bare_optional_marker(b, len, &mut i, c) {
+            continue;
+        }
+
         push_char_from(input, &mut i, &mut out);
     }
 
@@ -118,4 +132,41 @@ function handleStyleTags(data): Promise<void> {
         assert!(js.contains("return new Promise((resolve) => resolve());"));
         assert!(!js.contains(": Promise<void>"));
     }
+
+    #[test]
+    fn leaves_colon_inside_template_literal_untouched() {
+        let ts = r#"
+function label(name: string) {
+    return `${name}: done`;
+}
+        "#;
+        let js = remove_type_annotations(ts);
+        assert!(js.contains("function label(name)"));
+        assert!(js.contains("return `${name}: done`;"));
+    }
+
+    #[test]
+    fn strips_type_annotation_inside_template_interpolation() {
+        let ts = r#"
+function describe(items) {
+    return `names: ${items.map((item: Item) => item.name).join(', ')}`;
+}
+        "#;
+        let js = remove_type_annotations(ts);
+        assert!(js.contains("${items.map((item) => item.name).join(', ')}"));
+        assert!(!js.contains(": Item"));
+    }
+
+    #[test]
+    fn leaves_colon_inside_regex_literal_untouched() {
+        let ts = r#"
+function parse(input: string) {
+    const re = /ab:cd/g;
+    return re.test(input);
+}
+        "#;
+        let js = remove_type_annotations(ts);
+        assert!(js.contains("function parse(input)"));
+        assert!(js.contains("const re = /ab:cd/g;"));
+    }
 }
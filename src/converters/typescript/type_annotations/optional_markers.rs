@@ -0,0 +1,94 @@
+use crate::converters::typescript::utils::is_identifier_char;
+
+/// Handles a bare optional-parameter marker, e.g. the `?` in
+/// `function f(a?: number, b?) {}`. The `?: Type` form is already removed
+/// by [`super::colon::handle_colon`] when the colon is reached; this
+/// covers the case where a parameter is optional but has no type
+/// annotation, so the `?` is never adjacent to a `:`.
+///
+/// A `?` is only treated as this marker when it sits directly against an
+/// identifier character with no whitespace in between (ruling out the
+/// ternary operator, e.g. `x ? y : z`) and is immediately followed
+/// (skipping whitespace) by `)` or `,` (ruling out optional chaining like
+/// `obj?.prop` and nullish coalescing like `a ?? b`). Returns `true` if
+/// the marker was dropped.
+pub fn handle_bare_optional_marker(b: &[u8], len: usize, i: &mut usize, c: char) -> bool {
+    if c != '?' {
+        return false;
+    }
+
+    let preceded_by_identifier = *i > 0 && is_identifier_char(b[*i - 1] as char);
+    if !preceded_by_identifier {
+        return false;
+    }
+
+    let mut j = *i + 1;
+    while j < len && (b[j] as char).is_ascii_whitespace() {
+        j += 1;
+    }
+    let next_char = if j < len { b[j] as char } else { '\0' };
+
+    if next_char != ')' && next_char != ',' {
+        return false;
+    }
+
+    *i += 1; // drop the '?', keep everything else untouched
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_bare_optional_marker_before_closing_paren() {
+        let b = b"b?)";
+        let mut i = 1; // position of '?'
+
+        let handled = handle_bare_optional_marker(b, b.len(), &mut i, '?');
+
+        assert!(handled);
+        assert_eq!(i, 2);
+    }
+
+    #[test]
+    fn drops_bare_optional_marker_before_comma() {
+        let b = b"a?, b";
+        let mut i = 1; // position of '?'
+
+        let handled = handle_bare_optional_marker(b, b.len(), &mut i, '?');
+
+        assert!(handled);
+        assert_eq!(i, 2);
+    }
+
+    #[test]
+    fn leaves_ternary_question_mark_untouched() {
+        let b = b"x ? y : z";
+        let mut i = 2; // position of '?'
+
+        let handled = handle_bare_optional_marker(b, b.len(), &mut i, '?');
+
+        assert!(!handled);
+    }
+
+    #[test]
+    fn leaves_optional_chaining_untouched() {
+        let b = b"obj?.prop";
+        let mut i = 3; // position of '?'
+
+        let handled = handle_bare_optional_marker(b, b.len(), &mut i, '?');
+
+        assert!(!handled);
+    }
+
+    #[test]
+    fn leaves_nullish_coalescing_untouched() {
+        let b = b"a ?? b";
+        let mut i = 2; // position of the first '?'
+
+        let handled = handle_bare_optional_marker(b, b.len(), &mut i, '?');
+
+        assert!(!handled);
+    }
+}
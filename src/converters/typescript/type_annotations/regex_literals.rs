@@ -0,0 +1,104 @@
+use crate::converters::typescript::tokenizer;
+use crate::converters::typescript::utils::is_identifier_char;
+
+/// Handles JavaScript regex literals. A `/` only begins a regex when the
+/// last non-whitespace character before it does not look like something a
+/// division would follow - not an identifier/digit character, a closing
+/// quote, or one of `)`, `]`, `}` (the standard preceding-token
+/// heuristic). Otherwise the whole literal, including trailing flags, is
+/// handed to [`tokenizer::scan_regex_literal`] and copied verbatim, so a
+/// `:` or `"` inside it is never mistaken for a type annotation or a
+/// string delimiter. Returns `true` if `c` was consumed this way.
+pub fn handle_regex(input: &str, b: &[u8], i: &mut usize, c: char, out: &mut String) -> bool {
+    if c != '/' {
+        return false;
+    }
+
+    let mut j = *i;
+    while j > 0 && (b[j - 1] as char).is_ascii_whitespace() {
+        j -= 1;
+    }
+    let prev_char = if j > 0 { b[j - 1] as char } else { '\0' };
+
+    let looks_like_division =
+        is_identifier_char(prev_char) || matches!(prev_char, ')' | ']' | '}' | '"' | '\'' | '`');
+
+    if looks_like_division {
+        return false;
+    }
+
+    let token = tokenizer::scan_regex_literal(&input[*i..]);
+    out.push_str(token.text);
+    *i += token.text.len();
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consumes_regex_literal_at_start_of_input() {
+        let input = "/ab:cd/g; rest";
+        let b = input.as_bytes();
+        let mut i = 0;
+        let mut out = String::new();
+
+        let handled = handle_regex(input, b, &mut i, '/', &mut out);
+
+        assert!(handled);
+        assert_eq!(i, "/ab:cd/g".len());
+        assert_eq!(out, "/ab:cd/g");
+    }
+
+    #[test]
+    fn consumes_regex_literal_after_assignment() {
+        let input = "x = /ab:cd/; rest";
+        let b = input.as_bytes();
+        let mut i = 4; // position of '/'
+        let mut out = String::from("x = ");
+
+        let handled = handle_regex(input, b, &mut i, '/', &mut out);
+
+        assert!(handled);
+        assert_eq!(i, 11);
+        assert_eq!(out, "x = /ab:cd/");
+    }
+
+    #[test]
+    fn returns_false_for_division_after_identifier() {
+        let input = "a / b";
+        let b = input.as_bytes();
+        let mut i = 2; // position of '/'
+        let mut out = String::from("a ");
+
+        let handled = handle_regex(input, b, &mut i, '/', &mut out);
+
+        assert!(!handled);
+        assert_eq!(i, 2);
+    }
+
+    #[test]
+    fn returns_false_for_division_after_closing_paren() {
+        let input = "(a) / b";
+        let b = input.as_bytes();
+        let mut i = 4; // position of '/'
+        let mut out = String::from("(a) ");
+
+        let handled = handle_regex(input, b, &mut i, '/', &mut out);
+
+        assert!(!handled);
+        assert_eq!(i, 4);
+    }
+
+    #[test]
+    fn returns_false_for_non_slash_char() {
+        let input = "a";
+        let mut i = 0;
+        let mut out = String::new();
+
+        let handled = handle_regex(input, input.as_bytes(), &mut i, 'a', &mut out);
+
+        assert!(!handled);
+    }
+}
@@ -1,32 +1,47 @@
-use crate::converters::typescript::utils::push_char_from;
-
-use super::parse_state::ParseState;
-
-/// Handles string literal parsing and state updates.
-/// Returns `true` if a string delimiter was handled (indicating the caller should continue to next iteration).
-pub fn handle_strings(
-    input: &str,
-    i: &mut usize,
-    c: char,
-    state: &mut ParseState,
-    out: &mut String,
-) -> bool {
-    if !state.in_double && !state.in_backtick && c == '\'' {
-        state.in_single = !state.in_single;
-        push_char_from(input, i, out);
-        return true;
+use crate::converters::typescript::tokenizer::{self, TemplateSegment, TokenKind};
+
+use super::remove_type_annotations;
+
+/// Handles string literal parsing. A `'`, `"`, or `` ` `` is handed to
+/// [`tokenizer::first_token`] to consume the whole string - including any
+/// `\`-escaped quotes - in one shot, so no state needs to persist across
+/// scan positions for any string kind. A template literal's `${ ... }`
+/// interpolations are recursively run back through
+/// [`remove_type_annotations`] so a type annotation inside one (e.g. an
+/// arrow function parameter) is stripped too, while the surrounding
+/// template text is kept byte-for-byte. Returns `true` if `c` was handled
+/// (the caller should continue to the next iteration).
+pub fn handle_strings(input: &str, i: &mut usize, c: char, out: &mut String) -> bool {
+    if c != '"' && c != '\'' && c != '`' {
+        return false;
     }
-    if !state.in_single && !state.in_backtick && c == '"' {
-        state.in_double = !state.in_double;
-        push_char_from(input, i, out);
-        return true;
-    }
-    if !state.in_single && !state.in_double && c == '`' {
-        state.in_backtick = !state.in_backtick;
-        push_char_from(input, i, out);
-        return true;
+
+    let token = tokenizer::first_token(&input[*i..]);
+    match token.kind {
+        TokenKind::Str { .. } => {
+            out.push_str(token.text);
+        }
+        TokenKind::TemplateStr { terminated } => {
+            out.push('`');
+            for segment in tokenizer::split_template_segments(token.text) {
+                match segment {
+                    TemplateSegment::Text(text) => out.push_str(text),
+                    TemplateSegment::Interpolation(expr) => {
+                        out.push_str("${");
+                        out.push_str(&remove_type_annotations(expr));
+                        out.push('}');
+                    }
+                }
+            }
+            if terminated {
+                out.push('`');
+            }
+        }
+        _ => return false,
     }
-    false
+
+    *i += token.text.len();
+    true
 }
 
 #[cfg(test)]
@@ -34,109 +49,88 @@ mod tests {
     use super::*;
 
     #[test]
-    fn toggles_single_quote_state() {
-        let input = "'";
+    fn consumes_whole_single_quoted_string() {
+        let input = "'hi' rest";
         let mut i = 0;
-        let mut state = ParseState::new();
         let mut out = String::new();
 
-        let handled = handle_strings(input, &mut i, '\'', &mut state, &mut out);
+        let handled = handle_strings(input, &mut i, '\'', &mut out);
 
         assert!(handled);
-        assert!(state.in_single);
-        assert_eq!(i, 1);
-        assert_eq!(out, "'");
-
-        // Toggle off
-        i = 0;
-        let handled = handle_strings(input, &mut i, '\'', &mut state, &mut out);
-        assert!(handled);
-        assert!(!state.in_single);
+        assert_eq!(i, "'hi'".len());
+        assert_eq!(out, "'hi'");
     }
 
     #[test]
-    fn toggles_double_quote_state() {
-        let input = "\"";
+    fn consumes_whole_double_quoted_string_with_escaped_quote() {
+        let input = r#""he said \"hi\"" rest"#;
         let mut i = 0;
-        let mut state = ParseState::new();
         let mut out = String::new();
 
-        let handled = handle_strings(input, &mut i, '"', &mut state, &mut out);
+        let handled = handle_strings(input, &mut i, '"', &mut out);
 
         assert!(handled);
-        assert!(state.in_double);
-        assert_eq!(i, 1);
-        assert_eq!(out, "\"");
+        assert_eq!(i, r#""he said \"hi\"""#.len());
+        assert_eq!(out, r#""he said \"hi\"""#);
     }
 
     #[test]
-    fn toggles_backtick_state() {
-        let input = "`";
+    fn consumes_whole_template_literal() {
+        let input = "`hi ${name}` rest";
         let mut i = 0;
-        let mut state = ParseState::new();
         let mut out = String::new();
 
-        let handled = handle_strings(input, &mut i, '`', &mut state, &mut out);
+        let handled = handle_strings(input, &mut i, '`', &mut out);
 
         assert!(handled);
-        assert!(state.in_backtick);
-        assert_eq!(i, 1);
-        assert_eq!(out, "`");
+        assert_eq!(i, "`hi ${name}`".len());
+        assert_eq!(out, "`hi ${name}`");
     }
 
     #[test]
-    fn ignores_single_quote_when_in_double_quote() {
-        let input = "'";
+    fn template_literal_does_not_close_on_escaped_backtick() {
+        let input = r"`a\`b` rest";
         let mut i = 0;
-        let mut state = ParseState::new();
-        state.in_double = true;
         let mut out = String::new();
 
-        let handled = handle_strings(input, &mut i, '\'', &mut state, &mut out);
+        let handled = handle_strings(input, &mut i, '`', &mut out);
 
-        assert!(!handled);
-        assert!(!state.in_single);
-        assert!(state.in_double);
+        assert!(handled);
+        assert_eq!(i, r"`a\`b`".len());
+        assert_eq!(out, r"`a\`b`");
     }
 
     #[test]
-    fn ignores_double_quote_when_in_single_quote() {
-        let input = "\"";
+    fn strips_type_annotation_inside_template_interpolation() {
+        let input = "`names: ${items.map((item: Item) => item.name).join(', ')}` rest";
         let mut i = 0;
-        let mut state = ParseState::new();
-        state.in_single = true;
         let mut out = String::new();
 
-        let handled = handle_strings(input, &mut i, '"', &mut state, &mut out);
+        let handled = handle_strings(input, &mut i, '`', &mut out);
 
-        assert!(!handled);
-        assert!(state.in_single);
-        assert!(!state.in_double);
+        assert!(handled);
+        assert_eq!(out, "`names: ${items.map((item) => item.name).join(', ')}`");
     }
 
     #[test]
-    fn ignores_backtick_when_in_single_quote() {
-        let input = "`";
+    fn leaves_nested_template_literal_interpolation_untouched() {
+        let input = "`outer ${`inner ${1 + 1}`}` rest";
         let mut i = 0;
-        let mut state = ParseState::new();
-        state.in_single = true;
         let mut out = String::new();
 
-        let handled = handle_strings(input, &mut i, '`', &mut state, &mut out);
+        let handled = handle_strings(input, &mut i, '`', &mut out);
 
-        assert!(!handled);
-        assert!(state.in_single);
-        assert!(!state.in_backtick);
+        assert!(handled);
+        assert_eq!(out, "`outer ${`inner ${1 + 1}`}`");
     }
 
     #[test]
     fn returns_false_for_non_string_char() {
         let input = "a";
         let mut i = 0;
-        let mut state = ParseState::new();
         let mut out = String::new();
 
-        let handled = handle_strings(input, &mut i, 'a', &mut state, &mut out);
+        let handled = handle_strings(input, &mut i, 'a', &mut out);
 
         assert!(!handled);
     }
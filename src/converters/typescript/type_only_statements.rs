@@ -0,0 +1,418 @@
+use crate::converters::typescript::utils::{is_identifier_char, push_char_from};
+
+/// Represents the state of string and comment parsing
+struct ParseState {
+    in_single: bool,
+    in_double: bool,
+    in_backtick: bool,
+    in_line_comment: bool,
+    in_block_comment: bool,
+}
+
+impl ParseState {
+    fn new() -> Self {
+        Self {
+            in_single: false,
+            in_double: false,
+            in_backtick: false,
+            in_line_comment: false,
+            in_block_comment: false,
+        }
+    }
+
+    fn is_in_string(&self) -> bool {
+        self.in_single || self.in_double || self.in_backtick
+    }
+}
+
+/// Handles comment parsing and state updates
+fn handle_comments(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &mut ParseState,
+    out: &mut String,
+) -> bool {
+    if state.in_line_comment {
+        push_char_from(input, i, out);
+        if c == '\n' {
+            state.in_line_comment = false;
+        }
+        return true;
+    }
+    if state.in_block_comment {
+        push_char_from(input, i, out);
+        if c == '*' && *i < len && bytes[*i] as char == '/' {
+            out.push('/');
+            *i += 1;
+            state.in_block_comment = false;
+        }
+        return true;
+    }
+    if !state.is_in_string() && c == '/' && *i + 1 < len {
+        let n = bytes[*i + 1] as char;
+        if n == '/' {
+            state.in_line_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+        if n == '*' {
+            state.in_block_comment = true;
+            out.push(c);
+            out.push(n);
+            *i += 2;
+            return true;
+        }
+    }
+    false
+}
+
+/// Handles string literal parsing and state updates
+fn handle_strings(input: &str, i: &mut usize, c: char, state: &mut ParseState, out: &mut String) -> bool {
+    if !state.in_double && !state.in_backtick && c == '\'' {
+        state.in_single = !state.in_single;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_backtick && c == '"' {
+        state.in_double = !state.in_double;
+        push_char_from(input, i, out);
+        return true;
+    }
+    if !state.in_single && !state.in_double && c == '`' {
+        state.in_backtick = !state.in_backtick;
+        push_char_from(input, i, out);
+        return true;
+    }
+    false
+}
+
+fn skip_whitespace(bytes: &[u8], len: usize, i: &mut usize) {
+    while *i < len && (bytes[*i] as char).is_ascii_whitespace() {
+        *i += 1;
+    }
+}
+
+fn starts_with_declare_keyword(input: &str, i: usize) -> bool {
+    input.get(i..).is_some_and(|s| {
+        s.starts_with("declare ") || s.starts_with("declare\t") || s.starts_with("declare\n")
+    })
+}
+
+/// True if `declare` at position `i` is being used as a statement-level
+/// keyword rather than as an ordinary identifier (e.g. the variable in
+/// `const declare = 'value';`). A `declare` statement only starts right
+/// after `{`, `}`, `;`, `export`, or the start of the file.
+fn declare_keyword_is_a_statement(bytes: &[u8], i: usize) -> bool {
+    let mut j = i;
+    while j > 0 && (bytes[j - 1] as char).is_ascii_whitespace() {
+        j -= 1;
+    }
+    if j == 0 {
+        return true;
+    }
+    match bytes[j - 1] as char {
+        '{' | '}' | ';' => return true,
+        _ => {}
+    }
+
+    let mut word_start = j;
+    while word_start > 0 && is_identifier_char(bytes[word_start - 1] as char) {
+        word_start -= 1;
+    }
+    std::str::from_utf8(&bytes[word_start..j]).unwrap_or("") == "export"
+}
+
+fn starts_with_import_type(input: &str, i: usize) -> bool {
+    input
+        .get(i..)
+        .is_some_and(|s| s.starts_with("import type ") || s.starts_with("import type\t"))
+}
+
+fn starts_with_export_type(input: &str, i: usize) -> bool {
+    input
+        .get(i..)
+        .is_some_and(|s| s.starts_with("export type ") || s.starts_with("export type\t"))
+}
+
+/// Skips a `declare ...` statement body starting right after the `declare`
+/// keyword and its following whitespace. A declaration either introduces a
+/// braced body (`declare class Foo { ... }`, `declare namespace NS { ... }`,
+/// `declare global { ... }`) or a plain statement terminated by `;`
+/// (`declare const x: number;`, `declare function f(): void;`). Respects
+/// string literals and `{}`/`()`/`[]` nesting so punctuation inside either
+/// form doesn't end the skip early.
+fn skip_declare_body(bytes: &[u8], len: usize, i: &mut usize) {
+    let mut depth: i32 = 0;
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut in_backtick = false;
+    let mut seen_brace = false;
+
+    while *i < len {
+        let ch = bytes[*i] as char;
+        if in_single {
+            if ch == '\'' {
+                in_single = false;
+            }
+            *i += 1;
+            continue;
+        }
+        if in_double {
+            if ch == '"' {
+                in_double = false;
+            }
+            *i += 1;
+            continue;
+        }
+        if in_backtick {
+            if ch == '`' {
+                in_backtick = false;
+            }
+            *i += 1;
+            continue;
+        }
+        match ch {
+            '\'' => in_single = true,
+            '"' => in_double = true,
+            '`' => in_backtick = true,
+            '{' => {
+                depth += 1;
+                seen_brace = true;
+            }
+            '(' | '[' => depth += 1,
+            '}' => {
+                depth -= 1;
+                *i += 1;
+                if seen_brace && depth == 0 {
+                    return;
+                }
+                continue;
+            }
+            ')' | ']' => depth -= 1,
+            ';' if depth == 0 && !seen_brace => {
+                *i += 1;
+                return;
+            }
+            '\n' if depth == 0 && !seen_brace => return,
+            _ => {}
+        }
+        *i += 1;
+    }
+}
+
+/// Skips a top-level `import type ...` or `export type ...` statement
+/// through its terminating `;` or end of line, respecting string literals
+/// and `{}`/`()`/`[]` nesting so a `from './a;b'` module specifier doesn't
+/// end the statement early.
+fn skip_type_only_statement(bytes: &[u8], len: usize, i: &mut usize) {
+    let mut depth: i32 = 0;
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut in_backtick = false;
+
+    while *i < len {
+        let ch = bytes[*i] as char;
+        if in_single {
+            if ch == '\'' {
+                in_single = false;
+            }
+            *i += 1;
+            continue;
+        }
+        if in_double {
+            if ch == '"' {
+                in_double = false;
+            }
+            *i += 1;
+            continue;
+        }
+        if in_backtick {
+            if ch == '`' {
+                in_backtick = false;
+            }
+            *i += 1;
+            continue;
+        }
+        match ch {
+            '\'' => in_single = true,
+            '"' => in_double = true,
+            '`' => in_backtick = true,
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth -= 1,
+            ';' if depth <= 0 => {
+                *i += 1;
+                return;
+            }
+            '\n' if depth <= 0 => return,
+            _ => {}
+        }
+        *i += 1;
+    }
+}
+
+/// Removes a `declare ...` statement starting at `i`, if one is there.
+fn handle_declare(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &ParseState,
+) -> bool {
+    let preceded_by_identifier = *i > 0 && is_identifier_char(bytes[*i - 1] as char);
+    if state.is_in_string()
+        || preceded_by_identifier
+        || c != 'd'
+        || !starts_with_declare_keyword(input, *i)
+        || !declare_keyword_is_a_statement(bytes, *i)
+    {
+        return false;
+    }
+
+    *i += "declare".len();
+    skip_whitespace(bytes, len, i);
+    skip_declare_body(bytes, len, i);
+    skip_whitespace(bytes, len, i);
+    true
+}
+
+/// Removes an `import type ...` or `export type { ... } from ...` statement
+/// starting at `i`, if one is there. Plain `export type Name = ...;` alias
+/// exports are already stripped by an earlier pass
+/// ([`crate::converters::typescript::type_aliases::remove_type_aliases`]),
+/// so by the time this runs the only `export type` left is the re-export
+/// form with no JavaScript equivalent.
+fn handle_import_or_export_type(
+    input: &str,
+    bytes: &[u8],
+    len: usize,
+    i: &mut usize,
+    c: char,
+    state: &ParseState,
+) -> bool {
+    let preceded_by_identifier = *i > 0 && is_identifier_char(bytes[*i - 1] as char);
+    if state.is_in_string() || preceded_by_identifier {
+        return false;
+    }
+    let is_import = c == 'i' && starts_with_import_type(input, *i);
+    let is_export = c == 'e' && starts_with_export_type(input, *i);
+    if !is_import && !is_export {
+        return false;
+    }
+
+    skip_type_only_statement(bytes, len, i);
+    skip_whitespace(bytes, len, i);
+    true
+}
+
+/// Elides top-level `declare ...` statements and `import type`/`export
+/// type` statements; none of them have a JavaScript runtime equivalent.
+/// `interface`/`type`/`enum` declarations are handled by their own earlier
+/// passes, so this one only covers the forms those don't.
+pub fn elide_type_declarations(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut state = ParseState::new();
+
+    while i < len {
+        let c = bytes[i] as char;
+
+        if handle_comments(input, bytes, len, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+        if handle_strings(input, &mut i, c, &mut state, &mut out) {
+            continue;
+        }
+        if handle_declare(input, bytes, len, &mut i, c, &state) {
+            continue;
+        }
+        if handle_import_or_export_type(input, bytes, len, &mut i, c, &state) {
+            continue;
+        }
+
+        push_char_from(input, &mut i, &mut out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::elide_type_declarations;
+
+    #[test]
+    fn removes_declare_const_statement() {
+        let ts = "declare const x: number;\nconst a = 1;";
+        let js = elide_type_declarations(ts);
+        assert!(!js.contains("declare"));
+        assert!(js.contains("const a = 1;"));
+    }
+
+    #[test]
+    fn removes_declare_function_statement() {
+        let ts = "declare function f(): void;\nconst a = 1;";
+        let js = elide_type_declarations(ts);
+        assert!(!js.contains("declare"));
+        assert!(js.contains("const a = 1;"));
+    }
+
+    #[test]
+    fn removes_declare_block_with_nested_braces() {
+        let ts = "declare global {\n    interface Window { foo: string; }\n}\nconst a = 1;";
+        let js = elide_type_declarations(ts);
+        assert!(!js.contains("declare"));
+        assert!(js.contains("const a = 1;"));
+    }
+
+    #[test]
+    fn removes_import_type_statement() {
+        let ts = "import type { T } from './t';\nconst a: T = 1;";
+        let js = elide_type_declarations(ts);
+        assert!(!js.contains("import type"));
+        assert!(js.contains("const a: T = 1;"));
+    }
+
+    #[test]
+    fn removes_export_type_reexport_statement() {
+        let ts = "export type { T } from './t';\nconst a = 1;";
+        let js = elide_type_declarations(ts);
+        assert!(!js.contains("export type"));
+        assert!(js.contains("const a = 1;"));
+    }
+
+    #[test]
+    fn keeps_declare_word_in_strings() {
+        let ts = "console.log('declare const x: number;');";
+        let js = elide_type_declarations(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn keeps_import_type_word_in_strings() {
+        let ts = "console.log(\"import type { T } from './t';\");";
+        let js = elide_type_declarations(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn leaves_variable_named_declare_alone() {
+        let ts = "const declare = 'value';";
+        let js = elide_type_declarations(ts);
+        assert_eq!(js, ts);
+    }
+
+    #[test]
+    fn removes_exported_declare_statement() {
+        let ts = "export declare const x: number;\nconst a = 1;";
+        let js = elide_type_declarations(ts);
+        assert!(!js.contains("declare"));
+        assert!(js.contains("const a = 1;"));
+    }
+}
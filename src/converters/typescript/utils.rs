@@ -15,9 +15,58 @@ pub fn push_char_from(input: &str, index: &mut usize, out: &mut String) {
     }
 }
 
+/// Splits `input` on commas that are at depth zero with respect to
+/// `{}`/`()`/`[]`/`<>` nesting and string literals, e.g. splitting enum
+/// members or constructor parameter lists without breaking apart nested
+/// object types or default-value expressions.
+pub fn split_top_level_commas(input: &str) -> Vec<&str> {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut parts = Vec::new();
+    let mut depth: i32 = 0;
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut in_backtick = false;
+    let mut segment_start = 0;
+    let mut i = 0;
+
+    while i < len {
+        let c = bytes[i] as char;
+        if in_single {
+            if c == '\'' {
+                in_single = false;
+            }
+        } else if in_double {
+            if c == '"' {
+                in_double = false;
+            }
+        } else if in_backtick {
+            if c == '`' {
+                in_backtick = false;
+            }
+        } else {
+            match c {
+                '\'' => in_single = true,
+                '"' => in_double = true,
+                '`' => in_backtick = true,
+                '{' | '(' | '[' | '<' => depth += 1,
+                '}' | ')' | ']' | '>' => depth -= 1,
+                ',' if depth <= 0 => {
+                    parts.push(&input[segment_start..i]);
+                    segment_start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    parts.push(&input[segment_start..]);
+    parts
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{is_identifier_char, push_char_from};
+    use super::{is_identifier_char, push_char_from, split_top_level_commas};
 
     #[test]
     fn is_identifier_char_basic() {
@@ -36,4 +85,13 @@ mod tests {
         assert_eq!(out, "🎉");
         assert_eq!(i, "🎉".len());
     }
+
+    #[test]
+    fn split_top_level_commas_respects_nesting_and_strings() {
+        let parts = split_top_level_commas("a: { x: 1, y: 2 }, b = \"a,b\", c");
+        assert_eq!(
+            parts,
+            vec!["a: { x: 1, y: 2 }", " b = \"a,b\"", " c"]
+        );
+    }
 }
@@ -7,6 +7,8 @@ pub enum Error {
     Io(io::Error),
     /// Error that occurs during liquid processing
     Liquid(String),
+    /// Error that occurs while minifying embedded JSON
+    Json(String),
 }
 
 impl fmt::Display for Error {
@@ -14,6 +16,7 @@ impl fmt::Display for Error {
         match self {
             Error::Io(err) => write!(f, "IO error: {err}"),
             Error::Liquid(msg) => write!(f, "Liquid error: {msg}"),
+            Error::Json(msg) => write!(f, "JSON error: {msg}"),
         }
     }
 }
@@ -23,6 +26,7 @@ impl std::error::Error for Error {
         match self {
             Error::Io(err) => Some(err),
             Error::Liquid(_) => None,
+            Error::Json(_) => None,
         }
     }
 }
@@ -40,3 +44,83 @@ impl From<String> for Error {
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Computes the 1-indexed line and column for a byte offset within `text`,
+/// for turning a raw byte offset into a human-readable location in an error
+/// message (e.g. `"Unclosed Liquid variable at 12:5"`).
+pub fn line_col(text: &str, byte_offset: usize) -> (usize, usize) {
+    let offset = byte_offset.min(text.len());
+    let prefix = &text[..offset];
+    let line = prefix.matches('\n').count() + 1;
+    let col = match prefix.rfind('\n') {
+        Some(newline_idx) => offset - newline_idx,
+        None => offset + 1,
+    };
+    (line, col)
+}
+
+/// Builds an `Error::Liquid` that points at a specific byte offset in the
+/// template being processed, rather than a bare message: the 1-indexed
+/// line/column (via [`line_col`]) and a short snippet of the offending text
+/// are appended, so a build failure can point at the exact template
+/// location instead of a generic error.
+pub fn liquid_error_at(text: &str, byte_offset: usize, message: &str) -> Error {
+    let (line, col) = line_col(text, byte_offset);
+    let snippet = snippet_at(text, byte_offset);
+    Error::Liquid(format!("{message} at {line}:{col}: {snippet}"))
+}
+
+/// Extracts up to 40 characters of `text` starting at `byte_offset`, cut
+/// short at the first line break, for use as a one-line error snippet.
+fn snippet_at(text: &str, byte_offset: usize) -> &str {
+    let start = byte_offset.min(text.len());
+    let mut end = (start + 40).min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text[start..end].lines().next().unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_col_first_line() {
+        assert_eq!(line_col("hello world", 6), (1, 7));
+    }
+
+    #[test]
+    fn test_line_col_after_newline() {
+        assert_eq!(line_col("line one\nline two", 9), (2, 1));
+        assert_eq!(line_col("line one\nline two", 13), (2, 5));
+    }
+
+    #[test]
+    fn test_line_col_multiple_newlines() {
+        assert_eq!(line_col("a\nb\nc\nd", 6), (4, 1));
+    }
+
+    #[test]
+    fn test_liquid_error_at_includes_position_and_snippet() {
+        let text = "line one\n{% render missing %}";
+        let err = liquid_error_at(text, 9, "unknown template");
+        let Error::Liquid(msg) = err else {
+            panic!("expected Error::Liquid");
+        };
+        assert_eq!(msg, "unknown template at 2:1: {% render missing %}");
+    }
+
+    #[test]
+    fn test_liquid_error_at_snippet_stops_at_line_break() {
+        let text = "{% unless cond %}\nrest of the document continues here";
+        let err = liquid_error_at(text, 0, "missing {% endunless %} tag");
+        let Error::Liquid(msg) = err else {
+            panic!("expected Error::Liquid");
+        };
+        assert_eq!(
+            msg,
+            "missing {% endunless %} tag at 1:1: {% unless cond %}"
+        );
+    }
+}
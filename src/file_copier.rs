@@ -1,15 +1,72 @@
-use std::collections::hash_map::DefaultHasher;
 use std::fs::{self, File};
-use std::hash::Hasher;
 use std::io::{self, Read, Write};
 use std::path::Path;
 
-use crate::converters::scss::scss_to_css_with_inline_imports;
-use crate::converters::typescript::strip_typescript_types;
-use crate::minifier::css::minify_css;
-use crate::minifier::js::minify_js;
+use crate::converters::scss::{apply_browser_targets, scss_to_css_with_inline_imports, BrowserTargets};
+use crate::converters::typescript::{strip_typescript_types, strip_typescript_types_with_source_map};
+use crate::hashing::content_fingerprint;
+use crate::minifier::css::{minify_css, minify_css_with_source_map};
+use crate::minifier::html::{
+    minify_html_with_markers, minify_inline_assets, DEFAULT_PRESERVED_COMMENT_MARKERS,
+};
+use crate::minifier::js::{minify_js, minify_js_with_source_map};
+use crate::minifier::source_map::build_source_map_json;
 
 pub fn copy_file_with_versioning(source_path: &str, destination_dir: &str) -> io::Result<String> {
+    copy_file_with_versioning_with_source_maps(source_path, destination_dir, false)
+}
+
+/// Same as [`copy_file_with_versioning`], additionally writing a `.map`
+/// Source Map v3 file alongside the hashed CSS/JS/TS output when
+/// `emit_source_maps` is true. For `.ts` sources the map is composed from
+/// two line-granular mappings - type-stripping's (see
+/// [`strip_typescript_types_with_source_map`]) and minification's (see
+/// [`minify_js_with_source_map`]) - so it still points back at the
+/// original `.ts` file rather than the intermediate stripped JS.
+pub fn copy_file_with_versioning_with_source_maps(
+    source_path: &str,
+    destination_dir: &str,
+    emit_source_maps: bool,
+) -> io::Result<String> {
+    copy_file_with_versioning_with_options(
+        source_path,
+        destination_dir,
+        emit_source_maps,
+        None,
+        true,
+        DEFAULT_PRESERVED_COMMENT_MARKERS,
+    )
+}
+
+/// Applies a browserslist-style `css_targets` query (if any) to CSS/SCSS
+/// content before minification: vendor-prefixes properties that need it for
+/// the configured targets, and lowers 8-digit alpha hex colors when
+/// Internet Explorer is targeted. A `None`/empty query is a no-op.
+fn apply_css_targets_if_configured(css: &str, css_targets: Option<&str>) -> String {
+    match css_targets {
+        Some(spec) if !spec.trim().is_empty() => {
+            apply_browser_targets(css, &BrowserTargets::parse(spec))
+        }
+        _ => css.to_string(),
+    }
+}
+
+/// Same as [`copy_file_with_versioning_with_source_maps`], additionally
+/// accepting a browserslist-style `css_targets` query (see
+/// [`apply_css_targets_if_configured`]) applied to CSS/SCSS assets before
+/// minification, a `minify_inline_assets` flag controlling whether
+/// `<script>`/`<style>` bodies inside HTML assets get run through the
+/// CSS/JS minifiers (see [`minify_inline_assets`]), and the set of comment
+/// "preserve" markers HTML minification keeps verbatim (see
+/// [`minify_html_with_markers`]).
+pub fn copy_file_with_versioning_with_options(
+    source_path: &str,
+    destination_dir: &str,
+    emit_source_maps: bool,
+    css_targets: Option<&str>,
+    minify_inline_html_assets: bool,
+    preserved_comment_markers: &[&str],
+) -> io::Result<String> {
     let source_path = Path::new(source_path);
     let destination_dir = Path::new(destination_dir);
 
@@ -21,42 +78,95 @@ pub fn copy_file_with_versioning(source_path: &str, destination_dir: &str) -> io
     let mut contents = Vec::new();
     file.read_to_end(&mut contents)?;
 
+    let source_name = source_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+
     // Check file extension to determine if processing is needed
     let extension = source_path
         .extension()
         .and_then(|ext| ext.to_str())
         .unwrap_or_default();
 
-    // Process contents and decide output extension (for TS -> JS)
-    let (processed_contents, output_extension) = match extension.to_lowercase().as_str() {
+    // Process contents and decide output extension (for TS -> JS), along with
+    // a source map when one was requested and is supported for this type.
+    let (mut processed_contents, output_extension, source_map_json) = match extension
+        .to_lowercase()
+        .as_str()
+    {
         "css" => {
             let css_string = String::from_utf8_lossy(&contents);
-            let minified_css = minify_css(&css_string);
-            (minified_css.into_bytes(), "css")
+            let targeted = apply_css_targets_if_configured(&css_string, css_targets);
+            if emit_source_maps {
+                let (minified_css, source_lines) = minify_css_with_source_map(&targeted);
+                let map = build_source_map_json(source_name, &source_lines);
+                (minified_css.into_bytes(), "css", Some(map))
+            } else {
+                (minify_css(&targeted).into_bytes(), "css", None)
+            }
         }
         "scss" => {
             let inlined = scss_to_css_with_inline_imports(source_path)?;
-            let minified_css = minify_css(&inlined);
-            (minified_css.into_bytes(), "css")
+            let targeted = apply_css_targets_if_configured(&inlined, css_targets);
+            if emit_source_maps {
+                let (minified_css, source_lines) = minify_css_with_source_map(&targeted);
+                let map = build_source_map_json(source_name, &source_lines);
+                (minified_css.into_bytes(), "css", Some(map))
+            } else {
+                (minify_css(&targeted).into_bytes(), "css", None)
+            }
         }
         "js" => {
             let js_string = String::from_utf8_lossy(&contents);
-            let minified_js = minify_js(&js_string);
-            (minified_js.into_bytes(), "js")
+            if emit_source_maps {
+                let (minified_js, source_lines) = minify_js_with_source_map(&js_string);
+                let map = build_source_map_json(source_name, &source_lines);
+                (minified_js.into_bytes(), "js", Some(map))
+            } else {
+                (minify_js(&js_string).into_bytes(), "js", None)
+            }
         }
         "ts" => {
             let ts_string = String::from_utf8_lossy(&contents);
-            let stripped = strip_typescript_types(&ts_string);
-            let minified_js = minify_js(&stripped);
-            (minified_js.into_bytes(), "js")
+            if emit_source_maps {
+                let (stripped, ts_line_for_stripped_line) =
+                    strip_typescript_types_with_source_map(&ts_string);
+                let (minified_js, stripped_line_for_output_line) =
+                    minify_js_with_source_map(&stripped);
+                let source_lines: Vec<usize> = stripped_line_for_output_line
+                    .iter()
+                    .map(|&stripped_line| {
+                        ts_line_for_stripped_line
+                            .get(stripped_line - 1)
+                            .copied()
+                            .unwrap_or(stripped_line)
+                    })
+                    .collect();
+                let map = build_source_map_json(source_name, &source_lines);
+                (minified_js.into_bytes(), "js", Some(map))
+            } else {
+                let stripped = strip_typescript_types(&ts_string);
+                let minified_js = minify_js(&stripped);
+                (minified_js.into_bytes(), "js", None)
+            }
         }
-        _ => (contents, extension),
+        "html" | "htm" => {
+            let html_string = String::from_utf8_lossy(&contents);
+            let minified = minify_html_with_markers(&html_string, preserved_comment_markers);
+            let minified = if minify_inline_html_assets {
+                minify_inline_assets(&minified)
+            } else {
+                minified
+            };
+            (minified.into_bytes(), extension, None)
+        }
+        _ => (contents, extension, None),
     };
 
-    // Compute a simple hash of the processed contents
-    let mut hasher = DefaultHasher::new();
-    hasher.write(&processed_contents);
-    let hash = hasher.finish();
+    // Compute a deterministic, cross-platform fingerprint of the processed
+    // contents (see `hashing` module docs for why this isn't `DefaultHasher`).
+    let fingerprint = content_fingerprint(&processed_contents);
 
     // Split the file name and extension, then reassemble with the hash
     let file_stem = source_path
@@ -65,7 +175,19 @@ pub fn copy_file_with_versioning(source_path: &str, destination_dir: &str) -> io
         .unwrap_or_default();
 
     // If original extension is ts, use js for output
-    let new_file_name = format!("{file_stem}-{hash:x}.{output_extension}");
+    let new_file_name = format!("{file_stem}-{fingerprint}.{output_extension}");
+
+    if let Some(map_json) = &source_map_json {
+        let map_file_name = format!("{new_file_name}.map");
+        let comment = match output_extension {
+            "css" => format!("\n/*# sourceMappingURL={map_file_name} */"),
+            _ => format!("\n//# sourceMappingURL={map_file_name}"),
+        };
+        processed_contents.extend_from_slice(comment.as_bytes());
+
+        let map_path = destination_dir.join(&map_file_name);
+        fs::write(&map_path, map_json)?;
+    }
 
     let destination_path = destination_dir.join(&new_file_name);
 
@@ -140,6 +262,30 @@ mod tests {
         assert_eq!(copied_content, "function test(){return 42;}");
     }
 
+    #[test]
+    fn test_copy_html_file_with_minification() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+
+        let html_content = "<div>   <p>Hello   World</p>   </div>";
+        let source_file = source_dir.join("index.html");
+        fs::write(&source_file, html_content).unwrap();
+
+        let result =
+            copy_file_with_versioning(source_file.to_str().unwrap(), dest_dir.to_str().unwrap());
+
+        assert!(result.is_ok());
+        let new_filename = result.unwrap();
+
+        assert!(new_filename.starts_with("index-"));
+        assert!(new_filename.ends_with(".html"));
+
+        let copied_content = fs::read_to_string(dest_dir.join(&new_filename)).unwrap();
+        assert_eq!(copied_content, "<div><p>Hello World</p></div>");
+    }
+
     #[test]
     fn test_copy_non_minifiable_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -169,6 +315,27 @@ mod tests {
         assert_eq!(copied_content, txt_content);
     }
 
+    #[test]
+    fn test_fingerprint_filename_is_pinned_for_known_input() {
+        // Regression test: pins the exact emitted filename for a fixed input
+        // so the fingerprint scheme can never silently drift between Rust
+        // releases or build machines.
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+
+        let css_content = "body { margin: 0; }";
+        let source_file = source_dir.join("style.css");
+        fs::write(&source_file, css_content).unwrap();
+
+        let result =
+            copy_file_with_versioning(source_file.to_str().unwrap(), dest_dir.to_str().unwrap());
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "style-90cc951414ed45fe.css");
+    }
+
     #[test]
     fn test_file_hashing_consistency() {
         let temp_dir = TempDir::new().unwrap();
@@ -343,4 +510,197 @@ const b = (a as HTMLElement)!;
             copied.contains("https://static.llllllllllll.com/andor/assets/clippy/swaying.gif?c=")
         );
     }
+
+    #[test]
+    fn test_copy_css_file_with_source_map_emits_map_file_and_url_comment() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+
+        let css_content = "body {\n    margin: 0;\n}";
+        let source_file = source_dir.join("style.css");
+        fs::write(&source_file, css_content).unwrap();
+
+        let result = copy_file_with_versioning_with_source_maps(
+            source_file.to_str().unwrap(),
+            dest_dir.to_str().unwrap(),
+            true,
+        );
+
+        assert!(result.is_ok());
+        let new_filename = result.unwrap();
+
+        let copied_content = fs::read_to_string(dest_dir.join(&new_filename)).unwrap();
+        assert!(copied_content.starts_with("body{margin:0;}"));
+        let expected_comment = format!("/*# sourceMappingURL={new_filename}.map */");
+        assert!(copied_content.contains(&expected_comment));
+
+        let map_content = fs::read_to_string(dest_dir.join(format!("{new_filename}.map"))).unwrap();
+        assert!(map_content.contains("\"version\":3"));
+        assert!(map_content.contains("\"sources\":[\"style.css\"]"));
+    }
+
+    #[test]
+    fn test_copy_ts_file_with_source_maps_enabled_writes_map_pointing_at_ts_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+
+        let ts_content = "const a: number = 1;";
+        let source_file = source_dir.join("script.ts");
+        fs::write(&source_file, ts_content).unwrap();
+
+        let result = copy_file_with_versioning_with_source_maps(
+            source_file.to_str().unwrap(),
+            dest_dir.to_str().unwrap(),
+            true,
+        );
+
+        assert!(result.is_ok());
+        let new_filename = result.unwrap();
+        assert!(new_filename.ends_with(".js"));
+
+        let js_content = fs::read_to_string(dest_dir.join(&new_filename)).unwrap();
+        assert!(js_content.contains(&format!("//# sourceMappingURL={new_filename}.map")));
+
+        let map_content = fs::read_to_string(dest_dir.join(format!("{new_filename}.map"))).unwrap();
+        assert!(map_content.contains("\"version\":3"));
+        assert!(map_content.contains("\"sources\":[\"script.ts\"]"));
+    }
+
+    #[test]
+    fn test_copy_css_file_with_targets_adds_vendor_prefixes() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+
+        let css_content = ".test { user-select: none; }";
+        let source_file = source_dir.join("style.css");
+        fs::write(&source_file, css_content).unwrap();
+
+        let result = copy_file_with_versioning_with_options(
+            source_file.to_str().unwrap(),
+            dest_dir.to_str().unwrap(),
+            false,
+            Some("last 2 versions"),
+            true,
+            DEFAULT_PRESERVED_COMMENT_MARKERS,
+        );
+
+        assert!(result.is_ok());
+        let new_filename = result.unwrap();
+        let copied = fs::read_to_string(dest_dir.join(&new_filename)).unwrap();
+        assert!(copied.contains("-webkit-user-select:none"));
+    }
+
+    #[test]
+    fn test_copy_css_file_with_ie_target_lowers_alpha_hex() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+
+        let css_content = ".test { background: #ff000080; }";
+        let source_file = source_dir.join("style.css");
+        fs::write(&source_file, css_content).unwrap();
+
+        let result = copy_file_with_versioning_with_options(
+            source_file.to_str().unwrap(),
+            dest_dir.to_str().unwrap(),
+            false,
+            Some("ie 11"),
+            true,
+            DEFAULT_PRESERVED_COMMENT_MARKERS,
+        );
+
+        assert!(result.is_ok());
+        let new_filename = result.unwrap();
+        let copied = fs::read_to_string(dest_dir.join(&new_filename)).unwrap();
+        assert!(copied.contains("rgba(255,0,0,0.50)") || copied.contains("rgba(255, 0, 0, 0.50)"));
+        assert!(!copied.contains("#ff000080"));
+    }
+
+    #[test]
+    fn test_copy_css_file_without_targets_is_unchanged() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+
+        let css_content = ".test { user-select: none; }";
+        let source_file = source_dir.join("style.css");
+        fs::write(&source_file, css_content).unwrap();
+
+        let result = copy_file_with_versioning_with_options(
+            source_file.to_str().unwrap(),
+            dest_dir.to_str().unwrap(),
+            false,
+            None,
+            true,
+            DEFAULT_PRESERVED_COMMENT_MARKERS,
+        );
+
+        assert!(result.is_ok());
+        let new_filename = result.unwrap();
+        let copied = fs::read_to_string(dest_dir.join(&new_filename)).unwrap();
+        assert_eq!(copied, ".test{user-select:none;}");
+        assert!(!copied.contains("-webkit-user-select"));
+    }
+
+    #[test]
+    fn test_copy_html_file_minifies_inline_style_and_script() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+
+        let html_content =
+            "<html><style>  .a {  color: red;  }  </style><script>  var x = 1;  </script></html>";
+        let source_file = source_dir.join("index.html");
+        fs::write(&source_file, html_content).unwrap();
+
+        let result = copy_file_with_versioning_with_options(
+            source_file.to_str().unwrap(),
+            dest_dir.to_str().unwrap(),
+            false,
+            None,
+            true,
+            DEFAULT_PRESERVED_COMMENT_MARKERS,
+        );
+
+        assert!(result.is_ok());
+        let new_filename = result.unwrap();
+        let copied = fs::read_to_string(dest_dir.join(&new_filename)).unwrap();
+        assert!(copied.contains(".a{color:red}"));
+        assert!(!copied.contains("  var x"));
+    }
+
+    #[test]
+    fn test_copy_html_file_with_inline_assets_disabled_leaves_blocks_verbatim() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let dest_dir = temp_dir.path().join("dest");
+        fs::create_dir_all(&source_dir).unwrap();
+
+        let html_content = "<html><style>.a {  color: red;  }</style></html>";
+        let source_file = source_dir.join("index.html");
+        fs::write(&source_file, html_content).unwrap();
+
+        let result = copy_file_with_versioning_with_options(
+            source_file.to_str().unwrap(),
+            dest_dir.to_str().unwrap(),
+            false,
+            None,
+            false,
+            DEFAULT_PRESERVED_COMMENT_MARKERS,
+        );
+
+        assert!(result.is_ok());
+        let new_filename = result.unwrap();
+        let copied = fs::read_to_string(dest_dir.join(&new_filename)).unwrap();
+        assert!(copied.contains("color: red;"));
+    }
 }
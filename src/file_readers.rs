@@ -5,6 +5,42 @@ use std::fs;
 use std::io::{Error, ErrorKind, Result};
 use std::path::Path;
 
+/// Detects a leading `yyyy-mm-dd` date followed by `-` or `_` in a file stem (e.g.
+/// `2021-07-19-my-post`), returning the date and the remainder of the slug. Stems
+/// without a valid date prefix return `None` and are left untouched.
+fn extract_date_prefix(slug: &str) -> Option<(&str, &str)> {
+    let bytes = slug.as_bytes();
+    if bytes.len() < 11 || !bytes[..10].iter().enumerate().all(|(i, &b)| {
+        if matches!(i, 4 | 7) {
+            b == b'-'
+        } else {
+            b.is_ascii_digit()
+        }
+    }) {
+        return None;
+    }
+
+    if bytes[0] != b'1' && bytes[0] != b'2' {
+        return None;
+    }
+
+    let month: u32 = slug[5..7].parse().ok()?;
+    if !(1..=12).contains(&month) {
+        return None;
+    }
+
+    let day: u32 = slug[8..10].parse().ok()?;
+    if !(1..=31).contains(&day) {
+        return None;
+    }
+
+    if bytes[10] != b'-' && bytes[10] != b'_' {
+        return None;
+    }
+
+    Some((&slug[..10], &slug[11..]))
+}
+
 pub fn load_and_parse_file_with_front_matter(file_path: &Path) -> Result<ContentItem> {
     let content = fs::read_to_string(file_path).map_err(|e| {
         Error::new(
@@ -15,7 +51,15 @@ pub fn load_and_parse_file_with_front_matter(file_path: &Path) -> Result<Content
             ),
         )
     })?;
-    let mut parsed_content = parse_content_with_front_matter(&content);
+    let mut parsed_content = parse_content_with_front_matter(&content).map_err(|parse_error| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "Failed to parse front matter in '{file_path}': {parse_error}",
+                file_path = file_path.display()
+            ),
+        )
+    })?;
 
     if let Some(file_stem) = file_path.file_stem().and_then(|s| s.to_str()) {
         // For files like "resume.md.liquid", the extension is "liquid" and file_stem is "resume.md".
@@ -33,6 +77,19 @@ pub fn load_and_parse_file_with_front_matter(file_path: &Path) -> Result<Content
         } else {
             file_stem
         };
+
+        // Zola-style date prefixes (e.g. "2021-07-19-my-post") are stripped from the
+        // slug; the captured date fills in the `date` field unless front matter already
+        // set one.
+        let slug = if let Some((date, rest)) = extract_date_prefix(slug) {
+            parsed_content
+                .entry("date".to_string())
+                .or_insert_with(|| date.to_string());
+            rest
+        } else {
+            slug
+        };
+
         parsed_content.insert("slug".to_string(), slug.to_string());
     }
 
@@ -87,6 +144,88 @@ pub fn load_and_parse_files_with_front_matter_in_directory(
     Ok(results)
 }
 
+/// Like `load_and_parse_files_with_front_matter_in_directory`, but also descends into
+/// subdirectories (Zola's "sections" model). Each item discovered below the root gains
+/// a `section` key (its directory path relative to `dir_path`, e.g. `blog/2024/post.md`
+/// gets `section = "blog/2024"`) and a `permalink` that mirrors that directory structure,
+/// e.g. `blog/2024/post`. Items directly in `dir_path` behave exactly as the
+/// non-recursive loader, aside from also gaining a `permalink` equal to their slug.
+pub fn load_and_parse_files_with_front_matter_in_directory_recursive(
+    dir_path: &str,
+) -> Result<ContentCollection> {
+    let path = Path::new(dir_path);
+
+    if !path.exists() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("Directory '{dir_path}' does not exist. Make sure your site has the required directory structure."),
+        ));
+    }
+
+    let mut results = Vec::new();
+    collect_content_files_recursively(path, path, &mut results)?;
+
+    results.sort_by(|a: &ContentItem, b| b["slug"].cmp(&a["slug"]));
+
+    Ok(results)
+}
+
+fn collect_content_files_recursively(
+    root: &Path,
+    dir_path: &Path,
+    results: &mut ContentCollection,
+) -> Result<()> {
+    for entry in fs::read_dir(dir_path).map_err(|e| {
+        Error::new(
+            e.kind(),
+            format!("Failed to read directory '{}': {e}", dir_path.display()),
+        )
+    })? {
+        let entry = entry?;
+        let entry_path = entry.path();
+
+        if entry_path.is_dir() {
+            collect_content_files_recursively(root, &entry_path, results)?;
+            continue;
+        }
+
+        if let Some(extension) = entry_path.extension().and_then(|ext| ext.to_str()) {
+            if extension == "md" || extension == "liquid" {
+                let mut parsed_content = load_and_parse_file_with_front_matter(&entry_path)?;
+                add_section_and_permalink(root, &entry_path, &mut parsed_content);
+                results.push(parsed_content);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Derives the `section` (directory path relative to `root`) and `permalink` (section
+/// joined with the slug) for a file discovered by the recursive loader.
+fn add_section_and_permalink(root: &Path, file_path: &Path, parsed_content: &mut ContentItem) {
+    let slug = parsed_content.get("slug").cloned().unwrap_or_default();
+
+    let section = file_path
+        .parent()
+        .and_then(|parent| parent.strip_prefix(root).ok())
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .map(|relative| {
+            relative
+                .to_string_lossy()
+                .replace(std::path::MAIN_SEPARATOR, "/")
+        });
+
+    let permalink = match &section {
+        Some(section) => format!("{section}/{slug}"),
+        None => slug,
+    };
+    parsed_content.insert("permalink".to_string(), permalink);
+    if let Some(section) = section {
+        parsed_content.insert("section".to_string(), section);
+    }
+}
+
 pub fn load_site_config(site_name: &str) -> Result<ContentItem> {
     let config_path_str = format!("{SITES_BASE_DIR}/{site_name}/{CONFIG_FILE}");
     let config_path = Path::new(&config_path_str);
@@ -163,4 +302,102 @@ mod tests {
             Some(&"resume.md.liquid".to_string())
         );
     }
+
+    #[test]
+    fn test_date_prefix_is_stripped_from_slug_and_captured() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("2021-07-19-my-post.md");
+        let content = "---\ntitle: My Post\n---\ncontent";
+        fs::write(&file_path, content).unwrap();
+
+        let parsed = load_and_parse_file_with_front_matter(&file_path).unwrap();
+        assert_eq!(parsed.get("slug"), Some(&"my-post".to_string()));
+        assert_eq!(parsed.get("date"), Some(&"2021-07-19".to_string()));
+    }
+
+    #[test]
+    fn test_date_prefix_with_underscore_separator_is_stripped() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("2021-07-19_my-post.md");
+        let content = "---\ntitle: My Post\n---\ncontent";
+        fs::write(&file_path, content).unwrap();
+
+        let parsed = load_and_parse_file_with_front_matter(&file_path).unwrap();
+        assert_eq!(parsed.get("slug"), Some(&"my-post".to_string()));
+        assert_eq!(parsed.get("date"), Some(&"2021-07-19".to_string()));
+    }
+
+    #[test]
+    fn test_front_matter_date_wins_over_filename_date() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("2021-07-19-my-post.md");
+        let content = "---\ntitle: My Post\ndate: 2020-01-01\n---\ncontent";
+        fs::write(&file_path, content).unwrap();
+
+        let parsed = load_and_parse_file_with_front_matter(&file_path).unwrap();
+        assert_eq!(parsed.get("slug"), Some(&"my-post".to_string()));
+        assert_eq!(parsed.get("date"), Some(&"2020-01-01".to_string()));
+    }
+
+    #[test]
+    fn test_file_without_date_prefix_is_unaffected() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("my-post.md");
+        let content = "---\ntitle: My Post\n---\ncontent";
+        fs::write(&file_path, content).unwrap();
+
+        let parsed = load_and_parse_file_with_front_matter(&file_path).unwrap();
+        assert_eq!(parsed.get("slug"), Some(&"my-post".to_string()));
+        assert_eq!(parsed.get("date"), None);
+    }
+
+    #[test]
+    fn test_invalid_date_like_prefix_is_not_stripped() {
+        assert_eq!(extract_date_prefix("2021-13-19-my-post"), None);
+        assert_eq!(extract_date_prefix("2021-07-32-my-post"), None);
+        assert_eq!(extract_date_prefix("my-2021-07-19-post"), None);
+    }
+
+    #[test]
+    fn test_recursive_loader_derives_section_and_permalink_for_nested_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("top.md"), "---\ntitle: Top\n---\ntop").unwrap();
+
+        let nested_dir = dir.path().join("blog").join("2024");
+        fs::create_dir_all(&nested_dir).unwrap();
+        fs::write(
+            nested_dir.join("post.md"),
+            "---\ntitle: Post\n---\nnested",
+        )
+        .unwrap();
+
+        let results =
+            load_and_parse_files_with_front_matter_in_directory_recursive(dir.path().to_str().unwrap())
+                .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let top = results.iter().find(|item| item["slug"] == "top").unwrap();
+        assert_eq!(top.get("section"), None);
+        assert_eq!(top.get("permalink"), Some(&"top".to_string()));
+
+        let nested = results.iter().find(|item| item["slug"] == "post").unwrap();
+        assert_eq!(nested.get("section"), Some(&"blog/2024".to_string()));
+        assert_eq!(nested.get("permalink"), Some(&"blog/2024/post".to_string()));
+    }
+
+    #[test]
+    fn test_non_recursive_loader_skips_subdirectories() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("top.md"), "---\ntitle: Top\n---\ntop").unwrap();
+
+        let nested_dir = dir.path().join("blog");
+        fs::create_dir_all(&nested_dir).unwrap();
+        fs::write(nested_dir.join("post.md"), "---\ntitle: Post\n---\nnested").unwrap();
+
+        let results =
+            load_and_parse_files_with_front_matter_in_directory(dir.path().to_str().unwrap())
+                .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["slug"], "top");
+    }
 }
@@ -0,0 +1,460 @@
+use crate::error::Result;
+use crate::template_processors::process_template_tags;
+use crate::types::{ContentCollection, TemplateIncludes, Variables};
+use crate::write::write_html_to_file;
+
+/// A post's content rendered once as HTML (via [`process_template_tags`])
+/// and reused to derive both its Gemini and Gopher representations,
+/// mirroring how [`crate::rss_feed`]'s `FeedItem` is shared across
+/// syndication formats.
+struct SmallWebItem {
+    title: String,
+    slug: String,
+    html_content: String,
+}
+
+/// Runs every post's content through [`process_template_tags`] so the
+/// Gemini/Gopher targets render liquid/markdown identically to the HTML
+/// site, the same way [`crate::rss_feed::collect_latest_feed_items`] shares
+/// rendering across syndication formats.
+fn collect_small_web_items(
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    global_variables: &Variables,
+) -> Result<Vec<SmallWebItem>> {
+    posts
+        .iter()
+        .map(|post| {
+            let empty_string = String::new();
+            let content = post.get("content").unwrap_or(&empty_string);
+            let html_content =
+                process_template_tags(content, global_variables, Some(includes), Some(post))?;
+
+            Ok(SmallWebItem {
+                title: post.get("title").unwrap_or(&empty_string).clone(),
+                slug: post.get("slug").unwrap_or(&empty_string).clone(),
+                html_content,
+            })
+        })
+        .collect()
+}
+
+/// Extracts `name`'s quoted value from a tag's raw body (everything between
+/// `<` and `>`, excluding the tag name itself), e.g. `extract_attr("a
+/// href=\"/x\" class=\"y\"", "href")` returns `Some("/x")`.
+fn extract_attr(tag_body: &str, name: &str) -> Option<String> {
+    let needle = format!("{name}=");
+    let start = tag_body.find(&needle)? + needle.len();
+    let rest = tag_body[start..].trim_start();
+    let quote = rest.chars().next()?;
+    if quote != '"' && quote != '\'' {
+        return None;
+    }
+    let end = rest[1..].find(quote)?;
+    Some(rest[1..1 + end].to_string())
+}
+
+/// Returns the host portion of a `scheme://host/path...` site URL, falling
+/// back to the URL itself if it doesn't look like one.
+fn site_host(site_url: &str) -> &str {
+    site_url
+        .split("://")
+        .nth(1)
+        .unwrap_or(site_url)
+        .split('/')
+        .next()
+        .unwrap_or(site_url)
+}
+
+/// Rewrites an HTML anchor's `href` to its `gemini://` equivalent when it
+/// points back into this site (either an absolute `site_url`-prefixed link
+/// or a root-relative `/path`), leaving external links untouched. Returns
+/// `None` for empty or fragment-only (`#...`) hrefs, which have no
+/// replacement in gemtext - a same-page anchor.
+fn rewrite_href_for_gemini(href: &str, site_url: &str, host: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() || href.starts_with('#') {
+        return None;
+    }
+    if let Some(path) = href.strip_prefix(site_url) {
+        return Some(format!("gemini://{host}{path}"));
+    }
+    if let Some(path) = href.strip_prefix('/') {
+        return Some(format!("gemini://{host}/{path}"));
+    }
+    Some(href.to_string())
+}
+
+/// Ends the current gemtext line: trims any trailing space first so a block
+/// boundary never leaves "word \n" behind, and skips the push if the result
+/// is empty or already ends on a newline - mirrors
+/// [`crate::minifier::html::to_text`]'s line-break handling.
+fn push_line_break(out: &mut String) {
+    while out.ends_with(' ') {
+        out.pop();
+    }
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Converts a block of already-rendered HTML (as produced by
+/// [`process_template_tags`]) into gemtext: `<h1>`-`<h6>` become
+/// `#`-`######` heading lines, `<a href>` becomes its own `=> url text`
+/// link line (gemtext requires links to stand alone on a line), and
+/// `<pre>` bodies are wrapped in ` ``` ` fences. Every other tag is dropped
+/// and its text content flows into the current line.
+fn html_to_gemtext(html: &str, site_url: &str, host: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut chars = html.chars();
+    let mut current_href: Option<String> = None;
+    let mut link_text = String::new();
+
+    while let Some(c) = chars.next() {
+        if c != '<' {
+            if current_href.is_some() {
+                link_text.push(c);
+            } else {
+                out.push(c);
+            }
+            continue;
+        }
+
+        let mut tag_body = String::new();
+        for tc in chars.by_ref() {
+            if tc == '>' {
+                break;
+            }
+            tag_body.push(tc);
+        }
+        let is_closing = tag_body.starts_with('/');
+        let bare = tag_body.trim_start_matches('/');
+        let tag_name = bare
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+
+        match tag_name.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                if is_closing {
+                    push_line_break(&mut out);
+                } else {
+                    let level: usize = tag_name[1..].parse().unwrap_or(1);
+                    push_line_break(&mut out);
+                    out.push_str(&"#".repeat(level));
+                    out.push(' ');
+                }
+            }
+            "a" if !is_closing => {
+                current_href = extract_attr(bare, "href");
+                link_text.clear();
+            }
+            "a" if is_closing => {
+                if let Some(href) = current_href.take() {
+                    match rewrite_href_for_gemini(&href, site_url, host) {
+                        Some(url) => {
+                            push_line_break(&mut out);
+                            out.push_str("=> ");
+                            out.push_str(&url);
+                            if !link_text.trim().is_empty() {
+                                out.push(' ');
+                                out.push_str(link_text.trim());
+                            }
+                            push_line_break(&mut out);
+                        }
+                        None => out.push_str(link_text.trim()),
+                    }
+                }
+            }
+            "pre" => {
+                push_line_break(&mut out);
+                out.push_str("```");
+                push_line_break(&mut out);
+            }
+            "p" | "div" | "li" if is_closing => push_line_break(&mut out),
+            "br" => push_line_break(&mut out),
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// Renders one line of a Gopher directory listing (RFC 1436): an item-type
+/// prefix character (`0` text file, `1` submenu, `i` informational),
+/// followed by the tab-delimited display string, selector, host and port.
+fn gopher_menu_line(
+    item_type: char,
+    display: &str,
+    selector: &str,
+    host: &str,
+    port: u16,
+) -> String {
+    format!("{item_type}{display}\t{selector}\t{host}\t{port}\r\n")
+}
+
+/// Reduces a gemtext document back down to plain text for the Gopher `0`
+/// (text file) item type, which has no notion of gemtext's `=>` link
+/// syntax: heading markers are stripped and link lines become `text (url)`
+/// (or just `url` when the link carried no text).
+fn gemtext_to_plain_text(gemtext: &str) -> String {
+    gemtext
+        .lines()
+        .map(|line| {
+            if let Some(rest) = line.strip_prefix("=> ") {
+                let mut parts = rest.splitn(2, ' ');
+                let url = parts.next().unwrap_or("");
+                match parts.next().map(str::trim) {
+                    Some(text) if !text.is_empty() => format!("{text} ({url})"),
+                    _ => url.to_string(),
+                }
+            } else if line == "```" {
+                String::new()
+            } else if let Some(rest) = line.trim_start_matches('#').strip_prefix(' ') {
+                rest.to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Resolves the Gopher server's advertised host: an explicit `gopher_host`
+/// variable overrides the default of `localhost`.
+fn gopher_host(global_variables: &Variables) -> &str {
+    global_variables
+        .get("gopher_host")
+        .map_or("localhost", String::as_str)
+}
+
+/// Resolves the Gopher server's advertised port: an explicit `gopher_port`
+/// variable overrides the conventional default of `70`.
+fn gopher_port(global_variables: &Variables) -> u16 {
+    global_variables
+        .get("gopher_port")
+        .and_then(|value| value.parse::<u16>().ok())
+        .unwrap_or(70)
+}
+
+/// Publishes every post as a Gemini capsule under `out/gemini/`: each post's
+/// processed content is lowered to gemtext at `posts/<slug>.gmi`, and a
+/// capsule index links to all of them.
+pub fn generate_gemini_capsule(
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    global_variables: &Variables,
+) -> Result<()> {
+    let items = collect_small_web_items(posts, includes, global_variables)?;
+    let site_url = global_variables
+        .get("site_url")
+        .map_or("https://example.com", String::as_str);
+    let site_title = global_variables
+        .get("title")
+        .map_or("My Site", String::as_str);
+    let host = site_host(site_url);
+
+    let mut index = format!("# {site_title}\n\n");
+    for item in &items {
+        index.push_str(&format!("=> /posts/{}.gmi {}\n", item.slug, item.title));
+    }
+    write_html_to_file("out/gemini/index.gmi", &index)?;
+
+    for item in &items {
+        let gemtext = html_to_gemtext(&item.html_content, site_url, host);
+        let post_page = format!("# {}\n\n{gemtext}\n", item.title);
+        write_html_to_file(&format!("out/gemini/posts/{}.gmi", item.slug), &post_page)?;
+    }
+
+    println!("✓ Generated Gemini capsule with {} posts", items.len());
+
+    Ok(())
+}
+
+/// Publishes every post to a Gopher hole under `out/gopher/`: a `gophermap`
+/// directory listing (`0`/`1`/`i` item-type-prefixed, tab-delimited
+/// selector/host/port lines) links to each post's plain-text body at
+/// `posts/<slug>.txt`, derived from the same gemtext lowering the Gemini
+/// capsule uses.
+pub fn generate_gopher_hole(
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    global_variables: &Variables,
+) -> Result<()> {
+    let items = collect_small_web_items(posts, includes, global_variables)?;
+    let site_url = global_variables
+        .get("site_url")
+        .map_or("https://example.com", String::as_str);
+    let site_title = global_variables
+        .get("title")
+        .map_or("My Site", String::as_str);
+    let host = gopher_host(global_variables);
+    let port = gopher_port(global_variables);
+    let gemini_host = site_host(site_url);
+
+    let mut menu = String::new();
+    menu.push_str(&gopher_menu_line('i', site_title, "", host, port));
+    menu.push_str(&gopher_menu_line('i', "", "", host, port));
+    for item in &items {
+        let selector = format!("/posts/{}.txt", item.slug);
+        menu.push_str(&gopher_menu_line('0', &item.title, &selector, host, port));
+    }
+    menu.push_str(".\r\n");
+    write_html_to_file("out/gopher/gophermap", &menu)?;
+
+    for item in &items {
+        let gemtext = html_to_gemtext(&item.html_content, site_url, gemini_host);
+        let body = gemtext_to_plain_text(&gemtext);
+        write_html_to_file(&format!("out/gopher/posts/{}.txt", item.slug), &body)?;
+    }
+
+    println!("✓ Generated Gopher hole with {} posts", items.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OUTPUT_DIR;
+    use crate::types::ContentItem;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    // Both generators write to fixed paths under OUTPUT_DIR, so tests that
+    // exercise them must not run concurrently with each other.
+    static SMALL_WEB_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    fn create_test_post(title: &str, slug: &str, content: &str) -> ContentItem {
+        let mut post = HashMap::new();
+        post.insert("title".to_string(), title.to_string());
+        post.insert("slug".to_string(), slug.to_string());
+        post.insert("content".to_string(), content.to_string());
+        post.insert("file_type".to_string(), "html".to_string());
+        post
+    }
+
+    #[test]
+    fn test_html_to_gemtext_converts_headings_links_and_pre() {
+        let html = "<h1>Title</h1><p>Hello <a href=\"/about\">About</a>.</p><pre>code here</pre>";
+        let gemtext = html_to_gemtext(html, "https://example.com", "example.com");
+        assert_eq!(
+            gemtext,
+            "# Title\nHello\n=> gemini://example.com/about About\n.\n```\ncode here\n```"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_href_for_gemini_handles_site_relative_and_external_links() {
+        assert_eq!(
+            rewrite_href_for_gemini("/posts/hi", "https://example.com", "example.com"),
+            Some("gemini://example.com/posts/hi".to_string())
+        );
+        assert_eq!(
+            rewrite_href_for_gemini(
+                "https://example.com/posts/hi",
+                "https://example.com",
+                "example.com"
+            ),
+            Some("gemini://example.com/posts/hi".to_string())
+        );
+        assert_eq!(
+            rewrite_href_for_gemini(
+                "https://other.example/x",
+                "https://example.com",
+                "example.com"
+            ),
+            Some("https://other.example/x".to_string())
+        );
+        assert_eq!(
+            rewrite_href_for_gemini("#top", "https://example.com", "example.com"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_gopher_menu_line_formats_tab_delimited_fields() {
+        assert_eq!(
+            gopher_menu_line('0', "Hello", "/posts/hello.txt", "gopher.example.com", 70),
+            "0Hello\t/posts/hello.txt\tgopher.example.com\t70\r\n"
+        );
+    }
+
+    #[test]
+    fn test_gemtext_to_plain_text_flattens_headings_and_links() {
+        let gemtext = "# Title\n=> gemini://example.com/x Link text\n```\ncode\n```";
+        assert_eq!(
+            gemtext_to_plain_text(gemtext),
+            "Title\nLink text (gemini://example.com/x)\n\ncode\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_gemini_capsule_writes_index_and_posts() {
+        let _guard = SMALL_WEB_TEST_LOCK.lock().unwrap();
+
+        let posts = vec![create_test_post(
+            "First Post",
+            "first-post",
+            "<h1>First Post</h1><p>Hello <a href=\"/about\">about</a>.</p>",
+        )];
+
+        let mut global_variables = Variables::new();
+        global_variables.insert("title".to_string(), "Test Blog".to_string());
+        global_variables.insert(
+            "site_url".to_string(),
+            "https://test.example.com".to_string(),
+        );
+
+        fs::create_dir_all(OUTPUT_DIR).expect("Failed to create out directory");
+        let includes = std::collections::HashMap::new();
+
+        generate_gemini_capsule(&posts, &includes, &global_variables)
+            .expect("Failed to generate Gemini capsule");
+
+        assert!(Path::new("out/gemini/index.gmi").exists());
+        let index = fs::read_to_string("out/gemini/index.gmi").unwrap();
+        assert!(index.contains("=> /posts/first-post.gmi First Post"));
+
+        let post_page = fs::read_to_string("out/gemini/posts/first-post.gmi").unwrap();
+        assert!(post_page.contains("=> gemini://test.example.com/about about"));
+
+        let _ = fs::remove_dir_all("out/gemini");
+    }
+
+    #[test]
+    fn test_generate_gopher_hole_writes_gophermap_and_post_bodies() {
+        let _guard = SMALL_WEB_TEST_LOCK.lock().unwrap();
+
+        let posts = vec![create_test_post(
+            "First Post",
+            "first-post",
+            "<h1>First Post</h1><p>Hello world.</p>",
+        )];
+
+        let mut global_variables = Variables::new();
+        global_variables.insert("title".to_string(), "Test Blog".to_string());
+        global_variables.insert(
+            "site_url".to_string(),
+            "https://test.example.com".to_string(),
+        );
+
+        fs::create_dir_all(OUTPUT_DIR).expect("Failed to create out directory");
+        let includes = std::collections::HashMap::new();
+
+        generate_gopher_hole(&posts, &includes, &global_variables)
+            .expect("Failed to generate Gopher hole");
+
+        let menu = fs::read_to_string("out/gopher/gophermap").unwrap();
+        assert!(menu.contains("0First Post\t/posts/first-post.txt\tlocalhost\t70\r\n"));
+        assert!(menu.ends_with(".\r\n"));
+
+        let body = fs::read_to_string("out/gopher/posts/first-post.txt").unwrap();
+        assert!(body.contains("Hello world."));
+
+        let _ = fs::remove_dir_all("out/gopher");
+    }
+}
@@ -1,15 +1,24 @@
 use crate::{
+    build_manifest::{fingerprint_file_contents, BuildManifest, MANIFEST_FILE_NAME},
     config::SiteConfig,
     error::Result,
-    file_copier::copy_file_with_versioning,
+    file_copier::copy_file_with_versioning_with_options,
     file_readers::{load_and_parse_files_with_front_matter_in_directory, load_site_config},
+    gemini_gopher::{generate_gemini_capsule, generate_gopher_hole},
     generate_pagination_pages::generate_pagination_pages,
+    generate_taxonomy_pages::{generate_taxonomy_pages, group_posts_by_taxonomy},
+    hashing::content_fingerprint,
+    integrity::sri_hash_sha384,
     layout::load_layout,
     load_data::load_site_data,
     load_includes::load_liquid_includes,
+    pagination::SitemapSink,
     render_page::render_page,
-    types::{ContentCollection, TemplateIncludes, Variables},
+    rss_feed::{generate_feeds, generate_taxonomy_feeds},
+    sitemap_writer::generate_sitemap,
+    types::{ContentCollection, ContentItem, TemplateIncludes, Variables},
 };
+use rayon::prelude::*;
 use std::{
     collections::HashMap,
     fs,
@@ -65,11 +74,12 @@ fn load_site_content(site_name: &str, config: &SiteConfig) -> Result<SiteContent
     );
 
     // Gracefully handle sites without a posts directory
-    let posts = if std::path::Path::new(&posts_dir).exists() {
+    let mut posts = if std::path::Path::new(&posts_dir).exists() {
         load_and_parse_files_with_front_matter_in_directory(&posts_dir)?
     } else {
         Vec::new()
     };
+    sort_posts_by_date_descending(&mut posts);
     let pages = load_and_parse_files_with_front_matter_in_directory(&pages_dir)?;
     let includes = load_liquid_includes(&includes_dir);
     let site_config = load_site_config(site_name, config)?;
@@ -91,6 +101,22 @@ fn load_site_content(site_name: &str, config: &SiteConfig) -> Result<SiteContent
     })
 }
 
+/// Sorts posts by descending `date` (ISO `YYYY-MM-DD`, so a plain string
+/// comparison is also a chronological one), so `posts.0` is always the
+/// newest post both in pagination and in the indexed `posts.N.*` global
+/// variables. Dateless posts sort last, in their original (stable) order;
+/// front matter already wins over a filename-derived date by the time
+/// `date` reaches here, since `load_and_parse_file_with_front_matter` only
+/// fills it in when front matter didn't set one.
+fn sort_posts_by_date_descending(posts: &mut ContentCollection) {
+    posts.sort_by(|a, b| match (a.get("date"), b.get("date")) {
+        (Some(a_date), Some(b_date)) => b_date.cmp(a_date),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+}
+
 /// Sets up global variables from various sources
 fn setup_global_variables(
     content: &SiteContent,
@@ -132,17 +158,70 @@ fn setup_global_variables(
     add_collection_to_global_variables(&mut global_variables, "posts", &content.posts);
     add_collection_to_global_variables(&mut global_variables, "pages", &content.pages);
 
+    // Add each taxonomy's terms as indexed global variables (e.g.
+    // "tags.0.name", "tags.0.count") so a site's main layout can render a
+    // tag cloud or category list without visiting a term page first.
+    let listed_posts: ContentCollection = content
+        .posts
+        .iter()
+        .filter(|post| {
+            post.get("unlisted")
+                .is_none_or(|value| value.to_lowercase() != "true")
+        })
+        .cloned()
+        .collect();
+    for taxonomy in &config.taxonomies {
+        add_taxonomy_terms_to_global_variables(&mut global_variables, taxonomy, &listed_posts);
+    }
+
     (global_variables, posts_per_page)
 }
 
-/// Generates all site content (pagination, posts, pages)
+/// Convert a taxonomy's terms into indexed global variables for use with for
+/// loops, sorted by term name so the order is stable across runs.
+///
+/// Converts a taxonomy like `tags` grouping posts into "rust" (2 posts) and
+/// "web" (1 post) into variables like:
+/// - "tags.0.name" => "rust"
+/// - "tags.0.slug" => "rust"
+/// - "tags.0.count" => "2"
+/// - "tags.1.name" => "web"
+/// - "tags.1.slug" => "web"
+/// - "tags.1.count" => "1"
+fn add_taxonomy_terms_to_global_variables(
+    global_variables: &mut Variables,
+    taxonomy: &crate::config::TaxonomyConfig,
+    posts: &ContentCollection,
+) {
+    let mut terms: Vec<_> = group_posts_by_taxonomy(posts, taxonomy).into_iter().collect();
+    terms.sort_by(|(_, (a, _)), (_, (b, _))| a.cmp(b));
+
+    for (index, (slug, (name, term_posts))) in terms.into_iter().enumerate() {
+        global_variables.insert(format!("{}.{index}.name", taxonomy.name), name);
+        global_variables.insert(format!("{}.{index}.slug", taxonomy.name), slug);
+        global_variables.insert(format!("{}.{index}.count", taxonomy.name), term_posts.len().to_string());
+    }
+}
+
+/// Generates all site content (pagination, posts, pages) and returns every
+/// pager page's URL, collected for the site-wide sitemap writer.
 fn generate_site_content(
     site_name: &str,
     content: &SiteContent,
     global_variables: &Variables,
     posts_per_page: usize,
     config: &SiteConfig,
-) -> Result<()> {
+) -> Result<SitemapSink> {
+    let mut sitemap = SitemapSink::default();
+
+    // A content item's incremental-build fingerprint combines its own fields
+    // with this fingerprint of every shared input that affects every page's
+    // render output. That way a change to the main layout, an include, or
+    // the site config naturally invalidates every item's cache entry below,
+    // with no separate cache-reset step needed.
+    let shared_fingerprint =
+        fingerprint_shared_inputs(&content.main_layout, &content.includes, global_variables);
+
     // Filter out unlisted posts for pagination
     let filtered_posts: ContentCollection = content
         .posts
@@ -162,6 +241,18 @@ fn generate_site_content(
         &content.main_layout,
         global_variables,
         config,
+        &mut sitemap,
+    )?;
+
+    generate_taxonomy_pages(
+        site_name,
+        posts_per_page,
+        &filtered_posts,
+        &content.includes,
+        &content.main_layout,
+        global_variables,
+        config,
+        &mut sitemap,
     )?;
 
     // Generate posts
@@ -174,6 +265,7 @@ fn generate_site_content(
         output_directory: &format!("{}/{site_name}/posts/", config.output_dir),
         default_layout: Some("post"),
         site_config: config,
+        shared_fingerprint: &shared_fingerprint,
     })?;
 
     // Generate pages
@@ -186,9 +278,10 @@ fn generate_site_content(
         output_directory: &format!("{}/{site_name}/", config.output_dir),
         default_layout: None,
         site_config: config,
+        shared_fingerprint: &shared_fingerprint,
     })?;
 
-    Ok(())
+    Ok(sitemap)
 }
 
 /// Convert a content collection into indexed global variables for use with for loops
@@ -222,67 +315,254 @@ struct ContentGenerationConfig<'a> {
     output_directory: &'a str,
     default_layout: Option<&'a str>,
     site_config: &'a SiteConfig,
+    shared_fingerprint: &'a str,
 }
 
-/// Generic function to generate content items (posts or pages)
-fn generate_content_items(config: &ContentGenerationConfig) -> Result<()> {
-    for content_item in config.content_items {
-        let mut variables = config.global_variables.clone();
-        variables.extend(content_item.clone());
-        variables.insert("site_name".to_string(), config.site_name.to_string());
-
-        // Set default layout if provided
-        if let Some(layout) = config.default_layout {
-            variables.insert("layout".to_string(), layout.to_string());
-        }
+/// Fingerprints every shared input that affects every content item's render
+/// output - the main layout, every include (sorted by name for a stable
+/// order), and every global variable - into a single hash used by
+/// [`fingerprint_content_item`] below.
+///
+/// `global_variables` is hashed wholesale rather than picking out individual
+/// pieces, since it's the actual superset a render sees: it already carries
+/// the site config, data-file variables, versioned-asset hrefs/SRI hashes
+/// `copy_assets` recomputes every build, and the indexed `posts.N.*`/
+/// `pages.N.*`/taxonomy-term values Liquid `for`/`assign` tags read. Without
+/// this, editing one post's title (or a CSS asset changing its versioned
+/// filename) wouldn't change the fingerprint of any *other* page, so a page
+/// that merely loops over `posts` would keep serving its stale cached render
+/// forever. `generated_date` is excluded - it's a fresh timestamp on every
+/// build and would otherwise force every item to re-render every time
+/// regardless of whether anything an item actually depends on changed.
+fn fingerprint_shared_inputs(
+    main_layout: &str,
+    includes: &TemplateIncludes,
+    global_variables: &Variables,
+) -> String {
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(main_layout.as_bytes());
+    buffer.push(0);
+
+    let mut sorted_includes: Vec<_> = includes.iter().collect();
+    sorted_includes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (name, body) in sorted_includes {
+        buffer.extend_from_slice(name.as_bytes());
+        buffer.push(0);
+        buffer.extend_from_slice(body.as_bytes());
+        buffer.push(0);
+    }
 
-        // Handle page-specific CSS from front matter
-        if let Some(css_file) = content_item.get("css") {
-            // Look up the versioned filename from global variables (which contains versioned_assets)
-            if let Some(versioned_css) = config.global_variables.get(css_file) {
-                variables.insert("page_specific_css".to_string(), versioned_css.clone());
-            } else {
-                eprintln!(
-                    "⚠️  Warning: CSS file '{css_file}' specified in front matter was not found in assets"
-                );
-            }
+    let mut sorted_globals: Vec<_> = global_variables
+        .iter()
+        .filter(|(key, _)| key.as_str() != "generated_date")
+        .collect();
+    sorted_globals.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (key, value) in sorted_globals {
+        buffer.extend_from_slice(key.as_bytes());
+        buffer.push(0);
+        buffer.extend_from_slice(value.as_bytes());
+        buffer.push(0);
+    }
+
+    content_fingerprint(&buffer)
+}
+
+/// Combines a content item's own fields (sorted by key for a stable order)
+/// with `shared_fingerprint` into the fingerprint stored in the build
+/// manifest, so either one changing produces a different combined hash.
+fn fingerprint_content_item(content_item: &ContentItem, shared_fingerprint: &str) -> String {
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(shared_fingerprint.as_bytes());
+    buffer.push(0);
+
+    let mut sorted_fields: Vec<_> = content_item.iter().collect();
+    sorted_fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (key, value) in sorted_fields {
+        buffer.extend_from_slice(key.as_bytes());
+        buffer.push(0);
+        buffer.extend_from_slice(value.as_bytes());
+        buffer.push(0);
+    }
+
+    content_fingerprint(&buffer)
+}
+
+/// A content item's incremental-build outcome, recorded into the build
+/// manifest once every item in the collection has finished rendering (or
+/// been skipped as unchanged).
+struct ManifestUpdate {
+    output_path: String,
+    fingerprint: String,
+}
+
+/// Renders a single content item (post or page) into `config.output_directory`,
+/// unless its combined fingerprint (own fields + [`ContentGenerationConfig::shared_fingerprint`])
+/// matches the build manifest's recorded fingerprint for its output path and
+/// that output file still exists on disk, in which case rendering is skipped.
+fn generate_content_item(
+    config: &ContentGenerationConfig,
+    content_item: &ContentItem,
+    manifest: &BuildManifest,
+) -> Result<ManifestUpdate> {
+    let mut variables = config.global_variables.clone();
+    variables.extend(content_item.clone());
+    variables.insert("site_name".to_string(), config.site_name.to_string());
+
+    // Set default layout if provided
+    if let Some(layout) = config.default_layout {
+        variables.insert("layout".to_string(), layout.to_string());
+    }
+
+    // Handle page-specific CSS from front matter
+    if let Some(css_file) = content_item.get("css") {
+        // Look up the versioned filename from global variables (which contains versioned_assets)
+        if let Some(versioned_css) = config.global_variables.get(css_file) {
+            variables.insert("page_specific_css".to_string(), versioned_css.clone());
+        } else {
+            eprintln!("⚠️  Warning: CSS file '{css_file}' specified in front matter was not found in assets");
         }
+    }
+
+    // Store original page title before combining with site title
+    if let Some(original_title) = content_item.get("title") {
+        variables.insert("original_title".to_string(), original_title.clone());
+    }
 
-        // Store original page title before combining with site title
-        if let Some(original_title) = content_item.get("title") {
-            variables.insert("original_title".to_string(), original_title.clone());
+    // Merge title with site title if content item title exists
+    if let Some(title) = content_item.get("title") {
+        if let Some(site_title) = config.global_variables.get("title") {
+            variables.insert("title".to_string(), format!("{title} - {site_title}"));
         }
+    }
 
-        // Merge title with site title if content item title exists
-        if let Some(title) = content_item.get("title") {
-            if let Some(site_title) = config.global_variables.get("title") {
-                variables.insert("title".to_string(), format!("{title} - {site_title}"));
+    let content = content_item.get("content").map_or("", String::as_str);
+    let slug = content_item.get("slug").map_or("", String::as_str);
+
+    let word_count = content.split_whitespace().count();
+    let reading_time = word_count.div_ceil(config.site_config.reading_time_wpm.max(1)).max(1);
+    variables.insert("word_count".to_string(), word_count.to_string());
+    variables.insert("reading_time".to_string(), reading_time.to_string());
+
+    // Mirrors render_page's own output-extension resolution so the output
+    // path predicted here always matches the file render_page actually
+    // writes.
+    let output_extension = variables
+        .get("source_file_name")
+        .and_then(|name| name.strip_suffix(".liquid").or_else(|| name.strip_suffix(".html")))
+        .and_then(|name_without_liquid| name_without_liquid.rsplit_once('.'))
+        .map(|(_, ext)| ext)
+        .unwrap_or("html");
+    let output_path = format!("{}{slug}.{output_extension}", config.output_directory);
+    let fingerprint = fingerprint_content_item(content_item, config.shared_fingerprint);
+
+    if !config.site_config.force_rebuild {
+        if let Some(cached_output) = manifest.unchanged_output_path(&output_path, &fingerprint) {
+            if std::path::Path::new(cached_output).exists() {
+                return Ok(ManifestUpdate {
+                    output_path,
+                    fingerprint,
+                });
             }
         }
+    }
+
+    render_page(
+        content,
+        config.output_directory,
+        slug,
+        config.main_layout,
+        config.includes,
+        &variables,
+        config.site_config,
+    )?;
+
+    Ok(ManifestUpdate {
+        output_path,
+        fingerprint,
+    })
+}
+
+/// Generic function to generate content items (posts or pages). Each item
+/// writes to its own output path derived from its slug and only reads shared
+/// state (`includes`, `main_layout`, `global_variables`) plus a read-only
+/// [`BuildManifest`] snapshot, so the items are independent of one another
+/// and can be rendered in parallel. Controlled by
+/// [`SiteConfig::parallel_content_generation`] - disabled, this falls back to
+/// a plain sequential loop, e.g. for snapshot tests that expect deterministic
+/// output ordering in logs or error messages.
+///
+/// Manifest writes happen after every item has finished (inside this
+/// function, not inside [`generate_content_item`]) since rayon's parallel
+/// iterator can't safely share a `&mut BuildManifest` across its closures;
+/// each item instead returns the fingerprint/output path it settled on, and
+/// those are recorded and saved once, sequentially, at the end.
+fn generate_content_items(config: &ContentGenerationConfig) -> Result<()> {
+    let manifest_path = std::path::Path::new(&config.site_config.output_dir)
+        .join(config.site_name)
+        .join(MANIFEST_FILE_NAME);
+    let manifest = if config.site_config.force_rebuild {
+        BuildManifest::default()
+    } else {
+        BuildManifest::load(&manifest_path)
+    };
 
-        let content = content_item.get("content").map_or("", String::as_str);
-        let slug = content_item.get("slug").map_or("", String::as_str);
-
-        render_page(
-            content,
-            config.output_directory,
-            slug,
-            config.main_layout,
-            config.includes,
-            &variables,
-            config.site_config,
-        )?;
+    let updates = if config.site_config.parallel_content_generation {
+        config
+            .content_items
+            .par_iter()
+            .map(|content_item| generate_content_item(config, content_item, &manifest))
+            .collect::<Result<Vec<ManifestUpdate>>>()?
+    } else {
+        config
+            .content_items
+            .iter()
+            .map(|content_item| generate_content_item(config, content_item, &manifest))
+            .collect::<Result<Vec<ManifestUpdate>>>()?
+    };
+
+    if !config.site_config.force_rebuild {
+        let mut manifest = manifest;
+        for update in updates {
+            manifest.record(&update.output_path, update.fingerprint, update.output_path.clone());
+        }
+        let _ = manifest.save(&manifest_path);
     }
 
     Ok(())
 }
 
+/// Copies and versions every asset file, skipping reprocessing for files whose
+/// content fingerprint (bytes + mtime) is unchanged since the last build and
+/// whose previous output is still on disk. An incremental build manifest
+/// under the output directory tracks those fingerprints; `--force`
+/// (`config.force_rebuild`) bypasses it entirely.
+///
+/// For each CSS/JS asset, also computes a `sha384-...` Subresource Integrity
+/// digest over its final versioned output bytes and stores it under
+/// `asset_integrity.<original file name>`, so templates can render
+/// `integrity="{{ asset_integrity.style.css }}"` alongside the versioned
+/// `href`/`src`.
 fn copy_assets(site_name: &str, config: &SiteConfig) -> Result<HashMap<String, String>> {
     let assets_dir = format!(
         "{}/{site_name}/{}",
         config.sites_base_dir, config.assets_subdir
     );
+    let destination_dir = format!("./{}/{site_name}/assets/", config.output_dir);
     let mut versioned_assets = HashMap::new();
+    let preserved_comment_markers: Vec<&str> = config
+        .preserved_comment_markers
+        .iter()
+        .map(String::as_str)
+        .collect();
+
+    let manifest_path = std::path::Path::new(&config.output_dir)
+        .join(site_name)
+        .join(MANIFEST_FILE_NAME);
+    let mut manifest = if config.force_rebuild {
+        BuildManifest::default()
+    } else {
+        BuildManifest::load(&manifest_path)
+    };
 
     if let Ok(entries) = fs::read_dir(&assets_dir) {
         for entry in entries.flatten() {
@@ -293,16 +573,79 @@ fn copy_assets(site_name: &str, config: &SiteConfig) -> Result<HashMap<String, S
                     if file_name.starts_with('_') {
                         continue;
                     }
-                    let versioned_name = copy_file_with_versioning(
-                        &format!("{assets_dir}/{file_name}"),
-                        &format!("./{}/{site_name}/assets/", config.output_dir),
-                    )?;
+
+                    let source_path = format!("{assets_dir}/{file_name}");
+                    let fingerprint = (!config.force_rebuild).then(|| {
+                        fs::read(&source_path).ok().map(|bytes| {
+                            let mtime = fs::metadata(&source_path)
+                                .ok()
+                                .and_then(|metadata| metadata.modified().ok());
+                            fingerprint_file_contents(&bytes, mtime)
+                        })
+                    }).flatten();
+
+                    let mut cached_output = None;
+                    if let Some(fingerprint) = &fingerprint {
+                        if let Some(candidate) =
+                            manifest.unchanged_output_path(&source_path, fingerprint)
+                        {
+                            if std::path::Path::new(&destination_dir)
+                                .join(candidate)
+                                .exists()
+                            {
+                                cached_output = Some(candidate.to_string());
+                            }
+                        }
+                    }
+
+                    let versioned_name = match cached_output {
+                        Some(cached_output) => cached_output,
+                        None => {
+                            let versioned_name = copy_file_with_versioning_with_options(
+                                &source_path,
+                                &destination_dir,
+                                config.source_maps,
+                                config.css_targets.as_deref(),
+                                config.minify_inline_assets,
+                                &preserved_comment_markers,
+                            )?;
+
+                            if let Some(fingerprint) = fingerprint {
+                                manifest.record(&source_path, fingerprint, versioned_name.clone());
+                            }
+
+                            versioned_name
+                        }
+                    };
+
+                    // Subresource Integrity digests only make sense for
+                    // assets a template links via <script>/<link> with an
+                    // `integrity` attribute, so this is scoped to CSS/JS.
+                    let output_extension = std::path::Path::new(&versioned_name)
+                        .extension()
+                        .and_then(|ext| ext.to_str())
+                        .unwrap_or_default();
+                    if matches!(output_extension, "css" | "js") {
+                        if let Ok(output_bytes) =
+                            fs::read(std::path::Path::new(&destination_dir).join(&versioned_name))
+                        {
+                            versioned_assets.insert(
+                                format!("asset_integrity.{file_name}"),
+                                sri_hash_sha384(&output_bytes),
+                            );
+                        }
+                    }
+
                     versioned_assets.insert(file_name.to_string(), versioned_name);
                 }
             }
         }
     }
 
+    if !config.force_rebuild {
+        let _ = manifest.save(&manifest_path);
+    }
+
     Ok(versioned_assets)
 }
 
@@ -331,7 +674,7 @@ fn copy_data(site_name: &str, config: &SiteConfig) -> Result<()> {
     Ok(())
 }
 
-pub fn generate(site_name: &str, config: &SiteConfig) -> Result<()> {
+pub fn generate(site_name: &str, config: &SiteConfig) -> Result<SitemapSink> {
     // Start timing the generation process
     let start_time = Instant::now();
 
@@ -355,7 +698,7 @@ pub fn generate(site_name: &str, config: &SiteConfig) -> Result<()> {
         setup_global_variables(&content, versioned_assets, generated_date, config);
 
     // Generate all content
-    generate_site_content(
+    let sitemap = generate_site_content(
         site_name,
         &content,
         &global_variables,
@@ -363,6 +706,31 @@ pub fn generate(site_name: &str, config: &SiteConfig) -> Result<()> {
         config,
     )?;
 
+    // Write the combined sitemap.xml (posts, pages, and every pagination/
+    // taxonomy listing page recorded above)
+    generate_sitemap(
+        site_name,
+        &content.posts,
+        &content.pages,
+        &global_variables,
+        &sitemap,
+        config,
+    )?;
+
+    // Publish the same posts to the small-web ecosystem: a Gemini capsule
+    // and a Gopher hole alongside the HTML site.
+    generate_gemini_capsule(&content.posts, &content.includes, &global_variables)?;
+    generate_gopher_hole(&content.posts, &content.includes, &global_variables)?;
+
+    // Write the site-wide syndication feed(s) and one per taxonomy term.
+    generate_feeds(
+        site_name,
+        &content.posts,
+        &content.includes,
+        &global_variables,
+    )?;
+    generate_taxonomy_feeds(&content.posts, &content.includes, &global_variables, config)?;
+
     // Log the total generation time
     let elapsed = start_time.elapsed();
     println!(
@@ -371,7 +739,7 @@ pub fn generate(site_name: &str, config: &SiteConfig) -> Result<()> {
         elapsed.as_millis()
     );
 
-    Ok(())
+    Ok(sitemap)
 }
 
 #[cfg(test)]
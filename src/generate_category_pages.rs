@@ -1,7 +1,13 @@
 use crate::{
     config::SiteConfig,
     error::Result,
-    layout::load_and_render_pagination_layout,
+    layout::{load_and_render_pagination_layout, LayoutCache},
+    pagination::{
+        add_pagination_navigation_to_variables, add_posts_collection_to_variables,
+        chunk_posts_for_pagination, language_prefix, pagination_output_subdir,
+        pagination_page_url, resolve_posts_per_page, sort_posts_by_mode, PaginationSettings,
+        SitemapSink,
+    },
     render_page::render_page,
     template_processors::process_template_tags,
     types::{ContentCollection, ContentItem, TemplateIncludes, Variables},
@@ -45,6 +51,7 @@ pub fn group_posts_by_category(
 }
 
 /// Generate pagination pages for a single category
+#[allow(clippy::too_many_arguments)]
 fn generate_category_pagination_pages(
     site_name: &str,
     category_slug: &str,
@@ -55,16 +62,26 @@ fn generate_category_pagination_pages(
     main_layout: &str,
     global_variables: &Variables,
     config: &SiteConfig,
+    sitemap: &mut SitemapSink,
 ) -> Result<()> {
-    let total_pages = posts.len().div_ceil(posts_per_page);
+    let settings = PaginationSettings::from_variables(global_variables, &config.paginate_path, config.pagination_index_first_page);
+    let posts_per_page = resolve_posts_per_page(posts_per_page, global_variables);
+    let lang = language_prefix(global_variables);
+    let lang_url_prefix = lang.map_or(String::new(), |lang| format!("/{lang}"));
+    let lang_output_prefix = lang.map_or(String::new(), |lang| format!("{lang}/"));
+    let taxonomy_path_prefix = &config.taxonomy_path_prefix;
+    let base_url = format!("{lang_url_prefix}/{taxonomy_path_prefix}/{category_slug}");
+    let output_prefix = format!("{lang_output_prefix}{taxonomy_path_prefix}/{category_slug}/");
 
-    for page_num in 1..=total_pages {
-        let start = (page_num - 1) * posts_per_page;
-        let end = std::cmp::min(start + posts_per_page, posts.len());
-        let page_posts = &posts[start..end];
+    let mut sorted_posts = posts.clone();
+    sort_posts_by_mode(&mut sorted_posts, config.default_sort_mode);
 
+    let mut layout_cache = LayoutCache::new();
+
+    for (page_num, total_pages, page_posts) in chunk_posts_for_pagination(posts_per_page, &sorted_posts) {
         // Create context variables for category pagination template
         let mut variables = global_variables.clone();
+        variables.insert("posts_per_page".to_string(), posts_per_page.to_string());
         variables.insert(
             "title".to_string(),
             if page_num == 1 {
@@ -89,35 +106,17 @@ fn generate_category_pagination_pages(
         variables.insert("site_name".to_string(), site_name.to_string());
         variables.insert("category_name".to_string(), category_name.to_string());
         variables.insert("category_slug".to_string(), category_slug.to_string());
-        variables.insert("page_number".to_string(), page_num.to_string());
-        variables.insert("total_pages".to_string(), total_pages.to_string());
-
-        // Add pagination navigation context for categories
-        let has_previous = page_num > 1;
-        let has_next = page_num < total_pages;
-        variables.insert("has_previous".to_string(), has_previous.to_string());
-        variables.insert("has_next".to_string(), has_next.to_string());
-        
-        if has_previous {
-            let prev_page = page_num - 1;
-            let prev_url = format!("/category/{category_slug}/page{prev_page}");
-            variables.insert("previous_page_number".to_string(), prev_page.to_string());
-            variables.insert("previous_page_url".to_string(), prev_url);
-        }
-        
-        if has_next {
-            let next_page = page_num + 1;
-            let next_url = format!("/category/{category_slug}/page{next_page}");
-            variables.insert("next_page_number".to_string(), next_page.to_string());
-            variables.insert("next_page_url".to_string(), next_url);
-        }
 
         // Add category-specific navigation URLs
-        variables.insert("category_index_url".to_string(), format!("/category/{category_slug}/page1"));
+        variables.insert(
+            "category_index_url".to_string(),
+            pagination_page_url(&base_url, settings.pagination_path, 1, settings.index_first_page),
+        );
         variables.insert("site_index_url".to_string(), "/".to_string());
 
         // Add posts collection to context
-        add_category_posts_collection_to_variables(&mut variables, "page_posts", page_posts);
+        add_posts_collection_to_variables(&mut variables, "page_posts", page_posts);
+        add_pagination_navigation_to_variables(&mut variables, &base_url, page_num, total_pages, &settings);
 
         // Try to render using category pagination layout template first, then fallback to regular pagination layout
         let body = if let Some(rendered_content) = load_and_render_pagination_layout(
@@ -126,6 +125,7 @@ fn generate_category_pagination_pages(
             &variables,
             includes,
             config,
+            &mut layout_cache,
         ) {
             rendered_content
         } else if let Some(rendered_content) = load_and_render_pagination_layout(
@@ -134,6 +134,7 @@ fn generate_category_pagination_pages(
             &variables,
             includes,
             config,
+            &mut layout_cache,
         ) {
             rendered_content
         } else {
@@ -145,20 +146,27 @@ fn generate_category_pagination_pages(
                 category_name,
                 category_slug,
                 includes,
+                config,
+                settings.index_first_page,
             )?
         };
 
         // Determine the output file name and path
-        let output_directory = format!(
-            "{}/{}/category/{}/",
-            config.output_dir, site_name, category_slug
-        );
-        let page_slug = format!("page{}", page_num);
+        let output_subdir =
+            pagination_output_subdir(&output_prefix, settings.pagination_path, page_num, settings.index_first_page);
+        let output_directory = format!("{}/{}/{}", config.output_dir, site_name, output_subdir);
+
+        sitemap.record(pagination_page_url(
+            &base_url,
+            settings.pagination_path,
+            page_num,
+            settings.index_first_page,
+        ));
 
         render_page(
             &body,
             &output_directory,
-            &page_slug,
+            "index",
             main_layout,
             includes,
             &variables,
@@ -170,6 +178,7 @@ fn generate_category_pagination_pages(
 }
 
 /// Generate pagination pages for all categories
+#[allow(clippy::too_many_arguments)]
 pub fn generate_category_pages(
     site_name: &str,
     posts_per_page: usize,
@@ -178,6 +187,7 @@ pub fn generate_category_pages(
     main_layout: &str,
     global_variables: &Variables,
     config: &SiteConfig,
+    sitemap: &mut SitemapSink,
 ) -> Result<()> {
     // Filter out unlisted posts for category pagination (same as main pagination)
     let filtered_posts: ContentCollection = posts
@@ -202,6 +212,7 @@ pub fn generate_category_pages(
             main_layout,
             global_variables,
             config,
+            sitemap,
         )?;
     }
 
@@ -315,34 +326,36 @@ mod tests {
             main_layout,
             &global_variables,
             &config,
+            &mut SitemapSink::default(),
         )
         .expect("Failed to generate category pages");
 
         // Check that travel category pages were created (3 posts, 2 per page = 2 pages)
-        assert!(Path::new("out/category-test/category/travel/page1.html").exists());
-        assert!(Path::new("out/category-test/category/travel/page2.html").exists());
-        assert!(!Path::new("out/category-test/category/travel/page3.html").exists());
+        assert!(Path::new("out/category-test/category/travel/page/1/index.html").exists());
+        assert!(Path::new("out/category-test/category/travel/page/2/index.html").exists());
+        assert!(!Path::new("out/category-test/category/travel/page/3/index.html").exists());
 
         // Check that music category pages were created (2 posts, 2 per page = 1 page)
-        assert!(Path::new("out/category-test/category/music/page1.html").exists());
-        assert!(!Path::new("out/category-test/category/music/page2.html").exists());
+        assert!(Path::new("out/category-test/category/music/page/1/index.html").exists());
+        assert!(!Path::new("out/category-test/category/music/page/2/index.html").exists());
 
-        // Check the content of travel category index page
+        // Default sort mode is newest-date-first, so the most recent posts
+        // land on page 1.
         let travel_index_content =
-            fs::read_to_string("out/category-test/category/travel/page1.html").unwrap();
+            fs::read_to_string("out/category-test/category/travel/page/1/index.html").unwrap();
         assert!(travel_index_content.contains("Posts in category:"));
         assert!(travel_index_content.contains("<strong>Travel</strong>"));
-        assert!(travel_index_content.contains("Travel Post 1"));
+        assert!(travel_index_content.contains("Travel Post 3"));
         assert!(travel_index_content.contains("Travel Post 2"));
-        assert!(!travel_index_content.contains("Travel Post 3")); // Should be on page 2
+        assert!(!travel_index_content.contains("Travel Post 1")); // Oldest - on page 2
 
         // Check the content of travel category page 2
         let travel_page2_content =
-            fs::read_to_string("out/category-test/category/travel/page2.html").unwrap();
+            fs::read_to_string("out/category-test/category/travel/page/2/index.html").unwrap();
         assert!(travel_page2_content.contains("Posts in category:"));
         assert!(travel_page2_content.contains("<strong>Travel</strong>"));
-        assert!(travel_page2_content.contains("Travel Post 3"));
-        assert!(!travel_page2_content.contains("Travel Post 1")); // Should be on page 1
+        assert!(travel_page2_content.contains("Travel Post 1"));
+        assert!(!travel_page2_content.contains("Travel Post 3")); // Newest - on page 1
 
         // Check that uncategorized posts don't get category pages
         assert!(!Path::new("out/category-test/category/uncategorized").exists());
@@ -378,23 +391,59 @@ mod tests {
             main_layout,
             &global_variables,
             &config,
+            &mut SitemapSink::default(),
         )
         .expect("Failed to generate category pages");
 
         // Check that category pages were created
-        assert!(Path::new("out/test/category/technology/page1.html").exists());
-        assert!(Path::new("out/test/category/technology/page2.html").exists());
+        assert!(Path::new("out/test/category/technology/page/1/index.html").exists());
+        assert!(Path::new("out/test/category/technology/page/2/index.html").exists());
 
-        // Verify that the custom layout would be used (if available)
-        let page1_content = fs::read_to_string("out/test/category/technology/page1.html").unwrap();
-        // Should contain category-specific content regardless of template
-        assert!(page1_content.contains("Tech Post 1"));
-        assert!(!page1_content.contains("Tech Post 2"));
+        // Verify that the custom layout would be used (if available). Newest
+        // post first (default sort mode) puts "Tech Post 2" on page 1.
+        let page1_content = fs::read_to_string("out/test/category/technology/page/1/index.html").unwrap();
+        assert!(page1_content.contains("Tech Post 2"));
+        assert!(!page1_content.contains("Tech Post 1"));
 
         // Clean up
         let _ = fs::remove_dir_all(&config.output_dir);
     }
 
+    #[test]
+    fn test_category_pagination_nests_output_under_configured_language() {
+        let posts = vec![
+            create_test_post_with_category("Voyage Post 1", "2024-01-01", Some("Voyage")),
+            create_test_post_with_category("Voyage Post 2", "2024-01-02", Some("Voyage")),
+        ];
+
+        let includes = HashMap::new();
+        let main_layout = "<!DOCTYPE html><html><body>{{body}}</body></html>";
+        let mut global_variables = HashMap::new();
+        global_variables.insert("title".to_string(), "Test Site".to_string());
+        global_variables.insert("lang".to_string(), "fr".to_string());
+        let config = SiteConfig::default();
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+
+        generate_category_pages(
+            "test",
+            1,
+            &posts,
+            &includes,
+            main_layout,
+            &global_variables,
+            &config,
+            &mut SitemapSink::default(),
+        )
+        .expect("Failed to generate category pages");
+
+        assert!(Path::new("out/test/fr/category/voyage/page/1/index.html").exists());
+        assert!(!Path::new("out/test/category/voyage/page/1/index.html").exists());
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
+
     #[test]
     fn test_category_pagination_layout_fallback_behavior() {
         let posts = vec![
@@ -423,11 +472,12 @@ mod tests {
             main_layout,
             &global_variables,
             &config,
+            &mut SitemapSink::default(),
         )
         .expect("Failed to generate category pages with fallback");
 
         // Verify that the page was created with fallback HTML
-        let page1_path = Path::new("out/test/category/test-category/page1.html");
+        let page1_path = Path::new("out/test/category/test-category/page/1/index.html");
         assert!(page1_path.exists());
         
         let page1_content = fs::read_to_string(&page1_path).unwrap();
@@ -439,9 +489,89 @@ mod tests {
         // Clean up
         let _ = fs::remove_dir_all(&config.output_dir);
     }
+
+    #[test]
+    fn test_category_pagination_honors_configured_path_segments() {
+        let posts = vec![
+            create_test_post_with_category("Post 1", "2024-01-01", Some("Travel")),
+            create_test_post_with_category("Post 2", "2024-01-02", Some("Travel")),
+        ];
+
+        let includes = HashMap::new();
+        let main_layout = "<!DOCTYPE html><html><body>{{body}}</body></html>";
+        let mut global_variables = HashMap::new();
+        global_variables.insert("title".to_string(), "Test Site".to_string());
+        let mut config = SiteConfig::default();
+        config.taxonomy_path_prefix = "topics".to_string();
+        config.paginate_path = "p".to_string();
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+
+        generate_category_pages(
+            "test",
+            1,
+            &posts,
+            &includes,
+            main_layout,
+            &global_variables,
+            &config,
+            &mut SitemapSink::default(),
+        )
+        .expect("Failed to generate category pages");
+
+        assert!(Path::new("out/test/topics/travel/p/1/index.html").exists());
+        assert!(Path::new("out/test/topics/travel/p/2/index.html").exists());
+        assert!(!Path::new("out/test/category/travel/page/1/index.html").exists());
+
+        let page1_content = fs::read_to_string("out/test/topics/travel/p/1/index.html").unwrap();
+        assert!(page1_content.contains("href=\"/topics/travel/p/2/\""));
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
+
+    #[test]
+    fn test_category_pagination_index_first_page_writes_index_at_category_root() {
+        let posts = vec![
+            create_test_post_with_category("Post 1", "2024-01-01", Some("Travel")),
+            create_test_post_with_category("Post 2", "2024-01-02", Some("Travel")),
+        ];
+
+        let includes = HashMap::new();
+        let main_layout = "<!DOCTYPE html><html><body>{{body}}</body></html>";
+        let global_variables = HashMap::new();
+        let mut config = SiteConfig::default();
+        config.pagination_index_first_page = true;
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+
+        generate_category_pages(
+            "test",
+            1,
+            &posts,
+            &includes,
+            main_layout,
+            &global_variables,
+            &config,
+            &mut SitemapSink::default(),
+        )
+        .expect("Failed to generate category pages");
+
+        assert!(Path::new("out/test/category/travel/index.html").exists());
+        assert!(Path::new("out/test/category/travel/page/2/index.html").exists());
+        assert!(!Path::new("out/test/category/travel/page/1/index.html").exists());
+
+        let page1_content = fs::read_to_string("out/test/category/travel/index.html").unwrap();
+        assert!(page1_content.contains("href=\"/category/travel/page/2/\""));
+        assert!(page1_content.contains("href=\"/category/travel/\""));
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
 }
 
 /// Generates the original hardcoded category pagination HTML as a fallback
+#[allow(clippy::too_many_arguments)]
 fn generate_fallback_category_pagination_html(
     page_posts: &[ContentItem],
     page_num: usize,
@@ -449,6 +579,8 @@ fn generate_fallback_category_pagination_html(
     category_name: &str,
     category_slug: &str,
     includes: &TemplateIncludes,
+    config: &SiteConfig,
+    index_first_page: bool,
 ) -> Result<String> {
     let mut html_list = String::new();
 
@@ -469,10 +601,13 @@ fn generate_fallback_category_pagination_html(
     ));
     html_list.push_str("<p>This site uses classic pagination on purpose to help you stop when you want to. Doomscrolling not included.</p><ul class=\"pagination\">");
 
+    let taxonomy_path_prefix = &config.taxonomy_path_prefix;
+    let paginate_path = config.paginate_path.as_str();
+    let base_url = format!("/{taxonomy_path_prefix}/{category_slug}");
+
     // Previous page link
     if page_num > 1 {
-        let prev_page = page_num - 1;
-        let prev_url = format!("/category/{category_slug}/page{prev_page}");
+        let prev_url = pagination_page_url(&base_url, paginate_path, page_num - 1, index_first_page);
         write!(
             html_list,
             "<li><a href=\"{prev_url}\">🔙 Previous page</a>,&nbsp;</li>"
@@ -481,9 +616,10 @@ fn generate_fallback_category_pagination_html(
     }
 
     // Index page link for this category
+    let index_url = pagination_page_url(&base_url, paginate_path, 1, index_first_page);
     write!(
         html_list,
-        "<li><a href=\"/category/{category_slug}/page1\">Category index</a>,&nbsp;</li>"
+        "<li><a href=\"{index_url}\">Category index</a>,&nbsp;</li>"
     )
     .unwrap();
 
@@ -492,16 +628,16 @@ fn generate_fallback_category_pagination_html(
 
     // Page numbers
     for i in 1..=total_pages {
-        let page_url = format!("/category/{category_slug}/page{i}");
+        let page_url = pagination_page_url(&base_url, paginate_path, i, index_first_page);
         write!(html_list, "<li><a href=\"{page_url}\">{i}</a>,&nbsp;</li>").unwrap();
     }
 
     // Next page link
     if page_num < total_pages {
-        let next_page = page_num + 1;
+        let next_url = pagination_page_url(&base_url, paginate_path, page_num + 1, index_first_page);
         write!(
             html_list,
-            "<li><a href=\"/category/{category_slug}/page{next_page}\">Next page ⏭️</a></li>"
+            "<li><a href=\"{next_url}\">Next page ⏭️</a></li>"
         )
         .unwrap();
     }
@@ -510,17 +646,3 @@ fn generate_fallback_category_pagination_html(
 
     Ok(html_list)
 }
-
-/// Adds a posts collection to variables for category template access
-fn add_category_posts_collection_to_variables(
-    variables: &mut Variables,
-    collection_name: &str,
-    posts: &[ContentItem],
-) {
-    for (index, post) in posts.iter().enumerate() {
-        for (key, value) in post {
-            let variable_name = format!("{}.{}.{}", collection_name, index, key);
-            variables.insert(variable_name, value.clone());
-        }
-    }
-}
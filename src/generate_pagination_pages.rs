@@ -1,11 +1,18 @@
 use crate::{
     config::SiteConfig,
     error::Result,
-    layout::load_and_render_pagination_layout,
+    layout::{load_and_render_pagination_layout, LayoutCache},
+    pagination::{
+        add_pagination_navigation_to_variables, add_posts_collection_to_variables,
+        chunk_posts_for_pagination, language_prefix, pagination_output_subdir,
+        pagination_page_url, resolve_posts_per_page, sort_posts_by_post_sort_order,
+        PaginationSettings, SitemapSink,
+    },
     render_page::render_page,
-    types::{ContentCollection, ContentItem, TemplateIncludes, Variables},
+    types::{ContentCollection, TemplateIncludes, Variables},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn generate_pagination_pages(
     site_name: &str,
     posts_per_page: usize,
@@ -14,67 +21,36 @@ pub fn generate_pagination_pages(
     main_layout: &str,
     global_variables: &Variables,
     config: &SiteConfig,
+    sitemap: &mut SitemapSink,
 ) -> Result<()> {
     // Check if pagination layout is configured, if not, skip pagination generation
     if !global_variables.contains_key("pagination_layout") {
         return Ok(()); // Skip pagination generation
     }
 
-    let total_pages = posts.len().div_ceil(posts_per_page);
+    let settings = PaginationSettings::from_variables(global_variables, &config.paginate_path, config.pagination_index_first_page);
+    let posts_per_page = resolve_posts_per_page(posts_per_page, global_variables);
+    // Pages without a configured language keep the unprefixed, single-language
+    // layout; a `lang` pushes both the URL and output directory under it so
+    // languages don't overwrite each other's pages.
+    let lang = language_prefix(global_variables);
+    let base_url = lang.map_or(String::new(), |lang| format!("/{lang}"));
+    let output_prefix = lang.map_or(String::new(), |lang| format!("{lang}/"));
 
-    for page_num in 1..=total_pages {
-        let start = (page_num - 1) * posts_per_page;
-        let end = std::cmp::min(start + posts_per_page, posts.len());
-        let page_posts = &posts[start..end];
+    let mut sorted_posts = posts.clone();
+    sort_posts_by_post_sort_order(&mut sorted_posts, config.post_sort_order);
 
+    let mut layout_cache = LayoutCache::new();
+
+    for (page_num, total_pages, page_posts) in chunk_posts_for_pagination(posts_per_page, &sorted_posts) {
         // Create context variables for pagination template
         let mut variables = global_variables.clone();
         variables.insert("title".to_string(), format!("Page {page_num}"));
         variables.insert("site_name".to_string(), site_name.to_string());
-        variables.insert("page_number".to_string(), page_num.to_string());
-        variables.insert("total_pages".to_string(), total_pages.to_string());
-
-        // Add pagination navigation context
-        let has_previous = page_num > 1;
-        let has_next = page_num < total_pages;
-        variables.insert("has_previous".to_string(), has_previous.to_string());
-        variables.insert("has_next".to_string(), has_next.to_string());
-
-        if has_previous {
-            let prev_page = page_num - 1;
-            variables.insert("previous_page_number".to_string(), prev_page.to_string());
-            variables.insert("previous_page_url".to_string(), format!("/page{prev_page}"));
-        }
-
-        if has_next {
-            let next_page = page_num + 1;
-            variables.insert("next_page_number".to_string(), next_page.to_string());
-            variables.insert("next_page_url".to_string(), format!("/page{next_page}"));
-        }
+        variables.insert("posts_per_page".to_string(), posts_per_page.to_string());
 
-        // Add posts collection to context
         add_posts_collection_to_variables(&mut variables, "page_posts", page_posts);
-
-        // Add page numbers collection for iteration in templates
-        add_page_links_collection_to_variables(
-            &mut variables,
-            "page_numbers",
-            page_num,
-            total_pages,
-        );
-
-        // Add page navigation links (JSON format for backwards compatibility)
-        let mut page_links = Vec::new();
-        for i in 1..=total_pages {
-            page_links.push(format!(
-                "{{\"number\": {i}, \"url\": \"/page{i}\", \"current\": {}}}",
-                if i == page_num { "true" } else { "false" }
-            ));
-        }
-        variables.insert(
-            "page_links".to_string(),
-            format!("[{}]", page_links.join(", ")),
-        );
+        add_pagination_navigation_to_variables(&mut variables, &base_url, page_num, total_pages, &settings);
 
         // Try to render using pagination layout template
         let body = match load_and_render_pagination_layout(
@@ -83,15 +59,28 @@ pub fn generate_pagination_pages(
             &variables,
             includes,
             config,
+            &mut layout_cache,
         )? {
             Some(rendered_content) => rendered_content,
             None => return Ok(()), // This should not happen since we check above, but handle it gracefully
         };
 
+        let output_subdir = pagination_output_subdir(
+            &output_prefix,
+            settings.pagination_path,
+            page_num,
+            settings.index_first_page,
+        );
+        sitemap.record(pagination_page_url(
+            &base_url,
+            settings.pagination_path,
+            page_num,
+            settings.index_first_page,
+        ));
         render_page(
             &body,
-            &format!("{}/{site_name}/", config.output_dir),
-            &format!("page{page_num}"),
+            &format!("{}/{site_name}/{output_subdir}", config.output_dir),
+            "index",
             main_layout,
             includes,
             &variables,
@@ -102,48 +91,6 @@ pub fn generate_pagination_pages(
     Ok(())
 }
 
-/// Adds a posts collection to variables for template access
-fn add_posts_collection_to_variables(
-    variables: &mut Variables,
-    collection_name: &str,
-    posts: &[ContentItem],
-) {
-    for (index, post) in posts.iter().enumerate() {
-        for (key, value) in post {
-            let variable_name = format!("{}.{}.{}", collection_name, index, key);
-            variables.insert(variable_name, value.clone());
-        }
-    }
-}
-
-/// Adds page link variables for template iteration
-fn add_page_links_collection_to_variables(
-    variables: &mut Variables,
-    collection_name: &str,
-    current_page: usize,
-    total_pages: usize,
-) {
-    for page_num in 1..=total_pages {
-        let index = page_num - 1; // 0-based index
-        variables.insert(
-            format!("{}.{}.number", collection_name, index),
-            page_num.to_string(),
-        );
-        variables.insert(
-            format!("{}.{}.url", collection_name, index),
-            format!("/page{}", page_num),
-        );
-        variables.insert(
-            format!("{}.{}.current", collection_name, index),
-            if page_num == current_page {
-                "true".to_string()
-            } else {
-                "false".to_string()
-            },
-        );
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,28 +140,29 @@ mod tests {
             main_layout,
             &global_variables,
             &config,
+            &mut SitemapSink::default(),
         )
         .expect("Failed to generate pagination pages");
 
         // Verify the pages were created
-        assert!(Path::new("out/test/page1.html").exists());
-        assert!(Path::new("out/test/page2.html").exists());
-        assert!(Path::new("out/test/page3.html").exists());
+        assert!(Path::new("out/test/page/1/index.html").exists());
+        assert!(Path::new("out/test/page/2/index.html").exists());
+        assert!(Path::new("out/test/page/3/index.html").exists());
 
         // Verify page contents
-        let page1_content = fs::read_to_string("out/test/page1.html").unwrap();
+        let page1_content = fs::read_to_string("out/test/page/1/index.html").unwrap();
         assert!(page1_content.contains("Test Post 1"));
         assert!(page1_content.contains("Test Post 2"));
         assert!(page1_content.contains("Test Post 3"));
         assert!(!page1_content.contains("Test Post 4"));
 
-        let page2_content = fs::read_to_string("out/test/page2.html").unwrap();
+        let page2_content = fs::read_to_string("out/test/page/2/index.html").unwrap();
         assert!(page2_content.contains("Test Post 4"));
         assert!(page2_content.contains("Test Post 5"));
         assert!(page2_content.contains("Test Post 6"));
         assert!(!page2_content.contains("Test Post 7"));
 
-        let page3_content = fs::read_to_string("out/test/page3.html").unwrap();
+        let page3_content = fs::read_to_string("out/test/page/3/index.html").unwrap();
         assert!(page3_content.contains("Test Post 7"));
         assert!(!page3_content.contains("Test Post 1"));
 
@@ -253,11 +201,12 @@ mod tests {
             main_layout,
             &global_variables,
             &config,
+            &mut SitemapSink::default(),
         )
         .expect("Failed to generate pagination pages with legacy key");
 
         let test_dir = Path::new(&config.output_dir).join("test");
-        let page1_path = test_dir.join("page1.html");
+        let page1_path = test_dir.join("page/1/index.html");
         assert!(
             page1_path.exists(),
             "expected {} to exist",
@@ -266,7 +215,7 @@ mod tests {
         let page1_content = fs::read_to_string(&page1_path).unwrap();
         assert!(page1_content.contains("Legacy Post 1"));
 
-        let page2_path = test_dir.join("page2.html");
+        let page2_path = test_dir.join("page/2/index.html");
         assert!(
             page2_path.exists(),
             "expected {} to exist",
@@ -306,22 +255,23 @@ mod tests {
             main_layout,
             &global_variables,
             &config,
+            &mut SitemapSink::default(),
         )
         .expect("Failed to generate pagination pages");
 
         // Verify that pagination pages were created
-        assert!(Path::new("out/test/page1.html").exists());
-        assert!(Path::new("out/test/page2.html").exists());
-        assert!(Path::new("out/test/page3.html").exists());
+        assert!(Path::new("out/test/page/1/index.html").exists());
+        assert!(Path::new("out/test/page/2/index.html").exists());
+        assert!(Path::new("out/test/page/3/index.html").exists());
 
         // Verify that the custom layout is being used (if available)
-        let page1_content = fs::read_to_string("out/test/page1.html").unwrap();
+        let page1_content = fs::read_to_string("out/test/page/1/index.html").unwrap();
         // The test should work regardless of whether the layout file exists
         // (it will fall back to hardcoded HTML if not found)
         assert!(page1_content.contains("Test Post 1"));
         assert!(!page1_content.contains("Test Post 2"));
 
-        let page2_content = fs::read_to_string("out/test/page2.html").unwrap();
+        let page2_content = fs::read_to_string("out/test/page/2/index.html").unwrap();
         assert!(page2_content.contains("Test Post 2"));
         assert!(!page2_content.contains("Test Post 1"));
 
@@ -329,6 +279,199 @@ mod tests {
         let _ = fs::remove_dir_all(&config.output_dir);
     }
 
+    #[test]
+    fn test_pagination_with_custom_path() {
+        let mut posts = Vec::new();
+        for i in 1..=4 {
+            posts.push(create_test_post(&format!("Archive Post {i}"), "2024-03-20"));
+        }
+
+        let includes = load_liquid_includes("./sites/test/includes");
+        let main_layout = "<!DOCTYPE html><html><body>{{body}}</body></html>";
+        let mut global_variables = HashMap::new();
+        global_variables.insert("site_title".to_string(), "Test Site".to_string());
+        global_variables.insert("pagination_layout".to_string(), "pagination".to_string());
+        global_variables.insert("pagination_path".to_string(), "archive".to_string());
+        let config = SiteConfig::default();
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+
+        generate_pagination_pages(
+            "test",
+            2,
+            &posts,
+            &includes,
+            main_layout,
+            &global_variables,
+            &config,
+            &mut SitemapSink::default(),
+        )
+        .expect("Failed to generate pagination pages");
+
+        assert!(Path::new("out/test/archive/1/index.html").exists());
+        assert!(Path::new("out/test/archive/2/index.html").exists());
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
+
+    #[test]
+    fn test_pagination_with_index_first_page() {
+        let mut posts = Vec::new();
+        for i in 1..=4 {
+            posts.push(create_test_post(&format!("Home Post {i}"), "2024-03-20"));
+        }
+
+        let includes = load_liquid_includes("./sites/test/includes");
+        let main_layout = "<!DOCTYPE html><html><body>{{body}}</body></html>";
+        let mut global_variables = HashMap::new();
+        global_variables.insert("site_title".to_string(), "Test Site".to_string());
+        global_variables.insert("pagination_layout".to_string(), "pagination".to_string());
+        global_variables.insert("pagination_index_first_page".to_string(), "true".to_string());
+        let config = SiteConfig::default();
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+
+        generate_pagination_pages(
+            "test",
+            2,
+            &posts,
+            &includes,
+            main_layout,
+            &global_variables,
+            &config,
+            &mut SitemapSink::default(),
+        )
+        .expect("Failed to generate pagination pages");
+
+        // Page 1 renders at the section index instead of /page/1/.
+        assert!(Path::new("out/test/index.html").exists());
+        assert!(Path::new("out/test/page/2/index.html").exists());
+        assert!(!Path::new("out/test/page/1/index.html").exists());
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
+
+    #[test]
+    fn test_pagination_posts_per_page_override_takes_priority_over_default() {
+        let mut posts = Vec::new();
+        for i in 1..=6 {
+            posts.push(create_test_post(&format!("Sized Post {i}"), "2024-03-20"));
+        }
+
+        let includes = load_liquid_includes("./sites/test/includes");
+        let main_layout = "<!DOCTYPE html><html><body>{{body}}</body></html>";
+        let mut global_variables = HashMap::new();
+        global_variables.insert("site_title".to_string(), "Test Site".to_string());
+        global_variables.insert("pagination_layout".to_string(), "pagination".to_string());
+        // Overrides the 3-per-page default passed into the call below.
+        global_variables.insert("pagination_posts_per_page".to_string(), "2".to_string());
+        let config = SiteConfig::default();
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+
+        generate_pagination_pages(
+            "test",
+            3,
+            &posts,
+            &includes,
+            main_layout,
+            &global_variables,
+            &config,
+            &mut SitemapSink::default(),
+        )
+        .expect("Failed to generate pagination pages");
+
+        // 6 posts at 2 per page (the override) makes 3 pages, not 2.
+        assert!(Path::new("out/test/page/1/index.html").exists());
+        assert!(Path::new("out/test/page/2/index.html").exists());
+        assert!(Path::new("out/test/page/3/index.html").exists());
+
+        let page1_content = fs::read_to_string("out/test/page/1/index.html").unwrap();
+        assert!(page1_content.contains("Sized Post 1"));
+        assert!(page1_content.contains("Sized Post 2"));
+        assert!(!page1_content.contains("Sized Post 3"));
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
+
+    #[test]
+    fn test_pagination_nests_output_under_configured_language() {
+        let mut posts = Vec::new();
+        for i in 1..=4 {
+            posts.push(create_test_post(&format!("Lang Post {i}"), "2024-03-20"));
+        }
+
+        let includes = load_liquid_includes("./sites/test/includes");
+        let main_layout = "<!DOCTYPE html><html><body>{{body}}</body></html>";
+        let mut global_variables = HashMap::new();
+        global_variables.insert("site_title".to_string(), "Test Site".to_string());
+        global_variables.insert("pagination_layout".to_string(), "pagination".to_string());
+        global_variables.insert("lang".to_string(), "fr".to_string());
+        let config = SiteConfig::default();
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+
+        generate_pagination_pages(
+            "test",
+            2,
+            &posts,
+            &includes,
+            main_layout,
+            &global_variables,
+            &config,
+            &mut SitemapSink::default(),
+        )
+        .expect("Failed to generate pagination pages");
+
+        assert!(Path::new("out/test/fr/page/1/index.html").exists());
+        assert!(Path::new("out/test/fr/page/2/index.html").exists());
+        assert!(!Path::new("out/test/page/1/index.html").exists());
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
+
+    #[test]
+    fn test_pagination_windowed_pager_is_opt_in() {
+        let mut posts = Vec::new();
+        for i in 1..=10 {
+            posts.push(create_test_post(&format!("Windowed Post {i}"), "2024-03-20"));
+        }
+
+        let includes = load_liquid_includes("./sites/test/includes");
+        let main_layout = "<!DOCTYPE html><html><body>{{body}}</body></html>";
+        let mut global_variables = HashMap::new();
+        global_variables.insert("site_title".to_string(), "Test Site".to_string());
+        global_variables.insert("pagination_layout".to_string(), "pagination".to_string());
+        global_variables.insert("pagination_windowed".to_string(), "true".to_string());
+        global_variables.insert("pagination_window".to_string(), "1".to_string());
+        let config = SiteConfig::default();
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+
+        // 10 posts, 1 per page -> 10 pages; page 1 requested, window 1 means
+        // only pages 1 and 2 (plus the always-visible last page 10) show up.
+        generate_pagination_pages(
+            "test",
+            1,
+            &posts,
+            &includes,
+            main_layout,
+            &global_variables,
+            &config,
+            &mut SitemapSink::default(),
+        )
+        .expect("Failed to generate pagination pages");
+
+        assert!(Path::new("out/test/page/1/index.html").exists());
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
+
     #[test]
     fn test_pagination_layout_missing_file_error() {
         // Create test data
@@ -359,6 +502,7 @@ mod tests {
             main_layout,
             &global_variables,
             &config,
+            &mut SitemapSink::default(),
         );
 
         // Should return an error for missing layout file
@@ -397,13 +541,14 @@ mod tests {
             main_layout,
             &global_variables,
             &config,
+            &mut SitemapSink::default(),
         );
 
         // Should succeed without generating any pagination pages
         assert!(result.is_ok());
 
         // No pagination pages should be created
-        assert!(!Path::new("out/test/page1.html").exists());
+        assert!(!Path::new("out/test/page/1/index.html").exists());
 
         // Clean up
         let _ = fs::remove_dir_all(&config.output_dir);
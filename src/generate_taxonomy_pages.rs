@@ -0,0 +1,559 @@
+//! Generalized version of [`crate::generate_category_pages`]: instead of a
+//! single hardcoded `category` front-matter field, this loops over every
+//! [`TaxonomyConfig`] in [`SiteConfig::taxonomies`] and generates paginated
+//! term pages for each, sharing one pagination loop rather than duplicating
+//! it per taxonomy. A post can belong to several terms at once when its
+//! taxonomy is multi-valued (e.g. `tags: rust, web` becomes membership in
+//! both the `rust` and `web` terms).
+
+use crate::{
+    config::{SiteConfig, TaxonomyConfig},
+    error::Result,
+    layout::{load_and_render_pagination_layout, LayoutCache},
+    pagination::{
+        add_pagination_navigation_to_variables, add_posts_collection_to_variables,
+        chunk_posts_for_pagination, language_prefix, pagination_output_subdir,
+        pagination_page_url, resolve_posts_per_page, sort_posts_by_mode, PaginationSettings,
+        SitemapSink, DEFAULT_PAGINATION_PATH,
+    },
+    render_page::render_page,
+    template_processors::process_template_tags,
+    types::{ContentCollection, ContentItem, TemplateIncludes, Variables},
+};
+use std::{collections::HashMap, fmt::Write};
+
+/// Convert a taxonomy term value to a URL-safe slug.
+fn slugify_term(term: &str) -> String {
+    term.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Groups posts by one taxonomy's front-matter field. When the taxonomy is
+/// multi-valued, the field is split on commas, each value trimmed and
+/// slugified, and the post is pushed into every resulting term's
+/// collection.
+pub fn group_posts_by_taxonomy(
+    posts: &ContentCollection,
+    taxonomy: &TaxonomyConfig,
+) -> HashMap<String, (String, ContentCollection)> {
+    let mut terms: HashMap<String, (String, ContentCollection)> = HashMap::new();
+
+    for post in posts {
+        let Some(raw_value) = post.get(&taxonomy.front_matter_key) else {
+            continue;
+        };
+
+        let values: Vec<&str> = if taxonomy.multi_valued {
+            raw_value
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .collect()
+        } else {
+            let trimmed = raw_value.trim();
+            if trimmed.is_empty() {
+                Vec::new()
+            } else {
+                vec![trimmed]
+            }
+        };
+
+        for value in values {
+            let slug = slugify_term(value);
+            terms
+                .entry(slug)
+                .or_insert_with(|| (value.to_string(), Vec::new()))
+                .1
+                .push(post.clone());
+        }
+    }
+
+    terms
+}
+
+/// Generate pagination pages for a single taxonomy term
+#[allow(clippy::too_many_arguments)]
+fn generate_taxonomy_term_pagination_pages(
+    site_name: &str,
+    taxonomy: &TaxonomyConfig,
+    term_slug: &str,
+    term_name: &str,
+    posts_per_page: usize,
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    main_layout: &str,
+    global_variables: &Variables,
+    config: &SiteConfig,
+    sitemap: &mut SitemapSink,
+) -> Result<()> {
+    let settings = PaginationSettings::from_variables(global_variables, &config.paginate_path, config.pagination_index_first_page);
+    let posts_per_page = if taxonomy.paginated {
+        resolve_posts_per_page(posts_per_page, global_variables)
+    } else {
+        posts.len().max(1)
+    };
+    let lang = language_prefix(global_variables);
+    let lang_url_prefix = lang.map_or(String::new(), |lang| format!("/{lang}"));
+    let lang_output_prefix = lang.map_or(String::new(), |lang| format!("{lang}/"));
+    let base_url = format!("{lang_url_prefix}/{}/{term_slug}", taxonomy.name);
+    let output_prefix = format!("{lang_output_prefix}{}/{term_slug}/", taxonomy.name);
+
+    let mut sorted_posts = posts.clone();
+    sort_posts_by_mode(&mut sorted_posts, taxonomy.sort_mode.unwrap_or(config.default_sort_mode));
+
+    let mut layout_cache = LayoutCache::new();
+
+    for (page_num, total_pages, page_posts) in chunk_posts_for_pagination(posts_per_page, &sorted_posts) {
+        let mut variables = global_variables.clone();
+        variables.insert("posts_per_page".to_string(), posts_per_page.to_string());
+        variables.insert(
+            "title".to_string(),
+            if page_num == 1 {
+                format!(
+                    "{} - {}: {}",
+                    global_variables
+                        .get("title")
+                        .unwrap_or(&"My Site".to_string()),
+                    taxonomy.name,
+                    term_name
+                )
+            } else {
+                format!(
+                    "{} - {}: {} - Page {}",
+                    global_variables
+                        .get("title")
+                        .unwrap_or(&"My Site".to_string()),
+                    taxonomy.name,
+                    term_name,
+                    page_num
+                )
+            },
+        );
+        variables.insert("site_name".to_string(), site_name.to_string());
+        variables.insert("taxonomy_name".to_string(), taxonomy.name.clone());
+        variables.insert("term_name".to_string(), term_name.to_string());
+        variables.insert("term_slug".to_string(), term_slug.to_string());
+
+        variables.insert(
+            "term_index_url".to_string(),
+            pagination_page_url(&base_url, settings.pagination_path, 1, settings.index_first_page),
+        );
+        variables.insert("site_index_url".to_string(), "/".to_string());
+
+        add_posts_collection_to_variables(&mut variables, "page_posts", page_posts);
+        add_pagination_navigation_to_variables(&mut variables, &base_url, page_num, total_pages, &settings);
+
+        // Try a layout specific to this taxonomy first (e.g.
+        // `tags_pagination_layout`), then the generic pagination layout,
+        // then fall back to hardcoded HTML.
+        let body = if let Some(rendered_content) = load_and_render_pagination_layout(
+            site_name,
+            global_variables.get(&format!("{}_pagination_layout", taxonomy.name)),
+            &variables,
+            includes,
+            config,
+            &mut layout_cache,
+        ) {
+            rendered_content
+        } else if let Some(rendered_content) = load_and_render_pagination_layout(
+            site_name,
+            global_variables.get("pagination_layout"),
+            &variables,
+            includes,
+            config,
+            &mut layout_cache,
+        ) {
+            rendered_content
+        } else {
+            generate_fallback_taxonomy_pagination_html(
+                page_posts,
+                page_num,
+                total_pages,
+                taxonomy,
+                term_name,
+                term_slug,
+                includes,
+            )?
+        };
+
+        let output_subdir =
+            pagination_output_subdir(&output_prefix, settings.pagination_path, page_num, settings.index_first_page);
+        let output_directory = format!("{}/{}/{}", config.output_dir, site_name, output_subdir);
+
+        sitemap.record(pagination_page_url(
+            &base_url,
+            settings.pagination_path,
+            page_num,
+            settings.index_first_page,
+        ));
+
+        render_page(
+            &body,
+            &output_directory,
+            "index",
+            main_layout,
+            includes,
+            &variables,
+            config,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Generate paginated term pages for every configured taxonomy.
+pub fn generate_taxonomy_pages(
+    site_name: &str,
+    posts_per_page: usize,
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    main_layout: &str,
+    global_variables: &Variables,
+    config: &SiteConfig,
+    sitemap: &mut SitemapSink,
+) -> Result<()> {
+    // Filter out unlisted posts for taxonomy pagination (same as main pagination)
+    let filtered_posts: ContentCollection = posts
+        .iter()
+        .filter(|post| {
+            post.get("unlisted")
+                .is_none_or(|value| value.to_lowercase() != "true")
+        })
+        .cloned()
+        .collect();
+
+    for taxonomy in &config.taxonomies {
+        let terms = group_posts_by_taxonomy(&filtered_posts, taxonomy);
+
+        generate_taxonomy_overview_page(
+            site_name,
+            taxonomy,
+            &terms,
+            includes,
+            main_layout,
+            global_variables,
+            config,
+            sitemap,
+        )?;
+
+        for (term_slug, (term_name, term_posts)) in terms {
+            generate_taxonomy_term_pagination_pages(
+                site_name,
+                taxonomy,
+                &term_slug,
+                &term_name,
+                posts_per_page,
+                &term_posts,
+                includes,
+                main_layout,
+                global_variables,
+                config,
+                sitemap,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Generate the overview page listing every term in a taxonomy along with
+/// its post count (e.g. `out/{site}/tags/index.html`). Looks for a
+/// `{taxonomy.name}_index_layout`-named layout first, then falls back to
+/// hardcoded HTML, in the same spirit as the term pagination pages above.
+fn generate_taxonomy_overview_page(
+    site_name: &str,
+    taxonomy: &TaxonomyConfig,
+    terms: &HashMap<String, (String, ContentCollection)>,
+    includes: &TemplateIncludes,
+    main_layout: &str,
+    global_variables: &Variables,
+    config: &SiteConfig,
+    sitemap: &mut SitemapSink,
+) -> Result<()> {
+    let mut term_names: Vec<&String> = terms.values().map(|(name, _)| name).collect();
+    term_names.sort();
+
+    let lang = language_prefix(global_variables);
+    let lang_url_prefix = lang.map_or(String::new(), |lang| format!("/{lang}"));
+    let lang_output_prefix = lang.map_or(String::new(), |lang| format!("{lang}/"));
+    let base_url = format!("{lang_url_prefix}/{}", taxonomy.name);
+
+    let mut variables = global_variables.clone();
+    variables.insert("site_name".to_string(), site_name.to_string());
+    variables.insert("taxonomy_name".to_string(), taxonomy.name.clone());
+    variables.insert("site_index_url".to_string(), "/".to_string());
+
+    let mut layout_cache = LayoutCache::new();
+    let body = if let Some(rendered_content) = load_and_render_pagination_layout(
+        site_name,
+        global_variables.get(&format!("{}_index_layout", taxonomy.name)),
+        &variables,
+        includes,
+        config,
+        &mut layout_cache,
+    ) {
+        rendered_content
+    } else {
+        generate_fallback_taxonomy_overview_html(taxonomy, terms, &term_names)
+    };
+
+    let output_directory = format!("{}/{site_name}/{lang_output_prefix}{}", config.output_dir, taxonomy.name);
+    sitemap.record(format!("{base_url}/"));
+
+    render_page(&body, &output_directory, "index", main_layout, includes, &variables, config)
+}
+
+/// Generates a hardcoded taxonomy overview page as a fallback, in the same
+/// spirit as [`generate_fallback_taxonomy_pagination_html`].
+fn generate_fallback_taxonomy_overview_html(
+    taxonomy: &TaxonomyConfig,
+    terms: &HashMap<String, (String, ContentCollection)>,
+    sorted_term_names: &[&String],
+) -> String {
+    let mut html_list = format!("<p>Browse by {}:</p><ul class=\"taxonomy-overview\">", taxonomy.name);
+
+    for term_name in sorted_term_names {
+        let term_slug = slugify_term(term_name);
+        let Some((_, term_posts)) = terms.get(&term_slug) else {
+            continue;
+        };
+        write!(
+            html_list,
+            "<li><a href=\"/{}/{term_slug}\">{term_name}</a> ({})</li>",
+            taxonomy.name,
+            term_posts.len()
+        )
+        .unwrap();
+    }
+
+    html_list.push_str("</ul>");
+    html_list
+}
+
+/// Generates hardcoded taxonomy term pagination HTML as a fallback, in the
+/// same spirit as [`crate::generate_category_pages`]'s fallback renderer.
+#[allow(clippy::too_many_arguments)]
+fn generate_fallback_taxonomy_pagination_html(
+    page_posts: &[ContentItem],
+    page_num: usize,
+    total_pages: usize,
+    taxonomy: &TaxonomyConfig,
+    term_name: &str,
+    term_slug: &str,
+    includes: &TemplateIncludes,
+) -> Result<String> {
+    let mut html_list = String::new();
+
+    for post in page_posts {
+        let post_template = includes
+            .get("post")
+            .or_else(|| includes.get("post.liquid"))
+            .map_or("", |s| s.as_str());
+
+        html_list.push_str(&process_template_tags(post_template, post, None, None)?);
+    }
+
+    html_list.push_str(&format!(
+        "<p>Posts in {}: <strong>{}</strong></p>",
+        taxonomy.name, term_name
+    ));
+    html_list.push_str("<p>This site uses classic pagination on purpose to help you stop when you want to. Doomscrolling not included.</p><ul class=\"pagination\">");
+
+    let base_url = format!("/{}/{term_slug}", taxonomy.name);
+
+    if page_num > 1 {
+        let prev_url = pagination_page_url(&base_url, DEFAULT_PAGINATION_PATH, page_num - 1, false);
+        write!(
+            html_list,
+            "<li><a href=\"{prev_url}\">🔙 Previous page</a>,&nbsp;</li>"
+        )
+        .unwrap();
+    }
+
+    let index_url = pagination_page_url(&base_url, DEFAULT_PAGINATION_PATH, 1, false);
+    write!(
+        html_list,
+        "<li><a href=\"{index_url}\">{} index</a>,&nbsp;</li>",
+        taxonomy.name
+    )
+    .unwrap();
+
+    html_list.push_str("<li><a href=\"/\">Site index</a>,&nbsp;</li>");
+
+    for i in 1..=total_pages {
+        let page_url = pagination_page_url(&base_url, DEFAULT_PAGINATION_PATH, i, false);
+        write!(html_list, "<li><a href=\"{page_url}\">{i}</a>,&nbsp;</li>").unwrap();
+    }
+
+    if page_num < total_pages {
+        let next_url = pagination_page_url(&base_url, DEFAULT_PAGINATION_PATH, page_num + 1, false);
+        write!(
+            html_list,
+            "<li><a href=\"{next_url}\">Next page ⏭️</a></li>"
+        )
+        .unwrap();
+    }
+
+    html_list.push_str("</ul>");
+
+    Ok(html_list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::SiteConfig, load_includes::load_liquid_includes};
+    use std::fs;
+    use std::path::Path;
+
+    fn create_test_post(title: &str, date: &str, front_matter: &[(&str, &str)]) -> ContentItem {
+        let mut post = HashMap::new();
+        post.insert("title".to_string(), title.to_string());
+        post.insert("date".to_string(), date.to_string());
+        post.insert("slug".to_string(), title.to_lowercase().replace(' ', "-"));
+        post.insert("content".to_string(), format!("Content of {}", title));
+        for (key, value) in front_matter {
+            post.insert((*key).to_string(), (*value).to_string());
+        }
+        post
+    }
+
+    fn category_taxonomy() -> TaxonomyConfig {
+        TaxonomyConfig {
+            name: "category".to_string(),
+            front_matter_key: "category".to_string(),
+            multi_valued: false,
+            paginated: true,
+            sort_mode: None,
+        }
+    }
+
+    fn tags_taxonomy() -> TaxonomyConfig {
+        TaxonomyConfig {
+            name: "tags".to_string(),
+            front_matter_key: "tags".to_string(),
+            multi_valued: true,
+            paginated: true,
+            sort_mode: None,
+        }
+    }
+
+    #[test]
+    fn test_slugify_term() {
+        assert_eq!(slugify_term("Travel"), "travel");
+        assert_eq!(slugify_term("Music & Art"), "music-art");
+        assert_eq!(slugify_term("  Spaced  Out  "), "spaced-out");
+    }
+
+    #[test]
+    fn test_group_posts_by_single_valued_taxonomy() {
+        let posts = vec![
+            create_test_post("Post 1", "2024-01-01", &[("category", "Travel")]),
+            create_test_post("Post 2", "2024-01-02", &[("category", "Music")]),
+            create_test_post("Post 3", "2024-01-03", &[("category", "Travel")]),
+            create_test_post("Post 4", "2024-01-04", &[]),
+        ];
+
+        let groups = group_posts_by_taxonomy(&posts, &category_taxonomy());
+
+        assert_eq!(groups.len(), 2);
+        let (travel_name, travel_posts) = &groups["travel"];
+        assert_eq!(travel_name, "Travel");
+        assert_eq!(travel_posts.len(), 2);
+    }
+
+    #[test]
+    fn test_group_posts_by_multi_valued_taxonomy_splits_on_commas() {
+        let posts = vec![
+            create_test_post("Post 1", "2024-01-01", &[("tags", "rust, web")]),
+            create_test_post("Post 2", "2024-01-02", &[("tags", "rust")]),
+            create_test_post("Post 3", "2024-01-03", &[("tags", " web , design ")]),
+        ];
+
+        let groups = group_posts_by_taxonomy(&posts, &tags_taxonomy());
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups["rust"].1.len(), 2);
+        assert_eq!(groups["web"].1.len(), 2);
+        assert_eq!(groups["design"].1.len(), 1);
+        assert_eq!(groups["design"].0, "design");
+    }
+
+    #[test]
+    fn test_group_posts_by_taxonomy_ignores_empty_values() {
+        let posts = vec![
+            create_test_post("Post 1", "2024-01-01", &[("tags", "")]),
+            create_test_post("Post 2", "2024-01-02", &[]),
+        ];
+
+        let groups = group_posts_by_taxonomy(&posts, &tags_taxonomy());
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn test_generate_taxonomy_pages_produces_term_pages_for_multiple_taxonomies() {
+        let posts = vec![
+            create_test_post(
+                "Post 1",
+                "2024-01-01",
+                &[("category", "Travel"), ("tags", "sunny, hot")],
+            ),
+            create_test_post(
+                "Post 2",
+                "2024-01-02",
+                &[("category", "Travel"), ("tags", "sunny")],
+            ),
+        ];
+
+        let includes = load_liquid_includes("./sites/test/includes");
+        let main_layout = "<!DOCTYPE html><html><body>{{body}}</body></html>";
+        let mut global_variables = HashMap::new();
+        global_variables.insert("title".to_string(), "Test Site".to_string());
+        let mut config = SiteConfig::default();
+        config.taxonomies.push(tags_taxonomy());
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+
+        let mut sitemap = SitemapSink::default();
+        generate_taxonomy_pages(
+            "taxonomy-test",
+            5,
+            &posts,
+            &includes,
+            main_layout,
+            &global_variables,
+            &config,
+            &mut sitemap,
+        )
+        .expect("Failed to generate taxonomy pages");
+
+        assert!(sitemap
+            .urls()
+            .iter()
+            .any(|url| url.contains("/category/travel")));
+        assert!(sitemap.urls().iter().any(|url| url.contains("/tags/sunny")));
+
+        assert!(Path::new("out/taxonomy-test/category/travel/page/1/index.html").exists());
+        assert!(Path::new("out/taxonomy-test/tags/sunny/page/1/index.html").exists());
+        assert!(Path::new("out/taxonomy-test/tags/hot/page/1/index.html").exists());
+
+        let sunny_content =
+            fs::read_to_string("out/taxonomy-test/tags/sunny/page/1/index.html").unwrap();
+        assert!(sunny_content.contains("Post 1"));
+        assert!(sunny_content.contains("Post 2"));
+
+        let hot_content =
+            fs::read_to_string("out/taxonomy-test/tags/hot/page/1/index.html").unwrap();
+        assert!(hot_content.contains("Post 1"));
+        assert!(!hot_content.contains("Post 2"));
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
+}
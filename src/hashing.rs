@@ -0,0 +1,82 @@
+//! Deterministic content hashing for asset fingerprints.
+//!
+//! `std::collections::hash_map::DefaultHasher` is explicitly documented as
+//! unstable across Rust releases and even between runs of the same binary,
+//! so fingerprinting files with it can silently change `name-<hash>.ext`
+//! filenames between build machines and break long-term caching. FNV-1a is
+//! simple, dependency-free, and fully specified, so the same bytes always
+//! produce the same hash everywhere.
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Default hex width used by [`content_fingerprint`]: the full width of a
+/// 64-bit FNV-1a hash.
+pub const DEFAULT_HEX_LENGTH: usize = 16;
+
+/// Hashes `data` with 64-bit FNV-1a.
+fn fnv1a_hash(data: &[u8]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for &byte in data {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Hashes `data` and renders it as a zero-padded lowercase hex fingerprint,
+/// truncated to `hex_len` digits (at most [`DEFAULT_HEX_LENGTH`], the width
+/// of a 64-bit hash).
+pub fn content_fingerprint_with_length(data: &[u8], hex_len: usize) -> String {
+    let hex = format!("{:016x}", fnv1a_hash(data));
+    let hex_len = hex_len.min(hex.len());
+    hex[..hex_len].to_string()
+}
+
+/// Same as [`content_fingerprint_with_length`], using the default hex width.
+pub fn content_fingerprint(data: &[u8]) -> String {
+    content_fingerprint_with_length(data, DEFAULT_HEX_LENGTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_is_deterministic() {
+        let data = b"body { margin: 0; }";
+        assert_eq!(content_fingerprint(data), content_fingerprint(data));
+    }
+
+    #[test]
+    fn fingerprint_pins_known_input() {
+        // Regression test: pins the exact FNV-1a output for a fixed input so
+        // the hashing scheme (and therefore emitted asset filenames) can
+        // never silently drift.
+        assert_eq!(
+            content_fingerprint(b"hello world"),
+            "779a65e7023cd2e7"
+        );
+    }
+
+    #[test]
+    fn different_inputs_produce_different_fingerprints() {
+        assert_ne!(
+            content_fingerprint(b"hello world"),
+            content_fingerprint(b"hello world!")
+        );
+    }
+
+    #[test]
+    fn hex_length_is_configurable() {
+        let fingerprint = content_fingerprint_with_length(b"hello world", 8);
+        assert_eq!(fingerprint.len(), 8);
+        assert_eq!(fingerprint, content_fingerprint(b"hello world")[..8]);
+    }
+
+    #[test]
+    fn hex_length_is_clamped_to_hash_width() {
+        let fingerprint = content_fingerprint_with_length(b"hello world", 64);
+        assert_eq!(fingerprint.len(), DEFAULT_HEX_LENGTH);
+    }
+}
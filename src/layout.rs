@@ -1,7 +1,9 @@
 use crate::config::SiteConfig;
 use crate::error::Result;
+use crate::hashing::content_fingerprint;
 use std::fs;
 use std::path::Path;
+use std::time::SystemTime;
 
 use crate::template_processors::liquid::replace_template_variables;
 use crate::template_processors::process_template_tags;
@@ -36,25 +38,93 @@ pub fn insert_body_into_layout(layout: &str, body: &str) -> Result<String> {
     replace_template_variables(layout, &variables)
 }
 
+/// Caches a pagination layout's rendered output, keyed by `(layout_path,
+/// fingerprint)`, across repeated [`load_and_render_pagination_layout`]
+/// calls within the same generator run (one pagination/taxonomy/category
+/// page per call, usually sharing the same layout file). Mirrors
+/// [`crate::build_manifest::BuildManifest`]'s fingerprint-gated skip, scoped
+/// to a single in-memory run instead of persisted to disk.
+#[derive(Debug, Default)]
+pub struct LayoutCache {
+    entries: HashMap<String, (String, String)>,
+}
+
+impl LayoutCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Fingerprints a pagination layout render: the layout file's modification
+/// time together with a hash of its resolved context variables and
+/// includes, so an unchanged fingerprint means the render would produce
+/// byte-identical output without re-reading or re-rendering the layout.
+fn fingerprint_layout_render(layout_path: &str, context_variables: &Variables, includes: &TemplateIncludes) -> String {
+    let mtime = fs::metadata(layout_path)
+        .ok()
+        .and_then(|metadata| metadata.modified().ok())
+        .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok());
+
+    let mut buffer = Vec::new();
+    if let Some(duration) = mtime {
+        buffer.extend_from_slice(&duration.as_nanos().to_le_bytes());
+    }
+
+    let mut sorted_variables: Vec<_> = context_variables.iter().collect();
+    sorted_variables.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (key, value) in sorted_variables {
+        buffer.extend_from_slice(key.as_bytes());
+        buffer.push(0);
+        buffer.extend_from_slice(value.as_bytes());
+        buffer.push(0);
+    }
+
+    let mut sorted_includes: Vec<_> = includes.iter().collect();
+    sorted_includes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (name, body) in sorted_includes {
+        buffer.extend_from_slice(name.as_bytes());
+        buffer.push(0);
+        buffer.extend_from_slice(body.as_bytes());
+        buffer.push(0);
+    }
+
+    content_fingerprint(&buffer)
+}
+
 /// Loads and renders a pagination layout with the provided context variables.
 /// Returns None if the layout is not configured or cannot be loaded.
 /// This allows pagination generators to fall back to hardcoded HTML when needed.
+///
+/// `cache` short-circuits the load-and-render when an earlier call this run
+/// already rendered the same layout file (unchanged since on disk) with the
+/// same context - see [`fingerprint_layout_render`].
 pub fn load_and_render_pagination_layout(
     site_name: &str,
     layout_name: Option<&String>,
     context_variables: &Variables,
     includes: &TemplateIncludes,
     config: &SiteConfig,
+    cache: &mut LayoutCache,
 ) -> Option<String> {
     let layout_name = layout_name?;
-    
+
     let layout_path = build_layout_path(site_name, layout_name, config);
-    
+    let fingerprint = fingerprint_layout_render(&layout_path, context_variables, includes);
+
+    if let Some((cached_fingerprint, rendered)) = cache.entries.get(&layout_path) {
+        if cached_fingerprint == &fingerprint {
+            return Some(rendered.clone());
+        }
+    }
+
     match load_layout(&layout_path) {
         Ok(layout_content) => {
             // Process the layout content with all template tags and variables
             match process_template_tags(&layout_content, context_variables, Some(includes), None) {
-                Ok(rendered_content) => Some(rendered_content),
+                Ok(rendered_content) => {
+                    cache.entries.insert(layout_path, (fingerprint, rendered_content.clone()));
+                    Some(rendered_content)
+                }
                 Err(err) => {
                     eprintln!(
                         "⚠️  Warning: Failed to render pagination layout '{}': {}. Falling back to default markup.",
@@ -73,3 +143,140 @@ pub fn load_and_render_pagination_layout(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn test_config(sites_base_dir: &str) -> SiteConfig {
+        SiteConfig {
+            sites_base_dir: sites_base_dir.to_string(),
+            ..SiteConfig::default()
+        }
+    }
+
+    fn write_layout(sites_base_dir: &Path, site_name: &str, contents: &str) {
+        let layouts_dir = sites_base_dir.join(site_name).join("layouts");
+        fs::create_dir_all(&layouts_dir).unwrap();
+        fs::write(layouts_dir.join("pagination.html"), contents).unwrap();
+    }
+
+    #[test]
+    fn fingerprint_changes_when_context_variables_change() {
+        let dir = tempdir().unwrap();
+        write_layout(dir.path(), "test", "{{title}}");
+        let layout_path = build_layout_path("test", "pagination", &test_config(dir.path().to_str().unwrap()));
+
+        let mut before = HashMap::new();
+        before.insert("title".to_string(), "Page 1".to_string());
+        let mut after = HashMap::new();
+        after.insert("title".to_string(), "Page 2".to_string());
+
+        assert_ne!(
+            fingerprint_layout_render(&layout_path, &before, &HashMap::new()),
+            fingerprint_layout_render(&layout_path, &after, &HashMap::new())
+        );
+    }
+
+    #[test]
+    fn fingerprint_changes_when_includes_change() {
+        let dir = tempdir().unwrap();
+        write_layout(dir.path(), "test", "{{title}}");
+        let layout_path = build_layout_path("test", "pagination", &test_config(dir.path().to_str().unwrap()));
+
+        let mut before = HashMap::new();
+        before.insert("post".to_string(), "<article>1</article>".to_string());
+        let mut after = HashMap::new();
+        after.insert("post".to_string(), "<article>2</article>".to_string());
+
+        assert_ne!(
+            fingerprint_layout_render(&layout_path, &HashMap::new(), &before),
+            fingerprint_layout_render(&layout_path, &HashMap::new(), &after)
+        );
+    }
+
+    #[test]
+    fn fingerprint_is_independent_of_hash_map_iteration_order() {
+        let dir = tempdir().unwrap();
+        write_layout(dir.path(), "test", "{{title}}");
+        let layout_path = build_layout_path("test", "pagination", &test_config(dir.path().to_str().unwrap()));
+
+        let mut variables_a = HashMap::new();
+        variables_a.insert("title".to_string(), "Page 1".to_string());
+        variables_a.insert("site_name".to_string(), "test".to_string());
+
+        let mut variables_b = HashMap::new();
+        variables_b.insert("site_name".to_string(), "test".to_string());
+        variables_b.insert("title".to_string(), "Page 1".to_string());
+
+        assert_eq!(
+            fingerprint_layout_render(&layout_path, &variables_a, &HashMap::new()),
+            fingerprint_layout_render(&layout_path, &variables_b, &HashMap::new())
+        );
+    }
+
+    #[test]
+    fn cache_hit_returns_the_same_render_without_growing_the_cache() {
+        let dir = tempdir().unwrap();
+        write_layout(dir.path(), "test", "<p>{{title}}</p>");
+        let config = test_config(dir.path().to_str().unwrap());
+        let mut cache = LayoutCache::new();
+
+        let mut variables = HashMap::new();
+        variables.insert("title".to_string(), "Page 1".to_string());
+
+        let first = load_and_render_pagination_layout(
+            "test",
+            Some(&"pagination".to_string()),
+            &variables,
+            &HashMap::new(),
+            &config,
+            &mut cache,
+        );
+        let second = load_and_render_pagination_layout(
+            "test",
+            Some(&"pagination".to_string()),
+            &variables,
+            &HashMap::new(),
+            &config,
+            &mut cache,
+        );
+
+        assert_eq!(first, second);
+        assert_eq!(cache.entries.len(), 1);
+    }
+
+    #[test]
+    fn cache_miss_rerenders_when_context_variables_change() {
+        let dir = tempdir().unwrap();
+        write_layout(dir.path(), "test", "<p>{{title}}</p>");
+        let config = test_config(dir.path().to_str().unwrap());
+        let mut cache = LayoutCache::new();
+
+        let mut first_variables = HashMap::new();
+        first_variables.insert("title".to_string(), "Page 1".to_string());
+        let first = load_and_render_pagination_layout(
+            "test",
+            Some(&"pagination".to_string()),
+            &first_variables,
+            &HashMap::new(),
+            &config,
+            &mut cache,
+        );
+
+        let mut second_variables = HashMap::new();
+        second_variables.insert("title".to_string(), "Page 2".to_string());
+        let second = load_and_render_pagination_layout(
+            "test",
+            Some(&"pagination".to_string()),
+            &second_variables,
+            &HashMap::new(),
+            &config,
+            &mut cache,
+        );
+
+        assert_ne!(first, second);
+        assert_eq!(cache.entries.len(), 1);
+    }
+}
@@ -0,0 +1,231 @@
+//! A small, dependency-free lexer core shared by the CSS, SCSS, and
+//! TypeScript scanners, modeled on rustc_lexer: a [`Cursor`] walks a `&str`
+//! and [`Cursor::advance_token`] yields one [`Token`] (a type tag plus the
+//! byte length it consumed) at a time. Comment and quoted-string
+//! recognition - previously reimplemented with subtly different rules by
+//! each of those scanners - lives here exactly once.
+//!
+//! Unterminated comments/strings are reported via each token kind's
+//! `terminated` flag rather than panicking, so callers can decide how to
+//! handle a truncated input (e.g. keep scanning to end of file).
+
+use crate::minifier::find_byte_any;
+use std::str::Chars;
+
+/// The kind of a single token produced by [`Cursor::advance_token`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// `// ...`, up to (but not including) the next newline or end of input.
+    LineComment,
+    /// `/* ... */`. `terminated` is false if the input ended before `*/`.
+    BlockComment { terminated: bool },
+    /// `"..."`, honoring `\`-escaped characters. `terminated` is false if
+    /// the input ended before the closing quote.
+    DoubleQuotedString { terminated: bool },
+    /// `'...'`, honoring `\`-escaped characters. `terminated` is false if
+    /// the input ended before the closing quote.
+    SingleQuotedString { terminated: bool },
+    /// A run of input that isn't a comment or string: everything up to (but
+    /// not including) the next `/`, `"`, or `'`, or to end of input if none
+    /// remain.
+    Other,
+}
+
+/// A [`TokenKind`] paired with the number of bytes of input it consumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub len: usize,
+}
+
+/// Walks a `&str` one [`Token`] at a time without allocating.
+#[derive(Clone)]
+pub struct Cursor<'a> {
+    chars: Chars<'a>,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { chars: input.chars() }
+    }
+
+    /// The input not yet consumed.
+    pub fn as_str(&self) -> &'a str {
+        self.chars.as_str()
+    }
+
+    /// Whether the cursor has reached the end of input.
+    pub fn is_eof(&self) -> bool {
+        self.chars.as_str().is_empty()
+    }
+
+    fn first(&self) -> char {
+        self.chars.clone().next().unwrap_or('\0')
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        self.chars.next()
+    }
+
+    /// Consumes and returns the next token. Only call this when
+    /// [`Cursor::is_eof`] is false.
+    pub fn advance_token(&mut self) -> Token {
+        let start_len = self.chars.as_str().len();
+        let first = self.bump().expect("advance_token called at end of input");
+
+        let kind = match first {
+            '/' if self.first() == '/' => self.line_comment(),
+            '/' if self.first() == '*' => self.block_comment(),
+            '"' => self.quoted_string('"', |terminated| TokenKind::DoubleQuotedString { terminated }),
+            '\'' => self.quoted_string('\'', |terminated| TokenKind::SingleQuotedString { terminated }),
+            _ => self.other(),
+        };
+
+        let len = start_len - self.chars.as_str().len();
+        Token { kind, len }
+    }
+
+    fn line_comment(&mut self) -> TokenKind {
+        self.bump(); // second '/'
+        while !self.is_eof() && self.first() != '\n' {
+            self.bump();
+        }
+        TokenKind::LineComment
+    }
+
+    fn block_comment(&mut self) -> TokenKind {
+        self.bump(); // '*'
+        loop {
+            if self.is_eof() {
+                return TokenKind::BlockComment { terminated: false };
+            }
+            let c = self.bump().unwrap();
+            if c == '*' && self.first() == '/' {
+                self.bump();
+                return TokenKind::BlockComment { terminated: true };
+            }
+        }
+    }
+
+    fn quoted_string(&mut self, quote: char, make_kind: impl Fn(bool) -> TokenKind) -> TokenKind {
+        loop {
+            if self.is_eof() {
+                return make_kind(false);
+            }
+            let c = self.bump().unwrap();
+            if c == '\\' {
+                // An escaped character never ends the string, no matter
+                // what it is - consume it as part of the escape pair
+                // unconditionally.
+                self.bump();
+                continue;
+            }
+            if c == quote {
+                return make_kind(true);
+            }
+        }
+    }
+
+    fn other(&mut self) -> TokenKind {
+        // A run of "other" text is typically the bulk of the input - plain
+        // CSS/SCSS/TS source between comments and strings. `find_byte_any`
+        // jumps straight to the next `/`, `"`, or `'` in one SIMD pass
+        // instead of testing `self.first()` a character at a time; none of
+        // those three bytes can appear as a continuation byte of a
+        // multi-byte UTF-8 sequence, so a byte-offset match is always also
+        // a valid `char` boundary.
+        let remaining = self.chars.as_str();
+        let skip_len =
+            find_byte_any(remaining.as_bytes(), &[b'/', b'"', b'\'']).unwrap_or(remaining.len());
+        self.chars = remaining[skip_len..].chars();
+        TokenKind::Other
+    }
+}
+
+/// Tokenizes just the single token at the start of `input` - a convenience
+/// for callers that only need to classify one comment/string and learn how
+/// many bytes it spans, without keeping a [`Cursor`] around themselves.
+pub fn first_token(input: &str) -> Token {
+    Cursor::new(input).advance_token()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_comment_stops_before_newline() {
+        let token = first_token("// hi\nrest");
+        assert_eq!(token.kind, TokenKind::LineComment);
+        assert_eq!(&"// hi\nrest"[..token.len], "// hi");
+    }
+
+    #[test]
+    fn test_line_comment_at_end_of_input() {
+        let token = first_token("// hi");
+        assert_eq!(token.kind, TokenKind::LineComment);
+        assert_eq!(token.len, "// hi".len());
+    }
+
+    #[test]
+    fn test_block_comment_terminated() {
+        let token = first_token("/* hi */rest");
+        assert_eq!(token.kind, TokenKind::BlockComment { terminated: true });
+        assert_eq!(&"/* hi */rest"[..token.len], "/* hi */");
+    }
+
+    #[test]
+    fn test_block_comment_unterminated() {
+        let token = first_token("/* never closes");
+        assert_eq!(token.kind, TokenKind::BlockComment { terminated: false });
+        assert_eq!(token.len, "/* never closes".len());
+    }
+
+    #[test]
+    fn test_double_quoted_string_with_escaped_quote() {
+        let token = first_token(r#""He said \"hi\"" rest"#);
+        assert_eq!(token.kind, TokenKind::DoubleQuotedString { terminated: true });
+        assert_eq!(&r#""He said \"hi\"" rest"#[..token.len], r#""He said \"hi\"""#);
+    }
+
+    #[test]
+    fn test_single_quoted_string_unterminated() {
+        let token = first_token("'never closes");
+        assert_eq!(token.kind, TokenKind::SingleQuotedString { terminated: false });
+        assert_eq!(token.len, "'never closes".len());
+    }
+
+    #[test]
+    fn test_comment_delimiters_inside_a_string_are_not_a_comment() {
+        let token = first_token(r#""/* not a comment */" rest"#);
+        assert_eq!(token.kind, TokenKind::DoubleQuotedString { terminated: true });
+        assert_eq!(
+            &r#""/* not a comment */" rest"#[..token.len],
+            r#""/* not a comment */""#
+        );
+    }
+
+    #[test]
+    fn test_line_comment_marker_inside_a_block_comment_does_not_end_it() {
+        let token = first_token("/* still // a comment */rest");
+        assert_eq!(token.kind, TokenKind::BlockComment { terminated: true });
+        assert_eq!(
+            &"/* still // a comment */rest"[..token.len],
+            "/* still // a comment */"
+        );
+    }
+
+    #[test]
+    fn test_other_stops_before_next_interesting_char() {
+        let token = first_token("plain text/comment");
+        assert_eq!(token.kind, TokenKind::Other);
+        assert_eq!(&"plain text/comment"[..token.len], "plain text");
+    }
+
+    #[test]
+    fn test_other_consumes_lone_slash_not_starting_a_comment() {
+        let token = first_token("a/b");
+        assert_eq!(token.kind, TokenKind::Other);
+        assert_eq!(token.len, "a".len());
+    }
+}
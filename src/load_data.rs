@@ -1,21 +1,30 @@
 use crate::config::{DATA_SUBDIR, SITES_BASE_DIR};
 use crate::error::Result;
-use crate::parsers::{parse_json, JsonValue};
+use crate::parsers::{filter_array, parse_json, parse_toml, parse_yaml, JsonValue};
 use crate::types::Variables;
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
-/// Load all JSON data files from the site's data directory
+/// Extensions recognized as site data files, dispatched by [`load_data_file`].
+const DATA_EXTENSIONS: &[&str] = &["json", "yaml", "yml", "toml"];
+
+/// Load all JSON/YAML/TOML data files from the site's data directory
 ///
-/// Loads JSON files from sites/{site_name}/data/ and makes them available as variables.
+/// Loads data files from sites/{site_name}/data/ and makes them available as variables.
 /// For example, sites/test/data/navigation.json becomes accessible as {{ data.navigation }}
-/// and sites/test/data/authors.json becomes {{ data.authors }}
+/// and sites/test/data/authors.yaml becomes {{ data.authors }}
+///
+/// Files are processed in sorted filename order, so if two files share a stem
+/// (e.g. `authors.json` and `authors.yaml`), the one that sorts last wins and
+/// replaces the other's variables entirely, with a warning printed (matching
+/// the existing per-file warning style).
 ///
 /// # Arguments
 /// * `site_name` - The name of the site
 ///
 /// # Returns
-/// A Variables HashMap with data.{filename} keys pointing to the JSON content
+/// A Variables HashMap with data.{filename} keys pointing to the file's content
 pub fn load_site_data(site_name: &str) -> Result<Variables> {
     let data_dir = format!("{SITES_BASE_DIR}/{site_name}/{DATA_SUBDIR}");
     let mut data_variables = Variables::new();
@@ -26,29 +35,45 @@ pub fn load_site_data(site_name: &str) -> Result<Variables> {
         return Ok(data_variables);
     }
 
-    // Read all files in the data directory
-    let entries = fs::read_dir(&data_dir)?;
+    // Read all files in the data directory, sorted so that precedence
+    // between same-stem files (see doc comment above) is deterministic.
+    let mut entries: Vec<_> = fs::read_dir(&data_dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut loaded_stems: HashSet<String> = HashSet::new();
 
     for entry in entries {
-        let entry = entry?;
         let path = entry.path();
 
-        // Only process .json files
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            if let Some(file_name) = path.file_stem().and_then(|s| s.to_str()) {
-                match load_json_file(&path) {
-                    Ok(json_data) => {
-                        // Add each key-value pair from the JSON as data.{filename}.{key}
-                        add_json_data_to_variables(&mut data_variables, file_name, &json_data);
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "⚠️  Warning: Failed to load JSON file {}: {}",
-                            path.display(),
-                            e
-                        );
-                    }
-                }
+        let Some(extension) = path.extension().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if !DATA_EXTENSIONS.contains(&extension) {
+            continue;
+        }
+        let Some(file_name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        if !loaded_stems.insert(file_name.to_string()) {
+            eprintln!(
+                "⚠️  Warning: {} overrides an already-loaded `{file_name}` data file",
+                path.display()
+            );
+            remove_data_variables(&mut data_variables, file_name);
+        }
+
+        match load_data_file(&path) {
+            Ok(json_data) => {
+                // Add each key-value pair from the data as data.{filename}.{key}
+                add_json_data_to_variables(&mut data_variables, file_name, &json_data);
+            }
+            Err(e) => {
+                eprintln!(
+                    "⚠️  Warning: Failed to load data file {}: {}",
+                    path.display(),
+                    e
+                );
             }
         }
     }
@@ -56,16 +81,28 @@ pub fn load_site_data(site_name: &str) -> Result<Variables> {
     Ok(data_variables)
 }
 
-/// Load and parse a JSON file
-fn load_json_file(path: &Path) -> Result<JsonValue> {
+/// Parses a data file, dispatching to the JSON, YAML, or TOML parser by its
+/// extension (one of [`DATA_EXTENSIONS`]).
+fn load_data_file(path: &Path) -> Result<JsonValue> {
     let content = fs::read_to_string(path)?;
-    let json_value = parse_json(&content).map_err(|e| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("JSON parse error: {}", e),
-        )
-    })?;
-    Ok(json_value)
+    let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
+
+    let parse_result = match extension {
+        "yaml" | "yml" => parse_yaml(&content).map_err(|e| format!("YAML parse error: {e}")),
+        "toml" => parse_toml(&content).map_err(|e| format!("TOML parse error: {e}")),
+        _ => parse_json(&content).map_err(|e| format!("JSON parse error: {e}")),
+    };
+
+    parse_result.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e).into())
+}
+
+/// Removes every `data.{file_name}` variable (and anything nested under it),
+/// so a later file with the same stem fully replaces the earlier one instead
+/// of merging with its leftover keys.
+fn remove_data_variables(variables: &mut Variables, file_name: &str) {
+    let exact_key = format!("data.{file_name}");
+    let nested_prefix = format!("{exact_key}.");
+    variables.retain(|key, _| *key != exact_key && !key.starts_with(&nested_prefix));
 }
 
 /// Recursively add JSON data to variables with proper prefixing
@@ -92,7 +129,7 @@ fn add_json_data_to_variables(variables: &mut Variables, file_name: &str, json_v
 }
 
 /// Recursively flatten a JSON value into dot-notation variables
-fn flatten_json_value(variables: &mut Variables, prefix: &str, value: &JsonValue) {
+pub(crate) fn flatten_json_value(variables: &mut Variables, prefix: &str, value: &JsonValue) {
     match value {
         JsonValue::String(s) => {
             variables.insert(prefix.to_string(), s.clone());
@@ -100,6 +137,16 @@ fn flatten_json_value(variables: &mut Variables, prefix: &str, value: &JsonValue
         JsonValue::Integer(n) => {
             variables.insert(prefix.to_string(), n.to_string());
         }
+        JsonValue::Float(n) => {
+            variables.insert(prefix.to_string(), n.to_string());
+        }
+        JsonValue::Bool(b) => {
+            variables.insert(prefix.to_string(), b.to_string());
+        }
+        JsonValue::Null => {
+            // No variable is created for a null value, matching how an
+            // unresolved template variable is represented elsewhere.
+        }
         JsonValue::Array(arr) => {
             for (index, item) in arr.iter().enumerate() {
                 let key = format!("{}.{}", prefix, index);
@@ -115,6 +162,24 @@ fn flatten_json_value(variables: &mut Variables, prefix: &str, value: &JsonValue
     }
 }
 
+/// Applies a `where`-style filter expression (see
+/// `crate::parsers::filter_array`) to `array`, then flattens the surviving
+/// elements back into a fresh [`Variables`] map under `array_prefix`,
+/// renumbered from 0 - the same dot-notation convention
+/// [`flatten_json_value`] uses when a site's JSON data is first loaded.
+pub fn apply_where_filter(array: &[JsonValue], array_prefix: &str, expression: &str) -> Result<Variables> {
+    let matches = filter_array(array, expression).map_err(|e| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid filter expression: {e}"))
+    })?;
+
+    let mut variables = Variables::new();
+    for (index, item) in matches.into_iter().enumerate() {
+        let key = format!("{array_prefix}.{index}");
+        flatten_json_value(&mut variables, &key, item);
+    }
+    Ok(variables)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,4 +295,49 @@ mod tests {
             Some(&"johndoe".to_string())
         );
     }
+
+    #[test]
+    fn test_apply_where_filter_keeps_matching_elements_reindexed() {
+        let json_content = r#"
+        [
+            {"role": "admin", "name": "Ada"},
+            {"role": "editor", "name": "Grace"},
+            {"role": "admin", "name": "Linus"}
+        ]
+        "#;
+        let JsonValue::Array(authors) = parse_json(json_content).unwrap() else {
+            panic!("expected array");
+        };
+
+        let variables = apply_where_filter(&authors, "data.authors", r#"role = "admin""#).unwrap();
+
+        assert_eq!(
+            variables.get("data.authors.0.name"),
+            Some(&"Ada".to_string())
+        );
+        assert_eq!(
+            variables.get("data.authors.1.name"),
+            Some(&"Linus".to_string())
+        );
+        assert_eq!(variables.get("data.authors.2.name"), None);
+    }
+
+    #[test]
+    fn test_apply_where_filter_empty_expression_keeps_every_element() {
+        let json_content = r#"[{"name": "Ada"}, {"name": "Grace"}]"#;
+        let JsonValue::Array(authors) = parse_json(json_content).unwrap() else {
+            panic!("expected array");
+        };
+
+        let variables = apply_where_filter(&authors, "data.authors", "").unwrap();
+
+        assert_eq!(
+            variables.get("data.authors.0.name"),
+            Some(&"Ada".to_string())
+        );
+        assert_eq!(
+            variables.get("data.authors.1.name"),
+            Some(&"Grace".to_string())
+        );
+    }
 }
@@ -1,16 +1,21 @@
 // Core types and error handling
 mod config;
 mod error;
+mod traits;
 mod types;
 
 // File operations
+mod build_manifest;
 mod file_copier;
 mod file_readers;
+mod hashing;
+mod integrity;
 mod load_data;
 mod write;
 
 // Template processing
 mod layout;
+mod lexer;
 mod load_includes;
 mod minifier;
 mod parsers;
@@ -20,10 +25,15 @@ mod template_processors;
 mod converters;
 
 // Generation and rendering
+mod gemini_gopher;
 mod generate;
 mod generate_category_pages;
 mod generate_pagination_pages;
+mod generate_taxonomy_pages;
+mod pagination;
 mod render_page;
+mod rss_feed;
+mod sitemap_writer;
 
 // Development tools
 mod server;
@@ -39,6 +49,7 @@ use watch::watch;
 fn print_usage() {
     eprintln!("Available commands:");
     eprintln!("  generate <site_name>  Generate the static site");
+    eprintln!("  generate <site_name> --force  Regenerate every asset, ignoring the incremental build manifest");
     eprintln!("  serve <site_name>     Start the development server for a site");
     eprintln!("  watch <site_name>     Watch for changes and regenerate");
     eprintln!("  watch <site_name> --ramdisk  Watch with RAM-based output (Linux only)");
@@ -46,7 +57,7 @@ fn print_usage() {
 
 fn handle_command(args: &[&str]) -> Result<()> {
     // Create and validate configuration
-    let config = SiteConfig::from_environment();
+    let mut config = SiteConfig::from_environment();
     if let Err(error) = config.validate() {
         eprintln!("Configuration error: {error}");
         std::process::exit(1);
@@ -56,6 +67,10 @@ fn handle_command(args: &[&str]) -> Result<()> {
         ["generate", site_name] => {
             generate(site_name, &config)?;
         }
+        ["generate", site_name, "--force"] | ["generate", "--force", site_name] => {
+            config.force_rebuild = true;
+            generate(site_name, &config)?;
+        }
         ["generate"] => {
             eprintln!("Error: Site name is required for generate command.");
             eprintln!("Usage: {} generate <site_name>", args[0]);
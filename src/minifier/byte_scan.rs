@@ -0,0 +1,151 @@
+#[cfg(target_arch = "x86_64")]
+use core::arch::global_asm;
+#[cfg(target_arch = "x86_64")]
+use std::sync::OnceLock;
+
+#[cfg(target_arch = "x86_64")]
+global_asm!(include_str!("byte_scan_x86_64.s"));
+#[cfg(target_arch = "x86_64")]
+global_asm!(include_str!("byte_scan_x86_64_avx2.s"));
+
+#[cfg(target_arch = "x86_64")]
+extern "C" {
+    fn find_byte_any_scan(ptr: *const u8, len: usize, needles8: *const u8) -> usize;
+    fn find_byte_any_scan_avx2(ptr: *const u8, len: usize, needles8: *const u8) -> usize;
+}
+
+#[cfg(target_arch = "x86_64")]
+type ScanFn = unsafe extern "C" fn(*const u8, usize, *const u8) -> usize;
+
+/// Picks which `x86_64` kernel to use via `is_x86_feature_detected!` and
+/// caches the resolved function pointer for the life of the process - the
+/// feature probe itself isn't free, so it should only run once (the same
+/// probe-once-and-cache technique `httparse` uses for its own SIMD dispatch).
+#[cfg(target_arch = "x86_64")]
+fn resolved_scan_fn() -> ScanFn {
+    static RESOLVED: OnceLock<ScanFn> = OnceLock::new();
+    *RESOLVED.get_or_init(|| {
+        if is_x86_feature_detected!("avx2") {
+            find_byte_any_scan_avx2
+        } else {
+            // SSE2 is part of the x86_64 baseline ABI, so this is always
+            // available as the accelerated fallback below AVX2.
+            find_byte_any_scan
+        }
+    })
+}
+
+/// Finds the first position in `haystack` matching any byte in `needles`.
+///
+/// Supports up to 8 needle bytes on the accelerated `x86_64` path (the
+/// width the SIMD kernels broadcast into their compare registers); larger
+/// needle sets fall back to the scalar scan. Used by the CSS/HTML minifiers
+/// to jump straight to the next significant delimiter instead of
+/// inspecting every byte.
+///
+/// On `x86_64` the actual kernel (AVX2, or the SSE2 baseline) is resolved
+/// once via runtime CPU feature detection and reused for every call after
+/// that - see [`resolved_scan_fn`].
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn find_byte_any(haystack: &[u8], needles: &[u8]) -> Option<usize> {
+    if needles.is_empty() || needles.len() > 8 {
+        return haystack.iter().position(|b| needles.contains(b));
+    }
+
+    // Pad to exactly 8 slots by repeating the last needle; duplicate slots
+    // can't introduce a false match since every slot holds a real needle.
+    let mut padded = [needles[needles.len() - 1]; 8];
+    padded[..needles.len()].copy_from_slice(needles);
+
+    let scan = resolved_scan_fn();
+    let idx = unsafe { scan(haystack.as_ptr(), haystack.len(), padded.as_ptr()) };
+    if idx == usize::MAX {
+        None
+    } else {
+        Some(idx)
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+pub(crate) fn find_byte_any(haystack: &[u8], needles: &[u8]) -> Option<usize> {
+    haystack.iter().position(|b| needles.contains(b))
+}
+
+/// Finds the first position of a single `needle` byte in `haystack`. A thin
+/// convenience wrapper over [`find_byte_any`] for the common case of
+/// matching one delimiter rather than a set.
+pub(crate) fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    find_byte_any(haystack, &[needle])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_first_match_among_several_needles() {
+        let s = b"abc:def;ghi";
+        assert_eq!(find_byte_any(s, b";:"), Some(3));
+    }
+
+    #[test]
+    fn respects_needle_set_not_needle_order() {
+        // ';' appears before ':' in the haystack even though ':' is listed first.
+        let s = b"abc;def:ghi";
+        assert_eq!(find_byte_any(s, b":;"), Some(3));
+    }
+
+    #[test]
+    fn returns_none_for_empty_needle_set() {
+        let s = b"abcdef";
+        assert_eq!(find_byte_any(s, b""), None);
+    }
+
+    #[test]
+    fn returns_none_when_nothing_matches() {
+        let s = b"abcdef";
+        assert_eq!(find_byte_any(s, b"{};"), None);
+    }
+
+    #[test]
+    fn handles_empty_haystack() {
+        let s = b"";
+        assert_eq!(find_byte_any(s, b"{"), None);
+    }
+
+    #[test]
+    fn finds_match_at_chunk_boundary() {
+        // The 16-byte SSE2 chunk boundary falls right after index 15.
+        let mut haystack = vec![b'a'; 16];
+        haystack.push(b'{');
+        assert_eq!(find_byte_any(&haystack, b"{"), Some(16));
+    }
+
+    #[test]
+    fn finds_match_exactly_at_last_byte_of_a_chunk() {
+        let mut haystack = vec![b'a'; 15];
+        haystack.push(b':');
+        assert_eq!(find_byte_any(&haystack, b":"), Some(15));
+    }
+
+    #[test]
+    fn finds_match_spanning_the_avx2_chunk_boundary() {
+        // The 32-byte AVX2 chunk boundary falls right after index 31.
+        let mut haystack = vec![b'a'; 32];
+        haystack.push(b'{');
+        assert_eq!(find_byte_any(&haystack, b"{"), Some(32));
+    }
+
+    #[test]
+    fn supports_more_than_eight_needles_via_fallback() {
+        let s = b"aaaaaaaaz";
+        assert_eq!(find_byte_any(s, b"bcdefghiz"), Some(8));
+    }
+
+    #[test]
+    fn find_byte_matches_find_byte_any_with_single_needle() {
+        let s = b"abc;def";
+        assert_eq!(find_byte(s, b';'), Some(3));
+        assert_eq!(find_byte(s, b'z'), None);
+    }
+}
@@ -0,0 +1,274 @@
+//! Normalizes `url()` function calls and the `@import url(...)` at-rule
+//! shorthand.
+//!
+//! [`normalize_import_url`] collapses `@import url("x.css");` down to the
+//! shorter `@import "x.css";` form (the `url()` wrapper adds nothing an
+//! `@import` prelude needs - a bare string is just as valid there). An
+//! unquoted `url(x.css)` is handled the same way, quoting its content as it's
+//! unwrapped - `@import "x.css";` is three bytes shorter than
+//! `@import url(x.css);` either way. Anything left over - `url()` calls in
+//! declaration values like `background-image`, or an `@import` whose value
+//! wasn't wrapped in `url()` to begin with - is then handled by
+//! [`normalize_url_functions`], which drops the whitespace some authors pad
+//! the call with (`url( "x.png" )` -> `url("x.png")`) and unquotes the
+//! string when its content has none of the characters (quotes, parens,
+//! whitespace, backslash, control characters) that require quoting.
+//!
+//! Preserving the single space `and`/`or`/`not` need in a media-query
+//! prelude (`@media (min-width: 600px) and (max-width: 900px)`) isn't this
+//! module's concern - [`super::minifier`]'s `needs_space_before` already
+//! keeps it, since any identifier directly followed by `(` needs that space
+//! regardless of which at-rule (or declaration) it shows up in.
+
+use super::tokenizer::{Token, TokenSpan};
+
+/// Walks `spans`, replacing an `@import url("...")`/`@import url('...')`
+/// prelude's `url(...)` wrapper with its bare string argument. Only the
+/// `url(...)` run itself is replaced - a trailing media type list before the
+/// `;` (`@import url("print.css") print;`) is left untouched.
+pub fn normalize_import_url(spans: Vec<TokenSpan>) -> Vec<TokenSpan> {
+    let mut out = Vec::with_capacity(spans.len());
+    let mut i = 0;
+
+    while i < spans.len() {
+        if is_import_at_rule_start(&spans, i) {
+            if let Some((url_start, string_span, consumed)) = find_import_url_call(&spans, i) {
+                out.extend_from_slice(&spans[i..url_start]);
+                out.push(string_span);
+                i = url_start + consumed;
+                continue;
+            }
+        }
+        out.push(spans[i].clone());
+        i += 1;
+    }
+
+    out
+}
+
+fn is_import_at_rule_start(spans: &[TokenSpan], i: usize) -> bool {
+    spans.get(i).map(|s| s.token) == Some(Token::Delim('@'))
+        && spans.get(i + 1).is_some_and(|s| s.token == Token::Ident && s.text.eq_ignore_ascii_case("import"))
+}
+
+/// From an `@import` at `spans[i]`, looks past the `import` keyword and the
+/// one run of whitespace that must separate it from its value for a
+/// `url(...)` call. Returns the call's start index, the bare string span to
+/// replace it with, and how many spans it spans.
+fn find_import_url_call(spans: &[TokenSpan], i: usize) -> Option<(usize, TokenSpan, usize)> {
+    let mut j = i + 2; // past `@` and `import`
+    if spans.get(j).map(|s| s.token) != Some(Token::Whitespace) {
+        return None;
+    }
+    j += 1;
+
+    let url_start = j;
+    if !spans.get(j).is_some_and(|s| s.token == Token::Function && s.text.eq_ignore_ascii_case("url")) {
+        return None;
+    }
+    j += 1;
+    if spans.get(j).map(|s| s.token) != Some(Token::Delim('(')) {
+        return None;
+    }
+    j += 1;
+    if spans.get(j).map(|s| s.token) == Some(Token::Whitespace) {
+        j += 1;
+    }
+
+    let string_span = if spans.get(j).map(|s| s.token) == Some(Token::String) {
+        let span = spans[j].clone();
+        j += 1;
+        span
+    } else {
+        // An unquoted `url(x.css)` - its content isn't a single string span,
+        // just a run of ident/delim tokens up to the closing paren (or the
+        // whitespace padding it). Quoting that run as we unwrap it still
+        // comes out shorter than keeping the `url()` wrapper.
+        let content_start = j;
+        while spans.get(j).is_some_and(|s| !matches!(s.token, Token::Delim(')') | Token::Whitespace)) {
+            j += 1;
+        }
+        if j == content_start {
+            return None;
+        }
+        let content: String = spans[content_start..j].iter().map(|s| s.text.as_str()).collect();
+        TokenSpan { token: Token::String, text: format!("\"{content}\"") }
+    };
+
+    if spans.get(j).map(|s| s.token) == Some(Token::Whitespace) {
+        j += 1;
+    }
+    if spans.get(j).map(|s| s.token) != Some(Token::Delim(')')) {
+        return None;
+    }
+    j += 1;
+
+    Some((url_start, string_span, j - url_start))
+}
+
+/// Characters that require a `url()` argument to stay quoted: whitespace,
+/// both quote characters, parens (which would otherwise prematurely close
+/// the call), a backslash, and control characters.
+fn needs_quoting(content: &str) -> bool {
+    content.is_empty()
+        || content.chars().any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '(' | ')' | '\\') || c.is_control())
+}
+
+/// Walks `spans`, trimming the whitespace inside any remaining `url(...)`
+/// call and unquoting its string argument when [`needs_quoting`] says it's
+/// safe to.
+pub fn normalize_url_functions(spans: Vec<TokenSpan>) -> Vec<TokenSpan> {
+    let mut out = Vec::with_capacity(spans.len());
+    let mut i = 0;
+
+    while i < spans.len() {
+        if spans[i].token == Token::Function && spans[i].text.eq_ignore_ascii_case("url") {
+            if let Some((replacement, consumed)) = try_normalize_url_call(&spans, i) {
+                out.extend(replacement);
+                i += consumed;
+                continue;
+            }
+        }
+        out.push(spans[i].clone());
+        i += 1;
+    }
+
+    out
+}
+
+fn try_normalize_url_call(spans: &[TokenSpan], start: usize) -> Option<(Vec<TokenSpan>, usize)> {
+    let mut j = start + 1;
+    if spans.get(j).map(|s| s.token) != Some(Token::Delim('(')) {
+        return None;
+    }
+    j += 1;
+    if spans.get(j).map(|s| s.token) == Some(Token::Whitespace) {
+        j += 1;
+    }
+    let string_span = spans.get(j)?;
+    if string_span.token != Token::String {
+        return None;
+    }
+    let content = &string_span.text[1..string_span.text.len() - 1];
+    j += 1;
+    if spans.get(j).map(|s| s.token) == Some(Token::Whitespace) {
+        j += 1;
+    }
+    if spans.get(j).map(|s| s.token) != Some(Token::Delim(')')) {
+        return None;
+    }
+    j += 1;
+
+    let argument = if needs_quoting(content) {
+        string_span.clone()
+    } else {
+        TokenSpan { token: Token::Ident, text: content.to_string() }
+    };
+
+    Some((
+        vec![
+            spans[start].clone(),
+            TokenSpan { token: Token::Delim('('), text: "(".to_string() },
+            argument,
+            TokenSpan { token: Token::Delim(')'), text: ")".to_string() },
+        ],
+        j - start,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::tokenizer::tokenize;
+
+    fn import_pass(css: &str) -> String {
+        normalize_import_url(tokenize(css)).into_iter().map(|s| s.text).collect()
+    }
+
+    fn url_pass(css: &str) -> String {
+        normalize_url_functions(tokenize(css)).into_iter().map(|s| s.text).collect()
+    }
+
+    #[test]
+    fn test_import_url_with_double_quoted_string_collapses() {
+        assert_eq!(import_pass("@import url(\"x.css\");"), "@import \"x.css\";");
+    }
+
+    #[test]
+    fn test_import_url_with_single_quoted_string_collapses() {
+        assert_eq!(import_pass("@import url('x.css');"), "@import 'x.css';");
+    }
+
+    #[test]
+    fn test_import_url_keeps_trailing_media_type_list() {
+        assert_eq!(import_pass("@import url(\"print.css\") print;"), "@import \"print.css\" print;");
+    }
+
+    #[test]
+    fn test_import_url_with_internal_whitespace_still_collapses() {
+        assert_eq!(import_pass("@import url( \"x.css\" );"), "@import \"x.css\";");
+    }
+
+    #[test]
+    fn test_import_without_url_wrapper_is_untouched() {
+        assert_eq!(import_pass("@import \"x.css\";"), "@import \"x.css\";");
+    }
+
+    #[test]
+    fn test_import_unquoted_url_is_quoted_and_unwrapped() {
+        assert_eq!(import_pass("@import url(x.css);"), "@import \"x.css\";");
+    }
+
+    #[test]
+    fn test_import_unquoted_url_keeps_trailing_media_type_list() {
+        assert_eq!(import_pass("@import url(print.css) print;"), "@import \"print.css\" print;");
+    }
+
+    #[test]
+    fn test_import_case_insensitive_keyword_and_function_name() {
+        assert_eq!(import_pass("@IMPORT URL(\"x.css\");"), "@IMPORT \"x.css\";");
+    }
+
+    #[test]
+    fn test_unrelated_at_rule_is_untouched() {
+        assert_eq!(import_pass("@media (min-width: 600px) { a { color: red; } }"), "@media (min-width: 600px) { a { color: red; } }");
+    }
+
+    #[test]
+    fn test_url_whitespace_is_trimmed() {
+        // No special characters in the path, so it's also unquoted - see
+        // test_url_without_special_characters_is_unquoted for that case in
+        // isolation.
+        assert_eq!(url_pass("background:url( \"x.png\" );"), "background:url(x.png);");
+    }
+
+    #[test]
+    fn test_url_without_special_characters_is_unquoted() {
+        assert_eq!(url_pass("background:url(\"images/bg.png\");"), "background:url(images/bg.png);");
+    }
+
+    #[test]
+    fn test_url_with_space_in_path_stays_quoted() {
+        assert_eq!(url_pass("background:url(\"my images/bg.png\");"), "background:url(\"my images/bg.png\");");
+    }
+
+    #[test]
+    fn test_url_with_parenthesis_stays_quoted() {
+        assert_eq!(url_pass("background:url(\"bg(1).png\");"), "background:url(\"bg(1).png\");");
+    }
+
+    #[test]
+    fn test_empty_url_stays_quoted() {
+        assert_eq!(url_pass("background:url(\"\");"), "background:url(\"\");");
+    }
+
+    #[test]
+    fn test_url_with_single_quotes_is_unquoted() {
+        assert_eq!(url_pass("background:url('images/bg.png');"), "background:url(images/bg.png);");
+    }
+
+    #[test]
+    fn test_already_bare_url_is_untouched() {
+        assert_eq!(url_pass("background:url(images/bg.png);"), "background:url(images/bg.png);");
+    }
+}
@@ -0,0 +1,312 @@
+//! Recognizes `rgb()`/`rgba()`/`hsl()`/`hsla()` function tokens in a CSS
+//! token stream and replaces each one with the equivalent unshortened hex
+//! color (`#rrggbb`, or `#rrggbbaa` when the alpha channel isn't fully
+//! opaque) as a [`Token::Hash`] span, so the existing
+//! [`super::hex_colors::optimize_hex_color`] pass (already applied to every
+//! `Token::Hash` by [`super::minifier`]) takes care of shortening it the
+//! same way it would a literal hex color.
+
+use super::tokenizer::{Token, TokenSpan};
+
+/// Walks `spans`, replacing every convertible color function call with a
+/// single `Token::Hash` span. Calls that can't be converted (custom
+/// properties via `var()`/`env()`, an alpha that isn't exactly representable
+/// in two hex digits, an unexpected argument shape, ...) are left untouched.
+pub fn normalize_color_functions(spans: Vec<TokenSpan>) -> Vec<TokenSpan> {
+    let mut out = Vec::with_capacity(spans.len());
+    let mut i = 0;
+    while i < spans.len() {
+        if spans[i].token == Token::Function {
+            if let Some((hex, consumed)) = try_convert_color_function(&spans, i) {
+                out.push(TokenSpan { token: Token::Hash, text: hex });
+                i += consumed;
+                continue;
+            }
+        }
+        out.push(spans[i].clone());
+        i += 1;
+    }
+    out
+}
+
+enum ColorFn {
+    Rgb,
+    Rgba,
+    Hsl,
+    Hsla,
+}
+
+/// Attempts to convert the color function call starting at `spans[start]`
+/// (the function-name token itself). On success, returns the hex text and
+/// the number of spans (name + `(` + args + `)`) it consumed.
+fn try_convert_color_function(spans: &[TokenSpan], start: usize) -> Option<(String, usize)> {
+    let kind = match spans[start].text.to_ascii_lowercase().as_str() {
+        "rgb" => ColorFn::Rgb,
+        "rgba" => ColorFn::Rgba,
+        "hsl" => ColorFn::Hsl,
+        "hsla" => ColorFn::Hsla,
+        _ => return None,
+    };
+
+    if spans.get(start + 1)?.token != Token::Delim('(') {
+        return None;
+    }
+
+    let args_start = start + 2;
+    let mut depth = 1i32;
+    let mut end = args_start;
+    while end < spans.len() {
+        match spans[end].token {
+            Token::Delim('(') => depth += 1,
+            Token::Delim(')') => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+        end += 1;
+    }
+    if depth != 0 {
+        return None; // unterminated call
+    }
+    let close_idx = end;
+    let args = &spans[args_start..close_idx];
+
+    // Custom-property references can't be resolved at minification time, so
+    // leave the whole call unbroken.
+    let raw: String = args.iter().map(|s| s.text.as_str()).collect();
+    let raw_lower = raw.to_ascii_lowercase();
+    if raw_lower.contains("var(") || raw_lower.contains("env(") {
+        return None;
+    }
+
+    let components = split_top_level_components(args);
+    let hex = match kind {
+        ColorFn::Rgb => {
+            let [r, g, b] = components.as_slice() else {
+                return None;
+            };
+            let (r, g, b) = (channel_from_component(r)?, channel_from_component(g)?, channel_from_component(b)?);
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        }
+        ColorFn::Rgba => {
+            let [r, g, b, a] = components.as_slice() else {
+                return None;
+            };
+            let (r, g, b) = (channel_from_component(r)?, channel_from_component(g)?, channel_from_component(b)?);
+            let a = alpha_to_byte_lossless(a)?;
+            format_rgb_hex(r, g, b, a)
+        }
+        ColorFn::Hsl => {
+            let [h, s, l] = components.as_slice() else {
+                return None;
+            };
+            let (r, g, b) = hsl_to_rgb(hue_degrees(h)?, percentage_fraction(s)?, percentage_fraction(l)?);
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        }
+        ColorFn::Hsla => {
+            let [h, s, l, a] = components.as_slice() else {
+                return None;
+            };
+            let (r, g, b) = hsl_to_rgb(hue_degrees(h)?, percentage_fraction(s)?, percentage_fraction(l)?);
+            let a = alpha_to_byte_lossless(a)?;
+            format_rgb_hex(r, g, b, a)
+        }
+    };
+
+    Some((hex, close_idx - start + 1))
+}
+
+/// Formats an RGB triple plus an alpha byte as hex, dropping the alpha
+/// channel entirely when it's fully opaque (`0xff`) - an explicit alpha of
+/// `ff` is the color's default and only makes the 8-digit form longer than
+/// the 6-digit one for no benefit.
+fn format_rgb_hex(r: u8, g: u8, b: u8, a: u8) -> String {
+    if a == 0xff {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+/// Splits a color function's argument tokens into trimmed, comma-separated
+/// component strings (ignoring whitespace tokens, which `var()`/`env()`
+/// detection has already ruled out from containing a nested top-level
+/// comma-bearing call).
+fn split_top_level_components(tokens: &[TokenSpan]) -> Vec<String> {
+    let mut components = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+
+    for t in tokens {
+        match t.token {
+            Token::Whitespace => continue,
+            Token::Comma if depth == 0 => {
+                components.push(std::mem::take(&mut current));
+            }
+            Token::Delim('(') => {
+                depth += 1;
+                current.push_str(&t.text);
+            }
+            Token::Delim(')') => {
+                depth -= 1;
+                current.push_str(&t.text);
+            }
+            _ => current.push_str(&t.text),
+        }
+    }
+    components.push(current);
+    components
+}
+
+/// Parses an `rgb()`/`rgba()` channel component - a bare integer or a
+/// percentage of 255 - into a clamped, rounded byte.
+fn channel_from_component(text: &str) -> Option<u8> {
+    let value = if let Some(pct) = text.strip_suffix('%') {
+        pct.trim().parse::<f64>().ok()? / 100.0 * 255.0
+    } else {
+        text.trim().parse::<f64>().ok()?
+    };
+    Some(value.round().clamp(0.0, 255.0) as u8)
+}
+
+/// Parses an `hsl()` saturation/lightness component, which must be a
+/// percentage, into a `0.0..=1.0` fraction.
+fn percentage_fraction(text: &str) -> Option<f64> {
+    let pct = text.strip_suffix('%')?;
+    Some((pct.trim().parse::<f64>().ok()? / 100.0).clamp(0.0, 1.0))
+}
+
+/// Parses an `hsl()` hue component - a bare number or a `deg`-suffixed
+/// angle - into degrees. Other angle units (`rad`, `turn`, `grad`) aren't
+/// supported and fall back to leaving the call unconverted.
+fn hue_degrees(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let numeric = text.strip_suffix("deg").unwrap_or(text);
+    numeric.trim().parse::<f64>().ok()
+}
+
+/// Parses an alpha component - a `0.0..=1.0` number or a percentage - into a
+/// byte, returning `None` unless that byte reproduces the input exactly
+/// (e.g. `0.5` -> 127.5 is rejected; `0`, `1`, and `100%` are accepted).
+fn alpha_to_byte_lossless(text: &str) -> Option<u8> {
+    let fraction = if let Some(pct) = text.strip_suffix('%') {
+        pct.trim().parse::<f64>().ok()? / 100.0
+    } else {
+        text.trim().parse::<f64>().ok()?
+    };
+    let scaled = fraction * 255.0;
+    let rounded = scaled.round();
+    if (scaled - rounded).abs() > 1e-6 || !(0.0..=255.0).contains(&rounded) {
+        return None;
+    }
+    Some(rounded as u8)
+}
+
+/// Converts an HSL color (hue in degrees, saturation/lightness as
+/// `0.0..=1.0` fractions) to an 8-bit RGB triple.
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    let hue = ((hue % 360.0) + 360.0) % 360.0;
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - (((hue / 60.0) % 2.0) - 1.0).abs());
+    let m = lightness - chroma / 2.0;
+
+    let (r1, g1, b1) = match hue {
+        h if h < 60.0 => (chroma, x, 0.0),
+        h if h < 120.0 => (x, chroma, 0.0),
+        h if h < 180.0 => (0.0, chroma, x),
+        h if h < 240.0 => (0.0, x, chroma),
+        h if h < 300.0 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let to_byte = |channel: f64| ((channel + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (to_byte(r1), to_byte(g1), to_byte(b1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::tokenizer::tokenize;
+
+    fn convert(css: &str) -> Vec<TokenSpan> {
+        normalize_color_functions(tokenize(css))
+    }
+
+    #[test]
+    fn test_rgb_integers_convert_to_hex() {
+        let spans = convert("rgb(255,0,0)");
+        assert_eq!(spans, vec![TokenSpan { token: Token::Hash, text: "#ff0000".to_string() }]);
+    }
+
+    #[test]
+    fn test_rgb_percentages_convert_to_hex() {
+        let spans = convert("rgb(10%,30%,43%)");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].token, Token::Hash);
+        assert_eq!(spans[0].text, "#1a4d6e");
+    }
+
+    #[test]
+    fn test_rgba_with_lossless_alpha_converts_to_eight_digit_hex() {
+        let spans = convert("rgba(255,0,0,0)");
+        assert_eq!(spans, vec![TokenSpan { token: Token::Hash, text: "#ff000000".to_string() }]);
+    }
+
+    #[test]
+    fn test_rgba_with_lossy_alpha_is_left_untouched() {
+        let spans = convert("rgba(255,0,0,0.5)");
+        assert_eq!(spans[0].token, Token::Function);
+        assert_eq!(spans[0].text, "rgba");
+    }
+
+    #[test]
+    fn test_hsl_converts_to_hex() {
+        // hsl(0, 100%, 50%) is pure red.
+        let spans = convert("hsl(0,100%,50%)");
+        assert_eq!(spans, vec![TokenSpan { token: Token::Hash, text: "#ff0000".to_string() }]);
+    }
+
+    #[test]
+    fn test_hsla_with_lossless_opaque_alpha_drops_alpha_channel() {
+        // 100% alpha is fully opaque and therefore the hex color's default,
+        // so it's dropped entirely rather than kept as a redundant `ff` byte.
+        let spans = convert("hsla(0,100%,50%,100%)");
+        assert_eq!(spans, vec![TokenSpan { token: Token::Hash, text: "#ff0000".to_string() }]);
+    }
+
+    #[test]
+    fn test_rgba_with_lossless_opaque_alpha_drops_alpha_channel() {
+        let spans = convert("rgba(0,0,0,1)");
+        assert_eq!(spans, vec![TokenSpan { token: Token::Hash, text: "#000000".to_string() }]);
+    }
+
+    #[test]
+    fn test_var_reference_is_left_untouched() {
+        let spans = convert("rgba(0,0,0,var(--alpha))");
+        assert_eq!(spans[0].token, Token::Function);
+        assert_eq!(spans[0].text, "rgba");
+    }
+
+    #[test]
+    fn test_env_reference_is_left_untouched() {
+        let spans = convert("rgb(env(--r),0,0)");
+        assert_eq!(spans[0].token, Token::Function);
+        assert_eq!(spans[0].text, "rgb");
+    }
+
+    #[test]
+    fn test_wrong_argument_count_is_left_untouched() {
+        let spans = convert("rgb(255,0)");
+        assert_eq!(spans[0].token, Token::Function);
+    }
+
+    #[test]
+    fn test_non_color_function_is_left_untouched() {
+        let spans = convert("calc(100% - 10px)");
+        assert_eq!(spans[0].token, Token::Function);
+        assert_eq!(spans[0].text, "calc");
+    }
+}
@@ -0,0 +1,381 @@
+//! Bidirectional substitution between CSS named colors and hex literals,
+//! picking whichever spelling is shorter (`#808080` -> `gray`, but
+//! `#ffffff`/`#fff` stays hex since `white` is longer). Only applied at
+//! declaration-value positions - see [`classify_value_positions`] - so
+//! selectors, property names, and strings are never touched.
+
+use super::hex_colors::optimize_hex_color;
+use super::tokenizer::{Token, TokenSpan};
+
+/// `(name, canonical 6-digit hex, no "#")` for every CSS named color.
+const NAMED_COLORS: &[(&str, &str)] = &[
+    ("aliceblue", "f0f8ff"),
+    ("antiquewhite", "faebd7"),
+    ("aqua", "00ffff"),
+    ("aquamarine", "7fffd4"),
+    ("azure", "f0ffff"),
+    ("beige", "f5f5dc"),
+    ("bisque", "ffe4c4"),
+    ("black", "000000"),
+    ("blanchedalmond", "ffebcd"),
+    ("blue", "0000ff"),
+    ("blueviolet", "8a2be2"),
+    ("brown", "a52a2a"),
+    ("burlywood", "deb887"),
+    ("cadetblue", "5f9ea0"),
+    ("chartreuse", "7fff00"),
+    ("chocolate", "d2691e"),
+    ("coral", "ff7f50"),
+    ("cornflowerblue", "6495ed"),
+    ("cornsilk", "fff8dc"),
+    ("crimson", "dc143c"),
+    ("cyan", "00ffff"),
+    ("darkblue", "00008b"),
+    ("darkcyan", "008b8b"),
+    ("darkgoldenrod", "b8860b"),
+    ("darkgray", "a9a9a9"),
+    ("darkgreen", "006400"),
+    ("darkgrey", "a9a9a9"),
+    ("darkkhaki", "bdb76b"),
+    ("darkmagenta", "8b008b"),
+    ("darkolivegreen", "556b2f"),
+    ("darkorange", "ff8c00"),
+    ("darkorchid", "9932cc"),
+    ("darkred", "8b0000"),
+    ("darksalmon", "e9967a"),
+    ("darkseagreen", "8fbc8f"),
+    ("darkslateblue", "483d8b"),
+    ("darkslategray", "2f4f4f"),
+    ("darkslategrey", "2f4f4f"),
+    ("darkturquoise", "00ced1"),
+    ("darkviolet", "9400d3"),
+    ("deeppink", "ff1493"),
+    ("deepskyblue", "00bfff"),
+    ("dimgray", "696969"),
+    ("dimgrey", "696969"),
+    ("dodgerblue", "1e90ff"),
+    ("firebrick", "b22222"),
+    ("floralwhite", "fffaf0"),
+    ("forestgreen", "228b22"),
+    ("fuchsia", "ff00ff"),
+    ("gainsboro", "dcdcdc"),
+    ("ghostwhite", "f8f8ff"),
+    ("gold", "ffd700"),
+    ("goldenrod", "daa520"),
+    ("gray", "808080"),
+    ("green", "008000"),
+    ("greenyellow", "adff2f"),
+    ("grey", "808080"),
+    ("honeydew", "f0fff0"),
+    ("hotpink", "ff69b4"),
+    ("indianred", "cd5c5c"),
+    ("indigo", "4b0082"),
+    ("ivory", "fffff0"),
+    ("khaki", "f0e68c"),
+    ("lavender", "e6e6fa"),
+    ("lavenderblush", "fff0f5"),
+    ("lawngreen", "7cfc00"),
+    ("lemonchiffon", "fffacd"),
+    ("lightblue", "add8e6"),
+    ("lightcoral", "f08080"),
+    ("lightcyan", "e0ffff"),
+    ("lightgoldenrodyellow", "fafad2"),
+    ("lightgray", "d3d3d3"),
+    ("lightgreen", "90ee90"),
+    ("lightgrey", "d3d3d3"),
+    ("lightpink", "ffb6c1"),
+    ("lightsalmon", "ffa07a"),
+    ("lightseagreen", "20b2aa"),
+    ("lightskyblue", "87cefa"),
+    ("lightslategray", "778899"),
+    ("lightslategrey", "778899"),
+    ("lightsteelblue", "b0c4de"),
+    ("lightyellow", "ffffe0"),
+    ("lime", "00ff00"),
+    ("limegreen", "32cd32"),
+    ("linen", "faf0e6"),
+    ("magenta", "ff00ff"),
+    ("maroon", "800000"),
+    ("mediumaquamarine", "66cdaa"),
+    ("mediumblue", "0000cd"),
+    ("mediumorchid", "ba55d3"),
+    ("mediumpurple", "9370db"),
+    ("mediumseagreen", "3cb371"),
+    ("mediumslateblue", "7b68ee"),
+    ("mediumspringgreen", "00fa9a"),
+    ("mediumturquoise", "48d1cc"),
+    ("mediumvioletred", "c71585"),
+    ("midnightblue", "191970"),
+    ("mintcream", "f5fffa"),
+    ("mistyrose", "ffe4e1"),
+    ("moccasin", "ffe4b5"),
+    ("navajowhite", "ffdead"),
+    ("navy", "000080"),
+    ("oldlace", "fdf5e6"),
+    ("olive", "808000"),
+    ("olivedrab", "6b8e23"),
+    ("orange", "ffa500"),
+    ("orangered", "ff4500"),
+    ("orchid", "da70d6"),
+    ("palegoldenrod", "eee8aa"),
+    ("palegreen", "98fb98"),
+    ("paleturquoise", "afeeee"),
+    ("palevioletred", "db7093"),
+    ("papayawhip", "ffefd5"),
+    ("peachpuff", "ffdab9"),
+    ("peru", "cd853f"),
+    ("pink", "ffc0cb"),
+    ("plum", "dda0dd"),
+    ("powderblue", "b0e0e6"),
+    ("purple", "800080"),
+    ("rebeccapurple", "663399"),
+    ("red", "ff0000"),
+    ("rosybrown", "bc8f8f"),
+    ("royalblue", "4169e1"),
+    ("saddlebrown", "8b4513"),
+    ("salmon", "fa8072"),
+    ("sandybrown", "f4a460"),
+    ("seagreen", "2e8b57"),
+    ("seashell", "fff5ee"),
+    ("sienna", "a0522d"),
+    ("silver", "c0c0c0"),
+    ("skyblue", "87ceeb"),
+    ("slateblue", "6a5acd"),
+    ("slategray", "708090"),
+    ("slategrey", "708090"),
+    ("snow", "fffafa"),
+    ("springgreen", "00ff7f"),
+    ("steelblue", "4682b4"),
+    ("tan", "d2b48c"),
+    ("teal", "008080"),
+    ("thistle", "d8bfd8"),
+    ("tomato", "ff6347"),
+    ("turquoise", "40e0d0"),
+    ("violet", "ee82ee"),
+    ("wheat", "f5deb3"),
+    ("white", "ffffff"),
+    ("whitesmoke", "f5f5f5"),
+    ("yellow", "ffff00"),
+    ("yellowgreen", "9acd32"),
+];
+
+fn hex_for_name(name: &str) -> Option<&'static str> {
+    NAMED_COLORS.iter().find(|(n, _)| *n == name).map(|(_, h)| *h)
+}
+
+/// The shortest name among every entry sharing `hex6` (ties broken by
+/// whichever appears first in [`NAMED_COLORS`], e.g. `gray` over `grey`).
+fn name_for_hex(hex6: &str) -> Option<&'static str> {
+    NAMED_COLORS
+        .iter()
+        .filter(|(_, h)| *h == hex6)
+        .min_by_key(|(n, _)| n.len())
+        .map(|(n, _)| *n)
+}
+
+/// Expands a 3- or 6-digit hex run (without `#`) to its canonical 6-digit
+/// lowercase form. Returns `None` for any other length - a 4- or 8-digit
+/// run carries alpha, which no named color has an equivalent for.
+fn expand_to_six_digits(hex: &str) -> Option<String> {
+    match hex.len() {
+        3 => Some(
+            hex.chars()
+                .flat_map(|c| [c, c])
+                .collect::<String>()
+                .to_ascii_lowercase(),
+        ),
+        6 => Some(hex.to_ascii_lowercase()),
+        _ => None,
+    }
+}
+
+/// The shortest hex spelling of `name`'s color, with a leading `#`, run
+/// through the same shortening [`Token::Hash`] spans already get.
+fn shortest_hex_for_name(name: &str) -> Option<String> {
+    let hex6 = hex_for_name(name)?;
+    let mut chars = hex6.chars().peekable();
+    Some(format!("#{}", optimize_hex_color(&mut chars)))
+}
+
+/// Walks `spans`, swapping a `Token::Hash` at a value position for a named
+/// color when the name is shorter, and a `Token::Ident` at a value position
+/// matching a named color for its hex equivalent when the hex is shorter.
+/// Assumes `Token::Hash` spans have already been through
+/// [`super::hex_colors::optimize_hex_color`] (e.g. via
+/// [`super::minifier::minify_css_core`]'s pass ordering).
+pub fn normalize_color_names(spans: Vec<TokenSpan>) -> Vec<TokenSpan> {
+    let value_positions = classify_value_positions(&spans);
+
+    spans
+        .into_iter()
+        .enumerate()
+        .map(|(i, span)| {
+            if !value_positions[i] {
+                return span;
+            }
+            match span.token {
+                Token::Hash => {
+                    let Some(hex6) = expand_to_six_digits(&span.text[1..]) else {
+                        return span;
+                    };
+                    match name_for_hex(&hex6) {
+                        Some(name) if name.len() < span.text.len() => {
+                            TokenSpan { token: Token::Ident, text: name.to_string() }
+                        }
+                        _ => span,
+                    }
+                }
+                Token::Ident => {
+                    let lower = span.text.to_ascii_lowercase();
+                    match shortest_hex_for_name(&lower) {
+                        Some(hex) if hex.len() < span.text.len() => {
+                            TokenSpan { token: Token::Hash, text: hex }
+                        }
+                        _ => span,
+                    }
+                }
+                _ => span,
+            }
+        })
+        .collect()
+}
+
+/// Marks which token indices fall inside a declaration's value - after a
+/// top-level `:` and before the terminating `;`/`}` - as opposed to a
+/// selector header (including pseudo-class colons like `a:hover`) or a
+/// property name.
+///
+/// Scans the token stream as a sequence of segments, each bounded by the
+/// next unparenthesized `;`, `{`, or `}`. A segment ending in `{` is a
+/// selector header (any colon inside it, e.g. `a:hover`, is a pseudo-class,
+/// not a property separator) and contributes no value positions; a segment
+/// ending in `;`/`}`/end-of-input is a declaration, whose first
+/// unparenthesized colon (if any) splits it into property and value. This
+/// requires no explicit brace-depth tracking: a nested block's own
+/// declarations and selector headers are just the segments that follow.
+fn classify_value_positions(spans: &[TokenSpan]) -> Vec<bool> {
+    let mut in_value = vec![false; spans.len()];
+    let mut i = 0;
+
+    while i < spans.len() {
+        let mut colon_idx = None;
+        let mut paren_depth = 0i32;
+        let mut j = i;
+
+        while j < spans.len() {
+            match spans[j].token {
+                Token::Delim('(') => paren_depth += 1,
+                Token::Delim(')') => paren_depth -= 1,
+                Token::Colon if paren_depth == 0 && colon_idx.is_none() => colon_idx = Some(j),
+                Token::Semicolon | Token::BlockOpen | Token::BlockClose if paren_depth == 0 => break,
+                _ => {}
+            }
+            j += 1;
+        }
+
+        let is_selector_header = spans.get(j).map(|s| s.token) == Some(Token::BlockOpen);
+        if !is_selector_header {
+            if let Some(colon_idx) = colon_idx {
+                for slot in in_value.iter_mut().take(j).skip(colon_idx + 1) {
+                    *slot = true;
+                }
+            }
+        }
+
+        i = if j < spans.len() { j + 1 } else { j };
+    }
+
+    in_value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::tokenizer::tokenize;
+
+    fn run(css: &str) -> Vec<TokenSpan> {
+        normalize_color_names(tokenize(css))
+    }
+
+    fn joined(spans: &[TokenSpan]) -> String {
+        spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_hex_converts_to_shorter_name() {
+        // #808080 is already its shortest hex form, and "gray" is shorter.
+        let spans = run("color:#808080;");
+        assert_eq!(joined(&spans), "color:gray;");
+    }
+
+    #[test]
+    fn test_name_converts_to_shorter_hex() {
+        let spans = run("color:white;");
+        assert_eq!(joined(&spans), "color:#fff;");
+    }
+
+    #[test]
+    fn test_hex_that_is_already_shorter_than_its_name_is_left_alone() {
+        let spans = run("color:#fff;");
+        assert_eq!(joined(&spans), "color:#fff;");
+    }
+
+    #[test]
+    fn test_name_that_is_already_shorter_than_its_hex_is_left_alone() {
+        let spans = run("color:red;");
+        assert_eq!(joined(&spans), "color:red;");
+    }
+
+    #[test]
+    fn test_selector_pseudo_class_colon_is_not_a_value_position() {
+        // "hover" must never be looked up as if it were a declaration value.
+        let spans = run("a:hover{color:white;}");
+        assert_eq!(joined(&spans), "a:hover{color:#fff;}");
+    }
+
+    #[test]
+    fn test_id_selector_hash_is_left_untouched() {
+        let spans = run("#white{color:red;}");
+        assert_eq!(joined(&spans), "#white{color:red;}");
+    }
+
+    #[test]
+    fn test_class_selector_ident_is_left_untouched() {
+        let spans = run(".red{color:red;}");
+        assert_eq!(joined(&spans), ".red{color:red;}");
+    }
+
+    #[test]
+    fn test_property_name_is_left_untouched() {
+        // Not a real property, but it shouldn't matter - it's before the
+        // colon, so it's never even considered a color candidate.
+        let spans = run("gray:red;");
+        assert_eq!(joined(&spans), "gray:red;");
+    }
+
+    #[test]
+    fn test_converts_inside_function_arguments() {
+        // Both "white" (#fff, 4 chars) and "black" (#000, 4 chars) are
+        // longer than their hex equivalents.
+        let spans = run("background:linear-gradient(white,black);");
+        assert_eq!(joined(&spans), "background:linear-gradient(#fff,#000);");
+    }
+
+    #[test]
+    fn test_nested_block_value_is_still_converted() {
+        let spans = run("@media(max-width:600px){.foo{color:white;}}");
+        assert_eq!(joined(&spans), "@media(max-width:600px){.foo{color:#fff;}}");
+    }
+
+    #[test]
+    fn test_last_declaration_without_trailing_semicolon_is_converted() {
+        let spans = run("a{color:white}");
+        assert_eq!(joined(&spans), "a{color:#fff}");
+    }
+
+    #[test]
+    fn test_string_value_is_left_untouched() {
+        let spans = run("content:\"white\";");
+        assert_eq!(joined(&spans), "content:\"white\";");
+    }
+}
@@ -0,0 +1,235 @@
+//! Drops a declaration that's immediately shadowed by a later one for the
+//! same property within the same rule block (`color:red;color:blue;` ->
+//! `color:blue;`), the same way a browser's cascade would resolve it for two
+//! declarations of equal specificity. An `!important` declaration is the one
+//! exception - a later non-important declaration can't override it, so the
+//! later one is dropped instead.
+//!
+//! This walks brace nesting directly rather than building a full rule tree:
+//! a `{`/`}` pair's prelude decides whether its body holds further rules to
+//! recurse into (`@media`/`@supports`/`@keyframes`-style grouping rules) or
+//! a flat list of declarations to dedupe.
+
+use super::tokenizer::{Token, TokenSpan};
+use std::collections::HashMap;
+
+/// At-rule names whose block holds nested rules rather than declarations
+/// directly, so recursion (not deduping) is what needs to happen to their
+/// body.
+const NESTED_RULE_AT_RULES: &[&str] =
+    &["media", "supports", "document", "layer", "keyframes", "-webkit-keyframes", "-moz-keyframes", "-o-keyframes"];
+
+/// Walks `spans`, deduping declarations inside every plain rule block and
+/// recursing into every grouping at-rule's block, leaving everything else
+/// (selectors, preludes, statement-only at-rules like `@import ...;`)
+/// untouched.
+pub fn dedupe_declarations(spans: Vec<TokenSpan>) -> Vec<TokenSpan> {
+    process_block(&spans)
+}
+
+fn process_block(spans: &[TokenSpan]) -> Vec<TokenSpan> {
+    let mut out = Vec::with_capacity(spans.len());
+    let mut prelude_start = 0;
+    let mut i = 0;
+
+    while i < spans.len() {
+        if spans[i].token == Token::BlockOpen {
+            let prelude = &spans[prelude_start..i];
+            let close = matching_block_close(spans, i);
+            let body = &spans[i + 1..close.min(spans.len())];
+
+            out.extend_from_slice(prelude);
+            out.push(spans[i].clone());
+            if prelude_is_nested_rule_at_rule(prelude) {
+                out.extend(process_block(body));
+            } else {
+                out.extend(dedupe_block_declarations(body));
+            }
+            if close < spans.len() {
+                out.push(spans[close].clone());
+                i = close + 1;
+            } else {
+                i = spans.len();
+            }
+            prelude_start = i;
+        } else {
+            i += 1;
+        }
+    }
+
+    out.extend_from_slice(&spans[prelude_start..i]);
+    out
+}
+
+/// Finds the `BlockClose` matching the `BlockOpen` at `spans[open]`, or
+/// `spans.len()` if the input is malformed and never closes it.
+fn matching_block_close(spans: &[TokenSpan], open: usize) -> usize {
+    let mut depth = 1;
+    let mut j = open + 1;
+    while j < spans.len() {
+        match spans[j].token {
+            Token::BlockOpen => depth += 1,
+            Token::BlockClose => {
+                depth -= 1;
+                if depth == 0 {
+                    return j;
+                }
+            }
+            _ => {}
+        }
+        j += 1;
+    }
+    spans.len()
+}
+
+/// Whether `prelude` is one of [`NESTED_RULE_AT_RULES`], identified by an
+/// `@` delim directly followed by the at-rule's name.
+fn prelude_is_nested_rule_at_rule(prelude: &[TokenSpan]) -> bool {
+    prelude
+        .iter()
+        .position(|s| s.token == Token::Delim('@'))
+        .and_then(|i| prelude.get(i + 1))
+        .is_some_and(|s| s.token == Token::Ident && NESTED_RULE_AT_RULES.iter().any(|name| s.text.eq_ignore_ascii_case(name)))
+}
+
+/// Splits `body` into declaration segments on top-level `;` tokens (a
+/// declaration value never itself contains a block, so no brace-depth
+/// tracking is needed), then drops every declaration that a later one in the
+/// same block shadows.
+fn dedupe_block_declarations(body: &[TokenSpan]) -> Vec<TokenSpan> {
+    let ranges = split_on_semicolons(body);
+    let names: Vec<Option<String>> = ranges.iter().map(|&(s, e)| declaration_name(&body[s..e])).collect();
+    let important: Vec<bool> = ranges.iter().map(|&(s, e)| declaration_is_important(&body[s..e])).collect();
+
+    let mut last_kept: HashMap<String, usize> = HashMap::new();
+    let mut dropped = vec![false; ranges.len()];
+
+    for (idx, name) in names.iter().enumerate() {
+        let Some(name) = name else { continue };
+        if let Some(&prev) = last_kept.get(name) {
+            if important[prev] && !important[idx] {
+                dropped[idx] = true;
+                continue;
+            }
+            dropped[prev] = true;
+        }
+        last_kept.insert(name.clone(), idx);
+    }
+
+    let mut out = Vec::with_capacity(body.len());
+    for (idx, &(start, end)) in ranges.iter().enumerate() {
+        if !dropped[idx] {
+            out.extend_from_slice(&body[start..end]);
+        }
+    }
+    out
+}
+
+fn split_on_semicolons(body: &[TokenSpan]) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for (idx, span) in body.iter().enumerate() {
+        if span.token == Token::Semicolon {
+            ranges.push((start, idx + 1));
+            start = idx + 1;
+        }
+    }
+    if start < body.len() {
+        ranges.push((start, body.len()));
+    }
+    ranges
+}
+
+/// Extracts a declaration segment's property name, lowercased for
+/// case-insensitive comparison - but only if the segment actually looks like
+/// `<ident> : ...`, so a malformed or non-declaration segment (trailing
+/// whitespace before the closing brace, a stray token) is left alone rather
+/// than being mistaken for a duplicate of something else.
+fn declaration_name(segment: &[TokenSpan]) -> Option<String> {
+    let mut tokens = segment.iter().filter(|s| !matches!(s.token, Token::Whitespace | Token::Comment));
+    let name_span = tokens.next()?;
+    if name_span.token != Token::Ident {
+        return None;
+    }
+    if tokens.next()?.token != Token::Colon {
+        return None;
+    }
+    Some(name_span.text.to_ascii_lowercase())
+}
+
+/// Whether a declaration segment ends in `!important` (whitespace between
+/// the `!` and `important` is allowed, as CSS permits it).
+fn declaration_is_important(segment: &[TokenSpan]) -> bool {
+    let mut saw_bang = false;
+    for span in segment.iter().filter(|s| !matches!(s.token, Token::Whitespace | Token::Comment)) {
+        if span.token == Token::Delim('!') {
+            saw_bang = true;
+            continue;
+        }
+        if saw_bang && span.token == Token::Ident && span.text.eq_ignore_ascii_case("important") {
+            return true;
+        }
+        saw_bang = false;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::tokenizer::tokenize;
+
+    fn run(css: &str) -> String {
+        dedupe_declarations(tokenize(css)).into_iter().map(|s| s.text).collect()
+    }
+
+    #[test]
+    fn test_later_declaration_overrides_earlier_one() {
+        assert_eq!(run("a{color:red;color:blue;}"), "a{color:blue;}");
+    }
+
+    #[test]
+    fn test_unrelated_properties_are_both_kept() {
+        assert_eq!(run("a{color:red;margin:0;}"), "a{color:red;margin:0;}");
+    }
+
+    #[test]
+    fn test_important_earlier_declaration_survives_later_non_important_one() {
+        assert_eq!(run("a{color:red!important;color:blue;}"), "a{color:red!important;}");
+    }
+
+    #[test]
+    fn test_later_important_declaration_still_overrides_earlier_one() {
+        assert_eq!(run("a{color:red;color:blue!important;}"), "a{color:blue!important;}");
+    }
+
+    #[test]
+    fn test_property_names_compared_case_insensitively() {
+        assert_eq!(run("a{COLOR:red;color:blue;}"), "a{color:blue;}");
+    }
+
+    #[test]
+    fn test_three_declarations_of_same_property_keeps_only_the_last() {
+        assert_eq!(run("a{color:red;color:green;color:blue;}"), "a{color:blue;}");
+    }
+
+    #[test]
+    fn test_nested_rule_inside_media_query_is_deduped() {
+        assert_eq!(run("@media (min-width:600px){a{color:red;color:blue;}}"), "@media (min-width:600px){a{color:blue;}}");
+    }
+
+    #[test]
+    fn test_keyframes_block_is_deduped() {
+        assert_eq!(run("@keyframes fade{from{opacity:0;opacity:.2;}to{opacity:1;}}"), "@keyframes fade{from{opacity:.2;}to{opacity:1;}}");
+    }
+
+    #[test]
+    fn test_import_statement_without_a_block_is_untouched() {
+        assert_eq!(run("@import \"x.css\";a{color:red;}"), "@import \"x.css\";a{color:red;}");
+    }
+
+    #[test]
+    fn test_multiple_sibling_rules_deduped_independently() {
+        assert_eq!(run("a{color:red;color:blue;}b{margin:0;margin:1px;}"), "a{color:blue;}b{margin:1px;}");
+    }
+}
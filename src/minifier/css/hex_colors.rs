@@ -10,16 +10,18 @@ extern "C" {
 }
 
 #[cfg(target_arch = "x86_64")]
-/// Optimizes a hex color by shortening it from 6 digits to 3 digits when possible
-/// Returns the optimized color string (without the # prefix)
+/// Optimizes a hex color by shortening `#rrggbb` to `#rgb` (and `#rrggbbaa`
+/// to `#rgba`) when every channel pair is doubled.
+/// Returns the optimized color string (without the `#` prefix).
 pub fn optimize_hex_color(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
-    // Collect up to 6 characters to create a buffer for scanning
+    // Collect up to 8 characters (the longest hex color form, #rrggbbaa)
+    // to create a buffer for scanning.
     let mut temp_chars = Vec::new();
     let mut peek_count = 0;
 
     // Peek ahead to see what characters we have
     loop {
-        if peek_count >= 6 {
+        if peek_count >= 8 {
             break;
         }
         if let Some(&next_ch) = chars.peek() {
@@ -39,6 +41,14 @@ pub fn optimize_hex_color(chars: &mut std::iter::Peekable<std::str::Chars>) -> S
         return String::new();
     }
 
+    // The 8-digit (#rrggbbaa) shortening check has no assembly counterpart
+    // yet, so it's done directly in Rust; the existing 6-digit (#rrggbb)
+    // path still goes through the assembly scan below.
+    if temp_chars.len() == 8 {
+        let color_chars: Vec<char> = temp_chars.iter().map(|&b| b as char).collect();
+        return shorten_eight_digit(&color_chars);
+    }
+
     // Use assembly to check if we can shorten
     let mut can_shorten: u8 = 0;
     let consumed =
@@ -58,12 +68,14 @@ pub fn optimize_hex_color(chars: &mut std::iter::Peekable<std::str::Chars>) -> S
 }
 
 #[cfg(not(target_arch = "x86_64"))]
-/// Optimizes a hex color by shortening it from 6 digits to 3 digits when possible
-/// Returns the optimized color string (without the # prefix)
+/// Optimizes a hex color by shortening `#rrggbb` to `#rgb` (and `#rrggbbaa`
+/// to `#rgba`) when every channel pair is doubled.
+/// Returns the optimized color string (without the `#` prefix).
 pub fn optimize_hex_color(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
-    // Collect the next 6 characters to see if it's a hex color
+    // Collect up to 8 characters (the longest hex color form, #rrggbbaa)
+    // to see if it's a hex color.
     let mut color_chars = Vec::new();
-    for _ in 0..6 {
+    for _ in 0..8 {
         if let Some(&next_ch) = chars.peek() {
             if next_ch.is_ascii_hexdigit() {
                 color_chars.push(chars.next().unwrap());
@@ -75,22 +87,43 @@ pub fn optimize_hex_color(chars: &mut std::iter::Peekable<std::str::Chars>) -> S
         }
     }
 
-    // If we have exactly 6 hex digits, check if we can shorten it
-    if color_chars.len() == 6 {
-        let can_shorten = color_chars[0] == color_chars[1]
-            && color_chars[2] == color_chars[3]
-            && color_chars[4] == color_chars[5];
-
-        if can_shorten {
-            // Return the shortened version
-            format!("{}{}{}", color_chars[0], color_chars[2], color_chars[4])
-        } else {
-            // Return the full version
-            color_chars.into_iter().collect()
+    match color_chars.len() {
+        // #rrggbbaa shortens to #rgba only if every channel pair is doubled.
+        8 => shorten_eight_digit(&color_chars),
+        // #rrggbb shortens to #rgb only if every channel pair is doubled.
+        6 => {
+            let can_shorten = color_chars[0] == color_chars[1]
+                && color_chars[2] == color_chars[3]
+                && color_chars[4] == color_chars[5];
+
+            if can_shorten {
+                format!("{}{}{}", color_chars[0], color_chars[2], color_chars[4])
+            } else {
+                color_chars.into_iter().collect()
+            }
         }
+        // #rgba is already minimal; anything else (e.g. #rgb, a stray run
+        // of digits) isn't ours to shorten - return it untouched.
+        _ => color_chars.into_iter().collect(),
+    }
+}
+
+/// Shortens an 8-digit `rrggbbaa` run to `rgba` if every channel pair is
+/// doubled, otherwise returns it unchanged. Shared by both
+/// [`optimize_hex_color`] implementations above.
+fn shorten_eight_digit(color_chars: &[char]) -> String {
+    let can_shorten = color_chars[0] == color_chars[1]
+        && color_chars[2] == color_chars[3]
+        && color_chars[4] == color_chars[5]
+        && color_chars[6] == color_chars[7];
+
+    if can_shorten {
+        format!(
+            "{}{}{}{}",
+            color_chars[0], color_chars[2], color_chars[4], color_chars[6]
+        )
     } else {
-        // Not a 6-digit hex color, return as-is
-        color_chars.into_iter().collect()
+        color_chars.iter().collect()
     }
 }
 
@@ -131,4 +164,35 @@ mod tests {
         let mut chars = "1234".chars().peekable();
         assert_eq!(optimize_hex_color(&mut chars), "1234");
     }
+
+    #[test]
+    fn test_eight_digit_hex_with_doubled_channels_shortens_to_four() {
+        let mut chars = "aabbccdd".chars().peekable();
+        assert_eq!(optimize_hex_color(&mut chars), "abcd");
+    }
+
+    #[test]
+    fn test_eight_digit_hex_without_doubled_channels_is_left_as_is() {
+        let mut chars = "12345678".chars().peekable();
+        assert_eq!(optimize_hex_color(&mut chars), "12345678");
+    }
+
+    #[test]
+    fn test_four_digit_hex_is_left_as_is() {
+        let mut chars = "f0a1".chars().peekable();
+        assert_eq!(optimize_hex_color(&mut chars), "f0a1");
+    }
+
+    #[test]
+    fn test_nine_hex_chars_only_consumes_eight_and_leaves_trailing_digit() {
+        let mut chars = "123456789".chars().peekable();
+        assert_eq!(optimize_hex_color(&mut chars), "12345678");
+        assert_eq!(chars.collect::<String>(), "9");
+    }
+
+    #[test]
+    fn test_five_digit_run_is_emitted_verbatim() {
+        let mut chars = "12345".chars().peekable();
+        assert_eq!(optimize_hex_color(&mut chars), "12345");
+    }
 }
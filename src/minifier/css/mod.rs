@@ -4,23 +4,35 @@
 //! comments, and optimizing hex colors while preserving CSS functionality.
 //!
 //! The module is organized into separate components:
-//! - `comments`: CSS comment detection and removal
+//! - `at_rules`: Collapses `@import url(...)` to `@import "..."` and
+//!   trims/unquotes `url()` calls elsewhere
+//! - `color_functions`: Converts `rgb()`/`rgba()`/`hsl()`/`hsla()` calls to
+//!   their shortest equivalent hex color
+//! - `color_names`: Swaps a named color and its hex literal for whichever
+//!   spelling is shorter, at declaration-value positions only
+//! - `duplicate_declarations`: Drops an earlier declaration of the same
+//!   property within a rule block once a later one shadows it
 //! - `hex_colors`: Hex color optimization (with `x86_64` assembly optimization)
-//! - `minifier`: Main minification orchestration
-//! - `strings`: String literal handling
-//! - `whitespace`: Complex whitespace preservation rules
+//! - `minifier`: Main minification orchestration, built on top of `tokenizer`
+//! - `numbers`: Lowercases units and shortens numeric literals (leading/
+//!   trailing zeros, unitless zero lengths)
+//! - `tokenizer`: Scans CSS into typed tokens (identifiers, numbers, hashes,
+//!   strings, comments, ...); also reusable outside this module, e.g. by the
+//!   SCSS converter, for anything that needs a CSS-aware lexer
 
-mod comments;
+mod at_rules;
+mod color_functions;
+mod color_names;
+mod duplicate_declarations;
 mod hex_colors;
 mod minifier;
-mod should_preserve_space;
-mod strings;
-mod whitespace;
+mod numbers;
+pub mod tokenizer;
 
 use crate::traits::Minifier;
 
 // Re-export the main minify function
-pub use minifier::minify_css;
+pub use minifier::{minify_css, minify_css_with_source_map};
 
 /// CSS minifier implementation
 pub struct CssMinifier;
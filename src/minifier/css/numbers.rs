@@ -0,0 +1,232 @@
+//! Normalizes numeric literals in the token stream: lowercases units
+//! (`10PX` -> `10px`), strips a leading zero before the point (`0.6in` ->
+//! `.6in`) and trailing zeros in the fraction (`1.50rem` -> `1.5rem`),
+//! collapses `0.0` -> `0`, and drops the unit from a zero length (`0px` ->
+//! `0`) except inside `calc()`, where a bare `0` can change how the
+//! expression's operands are typed, and for angle/time/frequency/resolution
+//! units, which CSS requires even when the value is zero.
+//!
+//! Unlike [`super::color_names`], this isn't restricted to declaration-value
+//! positions: a numeric literal is unambiguous wherever the tokenizer
+//! produces a [`Token::Number`]/[`Token::Dimension`]/[`Token::Percentage`]
+//! span - e.g. inside an `:nth-child(2n+1)` selector - and every
+//! transformation here is meaning-preserving regardless of context.
+
+use super::tokenizer::{Token, TokenSpan};
+
+/// `<length>` units for which CSS allows a zero value to drop its unit
+/// entirely. Angle (`deg`, `grad`, `rad`, `turn`), time (`s`, `ms`),
+/// frequency (`hz`, `khz`), and resolution (`dpi`, `dpcm`, `dppx`) units are
+/// deliberately excluded - CSS requires them even on a zero value.
+const SAFE_ZERO_LENGTH_UNITS: &[&str] =
+    &["em", "ex", "ch", "rem", "vw", "vh", "vmin", "vmax", "cm", "mm", "q", "in", "pt", "pc", "px"];
+
+/// Walks `spans`, normalizing every `Number`/`Dimension`/`Percentage` span
+/// while tracking whether each one sits inside a `calc()` call's parens (the
+/// "in calc" state is inherited by any parens nested inside it, e.g.
+/// `calc(min(0px, 1px))`, since they're still part of the same expression).
+pub fn normalize_numbers(spans: Vec<TokenSpan>) -> Vec<TokenSpan> {
+    let mut in_calc_stack: Vec<bool> = Vec::new();
+    let mut out = Vec::with_capacity(spans.len());
+    let mut i = 0;
+
+    while i < spans.len() {
+        match spans[i].token {
+            Token::Function => {
+                let is_calc = spans[i].text.eq_ignore_ascii_case("calc");
+                out.push(spans[i].clone());
+                i += 1;
+                if spans.get(i).map(|s| s.token) == Some(Token::Delim('(')) {
+                    let parent_in_calc = in_calc_stack.last().copied().unwrap_or(false);
+                    in_calc_stack.push(is_calc || parent_in_calc);
+                    out.push(spans[i].clone());
+                    i += 1;
+                }
+            }
+            Token::Delim('(') => {
+                let parent_in_calc = in_calc_stack.last().copied().unwrap_or(false);
+                in_calc_stack.push(parent_in_calc);
+                out.push(spans[i].clone());
+                i += 1;
+            }
+            Token::Delim(')') => {
+                in_calc_stack.pop();
+                out.push(spans[i].clone());
+                i += 1;
+            }
+            Token::Number | Token::Dimension | Token::Percentage => {
+                let in_calc = in_calc_stack.last().copied().unwrap_or(false);
+                out.push(normalize_numeric_span(&spans[i], in_calc));
+                i += 1;
+            }
+            _ => {
+                out.push(spans[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+fn normalize_numeric_span(span: &TokenSpan, in_calc: bool) -> TokenSpan {
+    match span.token {
+        Token::Number => TokenSpan { token: Token::Number, text: normalize_number_text(&span.text) },
+
+        Token::Percentage => {
+            let numeric = &span.text[..span.text.len() - 1];
+            TokenSpan { token: Token::Percentage, text: format!("{}%", normalize_number_text(numeric)) }
+        }
+
+        Token::Dimension => {
+            let (numeric, unit) = split_number_and_unit(&span.text);
+            let normalized_numeric = normalize_number_text(numeric);
+            let lower_unit = unit.to_ascii_lowercase();
+            if !in_calc && normalized_numeric == "0" && SAFE_ZERO_LENGTH_UNITS.contains(&lower_unit.as_str()) {
+                TokenSpan { token: Token::Number, text: "0".to_string() }
+            } else {
+                TokenSpan { token: Token::Dimension, text: format!("{normalized_numeric}{lower_unit}") }
+            }
+        }
+
+        _ => span.clone(),
+    }
+}
+
+/// Splits a number's text (no unit) into `(sign, integer, fraction)` and
+/// rebuilds the shortest equivalent spelling: trailing fraction zeros are
+/// stripped (dropping the point entirely if nothing is left), a leading
+/// zero before the point is dropped, and a value that collapses to zero
+/// loses its sign (`-0` -> `0`).
+fn normalize_number_text(num: &str) -> String {
+    let (sign, rest) = match num.strip_prefix('-') {
+        Some(r) => ("-", r),
+        None => ("", num),
+    };
+    let (int_part, frac_part) = match rest.split_once('.') {
+        Some((i, f)) => (i, f),
+        None => (rest, ""),
+    };
+
+    let trimmed_int = int_part.trim_start_matches('0');
+    let trimmed_frac = frac_part.trim_end_matches('0');
+
+    if trimmed_frac.is_empty() {
+        if trimmed_int.is_empty() { "0".to_string() } else { format!("{sign}{trimmed_int}") }
+    } else if trimmed_int.is_empty() {
+        format!("{sign}.{trimmed_frac}")
+    } else {
+        format!("{sign}{trimmed_int}.{trimmed_frac}")
+    }
+}
+
+/// Splits a `Dimension` span's text into its numeric part and unit, e.g.
+/// `"-1.5rem"` -> `("-1.5", "rem")`.
+fn split_number_and_unit(text: &str) -> (&str, &str) {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    if bytes.first() == Some(&b'-') {
+        i += 1;
+    }
+    while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+        i += 1;
+    }
+    if bytes.get(i) == Some(&b'.') {
+        i += 1;
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+    }
+    text.split_at(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::tokenizer::tokenize;
+
+    fn run(css: &str) -> Vec<TokenSpan> {
+        normalize_numbers(tokenize(css))
+    }
+
+    fn joined(spans: &[TokenSpan]) -> String {
+        spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_lowercases_unit() {
+        assert_eq!(joined(&run("width:10PX;")), "width:10px;");
+    }
+
+    #[test]
+    fn test_strips_leading_zero_before_point() {
+        assert_eq!(joined(&run("margin:0.60in;")), "margin:.6in;");
+    }
+
+    #[test]
+    fn test_strips_trailing_zero_in_fraction() {
+        assert_eq!(joined(&run("margin:1.50rem;")), "margin:1.5rem;");
+    }
+
+    #[test]
+    fn test_collapses_zero_point_zero() {
+        assert_eq!(joined(&run("opacity:0.0;")), "opacity:0;");
+    }
+
+    #[test]
+    fn test_drops_unit_from_zero_length() {
+        assert_eq!(joined(&run("border:0px;")), "border:0;");
+    }
+
+    #[test]
+    fn test_keeps_unit_for_zero_angle() {
+        assert_eq!(joined(&run("transform:rotate(0deg);")), "transform:rotate(0deg);");
+    }
+
+    #[test]
+    fn test_keeps_unit_for_zero_time() {
+        assert_eq!(joined(&run("transition-delay:0s;")), "transition-delay:0s;");
+    }
+
+    #[test]
+    fn test_does_not_drop_unit_inside_calc() {
+        assert_eq!(joined(&run("width:calc(0px + 100%);")), "width:calc(0px + 100%);");
+    }
+
+    #[test]
+    fn test_drops_unit_outside_a_sibling_calc_call() {
+        // The zero here isn't inside calc()'s own parens, so it's still safe.
+        let spans = run("margin:0px calc(1px + 1px);");
+        assert_eq!(joined(&spans), "margin:0 calc(1px + 1px);");
+    }
+
+    #[test]
+    fn test_negative_zero_becomes_bare_zero() {
+        assert_eq!(joined(&run("margin:-0.0em;")), "margin:0;");
+    }
+
+    #[test]
+    fn test_percentage_numeric_part_is_normalized_but_percent_sign_kept() {
+        assert_eq!(joined(&run("width:050.0%;")), "width:50%;");
+    }
+
+    #[test]
+    fn test_non_zero_dimension_keeps_its_unit() {
+        assert_eq!(joined(&run("height:22MM;")), "height:22mm;");
+    }
+
+    #[test]
+    fn test_drops_unit_from_fractional_zero() {
+        assert_eq!(joined(&run("margin:0.0em;")), "margin:0;");
+    }
+
+    #[test]
+    fn test_lowercases_mixed_case_unit() {
+        assert_eq!(joined(&run("height:22mM;")), "height:22mm;");
+    }
+
+    #[test]
+    fn test_nested_calc_like_min_inherits_in_calc_state() {
+        assert_eq!(joined(&run("width:calc(min(0px,10px) + 1px);")), "width:calc(min(0px,10px) + 1px);");
+    }
+}
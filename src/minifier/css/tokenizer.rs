@@ -0,0 +1,362 @@
+//! A small CSS tokenizer, modeled on cssparser: scan the source into typed
+//! tokens instead of guessing whitespace significance from raw character
+//! pairs. [`minify_css`](super::minify_css) walks the resulting stream and
+//! only keeps a space between two tokens whose concatenated text would
+//! otherwise re-parse as something else - the same "serialization round
+//! trips" invariant cssparser enforces on its own token kinds.
+//!
+//! String and comment scanning itself is delegated to [`crate::lexer`], the
+//! core shared with the SCSS and TypeScript scanners (escaped quotes don't
+//! end a string, `/*` doesn't nest); a `/*!`-prefixed comment is flagged via
+//! [`is_preserved_comment`] for the minifier to keep verbatim.
+
+use crate::lexer::{self, TokenKind};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// A single lexical unit of a CSS source string. Carries no text itself -
+/// see [`TokenSpan::text`] for the raw slice it was scanned from - so two
+/// tokens of the same kind compare equal regardless of content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token {
+    /// A CSS identifier: property/value keywords, selector names, units
+    /// once split off a number, etc. (`solid`, `-webkit-transform`).
+    Ident,
+    /// A bare number with no unit (`0`, `1.5`, `-1`).
+    Number,
+    /// A number immediately followed by a unit (`10px`, `1.5rem`).
+    Dimension,
+    /// A number immediately followed by `%` (`50%`).
+    Percentage,
+    /// A `#` followed by an identifier run: a hex color or an id selector
+    /// (`#fff`, `#myid`).
+    Hash,
+    /// An identifier immediately followed by `(` with no space, e.g. the
+    /// `rgba` in `rgba(0,0,0,0.5)`. The `(` itself is a separate [`Delim`]
+    /// token right after.
+    ///
+    /// [`Delim`]: Token::Delim
+    Function,
+    /// Any other single-character punctuation not classified below, e.g.
+    /// `.`, `(`, `)`, `>`, `+`, `~`, `*`, `/`, `=`.
+    Delim(char),
+    Comma,
+    Colon,
+    Semicolon,
+    /// A run of one or more space/tab/CR/LF characters.
+    Whitespace,
+    /// `{`
+    BlockOpen,
+    /// `}`
+    BlockClose,
+    /// A quoted string, including its delimiters, kept byte-for-byte.
+    String,
+    /// A `/* ... */` comment, including its delimiters.
+    Comment,
+}
+
+/// A [`Token`] paired with the exact source text it was scanned from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenSpan {
+    pub token: Token,
+    pub text: String,
+}
+
+/// Whether a [`Token::Comment`]'s text is a `/*! ... */` banner comment that
+/// the minifier must keep verbatim, rather than an ordinary one it can drop.
+pub fn is_preserved_comment(text: &str) -> bool {
+    text.starts_with("/*!")
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '-'
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+fn is_css_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\r' | '\n')
+}
+
+/// Scans `css` into a flat stream of [`TokenSpan`]s. Every byte of the input
+/// is accounted for by exactly one span's `text`, so joining all spans back
+/// together reproduces `css` unchanged.
+pub fn tokenize(css: &str) -> Vec<TokenSpan> {
+    let mut spans = Vec::new();
+    let mut chars = css.chars().peekable();
+
+    while chars.peek().is_some() {
+        spans.push(next_token(&mut chars));
+    }
+
+    spans
+}
+
+fn next_token(chars: &mut Peekable<Chars>) -> TokenSpan {
+    let c = *chars.peek().expect("next_token called at end of input");
+
+    if c == '/' && peek_second(chars) == Some('*') {
+        return lex_comment(chars);
+    }
+    if c == '"' || c == '\'' {
+        return lex_string(chars, c);
+    }
+    if is_css_whitespace(c) {
+        return lex_run(chars, Token::Whitespace, is_css_whitespace);
+    }
+    if c == '#' {
+        return lex_hash(chars);
+    }
+    if starts_number(chars) {
+        return lex_number(chars);
+    }
+    if is_ident_start(c) {
+        return lex_ident_or_function(chars);
+    }
+
+    chars.next();
+    let text = c.to_string();
+    let token = match c {
+        ',' => Token::Comma,
+        ':' => Token::Colon,
+        ';' => Token::Semicolon,
+        '{' => Token::BlockOpen,
+        '}' => Token::BlockClose,
+        other => Token::Delim(other),
+    };
+    TokenSpan { token, text }
+}
+
+fn peek_second(chars: &Peekable<Chars>) -> Option<char> {
+    chars.clone().nth(1)
+}
+
+/// Consumes a run of characters all matching `pred` into a single span of
+/// `token`. Only called once `pred` is already known to match the first
+/// character, so the run is never empty.
+fn lex_run(chars: &mut Peekable<Chars>, token: Token, pred: impl Fn(char) -> bool) -> TokenSpan {
+    let mut text = String::new();
+    while let Some(&c) = chars.peek() {
+        if !pred(c) {
+            break;
+        }
+        text.push(c);
+        chars.next();
+    }
+    TokenSpan { token, text }
+}
+
+/// Scans a `/* ... */` comment by handing the remaining input to
+/// [`lexer::first_token`] and advancing `chars` past it one character at a
+/// time (the shared lexer reports a byte length, but `chars` only advances
+/// per `char`, so the span's own char count is what's actually consumed).
+fn lex_comment(chars: &mut Peekable<Chars>) -> TokenSpan {
+    lex_via_shared_lexer(chars, |kind| matches!(kind, TokenKind::LineComment | TokenKind::BlockComment { .. }), Token::Comment)
+}
+
+fn lex_string(chars: &mut Peekable<Chars>, delimiter: char) -> TokenSpan {
+    let expected = match delimiter {
+        '"' => |kind: TokenKind| matches!(kind, TokenKind::DoubleQuotedString { .. }),
+        _ => |kind: TokenKind| matches!(kind, TokenKind::SingleQuotedString { .. }),
+    };
+    lex_via_shared_lexer(chars, expected, Token::String)
+}
+
+fn lex_via_shared_lexer(
+    chars: &mut Peekable<Chars>,
+    expected_kind: impl Fn(TokenKind) -> bool,
+    token: Token,
+) -> TokenSpan {
+    let remaining: String = chars.clone().collect();
+    let scanned = lexer::first_token(&remaining);
+    debug_assert!(expected_kind(scanned.kind));
+    let text = remaining[..scanned.len].to_string();
+    for _ in text.chars() {
+        chars.next();
+    }
+    TokenSpan { token, text }
+}
+
+fn lex_hash(chars: &mut Peekable<Chars>) -> TokenSpan {
+    let mut text = String::new();
+    text.push(chars.next().unwrap()); // '#'
+    while let Some(&c) = chars.peek() {
+        if !is_ident_continue(c) {
+            break;
+        }
+        text.push(c);
+        chars.next();
+    }
+    TokenSpan { token: Token::Hash, text }
+}
+
+/// Whether a number starts at the current position: a digit, a `.` leading
+/// into one (`.5`), or a `-` leading into either.
+fn starts_number(chars: &Peekable<Chars>) -> bool {
+    let mut lookahead = chars.clone();
+    match lookahead.next() {
+        Some(c) if c.is_ascii_digit() => true,
+        Some('.') => matches!(lookahead.next(), Some(c) if c.is_ascii_digit()),
+        Some('-') => match lookahead.next() {
+            Some(c) if c.is_ascii_digit() => true,
+            Some('.') => matches!(lookahead.next(), Some(c) if c.is_ascii_digit()),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn lex_number(chars: &mut Peekable<Chars>) -> TokenSpan {
+    let mut text = String::new();
+    if chars.peek() == Some(&'-') {
+        text.push(chars.next().unwrap());
+    }
+    while let Some(&c) = chars.peek() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+        text.push(c);
+        chars.next();
+    }
+    if chars.peek() == Some(&'.') && matches!(peek_second(chars), Some(c) if c.is_ascii_digit()) {
+        text.push(chars.next().unwrap()); // '.'
+        while let Some(&c) = chars.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            text.push(c);
+            chars.next();
+        }
+    }
+
+    match chars.peek() {
+        Some('%') => {
+            text.push(chars.next().unwrap());
+            TokenSpan { token: Token::Percentage, text }
+        }
+        Some(&c) if is_ident_start(c) => {
+            while let Some(&c) = chars.peek() {
+                if !is_ident_continue(c) {
+                    break;
+                }
+                text.push(c);
+                chars.next();
+            }
+            TokenSpan { token: Token::Dimension, text }
+        }
+        _ => TokenSpan { token: Token::Number, text },
+    }
+}
+
+fn lex_ident_or_function(chars: &mut Peekable<Chars>) -> TokenSpan {
+    let mut text = String::new();
+    while let Some(&c) = chars.peek() {
+        if !is_ident_continue(c) {
+            break;
+        }
+        text.push(c);
+        chars.next();
+    }
+
+    let token = if chars.peek() == Some(&'(') {
+        Token::Function
+    } else {
+        Token::Ident
+    };
+    TokenSpan { token, text }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(css: &str) -> Vec<Token> {
+        tokenize(css).into_iter().map(|span| span.token).collect()
+    }
+
+    #[test]
+    fn test_roundtrips_to_original_source() {
+        let css = "a.b , #c { width: 10px solid; } /* note */ .d::before{content:\"x y\"}";
+        let joined: String = tokenize(css).into_iter().map(|span| span.text).collect();
+        assert_eq!(joined, css);
+    }
+
+    #[test]
+    fn test_classifies_selector_tokens() {
+        assert_eq!(
+            kinds(".foo"),
+            vec![Token::Delim('.'), Token::Ident]
+        );
+    }
+
+    #[test]
+    fn test_classifies_number_dimension_percentage() {
+        assert_eq!(kinds("0"), vec![Token::Number]);
+        assert_eq!(kinds("10px"), vec![Token::Dimension]);
+        assert_eq!(kinds("50%"), vec![Token::Percentage]);
+        assert_eq!(kinds("-1.5rem"), vec![Token::Dimension]);
+    }
+
+    #[test]
+    fn test_classifies_hash() {
+        let spans = tokenize("#fff");
+        assert_eq!(spans[0].token, Token::Hash);
+        assert_eq!(spans[0].text, "#fff");
+    }
+
+    #[test]
+    fn test_classifies_function_vs_ident() {
+        assert_eq!(kinds("solid"), vec![Token::Ident]);
+        let spans = tokenize("rgba(0,0,0)");
+        assert_eq!(spans[0].token, Token::Function);
+        assert_eq!(spans[0].text, "rgba");
+        assert_eq!(spans[1].token, Token::Delim('('));
+    }
+
+    #[test]
+    fn test_classifies_punctuation() {
+        assert_eq!(
+            kinds(",:;{}"),
+            vec![
+                Token::Comma,
+                Token::Colon,
+                Token::Semicolon,
+                Token::BlockOpen,
+                Token::BlockClose,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_whitespace_run_is_one_token() {
+        let spans = tokenize("a   \n\tb");
+        assert_eq!(spans[1].token, Token::Whitespace);
+        assert_eq!(spans[1].text, "   \n\t");
+    }
+
+    #[test]
+    fn test_string_with_escaped_quote_stays_one_token() {
+        let spans = tokenize(r#""He said \"hi\"" "#);
+        assert_eq!(spans[0].token, Token::String);
+        assert_eq!(spans[0].text, r#""He said \"hi\"""#);
+    }
+
+    #[test]
+    fn test_preserved_vs_normal_comment() {
+        let spans = tokenize("/*! keep */ /* drop */");
+        assert_eq!(spans[0].token, Token::Comment);
+        assert!(is_preserved_comment(&spans[0].text));
+        assert_eq!(spans[2].token, Token::Comment);
+        assert!(!is_preserved_comment(&spans[2].text));
+    }
+
+    #[test]
+    fn test_unterminated_comment_consumes_rest_of_input() {
+        let spans = tokenize("/* never closes");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].token, Token::Comment);
+        assert_eq!(spans[0].text, "/* never closes");
+    }
+}
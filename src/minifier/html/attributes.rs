@@ -0,0 +1,334 @@
+//! HTML attribute optimization
+//!
+//! Collapses boolean attributes (`disabled=""` -> `disabled`) and drops
+//! redundant quoting around attribute values that contain no whitespace or
+//! characters that would otherwise terminate an unquoted value. Only
+//! rewrites attributes inside real element tags - conditional comments,
+//! raw-text element bodies (`<script>`/`<style>`/`<pre>`/`<textarea>`) and
+//! plain text are left untouched.
+
+/// HTML boolean attributes whose mere presence signals `true`; collapsing
+/// `name=""` to the bare `name` preserves the same meaning for these.
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+];
+
+fn is_boolean_attribute(name: &str) -> bool {
+    BOOLEAN_ATTRIBUTES.contains(&name.to_lowercase().as_str())
+}
+
+/// A value can be written without quotes only if it contains none of the
+/// characters HTML5 reserves as unquoted-attribute-value terminators.
+fn is_safe_unquoted(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| !c.is_whitespace() && !matches!(c, '"' | '\'' | '=' | '<' | '>' | '`'))
+}
+
+/// Rewrites the attributes found in a single tag's inner text (everything
+/// between `<` and `>`, exclusive).
+fn optimize_tag_attributes(tag_body: &str) -> String {
+    let mut result = String::with_capacity(tag_body.len());
+    let mut chars = tag_body.chars().peekable();
+    let mut current_name = String::new();
+
+    while let Some(ch) = chars.next() {
+        if ch == '=' && matches!(chars.peek(), Some('"') | Some('\'')) {
+            let quote = chars.next().unwrap();
+            let mut value = String::new();
+            for vch in chars.by_ref() {
+                if vch == quote {
+                    break;
+                }
+                value.push(vch);
+            }
+
+            // An unquoted value runs up to the next whitespace or `>`, so a
+            // `/` immediately following it (the self-closing marker, with no
+            // separating space) would be parsed as part of the value itself.
+            let followed_by_slash = chars.peek() == Some(&'/');
+
+            if value.is_empty() && is_boolean_attribute(&current_name) {
+                // Drop `=""`/`=''` entirely; the bare name was already pushed.
+            } else if is_safe_unquoted(&value) && !followed_by_slash {
+                result.push('=');
+                result.push_str(&value);
+            } else {
+                result.push('=');
+                result.push(quote);
+                result.push_str(&value);
+                result.push(quote);
+            }
+
+            current_name.clear();
+            continue;
+        }
+
+        if ch.is_whitespace() {
+            current_name.clear();
+        } else {
+            current_name.push(ch);
+        }
+        result.push(ch);
+    }
+
+    result
+}
+
+/// Finds the index of the `>` that closes the tag starting at byte 0 of
+/// `rest` (which begins with `<`), respecting quoted attribute values so a
+/// `>` inside one doesn't end the tag early.
+fn find_tag_end(rest: &str) -> Option<usize> {
+    let bytes = rest.as_bytes();
+    let mut i = 1; // skip '<'
+    let mut in_string = false;
+    let mut quote = 0u8;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            if b == quote {
+                in_string = false;
+            }
+        } else if b == b'"' || b == b'\'' {
+            in_string = true;
+            quote = b;
+        } else if b == b'>' {
+            return Some(i);
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Byte-exact ASCII case-insensitive search for `needle` in `haystack`.
+fn find_case_insensitive_ascii(haystack: &str, needle: &str) -> Option<usize> {
+    let haystack_bytes = haystack.as_bytes();
+    let needle_bytes = needle.as_bytes();
+    if needle_bytes.is_empty() || haystack_bytes.len() < needle_bytes.len() {
+        return None;
+    }
+    (0..=(haystack_bytes.len() - needle_bytes.len())).find(|&i| {
+        haystack_bytes[i..i + needle_bytes.len()]
+            .iter()
+            .zip(needle_bytes)
+            .all(|(a, b)| a.to_ascii_lowercase() == b.to_ascii_lowercase())
+    })
+}
+
+/// Finds the start of the closing tag for a raw-text element (e.g.
+/// `</script`), matching real parser behavior: only an unescaped literal
+/// occurrence of the closing sequence ends the element, not its structural
+/// meaning.
+fn find_raw_content_close(rest: &str, tag_name: &str) -> Option<usize> {
+    let needle = format!("</{tag_name}");
+    let mut search_from = 0;
+
+    loop {
+        let relative = find_case_insensitive_ascii(&rest[search_from..], &needle)?;
+        let pos = search_from + relative;
+        let after = rest[pos + needle.len()..].chars().next();
+        if after.is_none_or(|c| c.is_whitespace() || c == '>') {
+            return Some(pos);
+        }
+        search_from = pos + 1;
+    }
+}
+
+/// Applies [`optimize_tag_attributes`] to every real element tag in
+/// already-minified HTML, leaving conditional comments, raw-text element
+/// bodies and plain text alone.
+///
+/// Like [`super::entities::optimize_html_entities`], this re-walks the
+/// output with its own in-tag/raw-content tracking, since by this point
+/// regular comments are already gone and whitespace is already collapsed.
+pub fn optimize_html_attributes(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    let mut in_raw_content: Option<&'static str> = None;
+
+    while !rest.is_empty() {
+        if let Some(raw_tag) = in_raw_content {
+            match find_raw_content_close(rest, raw_tag) {
+                Some(pos) => {
+                    result.push_str(&rest[..pos]);
+                    rest = &rest[pos..];
+                    in_raw_content = None;
+                }
+                None => {
+                    // Unterminated raw element (shouldn't happen in
+                    // well-formed input): keep the remainder untouched.
+                    result.push_str(rest);
+                    rest = "";
+                }
+            }
+            continue;
+        }
+
+        if let Some(after_open) = rest.strip_prefix("<!--") {
+            // Conditional comment left over from `minify_html` - copy verbatim.
+            if let Some(end) = after_open.find("-->") {
+                result.push_str("<!--");
+                result.push_str(&after_open[..end + 3]);
+                rest = &after_open[end + 3..];
+            } else {
+                result.push_str(rest);
+                rest = "";
+            }
+            continue;
+        }
+
+        if rest.starts_with('<') {
+            if let Some(tag_end) = find_tag_end(rest) {
+                let tag_body = &rest[1..tag_end];
+                let rewritten = optimize_tag_attributes(tag_body);
+                result.push('<');
+                result.push_str(&rewritten);
+                result.push('>');
+
+                let trimmed = tag_body.trim_start();
+                if let Some(stripped) = trimmed.strip_prefix('/') {
+                    let _ = stripped; // closing tags never open raw content
+                } else {
+                    let tag_name = trimmed.split_whitespace().next().unwrap_or_default();
+                    in_raw_content = match tag_name.to_lowercase().as_str() {
+                        "script" => Some("script"),
+                        "style" => Some("style"),
+                        "pre" => Some("pre"),
+                        "textarea" => Some("textarea"),
+                        _ => None,
+                    };
+                }
+
+                rest = &rest[tag_end + 1..];
+                continue;
+            }
+        }
+
+        let ch = rest.chars().next().expect("rest is non-empty");
+        result.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drops_redundant_quotes() {
+        assert_eq!(
+            optimize_html_attributes(r#"<div class="test">x</div>"#),
+            "<div class=test>x</div>"
+        );
+    }
+
+    #[test]
+    fn test_keeps_quotes_when_value_has_spaces() {
+        assert_eq!(
+            optimize_html_attributes(r#"<div class="my class">x</div>"#),
+            r#"<div class="my class">x</div>"#
+        );
+    }
+
+    #[test]
+    fn test_collapses_boolean_attribute() {
+        assert_eq!(
+            optimize_html_attributes(r#"<input disabled="">"#),
+            "<input disabled>"
+        );
+    }
+
+    #[test]
+    fn test_does_not_collapse_non_boolean_empty_attribute() {
+        assert_eq!(
+            optimize_html_attributes(r#"<img alt="">"#),
+            r#"<img alt="">"#
+        );
+    }
+
+    #[test]
+    fn test_single_quotes_dropped_too() {
+        assert_eq!(
+            optimize_html_attributes("<div id='test'>x</div>"),
+            "<div id=test>x</div>"
+        );
+    }
+
+    #[test]
+    fn test_keeps_quotes_when_value_has_equals() {
+        assert_eq!(
+            optimize_html_attributes(r#"<a href="a=b">x</a>"#),
+            r#"<a href="a=b">x</a>"#
+        );
+    }
+
+    #[test]
+    fn test_script_content_left_untouched() {
+        let html = r#"<script>if (a < b) { var s = "x=\"y\""; }</script>"#;
+        assert_eq!(optimize_html_attributes(html), html);
+    }
+
+    #[test]
+    fn test_style_content_left_untouched() {
+        let html = r#"<style>a[href="x"]{color:red}</style>"#;
+        assert_eq!(optimize_html_attributes(html), html);
+    }
+
+    #[test]
+    fn test_conditional_comment_left_untouched() {
+        let html = r#"<!--[if IE]><link rel="stylesheet" href="ie.css"><![endif]-->"#;
+        assert_eq!(optimize_html_attributes(html), html);
+    }
+
+    #[test]
+    fn test_keeps_quotes_before_self_closing_slash() {
+        assert_eq!(
+            optimize_html_attributes(r#"<input type="text"/>"#),
+            r#"<input type="text"/>"#
+        );
+    }
+
+    #[test]
+    fn test_unquotes_final_attribute_when_tag_is_not_self_closing() {
+        assert_eq!(
+            optimize_html_attributes(r#"<input type="text">"#),
+            "<input type=text>"
+        );
+    }
+
+    #[test]
+    fn test_script_tag_own_attributes_still_rewritten() {
+        let html = r#"<script defer="" src="app.js"></script>"#;
+        assert_eq!(
+            optimize_html_attributes(html),
+            "<script defer src=app.js></script>"
+        );
+    }
+}
@@ -0,0 +1,347 @@
+//! HTML character reference optimization
+//!
+//! Decodes and re-encodes `&name;`/`&#NN;`/`&#xHH;` references to whichever form
+//! (raw character or entity) is fewest bytes, the way high-end HTML minifiers do.
+//! Only operates on text nodes; tag markup, `<script>`/`<style>` bodies and
+//! unterminated ("ambiguous") ampersands are left untouched by the caller.
+
+/// Named entities this minifier knows about, sorted by name for binary search.
+/// This is a practical subset of the HTML5 named character reference table,
+/// not the full ~2200-entry list.
+const NAMED_ENTITIES: &[(&str, char)] = &[
+    ("AMP", '&'),
+    ("COPY", '©'),
+    ("GT", '>'),
+    ("LT", '<'),
+    ("QUOT", '"'),
+    ("REG", '®'),
+    ("amp", '&'),
+    ("apos", '\''),
+    ("copy", '©'),
+    ("deg", '°'),
+    ("gt", '>'),
+    ("hellip", '…'),
+    ("lt", '<'),
+    ("mdash", '—'),
+    ("middot", '·'),
+    ("nbsp", '\u{a0}'),
+    ("ndash", '–'),
+    ("quot", '"'),
+    ("reg", '®'),
+    ("trade", '™'),
+];
+
+fn lookup_named(name: &str) -> Option<char> {
+    NAMED_ENTITIES
+        .binary_search_by_key(&name, |(n, _)| n)
+        .ok()
+        .map(|i| NAMED_ENTITIES[i].1)
+}
+
+/// The shortest entity representation of `c`, if encoding it would take fewer
+/// bytes than its raw UTF-8 form.
+fn shortest_entity_for(c: char) -> Option<String> {
+    let raw_len = c.len_utf8();
+    let mut best: Option<String> = None;
+
+    if let Some((name, _)) = NAMED_ENTITIES.iter().find(|(_, ch)| *ch == c) {
+        best = Some(format!("&{name};"));
+    }
+
+    let numeric = format!("&#{};", c as u32);
+    if best.as_ref().is_none_or(|b| numeric.len() < b.len()) {
+        best = Some(numeric);
+    }
+
+    best.filter(|entity| entity.len() < raw_len)
+}
+
+/// Result of successfully parsing a character reference starting at `&`.
+struct ParsedReference {
+    /// The resolved scalar value, if it decodes to a valid character.
+    resolved: Option<char>,
+    /// Number of bytes the reference occupies in the source, including `&` and `;`.
+    byte_len: usize,
+}
+
+/// Longest reference body this minifier will look ahead for before giving up
+/// on finding a terminating `;`. Comfortably covers every name in
+/// [`NAMED_ENTITIES`] plus the longest numeric form (`#x10FFFF`); an
+/// unterminated `&` in a longer run of text is an ambiguous ampersand, not a
+/// reference, and shouldn't cost a scan to the next semicolon in the document.
+const MAX_REFERENCE_BODY_LEN: usize = 32;
+
+/// Parses a (named or numeric) character reference starting at byte offset 0 of
+/// `rest`, which begins with `&`. Returns `None` if `rest` isn't a terminated
+/// reference at all (an "ambiguous ampersand" that must be left untouched).
+fn parse_reference(rest: &str) -> Option<ParsedReference> {
+    let body_start = 1; // skip '&'
+    let mut window_end = (body_start + MAX_REFERENCE_BODY_LEN).min(rest.len());
+    while window_end > body_start && !rest.is_char_boundary(window_end) {
+        window_end -= 1;
+    }
+    let semi_offset = rest[body_start..window_end].find(';')?;
+    let body = &rest[body_start..body_start + semi_offset];
+    let byte_len = body_start + semi_offset + 1;
+
+    if let Some(hex) = body.strip_prefix('x').or_else(|| body.strip_prefix('X')) {
+        let resolved = u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32);
+        return Some(ParsedReference { resolved, byte_len });
+    }
+
+    if let Some(dec) = body.strip_prefix('#') {
+        let resolved = dec.parse::<u32>().ok().and_then(char::from_u32);
+        return Some(ParsedReference { resolved, byte_len });
+    }
+
+    if body.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Some(ParsedReference {
+            resolved: lookup_named(body),
+            byte_len,
+        });
+    }
+
+    None
+}
+
+/// Optimizes character references in a text node: decodes references whose raw
+/// character is no longer than the reference text, and encodes raw characters
+/// whose shortest entity form is shorter than their UTF-8 encoding.
+///
+/// Must only be called on text that is outside tags, attribute strings,
+/// comments, `<script>` and `<style>` bodies.
+pub fn optimize_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if rest.starts_with('&') {
+            match parse_reference(rest) {
+                Some(ParsedReference {
+                    resolved: Some(ch),
+                    byte_len,
+                }) if ch.len_utf8() <= byte_len && !matches!(ch, '<' | '>' | '&') => {
+                    result.push(ch);
+                    rest = &rest[byte_len..];
+                    continue;
+                }
+                Some(ParsedReference { byte_len, .. }) => {
+                    // Either unresolved (invalid scalar), decoding wouldn't
+                    // shrink it, or it decodes to '<', '>', or '&' - those
+                    // three must stay encoded in a text node no matter the
+                    // byte count, since decoding them verbatim would let
+                    // escaped markup (`&lt;script&gt;...`) turn back into a
+                    // live tag.
+                    result.push_str(&rest[..byte_len]);
+                    rest = &rest[byte_len..];
+                    continue;
+                }
+                None => {
+                    // Ambiguous ampersand: not a terminated reference, leave as-is.
+                    result.push('&');
+                    rest = &rest[1..];
+                    continue;
+                }
+            }
+        }
+
+        let c = rest.chars().next().expect("rest is non-empty");
+        if let Some(entity) = shortest_entity_for(c) {
+            result.push_str(&entity);
+        } else {
+            result.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+
+    result
+}
+
+/// Applies [`optimize_entities`] to the text-node runs of already-minified
+/// HTML, skipping tags, attribute strings, and `<script>`/`<style>` bodies.
+///
+/// This re-walks the output with the same kind of in-tag/in-string/in-special
+/// tracking `minify_html` itself uses, since by this point comments are
+/// already gone and whitespace is already collapsed.
+pub fn optimize_html_entities(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut text_run = String::new();
+    let mut in_tag = false;
+    let mut in_string = false;
+    let mut string_delimiter = '\0';
+    let mut in_script = false;
+    let mut in_style = false;
+    let mut tag_name = String::new();
+    let mut collecting_tag_name = false;
+    let mut prev_char = '\0';
+
+    let flush_text_run =
+        |text_run: &mut String, result: &mut String, in_script: bool, in_style: bool| {
+            if text_run.is_empty() {
+                return;
+            }
+            if in_script || in_style {
+                result.push_str(text_run);
+            } else {
+                result.push_str(&optimize_entities(text_run));
+            }
+            text_run.clear();
+        };
+
+    for ch in html.chars() {
+        if !in_tag && ch == '<' {
+            flush_text_run(&mut text_run, &mut result, in_script, in_style);
+            in_tag = true;
+            collecting_tag_name = true;
+            tag_name.clear();
+            result.push(ch);
+            prev_char = ch;
+            continue;
+        }
+
+        if in_tag {
+            if in_string {
+                result.push(ch);
+                if ch == string_delimiter && prev_char != '\\' {
+                    in_string = false;
+                }
+                prev_char = ch;
+                continue;
+            }
+            if matches!(ch, '"' | '\'') {
+                in_string = true;
+                string_delimiter = ch;
+                result.push(ch);
+                prev_char = ch;
+                continue;
+            }
+            if collecting_tag_name {
+                if ch.is_alphabetic() || ch == '/' {
+                    tag_name.push(ch);
+                } else {
+                    collecting_tag_name = false;
+                }
+            }
+            if ch == '>' {
+                let lower = tag_name.to_lowercase();
+                if let Some(stripped) = lower.strip_prefix('/') {
+                    match stripped {
+                        "script" => in_script = false,
+                        "style" => in_style = false,
+                        _ => {}
+                    }
+                } else {
+                    match lower.as_str() {
+                        "script" => in_script = true,
+                        "style" => in_style = true,
+                        _ => {}
+                    }
+                }
+                in_tag = false;
+            }
+            result.push(ch);
+            prev_char = ch;
+            continue;
+        }
+
+        text_run.push(ch);
+        prev_char = ch;
+    }
+    flush_text_run(&mut text_run, &mut result, in_script, in_style);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_short_named_entity() {
+        assert_eq!(optimize_entities("&deg;"), "°");
+    }
+
+    #[test]
+    fn test_never_decodes_lt_gt_amp_in_text_nodes() {
+        // Decoding these would turn escaped markup back into live tags
+        // (`&lt;script&gt;alert(1)&lt;/script&gt;` -> `<script>alert(1)</script>`),
+        // so '<', '>', and '&' must stay encoded no matter how many bytes
+        // that costs.
+        assert_eq!(optimize_entities("&lt;&gt;"), "&lt;&gt;");
+        assert_eq!(optimize_entities("&amp;"), "&amp;");
+        assert_eq!(
+            optimize_entities("&lt;script&gt;alert(1)&lt;/script&gt;"),
+            "&lt;script&gt;alert(1)&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn test_decodes_numeric_entities() {
+        assert_eq!(optimize_entities("&#65;"), "A");
+        assert_eq!(optimize_entities("&#x41;"), "A");
+    }
+
+    #[test]
+    fn test_keeps_reference_when_not_shorter() {
+        // "&trade;" (7 bytes) decodes to '™' (3 bytes UTF-8) so it IS shorter and decodes.
+        assert_eq!(optimize_entities("&trade;"), "™");
+    }
+
+    #[test]
+    fn test_invalid_numeric_scalar_preserved_verbatim() {
+        assert_eq!(optimize_entities("&#xD800;"), "&#xD800;");
+    }
+
+    #[test]
+    fn test_ambiguous_ampersand_left_untouched() {
+        assert_eq!(optimize_entities("&amp no semicolon"), "&amp no semicolon");
+        assert_eq!(optimize_entities("Q&A"), "Q&A");
+    }
+
+    #[test]
+    fn test_stray_ampersand_does_not_scan_past_bounded_window() {
+        // The next ';' is far beyond any real reference body, so this '&' must
+        // be treated as ambiguous rather than swallowing everything up to it.
+        let long_run = "x".repeat(100);
+        let text = format!("&{long_run};");
+        assert_eq!(optimize_entities(&text), text);
+    }
+
+    #[test]
+    fn test_encodes_nbsp_when_shorter() {
+        // U+00A0 is 2 bytes in UTF-8; "&nbsp;" is 6 bytes, so no encoding happens.
+        assert_eq!(optimize_entities("\u{a0}"), "\u{a0}");
+    }
+
+    #[test]
+    fn test_roundtrip_plain_text_unaffected() {
+        assert_eq!(optimize_entities("Hello, World!"), "Hello, World!");
+    }
+
+    #[test]
+    fn test_html_pass_decodes_text_nodes_only() {
+        let html = r#"<p>&hellip; quote</p><div class="a&gt;b">x</div>"#;
+        let result = optimize_html_entities(html);
+        assert!(result.contains("<p>… quote</p>"));
+        // Attribute values are left untouched
+        assert!(result.contains(r#"class="a&gt;b""#));
+    }
+
+    #[test]
+    fn test_html_pass_keeps_lt_gt_amp_encoded_in_text_nodes() {
+        let html = "<p>&lt;script&gt;alert(1)&lt;/script&gt;</p>";
+        let result = optimize_html_entities(html);
+        assert!(result.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!result.contains("<script>alert(1)</script>"));
+    }
+
+    #[test]
+    fn test_html_pass_skips_script_and_style() {
+        let html = "<script>if (a &gt; b) {}</script><style>a::before{content:\"&gt;\"}</style>";
+        let result = optimize_html_entities(html);
+        assert!(result.contains("a &gt; b"));
+        assert!(result.contains("content:\"&gt;\""));
+    }
+}
@@ -0,0 +1,290 @@
+//! Automatic `id` injection for `<h1>`-`<h6>` headings.
+//!
+//! A heading that has no `id` attribute gets one derived from its text
+//! content, letting generated pages link straight to a section (deep links,
+//! tables of contents) without the source markup having to spell out an id
+//! by hand. A heading that already has an `id` is left untouched.
+
+use super::to_text::html_to_text;
+use std::collections::HashMap;
+
+/// Tracks how many times a slug has been seen on a page, so repeated
+/// headings with the same text still get unique ids: the first occurrence
+/// is used as-is, and every later one appends `-1`, `-2`, etc.
+#[derive(Default)]
+struct SlugMap {
+    counts: HashMap<String, usize>,
+}
+
+impl SlugMap {
+    fn unique(&mut self, slug: String) -> String {
+        let count = self.counts.entry(slug.clone()).or_insert(0);
+        let id = if *count == 0 {
+            slug
+        } else {
+            format!("{slug}-{count}")
+        };
+        *count += 1;
+        id
+    }
+}
+
+/// Slugifies heading text into an id exactly like mdbook's `normalize_id`:
+/// lowercases, keeps `[a-z0-9_-]` as-is, maps each run of whitespace to a
+/// single `-`, and drops every other character.
+fn normalize_id(text: &str) -> String {
+    let mut id = String::with_capacity(text.len());
+    let mut pending_dash = false;
+
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            pending_dash = true;
+            continue;
+        }
+
+        let lower = ch.to_ascii_lowercase();
+        if lower.is_ascii_alphanumeric() || lower == '_' || lower == '-' {
+            if pending_dash {
+                id.push('-');
+                pending_dash = false;
+            }
+            id.push(lower);
+        }
+    }
+
+    id
+}
+
+/// Returns true if `tag_body` (the text between `<hN` and the closing `>`,
+/// exclusive of the tag name itself) already declares an `id` attribute.
+fn has_id_attribute(tag_body: &str) -> bool {
+    let mut chars = tag_body.chars().peekable();
+    let mut current_name = String::new();
+
+    while let Some(ch) = chars.next() {
+        if ch == '=' {
+            if current_name.eq_ignore_ascii_case("id") {
+                return true;
+            }
+            current_name.clear();
+            if matches!(chars.peek(), Some('"') | Some('\'')) {
+                let quote = chars.next().unwrap();
+                for vch in chars.by_ref() {
+                    if vch == quote {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+
+        if ch.is_whitespace() {
+            current_name.clear();
+        } else {
+            current_name.push(ch);
+        }
+    }
+
+    false
+}
+
+/// If `rest` (which begins with `<`) opens an `<h1>`-`<h6>` tag, returns its
+/// heading level. Requires a digit 1-6 immediately after `h`/`H`, itself
+/// immediately followed by whitespace or `>` so tags like `<header>` don't
+/// match.
+fn heading_level(rest: &str) -> Option<usize> {
+    let bytes = rest.as_bytes();
+    if !matches!(bytes.first(), Some(b'h') | Some(b'H')) {
+        return None;
+    }
+    let digit = *bytes.get(1)?;
+    if !digit.is_ascii_digit() {
+        return None;
+    }
+    let level = (digit - b'0') as usize;
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    match bytes.get(2) {
+        Some(b) if b.is_ascii_whitespace() || *b == b'>' => Some(level),
+        _ => None,
+    }
+}
+
+/// Finds the end (index of `>`) of the tag starting at byte 0 of `rest`
+/// (which begins with `<`), respecting quoted attribute values.
+fn find_tag_end(rest: &str) -> Option<usize> {
+    let bytes = rest.as_bytes();
+    let mut i = 1;
+    let mut in_string = false;
+    let mut quote = 0u8;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            if b == quote {
+                in_string = false;
+            }
+        } else if b == b'"' || b == b'\'' {
+            in_string = true;
+            quote = b;
+        } else if b == b'>' {
+            return Some(i);
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Byte-exact ASCII case-insensitive search for `needle` in `haystack`.
+fn find_case_insensitive_ascii(haystack: &str, needle: &str) -> Option<usize> {
+    let haystack_bytes = haystack.as_bytes();
+    let needle_bytes = needle.as_bytes();
+    if needle_bytes.is_empty() || haystack_bytes.len() < needle_bytes.len() {
+        return None;
+    }
+    (0..=(haystack_bytes.len() - needle_bytes.len())).find(|&i| {
+        haystack_bytes[i..i + needle_bytes.len()]
+            .iter()
+            .zip(needle_bytes)
+            .all(|(a, b)| a.to_ascii_lowercase() == b.to_ascii_lowercase())
+    })
+}
+
+/// Scans `html` for `<h1>`-`<h6>` elements lacking an `id` attribute and
+/// injects one slugified from their text content (see [`normalize_id`]),
+/// de-duplicating collisions within the page with [`SlugMap`]. Text
+/// extraction ignores nested tags - `<h2>Hello <code>world</code></h2>`
+/// slugifies to `hello-world` - by reusing [`html_to_text`] over the
+/// heading's own markup. Headings that already declare an `id`, or that
+/// have no matching close tag, are left untouched.
+pub fn inject_heading_ids(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    let mut slugs = SlugMap::default();
+
+    while let Some(lt_pos) = rest.find('<') {
+        result.push_str(&rest[..lt_pos]);
+        rest = &rest[lt_pos..];
+
+        let Some(level) = heading_level(&rest[1..]) else {
+            result.push('<');
+            rest = &rest[1..];
+            continue;
+        };
+
+        let Some(tag_end) = find_tag_end(rest) else {
+            result.push_str(rest);
+            rest = "";
+            break;
+        };
+        let tag_body = &rest[3..tag_end]; // skip the leading "<hN"
+
+        let closing_tag = format!("</h{level}");
+        let Some(close_start_rel) =
+            find_case_insensitive_ascii(&rest[tag_end + 1..], &closing_tag)
+        else {
+            // No matching close tag: leave this heading untouched.
+            result.push_str(&rest[..=tag_end]);
+            rest = &rest[tag_end + 1..];
+            continue;
+        };
+        let close_start = tag_end + 1 + close_start_rel;
+        let Some(close_end_rel) = rest[close_start..].find('>') else {
+            result.push_str(&rest[..=tag_end]);
+            rest = &rest[tag_end + 1..];
+            continue;
+        };
+        let close_end = close_start + close_end_rel;
+
+        if has_id_attribute(tag_body) {
+            result.push_str(&rest[..=close_end]);
+        } else {
+            let inner_html = &rest[tag_end + 1..close_start];
+            let text = html_to_text(&rest[..=close_end]);
+            let id = slugs.unique(normalize_id(&text));
+            result.push_str(&format!("<h{level} id=\"{id}\""));
+            result.push_str(tag_body);
+            result.push('>');
+            result.push_str(inner_html);
+            result.push_str(&rest[close_start..=close_end]);
+        }
+
+        rest = &rest[close_end + 1..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_injects_id_from_text() {
+        assert_eq!(
+            inject_heading_ids("<h2>Hello World</h2>"),
+            "<h2 id=\"hello-world\">Hello World</h2>"
+        );
+    }
+
+    #[test]
+    fn test_ignores_nested_tags_for_slug() {
+        assert_eq!(
+            inject_heading_ids("<h2>Hello <code>world</code></h2>"),
+            "<h2 id=\"hello-world\">Hello <code>world</code></h2>"
+        );
+    }
+
+    #[test]
+    fn test_leaves_existing_id_untouched() {
+        let html = "<h1 id=\"custom\">Title</h1>";
+        assert_eq!(inject_heading_ids(html), html);
+    }
+
+    #[test]
+    fn test_deduplicates_collisions() {
+        let html = "<h2>Intro</h2><h2>Intro</h2><h2>Intro</h2>";
+        assert_eq!(
+            inject_heading_ids(html),
+            "<h2 id=\"intro\">Intro</h2><h2 id=\"intro-1\">Intro</h2><h2 id=\"intro-2\">Intro</h2>"
+        );
+    }
+
+    #[test]
+    fn test_preserves_existing_attributes() {
+        assert_eq!(
+            inject_heading_ids("<h3 class=\"title\">Hi</h3>"),
+            "<h3 id=\"hi\" class=\"title\">Hi</h3>"
+        );
+    }
+
+    #[test]
+    fn test_all_heading_levels() {
+        for level in 1..=6 {
+            let html = format!("<h{level}>Section</h{level}>");
+            let expected = format!("<h{level} id=\"section\">Section</h{level}>");
+            assert_eq!(inject_heading_ids(&html), expected);
+        }
+    }
+
+    #[test]
+    fn test_non_heading_tags_untouched() {
+        let html = "<div><p>Hello</p></div>";
+        assert_eq!(inject_heading_ids(html), html);
+    }
+
+    #[test]
+    fn test_missing_close_tag_left_untouched() {
+        let html = "<h2>Oops, no closing tag";
+        assert_eq!(inject_heading_ids(html), html);
+    }
+
+    #[test]
+    fn test_header_tag_is_not_mistaken_for_a_heading() {
+        let html = "<header>Site banner</header>";
+        assert_eq!(inject_heading_ids(html), html);
+    }
+}
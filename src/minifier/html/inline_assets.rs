@@ -0,0 +1,218 @@
+//! Minifies the CSS/JS that `minify_html`'s main whitespace pass leaves
+//! verbatim inside `<style>` and `<script>` block bodies.
+//!
+//! Runs as a final pass over the already-minified HTML, reusing the same
+//! dedicated [`crate::minifier::css::minify_css`] and
+//! [`crate::minifier::js::minify_js`] minifiers asset files get, instead of
+//! re-implementing comment/whitespace stripping a third time.
+
+use crate::minifier::css::minify_css;
+use crate::minifier::js::minify_js;
+
+enum AssetKind {
+    Script,
+    Style,
+}
+
+/// `type` attribute values (case-insensitive, quotes stripped) that mark a
+/// `<script>` body as JavaScript. Anything else - `application/json`,
+/// `text/template`, a framework's custom template type, etc. - is left
+/// untouched, since a JS minifier has no business touching it.
+fn is_minifiable_script_type(type_attr: Option<&str>) -> bool {
+    match type_attr {
+        None => true,
+        Some(t) => matches!(
+            t.trim().trim_matches(|c| c == '"' || c == '\'').to_lowercase().as_str(),
+            "" | "text/javascript" | "application/javascript" | "module"
+        ),
+    }
+}
+
+/// Extracts the value of `attr_name="..."` (or `'...'`) from a tag's inner
+/// text (everything between `<` and `>`, exclusive), if present.
+fn extract_attribute_value<'a>(tag_inner: &'a str, attr_name: &str) -> Option<&'a str> {
+    let lower = tag_inner.to_lowercase();
+    let needle = format!("{attr_name}=");
+    let attr_start = lower.find(&needle)?;
+    let rest = &tag_inner[attr_start + needle.len()..];
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some(quote @ ('"' | '\'')) => {
+            let end = rest[quote.len_utf8()..].find(quote)?;
+            Some(&rest[quote.len_utf8()..quote.len_utf8() + end])
+        }
+        Some(_) => {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '>')
+                .unwrap_or(rest.len());
+            Some(&rest[..end])
+        }
+        None => None,
+    }
+}
+
+/// Finds the next `<script`/`<style` occurrence in `html` that actually
+/// opens a tag (i.e. is followed by whitespace, `>`, or `/`, not just a
+/// custom element sharing the prefix).
+fn find_tag_open(html: &str, prefix: &str) -> Option<usize> {
+    let mut search_from = 0;
+    while let Some(rel) = html[search_from..].find(prefix) {
+        let pos = search_from + rel;
+        let after = pos + prefix.len();
+        let boundary_ok = match html[after..].chars().next() {
+            Some(c) => c.is_whitespace() || c == '>' || c == '/',
+            None => true,
+        };
+        if boundary_ok {
+            return Some(pos);
+        }
+        search_from = pos + 1;
+    }
+    None
+}
+
+/// Finds whichever of `<script`/`<style` occurs first in `html`.
+fn find_next_open_tag(html: &str) -> Option<(AssetKind, usize)> {
+    let lower = html.to_lowercase();
+    let script_pos = find_tag_open(&lower, "<script");
+    let style_pos = find_tag_open(&lower, "<style");
+    match (script_pos, style_pos) {
+        (None, None) => None,
+        (Some(s), None) => Some((AssetKind::Script, s)),
+        (None, Some(y)) => Some((AssetKind::Style, y)),
+        (Some(s), Some(y)) if s <= y => Some((AssetKind::Script, s)),
+        (Some(_), Some(y)) => Some((AssetKind::Style, y)),
+    }
+}
+
+/// Runs the CSS/JS minifiers over `<style>`/JS-typed `<script>` block bodies
+/// found in already-minified `html`. An unterminated tag or missing closing
+/// tag stops the pass and copies the remainder verbatim.
+pub fn minify_inline_assets(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some((kind, start)) = find_next_open_tag(rest) {
+        result.push_str(&rest[..start]);
+
+        let Some(tag_end_rel) = rest[start..].find('>') else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+        let tag_end = start + tag_end_rel;
+        let tag_inner = &rest[start..tag_end];
+
+        let closing_tag = match kind {
+            AssetKind::Script => "</script",
+            AssetKind::Style => "</style",
+        };
+        let body_start = tag_end + 1;
+        let Some(close_rel) = rest[body_start..].to_lowercase().find(closing_tag) else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+        let body_end = body_start + close_rel;
+
+        let Some(close_tag_end_rel) = rest[body_end..].find('>') else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+        let close_tag_end = body_end + close_tag_end_rel;
+
+        let body = &rest[body_start..body_end];
+        let should_minify = match kind {
+            AssetKind::Style => true,
+            AssetKind::Script => {
+                is_minifiable_script_type(extract_attribute_value(tag_inner, "type"))
+            }
+        };
+
+        result.push_str(tag_inner);
+        result.push('>');
+        if should_minify {
+            let minified = match kind {
+                AssetKind::Style => minify_css(body),
+                AssetKind::Script => minify_js(body),
+            };
+            result.push_str(&minified);
+        } else {
+            result.push_str(body);
+        }
+        result.push_str(&rest[body_end..=close_tag_end]);
+
+        rest = &rest[close_tag_end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minifies_style_block() {
+        let html = "<style>  .a {  color: red;  }  </style>";
+        let result = minify_inline_assets(html);
+        assert_eq!(result, "<style>.a{color:red}</style>");
+    }
+
+    #[test]
+    fn test_minifies_script_without_type() {
+        let html = "<script>\n  // a comment\n  var x = 1;\n</script>";
+        let result = minify_inline_assets(html);
+        assert!(!result.contains("comment"));
+        assert!(result.contains("var x=1;") || result.contains("var x = 1;"));
+    }
+
+    #[test]
+    fn test_minifies_script_with_javascript_type() {
+        let html = "<script type=\"text/javascript\">  var x = 1;  </script>";
+        let result = minify_inline_assets(html);
+        assert!(result.starts_with("<script type=\"text/javascript\">"));
+        assert!(!result.contains("  var"));
+    }
+
+    #[test]
+    fn test_leaves_non_js_script_type_untouched() {
+        let html = "<script type=\"application/json\">{  \"a\": 1  }</script>";
+        let result = minify_inline_assets(html);
+        assert_eq!(result, html);
+    }
+
+    #[test]
+    fn test_leaves_template_script_type_untouched() {
+        let html = "<script type=\"text/x-handlebars-template\">  {{ name }}  </script>";
+        let result = minify_inline_assets(html);
+        assert_eq!(result, html);
+    }
+
+    #[test]
+    fn test_minifies_module_script() {
+        let html = "<script type=\"module\">  import x from 'x';  </script>";
+        let result = minify_inline_assets(html);
+        assert!(!result.contains("  import"));
+    }
+
+    #[test]
+    fn test_surrounding_html_is_left_alone() {
+        let html = "<p>Hi</p><style>.a { color: red; }</style><p>Bye</p>";
+        let result = minify_inline_assets(html);
+        assert!(result.starts_with("<p>Hi</p>"));
+        assert!(result.ends_with("<p>Bye</p>"));
+    }
+
+    #[test]
+    fn test_multiple_blocks_are_each_minified() {
+        let html = "<style>.a { color: red; }</style><script>var x = 1;</script>";
+        let result = minify_inline_assets(html);
+        assert_eq!(result, "<style>.a{color:red}</style><script>var x=1;</script>");
+    }
+
+    #[test]
+    fn test_unterminated_tag_is_left_verbatim() {
+        let html = "<p>Hi</p><style";
+        assert_eq!(minify_inline_assets(html), html);
+    }
+}
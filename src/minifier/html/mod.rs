@@ -1,3 +1,71 @@
+//! HTML minification module
+//!
+//! This module provides HTML minification by collapsing inter-element
+//! whitespace, stripping comments, and shrinking attribute markup, while
+//! preserving content verbatim inside raw-text elements (`<pre>`,
+//! `<textarea>`, `<script>`, `<style>`) and any comment matching a
+//! "preserve" marker (conditional comments by default).
+//!
+//! The module is organized into separate components:
+//! - `attributes`: Removes redundant attribute quoting and collapses
+//!   boolean attributes to their name-only form
+//! - `entities`: Rewrites character references to whichever of their
+//!   decoded or encoded form is shorter
+//! - `heading_ids`: Injects `id` attributes into headings that lack one
+//! - `inline_assets`: Routes `<style>`/`<script>` bodies through
+//!   [`crate::minifier::css::minify_css`]/[`crate::minifier::js::minify_js`]
+//! - `to_text`: Strips markup down to plain text
+
+mod attributes;
+mod entities;
+mod heading_ids;
+mod inline_assets;
+mod to_text;
+
+use crate::minifier::find_byte;
+use crate::traits::Minifier;
+use attributes::optimize_html_attributes;
+use entities::optimize_html_entities;
+
+pub use heading_ids::inject_heading_ids;
+pub use inline_assets::minify_inline_assets;
+pub use to_text::html_to_text;
+
+/// HTML minifier implementation
+pub struct HtmlMinifier;
+
+impl HtmlMinifier {
+    /// Create a new HTML minifier
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for HtmlMinifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Minifier for HtmlMinifier {
+    fn minify(&self, input: &str) -> String {
+        minify_html(input)
+    }
+
+    fn content_type(&self) -> &str {
+        "html"
+    }
+}
+
+/// Looks at the next character of `chars` without consuming it. Plain
+/// `std::str::Chars` has no built-in `peek`, unlike `Peekable`, but cloning
+/// it is cheap (just a pointer/length copy) and - unlike wrapping it in
+/// `Peekable` - leaves `chars.as_str()` reporting the true unconsumed
+/// remainder, which [`handle_content_whitespace`] needs for its SIMD fast path.
+fn peek(chars: &std::str::Chars) -> Option<char> {
+    chars.clone().next()
+}
+
 /// Represents the state of HTML parsing
 struct HtmlParseState {
     in_tag: bool,
@@ -7,7 +75,6 @@ struct HtmlParseState {
     in_textarea: bool,
     in_string: bool,
     string_delimiter: char,
-    in_comment: bool,
     tag_name: String,
     collecting_tag_name: bool,
 }
@@ -22,7 +89,6 @@ impl HtmlParseState {
             in_textarea: false,
             in_string: false,
             string_delimiter: '\0',
-            in_comment: false,
             tag_name: String::new(),
             collecting_tag_name: false,
         }
@@ -33,50 +99,59 @@ impl HtmlParseState {
     }
 }
 
-/// Handles HTML comment detection and processing
+/// Handles HTML comment detection and processing.
+///
+/// Ordinary comments are stripped entirely. A comment whose body starts
+/// with one of `preserved_comment_markers` is kept - e.g. IE conditional
+/// comments (`<!--[if ...]>...<![endif]-->`, which IE reads as actual
+/// markup rather than a comment) or a leading `!` (the "preserve this"
+/// convention, handy for license/legal notices). The markup inside a
+/// preserved comment is still run back through [`minify_html_with_markers`]
+/// rather than being kept opaque.
 fn handle_html_comments(
     ch: char,
-    chars: &mut std::iter::Peekable<std::str::Chars>,
+    chars: &mut std::str::Chars,
     state: &mut HtmlParseState,
     result: &mut String,
+    preserved_comment_markers: &[&str],
 ) -> bool {
-    match ch {
-        '<' if !state.in_string => {
-            if chars.peek() == Some(&'!') {
-                // Look ahead to see if this is a comment
-                let mut lookahead = chars.clone();
-                lookahead.next(); // consume '!'
-                if lookahead.next() == Some('-') && lookahead.next() == Some('-') {
-                    // This is a comment, skip it entirely
-                    chars.next(); // consume '!'
-                    chars.next(); // consume first '-'
-                    chars.next(); // consume second '-'
-                    state.in_comment = true;
-                    return true;
-                }
-            }
+    if ch != '<' || state.in_string {
+        return false;
+    }
 
-            if !state.in_comment {
-                state.in_tag = true;
-                state.collecting_tag_name = true;
-                state.tag_name.clear();
-                result.push(ch);
-            }
-            true
-        }
-        '-' if state.in_comment => {
-            if chars.peek() == Some(&'-') {
-                chars.next(); // consume second '-'
-                if chars.peek() == Some(&'>') {
-                    chars.next(); // consume '>'
-                    state.in_comment = false;
+    if peek(chars) == Some('!') {
+        let mut lookahead = chars.clone();
+        lookahead.next(); // consume '!'
+        if lookahead.next() == Some('-') && lookahead.next() == Some('-') {
+            chars.next(); // consume '!'
+            chars.next(); // consume first '-'
+            chars.next(); // consume second '-'
+
+            let remaining = chars.as_str();
+            if let Some(end) = remaining.find("-->") {
+                let inner = &remaining[..end];
+                if preserved_comment_markers
+                    .iter()
+                    .any(|marker| inner.starts_with(marker))
+                {
+                    result.push_str("<!--");
+                    result.push_str(&minify_html_with_markers(inner, preserved_comment_markers));
+                    result.push_str("-->");
                 }
+                *chars = remaining[end + 3..].chars();
+            } else {
+                // Unterminated comment: nothing left worth parsing.
+                *chars = "".chars();
             }
-            true // Don't add comment content to result
+            return true;
         }
-        _ if state.in_comment => true, // Skip comment content
-        _ => false,
     }
+
+    state.in_tag = true;
+    state.collecting_tag_name = true;
+    state.tag_name.clear();
+    result.push(ch);
+    true
 }
 
 /// Updates special content area flags based on tag names
@@ -106,7 +181,7 @@ fn update_special_content_flags(state: &mut HtmlParseState) {
 /// Handles tag processing
 fn handle_tags(ch: char, state: &mut HtmlParseState, result: &mut String) -> bool {
     match ch {
-        '>' if state.in_tag && !state.in_string && !state.in_comment => {
+        '>' if state.in_tag && !state.in_string => {
             result.push(ch);
             update_special_content_flags(state);
             state.in_tag = false;
@@ -133,7 +208,7 @@ fn handle_tag_strings(
     state: &mut HtmlParseState,
     result: &mut String,
 ) -> bool {
-    if matches!(ch, '"' | '\'') && state.in_tag && !state.in_comment {
+    if matches!(ch, '"' | '\'') && state.in_tag {
         if !state.in_string {
             state.in_string = true;
             state.string_delimiter = ch;
@@ -152,19 +227,43 @@ fn is_content_char(c: char) -> bool {
     c.is_alphanumeric() || c.len_utf8() > 1 || c.is_alphabetic()
 }
 
+/// Skips the run of whitespace characters at the front of `chars`.
+///
+/// Indentation between tags is typically a short run of plain ASCII
+/// whitespace immediately followed by the next `<`. For that common case,
+/// `find_byte_any` locates the `<` in one SIMD pass and the whole run is
+/// skipped in bulk instead of testing `char::is_whitespace` one character at
+/// a time. Anything else (non-ASCII whitespace, or content before the next
+/// `<`) falls back to the scalar loop.
+fn skip_whitespace_run(chars: &mut std::str::Chars) {
+    let remaining = chars.as_str();
+    if let Some(lt_pos) = find_byte(remaining.as_bytes(), b'<') {
+        let prefix = &remaining[..lt_pos];
+        if prefix.bytes().all(|b| b.is_ascii_whitespace()) {
+            // Every skipped byte is a single-byte ASCII char, so the byte
+            // offset of '<' is exactly the number of chars to consume.
+            if lt_pos > 0 {
+                chars.nth(lt_pos - 1);
+            }
+            return;
+        }
+    }
+
+    while peek(chars).is_some_and(|c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
 /// Handles whitespace minification outside of tags
 fn handle_content_whitespace(
-    chars: &mut std::iter::Peekable<std::str::Chars>,
+    chars: &mut std::str::Chars,
     state: &HtmlParseState,
     result: &mut String,
 ) -> bool {
     if !state.in_tag && !state.is_in_special_content() {
-        // Skip consecutive whitespace
-        while chars.peek().is_some_and(|c| c.is_whitespace()) {
-            chars.next();
-        }
+        skip_whitespace_run(chars);
 
-        let next_char = chars.peek().unwrap_or(&'\0');
+        let next_char = peek(chars).unwrap_or('\0');
 
         if !result.is_empty() {
             let last_char = result.chars().last().unwrap_or('\0');
@@ -173,11 +272,11 @@ fn handle_content_whitespace(
             // - content characters (words, emojis, unicode)
             // - after punctuation (comma, period, etc.) and before content
             // - content and tags
-            let should_preserve_space = (is_content_char(last_char) && is_content_char(*next_char))
-                || (is_content_char(last_char) && *next_char == '<')
-                || (last_char == '>' && is_content_char(*next_char))
+            let should_preserve_space = (is_content_char(last_char) && is_content_char(next_char))
+                || (is_content_char(last_char) && next_char == '<')
+                || (last_char == '>' && is_content_char(next_char))
                 || (matches!(last_char, ',' | '.' | ';' | ':' | '!' | '?')
-                    && is_content_char(*next_char));
+                    && is_content_char(next_char));
 
             if should_preserve_space {
                 result.push(' ');
@@ -190,20 +289,20 @@ fn handle_content_whitespace(
 
 /// Handles whitespace inside tags
 fn handle_tag_whitespace(
-    chars: &mut std::iter::Peekable<std::str::Chars>,
+    chars: &mut std::str::Chars,
     state: &mut HtmlParseState,
     result: &mut String,
 ) -> bool {
     if state.in_tag && !state.in_string {
         // Whitespace after the tag name means we've finished collecting it
         state.collecting_tag_name = false;
-        let next_char = chars.peek().unwrap_or(&'\0');
+        let next_char = peek(chars).unwrap_or('\0');
 
         if !result.is_empty() {
             let last_char = result.chars().last().unwrap_or('\0');
 
             // Preserve single space between attributes
-            if !last_char.is_whitespace() && !next_char.is_whitespace() && *next_char != '>' {
+            if !last_char.is_whitespace() && !next_char.is_whitespace() && next_char != '>' {
                 result.push(' ');
             }
         }
@@ -212,16 +311,37 @@ fn handle_tag_whitespace(
     false
 }
 
-/// Minifies HTML by removing unnecessary whitespace while preserving functionality
+/// Leading markers [`minify_html`] uses to decide which HTML comments to
+/// keep instead of stripping - see [`handle_html_comments`]. Callers that
+/// need a different set (e.g. from [`crate::config::SiteConfig::preserved_comment_markers`])
+/// should use [`minify_html_with_markers`] instead.
+pub const DEFAULT_PRESERVED_COMMENT_MARKERS: &[&str] = &["[if", "!"];
+
+/// Minifies HTML by removing unnecessary whitespace while preserving
+/// functionality, using [`DEFAULT_PRESERVED_COMMENT_MARKERS`] to decide
+/// which comments to keep. See [`minify_html_with_markers`] to customize
+/// the marker list.
 pub fn minify_html(html: &str) -> String {
+    minify_html_with_markers(html, DEFAULT_PRESERVED_COMMENT_MARKERS)
+}
+
+/// Same as [`minify_html`], but with a caller-supplied set of comment
+/// "preserve" markers instead of the default `[if`/`!` pair.
+pub fn minify_html_with_markers(html: &str, preserved_comment_markers: &[&str]) -> String {
     let mut result = String::with_capacity(html.len());
-    let mut chars = html.chars().peekable();
+    let mut chars = html.chars();
     let mut state = HtmlParseState::new();
     let mut prev_char = '\0';
 
     while let Some(ch) = chars.next() {
         // Handle HTML comments first
-        if handle_html_comments(ch, &mut chars, &mut state, &mut result) {
+        if handle_html_comments(
+            ch,
+            &mut chars,
+            &mut state,
+            &mut result,
+            preserved_comment_markers,
+        ) {
             prev_char = ch;
             continue;
         }
@@ -258,17 +378,15 @@ pub fn minify_html(html: &str) -> String {
         }
 
         // Handle other characters
-        if !state.in_comment {
-            if state.collecting_tag_name && !ch.is_alphabetic() && ch != '/' {
-                state.collecting_tag_name = false;
-            }
-            result.push(ch);
+        if state.collecting_tag_name && !ch.is_alphabetic() && ch != '/' {
+            state.collecting_tag_name = false;
         }
+        result.push(ch);
 
         prev_char = ch;
     }
 
-    result
+    optimize_html_entities(&optimize_html_attributes(&result))
 }
 
 #[cfg(test)]
@@ -295,6 +413,14 @@ mod tests {
         assert!(result.len() < html.len());
     }
 
+    #[test]
+    fn test_whitespace_run_between_tags_is_collapsed() {
+        // Exercises the SIMD fast path in `skip_whitespace_run`: a long run
+        // of indentation whitespace immediately followed by '<'.
+        let html = format!("<div>{}<span>x</span></div>", " ".repeat(64));
+        assert_eq!(minify_html(&html), "<div><span>x</span></div>");
+    }
+
     #[test]
     fn test_comment_removal() {
         let html = "<!-- This is a comment --><div>Hello World</div><!-- Another comment -->";
@@ -333,14 +459,14 @@ mod tests {
     #[test]
     fn test_preserve_attribute_values() {
         let html = r#"<div class="my class" id="test">Hello</div>"#;
-        let expected = r#"<div class="my class" id="test">Hello</div>"#;
+        let expected = r#"<div class="my class" id=test>Hello</div>"#;
         assert_eq!(minify_html(html), expected);
     }
 
     #[test]
     fn test_preserve_single_quotes() {
         let html = r#"<div class='my class' id='test'>Hello</div>"#;
-        let expected = r#"<div class='my class' id='test'>Hello</div>"#;
+        let expected = r#"<div class='my class' id=test>Hello</div>"#;
         assert_eq!(minify_html(html), expected);
     }
 
@@ -405,7 +531,7 @@ mod tests {
     #[test]
     fn test_self_closing_tags() {
         let html = "<img src='test.jpg' />  <br />  <hr />";
-        let expected = "<img src='test.jpg' /><br /><hr />";
+        let expected = "<img src=test.jpg /><br /><hr />";
         assert_eq!(minify_html(html), expected);
     }
 
@@ -501,4 +627,78 @@ body { margin: 0; }
         let result = minify_html(html);
         assert!(result.contains(expected_contains));
     }
+
+    #[test]
+    fn test_conditional_comment_preserved() {
+        let html = "<!--[if IE]><p>Only IE</p><![endif]--><div>Hello</div>";
+        let expected = "<!--[if IE]><p>Only IE</p><![endif]--><div>Hello</div>";
+        assert_eq!(minify_html(html), expected);
+    }
+
+    #[test]
+    fn test_ordinary_comment_stripped_next_to_conditional() {
+        let html = "<!-- normal --><!--[if IE]><link><![endif]-->";
+        let expected = "<!--[if IE]><link><![endif]-->";
+        assert_eq!(minify_html(html), expected);
+    }
+
+    #[test]
+    fn test_conditional_comment_inner_whitespace_is_minified() {
+        let html = "<!--[if IE]>\n    <p>   Only   IE   </p>\n<![endif]-->";
+        let expected = "<!--[if IE]><p> Only IE </p><![endif]-->";
+        assert_eq!(minify_html(html), expected);
+    }
+
+    #[test]
+    fn test_bang_marked_comment_preserved() {
+        let html = "<!--!Copyright 2026 Example Corp-->_<div>x</div>";
+        let expected = "<!--!Copyright 2026 Example Corp-->_<div>x</div>";
+        assert_eq!(minify_html(html), expected);
+    }
+
+    #[test]
+    fn test_custom_preserved_comment_markers() {
+        let html = "<!--keep this--><!--[if IE]>drop this<![endif]-->";
+        let expected = "<!--keep this-->";
+        assert_eq!(minify_html_with_markers(html, &["keep"]), expected);
+    }
+
+    #[test]
+    fn test_collapse_boolean_attribute() {
+        let html = r#"<input type="checkbox" disabled="">"#;
+        let expected = r#"<input type=checkbox disabled>"#;
+        assert_eq!(minify_html(html), expected);
+    }
+
+    #[test]
+    fn test_does_not_collapse_non_boolean_empty_attribute() {
+        let html = r#"<img src="x.png" alt="">"#;
+        let expected = r#"<img src=x.png alt="">"#;
+        assert_eq!(minify_html(html), expected);
+    }
+}
+
+#[cfg(test)]
+mod trait_tests {
+    use super::*;
+    use crate::traits::Minifier;
+
+    #[test]
+    fn test_html_minifier_trait() {
+        let minifier = HtmlMinifier::new();
+        assert_eq!(minifier.content_type(), "html");
+
+        let input = "<div>   <p>Hello   World</p>   </div>";
+        let result = minifier.minify(input);
+
+        assert!(result.len() <= input.len());
+        assert_eq!(result, "<div><p>Hello World</p></div>");
+    }
+
+    #[test]
+    fn test_html_minifier_preserves_whitespace_sensitive_elements() {
+        let minifier = HtmlMinifier::new();
+        let input = "<pre>  keep   me  </pre>";
+        assert_eq!(minifier.minify(input), input);
+    }
 }
@@ -0,0 +1,187 @@
+//! HTML-to-plaintext extraction
+//!
+//! Strips tags and attributes, skips `<script>`/`<style>` bodies, decodes
+//! entities and collapses whitespace - used to derive `<meta
+//! name="description">` snippets and a client-side search index at build
+//! time without needing a full HTML parser on the consuming end.
+
+use super::entities::optimize_entities;
+
+/// Tag names that end a visual line in the extracted text, modeled on
+/// `dehtml`-style conversion: these are block-level elements, so their
+/// close (or, for `br`, their mere presence) becomes a newline. Every other
+/// tag is treated as inline and simply dropped.
+const BLOCK_BREAK_TAGS: &[&str] = &["br", "p", "div", "li", "h1", "h2", "h3", "h4", "h5", "h6"];
+
+fn is_block_break_tag(tag_name: &str) -> bool {
+    BLOCK_BREAK_TAGS.contains(&tag_name)
+}
+
+/// Collapses a run of whitespace in the source to a single space, skipping
+/// the push entirely if the result already ends on a space or newline.
+fn push_space(result: &mut String) {
+    if !result.ends_with(' ') && !result.ends_with('\n') {
+        result.push(' ');
+    }
+}
+
+/// Ends the current visual line: trims any trailing space first so a block
+/// boundary never leaves "word \n" behind, and skips the push if the result
+/// is empty or already ends on a newline so adjacent block tags don't stack
+/// up blank lines.
+fn push_line_break(result: &mut String) {
+    while result.ends_with(' ') {
+        result.pop();
+    }
+    if !result.is_empty() && !result.ends_with('\n') {
+        result.push('\n');
+    }
+}
+
+/// Extracts the human-readable text of `html`: tags and their attributes
+/// are dropped, `<script>`/`<style>` bodies are skipped entirely, character
+/// references are decoded, and whitespace runs collapse to a single space -
+/// except at block-level boundaries (`<br>`, `<p>`, `<div>`, `<li>`,
+/// `<h1>`-`<h6>`), which each emit a single newline instead. The final
+/// result has its leading/trailing whitespace trimmed.
+pub fn html_to_text(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut in_tag = false;
+    let mut in_string = false;
+    let mut string_delimiter = '\0';
+    let mut in_script = false;
+    let mut in_style = false;
+    let mut tag_name = String::new();
+    let mut collecting_tag_name = false;
+
+    for ch in html.chars() {
+        if !in_tag && ch == '<' {
+            in_tag = true;
+            collecting_tag_name = true;
+            tag_name.clear();
+            continue;
+        }
+
+        if in_tag {
+            if in_string {
+                if ch == string_delimiter {
+                    in_string = false;
+                }
+                continue;
+            }
+            if matches!(ch, '"' | '\'') {
+                in_string = true;
+                string_delimiter = ch;
+                continue;
+            }
+            if collecting_tag_name {
+                if ch.is_alphabetic() || ch == '/' {
+                    tag_name.push(ch);
+                } else {
+                    collecting_tag_name = false;
+                }
+            }
+            if ch == '>' {
+                let lower = tag_name.to_lowercase();
+                let is_closing = lower.starts_with('/');
+                let bare_name = lower.trim_matches('/');
+
+                match bare_name {
+                    "script" => in_script = !is_closing,
+                    "style" => in_style = !is_closing,
+                    _ => {}
+                }
+
+                if is_block_break_tag(bare_name) {
+                    push_line_break(&mut result);
+                }
+
+                in_tag = false;
+            }
+            continue;
+        }
+
+        if in_script || in_style {
+            continue;
+        }
+
+        if ch.is_whitespace() {
+            push_space(&mut result);
+        } else {
+            result.push(ch);
+        }
+    }
+
+    optimize_entities(&result).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_tags_and_attributes() {
+        let html = r#"<div class="card"><a href="/x">Hello</a></div>"#;
+        assert_eq!(html_to_text(html), "Hello");
+    }
+
+    #[test]
+    fn test_skips_script_and_style_bodies() {
+        let html = "<p>Hello</p><script>var x = 1;</script><style>.a{color:red}</style>";
+        assert_eq!(html_to_text(html), "Hello");
+    }
+
+    #[test]
+    fn test_decodes_entities() {
+        let html = "<p>Fish &amp; Chips &mdash; &copy;2026</p>";
+        assert_eq!(html_to_text(html), "Fish & Chips — ©2026");
+    }
+
+    #[test]
+    fn test_collapses_whitespace_runs() {
+        let html = "<p>Hello   \n   World</p>";
+        assert_eq!(html_to_text(html), "Hello World");
+    }
+
+    #[test]
+    fn test_block_tags_become_newlines() {
+        let html = "<div>First</div><div>Second</div>";
+        assert_eq!(html_to_text(html), "First\nSecond");
+    }
+
+    #[test]
+    fn test_br_becomes_newline() {
+        let html = "<p>Line one<br>Line two</p>";
+        assert_eq!(html_to_text(html), "Line one\nLine two");
+    }
+
+    #[test]
+    fn test_list_items_become_lines() {
+        let html = "<ul><li>One</li><li>Two</li></ul>";
+        assert_eq!(html_to_text(html), "One\nTwo");
+    }
+
+    #[test]
+    fn test_headings_become_lines() {
+        let html = "<h1>Title</h1><p>Body text</p>";
+        assert_eq!(html_to_text(html), "Title\nBody text");
+    }
+
+    #[test]
+    fn test_inline_tags_stay_on_one_line() {
+        let html = "<p>Hello <strong>bold</strong> and <em>italic</em> text</p>";
+        assert_eq!(html_to_text(html), "Hello bold and italic text");
+    }
+
+    #[test]
+    fn test_adjacent_block_tags_do_not_stack_blank_lines() {
+        let html = "<p>First</p>\n\n<p></p><p>Second</p>";
+        assert_eq!(html_to_text(html), "First\nSecond");
+    }
+
+    #[test]
+    fn test_leading_and_trailing_whitespace_trimmed() {
+        let html = "  <p>  Hello  </p>  ";
+        assert_eq!(html_to_text(html), "Hello");
+    }
+}
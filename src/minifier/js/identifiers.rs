@@ -0,0 +1,286 @@
+//! Renames function-local `var`/`let`/`const`/parameter names that are
+//! never referenced outside their function to short, generated names.
+//!
+//! This does no real scope analysis - it's the same heuristic style as the
+//! TypeScript stripper: a function declaration is only considered at all
+//! if its body contains no nested `function`/`=>` (so there's no closure
+//! that could capture a renamed binding under a shadowed name), and a
+//! candidate name is only renamed if that exact identifier text never
+//! appears anywhere else in the file, renamed functions included. Both
+//! checks are conservative (they can decline a rename that would actually
+//! be safe) rather than approximate (never one that isn't), which matters
+//! more here than in the TS stripper since a wrong rename changes runtime
+//! behavior instead of just failing to strip a type.
+//!
+//! Bails out of renaming the whole file the moment `eval` or `with`
+//! appears anywhere, since either can reach a local by its original name
+//! in ways no static scan here accounts for.
+
+use super::tokens::JsToken;
+use crate::converters::typescript::tokenizer::TokenKind;
+use std::collections::HashSet;
+
+const RESERVED_WORDS: &[&str] = &[
+    "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete",
+    "do", "else", "enum", "export", "extends", "false", "finally", "for", "function", "if",
+    "implements", "import", "in", "instanceof", "interface", "let", "new", "null", "package",
+    "private", "protected", "public", "return", "static", "super", "switch", "this", "throw",
+    "true", "try", "typeof", "var", "void", "while", "with", "yield", "await",
+];
+
+/// The span of tokens making up one `function` declaration or expression,
+/// indices into the same token slice it was found in.
+struct FunctionSpan {
+    params_start: usize,
+    params_end: usize,
+    body_start: usize,
+    body_end: usize,
+}
+
+fn is_ident(token: &JsToken, text: &str) -> bool {
+    token.kind == TokenKind::Ident && token.text == text
+}
+
+fn contains_eval_or_with(tokens: &[JsToken]) -> bool {
+    tokens.iter().any(|t| is_ident(t, "eval") || is_ident(t, "with"))
+}
+
+/// Finds the index of the token matching `open` (already at `open_idx`)
+/// with `close`, skipping over any other token kind - string/comment/regex
+/// contents are already atomic tokens, so only `Punct` tokens of the same
+/// bracket family can unbalance the count.
+fn matching_close(tokens: &[JsToken], open_idx: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 1u32;
+    for (i, token) in tokens.iter().enumerate().skip(open_idx + 1) {
+        match token.kind {
+            TokenKind::Punct(c) if c == open => depth += 1,
+            TokenKind::Punct(c) if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn skip_trivia(tokens: &[JsToken], mut i: usize) -> usize {
+    while i < tokens.len()
+        && matches!(tokens[i].kind, TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment { .. })
+    {
+        i += 1;
+    }
+    i
+}
+
+/// Parses a `function` declaration/expression starting at `function_idx`
+/// (the `function` keyword itself), returning `None` if what follows
+/// doesn't actually look like one.
+fn parse_function_at(tokens: &[JsToken], function_idx: usize) -> Option<FunctionSpan> {
+    let mut i = skip_trivia(tokens, function_idx + 1);
+    if tokens.get(i)?.kind == TokenKind::Ident {
+        i = skip_trivia(tokens, i + 1);
+    }
+    if tokens.get(i)?.kind != TokenKind::Punct('(') {
+        return None;
+    }
+    let params_start = i;
+    let params_end = matching_close(tokens, params_start, '(', ')')?;
+
+    i = skip_trivia(tokens, params_end + 1);
+    if tokens.get(i)?.kind != TokenKind::Punct('{') {
+        return None;
+    }
+    let body_start = i;
+    let body_end = matching_close(tokens, body_start, '{', '}')?;
+
+    Some(FunctionSpan { params_start, params_end, body_start, body_end })
+}
+
+/// Finds every top-level `function` - one whose braces don't sit inside
+/// another function's braces. Nested functions are left alone entirely:
+/// skipping past a parsed span's body means its own `function`/`=>`
+/// tokens are never visited by this scan, so they're never candidates for
+/// renaming either - consistent with bailing out of a function whose body
+/// contains one (see the nested-closure check in [`rename_function_locals`]).
+fn find_top_level_functions(tokens: &[JsToken]) -> Vec<FunctionSpan> {
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if is_ident(&tokens[i], "function") {
+            if let Some(span) = parse_function_at(tokens, i) {
+                let next = span.body_end + 1;
+                spans.push(span);
+                i = next;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    spans
+}
+
+/// Whether `tokens[start..end]` contains another function or an arrow
+/// function - either means a closure could capture a binding under its
+/// original name, which this module's whole-file-occurrence check can't
+/// see through.
+fn contains_nested_function_or_arrow(tokens: &[JsToken], start: usize, end: usize) -> bool {
+    for i in start..end {
+        if is_ident(&tokens[i], "function") {
+            return true;
+        }
+        if tokens[i].kind == TokenKind::Punct('=') && tokens.get(i + 1).map(|t| t.kind) == Some(TokenKind::Punct('>')) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Collects the parameter names of a function, as indices into `tokens`.
+/// Bails (returns `None`) if the parameter list isn't a plain
+/// comma-separated list of identifiers - a default value or destructuring
+/// pattern is more than this heuristic is prepared to reason about.
+fn parameter_names(tokens: &[JsToken], span: &FunctionSpan) -> Option<Vec<usize>> {
+    let mut names = Vec::new();
+    let mut i = span.params_start + 1;
+    while i < span.params_end {
+        match tokens[i].kind {
+            TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment { .. } | TokenKind::Punct(',') => {}
+            TokenKind::Ident => names.push(i),
+            _ => return None,
+        }
+        i += 1;
+    }
+    Some(names)
+}
+
+/// Collects `var`/`let`/`const` names declared directly in the function's
+/// own statement list - not inside any further nested `{}` block, since
+/// `let`/`const` are block-scoped and this makes no attempt to track
+/// shadowing across blocks. Bails per-declaration (rather than for the
+/// whole function) on a destructuring pattern, simply not collecting names
+/// from it.
+fn local_declaration_names(tokens: &[JsToken], span: &FunctionSpan) -> Vec<usize> {
+    let mut names = Vec::new();
+    let mut depth = 0i32;
+    let mut i = span.body_start + 1;
+    while i < span.body_end {
+        match tokens[i].kind {
+            TokenKind::Punct('{') => depth += 1,
+            TokenKind::Punct('}') => depth -= 1,
+            TokenKind::Ident if depth == 0 && matches!(tokens[i].text.as_str(), "var" | "let" | "const") => {
+                let j = skip_trivia(tokens, i + 1);
+                if tokens.get(j).map(|t| t.kind) == Some(TokenKind::Ident) {
+                    names.push(j);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    names
+}
+
+/// Whether `name` is referenced anywhere in `tokens` outside
+/// `[span_start, span_end]` (inclusive), i.e. whether renaming it would
+/// reach outside the function it's local to.
+fn referenced_outside(tokens: &[JsToken], span_start: usize, span_end: usize, name: &str) -> bool {
+    tokens
+        .iter()
+        .enumerate()
+        .any(|(i, t)| (i < span_start || i > span_end) && is_ident(t, name))
+}
+
+/// Generates candidate short names in order: `a`, `b`, ..., `z`, `aa`,
+/// `ab`, ... (bijective base-26), for [`NameAllocator`] to filter down to
+/// ones that are actually free to use.
+fn nth_short_name(mut n: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        let rem = n % 26;
+        letters.push((b'a' + rem as u8) as char);
+        if n < 26 {
+            break;
+        }
+        n = n / 26 - 1;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Hands out short names that are neither a reserved word nor already used
+/// as an identifier anywhere in the file, advancing monotonically so two
+/// functions never get offered the same name twice even if the first
+/// offer went unused (declining one doesn't free it back up - simpler than
+/// reclaiming, and short names are not a scarce resource for real files).
+struct NameAllocator<'a> {
+    used: &'a mut HashSet<String>,
+    next: usize,
+}
+
+impl<'a> NameAllocator<'a> {
+    fn new(used: &'a mut HashSet<String>) -> Self {
+        Self { used, next: 0 }
+    }
+
+    fn next_name(&mut self) -> String {
+        loop {
+            let candidate = nth_short_name(self.next);
+            self.next += 1;
+            if !RESERVED_WORDS.contains(&candidate.as_str()) && !self.used.contains(&candidate) {
+                self.used.insert(candidate.clone());
+                return candidate;
+            }
+        }
+    }
+}
+
+fn rename_function_locals(tokens: &mut [JsToken], span: &FunctionSpan, allocator: &mut NameAllocator) {
+    if contains_nested_function_or_arrow(tokens, span.params_start, span.body_end) {
+        return;
+    }
+
+    let Some(mut candidate_indices) = parameter_names(tokens, span) else {
+        return;
+    };
+    candidate_indices.extend(local_declaration_names(tokens, span));
+
+    let mut seen = HashSet::new();
+    for idx in candidate_indices {
+        let name = tokens[idx].text.clone();
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        if referenced_outside(tokens, span.params_start, span.body_end, &name) {
+            continue;
+        }
+
+        let short = allocator.next_name();
+        if short.len() >= name.len() {
+            continue;
+        }
+
+        for token in tokens.iter_mut() {
+            if is_ident(token, &name) {
+                token.text = short.clone();
+            }
+        }
+    }
+}
+
+/// Renames function-local identifiers in place, as described in the module
+/// doc comment. A no-op if `tokens` contains `eval` or `with` anywhere.
+pub(super) fn rename_locals(tokens: &mut Vec<JsToken>) {
+    if contains_eval_or_with(tokens) {
+        return;
+    }
+
+    let mut used_names: HashSet<String> =
+        tokens.iter().filter(|t| t.kind == TokenKind::Ident).map(|t| t.text.clone()).collect();
+    let mut allocator = NameAllocator::new(&mut used_names);
+
+    for span in find_top_level_functions(tokens) {
+        rename_function_locals(tokens, &span, &mut allocator);
+    }
+}
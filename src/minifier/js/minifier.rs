@@ -0,0 +1,264 @@
+//! Orchestrates JS minification as a pipeline: tokenize (see
+//! [`super::tokens`]), rename function-local identifiers
+//! ([`super::identifiers`]), pool repeated string literals
+//! ([`super::strings`]), then hand the rewritten source to
+//! [`super::whitespace`] for the original character-level
+//! whitespace/comment/ASI pass.
+//!
+//! Renaming and pooling only ever replace a token's text or insert one
+//! fully-formed statement with no newline in it, so neither changes how
+//! many `\n` characters precede any given byte of output - the whitespace
+//! pass's line-granular source map stays valid against the true original
+//! input with no extra bookkeeping for the stages ahead of it.
+
+use super::identifiers;
+use super::strings;
+use super::tokens;
+use super::whitespace;
+
+/// Minifies JavaScript: shortens function-local identifiers, pools
+/// repeated string literals, and removes unnecessary whitespace and
+/// comments, while preserving functionality.
+pub fn minify_js(js: &str) -> String {
+    minify_js_core(js).0
+}
+
+/// Minifies JavaScript like [`minify_js`], additionally returning a
+/// line-granular source map: entry `i` is the 1-based source line that
+/// output line `i` (0-based) originated from.
+pub fn minify_js_with_source_map(js: &str) -> (String, Vec<usize>) {
+    minify_js_core(js)
+}
+
+fn minify_js_core(js: &str) -> (String, Vec<usize>) {
+    let rewritten = rewrite_tokens(js);
+    whitespace::minify_whitespace_and_comments(&rewritten)
+}
+
+fn rewrite_tokens(js: &str) -> String {
+    let mut tokens = tokens::tokenize(js);
+    identifiers::rename_locals(&mut tokens);
+    strings::pool_strings(&mut tokens);
+    tokens::join(&tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basic_whitespace_removal() {
+        let js = "function   test(  ) {   return   42;   }";
+        let expected = "function test(){return 42;}";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_newline_removal() {
+        let js = "function test() {\n    return 42;\n}";
+        let expected = "function test(){return 42;}";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_single_line_comment_removal() {
+        let js = "// This is a comment\nfunction test() {\n    return 42; // another comment\n}";
+        let expected = "function test(){return 42;}";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_multi_line_comment_removal() {
+        let js =
+            "/* This is a comment */\nfunction test() {\n    return 42; /* another comment */\n}";
+        let expected = "function test(){return 42;}";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_preserve_strings() {
+        let js = r#"const message = "Hello   World";"#;
+        let expected = r#"const message="Hello   World";"#;
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_preserve_single_quote_strings() {
+        let js = "const message = 'Hello   World';";
+        let expected = "const message='Hello   World';";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_preserve_template_literals() {
+        let js = "const message = `Hello   ${name}   World`;";
+        let expected = "const message=`Hello   ${name}   World`;";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_preserve_regex_literals() {
+        let js = "const pattern = /hello\\s+world/gi;";
+        let expected = "const pattern=/hello\\s+world/gi;";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_preserve_necessary_spaces_between_keywords() {
+        let js = "return value;";
+        let expected = "return value;";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_preserve_spaces_in_instanceof() {
+        let js = "obj instanceof Array;";
+        let expected = "obj instanceof Array;";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_preserve_spaces_in_typeof() {
+        let js = "typeof obj === 'string';";
+        let expected = "typeof obj==='string';";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_complex_javascript() {
+        let js = r#"
+        // Main function
+        function calculateSum(a, b) {
+            /* Calculate the sum of two numbers */
+            if (typeof a !== 'number' || typeof b !== 'number') {
+                throw new Error("Invalid arguments");
+            }
+            return a + b;
+        }
+
+        const result = calculateSum(10, 20);
+        console.log(`Result: ${result}`);
+        "#;
+
+        let result = minify_js(js);
+        assert!(!result.contains("//"));
+        assert!(!result.contains("/*"));
+        assert!(!result.contains("*/"));
+        assert!(result.contains("function calculateSum(a,b){"));
+        assert!(result.contains("typeof a"));
+        assert!(result.contains("typeof b"));
+        assert!(result.contains("`Result: ${result}`"));
+    }
+
+    #[test]
+    fn test_escaped_quotes_in_strings() {
+        let js = r#"const message = "He said \"Hello\"";"#;
+        let expected = r#"const message="He said \"Hello\"";"#;
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_regex_after_equals() {
+        let js = "const pattern = /test/g;";
+        let expected = "const pattern=/test/g;";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_division_vs_regex() {
+        let js = "const result = a / b; const pattern = /test/;";
+        let expected = "const result=a/b;const pattern=/test/;";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_preserve_increment_operators() {
+        let js = "i++; ++j; i--; --j;";
+        let expected = "i++;++j;i--;--j;";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_asi_preservation() {
+        let js = "return\n42;";
+        // This should preserve the newline to maintain ASI behavior
+        let result = minify_js(js);
+        assert!(result.contains("return\n") || result.contains("return 42"));
+    }
+
+    #[test]
+    fn test_template_literal_with_expressions() {
+        let js = "const html = `<div class=\"${className}\">${content}</div>`;";
+        let expected = "const html=`<div class=\"${className}\">${content}</div>`;";
+        assert_eq!(minify_js(js), expected);
+    }
+
+    #[test]
+    fn test_with_source_map_matches_minify_js_output() {
+        let js = "function test() {\n    return 42;\n}";
+        let (minified, _) = minify_js_with_source_map(js);
+        assert_eq!(minified, minify_js(js));
+    }
+
+    #[test]
+    fn test_with_source_map_tracks_preserved_asi_newline() {
+        let js = "foo();\nbar();";
+        let (minified, sources) = minify_js_with_source_map(js);
+        assert_eq!(minified, "foo();\nbar();");
+        assert_eq!(sources, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_renames_long_local_never_referenced_outside_its_function() {
+        let js = "function run() { var accumulatorTotal = 0; accumulatorTotal += 1; return accumulatorTotal; }";
+        let result = minify_js(js);
+        assert!(!result.contains("accumulatorTotal"));
+        assert!(result.contains("function run(){"));
+    }
+
+    #[test]
+    fn test_does_not_rename_identifier_referenced_outside_its_function() {
+        let js = "function run(shared) { return shared; } console.log(shared);";
+        let result = minify_js(js);
+        assert!(result.contains("shared"));
+    }
+
+    #[test]
+    fn test_does_not_rename_locals_when_function_has_a_nested_closure() {
+        let js = "function run() { var longLivedCounter = 0; return function() { return longLivedCounter; }; }";
+        let result = minify_js(js);
+        assert!(result.contains("longLivedCounter"));
+    }
+
+    #[test]
+    fn test_bails_out_of_renaming_entirely_when_eval_is_present() {
+        let js = "function run() { var accumulatorTotal = 0; eval('1'); return accumulatorTotal; }";
+        let result = minify_js(js);
+        assert!(result.contains("accumulatorTotal"));
+    }
+
+    #[test]
+    fn test_pools_repeated_string_literal_above_size_threshold() {
+        let js = r#"log("a repeated message");log("a repeated message");log("a repeated message");"#;
+        let result = minify_js(js);
+        assert!(result.contains(r#"var _s=["a repeated message"];"#));
+        assert_eq!(result.matches("_s[0]").count(), 3);
+        assert!(!result.contains("a repeated message\""));
+    }
+
+    #[test]
+    fn test_does_not_pool_string_used_only_once() {
+        let js = r#"log("a repeated message");"#;
+        let result = minify_js(js);
+        assert!(!result.contains("_s["));
+        assert!(result.contains("a repeated message"));
+    }
+
+    #[test]
+    fn test_does_not_pool_repeated_object_key() {
+        let js = r#"const a = {"configurationKey": 1};const b = {"configurationKey": 2};const c = {"configurationKey": 3};"#;
+        let result = minify_js(js);
+        assert!(!result.contains("_s["));
+        assert_eq!(result.matches("configurationKey").count(), 3);
+    }
+}
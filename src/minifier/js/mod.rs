@@ -0,0 +1,21 @@
+//! JavaScript minification module
+//!
+//! The module is organized into separate components:
+//! - `tokens`: Wraps the shared TypeScript tokenizer into an owned token
+//!   stream the passes below can rewrite
+//! - `identifiers`: Renames function-local identifiers that are never
+//!   referenced outside their function to short generated names
+//! - `strings`: Pools repeated string literals into a shared array when
+//!   doing so shrinks the output
+//! - `whitespace`: Removes unnecessary whitespace and comments while
+//!   preserving ASI-sensitive newlines - the original minifier, now the
+//!   pipeline's final serialization pass
+//! - `minifier`: Orchestrates the passes above
+
+mod identifiers;
+mod minifier;
+mod strings;
+mod tokens;
+mod whitespace;
+
+pub use minifier::{minify_js, minify_js_with_source_map};
@@ -0,0 +1,96 @@
+//! Pools repeated string literals into a single top-level array, replacing
+//! each occurrence with an index into it, when doing so actually shrinks
+//! the output.
+//!
+//! A literal of length `len` appearing `n` times costs `len*n` bytes
+//! in place; pooled, it costs `len` bytes once in the array plus roughly
+//! `REF_OVERHEAD` bytes (an `_s[N]` reference) at each of its `n` sites, so
+//! pooling only pays for itself once `len*n` exceeds that total. Object
+//! keys and similarly-positioned labels (`case "foo":`) are left alone,
+//! since replacing the key itself with `_s[N]` would need a computed-key
+//! rewrite this pass doesn't attempt; string-template interpolations never
+//! come up at all, since [`super::tokens`] leaves a whole template literal
+//! as one token and never descends into it.
+
+use super::tokens::{next_significant, JsToken};
+use crate::converters::typescript::tokenizer::TokenKind;
+use std::collections::HashMap;
+
+const POOL_VAR: &str = "_s";
+/// Approximate length of an `_s[N]` reference, for small `N`.
+const REF_OVERHEAD: usize = 5;
+/// The comma separating this entry from the next one in the array literal.
+const ARRAY_OVERHEAD: usize = 1;
+
+fn is_pooled_string(token: &JsToken) -> bool {
+    matches!(token.kind, TokenKind::Str { terminated: true })
+}
+
+fn is_key_or_label_position(tokens: &[JsToken], idx: usize) -> bool {
+    matches!(next_significant(tokens, idx), Some(t) if t.kind == TokenKind::Punct(':'))
+}
+
+/// Index just past a leading `"use strict";`/`'use strict';` directive, if
+/// one is present, so the pool declaration never gets inserted ahead of it
+/// and strip it of its directive-prologue status.
+fn directive_prologue_end(tokens: &[JsToken]) -> usize {
+    let mut i = 0;
+    while i < tokens.len() && tokens[i].kind == TokenKind::Whitespace {
+        i += 1;
+    }
+    let Some(token) = tokens.get(i) else { return 0 };
+    let unquoted = token.text.trim_matches(|c| c == '"' || c == '\'');
+    if !(is_pooled_string(token) && unquoted == "use strict") {
+        return 0;
+    }
+
+    i += 1;
+    while tokens.get(i).map(|t| t.kind) == Some(TokenKind::Whitespace) {
+        i += 1;
+    }
+    if tokens.get(i).map(|t| t.kind) == Some(TokenKind::Punct(';')) {
+        i += 1;
+    }
+    i
+}
+
+/// Inserts `declaration` as a single new token with no leading or trailing
+/// newline, so the whitespace pass downstream sees no new line breaks.
+fn insert_pool_declaration(tokens: &mut Vec<JsToken>, declaration: String) {
+    let at = directive_prologue_end(tokens);
+    tokens.insert(at, JsToken { kind: TokenKind::Unknown, text: declaration });
+}
+
+/// Pools repeated string literals in place, as described in the module doc
+/// comment. A no-op if no literal clears the size threshold.
+pub(super) fn pool_strings(tokens: &mut Vec<JsToken>) {
+    let mut occurrences: HashMap<String, Vec<usize>> = HashMap::new();
+    for idx in 0..tokens.len() {
+        if is_pooled_string(&tokens[idx]) && !is_key_or_label_position(tokens, idx) {
+            occurrences.entry(tokens[idx].text.clone()).or_default().push(idx);
+        }
+    }
+
+    let mut pooled: Vec<(String, Vec<usize>)> = occurrences
+        .into_iter()
+        .filter(|(text, indices)| {
+            let len = text.len();
+            let n = indices.len();
+            len * n > len + n * REF_OVERHEAD + ARRAY_OVERHEAD
+        })
+        .collect();
+    if pooled.is_empty() {
+        return;
+    }
+    pooled.sort_by_key(|(_, indices)| indices[0]);
+
+    for (slot, (_, indices)) in pooled.iter().enumerate() {
+        let reference = format!("{POOL_VAR}[{slot}]");
+        for &idx in indices {
+            tokens[idx].text = reference.clone();
+        }
+    }
+
+    let array_literal = pooled.iter().map(|(text, _)| text.as_str()).collect::<Vec<_>>().join(",");
+    insert_pool_declaration(tokens, format!("var {POOL_VAR}=[{array_literal}];"));
+}
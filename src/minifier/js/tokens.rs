@@ -0,0 +1,43 @@
+//! Owns the token text [`super::identifiers`] and [`super::strings`] need
+//! to rewrite, wrapping [`crate::converters::typescript::tokenizer`]'s
+//! borrowed [`Token`](crate::converters::typescript::tokenizer::Token)s the
+//! same way [`crate::minifier::css::tokenizer`] wraps its own scan into an
+//! owned `TokenSpan` - reusing that tokenizer instead of writing a second
+//! one, since the JS it scans is already a subset of what it handles for
+//! TypeScript.
+
+use crate::converters::typescript::tokenizer::{tokenize as scan, Token, TokenKind};
+
+/// A [`TokenKind`] paired with its own text, so a rewrite pass can replace
+/// `text` (a shorter identifier, a pool-array index) without touching any
+/// other token.
+pub(super) struct JsToken {
+    pub kind: TokenKind,
+    pub text: String,
+}
+
+impl From<Token<'_>> for JsToken {
+    fn from(token: Token<'_>) -> Self {
+        Self { kind: token.kind, text: token.text.to_string() }
+    }
+}
+
+/// Tokenizes all of `input` into an owned token stream.
+pub(super) fn tokenize(input: &str) -> Vec<JsToken> {
+    scan(input).into_iter().map(JsToken::from).collect()
+}
+
+/// Concatenates every token's text back into a single string. Inverse of
+/// [`tokenize`] when no token's text has been rewritten.
+pub(super) fn join(tokens: &[JsToken]) -> String {
+    tokens.iter().map(|t| t.text.as_str()).collect()
+}
+
+/// The next token in `tokens` after `from` that isn't whitespace or a
+/// comment, e.g. to look past the space in `"key" : value` when deciding
+/// whether a string sits in object-key position.
+pub(super) fn next_significant(tokens: &[JsToken], from: usize) -> Option<&JsToken> {
+    tokens[from + 1..]
+        .iter()
+        .find(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment { .. }))
+}
@@ -1,3 +1,9 @@
+//! The original character-by-character whitespace/comment/ASI minifier,
+//! now the final stage of [`super::minify_js`]'s pipeline rather than the
+//! whole thing: it still does all the actual byte-shrinking, just over
+//! text [`super::identifiers`] and [`super::strings`] have already
+//! rewritten tokens in.
+
 /// Represents the state of JavaScript parsing
 #[allow(clippy::struct_excessive_bools)]
 struct JsParseState {
@@ -218,54 +224,43 @@ fn handle_whitespace(
     }
 }
 
-/// Minifies JavaScript by removing unnecessary whitespace and comments while preserving functionality
-pub fn minify_js(js: &str) -> String {
+/// Removes unnecessary whitespace and comments from `js` while preserving
+/// functionality, returning a line-granular source map alongside it: entry
+/// `i` is the 1-based source line that output line `i` (0-based) originated
+/// from. This is the final serialization pass of [`super::minify_js`]'s
+/// pipeline, unchanged from when it was the entire minifier, so it still
+/// assumes `js` has the same newlines as the true original input - true by
+/// construction, since [`super::identifiers`] and [`super::strings`] only
+/// rewrite token text or insert a single-line declaration ahead of it.
+pub(super) fn minify_whitespace_and_comments(js: &str) -> (String, Vec<usize>) {
     let mut result = String::with_capacity(js.len());
     let mut chars = js.chars().peekable();
     let mut state = JsParseState::new();
     let mut prev_char = '\0';
     let mut prev_non_whitespace = '\0';
+    let mut source_line: usize = 1;
+    let mut output_line_sources: Vec<usize> = vec![1];
 
     while let Some(ch) = chars.next() {
-        // Handle single-line comments
-        if handle_single_line_comments(ch, &mut chars, &mut state, &mut result) {
-            prev_char = ch;
-            continue;
-        }
-
-        // Handle multi-line comments
-        if handle_multi_line_comments(ch, &mut chars, &mut state) {
-            prev_char = ch;
-            continue;
-        }
-
-        // Handle template literals
-        if handle_template_literals(ch, prev_char, &mut state, &mut result) {
-            prev_char = ch;
-            continue;
-        }
-
-        // Handle string literals
-        if handle_string_literals(ch, prev_char, &mut state, &mut result) {
-            prev_char = ch;
-            continue;
+        if ch == '\n' {
+            source_line += 1;
         }
+        let output_len_before = result.len();
 
-        // Handle regex literals
-        if handle_regex_literals(ch, prev_char, prev_non_whitespace, &mut state, &mut result) {
-            prev_char = ch;
-            continue;
-        }
-
-        // Preserve content inside strings, template literals, and regex
-        if state.is_in_any_string() {
+        if handle_single_line_comments(ch, &mut chars, &mut state, &mut result) {
+            // handled above
+        } else if handle_multi_line_comments(ch, &mut chars, &mut state) {
+            // handled above
+        } else if handle_template_literals(ch, prev_char, &mut state, &mut result) {
+            // handled above
+        } else if handle_string_literals(ch, prev_char, &mut state, &mut result) {
+            // handled above
+        } else if handle_regex_literals(ch, prev_char, prev_non_whitespace, &mut state, &mut result) {
+            // handled above
+        } else if state.is_in_any_string() {
+            // Preserve content inside strings, template literals, and regex
             result.push(ch);
-            prev_char = ch;
-            continue;
-        }
-
-        // Handle whitespace - skip unnecessary whitespace
-        if handle_whitespace(ch, &mut chars, &result) {
+        } else if handle_whitespace(ch, &mut chars, &result) {
             if ch == '\n' && !result.is_empty() {
                 let last_char = result.chars().last().unwrap_or('\0');
                 if matches!(last_char, ')' | ']' | '}' | ';') {
@@ -276,172 +271,20 @@ pub fn minify_js(js: &str) -> String {
             } else {
                 result.push(' ');
             }
-            prev_char = ch;
-            continue;
-        }
-
-        if ch.is_whitespace() {
-            prev_char = ch;
-            continue;
+        } else if ch.is_whitespace() {
+            // Skip unnecessary whitespace
+        } else {
+            // Handle other characters
+            result.push(ch);
+            prev_non_whitespace = ch;
         }
 
-        // Handle other characters
-        result.push(ch);
-        prev_non_whitespace = ch;
         prev_char = ch;
-    }
-
-    result
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_basic_whitespace_removal() {
-        let js = "function   test(  ) {   return   42;   }";
-        let expected = "function test(){return 42;}";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_newline_removal() {
-        let js = "function test() {\n    return 42;\n}";
-        let expected = "function test(){return 42;}";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_single_line_comment_removal() {
-        let js = "// This is a comment\nfunction test() {\n    return 42; // another comment\n}";
-        let expected = "function test(){return 42;}";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_multi_line_comment_removal() {
-        let js =
-            "/* This is a comment */\nfunction test() {\n    return 42; /* another comment */\n}";
-        let expected = "function test(){return 42;}";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_preserve_strings() {
-        let js = r#"const message = "Hello   World";"#;
-        let expected = r#"const message="Hello   World";"#;
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_preserve_single_quote_strings() {
-        let js = "const message = 'Hello   World';";
-        let expected = "const message='Hello   World';";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_preserve_template_literals() {
-        let js = "const message = `Hello   ${name}   World`;";
-        let expected = "const message=`Hello   ${name}   World`;";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_preserve_regex_literals() {
-        let js = "const pattern = /hello\\s+world/gi;";
-        let expected = "const pattern=/hello\\s+world/gi;";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_preserve_necessary_spaces_between_keywords() {
-        let js = "return value;";
-        let expected = "return value;";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_preserve_spaces_in_instanceof() {
-        let js = "obj instanceof Array;";
-        let expected = "obj instanceof Array;";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_preserve_spaces_in_typeof() {
-        let js = "typeof obj === 'string';";
-        let expected = "typeof obj==='string';";
-        assert_eq!(minify_js(js), expected);
-    }
 
-    #[test]
-    fn test_complex_javascript() {
-        let js = r#"
-        // Main function
-        function calculateSum(a, b) {
-            /* Calculate the sum of two numbers */
-            if (typeof a !== 'number' || typeof b !== 'number') {
-                throw new Error("Invalid arguments");
-            }
-            return a + b;
+        if result.len() > output_len_before && result.as_bytes()[output_len_before..].contains(&b'\n') {
+            output_line_sources.push(source_line);
         }
-
-        const result = calculateSum(10, 20);
-        console.log(`Result: ${result}`);
-        "#;
-
-        let result = minify_js(js);
-        assert!(!result.contains("//"));
-        assert!(!result.contains("/*"));
-        assert!(!result.contains("*/"));
-        assert!(result.contains("function calculateSum(a,b){"));
-        assert!(result.contains("typeof a"));
-        assert!(result.contains("typeof b"));
-        assert!(result.contains("`Result: ${result}`"));
-    }
-
-    #[test]
-    fn test_escaped_quotes_in_strings() {
-        let js = r#"const message = "He said \"Hello\"";"#;
-        let expected = r#"const message="He said \"Hello\"";"#;
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_regex_after_equals() {
-        let js = "const pattern = /test/g;";
-        let expected = "const pattern=/test/g;";
-        assert_eq!(minify_js(js), expected);
     }
 
-    #[test]
-    fn test_division_vs_regex() {
-        let js = "const result = a / b; const pattern = /test/;";
-        let expected = "const result=a/b;const pattern=/test/;";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_preserve_increment_operators() {
-        let js = "i++; ++j; i--; --j;";
-        let expected = "i++;++j;i--;--j;";
-        assert_eq!(minify_js(js), expected);
-    }
-
-    #[test]
-    fn test_asi_preservation() {
-        let js = "return\n42;";
-        // This should preserve the newline to maintain ASI behavior
-        let result = minify_js(js);
-        assert!(result.contains("return\n") || result.contains("return 42"));
-    }
-
-    #[test]
-    fn test_template_literal_with_expressions() {
-        let js = "const html = `<div class=\"${className}\">${content}</div>`;";
-        let expected = "const html=`<div class=\"${className}\">${content}</div>`;";
-        assert_eq!(minify_js(js), expected);
-    }
+    (result, output_line_sources)
 }
@@ -0,0 +1,103 @@
+//! Minifies JSON by removing insignificant whitespace between tokens.
+//!
+//! Runs a single pass over the input with a two-flag state (`in_string`,
+//! `prev_was_backslash`) so a quote preceded by an odd number of backslashes
+//! is recognized as escaped rather than as the closing quote - the rest of
+//! a string's contents, escape sequences included, pass through byte-for-byte.
+//! Numbers, `true`/`false`/`null`, and structural punctuation are untouched
+//! apart from the whitespace around them.
+
+use crate::error::{Error, Result};
+
+/// Minifies `json` by stripping whitespace outside of string literals.
+///
+/// Returns `Err(Error::Json(_))` if a string literal is left unterminated,
+/// so malformed embedded JSON fails the build rather than producing
+/// corrupt output.
+pub fn minify_json(json: &str) -> Result<String> {
+    let mut result = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut prev_was_backslash = false;
+
+    for ch in json.chars() {
+        if in_string {
+            result.push(ch);
+            if ch == '"' && !prev_was_backslash {
+                in_string = false;
+            }
+            prev_was_backslash = ch == '\\' && !prev_was_backslash;
+            continue;
+        }
+
+        if ch.is_whitespace() {
+            continue;
+        }
+
+        if ch == '"' {
+            in_string = true;
+        }
+        result.push(ch);
+    }
+
+    if in_string {
+        return Err(Error::Json("unterminated string literal".to_string()));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_removes_whitespace_between_tokens() {
+        let json = "{  \"a\" : 1,  \"b\"  :  [ 1 , 2 , 3 ]  }";
+        assert_eq!(minify_json(json).unwrap(), r#"{"a":1,"b":[1,2,3]}"#);
+    }
+
+    #[test]
+    fn test_preserves_whitespace_inside_strings() {
+        let json = r#"{ "message" : "hello   world" }"#;
+        assert_eq!(minify_json(json).unwrap(), r#"{"message":"hello   world"}"#);
+    }
+
+    #[test]
+    fn test_preserves_escaped_quote_inside_string() {
+        let json = r#"{ "message" : "he said \"hi\"" }"#;
+        assert_eq!(minify_json(json).unwrap(), r#"{"message":"he said \"hi\""}"#);
+    }
+
+    #[test]
+    fn test_preserves_escaped_backslash_before_closing_quote() {
+        let json = r#"{ "path" : "C:\\" }"#;
+        assert_eq!(minify_json(json).unwrap(), r#"{"path":"C:\\"}"#);
+    }
+
+    #[test]
+    fn test_preserves_unicode_escape_sequences() {
+        let json = r#"{ "emoji" : "\u0041\u0042" }"#;
+        assert_eq!(minify_json(json).unwrap(), r#"{"emoji":"\u0041\u0042"}"#);
+    }
+
+    #[test]
+    fn test_passes_through_numbers_and_literals() {
+        let json = "{ \"a\" : true , \"b\" : false , \"c\" : null , \"d\" : -1.5e10 }";
+        assert_eq!(
+            minify_json(json).unwrap(),
+            r#"{"a":true,"b":false,"c":null,"d":-1.5e10}"#
+        );
+    }
+
+    #[test]
+    fn test_errors_on_unterminated_string() {
+        let json = r#"{ "a" : "unterminated }"#;
+        assert!(matches!(minify_json(json), Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn test_errors_on_string_unterminated_by_trailing_backslash() {
+        let json = r#"{ "a" : "ends with backslash\"#;
+        assert!(matches!(minify_json(json), Err(Error::Json(_))));
+    }
+}
@@ -1,8 +1,17 @@
+mod byte_scan;
 pub mod css;
 pub mod html;
 pub mod js;
+mod json;
+pub mod source_map;
+
+pub(crate) use byte_scan::{find_byte, find_byte_any};
 
 // Re-export the trait implementations and functions
-pub use css::{CssMinifier, minify_css};
-pub use html::{HtmlMinifier, minify_html};
-pub use js::{JsMinifier, minify_js};
+pub use css::{CssMinifier, minify_css, minify_css_with_source_map};
+pub use html::{
+    html_to_text, inject_heading_ids, HtmlMinifier, minify_html, minify_html_with_markers,
+    minify_inline_assets, DEFAULT_PRESERVED_COMMENT_MARKERS,
+};
+pub use js::{JsMinifier, minify_js, minify_js_with_source_map};
+pub use json::minify_json;
@@ -0,0 +1,113 @@
+//! Minimal Source Map v3 support shared by the CSS/JS minifiers.
+//!
+//! Only line-granular mappings are produced: each output line gets a single
+//! segment pointing at column 0 of the source line it came from. This keeps
+//! the encoder simple while still letting devtools map a minified line back
+//! to readable source.
+
+const BASE64_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes a signed value as a Source Map base64-VLQ, appending it to `out`.
+/// The sign occupies the least significant bit of the first digit; each
+/// base64 digit carries 5 data bits plus a continuation bit in its own MSB.
+fn encode_vlq(value: i64, out: &mut String) {
+    let mut value = if value < 0 {
+        ((-value) as u64) << 1 | 1
+    } else {
+        (value as u64) << 1
+    };
+
+    loop {
+        let mut digit = (value & 0b11111) as u8;
+        value >>= 5;
+        if value > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64_ALPHABET[digit as usize] as char);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// Builds the `mappings` field of a Source Map v3 document from a
+/// line-granular mapping: `source_line_for_output_line[i]` is the 1-based
+/// source line that output line `i` (0-based) originated from.
+fn build_mappings(source_line_for_output_line: &[usize]) -> String {
+    let mut mappings = String::new();
+    let mut prev_source_line: i64 = 0;
+
+    for (i, &source_line) in source_line_for_output_line.iter().enumerate() {
+        if i > 0 {
+            mappings.push(';');
+        }
+        // A single segment per line: output column 0, source index 0,
+        // source line delta, source column 0. Column deltas reset every
+        // line, so the output-column field is always 0 here.
+        let source_line_delta = source_line as i64 - prev_source_line;
+        encode_vlq(0, &mut mappings);
+        encode_vlq(0, &mut mappings);
+        encode_vlq(source_line_delta, &mut mappings);
+        encode_vlq(0, &mut mappings);
+        prev_source_line = source_line as i64;
+    }
+
+    mappings
+}
+
+/// Builds a full Source Map v3 JSON document for a single source file, given
+/// the line-granular mapping produced alongside a minified output.
+pub fn build_source_map_json(source_name: &str, source_line_for_output_line: &[usize]) -> String {
+    let mappings = build_mappings(source_line_for_output_line);
+    let escaped_source_name = source_name.replace('\\', "\\\\").replace('"', "\\\"");
+    format!(
+        "{{\"version\":3,\"sources\":[\"{escaped_source_name}\"],\"names\":[],\"mappings\":\"{mappings}\"}}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_zero() {
+        let mut out = String::new();
+        encode_vlq(0, &mut out);
+        assert_eq!(out, "A");
+    }
+
+    #[test]
+    fn encodes_small_positive_and_negative_values() {
+        let mut out = String::new();
+        encode_vlq(1, &mut out);
+        assert_eq!(out, "C");
+
+        let mut out = String::new();
+        encode_vlq(-1, &mut out);
+        assert_eq!(out, "D");
+    }
+
+    #[test]
+    fn encodes_multi_digit_value() {
+        // 16 requires a continuation digit under the 5-bit VLQ scheme.
+        let mut out = String::new();
+        encode_vlq(16, &mut out);
+        assert_eq!(out, "gB");
+    }
+
+    #[test]
+    fn builds_one_segment_per_output_line() {
+        let mappings = build_mappings(&[1, 2, 5]);
+        assert_eq!(mappings, "AACA;AACA;AAGA");
+    }
+
+    #[test]
+    fn builds_full_source_map_json() {
+        let json = build_source_map_json("style.css", &[1, 2]);
+        assert_eq!(
+            json,
+            "{\"version\":3,\"sources\":[\"style.css\"],\"names\":[],\"mappings\":\"AACA;AACA\"}"
+        );
+    }
+}
@@ -0,0 +1,635 @@
+//! Chunking and context-building logic shared by every paginated listing -
+//! the global post feed in [`crate::generate_pagination_pages`] and taxonomy
+//! listings (categories today, tags potentially later) in
+//! [`crate::generate_category_pages`]. Both slice the same kind of post
+//! collection into `posts_per_page`-sized pages and need the same navigation
+//! variables (`page_number`, `total_pages`, prev/next URLs, a page-number
+//! list, ...) in their template context; only the base URL/output prefix and
+//! which layout to render with differ per listing.
+
+use crate::config::{PostSortOrder, SortMode};
+use crate::types::{ContentCollection, ContentItem, Variables};
+
+/// Default path segment used for pagination URLs/output directories when a
+/// listing doesn't configure its own via the `pagination_path` variable.
+pub const DEFAULT_PAGINATION_PATH: &str = "page";
+
+/// How many pages on either side of the current one stay visible in a
+/// windowed pager, when a listing doesn't configure its own via the
+/// `pagination_window` variable.
+pub const DEFAULT_PAGINATION_WINDOW: usize = 2;
+
+/// Reads the pagination-tuning variables (`pagination_path`,
+/// `pagination_index_first_page`, `pagination_windowed`,
+/// `pagination_window`) that are common to every paginated listing.
+pub struct PaginationSettings<'a> {
+    pub pagination_path: &'a str,
+    pub index_first_page: bool,
+    pub windowed: bool,
+    pub window: usize,
+}
+
+impl<'a> PaginationSettings<'a> {
+    /// Reads pagination settings from `global_variables`, falling back to
+    /// `default_pagination_path` (typically a site's configured
+    /// `paginate_path`) rather than the hardcoded [`DEFAULT_PAGINATION_PATH`]
+    /// when no `pagination_path` variable overrides it, and to
+    /// `default_index_first_page` (typically a site's configured
+    /// `pagination_index_first_page`) when no `pagination_index_first_page`
+    /// variable overrides it.
+    pub fn from_variables(
+        global_variables: &'a Variables,
+        default_pagination_path: &'a str,
+        default_index_first_page: bool,
+    ) -> Self {
+        Self {
+            pagination_path: global_variables
+                .get("pagination_path")
+                .map(String::as_str)
+                .unwrap_or(default_pagination_path),
+            index_first_page: global_variables
+                .get("pagination_index_first_page")
+                .map_or(default_index_first_page, |value| {
+                    value.eq_ignore_ascii_case("true")
+                }),
+            windowed: global_variables
+                .get("pagination_windowed")
+                .is_some_and(|value| value.eq_ignore_ascii_case("true")),
+            window: global_variables
+                .get("pagination_window")
+                .and_then(|value| value.parse::<usize>().ok())
+                .unwrap_or(DEFAULT_PAGINATION_WINDOW),
+        }
+    }
+}
+
+/// Reads the `lang` variable that marks a paginated listing as belonging to
+/// one language of a multilingual site, if set and non-empty. Pages without
+/// a language keep today's unprefixed behavior.
+pub fn language_prefix(variables: &Variables) -> Option<&str> {
+    variables
+        .get("lang")
+        .map(String::as_str)
+        .filter(|lang| !lang.is_empty())
+}
+
+/// Resolves the effective page size for a paginated listing: an explicit
+/// `pagination_posts_per_page` variable - set site-wide in `SiteConfig` or
+/// overridden in a collection's own front matter/global variables - takes
+/// priority over the site's default `posts_per_page`.
+pub fn resolve_posts_per_page(default_posts_per_page: usize, variables: &Variables) -> usize {
+    variables
+        .get("pagination_posts_per_page")
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|&posts_per_page| posts_per_page > 0)
+        .unwrap_or(default_posts_per_page)
+}
+
+/// Orders a taxonomy term's posts before pagination, so the first page is
+/// stable and meaningful regardless of the grouping's iteration order. A
+/// post missing (or holding an unparseable value for) the field a mode sorts
+/// by is treated as equal to any other post it's compared against, so the
+/// stable sort leaves it in its original relative position rather than
+/// sorting it to one end.
+pub fn sort_posts_by_mode(posts: &mut ContentCollection, mode: SortMode) {
+    match mode {
+        SortMode::None => {}
+        SortMode::Date => posts.sort_by(|a, b| {
+            match (a.get("date").filter(|d| !d.is_empty()), b.get("date").filter(|d| !d.is_empty())) {
+                (Some(date_a), Some(date_b)) => date_b.cmp(date_a),
+                _ => std::cmp::Ordering::Equal,
+            }
+        }),
+        SortMode::Order => posts.sort_by(|a, b| {
+            match (
+                a.get("order").and_then(|value| value.parse::<i64>().ok()),
+                b.get("order").and_then(|value| value.parse::<i64>().ok()),
+            ) {
+                (Some(order_a), Some(order_b)) => order_a.cmp(&order_b),
+                _ => std::cmp::Ordering::Equal,
+            }
+        }),
+    }
+}
+
+/// Orders the main post listing's posts before pagination, per
+/// [`PostSortOrder`]. Unlike [`sort_posts_by_mode`], a post missing (or
+/// holding an unparseable value for) the field a mode sorts by sorts after
+/// every post that has one, rather than keeping its original position.
+pub fn sort_posts_by_post_sort_order(posts: &mut ContentCollection, order: PostSortOrder) {
+    match order {
+        PostSortOrder::None => {}
+        PostSortOrder::DateDesc => posts.sort_by(|a, b| {
+            cmp_missing_last(a.get("date").filter(|d| !d.is_empty()), b.get("date").filter(|d| !d.is_empty()), |a, b| {
+                b.cmp(a)
+            })
+        }),
+        PostSortOrder::DateAsc => posts.sort_by(|a, b| {
+            cmp_missing_last(a.get("date").filter(|d| !d.is_empty()), b.get("date").filter(|d| !d.is_empty()), |a, b| {
+                a.cmp(b)
+            })
+        }),
+        PostSortOrder::Order => posts.sort_by(|a, b| {
+            let order_a = a.get("order").and_then(|value| value.parse::<i64>().ok());
+            let order_b = b.get("order").and_then(|value| value.parse::<i64>().ok());
+            cmp_missing_last(order_a, order_b, |a, b| a.cmp(b))
+        }),
+    }
+}
+
+/// Compares `a` and `b` with `present` when both are `Some`, but always sorts
+/// a missing value after a present one regardless of `present`'s direction,
+/// and treats two missing values as equal.
+fn cmp_missing_last<T>(a: Option<T>, b: Option<T>, present: impl FnOnce(&T, &T) -> std::cmp::Ordering) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => present(&a, &b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
+/// Slices `posts` into `posts_per_page`-sized pages, returning each page's
+/// 1-based number, the total page count, and its post slice.
+pub fn chunk_posts_for_pagination<'a>(
+    posts_per_page: usize,
+    posts: &'a [ContentItem],
+) -> Vec<(usize, usize, &'a [ContentItem])> {
+    let total_pages = posts.len().div_ceil(posts_per_page);
+    (1..=total_pages)
+        .map(|page_num| {
+            let start = (page_num - 1) * posts_per_page;
+            let end = std::cmp::min(start + posts_per_page, posts.len());
+            (page_num, total_pages, &posts[start..end])
+        })
+        .collect()
+}
+
+/// The URL a pagination page is served at. Page 1 renders at `base_url`
+/// itself (the section index) when `index_first_page` is set; every other
+/// page (and page 1 when it isn't) lives under
+/// `{base_url}/{pagination_path}/{page_num}/`. `base_url` is the listing's
+/// own root - `""` for the global feed, `/category/<slug>` for a taxonomy
+/// term - and carries no trailing slash.
+pub fn pagination_page_url(
+    base_url: &str,
+    pagination_path: &str,
+    page_num: usize,
+    index_first_page: bool,
+) -> String {
+    if page_num == 1 && index_first_page {
+        format!("{base_url}/")
+    } else {
+        format!("{base_url}/{pagination_path}/{page_num}/")
+    }
+}
+
+/// The output directory (relative to the site's output root, with a
+/// trailing slash) a pagination page's `index.html` is written into - the
+/// on-disk counterpart of [`pagination_page_url`]. `output_prefix` is the
+/// listing's own output directory relative to the site root - `""` for the
+/// global feed, `category/<slug>/` for a taxonomy term - and carries a
+/// trailing slash when non-empty.
+pub fn pagination_output_subdir(
+    output_prefix: &str,
+    pagination_path: &str,
+    page_num: usize,
+    index_first_page: bool,
+) -> String {
+    if page_num == 1 && index_first_page {
+        output_prefix.to_string()
+    } else {
+        format!("{output_prefix}{pagination_path}/{page_num}/")
+    }
+}
+
+/// One entry in a page list: either a real page number or a "gap" - a
+/// sentinel marking a run of skipped page numbers between two visible ones.
+pub enum PageListEntry {
+    Page(usize),
+    Gap,
+}
+
+/// Builds the windowed page list `1 .. current-window, current+window ..
+/// total_pages` needs: page 1 and `total_pages` are always visible, as are
+/// every page within `window` of `current_page`; any break between
+/// consecutive visible numbers becomes a single [`PageListEntry::Gap`].
+pub fn windowed_page_list(current_page: usize, total_pages: usize, window: usize) -> Vec<PageListEntry> {
+    let lower = current_page.saturating_sub(window).max(1);
+    let upper = std::cmp::min(current_page + window, total_pages);
+
+    let mut visible: Vec<usize> = vec![1];
+    visible.extend(lower..=upper);
+    visible.push(total_pages);
+    visible.sort_unstable();
+    visible.dedup();
+
+    let mut entries = Vec::with_capacity(visible.len() * 2);
+    let mut previous: Option<usize> = None;
+    for page_num in visible {
+        if let Some(prev) = previous {
+            if page_num > prev + 1 {
+                entries.push(PageListEntry::Gap);
+            }
+        }
+        entries.push(PageListEntry::Page(page_num));
+        previous = Some(page_num);
+    }
+    entries
+}
+
+/// Builds the full set of shared navigation variables for one pagination
+/// page: `page_number`, `total_pages`, `has_previous`/`has_next` and their
+/// URLs, ready-made `rel_prev_link`/`rel_next_link` `<link>` tags for a
+/// layout's `<head>`, `first_page_url`/`last_page_url`, gap flags, the
+/// `page_numbers` collection, and the backwards-compatible `page_links` JSON
+/// array.
+#[allow(clippy::too_many_arguments)]
+pub fn add_pagination_navigation_to_variables(
+    variables: &mut Variables,
+    base_url: &str,
+    page_num: usize,
+    total_pages: usize,
+    settings: &PaginationSettings,
+) {
+    variables.insert("page_number".to_string(), page_num.to_string());
+    variables.insert("total_pages".to_string(), total_pages.to_string());
+
+    let has_previous = page_num > 1;
+    let has_next = page_num < total_pages;
+    variables.insert("has_previous".to_string(), has_previous.to_string());
+    variables.insert("has_next".to_string(), has_next.to_string());
+
+    if has_previous {
+        let prev_page = page_num - 1;
+        let prev_url = pagination_page_url(base_url, settings.pagination_path, prev_page, settings.index_first_page);
+        variables.insert("previous_page_number".to_string(), prev_page.to_string());
+        variables.insert("previous_page_url".to_string(), prev_url.clone());
+        variables.insert(
+            "rel_prev_link".to_string(),
+            format!("<link rel=\"prev\" href=\"{prev_url}\">"),
+        );
+    }
+    if has_next {
+        let next_page = page_num + 1;
+        let next_url = pagination_page_url(base_url, settings.pagination_path, next_page, settings.index_first_page);
+        variables.insert("next_page_number".to_string(), next_page.to_string());
+        variables.insert("next_page_url".to_string(), next_url.clone());
+        variables.insert(
+            "rel_next_link".to_string(),
+            format!("<link rel=\"next\" href=\"{next_url}\">"),
+        );
+    }
+
+    variables.insert(
+        "first_page_url".to_string(),
+        pagination_page_url(base_url, settings.pagination_path, 1, settings.index_first_page),
+    );
+    variables.insert(
+        "last_page_url".to_string(),
+        pagination_page_url(base_url, settings.pagination_path, total_pages, settings.index_first_page),
+    );
+
+    let page_list = if settings.windowed {
+        windowed_page_list(page_num, total_pages, settings.window)
+    } else {
+        (1..=total_pages).map(PageListEntry::Page).collect()
+    };
+
+    let current_index = page_list
+        .iter()
+        .position(|entry| matches!(entry, PageListEntry::Page(n) if *n == page_num));
+    let has_gap_before = current_index
+        .is_some_and(|idx| page_list[..idx].iter().any(|entry| matches!(entry, PageListEntry::Gap)));
+    let has_gap_after = current_index
+        .is_some_and(|idx| page_list[idx + 1..].iter().any(|entry| matches!(entry, PageListEntry::Gap)));
+    variables.insert("has_gap_before".to_string(), has_gap_before.to_string());
+    variables.insert("has_gap_after".to_string(), has_gap_after.to_string());
+
+    add_page_links_collection_to_variables(variables, "page_numbers", page_num, &page_list, base_url, settings);
+
+    let mut page_links = Vec::new();
+    for entry in &page_list {
+        page_links.push(match entry {
+            PageListEntry::Page(i) => format!(
+                "{{\"number\": {i}, \"url\": \"{}\", \"current\": {}, \"is_gap\": false}}",
+                pagination_page_url(base_url, settings.pagination_path, *i, settings.index_first_page),
+                if *i == page_num { "true" } else { "false" }
+            ),
+            PageListEntry::Gap => {
+                "{\"number\": null, \"url\": \"\", \"current\": false, \"is_gap\": true}".to_string()
+            }
+        });
+    }
+    variables.insert("page_links".to_string(), format!("[{}]", page_links.join(", ")));
+}
+
+/// Adds the `{collection_name}.N.*` variables a template iterates over to
+/// render a page-number list, including gap placeholders.
+fn add_page_links_collection_to_variables(
+    variables: &mut Variables,
+    collection_name: &str,
+    current_page: usize,
+    page_list: &[PageListEntry],
+    base_url: &str,
+    settings: &PaginationSettings,
+) {
+    for (index, entry) in page_list.iter().enumerate() {
+        let (number, url, is_gap) = match entry {
+            PageListEntry::Page(page_num) => (
+                page_num.to_string(),
+                pagination_page_url(base_url, settings.pagination_path, *page_num, settings.index_first_page),
+                false,
+            ),
+            PageListEntry::Gap => (String::new(), String::new(), true),
+        };
+        variables.insert(format!("{collection_name}.{index}.number"), number);
+        variables.insert(format!("{collection_name}.{index}.url"), url);
+        variables.insert(
+            format!("{collection_name}.{index}.current"),
+            matches!(entry, PageListEntry::Page(page_num) if *page_num == current_page).to_string(),
+        );
+        variables.insert(format!("{collection_name}.{index}.is_gap"), is_gap.to_string());
+    }
+}
+
+/// Adds a post collection to variables for template access, flattening each
+/// post's fields under `{collection_name}.{index}.{field}`.
+pub fn add_posts_collection_to_variables(variables: &mut Variables, collection_name: &str, posts: &[ContentItem]) {
+    for (index, post) in posts.iter().enumerate() {
+        for (key, value) in post {
+            let variable_name = format!("{collection_name}.{index}.{key}");
+            variables.insert(variable_name, value.clone());
+        }
+    }
+}
+
+/// Accumulates the absolute URL of every pagination page written during a
+/// build - the main post feed's pager plus every category/taxonomy term's -
+/// so a sitemap writer can list pages that otherwise live in no front-matter
+/// content collection and would be invisible to crawlers.
+#[derive(Debug, Default)]
+pub struct SitemapSink {
+    urls: Vec<String>,
+}
+
+impl SitemapSink {
+    /// Records one pager page's absolute URL.
+    pub fn record(&mut self, url: String) {
+        self.urls.push(url);
+    }
+
+    /// The URLs recorded so far, in the order pages were generated.
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page_numbers(entries: &[PageListEntry]) -> Vec<Option<usize>> {
+        entries
+            .iter()
+            .map(|entry| match entry {
+                PageListEntry::Page(n) => Some(*n),
+                PageListEntry::Gap => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_windowed_page_list_shows_first_last_and_window_with_gaps() {
+        // 1 . 4 5 [6] 7 8 . 42
+        let entries = windowed_page_list(6, 42, 2);
+        assert_eq!(
+            page_numbers(&entries),
+            vec![Some(1), None, Some(4), Some(5), Some(6), Some(7), Some(8), None, Some(42)]
+        );
+    }
+
+    #[test]
+    fn test_windowed_page_list_has_no_gaps_when_window_covers_everything() {
+        let entries = windowed_page_list(2, 4, 2);
+        assert_eq!(page_numbers(&entries), vec![Some(1), Some(2), Some(3), Some(4)]);
+    }
+
+    #[test]
+    fn test_pagination_page_url_defaults_to_nested_path() {
+        assert_eq!(pagination_page_url("", "page", 2, false), "/page/2/");
+        assert_eq!(pagination_page_url("/category/travel", "page", 2, false), "/category/travel/page/2/");
+    }
+
+    #[test]
+    fn test_pagination_page_url_indexes_first_page_when_opted_in() {
+        assert_eq!(pagination_page_url("", "page", 1, true), "/");
+        assert_eq!(pagination_page_url("/category/travel", "page", 1, true), "/category/travel/");
+    }
+
+    #[test]
+    fn test_language_prefix_is_none_when_unset_or_empty() {
+        let variables = Variables::new();
+        assert_eq!(language_prefix(&variables), None);
+
+        let mut variables = Variables::new();
+        variables.insert("lang".to_string(), String::new());
+        assert_eq!(language_prefix(&variables), None);
+    }
+
+    #[test]
+    fn test_language_prefix_returns_the_configured_language() {
+        let mut variables = Variables::new();
+        variables.insert("lang".to_string(), "fr".to_string());
+        assert_eq!(language_prefix(&variables), Some("fr"));
+    }
+
+    #[test]
+    fn test_resolve_posts_per_page_falls_back_to_default_when_unset() {
+        let variables = Variables::new();
+        assert_eq!(resolve_posts_per_page(5, &variables), 5);
+    }
+
+    #[test]
+    fn test_resolve_posts_per_page_honors_override() {
+        let mut variables = Variables::new();
+        variables.insert("pagination_posts_per_page".to_string(), "10".to_string());
+        assert_eq!(resolve_posts_per_page(5, &variables), 10);
+    }
+
+    #[test]
+    fn test_resolve_posts_per_page_ignores_invalid_override() {
+        let mut variables = Variables::new();
+        variables.insert("pagination_posts_per_page".to_string(), "0".to_string());
+        assert_eq!(resolve_posts_per_page(5, &variables), 5);
+
+        variables.insert("pagination_posts_per_page".to_string(), "not-a-number".to_string());
+        assert_eq!(resolve_posts_per_page(5, &variables), 5);
+    }
+
+    #[test]
+    fn test_chunk_posts_for_pagination_splits_evenly_with_remainder() {
+        let posts: Vec<ContentItem> = (0..7).map(|_| std::collections::HashMap::new()).collect();
+        let chunks = chunk_posts_for_pagination(3, &posts);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].2.len(), 3);
+        assert_eq!(chunks[1].2.len(), 3);
+        assert_eq!(chunks[2].2.len(), 1);
+        assert_eq!(chunks[2].1, 3); // total_pages reported on every entry
+    }
+
+    #[test]
+    fn test_sitemap_sink_records_urls_in_order() {
+        let mut sitemap = SitemapSink::default();
+        sitemap.record("/page/1/".to_string());
+        sitemap.record("/category/travel/page/1/".to_string());
+        assert_eq!(sitemap.urls(), ["/page/1/", "/category/travel/page/1/"]);
+    }
+
+    #[test]
+    fn test_sitemap_sink_starts_empty() {
+        assert!(SitemapSink::default().urls().is_empty());
+    }
+
+    fn post_with(fields: &[(&str, &str)]) -> ContentItem {
+        fields
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_posts_by_mode_date_sorts_descending() {
+        let mut posts = vec![
+            post_with(&[("title", "old"), ("date", "2024-01-01")]),
+            post_with(&[("title", "new"), ("date", "2024-06-01")]),
+            post_with(&[("title", "mid"), ("date", "2024-03-01")]),
+        ];
+        sort_posts_by_mode(&mut posts, SortMode::Date);
+        let titles: Vec<&str> = posts.iter().map(|p| p["title"].as_str()).collect();
+        assert_eq!(titles, vec!["new", "mid", "old"]);
+    }
+
+    #[test]
+    fn test_sort_posts_by_mode_date_keeps_relative_order_for_missing_dates() {
+        let mut posts = vec![
+            post_with(&[("title", "a")]),
+            post_with(&[("title", "b"), ("date", "2024-01-01")]),
+            post_with(&[("title", "c")]),
+        ];
+        sort_posts_by_mode(&mut posts, SortMode::Date);
+        let titles: Vec<&str> = posts.iter().map(|p| p["title"].as_str()).collect();
+        assert_eq!(titles, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_sort_posts_by_mode_order_sorts_ascending() {
+        let mut posts = vec![
+            post_with(&[("title", "third"), ("order", "3")]),
+            post_with(&[("title", "first"), ("order", "1")]),
+            post_with(&[("title", "second"), ("order", "2")]),
+        ];
+        sort_posts_by_mode(&mut posts, SortMode::Order);
+        let titles: Vec<&str> = posts.iter().map(|p| p["title"].as_str()).collect();
+        assert_eq!(titles, vec!["first", "second", "third"]);
+    }
+
+    #[test]
+    fn test_add_pagination_navigation_emits_rel_links_for_middle_page() {
+        let global_variables = Variables::new();
+        let settings = PaginationSettings::from_variables(&global_variables, DEFAULT_PAGINATION_PATH, false);
+        let mut variables = Variables::new();
+        add_pagination_navigation_to_variables(&mut variables, "/category/travel", 2, 3, &settings);
+
+        assert_eq!(
+            variables.get("rel_prev_link").unwrap(),
+            "<link rel=\"prev\" href=\"/category/travel/page/1/\">"
+        );
+        assert_eq!(
+            variables.get("rel_next_link").unwrap(),
+            "<link rel=\"next\" href=\"/category/travel/page/3/\">"
+        );
+    }
+
+    #[test]
+    fn test_add_pagination_navigation_omits_rel_links_at_the_ends() {
+        let global_variables = Variables::new();
+        let settings = PaginationSettings::from_variables(&global_variables, DEFAULT_PAGINATION_PATH, false);
+        let mut variables = Variables::new();
+        add_pagination_navigation_to_variables(&mut variables, "/category/travel", 1, 3, &settings);
+
+        assert!(variables.get("rel_prev_link").is_none());
+        assert!(variables.get("rel_next_link").is_some());
+    }
+
+    #[test]
+    fn test_sort_posts_by_mode_none_preserves_input_order() {
+        let mut posts = vec![
+            post_with(&[("title", "b"), ("date", "2024-06-01")]),
+            post_with(&[("title", "a"), ("date", "2024-01-01")]),
+        ];
+        sort_posts_by_mode(&mut posts, SortMode::None);
+        let titles: Vec<&str> = posts.iter().map(|p| p["title"].as_str()).collect();
+        assert_eq!(titles, vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_sort_posts_by_post_sort_order_date_desc_sorts_newest_first() {
+        let mut posts = vec![
+            post_with(&[("title", "old"), ("date", "2024-01-01")]),
+            post_with(&[("title", "new"), ("date", "2024-06-01")]),
+            post_with(&[("title", "mid"), ("date", "2024-03-01")]),
+        ];
+        sort_posts_by_post_sort_order(&mut posts, PostSortOrder::DateDesc);
+        let titles: Vec<&str> = posts.iter().map(|p| p["title"].as_str()).collect();
+        assert_eq!(titles, vec!["new", "mid", "old"]);
+    }
+
+    #[test]
+    fn test_sort_posts_by_post_sort_order_date_asc_sorts_oldest_first() {
+        let mut posts = vec![
+            post_with(&[("title", "new"), ("date", "2024-06-01")]),
+            post_with(&[("title", "old"), ("date", "2024-01-01")]),
+            post_with(&[("title", "mid"), ("date", "2024-03-01")]),
+        ];
+        sort_posts_by_post_sort_order(&mut posts, PostSortOrder::DateAsc);
+        let titles: Vec<&str> = posts.iter().map(|p| p["title"].as_str()).collect();
+        assert_eq!(titles, vec!["old", "mid", "new"]);
+    }
+
+    #[test]
+    fn test_sort_posts_by_post_sort_order_date_desc_sorts_missing_dates_last() {
+        let mut posts = vec![
+            post_with(&[("title", "no-date")]),
+            post_with(&[("title", "new"), ("date", "2024-06-01")]),
+            post_with(&[("title", "old"), ("date", "2024-01-01")]),
+        ];
+        sort_posts_by_post_sort_order(&mut posts, PostSortOrder::DateDesc);
+        let titles: Vec<&str> = posts.iter().map(|p| p["title"].as_str()).collect();
+        assert_eq!(titles, vec!["new", "old", "no-date"]);
+    }
+
+    #[test]
+    fn test_sort_posts_by_post_sort_order_order_sorts_ascending_with_missing_last() {
+        let mut posts = vec![
+            post_with(&[("title", "no-order")]),
+            post_with(&[("title", "third"), ("order", "3")]),
+            post_with(&[("title", "first"), ("order", "1")]),
+            post_with(&[("title", "second"), ("order", "2")]),
+        ];
+        sort_posts_by_post_sort_order(&mut posts, PostSortOrder::Order);
+        let titles: Vec<&str> = posts.iter().map(|p| p["title"].as_str()).collect();
+        assert_eq!(titles, vec!["first", "second", "third", "no-order"]);
+    }
+
+    #[test]
+    fn test_sort_posts_by_post_sort_order_none_preserves_input_order() {
+        let mut posts = vec![
+            post_with(&[("title", "b"), ("date", "2024-06-01")]),
+            post_with(&[("title", "a"), ("date", "2024-01-01")]),
+        ];
+        sort_posts_by_post_sort_order(&mut posts, PostSortOrder::None);
+        let titles: Vec<&str> = posts.iter().map(|p| p["title"].as_str()).collect();
+        assert_eq!(titles, vec!["b", "a"]);
+    }
+}
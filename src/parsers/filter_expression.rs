@@ -0,0 +1,471 @@
+//! Small `where`-style filter expression language for selecting elements
+//! out of a JSON array, e.g. `role = "admin" AND active = true` or
+//! `age 18 TO 30`. A recursive-descent parser builds an [`Expr`] tree from
+//! the expression string, `NOT` binding tighter than `AND`, which in turn
+//! binds tighter than `OR` - the usual precedence order. `AND`, `OR`,
+//! `NOT`, `IN`, and `TO` are recognized only in upper case, matching the
+//! compact subset this is meant to cover.
+//!
+//! Not yet wired up as a Liquid filter: like
+//! [`super::json_path`], this evaluates against a `JsonValue` array, but
+//! `load_site_data` only ever retains the already-flattened `data.*`
+//! variables (see [`crate::load_data::flatten_json_value`]), not the
+//! original tree. [`crate::load_data::apply_where_filter`] shows how this
+//! evaluator re-flattens its surviving matches once a caller does have the
+//! source array in hand.
+
+use super::json::JsonValue;
+
+#[derive(Debug, Clone, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Comparison {
+        field: String,
+        op: CompareOp,
+        value: Literal,
+    },
+    In {
+        field: String,
+        values: Vec<Literal>,
+    },
+    Between {
+        field: String,
+        low: Literal,
+        high: Literal,
+    },
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+/// Filters `items`, keeping only the elements for which `expression`
+/// evaluates true. An empty (or all-whitespace) expression is a no-op that
+/// returns every element.
+pub fn filter_array<'a>(items: &'a [JsonValue], expression: &str) -> Result<Vec<&'a JsonValue>, String> {
+    match parse_where_expression(expression)? {
+        None => Ok(items.iter().collect()),
+        Some(expr) => Ok(items.iter().filter(|item| evaluate(&expr, item)).collect()),
+    }
+}
+
+fn parse_where_expression(expression: &str) -> Result<Option<Expr>, String> {
+    if expression.trim().is_empty() {
+        return Ok(None);
+    }
+    let tokens = tokenize(expression)?;
+    let mut pos = 0;
+    let expr = parse_or(&tokens, &mut pos)?;
+    if pos != tokens.len() {
+        return Err("Unexpected trailing tokens in filter expression".to_string());
+    }
+    Ok(Some(expr))
+}
+
+/// Evaluates `expr` against `element`. A field that doesn't resolve to a
+/// value anywhere in `element` makes every comparison involving it false,
+/// rather than erroring.
+fn evaluate(expr: &Expr, element: &JsonValue) -> bool {
+    match expr {
+        Expr::Comparison { field, op, value } => match lookup_dotted(element, field) {
+            Some(field_value) => compare(field_value, value, op),
+            None => false,
+        },
+        Expr::In { field, values } => match lookup_dotted(element, field) {
+            Some(field_value) => values.iter().any(|value| compare(field_value, value, &CompareOp::Eq)),
+            None => false,
+        },
+        Expr::Between { field, low, high } => match lookup_dotted(element, field) {
+            Some(field_value) => {
+                compare(field_value, low, &CompareOp::Ge) && compare(field_value, high, &CompareOp::Le)
+            }
+            None => false,
+        },
+        Expr::And(left, right) => evaluate(left, element) && evaluate(right, element),
+        Expr::Or(left, right) => evaluate(left, element) || evaluate(right, element),
+        Expr::Not(inner) => !evaluate(inner, element),
+    }
+}
+
+/// Resolves a dotted field path (`"author.name"`) against `value`, walking
+/// object keys one segment at a time.
+fn lookup_dotted<'a>(value: &'a JsonValue, path: &str) -> Option<&'a JsonValue> {
+    let mut current = value;
+    for segment in path.split('.') {
+        current = match current {
+            JsonValue::Object(map) => map.get(segment)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+/// Compares `field_value` against `literal`. Numeric comparison is used
+/// only when both sides parse as numbers; otherwise both are coerced to
+/// their string form and compared lexicographically.
+fn compare(field_value: &JsonValue, literal: &Literal, op: &CompareOp) -> bool {
+    if let (Some(field_number), Some(literal_number)) = (as_number(field_value), as_number_literal(literal)) {
+        return compare_ordering(field_number.total_cmp(&literal_number), op);
+    }
+    compare_ordering(display_string(field_value).cmp(&display_string_literal(literal)), op)
+}
+
+fn as_number(value: &JsonValue) -> Option<f64> {
+    match value {
+        JsonValue::Integer(n) => Some(*n as f64),
+        JsonValue::Float(n) => Some(*n),
+        JsonValue::String(s) => s.parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+fn as_number_literal(literal: &Literal) -> Option<f64> {
+    match literal {
+        Literal::Num(n) => Some(*n),
+        Literal::Str(s) => s.parse::<f64>().ok(),
+        Literal::Bool(_) => None,
+    }
+}
+
+fn display_string(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Integer(n) => n.to_string(),
+        JsonValue::Float(n) => n.to_string(),
+        JsonValue::Bool(b) => b.to_string(),
+        JsonValue::Null => String::new(),
+        JsonValue::Array(_) | JsonValue::Object(_) => value.to_string(),
+    }
+}
+
+fn display_string_literal(literal: &Literal) -> String {
+    match literal {
+        Literal::Str(s) => s.clone(),
+        Literal::Num(n) => n.to_string(),
+        Literal::Bool(b) => b.to_string(),
+    }
+}
+
+fn compare_ordering(ordering: std::cmp::Ordering, op: &CompareOp) -> bool {
+    use std::cmp::Ordering::{Equal, Greater, Less};
+    matches!(
+        (ordering, op),
+        (Equal, CompareOp::Eq | CompareOp::Le | CompareOp::Ge)
+            | (Less, CompareOp::Lt | CompareOp::Le | CompareOp::Ne)
+            | (Greater, CompareOp::Gt | CompareOp::Ge | CompareOp::Ne)
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Field(String),
+    Op(CompareOp),
+    Lit(Literal),
+    And,
+    Or,
+    Not,
+    In,
+    To,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut pos = 0;
+    let mut tokens = Vec::new();
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        match c {
+            ' ' | '\t' | '\n' | '\r' => pos += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                pos += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                pos += 1;
+            }
+            '[' => {
+                tokens.push(Token::LBracket);
+                pos += 1;
+            }
+            ']' => {
+                tokens.push(Token::RBracket);
+                pos += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                pos += 1;
+            }
+            '=' => {
+                tokens.push(Token::Op(CompareOp::Eq));
+                pos += 1;
+            }
+            '!' if chars.get(pos + 1) == Some(&'=') => {
+                tokens.push(Token::Op(CompareOp::Ne));
+                pos += 2;
+            }
+            '<' if chars.get(pos + 1) == Some(&'=') => {
+                tokens.push(Token::Op(CompareOp::Le));
+                pos += 2;
+            }
+            '<' => {
+                tokens.push(Token::Op(CompareOp::Lt));
+                pos += 1;
+            }
+            '>' if chars.get(pos + 1) == Some(&'=') => {
+                tokens.push(Token::Op(CompareOp::Ge));
+                pos += 2;
+            }
+            '>' => {
+                tokens.push(Token::Op(CompareOp::Gt));
+                pos += 1;
+            }
+            '\'' | '"' => {
+                let quote = c;
+                pos += 1;
+                let start = pos;
+                while chars.get(pos) != Some(&quote) {
+                    if pos >= chars.len() {
+                        return Err("Unterminated string literal in filter expression".to_string());
+                    }
+                    pos += 1;
+                }
+                let value: String = chars[start..pos].iter().collect();
+                pos += 1; // closing quote
+                tokens.push(Token::Lit(Literal::Str(value)));
+            }
+            c if c.is_ascii_digit() || (c == '-' && chars.get(pos + 1).is_some_and(char::is_ascii_digit)) => {
+                let start = pos;
+                pos += 1;
+                while chars.get(pos).is_some_and(|c| c.is_ascii_digit() || *c == '.') {
+                    pos += 1;
+                }
+                let text: String = chars[start..pos].iter().collect();
+                let number = text
+                    .parse::<f64>()
+                    .map_err(|_| format!("Invalid number in filter expression: {text}"))?;
+                tokens.push(Token::Lit(Literal::Num(number)));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = pos;
+                while chars.get(pos).is_some_and(|c| c.is_alphanumeric() || *c == '_' || *c == '.') {
+                    pos += 1;
+                }
+                let word: String = chars[start..pos].iter().collect();
+                tokens.push(match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    "IN" => Token::In,
+                    "TO" => Token::To,
+                    "true" => Token::Lit(Literal::Bool(true)),
+                    "false" => Token::Lit(Literal::Bool(false)),
+                    _ => Token::Field(word),
+                });
+            }
+            c => return Err(format!("Unexpected character in filter expression: {c}")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_or(tokens: &[Token], pos: &mut usize) -> Result<Expr, String> {
+    let mut expr = parse_and(tokens, pos)?;
+    while tokens.get(*pos) == Some(&Token::Or) {
+        *pos += 1;
+        let right = parse_and(tokens, pos)?;
+        expr = Expr::Or(Box::new(expr), Box::new(right));
+    }
+    Ok(expr)
+}
+
+fn parse_and(tokens: &[Token], pos: &mut usize) -> Result<Expr, String> {
+    let mut expr = parse_not(tokens, pos)?;
+    while tokens.get(*pos) == Some(&Token::And) {
+        *pos += 1;
+        let right = parse_not(tokens, pos)?;
+        expr = Expr::And(Box::new(expr), Box::new(right));
+    }
+    Ok(expr)
+}
+
+fn parse_not(tokens: &[Token], pos: &mut usize) -> Result<Expr, String> {
+    if tokens.get(*pos) == Some(&Token::Not) {
+        *pos += 1;
+        return Ok(Expr::Not(Box::new(parse_not(tokens, pos)?)));
+    }
+    parse_atom(tokens, pos)
+}
+
+fn parse_atom(tokens: &[Token], pos: &mut usize) -> Result<Expr, String> {
+    if tokens.get(*pos) == Some(&Token::LParen) {
+        *pos += 1;
+        let expr = parse_or(tokens, pos)?;
+        expect(tokens, pos, &Token::RParen)?;
+        return Ok(expr);
+    }
+    parse_predicate(tokens, pos)
+}
+
+fn parse_predicate(tokens: &[Token], pos: &mut usize) -> Result<Expr, String> {
+    let field = match tokens.get(*pos) {
+        Some(Token::Field(name)) => name.clone(),
+        _ => return Err("Expected a field name in filter expression".to_string()),
+    };
+    *pos += 1;
+
+    match tokens.get(*pos) {
+        Some(Token::Op(op)) => {
+            let op = op.clone();
+            *pos += 1;
+            let value = parse_literal(tokens, pos)?;
+            Ok(Expr::Comparison { field, op, value })
+        }
+        Some(Token::In) => {
+            *pos += 1;
+            expect(tokens, pos, &Token::LBracket)?;
+            let mut values = vec![parse_literal(tokens, pos)?];
+            while tokens.get(*pos) == Some(&Token::Comma) {
+                *pos += 1;
+                values.push(parse_literal(tokens, pos)?);
+            }
+            expect(tokens, pos, &Token::RBracket)?;
+            Ok(Expr::In { field, values })
+        }
+        _ => {
+            let low = parse_literal(tokens, pos)?;
+            expect(tokens, pos, &Token::To)?;
+            let high = parse_literal(tokens, pos)?;
+            Ok(Expr::Between { field, low, high })
+        }
+    }
+}
+
+fn parse_literal(tokens: &[Token], pos: &mut usize) -> Result<Literal, String> {
+    match tokens.get(*pos) {
+        Some(Token::Lit(literal)) => {
+            *pos += 1;
+            Ok(literal.clone())
+        }
+        _ => Err("Expected a literal value in filter expression".to_string()),
+    }
+}
+
+fn expect(tokens: &[Token], pos: &mut usize, expected: &Token) -> Result<(), String> {
+    if tokens.get(*pos) != Some(expected) {
+        return Err(format!("Expected {expected:?} in filter expression"));
+    }
+    *pos += 1;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::parse_json;
+
+    fn items_from(json: &str) -> JsonValue {
+        parse_json(json).unwrap()
+    }
+
+    #[test]
+    fn test_empty_expression_is_a_no_op() {
+        let value = items_from(r#"[{"role":"admin"},{"role":"editor"}]"#);
+        let JsonValue::Array(items) = &value else { panic!("expected array") };
+        let result = filter_array(items, "").unwrap();
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn test_simple_equality_comparison() {
+        let value = items_from(r#"[{"role":"admin"},{"role":"editor"}]"#);
+        let JsonValue::Array(items) = &value else { panic!("expected array") };
+        let result = filter_array(items, r#"role = "admin""#).unwrap();
+        assert_eq!(result, vec![&items[0]]);
+    }
+
+    #[test]
+    fn test_and_or_not_precedence() {
+        let value = items_from(
+            r#"[{"role":"admin","active":true},{"role":"admin","active":false},{"role":"editor","active":true}]"#,
+        );
+        let JsonValue::Array(items) = &value else { panic!("expected array") };
+        let result = filter_array(items, r#"role = "admin" AND active = true"#).unwrap();
+        assert_eq!(result, vec![&items[0]]);
+
+        let result = filter_array(items, r#"NOT role = "admin" OR NOT active = true"#).unwrap();
+        assert_eq!(result, vec![&items[1], &items[2]]);
+    }
+
+    #[test]
+    fn test_parentheses_override_precedence() {
+        let value = items_from(
+            r#"[{"role":"admin","active":true},{"role":"admin","active":false},{"role":"editor","active":false}]"#,
+        );
+        let JsonValue::Array(items) = &value else { panic!("expected array") };
+        let result = filter_array(items, r#"role = "admin" AND (active = true OR active = false)"#).unwrap();
+        assert_eq!(result, vec![&items[0], &items[1]]);
+    }
+
+    #[test]
+    fn test_in_list() {
+        let value = items_from(r#"[{"role":"admin"},{"role":"editor"},{"role":"viewer"}]"#);
+        let JsonValue::Array(items) = &value else { panic!("expected array") };
+        let result = filter_array(items, r#"role IN ["admin", "viewer"]"#).unwrap();
+        assert_eq!(result, vec![&items[0], &items[2]]);
+    }
+
+    #[test]
+    fn test_to_range() {
+        let value = items_from(r#"[{"age":17},{"age":25},{"age":40}]"#);
+        let JsonValue::Array(items) = &value else { panic!("expected array") };
+        let result = filter_array(items, "age 18 TO 30").unwrap();
+        assert_eq!(result, vec![&items[1]]);
+    }
+
+    #[test]
+    fn test_missing_field_evaluates_false() {
+        let value = items_from(r#"[{"role":"admin"},{}]"#);
+        let JsonValue::Array(items) = &value else { panic!("expected array") };
+        let result = filter_array(items, r#"role = "admin""#).unwrap();
+        assert_eq!(result, vec![&items[0]]);
+    }
+
+    #[test]
+    fn test_non_numeric_comparison_falls_back_to_string_ordering() {
+        let value = items_from(r#"[{"category":"apple"},{"category":"banana"}]"#);
+        let JsonValue::Array(items) = &value else { panic!("expected array") };
+        let result = filter_array(items, r#"category > "apple""#).unwrap();
+        assert_eq!(result, vec![&items[1]]);
+    }
+
+    #[test]
+    fn test_dotted_field_path() {
+        let value = items_from(r#"[{"author":{"name":"Ada"}},{"author":{"name":"Grace"}}]"#);
+        let JsonValue::Array(items) = &value else { panic!("expected array") };
+        let result = filter_array(items, r#"author.name = "Ada""#).unwrap();
+        assert_eq!(result, vec![&items[0]]);
+    }
+}
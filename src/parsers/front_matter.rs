@@ -0,0 +1,213 @@
+use crate::types::ContentItem;
+
+const YAML_FENCE: &str = "---";
+const TOML_FENCE: &str = "+++";
+
+/// Splits a content file into front matter and body, flattening the front matter
+/// into a `ContentItem` and storing the remaining body text under a `content` key.
+///
+/// Two front matter formats are supported, auto-detected from the opening fence on
+/// the first line:
+/// - YAML, delimited by `---`, parsed as flat `key: value` pairs.
+/// - TOML, delimited by `+++` (as used by Zola), with `[section]` tables flattened
+///   into the map using dot notation (e.g. `[extra]` + `author = "x"` becomes the
+///   key `extra.author`).
+///
+/// Files that open with neither fence are treated as plain body content. A parse
+/// error is returned if a front matter block is malformed, e.g. unterminated, or
+/// (for TOML) containing a line that isn't a table header or a `key = value` pair.
+pub fn parse_content_with_front_matter(input: &str) -> Result<ContentItem, String> {
+    if let Some(rest) = input.strip_prefix(TOML_FENCE).and_then(after_opening_fence) {
+        let (front_matter, body) = split_front_matter(rest, TOML_FENCE)
+            .ok_or_else(|| format!("unterminated TOML front matter (missing closing `{TOML_FENCE}`)"))?;
+        let mut content = parse_toml_front_matter(front_matter)?;
+        content.insert("content".to_string(), body.to_string());
+        return Ok(content);
+    }
+
+    if let Some(rest) = input.strip_prefix(YAML_FENCE).and_then(after_opening_fence) {
+        let (front_matter, body) = split_front_matter(rest, YAML_FENCE)
+            .ok_or_else(|| format!("unterminated YAML front matter (missing closing `{YAML_FENCE}`)"))?;
+        let mut content = parse_yaml_front_matter(front_matter);
+        content.insert("content".to_string(), body.to_string());
+        return Ok(content);
+    }
+
+    let mut content = ContentItem::new();
+    content.insert("content".to_string(), input.to_string());
+    Ok(content)
+}
+
+/// Requires the opening fence to be immediately followed by a newline (or end of
+/// input), returning whatever comes after that newline.
+fn after_opening_fence(rest: &str) -> Option<&str> {
+    rest.strip_prefix('\n').or_else(|| rest.is_empty().then_some(rest))
+}
+
+/// Finds the closing fence line and splits `rest` into the front matter text before
+/// it and the body text after it. The closing fence must be alone on its line and
+/// must itself be followed by a newline or the end of input.
+fn split_front_matter<'a>(rest: &'a str, fence: &str) -> Option<(&'a str, &'a str)> {
+    let mut search_from = 0;
+    loop {
+        let found_at = rest[search_from..].find(fence)? + search_from;
+        let at_line_start = found_at == 0 || rest.as_bytes()[found_at - 1] == b'\n';
+        if at_line_start {
+            let after_fence = &rest[found_at + fence.len()..];
+            if let Some(body) = after_fence.strip_prefix('\n').or_else(|| after_fence.is_empty().then_some(after_fence)) {
+                let front_matter = if found_at == 0 { "" } else { &rest[..found_at - 1] };
+                return Some((front_matter, body));
+            }
+        }
+        search_from = found_at + fence.len();
+    }
+}
+
+/// Parses flat `key: value` lines, ignoring blank lines and `#` comments.
+fn parse_yaml_front_matter(front_matter: &str) -> ContentItem {
+    let mut map = ContentItem::new();
+    for line in front_matter.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let key = key.trim();
+            if !key.is_empty() {
+                map.insert(key.to_string(), strip_matching_quotes(value.trim()).to_string());
+            }
+        }
+    }
+    map
+}
+
+/// Parses `key = value` lines and `[section]` table headers, flattening tables into
+/// the map with dot notation.
+fn parse_toml_front_matter(front_matter: &str) -> Result<ContentItem, String> {
+    let mut map = ContentItem::new();
+    let mut section_prefix = String::new();
+
+    for (zero_based_line, raw_line) in front_matter.lines().enumerate() {
+        let line_number = zero_based_line + 1;
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            let section = section.trim();
+            if section.is_empty() {
+                return Err(format!("empty TOML table header on line {line_number}"));
+            }
+            section_prefix = format!("{section}.");
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!(
+                "expected `key = value` or `[section]` in TOML front matter on line {line_number}"
+            ));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("empty TOML key on line {line_number}"));
+        }
+        let value = value.trim();
+        let parsed_value = parse_toml_scalar(value)
+            .ok_or_else(|| format!("unsupported TOML value on line {line_number}: `{value}`"))?;
+
+        map.insert(format!("{section_prefix}{key}"), parsed_value);
+    }
+
+    Ok(map)
+}
+
+/// Parses a TOML scalar value: a quoted string, a boolean, or a number. Other
+/// value forms (arrays, inline tables, dates) aren't needed for front matter here.
+fn parse_toml_scalar(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    if bytes.len() >= 2 && bytes[0] == b'"' && bytes[bytes.len() - 1] == b'"' {
+        return Some(value[1..value.len() - 1].to_string());
+    }
+    if value == "true" || value == "false" {
+        return Some(value.to_string());
+    }
+    if value.parse::<f64>().is_ok() {
+        return Some(value.to_string());
+    }
+    None
+}
+
+fn strip_matching_quotes(value: &str) -> &str {
+    let bytes = value.as_bytes();
+    if bytes.len() >= 2
+        && ((bytes[0] == b'"' && bytes[bytes.len() - 1] == b'"')
+            || (bytes[0] == b'\'' && bytes[bytes.len() - 1] == b'\''))
+    {
+        return &value[1..value.len() - 1];
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_content_with_front_matter;
+
+    #[test]
+    fn parses_yaml_front_matter() {
+        let input = "---\ntitle: My Post\ndate: 2021-07-19\n---\nHello, world!";
+        let parsed = parse_content_with_front_matter(input).unwrap();
+        assert_eq!(parsed.get("title"), Some(&"My Post".to_string()));
+        assert_eq!(parsed.get("date"), Some(&"2021-07-19".to_string()));
+        assert_eq!(parsed.get("content"), Some(&"Hello, world!".to_string()));
+    }
+
+    #[test]
+    fn parses_toml_front_matter_with_flattened_table() {
+        let input = "+++\ntitle = \"My Post\"\n\n[extra]\nauthor = \"x\"\n+++\nHello, world!";
+        let parsed = parse_content_with_front_matter(input).unwrap();
+        assert_eq!(parsed.get("title"), Some(&"My Post".to_string()));
+        assert_eq!(parsed.get("extra.author"), Some(&"x".to_string()));
+        assert_eq!(parsed.get("content"), Some(&"Hello, world!".to_string()));
+    }
+
+    #[test]
+    fn plain_content_without_fence_is_kept_as_is() {
+        let input = "Just a plain body with no front matter.";
+        let parsed = parse_content_with_front_matter(input).unwrap();
+        assert_eq!(
+            parsed.get("content"),
+            Some(&"Just a plain body with no front matter.".to_string())
+        );
+        assert_eq!(parsed.len(), 1);
+    }
+
+    #[test]
+    fn unterminated_yaml_front_matter_is_an_error() {
+        let input = "---\ntitle: My Post\nHello, world!";
+        let result = parse_content_with_front_matter(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn unterminated_toml_front_matter_is_an_error() {
+        let input = "+++\ntitle = \"My Post\"\nHello, world!";
+        let result = parse_content_with_front_matter(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn malformed_toml_line_is_an_error() {
+        let input = "+++\nthis is not valid toml\n+++\nbody";
+        let result = parse_content_with_front_matter(input);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn toml_fence_on_its_own_line_requires_trailing_newline() {
+        let input = "+++title = \"x\"\n+++\nbody";
+        let parsed = parse_content_with_front_matter(input).unwrap();
+        // Doesn't start with a bare "+++\n", so it's treated as plain content.
+        assert_eq!(parsed.get("content"), Some(&input.to_string()));
+    }
+}
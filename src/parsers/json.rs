@@ -1,17 +1,186 @@
 use std::collections::HashMap;
+use std::fmt;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum JsonValue {
     String(String),
     Integer(i64),
+    Float(f64),
     Bool(bool),
+    Null,
     Array(Vec<JsonValue>),
     Object(HashMap<String, JsonValue>),
 }
 
+// The inherent `to_string` below intentionally shadows the `Display`-derived
+// one so callers get the exact API `rustc_serialize`'s JSON encoder exposes;
+// both produce identical output since `Display` just delegates to it.
+#[allow(clippy::inherent_to_string_shadow_display)]
+impl JsonValue {
+    /// Serializes this value to compact JSON text, with object keys sorted
+    /// so output is reproducible across builds (the backing store is a
+    /// `HashMap`, which has no stable iteration order of its own).
+    pub fn to_string(&self) -> String {
+        let mut out = String::new();
+        self.write_compact(&mut out);
+        out
+    }
+
+    /// Serializes this value to indented JSON text, using `indent` spaces
+    /// per nesting level.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, indent, 0);
+        out
+    }
+
+    fn write_compact(&self, out: &mut String) {
+        match self {
+            JsonValue::String(s) => write_escaped_string(s, out),
+            JsonValue::Integer(n) => out.push_str(&n.to_string()),
+            JsonValue::Float(n) => out.push_str(&format_float(*n)),
+            JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    item.write_compact(out);
+                }
+                out.push(']');
+            }
+            JsonValue::Object(map) => {
+                out.push('{');
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                for (i, key) in keys.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_escaped_string(key, out);
+                    out.push(':');
+                    map[*key].write_compact(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    fn write_pretty(&self, out: &mut String, indent: usize, depth: usize) {
+        match self {
+            JsonValue::Array(items) if !items.is_empty() => {
+                out.push('[');
+                out.push('\n');
+                for (i, item) in items.iter().enumerate() {
+                    push_indent(out, indent, depth + 1);
+                    item.write_pretty(out, indent, depth + 1);
+                    if i + 1 < items.len() {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                push_indent(out, indent, depth);
+                out.push(']');
+            }
+            JsonValue::Object(map) if !map.is_empty() => {
+                out.push('{');
+                out.push('\n');
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                let len = keys.len();
+                for (i, key) in keys.into_iter().enumerate() {
+                    push_indent(out, indent, depth + 1);
+                    write_escaped_string(key, out);
+                    out.push_str(": ");
+                    map[key].write_pretty(out, indent, depth + 1);
+                    if i + 1 < len {
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                push_indent(out, indent, depth);
+                out.push('}');
+            }
+            // Empty arrays/objects and all scalar values have no nesting to
+            // indent, so pretty-printing falls back to the compact form.
+            _ => self.write_compact(out),
+        }
+    }
+}
+
+fn push_indent(out: &mut String, indent: usize, depth: usize) {
+    for _ in 0..indent * depth {
+        out.push(' ');
+    }
+}
+
+/// Formats a float so it always round-trips through [`parse_json`] and is
+/// visibly distinct from an integer (always has a decimal point or
+/// exponent), matching how JSON is conventionally pretty-printed.
+fn format_float(n: f64) -> String {
+    let formatted = format!("{n}");
+    if formatted.contains('.') || formatted.contains('e') || formatted.contains('E') {
+        formatted
+    } else {
+        format!("{formatted}.0")
+    }
+}
+
+/// Escapes `s` as a JSON string literal, including the surrounding quotes.
+/// This is the reverse of the decoding `parse_string` performs: quotes,
+/// backslashes and control characters are escaped; non-ASCII characters are
+/// passed through as literal UTF-8 rather than `\uXXXX`, since the output is
+/// itself valid UTF-8 JSON text and doesn't need to be restricted to ASCII.
+fn write_escaped_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{0008}' => out.push_str("\\b"),
+            '\u{000C}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string())
+    }
+}
+
+/// A JSON parse error, stamped with the 1-based line/column and the byte
+/// offset into the original input where it was raised.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    pub byte_offset: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 pub struct JsonParser {
     chars: Vec<char>,
     pos: usize,
+    line: usize,
+    column: usize,
+    byte_offset: usize,
 }
 
 #[allow(dead_code)]
@@ -20,19 +189,32 @@ impl JsonParser {
         Self {
             chars: input.chars().collect(),
             pos: 0,
+            line: 1,
+            column: 1,
+            byte_offset: 0,
         }
     }
 
-    pub fn parse(&mut self) -> Result<JsonValue, String> {
+    pub fn parse(&mut self) -> Result<JsonValue, ParseError> {
         self.skip_whitespace();
         self.parse_value()
     }
 
-    fn parse_value(&mut self) -> Result<JsonValue, String> {
+    /// Builds a [`ParseError`] stamped with the parser's current position.
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            line: self.line,
+            column: self.column,
+            byte_offset: self.byte_offset,
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, ParseError> {
         self.skip_whitespace();
 
         if self.pos >= self.chars.len() {
-            return Err("Unexpected end of input".to_string());
+            return Err(self.error("Unexpected end of input"));
         }
 
         match self.current_char() {
@@ -41,13 +223,14 @@ impl JsonParser {
             '{' => self.parse_object(),
             c if c.is_ascii_digit() || c == '-' => self.parse_number(),
             't' | 'f' => self.parse_boolean(),
-            _ => Err(format!("Unexpected character: {}", self.current_char())),
+            'n' => self.parse_null(),
+            _ => Err(self.error(format!("Unexpected character: {}", self.current_char()))),
         }
     }
 
-    fn parse_string(&mut self) -> Result<JsonValue, String> {
+    fn parse_string(&mut self) -> Result<JsonValue, ParseError> {
         if self.current_char() != '"' {
-            return Err("Expected opening quote".to_string());
+            return Err(self.error("Expected opening quote"));
         }
 
         self.advance(); // Skip opening quote
@@ -55,12 +238,11 @@ impl JsonParser {
 
         while self.pos < self.chars.len() && self.current_char() != '"' {
             if self.current_char() == '\\' {
-                value.push(self.current_char()); // Keep the backslash
-                self.advance();
-                if self.pos < self.chars.len() {
-                    value.push(self.current_char()); // Keep the escaped character
-                    self.advance();
+                self.advance(); // Skip backslash
+                if self.pos >= self.chars.len() {
+                    return Err(self.error("Unterminated escape sequence"));
                 }
+                value.push(self.parse_escape()?);
             } else {
                 value.push(self.current_char());
                 self.advance();
@@ -68,7 +250,7 @@ impl JsonParser {
         }
 
         if self.pos >= self.chars.len() {
-            return Err("Unterminated string".to_string());
+            return Err(self.error("Unterminated string"));
         }
 
         self.advance(); // Skip closing quote
@@ -76,8 +258,73 @@ impl JsonParser {
         Ok(JsonValue::String(value))
     }
 
-    fn parse_number(&mut self) -> Result<JsonValue, String> {
+    /// Decodes a single escape sequence, with `self.pos` positioned at the
+    /// character right after the backslash.
+    fn parse_escape(&mut self) -> Result<char, ParseError> {
+        let escape_char = self.current_char();
+        self.advance();
+
+        match escape_char {
+            '"' => Ok('"'),
+            '\\' => Ok('\\'),
+            '/' => Ok('/'),
+            'b' => Ok('\u{0008}'),
+            'f' => Ok('\u{000C}'),
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            't' => Ok('\t'),
+            'u' => self.parse_unicode_escape(),
+            other => Err(self.error(format!("Invalid escape character: \\{other}"))),
+        }
+    }
+
+    /// Decodes a `\uXXXX` escape, combining a high/low surrogate pair into a
+    /// single scalar value when present. `self.pos` must be positioned right
+    /// after the `u`.
+    fn parse_unicode_escape(&mut self) -> Result<char, ParseError> {
+        let code_point = self.parse_hex4()?;
+
+        if (0xD800..=0xDBFF).contains(&code_point) {
+            if self.current_char() != '\\' || self.chars.get(self.pos + 1).copied() != Some('u') {
+                return Err(self.error("Unpaired high surrogate in \\u escape"));
+            }
+            self.advance(); // Skip '\'
+            self.advance(); // Skip 'u'
+            let low = self.parse_hex4()?;
+
+            if !(0xDC00..=0xDFFF).contains(&low) {
+                return Err(self.error("Invalid low surrogate following high surrogate"));
+            }
+
+            let combined = 0x10000 + ((code_point - 0xD800) << 10) + (low - 0xDC00);
+            char::from_u32(combined).ok_or_else(|| self.error("Invalid surrogate pair"))
+        } else if (0xDC00..=0xDFFF).contains(&code_point) {
+            Err(self.error("Unpaired low surrogate in \\u escape"))
+        } else {
+            char::from_u32(code_point)
+                .ok_or_else(|| self.error(format!("Invalid \\u escape: {code_point:x}")))
+        }
+    }
+
+    /// Reads exactly 4 hex digits starting at `self.pos` and returns the
+    /// code point they encode, advancing `self.pos` past them.
+    fn parse_hex4(&mut self) -> Result<u32, ParseError> {
+        if self.pos + 4 > self.chars.len() {
+            return Err(self.error("Incomplete \\u escape"));
+        }
+
+        let hex: String = self.chars[self.pos..self.pos + 4].iter().collect();
+        let code_point = u32::from_str_radix(&hex, 16)
+            .map_err(|_| self.error(format!("Invalid \\u escape: {hex}")))?;
+        for _ in 0..4 {
+            self.advance();
+        }
+        Ok(code_point)
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, ParseError> {
         let mut number_str = String::new();
+        let mut is_float = false;
 
         if self.current_char() == '-' {
             number_str.push(self.current_char());
@@ -85,7 +332,7 @@ impl JsonParser {
         }
 
         if !self.current_char().is_ascii_digit() {
-            return Err("Invalid number format".to_string());
+            return Err(self.error("Invalid number format"));
         }
 
         while self.pos < self.chars.len() && self.current_char().is_ascii_digit() {
@@ -93,15 +340,57 @@ impl JsonParser {
             self.advance();
         }
 
-        number_str
-            .parse::<i64>()
-            .map(JsonValue::Integer)
-            .map_err(|_| "Invalid integer".to_string())
+        if self.current_char() == '.' {
+            is_float = true;
+            number_str.push(self.current_char());
+            self.advance();
+
+            if !self.current_char().is_ascii_digit() {
+                return Err(self.error("Invalid number format: period requires digit"));
+            }
+
+            while self.pos < self.chars.len() && self.current_char().is_ascii_digit() {
+                number_str.push(self.current_char());
+                self.advance();
+            }
+        }
+
+        if matches!(self.current_char(), 'e' | 'E') {
+            is_float = true;
+            number_str.push(self.current_char());
+            self.advance();
+
+            if matches!(self.current_char(), '+' | '-') {
+                number_str.push(self.current_char());
+                self.advance();
+            }
+
+            if !self.current_char().is_ascii_digit() {
+                return Err(self.error("Invalid number format: exponent requires digit"));
+            }
+
+            while self.pos < self.chars.len() && self.current_char().is_ascii_digit() {
+                number_str.push(self.current_char());
+                self.advance();
+            }
+        }
+
+        if is_float {
+            number_str
+                .parse::<f64>()
+                .map(JsonValue::Float)
+                .map_err(|_| self.error("Invalid float"))
+        } else {
+            number_str
+                .parse::<i64>()
+                .map(JsonValue::Integer)
+                .map_err(|_| self.error("Invalid integer"))
+        }
     }
 
-    fn parse_array(&mut self) -> Result<JsonValue, String> {
+    fn parse_array(&mut self) -> Result<JsonValue, ParseError> {
         if self.current_char() != '[' {
-            return Err("Expected opening bracket".to_string());
+            return Err(self.error("Expected opening bracket"));
         }
 
         self.advance(); // Skip '['
@@ -127,16 +416,16 @@ impl JsonParser {
                     self.advance();
                     break;
                 }
-                _ => return Err("Expected ',' or ']' in array".to_string()),
+                _ => return Err(self.error("Expected ',' or ']' in array")),
             }
         }
 
         Ok(JsonValue::Array(elements))
     }
 
-    fn parse_object(&mut self) -> Result<JsonValue, String> {
+    fn parse_object(&mut self) -> Result<JsonValue, ParseError> {
         if self.current_char() != '{' {
-            return Err("Expected opening brace".to_string());
+            return Err(self.error("Expected opening brace"));
         }
 
         self.advance(); // Skip '{'
@@ -153,13 +442,13 @@ impl JsonParser {
             // Parse key
             let key = match self.parse_value()? {
                 JsonValue::String(s) => s,
-                _ => return Err("Object key must be a string".to_string()),
+                _ => return Err(self.error("Object key must be a string")),
             };
 
             self.skip_whitespace();
 
             if self.current_char() != ':' {
-                return Err("Expected ':' after object key".to_string());
+                return Err(self.error("Expected ':' after object key"));
             }
 
             self.advance(); // Skip ':'
@@ -180,7 +469,7 @@ impl JsonParser {
                     self.advance();
                     break;
                 }
-                _ => return Err("Expected ',' or '}' in object".to_string()),
+                _ => return Err(self.error("Expected ',' or '}' in object")),
             }
         }
 
@@ -197,29 +486,56 @@ impl JsonParser {
         self.chars.get(self.pos).copied().unwrap_or('\0')
     }
 
+    /// Advances past the current character, updating line/column/byte
+    /// tracking so errors raised afterward point at the new position.
     fn advance(&mut self) {
+        if let Some(&c) = self.chars.get(self.pos) {
+            self.byte_offset += c.len_utf8();
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
         self.pos += 1;
     }
 
-    fn parse_boolean(&mut self) -> Result<JsonValue, String> {
+    fn parse_boolean(&mut self) -> Result<JsonValue, ParseError> {
         // Attempt to parse true/false literals
         if self.pos + 4 <= self.chars.len()
             && self.chars[self.pos..self.pos + 4] == ['t', 'r', 'u', 'e']
         {
-            self.pos += 4;
+            for _ in 0..4 {
+                self.advance();
+            }
             return Ok(JsonValue::Bool(true));
         }
         if self.pos + 5 <= self.chars.len()
             && self.chars[self.pos..self.pos + 5] == ['f', 'a', 'l', 's', 'e']
         {
-            self.pos += 5;
+            for _ in 0..5 {
+                self.advance();
+            }
             return Ok(JsonValue::Bool(false));
         }
-        Err("Invalid boolean literal".to_string())
+        Err(self.error("Invalid boolean literal"))
+    }
+
+    fn parse_null(&mut self) -> Result<JsonValue, ParseError> {
+        if self.pos + 4 <= self.chars.len()
+            && self.chars[self.pos..self.pos + 4] == ['n', 'u', 'l', 'l']
+        {
+            for _ in 0..4 {
+                self.advance();
+            }
+            return Ok(JsonValue::Null);
+        }
+        Err(self.error("Invalid null literal"))
     }
 }
 
-pub fn parse_json(input: &str) -> Result<JsonValue, String> {
+pub fn parse_json(input: &str) -> Result<JsonValue, ParseError> {
     let mut parser = JsonParser::new(input);
     parser.parse()
 }
@@ -240,7 +556,7 @@ mod tests {
         assert_eq!(result, JsonValue::String("  hello   world  ".to_string()));
 
         let result = parse_json("\"\\t\\n\\r\"").unwrap();
-        assert_eq!(result, JsonValue::String("\\t\\n\\r".to_string()));
+        assert_eq!(result, JsonValue::String("\t\n\r".to_string()));
     }
 
     #[test]
@@ -409,4 +725,222 @@ mod tests {
         expected.insert("deleted".to_string(), JsonValue::Bool(false));
         assert_eq!(result, JsonValue::Object(expected));
     }
+
+    #[test]
+    fn test_parse_null() {
+        let result = parse_json("null").unwrap();
+        assert_eq!(result, JsonValue::Null);
+    }
+
+    #[test]
+    fn test_parse_null_invalid_literal() {
+        assert!(parse_json("nul").is_err());
+        assert!(parse_json("nothing").is_err());
+    }
+
+    #[test]
+    fn test_parse_null_in_array_and_object() {
+        let result = parse_json("[null, 1]").unwrap();
+        assert_eq!(
+            result,
+            JsonValue::Array(vec![JsonValue::Null, JsonValue::Integer(1)])
+        );
+
+        let result = parse_json("{\"value\": null}").unwrap();
+        let mut expected = HashMap::new();
+        expected.insert("value".to_string(), JsonValue::Null);
+        assert_eq!(result, JsonValue::Object(expected));
+    }
+
+    #[test]
+    fn test_parse_float() {
+        let result = parse_json("3.25").unwrap();
+        assert_eq!(result, JsonValue::Float(3.25));
+
+        let result = parse_json("-0.5").unwrap();
+        assert_eq!(result, JsonValue::Float(-0.5));
+    }
+
+    #[test]
+    fn test_parse_negative_zero() {
+        let result = parse_json("-0").unwrap();
+        assert_eq!(result, JsonValue::Integer(0));
+
+        let result = parse_json("-0.0").unwrap();
+        assert_eq!(result, JsonValue::Float(-0.0));
+    }
+
+    #[test]
+    fn test_parse_float_missing_digit_after_period_is_error() {
+        assert!(parse_json("1.").is_err());
+        assert!(parse_json("1.e5").is_err());
+    }
+
+    #[test]
+    fn test_parse_scientific_notation() {
+        assert_eq!(parse_json("1e3").unwrap(), JsonValue::Float(1000.0));
+        assert_eq!(parse_json("1E3").unwrap(), JsonValue::Float(1000.0));
+        assert_eq!(parse_json("1.5e2").unwrap(), JsonValue::Float(150.0));
+        assert_eq!(parse_json("1e-2").unwrap(), JsonValue::Float(0.01));
+        assert_eq!(parse_json("1e+2").unwrap(), JsonValue::Float(100.0));
+    }
+
+    #[test]
+    fn test_parse_exponent_missing_digit_is_error() {
+        assert!(parse_json("1e").is_err());
+        assert!(parse_json("1e+").is_err());
+        assert!(parse_json("1e-").is_err());
+    }
+
+    #[test]
+    fn test_parse_string_basic_escapes() {
+        let result = parse_json(r#""a\"b\\c\/d\be\ff\ng\rh\ti""#).unwrap();
+        assert_eq!(
+            result,
+            JsonValue::String("a\"b\\c/d\u{0008}e\u{000C}f\ng\rh\ti".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_string_unicode_escape() {
+        let result = parse_json(r#""A\u00e9""#).unwrap();
+        assert_eq!(result, JsonValue::String("A\u{00e9}".to_string()));
+    }
+
+    #[test]
+    fn test_parse_string_surrogate_pair() {
+        // U+1F600 GRINNING FACE, encoded as a UTF-16 surrogate pair.
+        let result = parse_json(r#""\ud83d\ude00""#).unwrap();
+        assert_eq!(result, JsonValue::String("\u{1F600}".to_string()));
+    }
+
+    #[test]
+    fn test_parse_string_unpaired_high_surrogate_is_error() {
+        assert!(parse_json(r#""\ud83d""#).is_err());
+        assert!(parse_json(r#""\ud83dX""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_string_unpaired_low_surrogate_is_error() {
+        assert!(parse_json(r#""\ude00""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_string_invalid_escape_letter_is_error() {
+        assert!(parse_json(r#""\q""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_string_incomplete_unicode_escape_is_error() {
+        assert!(parse_json(r#""\u12""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_float_in_array_and_object() {
+        let result = parse_json("[1.5, 2]").unwrap();
+        assert_eq!(
+            result,
+            JsonValue::Array(vec![JsonValue::Float(1.5), JsonValue::Integer(2)])
+        );
+
+        let result = parse_json("{\"ratio\": 3.25}").unwrap();
+        let mut expected = HashMap::new();
+        expected.insert("ratio".to_string(), JsonValue::Float(3.25));
+        assert_eq!(result, JsonValue::Object(expected));
+    }
+
+    #[test]
+    fn test_to_string_scalars() {
+        assert_eq!(JsonValue::Null.to_string(), "null");
+        assert_eq!(JsonValue::Bool(true).to_string(), "true");
+        assert_eq!(JsonValue::Integer(-42).to_string(), "-42");
+        assert_eq!(JsonValue::Float(1.5).to_string(), "1.5");
+        assert_eq!(JsonValue::Float(3.0).to_string(), "3.0");
+    }
+
+    #[test]
+    fn test_to_string_escapes_string() {
+        let value = JsonValue::String("a\"b\\c\nd\te\u{00e9}".to_string());
+        assert_eq!(value.to_string(), r#""a\"b\\c\nd\teé""#);
+    }
+
+    #[test]
+    fn test_to_string_escapes_astral_character_as_surrogate_pair() {
+        let value = JsonValue::String("\u{1F600}".to_string());
+        assert_eq!(value.to_string(), r#""😀""#);
+    }
+
+    #[test]
+    fn test_to_string_array() {
+        let value = JsonValue::Array(vec![
+            JsonValue::Integer(1),
+            JsonValue::Bool(false),
+            JsonValue::Null,
+        ]);
+        assert_eq!(value.to_string(), "[1,false,null]");
+    }
+
+    #[test]
+    fn test_to_string_object_sorts_keys() {
+        let mut map = HashMap::new();
+        map.insert("b".to_string(), JsonValue::Integer(2));
+        map.insert("a".to_string(), JsonValue::Integer(1));
+        let value = JsonValue::Object(map);
+        assert_eq!(value.to_string(), r#"{"a":1,"b":2}"#);
+    }
+
+    #[test]
+    fn test_to_string_round_trips_through_parse_json() {
+        let original = parse_json(r#"{"name":"Café","tags":[1,2.5,null,true]}"#).unwrap();
+        let serialized = original.to_string();
+        let reparsed = parse_json(&serialized).unwrap();
+        assert_eq!(original, reparsed);
+    }
+
+    #[test]
+    fn test_to_string_pretty_nested_object_and_array() {
+        let mut inner = HashMap::new();
+        inner.insert("b".to_string(), JsonValue::Integer(2));
+        inner.insert("a".to_string(), JsonValue::Array(vec![JsonValue::Integer(1)]));
+        let value = JsonValue::Object(inner);
+
+        assert_eq!(
+            value.to_string_pretty(2),
+            "{\n  \"a\": [\n    1\n  ],\n  \"b\": 2\n}"
+        );
+    }
+
+    #[test]
+    fn test_to_string_pretty_empty_containers_stay_compact() {
+        assert_eq!(JsonValue::Array(vec![]).to_string_pretty(2), "[]");
+        assert_eq!(JsonValue::Object(HashMap::new()).to_string_pretty(2), "{}");
+    }
+
+    #[test]
+    fn test_display_matches_to_string() {
+        let value = JsonValue::Array(vec![JsonValue::Integer(1), JsonValue::Bool(true)]);
+        assert_eq!(format!("{value}"), value.to_string());
+    }
+
+    #[test]
+    fn test_parse_error_position_on_first_line() {
+        let err = parse_json("@").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert_eq!(err.column, 1);
+        assert_eq!(err.byte_offset, 0);
+    }
+
+    #[test]
+    fn test_parse_error_position_after_newline() {
+        let err = parse_json("{\n  \"a\": }").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 8);
+        assert_eq!(err.byte_offset, 9);
+    }
+
+    #[test]
+    fn test_parse_error_display_renders_line_col_message() {
+        let err = parse_json("@").unwrap_err();
+        assert_eq!(format!("{err}"), "1:1: Unexpected character: @");
+    }
 }
@@ -0,0 +1,690 @@
+//! JSONPath-style query engine over [`JsonValue`].
+//!
+//! Supports the common subset of JSONPath: `$` root, `.key` / `['key']`
+//! child access, `[n]` index access (negative counts from the end, like
+//! Python), `[start:end:step]` slices with Python-style defaults, `[*]` /
+//! `.*` wildcard over array elements or object values, `..key` recursive
+//! descent, and `[?(@.field OP value)]` filters that keep only array
+//! elements whose field compares true against a quoted string or numeric
+//! literal. A path is first tokenized into a flat stream, then a walker
+//! threads a working set of `&JsonValue` references through each token in
+//! turn, mapping the current set to the next one.
+//!
+//! Not yet wired up as a Liquid filter: `load_site_data` flattens every
+//! loaded `JsonValue` tree into scalar `data.*` variables before templates
+//! ever see it (see [`crate::load_data::flatten_json_value`]), and the
+//! Liquid filter chain only threads already-resolved scalar strings through
+//! [`crate::template_processors::liquid::filters::apply_filter_chain`].
+//! Exposing `select` as `{{ data.x | jsonpath: "..." }}` needs the renderer
+//! to resolve `data.x` to a structured value (and a way to flatten a
+//! `Vec<&JsonValue>` selection back into template output) before a filter
+//! can run against it - a bigger change than this evaluator on its own.
+
+use super::json::JsonValue;
+
+/// Recursive descent stops expanding past this depth. JSON values can't
+/// actually cycle, but a pathologically deep tree would otherwise recurse
+/// without bound.
+const MAX_RECURSIVE_DESCENT_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathToken {
+    RootNode,
+    Key(String),
+    Index(i64),
+    Slice {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+    },
+    Wildcard,
+    RecursiveDescent,
+    Filter {
+        field: String,
+        op: CompareOp,
+        value: FilterValue,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FilterValue {
+    String(String),
+    Number(f64),
+}
+
+impl JsonValue {
+    /// Selects values out of this tree using a JSONPath-style query, e.g.
+    /// `$.store.book[0].title` or `$..price`. Matches are returned in
+    /// traversal order; array order is always preserved, but object field
+    /// order follows whatever order the backing `HashMap` iterates in,
+    /// since [`JsonValue::Object`] doesn't track insertion order.
+    pub fn select(&self, path: &str) -> Result<Vec<&JsonValue>, String> {
+        let tokens = tokenize(path)?;
+        let mut current: Vec<&JsonValue> = vec![self];
+
+        let mut i = 1; // tokens[0] is RootNode, already represented by `current`
+        while i < tokens.len() {
+            match &tokens[i] {
+                PathToken::RecursiveDescent => {
+                    let key = match tokens.get(i + 1) {
+                        Some(PathToken::Key(key)) => key,
+                        _ => return Err("'..' must be followed by a key".to_string()),
+                    };
+                    current = current
+                        .into_iter()
+                        .flat_map(|value| collect_recursive(value, key))
+                        .collect();
+                    i += 2;
+                }
+                token => {
+                    current = apply_token(current, token);
+                    i += 1;
+                }
+            }
+        }
+
+        Ok(current)
+    }
+}
+
+fn apply_token<'a>(current: Vec<&'a JsonValue>, token: &PathToken) -> Vec<&'a JsonValue> {
+    match token {
+        PathToken::RootNode | PathToken::RecursiveDescent => current,
+        PathToken::Key(key) => current
+            .into_iter()
+            .filter_map(|value| match value {
+                JsonValue::Object(map) => map.get(key),
+                _ => None,
+            })
+            .collect(),
+        PathToken::Index(index) => current
+            .into_iter()
+            .filter_map(|value| match value {
+                JsonValue::Array(items) => {
+                    resolve_index(items.len(), *index).and_then(|i| items.get(i))
+                }
+                _ => None,
+            })
+            .collect(),
+        PathToken::Slice { start, end, step } => current
+            .into_iter()
+            .flat_map(|value| -> Vec<&JsonValue> {
+                match value {
+                    JsonValue::Array(items) => slice_indices(items.len(), *start, *end, *step)
+                        .into_iter()
+                        .filter_map(|i| items.get(i))
+                        .collect(),
+                    _ => Vec::new(),
+                }
+            })
+            .collect(),
+        PathToken::Wildcard => current
+            .into_iter()
+            .flat_map(|value| -> Vec<&JsonValue> {
+                match value {
+                    JsonValue::Array(items) => items.iter().collect(),
+                    JsonValue::Object(map) => map.values().collect(),
+                    _ => Vec::new(),
+                }
+            })
+            .collect(),
+        PathToken::Filter { field, op, value } => current
+            .into_iter()
+            .flat_map(|current_value| -> Vec<&JsonValue> {
+                match current_value {
+                    JsonValue::Array(items) => items
+                        .iter()
+                        .filter(|item| matches_filter(item, field, op, value))
+                        .collect(),
+                    _ => Vec::new(),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Resolves a possibly-negative JSONPath index (Python-style, counting from
+/// the end) against a collection of length `len`, returning `None` if it's
+/// still out of range once resolved.
+fn resolve_index(len: usize, index: i64) -> Option<usize> {
+    let resolved = if index < 0 { index + len as i64 } else { index };
+    usize::try_from(resolved).ok().filter(|i| *i < len)
+}
+
+/// Computes the indices a `[start:end:step]` slice selects out of a
+/// collection of length `len`, following Python's slicing semantics
+/// (negative bounds count from the end, omitted bounds default to the
+/// start/end of the collection depending on the step's sign).
+fn slice_indices(
+    len: usize,
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+) -> Vec<usize> {
+    let step = step.unwrap_or(1);
+    if step == 0 || len == 0 {
+        return Vec::new();
+    }
+
+    let len_i = len as i64;
+    let clamp = |i: i64| i.clamp(0, len_i);
+    let normalize = |i: i64| if i < 0 { i + len_i } else { i };
+
+    let (default_start, default_end) = if step > 0 { (0, len_i) } else { (len_i - 1, -1) };
+    let start = start.map(normalize).map(clamp).unwrap_or(default_start);
+    let end = end
+        .map(normalize)
+        .map(|e| if step > 0 { clamp(e) } else { e.clamp(-1, len_i - 1) })
+        .unwrap_or(default_end);
+
+    let mut indices = Vec::new();
+    let mut i = start;
+    while (step > 0 && i < end) || (step < 0 && i > end) {
+        if i >= 0 && i < len_i {
+            indices.push(i as usize);
+        }
+        i += step;
+    }
+    indices
+}
+
+/// Evaluates a `[?(@.field OP value)]` filter predicate against one array
+/// element. Non-object elements and missing fields evaluate to `false`
+/// rather than erroring.
+fn matches_filter(item: &JsonValue, field: &str, op: &CompareOp, value: &FilterValue) -> bool {
+    let JsonValue::Object(map) = item else {
+        return false;
+    };
+    let Some(field_value) = map.get(field) else {
+        return false;
+    };
+
+    match value {
+        FilterValue::Number(n) => {
+            let Some(field_number) = as_number(field_value) else {
+                return false;
+            };
+            compare(field_number.total_cmp(n), op)
+        }
+        FilterValue::String(s) => {
+            let JsonValue::String(field_string) = field_value else {
+                return false;
+            };
+            compare(field_string.as_str().cmp(s.as_str()), op)
+        }
+    }
+}
+
+fn as_number(value: &JsonValue) -> Option<f64> {
+    match value {
+        JsonValue::Integer(n) => Some(*n as f64),
+        JsonValue::Float(n) => Some(*n),
+        _ => None,
+    }
+}
+
+fn compare(ordering: std::cmp::Ordering, op: &CompareOp) -> bool {
+    match (ordering, op) {
+        (std::cmp::Ordering::Equal, CompareOp::Eq) => true,
+        (std::cmp::Ordering::Equal, CompareOp::Ne) => false,
+        (std::cmp::Ordering::Less, CompareOp::Ne) | (std::cmp::Ordering::Greater, CompareOp::Ne) => {
+            true
+        }
+        (std::cmp::Ordering::Less, CompareOp::Lt | CompareOp::Le) => true,
+        (std::cmp::Ordering::Greater, CompareOp::Gt | CompareOp::Ge) => true,
+        (std::cmp::Ordering::Equal, CompareOp::Le | CompareOp::Ge) => true,
+        _ => false,
+    }
+}
+
+/// Collects every descendant of `value` (at any depth, up to
+/// [`MAX_RECURSIVE_DESCENT_DEPTH`]) whose key is `key`.
+fn collect_recursive<'a>(value: &'a JsonValue, key: &str) -> Vec<&'a JsonValue> {
+    let mut matches = Vec::new();
+    collect_recursive_into(value, key, &mut matches, 0);
+    matches
+}
+
+fn collect_recursive_into<'a>(
+    value: &'a JsonValue,
+    key: &str,
+    matches: &mut Vec<&'a JsonValue>,
+    depth: usize,
+) {
+    if depth >= MAX_RECURSIVE_DESCENT_DEPTH {
+        return;
+    }
+    match value {
+        JsonValue::Object(map) => {
+            if let Some(found) = map.get(key) {
+                matches.push(found);
+            }
+            for child in map.values() {
+                collect_recursive_into(child, key, matches, depth + 1);
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items {
+                collect_recursive_into(item, key, matches, depth + 1);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn tokenize(path: &str) -> Result<Vec<PathToken>, String> {
+    let chars: Vec<char> = path.chars().collect();
+    let mut pos = 0;
+    let mut tokens = Vec::new();
+
+    if chars.first() != Some(&'$') {
+        return Err("Path must start with '$'".to_string());
+    }
+    tokens.push(PathToken::RootNode);
+    pos += 1;
+
+    while pos < chars.len() {
+        match chars[pos] {
+            '.' => {
+                pos += 1;
+                if chars.get(pos) == Some(&'.') {
+                    pos += 1;
+                    let key = read_identifier(&chars, &mut pos)?;
+                    tokens.push(PathToken::RecursiveDescent);
+                    tokens.push(PathToken::Key(key));
+                } else if chars.get(pos) == Some(&'*') {
+                    pos += 1;
+                    tokens.push(PathToken::Wildcard);
+                } else {
+                    let key = read_identifier(&chars, &mut pos)?;
+                    tokens.push(PathToken::Key(key));
+                }
+            }
+            '[' => {
+                pos += 1;
+                match chars.get(pos) {
+                    Some('*') => {
+                        pos += 1;
+                        expect_char(&chars, &mut pos, ']')?;
+                        tokens.push(PathToken::Wildcard);
+                    }
+                    Some('?') => {
+                        pos += 1;
+                        tokens.push(read_filter(&chars, &mut pos)?);
+                    }
+                    Some('\'') | Some('"') => {
+                        let quote = chars[pos];
+                        pos += 1;
+                        let start = pos;
+                        while chars.get(pos) != Some(&quote) {
+                            if pos >= chars.len() {
+                                return Err("Unterminated key in path".to_string());
+                            }
+                            pos += 1;
+                        }
+                        let key: String = chars[start..pos].iter().collect();
+                        pos += 1; // closing quote
+                        expect_char(&chars, &mut pos, ']')?;
+                        tokens.push(PathToken::Key(key));
+                    }
+                    Some(c) if c.is_ascii_digit() || *c == '-' || *c == ':' => {
+                        tokens.push(read_index_or_slice(&chars, &mut pos)?);
+                    }
+                    _ => return Err("Invalid bracket expression in path".to_string()),
+                }
+            }
+            c => return Err(format!("Unexpected character in path: {c}")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Reads a `[n]` index or `[start:end:step]` slice, assuming `*pos` is right
+/// after the opening `[`. Distinguishing the two requires scanning ahead for
+/// a `:`, since both start with an optional `-` and digits.
+fn read_index_or_slice(chars: &[char], pos: &mut usize) -> Result<PathToken, String> {
+    let first = read_optional_signed_int(chars, pos)?;
+
+    if chars.get(*pos) != Some(&':') {
+        expect_char(chars, pos, ']')?;
+        return first
+            .map(PathToken::Index)
+            .ok_or_else(|| "Invalid bracket expression in path".to_string());
+    }
+
+    *pos += 1; // ':'
+    let end = read_optional_signed_int(chars, pos)?;
+    let step = if chars.get(*pos) == Some(&':') {
+        *pos += 1;
+        read_optional_signed_int(chars, pos)?
+    } else {
+        None
+    };
+    expect_char(chars, pos, ']')?;
+    Ok(PathToken::Slice { start: first, end, step })
+}
+
+/// Reads an optional signed integer (`-`? digits), returning `None` if there
+/// are no digits at `*pos` (used for slice bounds that are left blank, e.g.
+/// `[:5]` or `[2:]`).
+fn read_optional_signed_int(chars: &[char], pos: &mut usize) -> Result<Option<i64>, String> {
+    let start = *pos;
+    if chars.get(*pos) == Some(&'-') {
+        *pos += 1;
+    }
+    let digits_start = *pos;
+    while chars.get(*pos).is_some_and(char::is_ascii_digit) {
+        *pos += 1;
+    }
+    if *pos == digits_start {
+        *pos = start;
+        return Ok(None);
+    }
+    let text: String = chars[start..*pos].iter().collect();
+    text.parse::<i64>()
+        .map(Some)
+        .map_err(|_| format!("Invalid index in path: {text}"))
+}
+
+/// Reads a `?(@.field OP value)]` filter predicate, assuming `*pos` is right
+/// after the `?`.
+fn read_filter(chars: &[char], pos: &mut usize) -> Result<PathToken, String> {
+    expect_char(chars, pos, '(')?;
+    expect_char(chars, pos, '@')?;
+    expect_char(chars, pos, '.')?;
+    let field = read_identifier(chars, pos)?;
+    skip_spaces(chars, pos);
+    let op = read_compare_op(chars, pos)?;
+    skip_spaces(chars, pos);
+    let value = read_filter_value(chars, pos)?;
+    expect_char(chars, pos, ')')?;
+    expect_char(chars, pos, ']')?;
+    Ok(PathToken::Filter { field, op, value })
+}
+
+fn skip_spaces(chars: &[char], pos: &mut usize) {
+    while chars.get(*pos) == Some(&' ') {
+        *pos += 1;
+    }
+}
+
+fn read_compare_op(chars: &[char], pos: &mut usize) -> Result<CompareOp, String> {
+    let op = match (chars.get(*pos), chars.get(*pos + 1)) {
+        (Some('='), Some('=')) => (CompareOp::Eq, 2),
+        (Some('!'), Some('=')) => (CompareOp::Ne, 2),
+        (Some('<'), Some('=')) => (CompareOp::Le, 2),
+        (Some('>'), Some('=')) => (CompareOp::Ge, 2),
+        (Some('<'), _) => (CompareOp::Lt, 1),
+        (Some('>'), _) => (CompareOp::Gt, 1),
+        _ => return Err("Expected a comparison operator in filter".to_string()),
+    };
+    *pos += op.1;
+    Ok(op.0)
+}
+
+fn read_filter_value(chars: &[char], pos: &mut usize) -> Result<FilterValue, String> {
+    match chars.get(*pos) {
+        Some('\'') | Some('"') => {
+            let quote = chars[*pos];
+            *pos += 1;
+            let start = *pos;
+            while chars.get(*pos) != Some(&quote) {
+                if *pos >= chars.len() {
+                    return Err("Unterminated string in filter".to_string());
+                }
+                *pos += 1;
+            }
+            let value: String = chars[start..*pos].iter().collect();
+            *pos += 1; // closing quote
+            Ok(FilterValue::String(value))
+        }
+        _ => {
+            let start = *pos;
+            if chars.get(*pos) == Some(&'-') {
+                *pos += 1;
+            }
+            while chars
+                .get(*pos)
+                .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+            {
+                *pos += 1;
+            }
+            let text: String = chars[start..*pos].iter().collect();
+            text.parse::<f64>()
+                .map(FilterValue::Number)
+                .map_err(|_| format!("Invalid filter value: {text}"))
+        }
+    }
+}
+
+fn read_identifier(chars: &[char], pos: &mut usize) -> Result<String, String> {
+    let start = *pos;
+    while chars
+        .get(*pos)
+        .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+    {
+        *pos += 1;
+    }
+    if *pos == start {
+        return Err("Expected identifier in path".to_string());
+    }
+    Ok(chars[start..*pos].iter().collect())
+}
+
+fn expect_char(chars: &[char], pos: &mut usize, expected: char) -> Result<(), String> {
+    if chars.get(*pos) != Some(&expected) {
+        return Err(format!("Expected '{expected}' in path"));
+    }
+    *pos += 1;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::parse_json;
+
+    #[test]
+    fn test_select_root() {
+        let value = parse_json(r#"{"a":1}"#).unwrap();
+        assert_eq!(value.select("$").unwrap(), vec![&value]);
+    }
+
+    #[test]
+    fn test_select_dot_key() {
+        let value = parse_json(r#"{"store":{"name":"Acme"}}"#).unwrap();
+        let result = value.select("$.store.name").unwrap();
+        assert_eq!(result, vec![&JsonValue::String("Acme".to_string())]);
+    }
+
+    #[test]
+    fn test_select_bracket_key() {
+        let value = parse_json(r#"{"store":{"name":"Acme"}}"#).unwrap();
+        let result = value.select("$['store']['name']").unwrap();
+        assert_eq!(result, vec![&JsonValue::String("Acme".to_string())]);
+    }
+
+    #[test]
+    fn test_select_index() {
+        let value = parse_json(r#"{"items":["a","b","c"]}"#).unwrap();
+        let result = value.select("$.items[1]").unwrap();
+        assert_eq!(result, vec![&JsonValue::String("b".to_string())]);
+    }
+
+    #[test]
+    fn test_select_wildcard_over_array() {
+        let value = parse_json(r#"[1,2,3]"#).unwrap();
+        let result = value.select("$[*]").unwrap();
+        assert_eq!(
+            result,
+            vec![
+                &JsonValue::Integer(1),
+                &JsonValue::Integer(2),
+                &JsonValue::Integer(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_dot_wildcard_over_object() {
+        let value = parse_json(r#"{"a":1}"#).unwrap();
+        let result = value.select("$.*").unwrap();
+        assert_eq!(result, vec![&JsonValue::Integer(1)]);
+    }
+
+    #[test]
+    fn test_select_recursive_descent() {
+        let value = parse_json(
+            r#"{"store":{"book":[{"price":10},{"price":20}],"bicycle":{"price":30}}}"#,
+        )
+        .unwrap();
+        let mut prices: Vec<i64> = value
+            .select("$..price")
+            .unwrap()
+            .into_iter()
+            .map(|v| match v {
+                JsonValue::Integer(n) => *n,
+                _ => panic!("expected integer"),
+            })
+            .collect();
+        prices.sort_unstable();
+        assert_eq!(prices, vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn test_select_missing_key_returns_empty() {
+        let value = parse_json(r#"{"a":1}"#).unwrap();
+        assert_eq!(value.select("$.missing").unwrap(), Vec::<&JsonValue>::new());
+    }
+
+    #[test]
+    fn test_select_out_of_bounds_index_returns_empty() {
+        let value = parse_json(r#"[1,2]"#).unwrap();
+        assert_eq!(value.select("$[5]").unwrap(), Vec::<&JsonValue>::new());
+    }
+
+    #[test]
+    fn test_select_path_must_start_with_root() {
+        let value = parse_json(r#"{"a":1}"#).unwrap();
+        assert!(value.select("a").is_err());
+    }
+
+    #[test]
+    fn test_select_recursive_descent_without_key_is_error() {
+        let value = parse_json(r#"{"a":1}"#).unwrap();
+        assert!(value.select("$..").is_err());
+    }
+
+    #[test]
+    fn test_select_invalid_bracket_expression_is_error() {
+        let value = parse_json(r#"{"a":1}"#).unwrap();
+        assert!(value.select("$[").is_err());
+        assert!(value.select("$[abc]").is_err());
+    }
+
+    #[test]
+    fn test_select_negative_index_counts_from_the_end() {
+        let value = parse_json(r#"["a","b","c"]"#).unwrap();
+        let result = value.select("$[-1]").unwrap();
+        assert_eq!(result, vec![&JsonValue::String("c".to_string())]);
+    }
+
+    #[test]
+    fn test_select_negative_index_out_of_range_returns_empty() {
+        let value = parse_json(r#"["a","b"]"#).unwrap();
+        assert_eq!(value.select("$[-5]").unwrap(), Vec::<&JsonValue>::new());
+    }
+
+    #[test]
+    fn test_select_slice_with_defaults() {
+        let value = parse_json(r#"[0,1,2,3,4]"#).unwrap();
+        let result = value.select("$[1:3]").unwrap();
+        assert_eq!(
+            result,
+            vec![&JsonValue::Integer(1), &JsonValue::Integer(2)]
+        );
+    }
+
+    #[test]
+    fn test_select_slice_with_negative_bounds_and_step() {
+        let value = parse_json(r#"[0,1,2,3,4]"#).unwrap();
+        let result = value.select("$[::-1]").unwrap();
+        assert_eq!(
+            result,
+            vec![
+                &JsonValue::Integer(4),
+                &JsonValue::Integer(3),
+                &JsonValue::Integer(2),
+                &JsonValue::Integer(1),
+                &JsonValue::Integer(0),
+            ]
+        );
+
+        let result = value.select("$[-2:]").unwrap();
+        assert_eq!(
+            result,
+            vec![&JsonValue::Integer(3), &JsonValue::Integer(4)]
+        );
+    }
+
+    #[test]
+    fn test_select_filter_numeric_comparison() {
+        let value =
+            parse_json(r#"{"book":[{"price":10},{"price":20},{"price":30}]}"#).unwrap();
+        let result = value.select("$.book[?(@.price>15)]").unwrap();
+        assert_eq!(
+            result,
+            vec![
+                &parse_json(r#"{"price":20}"#).unwrap(),
+                &parse_json(r#"{"price":30}"#).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_filter_string_equality() {
+        let value = parse_json(
+            r#"{"authors":[{"role":"admin"},{"role":"editor"}]}"#,
+        )
+        .unwrap();
+        let result = value.select("$.authors[?(@.role==\"admin\")]").unwrap();
+        assert_eq!(result, vec![&parse_json(r#"{"role":"admin"}"#).unwrap()]);
+    }
+
+    #[test]
+    fn test_select_filter_missing_field_evaluates_false() {
+        let value = parse_json(r#"{"items":[{"a":1},{}]}"#).unwrap();
+        let result = value.select("$.items[?(@.a==1)]").unwrap();
+        assert_eq!(result, vec![&parse_json(r#"{"a":1}"#).unwrap()]);
+    }
+
+    #[test]
+    fn test_select_wildcard_on_scalar_yields_nothing() {
+        let value = parse_json(r#"{"a":1}"#).unwrap();
+        let result = value.select("$.a[*]").unwrap();
+        assert_eq!(result, Vec::<&JsonValue>::new());
+    }
+
+    #[test]
+    fn test_select_recursive_descent_does_not_exceed_depth_guard() {
+        let mut json = "0".to_string();
+        for _ in 0..(MAX_RECURSIVE_DESCENT_DEPTH + 10) {
+            json = format!(r#"{{"child":{json}}}"#);
+        }
+        let value = parse_json(&json).unwrap();
+        // Should terminate promptly instead of recursing past the guard.
+        let result = value.select("$..child").unwrap();
+        assert!(result.len() <= MAX_RECURSIVE_DESCENT_DEPTH);
+    }
+}
@@ -0,0 +1,12 @@
+mod filter_expression;
+mod front_matter;
+mod json;
+mod json_path;
+mod toml;
+mod yaml;
+
+pub use filter_expression::filter_array;
+pub use front_matter::parse_content_with_front_matter;
+pub use json::{parse_json, JsonValue, ParseError};
+pub use toml::parse_toml;
+pub use yaml::parse_yaml;
@@ -0,0 +1,291 @@
+//! A parser for a practical subset of TOML, producing the same
+//! [`JsonValue`] tree [`crate::parsers::parse_json`] does, so data files can
+//! use either format interchangeably (see `crate::load_data::load_site_data`).
+//!
+//! Supported: `key = value` pairs, `[table]` and `[table.nested]` headers,
+//! `[[array.of.tables]]` headers, and values that are strings, integers,
+//! floats, booleans, or inline arrays of those. Inline tables, dates, and
+//! multi-line strings aren't needed for the data files this parses and are
+//! out of scope, matching the scope trimming
+//! [`crate::parsers::front_matter`]'s TOML front matter already does.
+
+use crate::parsers::JsonValue;
+use std::collections::HashMap;
+
+pub fn parse_toml(input: &str) -> Result<JsonValue, String> {
+    let mut root = HashMap::new();
+    let mut path: Vec<String> = Vec::new();
+
+    for (zero_based_line, raw_line) in input.lines().enumerate() {
+        let line_number = zero_based_line + 1;
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix("[[").and_then(|s| s.strip_suffix("]]")) {
+            path = split_dotted_path(header, line_number)?;
+            append_array_table(&mut root, &path, line_number)?;
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            path = split_dotted_path(header, line_number)?;
+            // Visiting ensures the table exists without needing its handle here.
+            navigate_mut(&mut root, &path, line_number)?;
+            continue;
+        }
+
+        let Some((key, value_str)) = line.split_once('=') else {
+            return Err(format!(
+                "expected `key = value` or a table header on line {line_number}"
+            ));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("empty TOML key on line {line_number}"));
+        }
+
+        let value = parse_toml_value(value_str.trim(), line_number)?;
+        let table = navigate_mut(&mut root, &path, line_number)?;
+        table.insert(key.to_string(), value);
+    }
+
+    Ok(JsonValue::Object(root))
+}
+
+/// Drops a trailing `#` comment, ignoring `#` characters inside quotes.
+fn strip_comment(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    let mut in_single = false;
+    let mut in_double = false;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'\'' if !in_double => in_single = !in_single,
+            b'"' if !in_single => in_double = !in_double,
+            b'#' if !in_single && !in_double => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+fn split_dotted_path(header: &str, line_number: usize) -> Result<Vec<String>, String> {
+    let header = header.trim();
+    if header.is_empty() {
+        return Err(format!("empty table header on line {line_number}"));
+    }
+
+    header
+        .split('.')
+        .map(|segment| {
+            let segment = segment.trim();
+            if segment.is_empty() {
+                Err(format!("empty table header segment on line {line_number}"))
+            } else {
+                Ok(unquote_segment(segment))
+            }
+        })
+        .collect()
+}
+
+fn unquote_segment(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    if bytes.len() >= 2 && ((bytes[0] == b'"' && bytes[bytes.len() - 1] == b'"') || (bytes[0] == b'\'' && bytes[bytes.len() - 1] == b'\'')) {
+        segment[1..segment.len() - 1].to_string()
+    } else {
+        segment.to_string()
+    }
+}
+
+/// Walks `segments` from `map`, creating empty tables as needed, descending
+/// into the last element of an array of tables where one is in the path.
+fn navigate_mut<'a>(
+    map: &'a mut HashMap<String, JsonValue>,
+    segments: &[String],
+    line_number: usize,
+) -> Result<&'a mut HashMap<String, JsonValue>, String> {
+    let mut current = map;
+
+    for segment in segments {
+        let entry = current
+            .entry(segment.clone())
+            .or_insert_with(|| JsonValue::Object(HashMap::new()));
+        current = match entry {
+            JsonValue::Object(inner) => inner,
+            JsonValue::Array(items) => match items.last_mut() {
+                Some(JsonValue::Object(inner)) => inner,
+                _ => return Err(format!("`{segment}` is not a table on line {line_number}")),
+            },
+            _ => return Err(format!("`{segment}` is not a table on line {line_number}")),
+        };
+    }
+
+    Ok(current)
+}
+
+/// Appends a fresh table to the array of tables named by `segments`,
+/// creating the array (and any parent tables) if it doesn't exist yet.
+fn append_array_table(root: &mut HashMap<String, JsonValue>, segments: &[String], line_number: usize) -> Result<(), String> {
+    let Some((last, parents)) = segments.split_last() else {
+        return Err(format!("empty table header on line {line_number}"));
+    };
+
+    let parent = navigate_mut(root, parents, line_number)?;
+    match parent.entry(last.clone()).or_insert_with(|| JsonValue::Array(Vec::new())) {
+        JsonValue::Array(items) => items.push(JsonValue::Object(HashMap::new())),
+        _ => return Err(format!("`{last}` is not an array of tables on line {line_number}")),
+    }
+
+    Ok(())
+}
+
+fn parse_toml_value(value: &str, line_number: usize) -> Result<JsonValue, String> {
+    if let Some(inner) = value.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+        let mut items = Vec::new();
+        for part in split_top_level_commas(inner) {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            items.push(parse_toml_value(part, line_number)?);
+        }
+        return Ok(JsonValue::Array(items));
+    }
+
+    if let Some(quoted) = value.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        return Ok(JsonValue::String(quoted.to_string()));
+    }
+    if let Some(quoted) = value.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+        return Ok(JsonValue::String(quoted.to_string()));
+    }
+
+    match value {
+        "true" => return Ok(JsonValue::Bool(true)),
+        "false" => return Ok(JsonValue::Bool(false)),
+        _ => {}
+    }
+
+    if let Ok(i) = value.parse::<i64>() {
+        return Ok(JsonValue::Integer(i));
+    }
+    if let Ok(f) = value.parse::<f64>() {
+        return Ok(JsonValue::Float(f));
+    }
+
+    Err(format!("unsupported TOML value on line {line_number}: `{value}`"))
+}
+
+/// Splits `input` on commas outside of `[]` nesting and string literals.
+fn split_top_level_commas(input: &str) -> Vec<&str> {
+    let bytes = input.as_bytes();
+    let mut depth: i32 = 0;
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut parts = Vec::new();
+    let mut start = 0;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'\'' if !in_double => in_single = !in_single,
+            b'"' if !in_single => in_double = !in_double,
+            b'[' if !in_single && !in_double => depth += 1,
+            b']' if !in_single && !in_double => depth -= 1,
+            b',' if !in_single && !in_double && depth == 0 => {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_toml;
+    use crate::parsers::JsonValue;
+
+    #[test]
+    fn parses_flat_key_values() {
+        let result = parse_toml("title = \"My Site\"\nversion = 1").unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        assert_eq!(map.get("title"), Some(&JsonValue::String("My Site".to_string())));
+        assert_eq!(map.get("version"), Some(&JsonValue::Integer(1)));
+    }
+
+    #[test]
+    fn parses_nested_table_headers() {
+        let input = "[extra]\nauthor = \"x\"\n\n[extra.social]\ntwitter = \"@x\"";
+        let result = parse_toml(input).unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        let JsonValue::Object(extra) = &map["extra"] else {
+            panic!("expected nested object");
+        };
+        assert_eq!(extra.get("author"), Some(&JsonValue::String("x".to_string())));
+        let JsonValue::Object(social) = &extra["social"] else {
+            panic!("expected nested object");
+        };
+        assert_eq!(social.get("twitter"), Some(&JsonValue::String("@x".to_string())));
+    }
+
+    #[test]
+    fn parses_array_of_tables() {
+        let input = "[[authors]]\nname = \"Ada\"\n\n[[authors]]\nname = \"Grace\"";
+        let result = parse_toml(input).unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        let JsonValue::Array(authors) = &map["authors"] else {
+            panic!("expected array");
+        };
+        assert_eq!(authors.len(), 2);
+        let JsonValue::Object(first) = &authors[0] else {
+            panic!("expected object");
+        };
+        assert_eq!(first.get("name"), Some(&JsonValue::String("Ada".to_string())));
+        let JsonValue::Object(second) = &authors[1] else {
+            panic!("expected object");
+        };
+        assert_eq!(second.get("name"), Some(&JsonValue::String("Grace".to_string())));
+    }
+
+    #[test]
+    fn parses_inline_array_of_scalars() {
+        let result = parse_toml("tags = [\"rust\", \"static-site\"]").unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        assert_eq!(
+            map["tags"],
+            JsonValue::Array(vec![
+                JsonValue::String("rust".to_string()),
+                JsonValue::String("static-site".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn ignores_comments_and_blank_lines() {
+        let input = "# a comment\ntitle = \"My Site\" # trailing\n\nversion = 1";
+        let result = parse_toml(input).unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        assert_eq!(map.get("title"), Some(&JsonValue::String("My Site".to_string())));
+        assert_eq!(map.get("version"), Some(&JsonValue::Integer(1)));
+    }
+
+    #[test]
+    fn malformed_line_is_an_error() {
+        assert!(parse_toml("this is not valid toml").is_err());
+    }
+}
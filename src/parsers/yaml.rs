@@ -0,0 +1,345 @@
+//! A line/indentation-based parser for a practical subset of YAML, producing
+//! the same [`JsonValue`] tree [`crate::parsers::parse_json`] does, so data
+//! files can use either format interchangeably (see
+//! `crate::load_data::load_site_data`).
+//!
+//! Supported: block mappings, block sequences (including sequences of
+//! mappings, e.g. a list of author objects), quoted and unquoted scalars
+//! (strings, integers, floats, booleans, `null`/`~`), and `#` comments. Flow
+//! style (`[a, b]`, `{a: b}`), anchors/aliases, and multi-document streams
+//! aren't needed for the data files this parses and are out of scope,
+//! matching the scope trimming [`crate::parsers::front_matter`]'s YAML front
+//! matter already does.
+
+use crate::parsers::JsonValue;
+use std::collections::HashMap;
+
+struct Line<'a> {
+    indent: usize,
+    content: &'a str,
+}
+
+pub fn parse_yaml(input: &str) -> Result<JsonValue, String> {
+    let lines = preprocess_lines(input);
+    if lines.is_empty() {
+        return Ok(JsonValue::Null);
+    }
+
+    let indent = lines[0].indent;
+    let mut pos = 0;
+    let value = parse_block(&lines, &mut pos, indent)?;
+
+    if pos != lines.len() {
+        return Err(format!(
+            "unexpected indentation on line with indent {}",
+            lines[pos].indent
+        ));
+    }
+
+    Ok(value)
+}
+
+/// Strips comments and blank lines, recording each remaining line's
+/// indentation depth alongside its trimmed content.
+fn preprocess_lines(input: &str) -> Vec<Line<'_>> {
+    input
+        .lines()
+        .filter_map(|raw_line| {
+            let without_comment = strip_comment(raw_line);
+            let trimmed = without_comment.trim_end();
+            if trimmed.trim().is_empty() {
+                return None;
+            }
+            let indent = trimmed.len() - trimmed.trim_start().len();
+            Some(Line {
+                indent,
+                content: trimmed.trim_start(),
+            })
+        })
+        .collect()
+}
+
+/// Drops a trailing `#` comment, ignoring `#` characters inside quotes.
+fn strip_comment(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    let mut in_single = false;
+    let mut in_double = false;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'\'' if !in_double => in_single = !in_single,
+            b'"' if !in_single => in_double = !in_double,
+            b'#' if !in_single && !in_double && (i == 0 || bytes[i - 1].is_ascii_whitespace()) => {
+                return &line[..i];
+            }
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/// Parses the block (mapping or sequence) starting at `lines[*pos]`, which
+/// must already be indented to `indent`.
+fn parse_block(lines: &[Line], pos: &mut usize, indent: usize) -> Result<JsonValue, String> {
+    if *pos >= lines.len() || lines[*pos].indent != indent {
+        return Err(format!("expected content at indent {indent}"));
+    }
+
+    if is_sequence_item(lines[*pos].content) {
+        parse_sequence(lines, pos, indent)
+    } else {
+        parse_mapping(lines, pos, indent)
+    }
+}
+
+fn is_sequence_item(content: &str) -> bool {
+    content == "-" || content.starts_with("- ")
+}
+
+fn parse_sequence(lines: &[Line], pos: &mut usize, indent: usize) -> Result<JsonValue, String> {
+    let mut items = Vec::new();
+
+    while *pos < lines.len() && lines[*pos].indent == indent && is_sequence_item(lines[*pos].content) {
+        let content = lines[*pos].content;
+        let after_dash = &content[1..];
+        let leading_spaces = after_dash.len() - after_dash.trim_start().len();
+        let item_body = after_dash.trim_start();
+        let item_column = indent + 1 + leading_spaces;
+
+        if item_body.is_empty() {
+            *pos += 1;
+            items.push(parse_nested_or_null(lines, pos, indent)?);
+        } else if let Some((key, rest)) = split_mapping_line(item_body) {
+            items.push(parse_sequence_item_mapping(lines, pos, item_column, key, rest)?);
+        } else {
+            items.push(parse_scalar(item_body));
+            *pos += 1;
+        }
+    }
+
+    Ok(JsonValue::Array(items))
+}
+
+/// Parses a `- key: value` sequence item, which opens an inline mapping
+/// continued by any following lines indented to line up with `key`.
+fn parse_sequence_item_mapping(
+    lines: &[Line],
+    pos: &mut usize,
+    item_column: usize,
+    first_key: String,
+    first_rest: &str,
+) -> Result<JsonValue, String> {
+    *pos += 1;
+    let mut map = HashMap::new();
+    let first_value = if first_rest.is_empty() {
+        parse_nested_or_null(lines, pos, item_column)?
+    } else {
+        parse_scalar(first_rest)
+    };
+    map.insert(first_key, first_value);
+
+    while *pos < lines.len() && lines[*pos].indent == item_column && !is_sequence_item(lines[*pos].content) {
+        let (key, value) = parse_mapping_entry(lines, pos, item_column)?;
+        map.insert(key, value);
+    }
+
+    Ok(JsonValue::Object(map))
+}
+
+/// A value left blank on its own line (either `key:` or a bare `-`) is
+/// either `null`, or the nested block that follows it at deeper indentation.
+fn parse_nested_or_null(lines: &[Line], pos: &mut usize, indent: usize) -> Result<JsonValue, String> {
+    if *pos < lines.len() && lines[*pos].indent > indent {
+        let nested_indent = lines[*pos].indent;
+        parse_block(lines, pos, nested_indent)
+    } else {
+        Ok(JsonValue::Null)
+    }
+}
+
+fn parse_mapping(lines: &[Line], pos: &mut usize, indent: usize) -> Result<JsonValue, String> {
+    let mut map = HashMap::new();
+
+    while *pos < lines.len() && lines[*pos].indent == indent && !is_sequence_item(lines[*pos].content) {
+        let (key, value) = parse_mapping_entry(lines, pos, indent)?;
+        map.insert(key, value);
+    }
+
+    Ok(JsonValue::Object(map))
+}
+
+fn parse_mapping_entry(lines: &[Line], pos: &mut usize, indent: usize) -> Result<(String, JsonValue), String> {
+    let content = lines[*pos].content;
+    let (key, rest) =
+        split_mapping_line(content).ok_or_else(|| format!("expected `key: value` at indent {indent}: `{content}`"))?;
+    *pos += 1;
+
+    let value = if rest.is_empty() {
+        parse_nested_or_null(lines, pos, indent)?
+    } else {
+        parse_scalar(rest)
+    };
+
+    Ok((key, value))
+}
+
+/// Splits a `key: value` line on the first unquoted `:` that's followed by a
+/// space or the end of the line, unquoting the key.
+fn split_mapping_line(content: &str) -> Option<(String, &str)> {
+    let bytes = content.as_bytes();
+    let mut in_single = false;
+    let mut in_double = false;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'\'' if !in_double => in_single = !in_single,
+            b'"' if !in_single => in_double = !in_double,
+            b':' if !in_single && !in_double && (i + 1 == bytes.len() || bytes[i + 1] == b' ') => {
+                let key = unquote(content[..i].trim());
+                let value = content[i + 1..].trim_start();
+                return Some((key, value));
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn parse_scalar(raw: &str) -> JsonValue {
+    let trimmed = raw.trim();
+
+    if let Some(unquoted) = unquote_str(trimmed) {
+        return JsonValue::String(unquoted);
+    }
+
+    match trimmed {
+        "true" | "True" | "TRUE" => return JsonValue::Bool(true),
+        "false" | "False" | "FALSE" => return JsonValue::Bool(false),
+        "null" | "Null" | "NULL" | "~" | "" => return JsonValue::Null,
+        _ => {}
+    }
+
+    if let Ok(i) = trimmed.parse::<i64>() {
+        return JsonValue::Integer(i);
+    }
+    if let Ok(f) = trimmed.parse::<f64>() {
+        return JsonValue::Float(f);
+    }
+
+    JsonValue::String(trimmed.to_string())
+}
+
+fn unquote(s: &str) -> String {
+    unquote_str(s).unwrap_or_else(|| s.to_string())
+}
+
+fn unquote_str(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    if bytes.len() >= 2 && bytes[0] == b'"' && bytes[bytes.len() - 1] == b'"' {
+        return Some(s[1..s.len() - 1].replace("\\\"", "\""));
+    }
+    if bytes.len() >= 2 && bytes[0] == b'\'' && bytes[bytes.len() - 1] == b'\'' {
+        return Some(s[1..s.len() - 1].replace("''", "'"));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_yaml;
+    use crate::parsers::JsonValue;
+
+    #[test]
+    fn parses_flat_mapping() {
+        let result = parse_yaml("title: My Site\nversion: \"1.0\"").unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        assert_eq!(map.get("title"), Some(&JsonValue::String("My Site".to_string())));
+        assert_eq!(map.get("version"), Some(&JsonValue::String("1.0".to_string())));
+    }
+
+    #[test]
+    fn parses_nested_mapping() {
+        let input = "author:\n  name: John Doe\n  social:\n    twitter: \"@johndoe\"";
+        let result = parse_yaml(input).unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        let JsonValue::Object(author) = &map["author"] else {
+            panic!("expected nested object");
+        };
+        assert_eq!(author.get("name"), Some(&JsonValue::String("John Doe".to_string())));
+        let JsonValue::Object(social) = &author["social"] else {
+            panic!("expected nested object");
+        };
+        assert_eq!(social.get("twitter"), Some(&JsonValue::String("@johndoe".to_string())));
+    }
+
+    #[test]
+    fn parses_sequence_of_mappings() {
+        let input = "navigation:\n  - name: Home\n    url: /\n  - name: About\n    url: /about";
+        let result = parse_yaml(input).unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        let JsonValue::Array(items) = &map["navigation"] else {
+            panic!("expected array");
+        };
+        assert_eq!(items.len(), 2);
+        let JsonValue::Object(first) = &items[0] else {
+            panic!("expected object");
+        };
+        assert_eq!(first.get("name"), Some(&JsonValue::String("Home".to_string())));
+        assert_eq!(first.get("url"), Some(&JsonValue::String("/".to_string())));
+    }
+
+    #[test]
+    fn parses_sequence_of_scalars() {
+        let input = "tags:\n  - rust\n  - static-site";
+        let result = parse_yaml(input).unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        assert_eq!(
+            map["tags"],
+            JsonValue::Array(vec![
+                JsonValue::String("rust".to_string()),
+                JsonValue::String("static-site".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_scalar_types() {
+        let input = "count: 3\nratio: 1.5\nactive: true\nmissing: null\nempty:";
+        let result = parse_yaml(input).unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        assert_eq!(map.get("count"), Some(&JsonValue::Integer(3)));
+        assert_eq!(map.get("ratio"), Some(&JsonValue::Float(1.5)));
+        assert_eq!(map.get("active"), Some(&JsonValue::Bool(true)));
+        assert_eq!(map.get("missing"), Some(&JsonValue::Null));
+        assert_eq!(map.get("empty"), Some(&JsonValue::Null));
+    }
+
+    #[test]
+    fn ignores_comments_and_blank_lines() {
+        let input = "# a comment\ntitle: My Site\n\n# another\nversion: 1";
+        let result = parse_yaml(input).unwrap();
+        let JsonValue::Object(map) = result else {
+            panic!("expected object");
+        };
+        assert_eq!(map.get("title"), Some(&JsonValue::String("My Site".to_string())));
+        assert_eq!(map.get("version"), Some(&JsonValue::Integer(1)));
+    }
+
+    #[test]
+    fn empty_document_is_null() {
+        assert_eq!(parse_yaml("").unwrap(), JsonValue::Null);
+        assert_eq!(parse_yaml("# just a comment").unwrap(), JsonValue::Null);
+    }
+}
@@ -1,18 +1,23 @@
 use crate::config::{LAYOUTS_SUBDIR, SITES_BASE_DIR};
 use crate::error::Result;
 use crate::layout::{insert_body_into_layout, load_layout};
+use crate::pagination::{
+    add_posts_collection_to_variables, chunk_posts_for_pagination, pagination_output_subdir,
+    pagination_page_url, DEFAULT_PAGINATION_PATH,
+};
 
-use crate::template_processors::markdown::markdown_to_html;
-use crate::template_processors::process_template_tags;
-use crate::types::{TemplateIncludes, Variables};
+use crate::template_processors::{process_shortcodes, process_template_tags};
+use crate::types::{ContentCollection, TemplateIncludes, Variables};
 use crate::write::write_html_to_file;
 
 /// Processes a page through the template pipeline:
-/// 1. Converts markdown to HTML (if content is markdown)
-/// 2. Inserts into secondary layout (if specified)
-/// 3. Inserts into main layout (can be overridden via `main_layout` in front matter)
-/// 4. Processes all template tags (liquid includes + conditionals + variables)
-/// 5. Writes to file
+/// 1. Expands shortcode tags in the raw body (resolved against `includes`)
+/// 2. Converts markdown or Org content to HTML, injecting a table of
+///    contents when requested (if content is markdown or Org)
+/// 3. Inserts into secondary layout (if specified)
+/// 4. Inserts into main layout (can be overridden via `main_layout` in front matter)
+/// 5. Processes all template tags (liquid includes + conditionals + variables)
+/// 6. Writes to file
 pub fn render_page(
     body: &str,
     directory: &str,
@@ -21,6 +26,15 @@ pub fn render_page(
     includes: &TemplateIncludes,
     variables: &Variables,
 ) -> Result<()> {
+    // Expand shortcode tags (`{{ name(arg: ...) }}` / `{% name(...) %}...{% endname %}`)
+    // before anything else, so a shortcode's own output - which may itself
+    // be markdown - still goes through the markdown conversion below.
+    // Shortcodes resolve against the same per-site `includes` registry
+    // `{% include %}` partials use; a shortcode is just a named template
+    // invoked with function-call syntax instead.
+    let body = process_shortcodes(body, includes, variables)?;
+    let body = body.as_str();
+
     // Determine output extension from source file name:
     // - If original source file is like name.<ext>.liquid -> use <ext> for output
     // - Otherwise, default to .html
@@ -52,20 +66,23 @@ pub fn render_page(
         }
     }
 
-    // Check if the content is markdown or HTML or liquid template
+    // Check if the content is markdown, Org, HTML, or a liquid template
     let is_markdown = variables.get("file_type").is_none_or(|ft| ft == "md");
+    let is_org = variables.get("file_type").is_some_and(|ft| ft == "org");
     let is_liquid = variables.get("file_type").is_some_and(|ft| ft == "liquid");
 
     // Process the body content first
-    let processed_body = if is_markdown {
-        markdown_to_html(body)
-    } else {
+    let processed_body = if is_markdown || is_org {
+        // Route through the shared processor with `variables` doubling as the
+        // content item, so the same markdown/Org conversion, heading-id, and
+        // `{% toc %}` support content_processor::process_content offers is
+        // available on the real render path, not just in its own tests.
+        process_template_tags(body, variables, None, Some(variables))?
+    } else if is_liquid {
         // For liquid files, process the template variables first
-        if is_liquid {
-            process_template_tags(body, variables, None, None)?
-        } else {
-            body.to_string()
-        }
+        process_template_tags(body, variables, None, None)?
+    } else {
+        body.to_string()
     };
 
     // Apply secondary layout if specified in front matter
@@ -117,6 +134,69 @@ pub fn render_page(
     Ok(())
 }
 
+/// Renders a front-matter-driven pagination page: slices `collection` into
+/// `paginate_by`-sized chunks and delegates each one to [`render_page`], so
+/// pagination composes with whatever secondary/main layout `slug` already
+/// resolves to, unchanged.
+///
+/// `collection_name` is the `paginate_over` front-matter value - the name
+/// the page's template iterates under (e.g. `{% for post in posts %}`) -
+/// and is used to flatten each chunk's items into `{collection_name}.N.*`
+/// variables via [`add_posts_collection_to_variables`]. Every other
+/// `Variables` entry (front matter, global variables) is carried over to
+/// every chunk unchanged.
+///
+/// The first page is written to `{directory}{slug}/index.html`; every later
+/// page to `{directory}{slug}/page/{n}/index.html`, mirroring
+/// [`crate::generate_pagination_pages`]'s index-first-page convention. Each
+/// chunk also receives `paginator.current_index`, `paginator.number_of_pages`,
+/// and (where applicable) `paginator.previous_page_url`/`paginator.next_page_url`
+/// so a template can render prev/next controls.
+#[allow(clippy::too_many_arguments)]
+pub fn render_paginated(
+    body: &str,
+    directory: &str,
+    slug: &str,
+    layout: &str,
+    includes: &TemplateIncludes,
+    variables: &Variables,
+    collection_name: &str,
+    collection: &ContentCollection,
+    paginate_by: usize,
+) -> Result<()> {
+    let base_url = format!("/{slug}");
+    let output_prefix = format!("{slug}/");
+
+    for (page_num, total_pages, page_items) in chunk_posts_for_pagination(paginate_by, collection) {
+        let mut page_variables = variables.clone();
+        add_posts_collection_to_variables(&mut page_variables, collection_name, page_items);
+
+        page_variables.insert("paginator.current_index".to_string(), page_num.to_string());
+        page_variables.insert("paginator.number_of_pages".to_string(), total_pages.to_string());
+        page_variables.insert("paginator.has_previous".to_string(), (page_num > 1).to_string());
+        page_variables.insert("paginator.has_next".to_string(), (page_num < total_pages).to_string());
+        if page_num > 1 {
+            page_variables.insert(
+                "paginator.previous_page_url".to_string(),
+                pagination_page_url(&base_url, DEFAULT_PAGINATION_PATH, page_num - 1, true),
+            );
+        }
+        if page_num < total_pages {
+            page_variables.insert(
+                "paginator.next_page_url".to_string(),
+                pagination_page_url(&base_url, DEFAULT_PAGINATION_PATH, page_num + 1, true),
+            );
+        }
+
+        let output_subdir = pagination_output_subdir(&output_prefix, DEFAULT_PAGINATION_PATH, page_num, true);
+        let page_directory = format!("{directory}{output_subdir}");
+
+        render_page(body, &page_directory, "index", layout, includes, &page_variables)?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,4 +267,80 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_render_page_expands_shortcode_before_markdown_conversion() {
+        let body = "# Title\n\n{{ youtube(id=\"abc\") }}";
+        let directory = "out/render_tests/";
+        let slug = "shortcode_page";
+        let main_layout = "{{body}}";
+
+        let mut includes: TemplateIncludes = HashMap::new();
+        includes.insert("youtube".into(), "Watch {{ id }}".into());
+
+        let mut variables: Variables = HashMap::new();
+        variables.insert("file_type".into(), "md".into());
+
+        render_page(body, directory, slug, main_layout, &includes, &variables)
+            .expect("render_page failed");
+
+        let out_path = format!("{}{}.html", directory, slug);
+        let content = read_file(&out_path);
+        assert_eq!(content, "<h1 id=\"title\">Title</h1>Watch abc");
+    }
+
+    fn post_with_title(title: &str) -> crate::types::ContentItem {
+        let mut item = HashMap::new();
+        item.insert("title".to_string(), title.to_string());
+        item
+    }
+
+    #[test]
+    fn test_render_paginated_writes_one_file_per_chunk() {
+        let body = "{% for post in posts %}{{ post.title }} {% endfor %}";
+        let directory = "out/render_tests/";
+        let slug = "paginated_blog";
+        let main_layout = "{{body}}";
+
+        let includes: TemplateIncludes = HashMap::new();
+        let mut variables: Variables = HashMap::new();
+        variables.insert("file_type".into(), "liquid".into());
+
+        let posts: ContentCollection = (1..=3).map(|i| post_with_title(&format!("Post {i}"))).collect();
+
+        render_paginated(body, directory, slug, main_layout, &includes, &variables, "posts", &posts, 2)
+            .expect("render_paginated failed");
+
+        let page1 = read_file(&format!("{directory}{slug}/index.html"));
+        assert!(page1.contains("Post 1"));
+        assert!(page1.contains("Post 2"));
+        assert!(!page1.contains("Post 3"));
+
+        let page2 = read_file(&format!("{directory}{slug}/page/2/index.html"));
+        assert!(page2.contains("Post 3"));
+        assert!(!page2.contains("Post 1"));
+    }
+
+    #[test]
+    fn test_render_paginated_injects_paginator_navigation_variables() {
+        let body = "{{ paginator.current_index }}/{{ paginator.number_of_pages }}{% if paginator.has_next %} next:{{ paginator.next_page_url }}{% endif %}";
+        let directory = "out/render_tests/";
+        let slug = "paginated_nav";
+        let main_layout = "{{body}}";
+
+        let includes: TemplateIncludes = HashMap::new();
+        let mut variables: Variables = HashMap::new();
+        variables.insert("file_type".into(), "liquid".into());
+
+        let posts: ContentCollection = (1..=3).map(|i| post_with_title(&format!("Post {i}"))).collect();
+
+        render_paginated(body, directory, slug, main_layout, &includes, &variables, "posts", &posts, 1)
+            .expect("render_paginated failed");
+
+        let page1 = read_file(&format!("{directory}{slug}/index.html"));
+        assert_eq!(page1, "1/3 next:/paginated_nav/page/2/");
+
+        let page3 = read_file(&format!("{directory}{slug}/page/3/index.html"));
+        assert_eq!(page3, "3/3");
+    }
 }
@@ -1,38 +1,199 @@
+use crate::config::SiteConfig;
 use crate::error::Result;
+use crate::generate_taxonomy_pages::group_posts_by_taxonomy;
 use crate::template_processors::process_template_tags;
 use crate::types::{ContentCollection, ContentItem, TemplateIncludes, Variables};
 use crate::write::write_html_to_file;
 
-pub fn generate_rss_feed(
-    _site_name: &str,
+/// A single post, sorted into feed order and rendered to HTML, ready to be
+/// formatted into whichever syndication format is being generated.
+struct FeedItem {
+    title: String,
+    slug: String,
+    date: String,
+    html_content: String,
+}
+
+/// How posts are ordered before a feed is trimmed to its item-count limit,
+/// selected via `global_variables`'s `feed_sort` entry. Defaults to `Date`,
+/// preserving the feed's original newest-first behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeedSortMode {
+    /// Newest `date` first.
+    Date,
+    /// Ascending by a numeric `order`/`weight` front-matter field, falling
+    /// back to newest `date` first when two posts share the same value.
+    Order,
+    /// Alphabetical by `title`.
+    Title,
+}
+
+impl FeedSortMode {
+    fn from_variables(global_variables: &Variables) -> Self {
+        match global_variables.get("feed_sort").map(String::as_str) {
+            Some("order") => FeedSortMode::Order,
+            Some("title") => FeedSortMode::Title,
+            _ => FeedSortMode::Date,
+        }
+    }
+}
+
+/// Orders posts for a feed per `mode`, mirroring how
+/// [`crate::pagination::sort_posts_by_mode`] orders a taxonomy term's posts
+/// before pagination.
+fn sort_posts_for_feed(posts: &mut [&ContentItem], mode: FeedSortMode) {
+    match mode {
+        FeedSortMode::Date => posts.sort_by(|a, b| {
+            let empty_string = String::new();
+            let date_a = a.get("date").unwrap_or(&empty_string);
+            let date_b = b.get("date").unwrap_or(&empty_string);
+            date_b.cmp(date_a)
+        }),
+        FeedSortMode::Order => posts.sort_by(|a, b| {
+            let order_a = a
+                .get("order")
+                .or_else(|| a.get("weight"))
+                .and_then(|value| value.parse::<i64>().ok());
+            let order_b = b
+                .get("order")
+                .or_else(|| b.get("weight"))
+                .and_then(|value| value.parse::<i64>().ok());
+            let by_date = || {
+                let empty_string = String::new();
+                let date_a = a.get("date").unwrap_or(&empty_string);
+                let date_b = b.get("date").unwrap_or(&empty_string);
+                date_b.cmp(date_a)
+            };
+            cmp_order_missing_last(order_a, order_b, by_date)
+        }),
+        FeedSortMode::Title => posts.sort_by(|a, b| {
+            let empty_string = String::new();
+            let title_a = a.get("title").unwrap_or(&empty_string);
+            let title_b = b.get("title").unwrap_or(&empty_string);
+            title_a.cmp(title_b)
+        }),
+    }
+}
+
+/// Compares two optional `order`/`weight` values for [`FeedSortMode::Order`],
+/// mirroring [`crate::pagination::sort_posts_by_post_sort_order`]'s
+/// `cmp_missing_last`: a missing value always sorts after a present one, and
+/// ties (both missing, or both present with the same value) fall back to
+/// `on_tie` (the feed's date ordering).
+fn cmp_order_missing_last(
+    a: Option<i64>,
+    b: Option<i64>,
+    on_tie: impl FnOnce() -> std::cmp::Ordering,
+) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b).then_with(on_tie),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => on_tie(),
+    }
+}
+
+/// Resolves the feed item-count limit: an explicit `feed_limit` variable
+/// overrides the default of 20 latest posts.
+fn resolve_feed_limit(global_variables: &Variables) -> usize {
+    global_variables
+        .get("feed_limit")
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|&limit| limit > 0)
+        .unwrap_or(20)
+}
+
+/// Resolves the link path template feed items are linked with: an explicit
+/// `feed_link_path` variable (containing a `{slug}` placeholder) overrides
+/// the default `posts/{slug}`.
+fn resolve_link_path_template(global_variables: &Variables) -> &str {
+    global_variables
+        .get("feed_link_path")
+        .map_or("posts/{slug}", String::as_str)
+}
+
+/// Builds a feed item's absolute URL from `site_url` and `link_path_template`.
+fn feed_item_url(site_url: &str, link_path_template: &str, slug: &str) -> String {
+    format!("{site_url}/{}", link_path_template.replace("{slug}", slug))
+}
+
+/// Shared by all three feed generators: sorts posts per [`FeedSortMode`]
+/// (`global_variables`'s `feed_sort` entry), keeps the `feed_limit` latest
+/// (20 by default), and runs each one's content through
+/// [`process_template_tags`] (handling liquid includes, markdown, etc.) so
+/// RSS, Atom and JSON Feed all render posts identically.
+fn collect_latest_feed_items(
     posts: &ContentCollection,
     includes: &TemplateIncludes,
     global_variables: &Variables,
-) -> Result<()> {
-    // Get the 20 latest posts sorted by date (newest first)
+) -> Result<Vec<FeedItem>> {
     let mut sorted_post_refs: Vec<&ContentItem> = posts.iter().collect();
-    sorted_post_refs.sort_by(|a, b| {
-        let empty_string = String::new();
-        let date_a = a.get("date").unwrap_or(&empty_string);
-        let date_b = b.get("date").unwrap_or(&empty_string);
-        date_b.cmp(date_a) // Reverse order for newest first
-    });
+    sort_posts_for_feed(
+        &mut sorted_post_refs,
+        FeedSortMode::from_variables(global_variables),
+    );
 
-    // Take only the 20 latest posts
-    let latest_posts: Vec<&ContentItem> = sorted_post_refs.into_iter().take(20).collect();
+    let limit = resolve_feed_limit(global_variables);
 
-    // Get site information
-    let site_title = global_variables
-        .get("title")
-        .map_or("My Site", String::as_str);
-    let site_description = global_variables
-        .get("description")
-        .map_or("Latest posts from my site", String::as_str);
-    let site_url = global_variables
-        .get("site_url")
-        .map_or("https://example.com", String::as_str);
+    sorted_post_refs
+        .into_iter()
+        .take(limit)
+        .map(|post| {
+            let empty_string = String::new();
+            let content = post.get("content").unwrap_or(&empty_string);
+            let html_content =
+                process_template_tags(content, global_variables, Some(includes), Some(post))?;
+
+            Ok(FeedItem {
+                title: post.get("title").unwrap_or(&empty_string).clone(),
+                slug: post.get("slug").unwrap_or(&empty_string).clone(),
+                date: post.get("date").unwrap_or(&empty_string).clone(),
+                html_content,
+            })
+        })
+        .collect()
+}
+
+/// Generates the syndication feed formats selected via `global_variables`'s
+/// `feed_formats` entry - a comma-separated list of `rss`, `atom` and/or
+/// `json`. Defaults to `rss` alone when the variable is absent, so sites
+/// that don't opt in keep today's single-format behavior.
+pub fn generate_feeds(
+    site_name: &str,
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    global_variables: &Variables,
+) -> Result<()> {
+    let formats = global_variables
+        .get("feed_formats")
+        .map_or("rss", String::as_str);
+
+    for format in formats.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        match format {
+            "rss" => generate_rss_feed(site_name, posts, includes, global_variables)?,
+            "atom" => generate_atom_feed(site_name, posts, includes, global_variables)?,
+            "json" => generate_json_feed(site_name, posts, includes, global_variables)?,
+            unknown => eprintln!("Warning: Unknown feed format '{unknown}', skipping."),
+        }
+    }
 
-    // Start building RSS XML
+    Ok(())
+}
+
+/// Builds an RSS 2.0 `<rss>` document's full XML for `items`, parameterized
+/// by `feed_url` (the feed's own self-link) and `link_path_template` (how
+/// each item's `<link>`/`<guid>` is built via [`feed_item_url`]) so
+/// [`generate_rss_feed`] and [`generate_taxonomy_feeds`] can share the same
+/// renderer for the site-wide feed and per-term feeds alike.
+#[allow(clippy::too_many_arguments)]
+fn render_rss_xml(
+    items: &[FeedItem],
+    site_title: &str,
+    site_description: &str,
+    site_url: &str,
+    feed_url: &str,
+    link_path_template: &str,
+) -> String {
     let mut rss_xml = String::new();
 
     // XML declaration and RSS opening
@@ -48,8 +209,8 @@ pub fn generate_rss_feed(
     ));
     rss_xml.push_str(&format!("    <link>{}</link>\n", escape_xml(site_url)));
     rss_xml.push_str(&format!(
-        "    <atom:link href=\"{}/feed.xml\" rel=\"self\" type=\"application/rss+xml\" />\n",
-        escape_xml(site_url)
+        "    <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\" />\n",
+        escape_xml(feed_url)
     ));
     rss_xml.push_str("    <language>en-us</language>\n");
     rss_xml.push_str("    <generator>lepkefing static site generator</generator>\n");
@@ -62,36 +223,22 @@ pub fn generate_rss_feed(
     ));
 
     // Add items
-    for post in &latest_posts {
-        let empty_string = String::new();
-        let title = post.get("title").unwrap_or(&empty_string);
-        let slug = post.get("slug").unwrap_or(&empty_string);
-        let date = post.get("date").unwrap_or(&empty_string);
-        let content = post.get("content").unwrap_or(&empty_string);
-
-        // Process content through centralized processor (handles liquid includes, markdown, etc.)
-        let html_content =
-            process_template_tags(content, global_variables, Some(includes), Some(post))?;
-
+    for item in items {
         // Format date for RSS (RFC 2822 format)
-        let pub_date = format_date_for_rss(date);
+        let pub_date = format_date_for_rss(&item.date);
+        let item_url = feed_item_url(site_url, link_path_template, &item.slug);
 
         rss_xml.push_str("    <item>\n");
-        rss_xml.push_str(&format!("      <title>{}</title>\n", escape_xml(title)));
         rss_xml.push_str(&format!(
-            "      <link>{}/posts/{}</link>\n",
-            escape_xml(site_url),
-            escape_xml(slug)
-        ));
-        rss_xml.push_str(&format!(
-            "      <guid>{}/posts/{}</guid>\n",
-            escape_xml(site_url),
-            escape_xml(slug)
+            "      <title>{}</title>\n",
+            escape_xml(&item.title)
         ));
+        rss_xml.push_str(&format!("      <link>{}</link>\n", escape_xml(&item_url)));
+        rss_xml.push_str(&format!("      <guid>{}</guid>\n", escape_xml(&item_url)));
         rss_xml.push_str(&format!("      <pubDate>{}</pubDate>\n", pub_date));
         rss_xml.push_str(&format!(
             "      <description><![CDATA[{}]]></description>\n",
-            html_content
+            item.html_content
         ));
         rss_xml.push_str("    </item>\n");
     }
@@ -100,11 +247,208 @@ pub fn generate_rss_feed(
     rss_xml.push_str("  </channel>\n");
     rss_xml.push_str("</rss>\n");
 
+    rss_xml
+}
+
+pub fn generate_rss_feed(
+    _site_name: &str,
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    global_variables: &Variables,
+) -> Result<()> {
+    let items = collect_latest_feed_items(posts, includes, global_variables)?;
+
+    // Get site information
+    let site_title = global_variables
+        .get("title")
+        .map_or("My Site", String::as_str);
+    let site_description = global_variables
+        .get("description")
+        .map_or("Latest posts from my site", String::as_str);
+    let site_url = global_variables
+        .get("site_url")
+        .map_or("https://example.com", String::as_str);
+    let link_path_template = resolve_link_path_template(global_variables);
+
+    let rss_xml = render_rss_xml(
+        &items,
+        site_title,
+        site_description,
+        site_url,
+        &format!("{site_url}/feed.xml"),
+        link_path_template,
+    );
+
     // Write RSS feed to file
     let output_path = "out/feed.xml";
     write_html_to_file(output_path, &rss_xml)?;
 
-    println!("✓ Generated RSS feed with {} posts", latest_posts.len());
+    println!("✓ Generated RSS feed with {} posts", items.len());
+
+    Ok(())
+}
+
+/// Generates a separate RSS feed per term of every configured taxonomy
+/// (e.g. `out/tags/rust/feed.xml`), filtered to only the posts carrying
+/// that term, reusing [`group_posts_by_taxonomy`] to group posts the same
+/// way [`crate::generate_taxonomy_pages::generate_taxonomy_pages`] does for
+/// paginated term pages.
+pub fn generate_taxonomy_feeds(
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    global_variables: &Variables,
+    config: &SiteConfig,
+) -> Result<()> {
+    let site_title = global_variables
+        .get("title")
+        .map_or("My Site", String::as_str);
+    let site_url = global_variables
+        .get("site_url")
+        .map_or("https://example.com", String::as_str);
+    let link_path_template = resolve_link_path_template(global_variables);
+
+    for taxonomy in &config.taxonomies {
+        let terms = group_posts_by_taxonomy(posts, taxonomy);
+
+        for (term_slug, (term_name, term_posts)) in terms {
+            let items = collect_latest_feed_items(&term_posts, includes, global_variables)?;
+            let site_description = format!("Posts in {}: {}", taxonomy.name, term_name);
+            let feed_url = format!("{site_url}/{}/{term_slug}/feed.xml", taxonomy.name);
+
+            let rss_xml = render_rss_xml(
+                &items,
+                site_title,
+                &site_description,
+                site_url,
+                &feed_url,
+                link_path_template,
+            );
+
+            let output_path = format!(
+                "{}/{}/{term_slug}/feed.xml",
+                config.output_dir, taxonomy.name
+            );
+            write_html_to_file(&output_path, &rss_xml)?;
+
+            println!(
+                "✓ Generated {}/{term_slug} feed with {} posts",
+                taxonomy.name,
+                items.len()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates an Atom 1.0 feed (`out/atom.xml`) from the same post
+/// collection the RSS feed uses.
+pub fn generate_atom_feed(
+    _site_name: &str,
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    global_variables: &Variables,
+) -> Result<()> {
+    let items = collect_latest_feed_items(posts, includes, global_variables)?;
+
+    let site_title = global_variables
+        .get("title")
+        .map_or("My Site", String::as_str);
+    let site_url = global_variables
+        .get("site_url")
+        .map_or("https://example.com", String::as_str);
+    let link_path_template = resolve_link_path_template(global_variables);
+
+    let mut atom_xml = String::new();
+    atom_xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    atom_xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    atom_xml.push_str(&format!("  <title>{}</title>\n", escape_xml(site_title)));
+    atom_xml.push_str(&format!("  <link href=\"{}\" />\n", escape_xml(site_url)));
+    atom_xml.push_str(&format!(
+        "  <link href=\"{}/atom.xml\" rel=\"self\" />\n",
+        escape_xml(site_url)
+    ));
+    atom_xml.push_str(&format!("  <id>{}/</id>\n", escape_xml(site_url)));
+    atom_xml.push_str(&format!(
+        "  <updated>{}</updated>\n",
+        get_current_rfc3339_date()
+    ));
+
+    for item in &items {
+        let entry_url = feed_item_url(site_url, link_path_template, &item.slug);
+
+        atom_xml.push_str("  <entry>\n");
+        atom_xml.push_str(&format!("    <title>{}</title>\n", escape_xml(&item.title)));
+        atom_xml.push_str(&format!("    <id>{}</id>\n", escape_xml(&entry_url)));
+        atom_xml.push_str(&format!(
+            "    <link rel=\"alternate\" href=\"{}\" />\n",
+            escape_xml(&entry_url)
+        ));
+        atom_xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            format_date_for_atom(&item.date)
+        ));
+        atom_xml.push_str(&format!(
+            "    <content type=\"html\">{}</content>\n",
+            escape_xml(&item.html_content)
+        ));
+        atom_xml.push_str("  </entry>\n");
+    }
+
+    atom_xml.push_str("</feed>\n");
+
+    write_html_to_file("out/atom.xml", &atom_xml)?;
+
+    println!("✓ Generated Atom feed with {} posts", items.len());
+
+    Ok(())
+}
+
+/// Generates a JSON Feed 1.1 document (`out/feed.json`) from the same post
+/// collection the RSS and Atom feeds use.
+/// <https://www.jsonfeed.org/version/1.1/>
+pub fn generate_json_feed(
+    _site_name: &str,
+    posts: &ContentCollection,
+    includes: &TemplateIncludes,
+    global_variables: &Variables,
+) -> Result<()> {
+    let items = collect_latest_feed_items(posts, includes, global_variables)?;
+
+    let site_title = global_variables
+        .get("title")
+        .map_or("My Site", String::as_str);
+    let site_url = global_variables
+        .get("site_url")
+        .map_or("https://example.com", String::as_str);
+    let link_path_template = resolve_link_path_template(global_variables);
+
+    let item_entries: Vec<String> = items
+        .iter()
+        .map(|item| {
+            let item_url = feed_item_url(site_url, link_path_template, &item.slug);
+            format!(
+                "    {{\n      \"id\": \"{}\",\n      \"url\": \"{}\",\n      \"title\": \"{}\",\n      \"content_html\": \"{}\",\n      \"date_published\": \"{}\"\n    }}",
+                escape_json(&item_url),
+                escape_json(&item_url),
+                escape_json(&item.title),
+                escape_json(&item.html_content),
+                escape_json(&format_date_for_atom(&item.date))
+            )
+        })
+        .collect();
+
+    let json_feed = format!(
+        "{{\n  \"version\": \"https://jsonfeed.org/version/1.1\",\n  \"title\": \"{}\",\n  \"home_page_url\": \"{}\",\n  \"feed_url\": \"{}/feed.json\",\n  \"items\": [\n{}\n  ]\n}}\n",
+        escape_json(site_title),
+        escape_json(site_url),
+        escape_json(site_url),
+        item_entries.join(",\n")
+    );
+
+    write_html_to_file("out/feed.json", &json_feed)?;
+
+    println!("✓ Generated JSON feed with {} posts", items.len());
 
     Ok(())
 }
@@ -118,6 +462,22 @@ fn escape_xml(text: &str) -> String {
         .replace('\'', "&apos;")
 }
 
+/// Escapes characters that would otherwise break a JSON string literal
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 /// Formats a date string for RSS (RFC 2822 format)
 fn format_date_for_rss(date_str: &str) -> String {
     // Try to parse the date string (assuming YYYY-MM-DD format)
@@ -129,6 +489,66 @@ fn format_date_for_rss(date_str: &str) -> String {
     }
 }
 
+/// Formats a date string for Atom/JSON Feed (RFC 3339 format)
+fn format_date_for_atom(date_str: &str) -> String {
+    // Try to parse the date string (assuming YYYY-MM-DD format)
+    if let Some(rfc3339_date) = parse_date_to_rfc3339(date_str) {
+        rfc3339_date
+    } else {
+        // If parsing fails, return current date as fallback
+        get_current_rfc3339_date()
+    }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const WEEKDAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// Converts a day count since the Unix epoch (1970-01-01) to a proleptic
+/// Gregorian `(year, month, day)`, using Howard Hinnant's days-to-civil
+/// algorithm - exact for any day count, unlike a `days / 365` approximation.
+/// <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+    (y, m as u32, d as u32)
+}
+
+/// The inverse of [`civil_from_days`]: converts a proleptic Gregorian
+/// `(year, month, day)` to a day count since the Unix epoch, so a weekday
+/// can be derived for a date that didn't come from a timestamp.
+/// <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = if month > 2 {
+        month as i64 - 3
+    } else {
+        month as i64 + 9
+    };
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Derives the RFC 2822 weekday token for a day count since the Unix
+/// epoch. 1970-01-01 (day 0) was a Thursday, so `(days + 4) % 7` gives the
+/// weekday index with 0 = Sunday.
+fn weekday_name(days: i64) -> &'static str {
+    WEEKDAY_NAMES[(days + 4).rem_euclid(7) as usize]
+}
+
 /// Parses a YYYY-MM-DD date string to RFC 2822 format
 fn parse_date_to_rfc2822(date_str: &str) -> Option<String> {
     let parts: Vec<&str> = date_str.split('-').collect();
@@ -136,7 +556,7 @@ fn parse_date_to_rfc2822(date_str: &str) -> Option<String> {
         return None;
     }
 
-    let year: i32 = parts[0].parse().ok()?;
+    let year: i64 = parts[0].parse().ok()?;
     let month: u32 = parts[1].parse().ok()?;
     let day: u32 = parts[2].parse().ok()?;
 
@@ -145,19 +565,54 @@ fn parse_date_to_rfc2822(date_str: &str) -> Option<String> {
         return None;
     }
 
-    let month_names = [
-        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
-    ];
-
-    let month_name = month_names.get((month - 1) as usize)?;
+    let month_name = MONTH_NAMES.get((month - 1) as usize)?;
+    let weekday = weekday_name(days_from_civil(year, month, day));
 
     // Format as RFC 2822: "Mon, 01 Jan 2024 00:00:00 +0000"
     Some(format!(
-        "Mon, {:02} {} {} 00:00:00 +0000",
-        day, month_name, year
+        "{weekday}, {day:02} {month_name} {year} 00:00:00 +0000"
     ))
 }
 
+/// Parses a YYYY-MM-DD date string to RFC 3339 format
+fn parse_date_to_rfc3339(date_str: &str) -> Option<String> {
+    let parts: Vec<&str> = date_str.split('-').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    let year: i64 = parts[0].parse().ok()?;
+    let month: u32 = parts[1].parse().ok()?;
+    let day: u32 = parts[2].parse().ok()?;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // A YYYY-MM-DD date has no time-of-day component, so 00:00:00 is used,
+    // the same convention parse_date_to_rfc2822 uses for its RFC 2822 time.
+    Some(format!("{year:04}-{month:02}-{day:02}T00:00:00Z"))
+}
+
+/// Gets the current date in RFC 3339 format
+fn get_current_rfc3339_date() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let now = SystemTime::now();
+    let duration = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let timestamp = duration.as_secs();
+
+    let days_since_epoch = (timestamp / 86400) as i64;
+    let (year, month, day) = civil_from_days(days_since_epoch);
+
+    let seconds_of_day = timestamp % 86400;
+    let hour = seconds_of_day / 3600;
+    let minute = (seconds_of_day % 3600) / 60;
+    let second = seconds_of_day % 60;
+
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+}
+
 /// Gets the current date in RFC 2822 format
 fn get_current_rfc2822_date() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -166,23 +621,18 @@ fn get_current_rfc2822_date() -> String {
     let duration = now.duration_since(UNIX_EPOCH).unwrap_or_default();
     let timestamp = duration.as_secs();
 
-    // Convert Unix timestamp to a basic date format
-    // This is a simplified implementation for RFC 2822 format
-    let days_since_epoch = timestamp / 86400;
-    let year = 1970 + (days_since_epoch / 365);
-    let day_of_year = days_since_epoch % 365;
-    let month = (day_of_year / 30) + 1;
-    let day = (day_of_year % 30) + 1;
+    let days_since_epoch = (timestamp / 86400) as i64;
+    let (year, month, day) = civil_from_days(days_since_epoch);
+    let weekday = weekday_name(days_since_epoch);
 
-    let month_names = [
-        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
-    ];
+    let seconds_of_day = timestamp % 86400;
+    let hour = seconds_of_day / 3600;
+    let minute = (seconds_of_day % 3600) / 60;
+    let second = seconds_of_day % 60;
 
-    let month_name = month_names
-        .get((month.min(12) - 1) as usize)
-        .unwrap_or(&"Jan");
+    let month_name = MONTH_NAMES[(month - 1) as usize];
 
-    format!("Mon, {:02} {} {} 00:00:00 +0000", day, month_name, year)
+    format!("{weekday}, {day:02} {month_name} {year} {hour:02}:{minute:02}:{second:02} +0000")
 }
 
 #[cfg(test)]
@@ -193,6 +643,11 @@ mod tests {
     use std::collections::HashMap;
     use std::fs;
     use std::path::Path;
+    use std::sync::Mutex;
+
+    // All feed generators write to fixed paths under OUTPUT_DIR, so tests
+    // that exercise them must not run concurrently with each other.
+    static FEED_TEST_LOCK: Mutex<()> = Mutex::new(());
 
     fn create_test_post(title: &str, date: &str, content: &str) -> ContentItem {
         let mut post = HashMap::new();
@@ -206,6 +661,8 @@ mod tests {
 
     #[test]
     fn test_generate_rss_feed() {
+        let _guard = FEED_TEST_LOCK.lock().unwrap();
+
         // Create test posts
         let posts = vec![
             create_test_post(
@@ -263,6 +720,268 @@ mod tests {
         let _ = fs::remove_dir_all(OUTPUT_DIR);
     }
 
+    #[test]
+    fn test_generate_atom_feed() {
+        let _guard = FEED_TEST_LOCK.lock().unwrap();
+
+        let posts = vec![create_test_post(
+            "First Post",
+            "2024-01-01",
+            "This is the first post content.",
+        )];
+
+        let mut global_variables = Variables::new();
+        global_variables.insert("title".to_string(), "Test Blog".to_string());
+        global_variables.insert(
+            "site_url".to_string(),
+            "https://test.example.com".to_string(),
+        );
+
+        fs::create_dir_all(OUTPUT_DIR).expect("Failed to create out directory");
+        let includes = std::collections::HashMap::new();
+
+        generate_atom_feed("test", &posts, &includes, &global_variables)
+            .expect("Failed to generate Atom feed");
+
+        assert!(Path::new("out/atom.xml").exists());
+
+        let atom_content = fs::read_to_string("out/atom.xml").unwrap();
+        assert!(atom_content.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+        assert!(atom_content.contains("<title>Test Blog</title>"));
+        assert!(atom_content.contains("<id>https://test.example.com/posts/first-post</id>"));
+        assert!(atom_content.contains("<updated>2024-01-01T00:00:00Z</updated>"));
+        assert!(atom_content
+            .contains("<content type=\"html\">This is the first post content.</content>"));
+
+        let _ = fs::remove_file("out/atom.xml");
+    }
+
+    #[test]
+    fn test_generate_json_feed() {
+        let _guard = FEED_TEST_LOCK.lock().unwrap();
+
+        let posts = vec![create_test_post(
+            "First Post",
+            "2024-01-01",
+            "This is the first post content.",
+        )];
+
+        let mut global_variables = Variables::new();
+        global_variables.insert("title".to_string(), "Test Blog".to_string());
+        global_variables.insert(
+            "site_url".to_string(),
+            "https://test.example.com".to_string(),
+        );
+
+        fs::create_dir_all(OUTPUT_DIR).expect("Failed to create out directory");
+        let includes = std::collections::HashMap::new();
+
+        generate_json_feed("test", &posts, &includes, &global_variables)
+            .expect("Failed to generate JSON feed");
+
+        assert!(Path::new("out/feed.json").exists());
+
+        let json_content = fs::read_to_string("out/feed.json").unwrap();
+        assert!(json_content.contains("\"version\": \"https://jsonfeed.org/version/1.1\""));
+        assert!(json_content.contains("\"title\": \"Test Blog\""));
+        assert!(json_content.contains("\"url\": \"https://test.example.com/posts/first-post\""));
+        assert!(json_content.contains("\"date_published\": \"2024-01-01T00:00:00Z\""));
+
+        let _ = fs::remove_file("out/feed.json");
+    }
+
+    #[test]
+    fn test_generate_feeds_defaults_to_rss_only() {
+        let _guard = FEED_TEST_LOCK.lock().unwrap();
+
+        let posts = vec![create_test_post("First Post", "2024-01-01", "Content.")];
+        let global_variables = Variables::new();
+
+        fs::create_dir_all(OUTPUT_DIR).expect("Failed to create out directory");
+        let includes = std::collections::HashMap::new();
+
+        generate_feeds("test", &posts, &includes, &global_variables)
+            .expect("Failed to generate feeds");
+
+        assert!(Path::new("out/feed.xml").exists());
+
+        let _ = fs::remove_file("out/feed.xml");
+    }
+
+    #[test]
+    fn test_generate_feeds_honors_feed_formats_variable() {
+        let _guard = FEED_TEST_LOCK.lock().unwrap();
+
+        let posts = vec![create_test_post("First Post", "2024-01-01", "Content.")];
+        let mut global_variables = Variables::new();
+        global_variables.insert("feed_formats".to_string(), "atom, json".to_string());
+
+        fs::create_dir_all(OUTPUT_DIR).expect("Failed to create out directory");
+        let includes = std::collections::HashMap::new();
+
+        generate_feeds("test", &posts, &includes, &global_variables)
+            .expect("Failed to generate feeds");
+
+        assert!(Path::new("out/atom.xml").exists());
+        assert!(Path::new("out/feed.json").exists());
+
+        let _ = fs::remove_file("out/atom.xml");
+        let _ = fs::remove_file("out/feed.json");
+    }
+
+    #[test]
+    fn test_sort_posts_for_feed_order_falls_back_to_date_on_tie() {
+        let a = create_test_post("A", "2024-01-01", "content");
+        let mut b = create_test_post("B", "2024-01-02", "content");
+        b.insert("order".to_string(), "5".to_string());
+        let mut c = create_test_post("C", "2024-01-03", "content");
+        c.insert("order".to_string(), "5".to_string());
+        let mut d = create_test_post("D", "2024-01-04", "content");
+        d.insert("weight".to_string(), "1".to_string());
+
+        let mut refs: Vec<&ContentItem> = vec![&a, &b, &c, &d];
+        sort_posts_for_feed(&mut refs, FeedSortMode::Order);
+
+        // D (weight 1) first, then B/C (order 5, tied, newest date first),
+        // then A (no order/weight at all, compares Equal to the rest).
+        let titles: Vec<&str> = refs
+            .iter()
+            .map(|post| post.get("title").unwrap().as_str())
+            .collect();
+        assert_eq!(titles[0], "D");
+        assert_eq!(&titles[1..3], &["C", "B"]);
+    }
+
+    #[test]
+    fn test_sort_posts_for_feed_title_mode_sorts_alphabetically() {
+        let a = create_test_post("Zebra", "2024-01-01", "content");
+        let b = create_test_post("Apple", "2024-01-02", "content");
+
+        let mut refs: Vec<&ContentItem> = vec![&a, &b];
+        sort_posts_for_feed(&mut refs, FeedSortMode::Title);
+
+        assert_eq!(refs[0].get("title").unwrap(), "Apple");
+        assert_eq!(refs[1].get("title").unwrap(), "Zebra");
+    }
+
+    #[test]
+    fn test_resolve_feed_limit_honors_variable_and_ignores_zero() {
+        let mut global_variables = Variables::new();
+        assert_eq!(resolve_feed_limit(&global_variables), 20);
+
+        global_variables.insert("feed_limit".to_string(), "5".to_string());
+        assert_eq!(resolve_feed_limit(&global_variables), 5);
+
+        global_variables.insert("feed_limit".to_string(), "0".to_string());
+        assert_eq!(resolve_feed_limit(&global_variables), 20);
+    }
+
+    #[test]
+    fn test_feed_item_url_substitutes_slug_into_custom_template() {
+        assert_eq!(
+            feed_item_url("https://example.com", "posts/{slug}", "hello-world"),
+            "https://example.com/posts/hello-world"
+        );
+        assert_eq!(
+            feed_item_url("https://example.com", "articles/{slug}/", "hello-world"),
+            "https://example.com/articles/hello-world/"
+        );
+    }
+
+    #[test]
+    fn test_generate_rss_feed_honors_feed_link_path_variable() {
+        let _guard = FEED_TEST_LOCK.lock().unwrap();
+
+        let posts = vec![create_test_post("First Post", "2024-01-01", "Content.")];
+        let mut global_variables = Variables::new();
+        global_variables.insert(
+            "site_url".to_string(),
+            "https://test.example.com".to_string(),
+        );
+        global_variables.insert("feed_link_path".to_string(), "articles/{slug}".to_string());
+
+        fs::create_dir_all(OUTPUT_DIR).expect("Failed to create out directory");
+        let includes = std::collections::HashMap::new();
+
+        generate_rss_feed("test", &posts, &includes, &global_variables)
+            .expect("Failed to generate RSS feed");
+
+        let rss_content = fs::read_to_string("out/feed.xml").unwrap();
+        assert!(rss_content.contains("<link>https://test.example.com/articles/first-post</link>"));
+        assert!(rss_content.contains("<guid>https://test.example.com/articles/first-post</guid>"));
+
+        let _ = fs::remove_file("out/feed.xml");
+    }
+
+    #[test]
+    fn test_generate_taxonomy_feeds_writes_a_feed_per_term() {
+        let _guard = FEED_TEST_LOCK.lock().unwrap();
+
+        let mut post_a = create_test_post("Rust Post", "2024-01-01", "About Rust.");
+        post_a.insert("tags".to_string(), "rust, web".to_string());
+        let mut post_b = create_test_post("Cooking Post", "2024-01-02", "About food.");
+        post_b.insert("tags".to_string(), "food".to_string());
+
+        let posts = vec![post_a, post_b];
+
+        let mut global_variables = Variables::new();
+        global_variables.insert(
+            "site_url".to_string(),
+            "https://test.example.com".to_string(),
+        );
+
+        let mut config = SiteConfig {
+            output_dir: "out/test_generate_taxonomy_feeds_writes_a_feed_per_term".to_string(),
+            ..SiteConfig::default()
+        };
+        config.taxonomies.push(crate::config::TaxonomyConfig {
+            name: "tags".to_string(),
+            front_matter_key: "tags".to_string(),
+            multi_valued: true,
+            paginated: true,
+            sort_mode: None,
+        });
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+        fs::create_dir_all(&config.output_dir).expect("Failed to create output directory");
+        let includes = std::collections::HashMap::new();
+
+        generate_taxonomy_feeds(&posts, &includes, &global_variables, &config)
+            .expect("Failed to generate taxonomy feeds");
+
+        let rust_feed_path = format!("{}/tags/rust/feed.xml", config.output_dir);
+        let web_feed_path = format!("{}/tags/web/feed.xml", config.output_dir);
+        let food_feed_path = format!("{}/tags/food/feed.xml", config.output_dir);
+
+        let rust_content = fs::read_to_string(&rust_feed_path).unwrap();
+        assert!(rust_content.contains("Rust Post"));
+        assert!(!rust_content.contains("Cooking Post"));
+
+        let food_content = fs::read_to_string(&food_feed_path).unwrap();
+        assert!(food_content.contains("Cooking Post"));
+        assert!(!food_content.contains("Rust Post"));
+
+        assert!(Path::new(&web_feed_path).exists());
+
+        let _ = fs::remove_dir_all(&config.output_dir);
+    }
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!(escape_json("Hello \"World\""), "Hello \\\"World\\\"");
+        assert_eq!(escape_json("back\\slash"), "back\\\\slash");
+        assert_eq!(escape_json("line\nbreak"), "line\\nbreak");
+    }
+
+    #[test]
+    fn test_parse_date_to_rfc3339() {
+        assert_eq!(
+            parse_date_to_rfc3339("2024-01-01"),
+            Some("2024-01-01T00:00:00Z".to_string())
+        );
+        assert_eq!(parse_date_to_rfc3339("not-a-date"), None);
+    }
+
     #[test]
     fn test_escape_xml() {
         assert_eq!(escape_xml("Hello & World"), "Hello &amp; World");
@@ -290,4 +1009,38 @@ mod tests {
         assert!(formatted.contains("01"));
         assert!(formatted.contains("00:00:00 +0000"));
     }
+
+    #[test]
+    fn test_parse_date_to_rfc2822_computes_correct_weekday() {
+        // 2024-01-01 happens to be a Monday, which the old hardcoded "Mon"
+        // would have matched by coincidence - these dates have other
+        // weekdays, so they only pass with real civil-date arithmetic.
+        assert_eq!(
+            parse_date_to_rfc2822("2000-01-01").unwrap(),
+            "Sat, 01 Jan 2000 00:00:00 +0000"
+        );
+        assert_eq!(
+            parse_date_to_rfc2822("2024-02-29").unwrap(),
+            "Thu, 29 Feb 2024 00:00:00 +0000"
+        );
+        assert_eq!(
+            parse_date_to_rfc2822("1999-12-31").unwrap(),
+            "Fri, 31 Dec 1999 00:00:00 +0000"
+        );
+    }
+
+    #[test]
+    fn test_civil_from_days_and_days_from_civil_round_trip() {
+        for (year, month, day) in [
+            (1970, 1, 1),
+            (2000, 1, 1),
+            (2024, 1, 1),
+            (2024, 2, 29),
+            (1999, 12, 31),
+            (2024, 7, 4),
+        ] {
+            let days = days_from_civil(year, month, day);
+            assert_eq!(civil_from_days(days), (year, month, day));
+        }
+    }
 }
@@ -1,55 +1,263 @@
-use crate::config::OUTPUT_DIR;
-use crate::error::{Error, Result};
+use crate::config::SiteConfig;
+use crate::error::Result;
+use super::request::{self, Header, ParseStatus, EMPTY_HEADER};
 use std::fs;
 use std::io::prelude::*;
 use std::net::TcpStream;
 use std::path::PathBuf;
 use std::time::Duration;
 
-pub(super) fn handle_client(mut stream: TcpStream) -> Result<()> {
-    stream.set_read_timeout(Some(Duration::new(5, 0)))?;
-
-    let mut buffer = [0; 512];
-    match stream.read(&mut buffer) {
-        Ok(bytes_read) => {
-            let request = String::from_utf8_lossy(&buffer[..bytes_read]);
-            // println!("Received a request: {}", request);
-
-            // Parse the request to get the path
-            let path = request.split_whitespace().nth(1).unwrap_or("/");
-            let path = path.trim_start_matches('/');
-
-            // Construct the file path
-            let mut file_path = PathBuf::from(OUTPUT_DIR);
-
-            // If path is empty or just "/", serve index.html
-            if path.is_empty() {
-                file_path.push("index.html");
-            } else {
-                file_path.push(path);
-                // If the path doesn't have an extension, assume it's .html
-                if file_path.extension().is_none() {
-                    file_path.set_extension("html");
+/// Bytes read from the socket per `read()` call.
+const READ_CHUNK: usize = 512;
+/// Upper bound on how many headers a single request may have; past this
+/// [`request::parse_request`] reports [`request::ParseError::TooManyHeaders`].
+const MAX_HEADERS: usize = 32;
+/// How long a connection may sit with no bytes arriving - for the first
+/// request of a freshly accepted socket, and for every subsequent one kept
+/// open via `Connection: keep-alive` - before it's dropped.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serves requests off `stream` until the client closes the connection,
+/// sends `Connection: close`, or goes idle past [`IDLE_TIMEOUT`].
+///
+/// Each request is read into a growing buffer and handed to
+/// [`request::parse_request`]; a [`ParseStatus::Partial`] result just means
+/// more bytes are needed, so the loop keeps reading until the request head
+/// is complete (or too large). Responses are `Content-Length`-delimited so
+/// the socket can be reused for the next request on the same connection.
+pub(super) fn handle_client(
+    mut stream: TcpStream,
+    site_name: &str,
+    config: &SiteConfig,
+) -> Result<()> {
+    stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut chunk = [0u8; READ_CHUNK];
+
+    loop {
+        let (response, keep_alive, consumed) = loop {
+            let mut headers = [EMPTY_HEADER; MAX_HEADERS];
+            match request::parse_request(&buffer, &mut headers) {
+                Ok(ParseStatus::Complete(parsed, consumed)) => {
+                    let keep_alive = wants_keep_alive(parsed.minor_version, parsed.headers);
+                    let response = build_response(parsed.target, site_name, config);
+                    break (response, keep_alive, consumed);
+                }
+                Ok(ParseStatus::Partial) => {
+                    if read_more(&mut stream, &mut buffer, &mut chunk)? {
+                        continue;
+                    }
+                    return Ok(());
+                }
+                Err(_) => {
+                    let _ =
+                        stream.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n");
+                    return Ok(());
                 }
             }
+        };
+
+        stream.write_all(&response)?;
+        stream.flush()?;
+        buffer.drain(..consumed);
+
+        if !keep_alive {
+            return Ok(());
+        }
+    }
+}
+
+/// Reads the next chunk into `buffer`. Returns `Ok(false)` for a graceful
+/// end of the connection - the client closed its write side, or the idle
+/// timeout elapsed with nothing more to say - rather than an error, since
+/// both are routine ways for a keep-alive connection to end.
+fn read_more(stream: &mut TcpStream, buffer: &mut Vec<u8>, chunk: &mut [u8]) -> Result<bool> {
+    match stream.read(chunk) {
+        Ok(0) => Ok(false),
+        Ok(n) => {
+            buffer.extend_from_slice(&chunk[..n]);
+            Ok(true)
+        }
+        Err(e)
+            if matches!(
+                e.kind(),
+                std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+            ) =>
+        {
+            Ok(false)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// HTTP/1.1 defaults to keep-alive and HTTP/1.0 defaults to close; either
+/// is overridden by an explicit `Connection` header.
+fn wants_keep_alive(minor_version: u8, headers: &[Header]) -> bool {
+    let connection = headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case("connection"))
+        .and_then(|header| std::str::from_utf8(header.value).ok())
+        .map(str::trim);
+
+    match connection {
+        Some(value) if value.eq_ignore_ascii_case("close") => false,
+        Some(value) if value.eq_ignore_ascii_case("keep-alive") => true,
+        _ => minor_version >= 1,
+    }
+}
+
+/// Resolves `target` (the request-line path, query string ignored) against
+/// the site's output directory and reads the matching file, building a
+/// `Content-Length`-delimited response either way.
+fn build_response(target: &str, site_name: &str, config: &SiteConfig) -> Vec<u8> {
+    let path = target
+        .split('?')
+        .next()
+        .unwrap_or("/")
+        .trim_start_matches('/');
+
+    let output_dir = PathBuf::from(format!("{}/{site_name}", config.output_dir));
+    let mut file_path = output_dir.clone();
+    if path.is_empty() {
+        file_path.push("index.html");
+    } else {
+        file_path.push(path);
+        if file_path.extension().is_none() {
+            file_path.set_extension("html");
+        }
+    }
 
-            // Read the file and construct the response
-            println!("File path: {}", file_path.display());
-            let response = match fs::canonicalize(&file_path).and_then(|path| {
-                println!("Trying to serve file: {}", path.display());
-                fs::read_to_string(path)
-            }) {
-                Ok(contents) => format!("HTTP/1.1 200 OK\r\n\r\n{}", contents),
-                Err(e) => format!("HTTP/1.1 404 Not Found\r\n\r\nFailed to read file: {}", e),
-            };
-
-            stream.write_all(response.as_bytes())?;
-            stream.flush()?;
-            Ok(())
+    match read_within(&output_dir, &file_path) {
+        Ok(contents) => {
+            let content_type = content_type_for(&file_path);
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n",
+                contents.len()
+            )
+            .into_bytes();
+            response.extend_from_slice(&contents);
+            response
         }
         Err(e) => {
-            eprintln!("Failed to read from stream: {}", e);
-            Err(Error::Io(e))
+            let body = format!("Failed to read file: {e}");
+            let mut response = format!(
+                "HTTP/1.1 404 Not Found\r\nContent-Length: {}\r\n\r\n",
+                body.len()
+            )
+            .into_bytes();
+            response.extend_from_slice(body.as_bytes());
+            response
         }
     }
 }
+
+/// Canonicalizes both `output_dir` and `file_path` and reads the file only if
+/// it's still inside `output_dir` once symlinks and `..` segments are
+/// resolved, so a request like `/../../etc/passwd` can't escape the site's
+/// output directory.
+fn read_within(output_dir: &std::path::Path, file_path: &std::path::Path) -> std::io::Result<Vec<u8>> {
+    let canonical_root = fs::canonicalize(output_dir)?;
+    let canonical_file = fs::canonicalize(file_path)?;
+    if !canonical_file.starts_with(&canonical_root) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "path escapes the site's output directory",
+        ));
+    }
+    fs::read(canonical_file)
+}
+
+/// Maps a file extension to the `Content-Type` a browser expects, falling
+/// back to a generic binary type for anything unrecognized.
+fn content_type_for(file_path: &std::path::Path) -> &'static str {
+    match file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" => "application/json; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "wasm" => "application/wasm",
+        "txt" => "text/plain; charset=utf-8",
+        "xml" => "application/xml",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keep_alive_defaults_for_http_1_1() {
+        assert!(wants_keep_alive(1, &[]));
+    }
+
+    #[test]
+    fn test_close_defaults_for_http_1_0() {
+        assert!(!wants_keep_alive(0, &[]));
+    }
+
+    #[test]
+    fn test_explicit_close_overrides_http_1_1_default() {
+        let headers = [Header {
+            name: "Connection",
+            value: b"close",
+        }];
+        assert!(!wants_keep_alive(1, &headers));
+    }
+
+    #[test]
+    fn test_explicit_keep_alive_overrides_http_1_0_default() {
+        let headers = [Header {
+            name: "Connection",
+            value: b"keep-alive",
+        }];
+        assert!(wants_keep_alive(0, &headers));
+    }
+
+    #[test]
+    fn test_content_type_for_known_extensions() {
+        assert_eq!(content_type_for(std::path::Path::new("a.html")), "text/html; charset=utf-8");
+        assert_eq!(content_type_for(std::path::Path::new("a.png")), "image/png");
+        assert_eq!(content_type_for(std::path::Path::new("a.woff2")), "font/woff2");
+        assert_eq!(content_type_for(std::path::Path::new("a.wasm")), "application/wasm");
+    }
+
+    #[test]
+    fn test_content_type_for_unknown_extension_falls_back_to_octet_stream() {
+        assert_eq!(
+            content_type_for(std::path::Path::new("a.bin")),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn test_read_within_rejects_path_outside_output_dir() {
+        let dir = std::env::temp_dir().join("handle_client_traversal_test");
+        let nested = dir.join("site");
+        fs::create_dir_all(&nested).expect("create test dir");
+        let outside = dir.join("secret.txt");
+        fs::write(&outside, b"secret").expect("write test file");
+
+        let escaping_path = nested.join("../secret.txt");
+        let result = read_within(&nested, &escaping_path);
+
+        assert!(result.is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
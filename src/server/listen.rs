@@ -1,9 +1,16 @@
 use crate::config::SiteConfig;
 use crate::error::Result;
 use super::handle_client::handle_client;
+use super::watch::watch;
 use std::net::TcpListener;
 
 pub fn listen(site_name: &str, config: &SiteConfig) -> Result<()> {
+    if config.watch {
+        let site_name = site_name.to_string();
+        let config = config.clone();
+        std::thread::spawn(move || watch(&site_name, &config));
+    }
+
     let server_addr = format!("{}:{}", config.server_host, config.server_port);
     println!("Starting server on http://{server_addr}");
     let listener = TcpListener::bind(&server_addr)?;
@@ -0,0 +1,8 @@
+// Development server module
+
+mod handle_client;
+mod listen;
+mod request;
+mod watch;
+
+pub use listen::listen;
@@ -0,0 +1,232 @@
+//! A push-style HTTP/1.x request-line/header parser, modeled on `httparse`:
+//! the caller owns the read buffer and a fixed-size header slice, and
+//! [`parse_request`] borrows straight out of both rather than allocating a
+//! `String`/`Vec` per header. Call it again with more bytes appended to the
+//! same buffer when it reports [`ParseStatus::Partial`].
+
+/// A single `name: value` header, borrowed from the request buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct Header<'buf> {
+    pub name: &'buf str,
+    pub value: &'buf [u8],
+}
+
+/// Placeholder used to fill a caller-supplied header slice before parsing,
+/// the same way `httparse::EMPTY_HEADER` does.
+pub const EMPTY_HEADER: Header<'static> = Header {
+    name: "",
+    value: b"",
+};
+
+/// A fully parsed request line plus headers. `'buf` is the request buffer
+/// the method/target/header values are borrowed from; `'h` is the
+/// caller-supplied header slice (see [`parse_request`]) they're stored in.
+#[derive(Debug)]
+pub struct ParsedRequest<'buf, 'h> {
+    pub method: &'buf str,
+    pub target: &'buf str,
+    /// The `N` in `HTTP/1.N`.
+    pub minor_version: u8,
+    pub headers: &'h [Header<'buf>],
+}
+
+/// Outcome of a parse attempt over the bytes accumulated so far.
+pub enum ParseStatus<T> {
+    /// A full request head was found; `usize` is how many leading bytes of
+    /// the buffer it occupies (including the terminating blank line), so
+    /// the caller can drain exactly that much before reading the body or
+    /// the next request.
+    Complete(T, usize),
+    /// Not enough bytes yet; call again once more have arrived.
+    Partial,
+}
+
+/// Why a request head was rejected outright (as opposed to merely
+/// incomplete).
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The request line or a header line didn't look like HTTP.
+    Malformed,
+    /// More header lines than the caller's `headers` slice has room for.
+    TooManyHeaders,
+    /// No blank line terminating the headers within [`MAX_HEAD_BYTES`].
+    HeadTooLarge,
+}
+
+/// Upper bound on the size of the request line + headers, guarding against
+/// a client trickling bytes forever to keep a connection's buffer growing.
+pub const MAX_HEAD_BYTES: usize = 8 * 1024;
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn strip_trailing_cr(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+fn parse_request_line(line: &[u8]) -> Result<(&str, &str, u8), ParseError> {
+    let line = std::str::from_utf8(line).map_err(|_| ParseError::Malformed)?;
+    let mut parts = line.splitn(3, ' ');
+    let method = parts.next().filter(|s| !s.is_empty()).ok_or(ParseError::Malformed)?;
+    let target = parts.next().filter(|s| !s.is_empty()).ok_or(ParseError::Malformed)?;
+    let version = parts.next().ok_or(ParseError::Malformed)?;
+
+    let minor_version = match version {
+        "HTTP/1.0" => 0,
+        "HTTP/1.1" => 1,
+        _ => return Err(ParseError::Malformed),
+    };
+
+    Ok((method, target, minor_version))
+}
+
+fn parse_header_line(line: &[u8]) -> Result<Header<'_>, ParseError> {
+    let colon = line.iter().position(|&b| b == b':').ok_or(ParseError::Malformed)?;
+    let name = std::str::from_utf8(&line[..colon]).map_err(|_| ParseError::Malformed)?;
+    if name.is_empty() {
+        return Err(ParseError::Malformed);
+    }
+    let value = line[colon + 1..]
+        .iter()
+        .position(|&b| !b.is_ascii_whitespace())
+        .map_or(&line[colon + 1..colon + 1], |start| &line[colon + 1 + start..]);
+
+    Ok(Header { name, value })
+}
+
+/// Attempts to parse a request head (request line + headers) from the
+/// front of `buf`, writing headers into the caller-supplied `headers`
+/// slice in order (no per-header allocation).
+///
+/// Returns [`ParseStatus::Partial`] if `buf` doesn't yet contain the blank
+/// line that ends the headers and is still under [`MAX_HEAD_BYTES`]; past
+/// that size it's [`ParseError::HeadTooLarge`] instead. More header lines
+/// than `headers` can hold is [`ParseError::TooManyHeaders`].
+pub fn parse_request<'buf, 'h>(
+    buf: &'buf [u8],
+    headers: &'h mut [Header<'buf>],
+) -> Result<ParseStatus<ParsedRequest<'buf, 'h>>, ParseError> {
+    let Some(head_end) = find_subslice(buf, b"\r\n\r\n") else {
+        return if buf.len() >= MAX_HEAD_BYTES {
+            Err(ParseError::HeadTooLarge)
+        } else {
+            Ok(ParseStatus::Partial)
+        };
+    };
+
+    let head = &buf[..head_end];
+    let mut lines = head.split(|&b| b == b'\n').map(strip_trailing_cr);
+
+    let request_line = lines.next().ok_or(ParseError::Malformed)?;
+    let (method, target, minor_version) = parse_request_line(request_line)?;
+
+    let mut count = 0;
+    for line in lines {
+        if line.is_empty() {
+            continue;
+        }
+        if count >= headers.len() {
+            return Err(ParseError::TooManyHeaders);
+        }
+        headers[count] = parse_header_line(line)?;
+        count += 1;
+    }
+
+    Ok(ParseStatus::Complete(
+        ParsedRequest {
+            method,
+            target,
+            minor_version,
+            headers: &headers[..count],
+        },
+        head_end + 4,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_request_reports_partial() {
+        let buf = b"GET / HTTP/1.1\r\nHost: x";
+        let mut headers = [EMPTY_HEADER; 8];
+        assert!(matches!(
+            parse_request(buf, &mut headers),
+            Ok(ParseStatus::Partial)
+        ));
+    }
+
+    #[test]
+    fn test_complete_request_parses_method_target_version() {
+        let buf = b"GET /index.html HTTP/1.1\r\nHost: example.com\r\n\r\n";
+        let mut headers = [EMPTY_HEADER; 8];
+        let (parsed, consumed) = match parse_request(buf, &mut headers).unwrap() {
+            ParseStatus::Complete(parsed, consumed) => (parsed, consumed),
+            ParseStatus::Partial => panic!("expected Complete"),
+        };
+        assert_eq!(parsed.method, "GET");
+        assert_eq!(parsed.target, "/index.html");
+        assert_eq!(parsed.minor_version, 1);
+        assert_eq!(consumed, buf.len());
+        assert_eq!(parsed.headers.len(), 1);
+        assert_eq!(parsed.headers[0].name, "Host");
+        assert_eq!(parsed.headers[0].value, b"example.com");
+    }
+
+    #[test]
+    fn test_header_value_leading_space_trimmed() {
+        let buf = b"GET / HTTP/1.1\r\nConnection:   keep-alive\r\n\r\n";
+        let mut headers = [EMPTY_HEADER; 8];
+        match parse_request(buf, &mut headers).unwrap() {
+            ParseStatus::Complete(parsed, _) => {
+                assert_eq!(parsed.headers[0].value, b"keep-alive");
+            }
+            ParseStatus::Partial => panic!("expected Complete"),
+        }
+    }
+
+    #[test]
+    fn test_too_many_headers() {
+        let buf = b"GET / HTTP/1.1\r\nA: 1\r\nB: 2\r\nC: 3\r\n\r\n";
+        let mut headers = [EMPTY_HEADER; 2];
+        assert!(matches!(
+            parse_request(buf, &mut headers),
+            Err(ParseError::TooManyHeaders)
+        ));
+    }
+
+    #[test]
+    fn test_malformed_request_line() {
+        let buf = b"NOT A REQUEST\r\n\r\n";
+        let mut headers = [EMPTY_HEADER; 8];
+        assert!(matches!(
+            parse_request(buf, &mut headers),
+            Err(ParseError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_version_is_malformed() {
+        let buf = b"GET / HTTP/2.0\r\n\r\n";
+        let mut headers = [EMPTY_HEADER; 8];
+        assert!(matches!(
+            parse_request(buf, &mut headers),
+            Err(ParseError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn test_oversized_head_without_terminator_errors() {
+        let mut buf = b"GET / HTTP/1.1\r\n".to_vec();
+        buf.extend(std::iter::repeat_n(b'a', MAX_HEAD_BYTES));
+        let mut headers = [EMPTY_HEADER; 8];
+        assert!(matches!(
+            parse_request(&buf, &mut headers),
+            Err(ParseError::HeadTooLarge)
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+//! Polling filesystem watcher that rebuilds the site on change, in the
+//! spirit of `zola serve`. This crate has no dependency manifest to add a
+//! crate like `notify` to, so change detection is done the same from-scratch
+//! way the rest of the crate favors (see `crate::parsers::yaml`/`toml`):
+//! snapshot every watched file's modification time and compare it against
+//! the previous snapshot once per [`SiteConfig::watch_debounce_ms`].
+//!
+//! `crate::generate::generate` has no seam for rebuilding a single file, so
+//! any change - in a post, a layout, anything - triggers a full rebuild.
+//! `crate::build_manifest` already skips reprocessing unchanged assets
+//! within that rebuild, which keeps repeated saves cheap in practice.
+
+use crate::config::SiteConfig;
+use crate::generate::generate;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Watches `config.watched_paths(site_name)` and rebuilds the site whenever
+/// one of those files changes. Runs until the process exits; intended to be
+/// spawned on its own thread alongside [`super::listen`].
+pub fn watch(site_name: &str, config: &SiteConfig) {
+    let paths = config.watched_paths(site_name);
+    let mut snapshot = snapshot_mtimes(&paths);
+
+    loop {
+        std::thread::sleep(Duration::from_millis(config.watch_debounce_ms));
+
+        let current = snapshot_mtimes(&paths);
+        if current == snapshot {
+            continue;
+        }
+        snapshot = current;
+
+        println!("Change detected, rebuilding {site_name}...");
+        if let Err(e) = generate(site_name, config) {
+            eprintln!("Rebuild failed: {e}");
+        }
+    }
+}
+
+/// Maps every file under `paths` (recursively) to its last-modified time.
+/// Directories that don't exist yet are simply skipped, so a site missing
+/// one of the optional subdirs (e.g. no `data/`) doesn't error.
+fn snapshot_mtimes(paths: &[PathBuf]) -> HashMap<PathBuf, SystemTime> {
+    let mut snapshot = HashMap::new();
+    for root in paths {
+        collect_mtimes(root, &mut snapshot);
+    }
+    snapshot
+}
+
+fn collect_mtimes(dir: &Path, snapshot: &mut HashMap<PathBuf, SystemTime>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_mtimes(&path, snapshot);
+        } else if let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) {
+            snapshot.insert(path, modified);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_mtimes_is_empty_for_missing_directory() {
+        let snapshot = snapshot_mtimes(&[PathBuf::from("/definitely/not/a/real/dir")]);
+        assert!(snapshot.is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_mtimes_detects_a_new_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = vec![dir.path().to_path_buf()];
+
+        let before = snapshot_mtimes(&paths);
+        std::fs::write(dir.path().join("post.md"), "hello").unwrap();
+        let after = snapshot_mtimes(&paths);
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn test_snapshot_mtimes_recurses_into_subdirectories() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("nested");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(nested.join("file.txt"), "hello").unwrap();
+
+        let snapshot = snapshot_mtimes(&[dir.path().to_path_buf()]);
+        assert_eq!(snapshot.len(), 1);
+        assert!(snapshot.contains_key(&nested.join("file.txt")));
+    }
+}
@@ -0,0 +1,130 @@
+//! Writes `out/{site}/sitemap.xml`: one `<url>` entry per post/page content
+//! item, plus every pagination/taxonomy listing page recorded in a
+//! [`SitemapSink`] during [`crate::generate::generate`]. The sitemap format
+//! is simple enough not to need a dependency - mirrors `rss_feed.rs`'s
+//! plain string-building approach to XML, though the two modules keep their
+//! own private copies of the XML-escaping and date-math helpers rather than
+//! sharing one.
+
+use crate::config::SiteConfig;
+use crate::error::Result;
+use crate::pagination::SitemapSink;
+use crate::types::{ContentCollection, ContentItem, Variables};
+use crate::write::write_html_to_file;
+
+/// Escapes a string for safe inclusion in sitemap XML content. Logic mirrors
+/// `rss_feed`'s private `escape_xml`, kept as its own copy here since that
+/// one isn't `pub`.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Converts a day count since the Unix epoch (1970-01-01) to a proleptic
+/// Gregorian `(year, month, day)`, using Howard Hinnant's days-to-civil
+/// algorithm - exact for any day count, unlike a `days / 365` approximation.
+/// Duplicated from `rss_feed`'s private copy of the same algorithm, since
+/// that one isn't `pub` either.
+/// <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+    (y, m as u32, d as u32)
+}
+
+/// Converts the `generated_date` epoch-seconds string `generate` computes
+/// into a `YYYY-MM-DD` fallback `<lastmod>`, for content items with no
+/// front-matter `date`.
+fn lastmod_from_generated_date(generated_date: &str) -> String {
+    let Some(timestamp) = generated_date.parse::<u64>().ok() else {
+        return generated_date.to_string();
+    };
+    let days_since_epoch = (timestamp / 86400) as i64;
+    let (year, month, day) = civil_from_days(days_since_epoch);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Whether a content item should be left out of the sitemap: `unlisted`
+/// posts (the same flag [`crate::generate::generate_site_content`] filters
+/// out of pagination) and anything carrying `sitemap: false`.
+fn is_excluded_from_sitemap(item: &ContentItem) -> bool {
+    item.get("unlisted").is_some_and(|value| value.eq_ignore_ascii_case("true"))
+        || item.get("sitemap").is_some_and(|value| value.eq_ignore_ascii_case("false"))
+}
+
+/// Builds one `<url>` element for a post/page, linked at `{site_url}/{link_path_prefix}{slug}`.
+fn content_item_url_entry(site_url: &str, link_path_prefix: &str, item: &ContentItem, fallback_lastmod: &str) -> Option<String> {
+    if is_excluded_from_sitemap(item) {
+        return None;
+    }
+
+    let slug = item.get("slug")?;
+    let loc = format!("{site_url}/{link_path_prefix}{slug}");
+    let lastmod = item.get("date").map_or(fallback_lastmod, String::as_str);
+
+    Some(format!(
+        "  <url>\n    <loc>{}</loc>\n    <lastmod>{}</lastmod>\n  </url>\n",
+        escape_xml(&loc),
+        escape_xml(lastmod)
+    ))
+}
+
+/// Generates `out/{site}/sitemap.xml`, combining `posts` and `pages` entries
+/// with every pagination/taxonomy page URL already recorded in `sitemap`.
+pub fn generate_sitemap(
+    site_name: &str,
+    posts: &ContentCollection,
+    pages: &ContentCollection,
+    global_variables: &Variables,
+    sitemap: &SitemapSink,
+    config: &SiteConfig,
+) -> Result<()> {
+    let site_url = global_variables
+        .get("site_url")
+        .map_or("https://example.com", String::as_str);
+    let fallback_lastmod = global_variables
+        .get("generated_date")
+        .map_or_else(String::new, |generated_date| lastmod_from_generated_date(generated_date));
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+
+    for post in posts {
+        if let Some(entry) = content_item_url_entry(site_url, "posts/", post, &fallback_lastmod) {
+            xml.push_str(&entry);
+        }
+    }
+
+    for page in pages {
+        if let Some(entry) = content_item_url_entry(site_url, "", page, &fallback_lastmod) {
+            xml.push_str(&entry);
+        }
+    }
+
+    for url in sitemap.urls() {
+        xml.push_str(&format!(
+            "  <url>\n    <loc>{site_url}{}</loc>\n    <lastmod>{}</lastmod>\n  </url>\n",
+            escape_xml(url),
+            escape_xml(&fallback_lastmod)
+        ));
+    }
+
+    xml.push_str("</urlset>\n");
+
+    let output_path = format!("{}/{site_name}/sitemap.xml", config.output_dir);
+    write_html_to_file(&output_path, &xml)?;
+
+    Ok(())
+}
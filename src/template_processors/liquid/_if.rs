@@ -1,112 +1,241 @@
-use crate::error::{Error, Result};
+use crate::error::Result;
 use std::collections::HashMap;
-use std::iter::Peekable;
-use std::str::Chars;
-
-/// Represents a nested-aware conditional block
-#[derive(Debug, PartialEq)]
-struct NestedIfBlock {
-    start: usize,
-    end: usize,
-    condition: String,
-    inner_content: String,
-}
 
-/// Finds the next IF block with proper nested depth awareness
-fn find_nested_if_block(template: &str, start_pos: usize) -> Result<Option<NestedIfBlock>> {
-    let template_slice = &template[start_pos..];
-
-    // Find the start of the next {% if tag
-    let Some(if_start_rel) = template_slice.find("{% if") else {
-        return Ok(None);
-    };
-    let if_start = start_pos + if_start_rel;
-
-    // Find the end of the opening tag
-    let Some(opening_end_rel) = template_slice[if_start_rel..].find("%}") else {
-        return Err(Error::Liquid("Unclosed {% if tag".to_string()));
-    };
-    let opening_end = if_start + opening_end_rel + 2;
-
-    // Extract the condition from the opening tag
-    let condition_start = if_start + 5; // Skip "{% if"
-    let condition_end = opening_end - 2; // Before "%}"
-    let condition = template[condition_start..condition_end].trim().to_string();
-
-    // Now use character iteration to find the matching {% endif %} with proper nesting
-    let mut chars = template[opening_end..].chars().peekable();
-    let inner_content = read_nested_if_content(&mut chars)?;
-
-    // Calculate the end position
-    let content_len = inner_content.len();
-    let inner_end = opening_end + content_len;
-    let endif_end = inner_end + "{% endif %}".len();
-
-    Ok(Some(NestedIfBlock {
-        start: if_start,
-        end: endif_end,
-        condition,
-        inner_content,
-    }))
+/// A resolved operand in a condition expression: either a literal (a quoted
+/// string or a bare numeric token) or an identifier to be looked up in
+/// `variables`.
+enum ConditionValue {
+    Literal(String),
+    Identifier(String),
 }
 
-/// Reads the content of an IF block until finding the matching endif, tracking nested depth
-fn read_nested_if_content(chars: &mut Peekable<Chars>) -> Result<String> {
-    let mut content = String::new();
-    let mut depth = 1i32; // We start inside an {% if %} block
+impl ConditionValue {
+    /// Parses a single token into a literal or an identifier. Quoted tokens
+    /// and bare tokens that parse as `f64` are literals; everything else is
+    /// an identifier.
+    fn parse(token: &str) -> Self {
+        let is_quoted = token.len() >= 2
+            && ((token.starts_with('"') && token.ends_with('"'))
+                || (token.starts_with('\'') && token.ends_with('\'')));
+
+        if is_quoted {
+            ConditionValue::Literal(token[1..token.len() - 1].to_string())
+        } else if token.parse::<f64>().is_ok() {
+            ConditionValue::Literal(token.to_string())
+        } else {
+            ConditionValue::Identifier(token.to_string())
+        }
+    }
 
-    while depth > 0 {
-        let Some(c) = chars.next() else {
-            return Err(Error::Liquid(
-                "Unclosed block - missing {% endif %}".to_string(),
-            ));
-        };
+    /// Resolves this operand to its string value: a literal's own text, or
+    /// the variable's value (empty if the variable is missing).
+    fn resolve(&self, variables: &HashMap<String, String>) -> String {
+        match self {
+            ConditionValue::Literal(value) => value.clone(),
+            ConditionValue::Identifier(name) => variables.get(name).cloned().unwrap_or_default(),
+        }
+    }
 
-        if c == '{' && chars.peek() == Some(&'%') {
-            chars.next(); // consume '%'
-            let mut tag_content = String::new();
-
-            // Read the tag content until %}
-            while let Some(tc) = chars.next() {
-                if tc == '%' && chars.peek() == Some(&'}') {
-                    chars.next(); // consume '}'
-                    break;
-                }
-                tag_content.push(tc);
+    /// The existing truthiness rule: present, non-empty, not `"false"`.
+    /// A literal is judged by its own text; an identifier by its variable's
+    /// value.
+    fn is_truthy(&self, variables: &HashMap<String, String>) -> bool {
+        match self {
+            ConditionValue::Literal(value) => {
+                let trimmed = value.trim();
+                !trimmed.is_empty() && trimmed != "false"
             }
+            ConditionValue::Identifier(name) => variables.get(name).is_some_and(|v| {
+                let trimmed = v.trim();
+                !trimmed.is_empty() && trimmed != "false"
+            }),
+        }
+    }
+}
 
-            let trimmed = tag_content.trim();
+/// Splits a condition string into atoms, quoted strings, comparison
+/// operators, and the `and`/`or`/`not` keywords (the keywords tokenize like
+/// any other bare word; the parser below gives them meaning).
+fn tokenize_condition(condition: &str) -> Vec<String> {
+    let chars: Vec<char> = condition.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
 
-            // Check if this affects our depth
-            if trimmed.starts_with("if ") {
-                depth += 1;
-            } else if trimmed == "endif" {
-                depth -= 1;
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c == '"' || c == '\'' {
+            let quote = c;
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != quote {
+                i += 1;
             }
+            if i < chars.len() {
+                i += 1; // consume closing quote
+            }
+            tokens.push(chars[start..i].iter().collect());
+            continue;
+        }
 
-            // Only add to content if we're still inside the block
-            if depth > 0 {
-                content.push_str("{% ");
-                content.push_str(trimmed);
-                content.push_str(" %}");
+        if i + 1 < chars.len() {
+            let two: String = chars[i..i + 2].iter().collect();
+            if matches!(two.as_str(), "==" | "!=" | "<=" | ">=") {
+                tokens.push(two);
+                i += 2;
+                continue;
             }
-        } else if depth > 0 {
-            content.push(c);
         }
+
+        if c == '<' || c == '>' {
+            tokens.push(c.to_string());
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len()
+            && !chars[i].is_whitespace()
+            && !matches!(chars[i], '"' | '\'' | '=' | '!' | '<' | '>')
+        {
+            i += 1;
+        }
+        tokens.push(chars[start..i].iter().collect());
     }
 
-    Ok(content)
+    tokens
+}
+
+/// Recursive-descent evaluator for `{% if %}` condition expressions, with
+/// precedence (lowest to highest) `or` -> `and` -> `not` -> comparison ->
+/// atom.
+struct ConditionParser<'a> {
+    tokens: Vec<String>,
+    pos: usize,
+    variables: &'a HashMap<String, String>,
+}
+
+impl<'a> ConditionParser<'a> {
+    fn new(condition: &str, variables: &'a HashMap<String, String>) -> Self {
+        ConditionParser {
+            tokens: tokenize_condition(condition),
+            pos: 0,
+            variables,
+        }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn evaluate(&mut self) -> bool {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> bool {
+        let mut result = self.parse_and();
+        while self.peek() == Some("or") {
+            self.next();
+            let right = self.parse_and();
+            result = result || right;
+        }
+        result
+    }
+
+    fn parse_and(&mut self) -> bool {
+        let mut result = self.parse_not();
+        while self.peek() == Some("and") {
+            self.next();
+            let right = self.parse_not();
+            result = result && right;
+        }
+        result
+    }
+
+    fn parse_not(&mut self) -> bool {
+        if self.peek() == Some("not") {
+            self.next();
+            !self.parse_not()
+        } else {
+            self.parse_comparison()
+        }
+    }
+
+    fn parse_comparison(&mut self) -> bool {
+        let Some(left_token) = self.next() else {
+            return false;
+        };
+        let left = ConditionValue::parse(&left_token);
+
+        let operator = self.peek().filter(|op| {
+            matches!(*op, "==" | "!=" | "<" | ">" | "<=" | ">=")
+        });
+
+        let Some(operator) = operator.map(str::to_string) else {
+            return left.is_truthy(self.variables);
+        };
+        self.next();
+
+        let Some(right_token) = self.next() else {
+            return false;
+        };
+        let right = ConditionValue::parse(&right_token);
+
+        let left_value = left.resolve(self.variables);
+        let right_value = right.resolve(self.variables);
+
+        let ordering = match (left_value.parse::<f64>(), right_value.parse::<f64>()) {
+            (Ok(l), Ok(r)) => l.partial_cmp(&r).unwrap_or(std::cmp::Ordering::Equal),
+            _ => left_value.trim().cmp(right_value.trim()),
+        };
+
+        match operator.as_str() {
+            "==" => ordering.is_eq(),
+            "!=" => !ordering.is_eq(),
+            "<" => ordering.is_lt(),
+            ">" => ordering.is_gt(),
+            "<=" => ordering.is_le(),
+            ">=" => ordering.is_ge(),
+            _ => false,
+        }
+    }
+}
+
+/// Evaluates a `{% if %}` condition expression against `variables`,
+/// supporting `==`, `!=`, `<`, `>`, `<=`, `>=` comparisons, `and`/`or`/`not`,
+/// and bare-identifier truthiness.
+pub(super) fn evaluate_condition(condition: &str, variables: &HashMap<String, String>) -> bool {
+    ConditionParser::new(condition, variables).evaluate()
 }
 
 /// Processes Liquid conditional tags in a template string with proper nested support.
 ///
-/// This function handles {% if condition %}content{% endif %} tags by:
-/// - Keeping the content if the condition is truthy based on variables
-/// - Removing the content if the condition is falsy
-/// - Properly handling nested {% if %} blocks with depth tracking
+/// This function handles `{% if condition %}...{% elsif condition %}...{% else %}...{% endif %}`
+/// tags by:
+/// - Evaluating each branch's condition in order and keeping the first matching branch's content
+/// - Falling back to the `else` branch, if any, when no condition matches
+/// - Properly handling nested `{% if %}` blocks with depth tracking
 ///
-/// Truthiness: any value present in `variables` that is not empty and not equal to "false".
-/// Missing variables are falsy.
+/// Conditions support `==`, `!=`, `<`, `>`, `<=`, `>=` comparisons and
+/// `and`/`or`/`not` (see [`evaluate_condition`]). A bare identifier with no
+/// comparison falls back to truthiness: present in `variables`, non-empty,
+/// and not equal to "false". Missing variables are falsy.
+///
+/// A thin wrapper over [`super::template::compile`]'s tokenizer/parser: the
+/// template is tokenized once and walked as a nested AST, rather than
+/// re-scanned with `find` at every nesting level the way the previous
+/// implementation did.
 ///
 /// # Arguments
 /// * `template` - The template string containing conditional tags
@@ -118,43 +247,7 @@ pub fn process_liquid_conditional_tags(
     template: &str,
     variables: &HashMap<String, String>,
 ) -> Result<String> {
-    if template.is_empty() {
-        return Ok(String::new());
-    }
-
-    let mut result = template.to_string();
-    let mut replacements = Vec::new();
-    let mut current_pos = 0;
-
-    // Find and process all conditional tags with proper nesting
-    while let Some(if_block) = find_nested_if_block(&result, current_pos)? {
-        let condition = if_block.condition.trim();
-        let is_truthy = variables.get(condition).is_some_and(|v| {
-            let t = v.trim();
-            !t.is_empty() && t != "false"
-        });
-
-        // Recursively process the inner content if the condition is truthy
-        let replacement = if is_truthy {
-            // Process nested IF blocks within the content recursively
-            process_liquid_conditional_tags(&if_block.inner_content, variables)?
-        } else {
-            String::new()
-        };
-
-        replacements.push((if_block.start, if_block.end, replacement));
-        current_pos = if_block.end;
-    }
-
-    // Apply replacements in reverse order to maintain correct positions
-    super::utils::apply_replacements_in_reverse(&mut result, &replacements);
-
-    // Check if there are any unclosed if tags remaining
-    if result.contains("{% if") {
-        return Err(Error::Liquid("Missing {% endif %} tag".to_string()));
-    }
-
-    Ok(result)
+    super::template::render_conditionals(template, variables)
 }
 
 #[cfg(test)]
@@ -271,4 +364,177 @@ mod tests {
                 || error_msg.contains("Missing {% endif %} tag")
         );
     }
+
+    #[test]
+    fn test_equality_comparison_with_string_literal() {
+        let input = "{% if status == \"active\" %}Active{% endif %}";
+        let mut variables = HashMap::new();
+        variables.insert("status".to_string(), "active".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Active"
+        );
+
+        variables.insert("status".to_string(), "inactive".to_string());
+        assert_eq!(process_liquid_conditional_tags(input, &variables).unwrap(), "");
+    }
+
+    #[test]
+    fn test_numeric_inequality_comparison() {
+        let input = "{% if count != 0 %}Has items{% endif %}";
+        let mut variables = HashMap::new();
+        variables.insert("count".to_string(), "3".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Has items"
+        );
+
+        variables.insert("count".to_string(), "0".to_string());
+        assert_eq!(process_liquid_conditional_tags(input, &variables).unwrap(), "");
+    }
+
+    #[test]
+    fn test_numeric_ordering_comparison() {
+        let input = "{% if count > 10 %}Many{% endif %}";
+        let mut variables = HashMap::new();
+        variables.insert("count".to_string(), "25".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Many"
+        );
+
+        variables.insert("count".to_string(), "5".to_string());
+        assert_eq!(process_liquid_conditional_tags(input, &variables).unwrap(), "");
+    }
+
+    #[test]
+    fn test_and_operator() {
+        let input = "{% if a and b %}Both{% endif %}";
+        let mut variables = HashMap::new();
+        variables.insert("a".to_string(), "true".to_string());
+        variables.insert("b".to_string(), "true".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Both"
+        );
+
+        variables.insert("b".to_string(), "false".to_string());
+        assert_eq!(process_liquid_conditional_tags(input, &variables).unwrap(), "");
+    }
+
+    #[test]
+    fn test_not_operator() {
+        let input = "{% if not published %}Draft{% endif %}";
+        let mut variables = HashMap::new();
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Draft"
+        );
+
+        variables.insert("published".to_string(), "true".to_string());
+        assert_eq!(process_liquid_conditional_tags(input, &variables).unwrap(), "");
+    }
+
+    #[test]
+    fn test_or_with_comparisons() {
+        let input = "{% if role == \"admin\" or role == \"editor\" %}Can edit{% endif %}";
+        let mut variables = HashMap::new();
+
+        variables.insert("role".to_string(), "editor".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Can edit"
+        );
+
+        variables.insert("role".to_string(), "viewer".to_string());
+        assert_eq!(process_liquid_conditional_tags(input, &variables).unwrap(), "");
+    }
+
+    #[test]
+    fn test_plain_identifier_condition_still_uses_truthiness() {
+        let input = "{% if published %}Live{% endif %}";
+        let mut variables = HashMap::new();
+        variables.insert("published".to_string(), "true".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Live"
+        );
+    }
+
+    #[test]
+    fn test_if_else_picks_else_branch_when_falsy() {
+        let input = "{% if published %}Live{% else %}Draft{% endif %}";
+        let variables: HashMap<String, String> = HashMap::new();
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Draft"
+        );
+    }
+
+    #[test]
+    fn test_if_else_picks_if_branch_when_truthy() {
+        let input = "{% if published %}Live{% else %}Draft{% endif %}";
+        let mut variables = HashMap::new();
+        variables.insert("published".to_string(), "true".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Live"
+        );
+    }
+
+    #[test]
+    fn test_elsif_chain_picks_first_matching_branch() {
+        let input = "{% if role == \"admin\" %}Admin{% elsif role == \"editor\" %}Editor{% elsif role == \"viewer\" %}Viewer{% else %}Guest{% endif %}";
+        let mut variables = HashMap::new();
+
+        variables.insert("role".to_string(), "editor".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Editor"
+        );
+
+        variables.insert("role".to_string(), "admin".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Admin"
+        );
+
+        variables.insert("role".to_string(), "nobody".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Guest"
+        );
+    }
+
+    #[test]
+    fn test_if_without_else_and_no_match_is_empty() {
+        let input = "{% if role == \"admin\" %}Admin{% elsif role == \"editor\" %}Editor{% endif %}";
+        let mut variables = HashMap::new();
+        variables.insert("role".to_string(), "viewer".to_string());
+        assert_eq!(process_liquid_conditional_tags(input, &variables).unwrap(), "");
+    }
+
+    #[test]
+    fn test_nested_if_else_inside_outer_if_branch() {
+        let input = "{% if outer %}{% if inner %}Inner true{% else %}Inner false{% endif %}{% else %}Outer false{% endif %}";
+        let mut variables = HashMap::new();
+        variables.insert("outer".to_string(), "true".to_string());
+
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Inner false"
+        );
+
+        variables.insert("inner".to_string(), "true".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Inner true"
+        );
+
+        variables.insert("outer".to_string(), "false".to_string());
+        assert_eq!(
+            process_liquid_conditional_tags(input, &variables).unwrap(),
+            "Outer false"
+        );
+    }
 }
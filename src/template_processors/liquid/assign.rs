@@ -1,10 +1,20 @@
 use super::utils::{
-    clear_variables_with_prefix, extract_tag_parameter, get_array_items, parse_assignment,
-    read_until_closing_tag, resolve_variable_value, split_respecting_quotes, trim_quotes,
+    clear_variables_with_prefix, extract_tag_parameter, find_delimiter, get_array_items,
+    parse_assignment, parse_filter_invocation, read_until_closing_tag, resolve_variable_value,
+    skip_leading_whitespace_from, split_pipeline, split_respecting_quotes, trim_quotes,
 };
 use crate::error::{Error, Result};
 use std::collections::HashMap;
 
+/// The result of folding a filter pipeline: an array of property maps
+/// (`where`, `sort`, ...), a flat list of scalars (`map`), or a single
+/// scalar (`first`, `size`, ...), depending on which filter ran last.
+enum FilterValue {
+    Rows(Vec<HashMap<String, String>>),
+    List(Vec<String>),
+    Scalar(String),
+}
+
 /// Processes Liquid assign tags with filter support
 ///
 /// Converts:
@@ -19,31 +29,44 @@ pub fn process_liquid_assign_tags(
     variables: &mut HashMap<String, String>,
 ) -> Result<String> {
     let mut result = String::new();
-    let mut chars = template.chars().peekable();
-
-    while let Some(current) = chars.next() {
-        if current == '{' && chars.peek() == Some(&'%') {
-            chars.next(); // Skip '%'
-
-            // Use utility functions for tag processing
-            let tag_content = read_until_closing_tag(&mut chars)?;
-            let trimmed_content = tag_content.trim();
-
-            if let Some(assign_content) = extract_tag_parameter(trimmed_content, "assign") {
-                // Parse the assign statement
-                process_assign_statement(&assign_content, variables)?;
-                // Assign tags are removed from output (they don't render anything)
-            } else {
-                // Not an assign tag, keep the original tag
-                result.push_str("{% ");
-                result.push_str(trimmed_content);
-                result.push_str(" %}");
-            }
+    let bytes = template.as_bytes();
+    let mut pos = 0;
+
+    while let Some(rel) = find_delimiter(&bytes[pos..], *b"{%") {
+        let tag_start = pos + rel;
+        // A `{%-` opening delimiter trims trailing whitespace from the text
+        // emitted immediately before the tag.
+        let trim_left = bytes.get(tag_start + 2) == Some(&b'-');
+        let before = &template[pos..tag_start];
+        result.push_str(if trim_left { before.trim_end() } else { before });
+
+        // Use utility functions for tag processing
+        let content_start = tag_start + if trim_left { 3 } else { 2 };
+        let (tag_content, trim_right, next_pos) = read_until_closing_tag(template, content_start)?;
+        let trimmed_content = tag_content.trim();
+
+        if let Some(assign_content) = extract_tag_parameter(trimmed_content, "assign") {
+            // Parse the assign statement
+            process_assign_statement(&assign_content, variables)?;
+            // Assign tags are removed from output (they don't render anything)
         } else {
-            result.push(current);
+            // Not an assign tag, keep the original tag, dash markers included
+            // so a later pass (e.g. for/if processing) still sees them.
+            result.push_str(if trim_left { "{%- " } else { "{% " });
+            result.push_str(trimmed_content);
+            result.push_str(if trim_right { " -%}" } else { " %}" });
         }
+
+        // A `-%}` closing delimiter trims leading whitespace from the text
+        // that follows, i.e. the start of the next literal segment.
+        pos = if trim_right {
+            skip_leading_whitespace_from(template, next_pos)
+        } else {
+            next_pos
+        };
     }
 
+    result.push_str(&template[pos..]);
     Ok(result)
 }
 
@@ -51,58 +74,101 @@ fn process_assign_statement(
     statement: &str,
     variables: &mut HashMap<String, String>,
 ) -> Result<()> {
-    // Parse: variable_name = source | filter: args using utility function
+    // Parse: variable_name = source | filter: args | filter: args ...
     let (variable_name, expression) = parse_assignment(statement)
         .ok_or_else(|| Error::Liquid("Invalid assign syntax".to_string()))?;
 
-    // Check if there's a filter
-    if let Some(pipe_pos) = expression.find('|') {
-        let source = expression[..pipe_pos].trim();
-        let filter_part = expression[pipe_pos + 1..].trim();
+    let mut stages = split_pipeline(&expression);
+    let source = if stages.is_empty() {
+        String::new()
+    } else {
+        stages.remove(0)
+    };
 
-        // Process the filter
-        let filtered_result = apply_filter(source, filter_part, variables)?;
+    if stages.is_empty() {
+        // No filter, direct assignment
+        if let Some(value) = resolve_variable_value(&source, variables) {
+            variables.insert(variable_name, value);
+        }
+        return Ok(());
+    }
 
-        // Clear any existing variables with the same prefix before storing new results
-        clear_variables_with_prefix(variables, &variable_name);
+    // Fold each filter left-to-right: the source array feeds filter 1,
+    // whose output feeds filter 2, and so on.
+    let mut value = FilterValue::Rows(get_array_items(&source, variables));
+    for filter_expression in stages {
+        let (filter_name, filter_args) = parse_filter_invocation(&filter_expression)
+            .ok_or_else(|| Error::Liquid("Invalid filter syntax".to_string()))?;
+        value = apply_filter(value, &filter_name, &filter_args)?;
+    }
 
-        // Store filtered results as indexed variables
-        for (index, item) in filtered_result.iter().enumerate() {
-            for (key, value) in item {
-                let full_key = format!("{variable_name}.{index}.{key}");
-                variables.insert(full_key, value.clone());
+    match value {
+        FilterValue::Rows(rows) => {
+            // Clear any existing variables with the same prefix before storing new results
+            clear_variables_with_prefix(variables, &variable_name);
+            for (index, item) in rows.iter().enumerate() {
+                for (key, val) in item {
+                    let full_key = format!("{variable_name}.{index}.{key}");
+                    variables.insert(full_key, val.clone());
+                }
             }
         }
-    } else {
-        // No filter, direct assignment
-        if let Some(value) = resolve_variable_value(&expression, variables) {
-            variables.insert(variable_name.clone(), value);
+        FilterValue::List(list) => {
+            clear_variables_with_prefix(variables, &variable_name);
+            for (index, item) in list.into_iter().enumerate() {
+                variables.insert(format!("{variable_name}.{index}"), item);
+            }
+        }
+        FilterValue::Scalar(scalar) => {
+            variables.insert(variable_name, scalar);
         }
     }
 
     Ok(())
 }
 
-fn apply_filter(
-    source: &str,
-    filter_expression: &str,
-    variables: &HashMap<String, String>,
-) -> Result<Vec<HashMap<String, String>>> {
-    // Parse filter: "name: args"
-    let (filter_name, filter_args) = super::utils::parse_filter_invocation(filter_expression)
-        .ok_or_else(|| Error::Liquid("Invalid filter syntax".to_string()))?;
+/// A filter implementation, keyed by name in [`FILTERS`].
+type Filter = fn(FilterValue, &str) -> Result<FilterValue>;
+
+/// Registry of filters supported in `{% assign %}` pipelines. Adding a
+/// filter is one function plus one entry here, rather than another arm in a
+/// growing `match`.
+const FILTERS: &[(&str, Filter)] = &[
+    ("where", filter_where),
+    ("where_exp", filter_where_exp),
+    ("sort", filter_sort),
+    ("reverse", filter_reverse),
+    ("first", filter_first),
+    ("last", filter_last),
+    ("size", filter_size),
+    ("uniq", filter_uniq),
+    ("map", filter_map),
+];
+
+fn apply_filter(input: FilterValue, filter_name: &str, filter_args: &str) -> Result<FilterValue> {
+    let handler = FILTERS
+        .iter()
+        .find(|(name, _)| *name == filter_name)
+        .map(|(_, handler)| *handler)
+        .ok_or_else(|| Error::Liquid(format!("Unknown filter: {filter_name}")))?;
+
+    handler(input, filter_args)
+}
 
-    match filter_name.as_str() {
-        "where" => apply_where_filter(source, &filter_args, variables),
-        _ => Err(Error::Liquid(format!("Unknown filter: {filter_name}"))),
+/// Unwraps a `FilterValue` into rows, for filters that only operate on an
+/// array of objects.
+fn rows_or_err(input: FilterValue, filter_name: &str) -> Result<Vec<HashMap<String, String>>> {
+    match input {
+        FilterValue::Rows(rows) => Ok(rows),
+        _ => Err(Error::Liquid(format!(
+            "{filter_name} filter requires an array of objects"
+        ))),
     }
 }
 
-fn apply_where_filter(
-    source: &str,
-    args: &str,
-    variables: &HashMap<String, String>,
-) -> Result<Vec<HashMap<String, String>>> {
+fn filter_where(input: FilterValue, args: &str) -> Result<FilterValue> {
+    let rows = rows_or_err(input, "where")?;
+
     // Parse args: "property", value or 'property', value
     let parts = split_respecting_quotes(args);
     if parts.len() != 2 {
@@ -114,11 +180,9 @@ fn apply_where_filter(
     let property = trim_quotes(&parts[0]);
     let target_value = trim_quotes(&parts[1]);
 
-    // Get all items from the source array
-    let source_items = get_array_items(source, variables);
     let mut filtered_items = Vec::new();
 
-    for item in source_items {
+    for item in rows {
         let matches = if target_value == "nil" {
             // For nil, match items that either don't have the property or have it set to nil/empty
             match item.get(property) {
@@ -138,7 +202,225 @@ fn apply_where_filter(
         }
     }
 
-    Ok(filtered_items)
+    Ok(FilterValue::Rows(filtered_items))
+}
+
+/// One side of a `where_exp` comparison: either a literal value or a lookup
+/// of `property` on the item being tested.
+enum Operand {
+    Literal(String),
+    Property(String),
+}
+
+/// The comparison operators `where_exp` understands, longest-first so that
+/// e.g. `>=` isn't mistaken for a bare `>`.
+const WHERE_EXP_OPERATORS: &[&str] = &["==", "!=", ">=", "<=", "contains", ">", "<"];
+
+/// Parses `item_var.property op value` (or `value op item_var.property`)
+/// into its operand/operator/operand parts. The operator is matched at the
+/// leftmost position where any of [`WHERE_EXP_OPERATORS`] occurs.
+fn parse_predicate(expression: &str, item_var: &str) -> Option<(Operand, &'static str, Operand)> {
+    let mut match_at = None;
+    for (index, _) in expression.char_indices() {
+        for op in WHERE_EXP_OPERATORS {
+            if expression[index..].starts_with(op) {
+                match_at = Some((index, *op));
+                break;
+            }
+        }
+        if match_at.is_some() {
+            break;
+        }
+    }
+    let (op_index, op) = match_at?;
+
+    let left = parse_operand(&expression[..op_index], item_var);
+    let right = parse_operand(&expression[op_index + op.len()..], item_var);
+    Some((left, op, right))
+}
+
+fn parse_operand(raw: &str, item_var: &str) -> Operand {
+    let raw = raw.trim();
+    match raw.strip_prefix(item_var).and_then(|rest| rest.strip_prefix('.')) {
+        Some(property) => Operand::Property(property.to_string()),
+        None => Operand::Literal(trim_quotes(raw).to_string()),
+    }
+}
+
+/// Resolves an operand against `item`. A missing property resolves to
+/// `None`, distinct from a property present but empty.
+fn resolve_operand<'a>(operand: &'a Operand, item: &'a HashMap<String, String>) -> Option<&'a str> {
+    match operand {
+        Operand::Literal(value) => Some(value.as_str()),
+        Operand::Property(property) => item.get(property).map(String::as_str),
+    }
+}
+
+fn evaluate_predicate(op: &str, left: Option<&str>, right: Option<&str>) -> bool {
+    match op {
+        "==" => values_equal(left, right),
+        "!=" => !values_equal(left, right),
+        "contains" => left.unwrap_or("").contains(right.unwrap_or("")),
+        _ => {
+            let left = left.unwrap_or("");
+            let right = right.unwrap_or("");
+            let ordering = match (left.parse::<f64>(), right.parse::<f64>()) {
+                (Ok(l), Ok(r)) => l.partial_cmp(&r).unwrap_or(std::cmp::Ordering::Equal),
+                _ => left.cmp(right),
+            };
+            match op {
+                ">" => ordering.is_gt(),
+                ">=" => ordering.is_ge(),
+                "<" => ordering.is_lt(),
+                "<=" => ordering.is_le(),
+                _ => false,
+            }
+        }
+    }
+}
+
+/// `==`/`!=` semantics shared with the two-argument `where` filter: `nil` on
+/// either side matches a missing or empty value.
+fn values_equal(left: Option<&str>, right: Option<&str>) -> bool {
+    if left == Some("nil") || right == Some("nil") {
+        let other = if left == Some("nil") { right } else { left };
+        return other.map_or(true, |v| v.is_empty() || v == "nil");
+    }
+    left == right
+}
+
+fn filter_where_exp(input: FilterValue, args: &str) -> Result<FilterValue> {
+    let rows = rows_or_err(input, "where_exp")?;
+
+    let parts = split_respecting_quotes(args);
+    if parts.len() != 2 {
+        return Err(Error::Liquid(
+            "where_exp filter requires exactly 2 arguments".to_string(),
+        ));
+    }
+
+    let item_var = trim_quotes(&parts[0]);
+    let expression = trim_quotes(&parts[1]);
+
+    let (left, op, right) = parse_predicate(expression, item_var).ok_or_else(|| {
+        Error::Liquid(format!("Invalid where_exp predicate: {expression}"))
+    })?;
+
+    let filtered = rows
+        .into_iter()
+        .filter(|item| {
+            let left_value = resolve_operand(&left, item);
+            let right_value = resolve_operand(&right, item);
+            evaluate_predicate(op, left_value, right_value)
+        })
+        .collect();
+
+    Ok(FilterValue::Rows(filtered))
+}
+
+fn filter_sort(input: FilterValue, args: &str) -> Result<FilterValue> {
+    let mut rows = rows_or_err(input, "sort")?;
+    let property = trim_quotes(args.trim());
+    if property.is_empty() {
+        return Err(Error::Liquid(
+            "sort filter requires a property argument".to_string(),
+        ));
+    }
+
+    rows.sort_by(|a, b| {
+        let a_value = a.get(property).map(String::as_str).unwrap_or("");
+        let b_value = b.get(property).map(String::as_str).unwrap_or("");
+        a_value.cmp(b_value)
+    });
+
+    Ok(FilterValue::Rows(rows))
+}
+
+fn filter_reverse(input: FilterValue, _args: &str) -> Result<FilterValue> {
+    match input {
+        FilterValue::Rows(mut rows) => {
+            rows.reverse();
+            Ok(FilterValue::Rows(rows))
+        }
+        FilterValue::List(mut list) => {
+            list.reverse();
+            Ok(FilterValue::List(list))
+        }
+        FilterValue::Scalar(_) => Err(Error::Liquid(
+            "reverse filter requires an array input".to_string(),
+        )),
+    }
+}
+
+fn filter_first(input: FilterValue, _args: &str) -> Result<FilterValue> {
+    match input {
+        FilterValue::Rows(rows) => Ok(FilterValue::Rows(rows.into_iter().take(1).collect())),
+        FilterValue::List(list) => {
+            Ok(FilterValue::Scalar(list.into_iter().next().unwrap_or_default()))
+        }
+        FilterValue::Scalar(_) => Err(Error::Liquid(
+            "first filter requires an array input".to_string(),
+        )),
+    }
+}
+
+fn filter_last(input: FilterValue, _args: &str) -> Result<FilterValue> {
+    match input {
+        FilterValue::Rows(rows) => Ok(FilterValue::Rows(rows.into_iter().next_back().into_iter().collect())),
+        FilterValue::List(list) => {
+            Ok(FilterValue::Scalar(list.into_iter().next_back().unwrap_or_default()))
+        }
+        FilterValue::Scalar(_) => Err(Error::Liquid(
+            "last filter requires an array input".to_string(),
+        )),
+    }
+}
+
+fn filter_size(input: FilterValue, _args: &str) -> Result<FilterValue> {
+    let size = match &input {
+        FilterValue::Rows(rows) => rows.len(),
+        FilterValue::List(list) => list.len(),
+        FilterValue::Scalar(s) => s.len(),
+    };
+    Ok(FilterValue::Scalar(size.to_string()))
+}
+
+fn filter_uniq(input: FilterValue, args: &str) -> Result<FilterValue> {
+    let rows = rows_or_err(input, "uniq")?;
+    let property = trim_quotes(args.trim());
+    if property.is_empty() {
+        return Err(Error::Liquid(
+            "uniq filter requires a property argument".to_string(),
+        ));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let mut deduped = Vec::new();
+    for item in rows {
+        let key = item.get(property).cloned().unwrap_or_default();
+        if seen.insert(key) {
+            deduped.push(item);
+        }
+    }
+
+    Ok(FilterValue::Rows(deduped))
+}
+
+fn filter_map(input: FilterValue, args: &str) -> Result<FilterValue> {
+    let rows = rows_or_err(input, "map")?;
+    let property = trim_quotes(args.trim());
+    if property.is_empty() {
+        return Err(Error::Liquid(
+            "map filter requires a property argument".to_string(),
+        ));
+    }
+
+    let list = rows
+        .into_iter()
+        .map(|item| item.get(property).cloned().unwrap_or_default())
+        .collect();
+
+    Ok(FilterValue::List(list))
 }
 
 #[cfg(test)]
@@ -242,6 +524,30 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_chained_filter_pipeline() {
+        let mut variables = HashMap::new();
+        variables.insert("users.0.name".to_string(), "Alice".to_string());
+        variables.insert("users.0.active".to_string(), "true".to_string());
+        variables.insert("users.0.vip".to_string(), "true".to_string());
+        variables.insert("users.1.name".to_string(), "Bob".to_string());
+        variables.insert("users.1.active".to_string(), "true".to_string());
+        variables.insert("users.1.vip".to_string(), "false".to_string());
+        variables.insert("users.2.name".to_string(), "Charlie".to_string());
+        variables.insert("users.2.active".to_string(), "false".to_string());
+        variables.insert("users.2.vip".to_string(), "true".to_string());
+
+        let template = r#"{% assign vip_active = users | where: "active", "true" | where: "vip", "true" %}"#;
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(
+            variables.get("vip_active.0.name"),
+            Some(&"Alice".to_string())
+        );
+        assert_eq!(variables.get("vip_active.1.name"), None);
+    }
+
     #[test]
     fn test_invalid_filter() {
         let mut variables = HashMap::new();
@@ -253,6 +559,28 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_assign_trim_markers_remove_surrounding_whitespace() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = "Hi  \n  {%- assign user_name = name -%}  \n  !";
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "Hi!");
+        assert_eq!(variables.get("user_name"), Some(&"Alice".to_string()));
+    }
+
+    #[test]
+    fn test_non_assign_tag_trim_markers_preserved_for_later_passes() {
+        let mut variables = HashMap::new();
+
+        let template = "{%- if condition -%}Hello{% endif %}";
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "{%- if condition -%}Hello{% endif %}");
+    }
+
     #[test]
     fn test_non_assign_tags_unchanged() {
         let mut variables = HashMap::new();
@@ -482,4 +810,240 @@ mod tests {
         assert_eq!(result, "OK");
         assert!(variables.get("x").is_none());
     }
+
+    #[test]
+    fn test_sort_filter() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Charlie".to_string());
+        variables.insert("posts.0.date".to_string(), "2024-03-01".to_string());
+        variables.insert("posts.1.title".to_string(), "Alice".to_string());
+        variables.insert("posts.1.date".to_string(), "2024-01-01".to_string());
+        variables.insert("posts.2.title".to_string(), "Bob".to_string());
+        variables.insert("posts.2.date".to_string(), "2024-02-01".to_string());
+
+        let template = "{% assign sorted_posts = posts | sort: \"date\" %}";
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(
+            variables.get("sorted_posts.0.title"),
+            Some(&"Alice".to_string())
+        );
+        assert_eq!(
+            variables.get("sorted_posts.1.title"),
+            Some(&"Bob".to_string())
+        );
+        assert_eq!(
+            variables.get("sorted_posts.2.title"),
+            Some(&"Charlie".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reverse_filter() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Alice".to_string());
+        variables.insert("posts.1.title".to_string(), "Bob".to_string());
+
+        let template = "{% assign reversed_posts = posts | reverse %}";
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(
+            variables.get("reversed_posts.0.title"),
+            Some(&"Bob".to_string())
+        );
+        assert_eq!(
+            variables.get("reversed_posts.1.title"),
+            Some(&"Alice".to_string())
+        );
+    }
+
+    #[test]
+    fn test_first_and_last_filters() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Alice".to_string());
+        variables.insert("posts.1.title".to_string(), "Bob".to_string());
+        variables.insert("posts.2.title".to_string(), "Charlie".to_string());
+
+        let template = "{% assign newest = posts | map: \"title\" | first %}{% assign oldest = posts | map: \"title\" | last %}";
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(variables.get("newest"), Some(&"Alice".to_string()));
+        assert_eq!(variables.get("oldest"), Some(&"Charlie".to_string()));
+    }
+
+    #[test]
+    fn test_size_filter() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Alice".to_string());
+        variables.insert("posts.1.title".to_string(), "Bob".to_string());
+        variables.insert("posts.2.title".to_string(), "Charlie".to_string());
+
+        let template = "{% assign post_count = posts | size %}";
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(variables.get("post_count"), Some(&"3".to_string()));
+    }
+
+    #[test]
+    fn test_uniq_filter() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.category".to_string(), "news".to_string());
+        variables.insert("posts.1.category".to_string(), "sports".to_string());
+        variables.insert("posts.2.category".to_string(), "news".to_string());
+
+        let template = "{% assign categories = posts | uniq: \"category\" %}";
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(
+            variables.get("categories.0.category"),
+            Some(&"news".to_string())
+        );
+        assert_eq!(
+            variables.get("categories.1.category"),
+            Some(&"sports".to_string())
+        );
+        assert_eq!(variables.get("categories.2.category"), None);
+    }
+
+    #[test]
+    fn test_map_filter() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Alice".to_string());
+        variables.insert("posts.1.title".to_string(), "Bob".to_string());
+
+        let template = "{% assign titles = posts | map: \"title\" %}";
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(variables.get("titles.0"), Some(&"Alice".to_string()));
+        assert_eq!(variables.get("titles.1"), Some(&"Bob".to_string()));
+    }
+
+    #[test]
+    fn test_where_sort_first_pipeline() {
+        let mut variables = HashMap::new();
+        variables.insert("site.posts.0.title".to_string(), "Old Draft".to_string());
+        variables.insert("site.posts.0.draft".to_string(), "true".to_string());
+        variables.insert("site.posts.0.date".to_string(), "2024-01-01".to_string());
+        variables.insert("site.posts.1.title".to_string(), "Published Early".to_string());
+        variables.insert("site.posts.1.date".to_string(), "2024-02-01".to_string());
+        variables.insert("site.posts.2.title".to_string(), "Published Late".to_string());
+        variables.insert("site.posts.2.date".to_string(), "2024-03-01".to_string());
+
+        let template = r#"{% assign latest_post = site.posts | where: "draft", nil | sort: "date" | last %}"#;
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(
+            variables.get("latest_post.0.title"),
+            Some(&"Published Late".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sort_filter_missing_property_argument() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Alice".to_string());
+        let template = "{% assign sorted = posts | sort %}";
+        let result = process_liquid_assign_tags(template, &mut variables);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reverse_filter_on_scalar_errors() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Alice".to_string());
+        let template = "{% assign reversed = posts | size | reverse %}";
+        let result = process_liquid_assign_tags(template, &mut variables);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_where_exp_numeric_greater_than() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Old Post".to_string());
+        variables.insert("posts.0.year".to_string(), "2019".to_string());
+        variables.insert("posts.1.title".to_string(), "New Post".to_string());
+        variables.insert("posts.1.year".to_string(), "2023".to_string());
+
+        let template = r#"{% assign recent = posts | where_exp: "p", "p.year > 2020" %}"#;
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(
+            variables.get("recent.0.title"),
+            Some(&"New Post".to_string())
+        );
+        assert_eq!(variables.get("recent.1.title"), None);
+    }
+
+    #[test]
+    fn test_where_exp_contains() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Rust for beginners".to_string());
+        variables.insert("posts.1.title".to_string(), "Learning Go".to_string());
+
+        let template = r#"{% assign rust_posts = posts | where_exp: "p", "p.title contains Rust" %}"#;
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(
+            variables.get("rust_posts.0.title"),
+            Some(&"Rust for beginners".to_string())
+        );
+        assert_eq!(variables.get("rust_posts.1.title"), None);
+    }
+
+    #[test]
+    fn test_where_exp_nil_semantics_match_where_filter() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Post 1".to_string());
+        // No draft property at all
+        variables.insert("posts.1.title".to_string(), "Post 2".to_string());
+        variables.insert("posts.1.draft".to_string(), "true".to_string());
+
+        let template = r#"{% assign published = posts | where_exp: "p", "p.draft == nil" %}"#;
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(
+            variables.get("published.0.title"),
+            Some(&"Post 1".to_string())
+        );
+        assert_eq!(variables.get("published.1.title"), None);
+    }
+
+    #[test]
+    fn test_where_exp_lexical_fallback_for_non_numeric_ordering() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Alpha".to_string());
+        variables.insert("posts.0.slug".to_string(), "alpha".to_string());
+        variables.insert("posts.1.title".to_string(), "Zulu".to_string());
+        variables.insert("posts.1.slug".to_string(), "zulu".to_string());
+
+        let template = r#"{% assign later = posts | where_exp: "p", "p.slug > alpha" %}"#;
+        let result = process_liquid_assign_tags(template, &mut variables).unwrap();
+
+        assert_eq!(result, "");
+        assert_eq!(
+            variables.get("later.0.title"),
+            Some(&"Zulu".to_string())
+        );
+        assert_eq!(variables.get("later.1.title"), None);
+    }
+
+    #[test]
+    fn test_where_exp_invalid_predicate_errors() {
+        let mut variables = HashMap::new();
+        variables.insert("posts.0.title".to_string(), "Post 1".to_string());
+
+        let template = r#"{% assign filtered = posts | where_exp: "p", "p.year" %}"#;
+        let result = process_liquid_assign_tags(template, &mut variables);
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,347 @@
+use super::utils::{extract_tag_inner, trim_quotes};
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Represents a nested-aware `{% block name %}...{% endblock %}` region.
+struct NestedBlock {
+    start: usize,
+    end: usize,
+    name: String,
+    inner_content: String,
+}
+
+/// Finds the next `{% block %}` region with proper nested depth awareness,
+/// the same way `_if`'s `find_nested_if_block` handles `{% if %}`/`{% endif %}`.
+/// A block may itself contain other `{% block %}` regions, so a plain
+/// first-match search for `{% endblock %}` would stop at the wrong one.
+fn find_nested_block(template: &str, start_pos: usize) -> Result<Option<NestedBlock>> {
+    let template_slice = &template[start_pos..];
+
+    let Some(block_start_rel) = template_slice.find("{% block") else {
+        return Ok(None);
+    };
+    let block_start = start_pos + block_start_rel;
+
+    let Some(opening_end_rel) = template_slice[block_start_rel..].find("%}") else {
+        return Err(Error::Liquid("Unclosed {% block tag".to_string()));
+    };
+    let opening_end = block_start + opening_end_rel + 2;
+
+    let name_start = block_start + "{% block".len();
+    let name_end = opening_end - 2; // Before "%}"
+    let name = template[name_start..name_end].trim().to_string();
+
+    let mut chars = template[opening_end..].chars().peekable();
+    let inner_content = read_nested_block_content(&mut chars)?;
+
+    let inner_end = opening_end + inner_content.len();
+    let endblock_end = inner_end + "{% endblock %}".len();
+
+    Ok(Some(NestedBlock {
+        start: block_start,
+        end: endblock_end,
+        name,
+        inner_content,
+    }))
+}
+
+/// Reads the content of a `{% block %}` until finding its matching
+/// `{% endblock %}`, tracking nested depth.
+fn read_nested_block_content(chars: &mut Peekable<Chars>) -> Result<String> {
+    let mut content = String::new();
+    let mut depth = 1i32; // We start inside a {% block %}
+
+    while depth > 0 {
+        let Some(c) = chars.next() else {
+            return Err(Error::Liquid(
+                "Unclosed block - missing {% endblock %}".to_string(),
+            ));
+        };
+
+        if c == '{' && chars.peek() == Some(&'%') {
+            chars.next(); // consume '%'
+            let mut tag_content = String::new();
+
+            while let Some(tc) = chars.next() {
+                if tc == '%' && chars.peek() == Some(&'}') {
+                    chars.next(); // consume '}'
+                    break;
+                }
+                tag_content.push(tc);
+            }
+
+            let trimmed = tag_content.trim();
+
+            if trimmed.starts_with("block ") {
+                depth += 1;
+            } else if trimmed == "endblock" {
+                depth -= 1;
+            }
+
+            if depth > 0 {
+                content.push_str("{% ");
+                content.push_str(trimmed);
+                content.push_str(" %}");
+            }
+        } else if depth > 0 {
+            content.push(c);
+        }
+    }
+
+    Ok(content)
+}
+
+/// Parses every `{% block name %}...{% endblock %}` region in `template`
+/// into a flat map of block name to its body, including blocks nested
+/// inside other blocks, so a child can override an inner region without
+/// re-declaring its parent.
+fn parse_blocks(template: &str) -> Result<HashMap<String, String>> {
+    let mut blocks = HashMap::new();
+    let mut pos = 0;
+
+    while let Some(block) = find_nested_block(template, pos)? {
+        pos = block.end;
+        blocks.extend(parse_blocks(&block.inner_content)?);
+        blocks.insert(block.name, block.inner_content);
+    }
+
+    Ok(blocks)
+}
+
+/// Finds the `{% extends "name" %}` tag, if any, and returns the normalized
+/// parent template name (quotes and `.liquid` extension stripped).
+fn parse_extends(template: &str) -> Option<String> {
+    let tag_start = template.find("{% extends")?;
+    let tag_end = template[tag_start..]
+        .find("%}")
+        .map(|pos| tag_start + pos + 2)?;
+    let inner = extract_tag_inner(&template[tag_start..tag_end], "extends")?;
+    let name = trim_quotes(inner.trim());
+    let name = name.strip_suffix(".liquid").unwrap_or(name);
+    Some(name.to_string())
+}
+
+/// Replaces each `{% block name %}default{% endblock %}` placeholder in
+/// `template` with the matching override from `overrides`, falling back to
+/// the placeholder's own default body when there is no override. `{{ block.super }}`
+/// inside an override is spliced with the placeholder's default body.
+/// Whichever body is chosen (override or default) is itself scanned for
+/// nested `{% block %}` placeholders, so overrides can reach inner regions.
+fn substitute_blocks(template: &str, overrides: &HashMap<String, String>) -> Result<String> {
+    let mut result = String::new();
+    let mut pos = 0;
+
+    while let Some(block) = find_nested_block(template, pos)? {
+        result.push_str(&template[pos..block.start]);
+
+        let chosen = match overrides.get(&block.name) {
+            Some(override_body) => {
+                override_body.replace("{{ block.super }}", &block.inner_content)
+            }
+            None => block.inner_content,
+        };
+        result.push_str(&substitute_blocks(&chosen, overrides)?);
+
+        pos = block.end;
+    }
+
+    result.push_str(&template[pos..]);
+    Ok(result)
+}
+
+/// Resolves Liquid layout inheritance (`{% extends %}` / `{% block %}`).
+///
+/// A template declaring `{% extends "layout" %}` has its `{% block name %}`
+/// overrides substituted into the named parent's matching placeholders,
+/// falling back to the parent's default body for blocks the child omits.
+/// Parent templates may themselves `extends` a grandparent, in which case
+/// every level's blocks are merged before substituting into the root (the
+/// first ancestor with no `extends` of its own), with a level taking
+/// precedence over its ancestors for any block name they both declare.
+/// Blocks may nest, and a repeated parent name in the chain is reported as
+/// an extends cycle instead of recursing forever.
+///
+/// Templates without an `{% extends %}` tag are returned unchanged.
+pub fn process_liquid_extends(
+    template: &str,
+    templates: &HashMap<String, String>,
+) -> Result<String> {
+    let Some(first_parent) = parse_extends(template) else {
+        return Ok(template.to_string());
+    };
+
+    // Walk the extends chain up to the root, collecting each level's raw
+    // content as we go. `levels[0]` is `template` itself (the leaf); the
+    // last entry pushed is the root, with no `extends` of its own.
+    let mut chain_names = vec![first_parent.clone()];
+    let mut levels: Vec<&str> = vec![template];
+    let mut parent_content = lookup_parent(templates, &first_parent)?;
+
+    loop {
+        levels.push(parent_content);
+        let Some(next_parent) = parse_extends(parent_content) else {
+            break;
+        };
+        if chain_names.contains(&next_parent) {
+            chain_names.push(next_parent);
+            return Err(Error::Liquid(format!(
+                "Extends cycle detected: {}",
+                chain_names.join(" -> ")
+            )));
+        }
+        parent_content = lookup_parent(templates, &next_parent)?;
+        chain_names.push(next_parent);
+    }
+
+    let root = levels.pop().expect("levels always has at least one entry");
+
+    // Merge every non-root level's blocks, leaf-first-reversed so that the
+    // leaf (closest to `template`) overwrites any same-named block declared
+    // by a closer-to-root ancestor.
+    let mut merged_overrides = HashMap::new();
+    for level in levels.iter().rev() {
+        merged_overrides.extend(parse_blocks(level)?);
+    }
+
+    substitute_blocks(root, &merged_overrides)
+}
+
+fn lookup_parent<'a>(templates: &'a HashMap<String, String>, name: &str) -> Result<&'a str> {
+    templates
+        .get(name)
+        .map(String::as_str)
+        .ok_or_else(|| Error::Liquid(format!("Unknown parent template in extends: {name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_extends_returns_template_unchanged() {
+        let templates = HashMap::new();
+        let input = "<p>Hello</p>";
+        assert_eq!(process_liquid_extends(input, &templates).unwrap(), input);
+    }
+
+    #[test]
+    fn test_basic_extends_fills_block() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "layout".to_string(),
+            "<html><body>{% block content %}default{% endblock %}</body></html>".to_string(),
+        );
+
+        let input = "{% extends \"layout\" %}{% block content %}Hello{% endblock %}";
+        let result = process_liquid_extends(input, &templates).unwrap();
+        assert_eq!(result, "<html><body>Hello</body></html>");
+    }
+
+    #[test]
+    fn test_missing_block_falls_back_to_default() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "layout".to_string(),
+            "<body>{% block content %}default{% endblock %}</body>".to_string(),
+        );
+
+        let input = "{% extends \"layout\" %}";
+        let result = process_liquid_extends(input, &templates).unwrap();
+        assert_eq!(result, "<body>default</body>");
+    }
+
+    #[test]
+    fn test_block_super_splices_parent_default() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "layout".to_string(),
+            "<head>{% block head %}<title>Site</title>{% endblock %}</head>".to_string(),
+        );
+
+        let input = "{% extends \"layout\" %}{% block head %}{{ block.super }}<meta charset=\"utf-8\">{% endblock %}";
+        let result = process_liquid_extends(input, &templates).unwrap();
+        assert_eq!(
+            result,
+            "<head><title>Site</title><meta charset=\"utf-8\"></head>"
+        );
+    }
+
+    #[test]
+    fn test_multi_level_extends_chain() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "base".to_string(),
+            "<body>{% block content %}base-default{% endblock %}</body>".to_string(),
+        );
+        templates.insert(
+            "layout".to_string(),
+            "{% extends \"base\" %}{% block content %}layout-default{% endblock %}".to_string(),
+        );
+
+        let input = "{% extends \"layout\" %}{% block content %}page content{% endblock %}";
+        let result = process_liquid_extends(input, &templates).unwrap();
+        assert_eq!(result, "<body>page content</body>");
+    }
+
+    #[test]
+    fn test_unknown_parent_is_an_error() {
+        let templates = HashMap::new();
+        let input = "{% extends \"missing\" %}";
+        assert!(process_liquid_extends(input, &templates).is_err());
+    }
+
+    #[test]
+    fn test_nested_blocks_can_be_overridden_independently() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "layout".to_string(),
+            "{% block page %}<header>{% block header %}default header{% endblock %}</header>\
+             <main>{% block content %}default content{% endblock %}</main>{% endblock %}"
+                .to_string(),
+        );
+
+        // The child overrides only the nested "content" block, leaving the
+        // outer "page" block and the "header" block at their defaults.
+        let input = "{% extends \"layout\" %}{% block content %}custom content{% endblock %}";
+        let result = process_liquid_extends(input, &templates).unwrap();
+        assert_eq!(
+            result,
+            "<header>default header</header><main>custom content</main>"
+        );
+    }
+
+    #[test]
+    fn test_overriding_outer_block_replaces_its_nested_blocks_too() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "layout".to_string(),
+            "{% block page %}<header>{% block header %}default header{% endblock %}</header>{% endblock %}"
+                .to_string(),
+        );
+
+        let input = "{% extends \"layout\" %}{% block page %}<p>replaced entirely</p>{% endblock %}";
+        let result = process_liquid_extends(input, &templates).unwrap();
+        assert_eq!(result, "<p>replaced entirely</p>");
+    }
+
+    #[test]
+    fn test_direct_extends_cycle_is_an_error() {
+        let mut templates = HashMap::new();
+        templates.insert("a".to_string(), "{% extends \"a\" %}".to_string());
+
+        let input = "{% extends \"a\" %}";
+        assert!(process_liquid_extends(input, &templates).is_err());
+    }
+
+    #[test]
+    fn test_indirect_extends_cycle_is_an_error() {
+        let mut templates = HashMap::new();
+        templates.insert("a".to_string(), "{% extends \"b\" %}".to_string());
+        templates.insert("b".to_string(), "{% extends \"a\" %}".to_string());
+
+        let input = "{% extends \"a\" %}";
+        assert!(process_liquid_extends(input, &templates).is_err());
+    }
+}
@@ -0,0 +1,347 @@
+use super::utils::{parse_filter_invocation, split_respecting_quotes, trim_quotes};
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Splits the content between `{{` and `}}` on unescaped `|` (respecting quoted
+/// string literals) into a variable reference followed by its filter invocations.
+///
+/// The variable reference is always present (even if no filters follow) and is
+/// returned already trimmed, matching the historical behavior of a plain
+/// `{{ var }}` lookup.
+pub fn split_filter_chain(content: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut quote_char = '"';
+
+    for ch in content.chars() {
+        match ch {
+            '"' | '\'' if !in_quotes => {
+                in_quotes = true;
+                quote_char = ch;
+                current.push(ch);
+            }
+            c if in_quotes && c == quote_char => {
+                in_quotes = false;
+                current.push(c);
+            }
+            '|' if !in_quotes => {
+                parts.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(ch),
+        }
+    }
+    parts.push(current.trim().to_string());
+
+    parts
+}
+
+/// A filter that transforms an already-resolved value given its raw
+/// (still-quoted) arguments. Used for filters simple enough to never fail -
+/// the extensible part of the registry described in [`simple_filter_registry`].
+type SimpleFilter = fn(&str, &[String]) -> String;
+
+/// Registry of filters that unconditionally transform a present value.
+/// Adding a new filter here is all that's needed to support `{{ x | name }}` -
+/// filters that need to see a missing value (`default`) or can fail on bad
+/// arguments (`truncate`, `replace`) are handled separately in [`apply_filter`].
+fn simple_filter_registry() -> HashMap<&'static str, SimpleFilter> {
+    let mut registry: HashMap<&'static str, SimpleFilter> = HashMap::new();
+    registry.insert("upcase", |v, _| v.to_uppercase());
+    registry.insert("downcase", |v, _| v.to_lowercase());
+    registry.insert("capitalize", |v, _| capitalize(v));
+    registry.insert("escape", |v, _| escape_html(v));
+    registry.insert("append", |v, args| v.to_string() + &filter_arg(args, 0));
+    registry.insert("prepend", |v, args| filter_arg(args, 0) + v);
+    registry.insert("json", |v, _| to_json_string(v));
+    registry
+}
+
+/// Applies a chain of filter invocations (each of the form `name` or
+/// `name: arg1, arg2`) to a resolved variable value, left-to-right.
+///
+/// `value` is `None` when the variable itself could not be resolved - most
+/// filters pass that through unchanged, but `default` substitutes its
+/// argument so the caller can skip re-emitting the original placeholder.
+pub fn apply_filter_chain(
+    mut value: Option<String>,
+    filter_segments: &[String],
+) -> Result<Option<String>> {
+    for segment in filter_segments {
+        value = apply_filter(value, segment)?;
+    }
+    Ok(value)
+}
+
+fn apply_filter(value: Option<String>, segment: &str) -> Result<Option<String>> {
+    let (name, args_str) = match parse_filter_invocation(segment) {
+        Some((name, args)) => (name, args),
+        None => (segment.trim().to_string(), String::new()),
+    };
+    let args: Vec<String> = split_respecting_quotes(&args_str);
+
+    match name.as_str() {
+        "default" => match value {
+            Some(v) if !v.is_empty() => Ok(Some(v)),
+            _ => Ok(Some(filter_arg(&args, 0))),
+        },
+        "truncate" => {
+            let length: usize = filter_arg(&args, 0)
+                .parse()
+                .map_err(|_| Error::Liquid(format!("truncate filter requires a numeric argument, got: {segment}")))?;
+            Ok(value.map(|v| truncate(&v, length)))
+        }
+        "replace" => {
+            if args.len() != 2 {
+                return Err(Error::Liquid(
+                    "replace filter requires exactly 2 arguments".to_string(),
+                ));
+            }
+            let from = trim_quotes(&args[0]);
+            let to = trim_quotes(&args[1]);
+            Ok(value.map(|v| v.replace(from, to)))
+        }
+        _ => match simple_filter_registry().get(name.as_str()) {
+            Some(filter) => Ok(value.map(|v| filter(&v, &args))),
+            None => Err(Error::Liquid(format!("Unknown filter: {name}"))),
+        },
+    }
+}
+
+/// Fetches a filter argument by position, trimming any surrounding quotes.
+/// Missing arguments resolve to an empty string.
+fn filter_arg(args: &[String], index: usize) -> String {
+    args.get(index)
+        .map(|a| trim_quotes(a).to_string())
+        .unwrap_or_default()
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Extracts a filter invocation's name (`"upcase"` from `"upcase"`, `"replace"`
+/// from `"replace: \"a\", \"b\""`) without running the invocation, so callers
+/// that only need to recognize a particular filter by name - such as the
+/// auto-escaping opt-out in [`super::replace_variables`] - don't need to
+/// duplicate [`parse_filter_invocation`]'s split-on-`:` logic.
+pub(super) fn filter_invocation_name(segment: &str) -> String {
+    match parse_filter_invocation(segment) {
+        Some((name, _)) => name,
+        None => segment.trim().to_string(),
+    }
+}
+
+pub(super) fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Serializes `s` as a JSON string literal (quoted, with the characters JSON
+/// requires escaped).
+fn to_json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                use std::fmt::Write;
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn truncate(s: &str, length: usize) -> String {
+    s.chars().take(length).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_filter_chain_no_filters() {
+        assert_eq!(split_filter_chain("name"), vec!["name".to_string()]);
+        assert_eq!(split_filter_chain("  name  "), vec!["name".to_string()]);
+    }
+
+    #[test]
+    fn test_split_filter_chain_single_filter() {
+        assert_eq!(
+            split_filter_chain("name | upcase"),
+            vec!["name".to_string(), "upcase".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_split_filter_chain_multiple_filters_with_args() {
+        assert_eq!(
+            split_filter_chain(r#"name | replace: "a", "b" | upcase"#),
+            vec![
+                "name".to_string(),
+                r#"replace: "a", "b""#.to_string(),
+                "upcase".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_filter_chain_pipe_inside_quotes_is_not_a_separator() {
+        assert_eq!(
+            split_filter_chain(r#"name | replace: "a|b", "c""#),
+            vec!["name".to_string(), r#"replace: "a|b", "c""#.to_string()]
+        );
+    }
+
+    #[test]
+    fn test_apply_filter_chain_upcase_downcase_capitalize() {
+        assert_eq!(
+            apply_filter_chain(Some("Hello".to_string()), &["upcase".to_string()]).unwrap(),
+            Some("HELLO".to_string())
+        );
+        assert_eq!(
+            apply_filter_chain(Some("Hello".to_string()), &["downcase".to_string()]).unwrap(),
+            Some("hello".to_string())
+        );
+        assert_eq!(
+            apply_filter_chain(Some("hello".to_string()), &["capitalize".to_string()]).unwrap(),
+            Some("Hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_filter_chain_default_used_only_when_missing() {
+        assert_eq!(
+            apply_filter_chain(None, &[r#"default: "fallback""#.to_string()]).unwrap(),
+            Some("fallback".to_string())
+        );
+        assert_eq!(
+            apply_filter_chain(Some("value".to_string()), &[r#"default: "fallback""#.to_string()])
+                .unwrap(),
+            Some("value".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_filter_chain_truncate() {
+        assert_eq!(
+            apply_filter_chain(Some("Hello World".to_string()), &["truncate: 5".to_string()])
+                .unwrap(),
+            Some("Hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_filter_chain_truncate_invalid_argument() {
+        let result = apply_filter_chain(Some("Hello".to_string()), &["truncate: abc".to_string()]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_apply_filter_chain_escape() {
+        assert_eq!(
+            apply_filter_chain(
+                Some(r#"<a href="x">'it's'</a> & more"#.to_string()),
+                &["escape".to_string()]
+            )
+            .unwrap(),
+            Some("&lt;a href=&quot;x&quot;&gt;&#39;it&#39;s&#39;&lt;/a&gt; &amp; more".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_filter_chain_replace() {
+        assert_eq!(
+            apply_filter_chain(
+                Some("hello world".to_string()),
+                &[r#"replace: "world", "there""#.to_string()]
+            )
+            .unwrap(),
+            Some("hello there".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_filter_chain_replace_wrong_argument_count() {
+        let result = apply_filter_chain(
+            Some("hello world".to_string()),
+            &[r#"replace: "world""#.to_string()],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_apply_filter_chain_append_prepend() {
+        assert_eq!(
+            apply_filter_chain(Some("file".to_string()), &[r#"append: ".txt""#.to_string()])
+                .unwrap(),
+            Some("file.txt".to_string())
+        );
+        assert_eq!(
+            apply_filter_chain(Some("world".to_string()), &[r#"prepend: "hello ""#.to_string()])
+                .unwrap(),
+            Some("hello world".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_filter_chain_unknown_filter_errors() {
+        let result = apply_filter_chain(Some("value".to_string()), &["frobnicate".to_string()]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_apply_filter_chain_left_to_right_order() {
+        let result = apply_filter_chain(
+            Some("  Hello World  ".to_string()),
+            &["downcase".to_string(), "truncate: 5".to_string()],
+        )
+        .unwrap();
+        assert_eq!(result, Some("  hel".to_string()));
+    }
+
+    #[test]
+    fn test_apply_filter_chain_json() {
+        assert_eq!(
+            apply_filter_chain(Some("hello".to_string()), &["json".to_string()]).unwrap(),
+            Some("\"hello\"".to_string())
+        );
+        assert_eq!(
+            apply_filter_chain(
+                Some("say \"hi\"\nnow".to_string()),
+                &["json".to_string()]
+            )
+            .unwrap(),
+            Some("\"say \\\"hi\\\"\\nnow\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_filter_chain_default_treats_empty_value_as_missing() {
+        assert_eq!(
+            apply_filter_chain(
+                Some(String::new()),
+                &[r#"default: "fallback""#.to_string()]
+            )
+            .unwrap(),
+            Some("fallback".to_string())
+        );
+    }
+}
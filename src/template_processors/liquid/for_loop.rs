@@ -1,5 +1,9 @@
+use super::_if::process_liquid_conditional_tags;
+use super::replace_variables::replace_template_variables;
+use super::unless::process_liquid_unless_tags;
 use super::utils::{
-    find_collection_size, read_until_closing_tag, read_until_endunless, skip_to_endunless,
+    find_collection_size, find_delimiter, find_tag_block, get_array_items, read_nested_block,
+    read_until_closing_tag, read_until_endunless, skip_leading_whitespace_from, skip_to_endunless,
 };
 use crate::error::{Error, Result};
 use std::collections::HashMap;
@@ -22,6 +26,7 @@ use std::collections::HashMap;
 ///
 /// This allows the existing variable replacement system to handle the actual substitution.
 /// Supports nested loops by recursively processing until no more for loops remain.
+/// An `{% else %}` branch, if present, is emitted verbatim when the collection is empty.
 pub fn process_liquid_for_loops(
     template: &str,
     variables: &HashMap<String, String>,
@@ -46,94 +51,199 @@ pub fn process_liquid_for_loops(
 /// Processes a single pass of for loop expansion
 fn process_single_pass(template: &str, variables: &HashMap<String, String>) -> Result<String> {
     let mut result = String::new();
-    let mut chars = template.chars().peekable();
-
-    while let Some(current) = chars.next() {
-        if current == '{' && chars.peek() == Some(&'%') {
-            chars.next(); // Skip '%'
+    let bytes = template.as_bytes();
+    let mut pos = 0;
+
+    while let Some(rel) = find_delimiter(&bytes[pos..], *b"{%") {
+        let tag_start = pos + rel;
+        // A `{%-` opening delimiter trims trailing whitespace from the text
+        // emitted immediately before the tag.
+        let trim_left = bytes.get(tag_start + 2) == Some(&b'-');
+        let before = &template[pos..tag_start];
+        result.push_str(if trim_left { before.trim_end() } else { before });
+
+        // Read tag content until %}
+        let content_start = tag_start + if trim_left { 3 } else { 2 };
+        let (tag_content, trim_right, after_tag) = read_until_closing_tag(template, content_start)?;
+        let tag_content = tag_content.trim().to_string();
+        // A `-%}` closing delimiter trims leading whitespace from the text
+        // that follows - for a for-loop, that's the start of its body.
+        let after_tag = if trim_right {
+            skip_leading_whitespace_from(template, after_tag)
+        } else {
+            after_tag
+        };
 
-            // Read tag content until %}
-            let tag_content = read_until_closing_tag(&mut chars)?;
-            let tag_content = tag_content.trim().to_string();
+        pos = if let Some(for_content) = tag_content.strip_prefix("for ") {
+            // Parse the for loop
+            let parts: Vec<&str> = for_content.split(" in ").collect();
 
-            if let Some(for_content) = tag_content.strip_prefix("for ") {
-                // Parse the for loop
-                let parts: Vec<&str> = for_content.split(" in ").collect();
+            if parts.len() != 2 {
+                return Err(Error::Liquid("Invalid for loop syntax".to_string()));
+            }
 
-                if parts.len() != 2 {
-                    return Err(Error::Liquid("Invalid for loop syntax".to_string()));
+            let item_var = parts[0].trim();
+
+            // Split the RHS into collection identifier and optional parameters
+            let rhs = parts[1].trim();
+            let mut rhs_iter = rhs.split_whitespace();
+            let collection_var = rhs_iter
+                .next()
+                .ok_or_else(|| Error::Liquid("Invalid for loop syntax".to_string()))?
+                .trim();
+            let params_str = rhs_iter.collect::<Vec<_>>().join(" ");
+
+            // Parse optional parameters (limit:N, offset:N, reversed)
+            let mut limit: Option<usize> = None;
+            let mut offset: usize = 0;
+            let mut reversed = false;
+            if !params_str.is_empty() {
+                let params = super::utils::parse_space_separated_key_value_params(&params_str);
+                if let Some(limit_str) = params.get("limit") {
+                    if let Ok(lim) = limit_str.parse::<usize>() {
+                        limit = Some(lim);
+                    }
                 }
-
-                let item_var = parts[0].trim();
-
-                // Split the RHS into collection identifier and optional parameters
-                let rhs = parts[1].trim();
-                let mut rhs_iter = rhs.split_whitespace();
-                let collection_var = rhs_iter
-                    .next()
-                    .ok_or_else(|| Error::Liquid("Invalid for loop syntax".to_string()))?
-                    .trim();
-                let params_str = rhs_iter.collect::<Vec<_>>().join(" ");
-
-                // Parse optional parameters (e.g., limit:10)
-                let mut limit: Option<usize> = None;
-                if !params_str.is_empty() {
-                    let params = super::utils::parse_space_separated_key_value_params(&params_str);
-                    if let Some(limit_str) = params.get("limit") {
-                        if let Ok(lim) = limit_str.parse::<usize>() {
-                            limit = Some(lim);
-                        }
+                if let Some(offset_str) = params.get("offset") {
+                    if let Ok(off) = offset_str.parse::<usize>() {
+                        offset = off;
                     }
                 }
-
-                // Find the loop body until {% endfor %}
-                let loop_body = super::utils::read_nested_block(&mut chars, "for ", "endfor")?;
-
-                // Expand the loop
-                let expanded =
-                    expand_for_loop(item_var, collection_var, &loop_body, variables, limit)?;
-                result.push_str(&expanded);
-            } else {
-                // Not a for loop, keep the original tag
-                result.push_str("{% ");
-                result.push_str(&tag_content);
-                result.push_str(" %}");
+                reversed = params_str.split_whitespace().any(|token| token == "reversed");
             }
+            let params = ForLoopParams { limit, offset, reversed };
+
+            // Find the loop body until {% endfor %}
+            let (loop_body, after_loop) =
+                read_nested_block(template, after_tag, "for ", "endfor")?;
+            let (iteration_body, else_body) = split_loop_body_at_else(&loop_body);
+
+            // Expand the loop
+            let expanded = expand_for_loop(
+                item_var,
+                collection_var,
+                iteration_body,
+                else_body,
+                variables,
+                &params,
+            )?;
+            result.push_str(&expanded);
+            after_loop
         } else {
-            result.push(current);
-        }
+            // Not a for loop, keep the original tag, dash markers included
+            // so a later pass (e.g. if processing) still sees them.
+            result.push_str(if trim_left { "{%- " } else { "{% " });
+            result.push_str(&tag_content);
+            result.push_str(if trim_right { " -%}" } else { " %}" });
+            after_tag
+        };
     }
 
+    result.push_str(&template[pos..]);
     Ok(result)
 }
 
+/// Parsed `limit:`/`offset:`/`reversed` for-loop parameters, applied in that
+/// order when turning a collection size into the sequence of positions to
+/// iterate (see [`build_iteration_sequence`]).
+struct ForLoopParams {
+    limit: Option<usize>,
+    offset: usize,
+    reversed: bool,
+}
+
+/// Parses a `(A..B)` integer-range for-loop source, e.g. `(1..5)`. `A`/`B`
+/// may be integer literals or names resolvable via `variables`.
+fn parse_range(token: &str, variables: &HashMap<String, String>) -> Option<(i64, i64)> {
+    let inner = token.strip_prefix('(')?.strip_suffix(')')?;
+    let (start_str, end_str) = inner.split_once("..")?;
+    let start = resolve_range_bound(start_str.trim(), variables)?;
+    let end = resolve_range_bound(end_str.trim(), variables)?;
+    Some((start, end))
+}
+
+fn resolve_range_bound(s: &str, variables: &HashMap<String, String>) -> Option<i64> {
+    s.parse::<i64>().ok().or_else(|| variables.get(s)?.parse::<i64>().ok())
+}
+
+/// Turns a collection size into the ordered positions to iterate, applying
+/// `offset`, then `limit`, then `reversed` - in that order, matching how
+/// standard Liquid composes these three for-loop parameters.
+fn build_iteration_sequence(total_size: usize, params: &ForLoopParams) -> Vec<usize> {
+    let mut sequence: Vec<usize> = (params.offset.min(total_size)..total_size).collect();
+    if let Some(limit) = params.limit {
+        sequence.truncate(limit);
+    }
+    if params.reversed {
+        sequence.reverse();
+    }
+    sequence
+}
+
+/// Splits a for-loop's body into its iteration body and, if present, the
+/// part after a top-level `{% else %}` tag - the fallback rendered when the
+/// collection has no items. Ignores `{% else %}` tags belonging to a nested
+/// `{% if %}`, `{% unless %}`, or `{% for %}` block.
+fn split_loop_body_at_else(body: &str) -> (&str, Option<&str>) {
+    let mut depth = 0i32;
+    let mut search_from = 0;
+
+    while let Some(rel) = find_delimiter(body[search_from..].as_bytes(), *b"{%") {
+        let tag_start = search_from + rel;
+        let Some(close_rel) = find_delimiter(body[tag_start..].as_bytes(), *b"%}") else {
+            break;
+        };
+        let tag_end = tag_start + close_rel + 2;
+        let content = body[tag_start + 2..tag_end - 2].trim().trim_matches('-').trim();
+
+        if content.starts_with("if ")
+            || content == "if"
+            || content.starts_with("unless ")
+            || content == "unless"
+            || content.starts_with("for ")
+        {
+            depth += 1;
+        } else if content == "endif" || content == "endunless" || content == "endfor" {
+            depth -= 1;
+        } else if depth == 0 && content == "else" {
+            return (&body[..tag_start], Some(&body[tag_end..]));
+        }
+
+        search_from = tag_end;
+    }
+
+    (body, None)
+}
+
 fn expand_for_loop(
     item_var: &str,
     collection_var: &str,
     loop_body: &str,
+    else_body: Option<&str>,
     variables: &HashMap<String, String>,
-    limit: Option<usize>,
+    params: &ForLoopParams,
 ) -> Result<String> {
+    if let Some((start, end)) = parse_range(collection_var, variables) {
+        return expand_for_range(item_var, start, end, loop_body, params);
+    }
+
     // Find how many items are in the collection
     let total_size = find_collection_size(collection_var, variables);
 
-    // If no indexed items found, return empty string
+    // If no indexed items found, render the `{% else %}` fallback (if any)
     if total_size == 0 {
-        return Ok(String::new());
+        return Ok(else_body.unwrap_or_default().to_string());
     }
 
-    // Determine how many iterations to perform based on optional limit
-    let loop_len = match limit {
-        Some(lim) => std::cmp::min(total_size, lim),
-        None => total_size,
-    };
+    let sequence = build_iteration_sequence(total_size, params);
+    let loop_len = sequence.len();
 
     // Expand the loop body for each item
     let mut result = String::new();
-    for i in 0..loop_len {
+    for (pos, &i) in sequence.iter().enumerate() {
         // Replace forloop context directly with actual values (no assign tags)
-        let is_last = i == loop_len - 1;
-        let is_first = i == 0;
+        let is_last = pos + 1 == loop_len;
+        let is_first = pos == 0;
 
         let mut expanded_body = loop_body.to_string();
 
@@ -143,8 +253,8 @@ fn expand_for_loop(
             &expanded_body,
             is_last,
             is_first,
-            i + 1, // 1-based index
-            i,     // 0-based index
+            pos + 1, // 1-based index
+            pos,     // 0-based index
             loop_len,
         );
 
@@ -180,6 +290,48 @@ fn expand_for_loop(
     Ok(result)
 }
 
+/// Expands a `{% for i in (A..B) %}` integer-range loop, emitting the
+/// literal range values directly instead of `collection.i.`-style
+/// placeholders, since a range value has no fields to look up later.
+fn expand_for_range(
+    item_var: &str,
+    start: i64,
+    end: i64,
+    loop_body: &str,
+    params: &ForLoopParams,
+) -> Result<String> {
+    let step: i64 = if end >= start { 1 } else { -1 };
+    let total_size = start.abs_diff(end) as usize + 1;
+
+    let sequence = build_iteration_sequence(total_size, params);
+    let loop_len = sequence.len();
+
+    let mut result = String::new();
+    for (pos, &i) in sequence.iter().enumerate() {
+        let is_last = pos + 1 == loop_len;
+        let is_first = pos == 0;
+        let value = (start + step * i as i64).to_string();
+
+        let mut expanded_body = loop_body.to_string();
+        expanded_body = replace_forloop_context_at_current_level(
+            &expanded_body,
+            is_last,
+            is_first,
+            pos + 1,
+            pos,
+            loop_len,
+        );
+
+        let patterns = super::utils::variable_placeholders(item_var);
+        expanded_body = expanded_body.replace(&patterns[2], &value);
+        expanded_body = expanded_body.replace(&patterns[3], &value);
+
+        result.push_str(&expanded_body);
+    }
+
+    Ok(result)
+}
+
 fn replace_forloop_context_at_current_level(
     template: &str,
     is_last: bool,
@@ -282,6 +434,127 @@ fn replace_forloop_context_at_current_level(
     result
 }
 
+/// Processes `{% for item in collection %}...{% endfor %}` blocks by fully
+/// rendering each iteration, rather than expanding `{{ item.field }}` into
+/// flattened `{{ collection.N.field }}` references for a later pass the way
+/// [`process_liquid_for_loops`] does.
+///
+/// For each item, the item's fields and the standard `forloop` metadata
+/// (`forloop.index0`, `forloop.index1`, `forloop.first`, `forloop.last`,
+/// `forloop.length`) are layered onto a per-iteration copy of `variables`,
+/// and the loop body is resolved against that copy immediately - `{% unless
+/// %}`/`{% if %}` tags referencing `forloop.*` (e.g. the `{% unless
+/// forloop.last %}, {% endunless %}` separator pattern), nested `{% for %}`
+/// blocks, and `{{ }}` interpolations are all evaluated per-iteration, since
+/// nothing downstream of this function has per-iteration variable scope to
+/// resolve them correctly. An `{% else %}` branch, if present, is rendered
+/// once when the collection is empty or absent instead of being treated as
+/// the body of a zero-iteration loop.
+///
+/// Blocks are paired with [`find_tag_block`], the same non-nesting
+/// first-match scan used for `{% unless %}` and block-form `{% render %}` -
+/// a `{% for %}` pairs with the nearest subsequent `{% endfor %}`, so a
+/// `{% for %}` nested inside another `{% for %}`'s body is not supported
+/// here (use [`process_liquid_for_loops`] for that case).
+///
+/// # Arguments
+/// * `template` - The template string containing for tags
+/// * `variables` - Map of variables used to resolve the collection and each iteration's body
+///
+/// # Returns
+/// The processed template with for tags expanded
+pub fn process_liquid_for_tags(
+    template: &str,
+    variables: &HashMap<String, String>,
+) -> Result<String> {
+    let mut result = template.to_string();
+    let mut current_pos = 0;
+
+    while let Some(tag_block) = find_tag_block(&result, "{% for", "{% endfor %}", current_pos) {
+        let for_content = tag_block.tag_content.trim();
+        let parts: Vec<&str> = for_content.split(" in ").collect();
+        if parts.len() != 2 {
+            return Err(Error::Liquid("Invalid for loop syntax".to_string()));
+        }
+        let item_var = parts[0].trim();
+        let collection_var = parts[1].trim();
+
+        let (body, else_body) = split_for_tag_else(&tag_block.inner_content);
+        let items = get_array_items(collection_var, variables);
+
+        let expanded = if items.is_empty() {
+            match else_body {
+                Some(else_body) => process_liquid_for_tags(&else_body, variables)?,
+                None => String::new(),
+            }
+        } else {
+            let length = items.len();
+            let mut rendered = String::new();
+
+            for (index0, item) in items.iter().enumerate() {
+                let mut iteration_variables = variables.clone();
+                for (field, value) in item {
+                    iteration_variables.insert(format!("{item_var}.{field}"), value.clone());
+                }
+                iteration_variables.insert("forloop.index0".to_string(), index0.to_string());
+                iteration_variables.insert("forloop.index1".to_string(), (index0 + 1).to_string());
+                iteration_variables
+                    .insert("forloop.first".to_string(), (index0 == 0).to_string());
+                iteration_variables
+                    .insert("forloop.last".to_string(), (index0 == length - 1).to_string());
+                iteration_variables.insert("forloop.length".to_string(), length.to_string());
+
+                let iteration = process_liquid_unless_tags(&body, &iteration_variables)?;
+                let iteration = process_liquid_conditional_tags(&iteration, &iteration_variables)?;
+                let iteration = process_liquid_for_tags(&iteration, &iteration_variables)?;
+                let iteration = replace_template_variables(&iteration, &iteration_variables)?;
+                rendered.push_str(&iteration);
+            }
+
+            rendered
+        };
+
+        result.replace_range(tag_block.start..tag_block.end, &expanded);
+        current_pos = tag_block.start + expanded.len();
+    }
+
+    Ok(result)
+}
+
+/// Splits a `{% for %}` body on its top-level `{% else %}` tag, if any,
+/// skipping over `{% else %}` tags that belong to a nested `{% if %}`,
+/// `{% unless %}`, or `{% for %}` block.
+fn split_for_tag_else(body: &str) -> (String, Option<String>) {
+    let mut depth = 0i32;
+    let mut search_from = 0;
+
+    while let Some(rel) = body[search_from..].find("{%") {
+        let tag_start = search_from + rel;
+        let Some(close_rel) = body[tag_start..].find("%}") else {
+            break;
+        };
+        let tag_end = tag_start + close_rel + 2;
+        let content = body[tag_start + 2..tag_end - 2].trim().trim_matches('-').trim();
+
+        if content.starts_with("if ")
+            || content == "if"
+            || content.starts_with("unless ")
+            || content == "unless"
+            || content.starts_with("for ")
+        {
+            depth += 1;
+        } else if content == "endif" || content == "endunless" || content == "endfor" {
+            depth -= 1;
+        } else if depth == 0 && content == "else" {
+            return (body[..tag_start].to_string(), Some(body[tag_end..].to_string()));
+        }
+
+        search_from = tag_end;
+    }
+
+    (body.to_string(), None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,6 +613,18 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_for_loop_trim_markers_remove_surrounding_whitespace() {
+        let mut variables = HashMap::new();
+        variables.insert("people.0.name".to_string(), "Alice".to_string());
+
+        let template =
+            "Before  \n  {%- for person in people -%}  \n  {{ person.name }}{% endfor %}  \n  After";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        assert_eq!(result, "Before{{ people.0.name }}  \n  After");
+    }
+
     #[test]
     fn test_non_for_loop_tags_unchanged() {
         let variables = HashMap::new();
@@ -380,6 +665,118 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_for_loop_with_offset() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0.name".to_string(), "A".to_string());
+        variables.insert("items.1.name".to_string(), "B".to_string());
+        variables.insert("items.2.name".to_string(), "C".to_string());
+
+        let template = "{% for item in items offset:1 %}{{ item.name }}\n{% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        let expected = "{{ items.1.name }}\n{{ items.2.name }}\n";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_for_loop_with_offset_and_limit() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0.name".to_string(), "A".to_string());
+        variables.insert("items.1.name".to_string(), "B".to_string());
+        variables.insert("items.2.name".to_string(), "C".to_string());
+        variables.insert("items.3.name".to_string(), "D".to_string());
+
+        let template = "{% for item in items offset:1 limit:2 %}{{ forloop.index }}/{{ forloop.length }}: {{ item.name }}\n{% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        let expected = "1/2: {{ items.1.name }}\n2/2: {{ items.2.name }}\n";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_for_loop_reversed() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0.name".to_string(), "A".to_string());
+        variables.insert("items.1.name".to_string(), "B".to_string());
+        variables.insert("items.2.name".to_string(), "C".to_string());
+
+        let template = "{% for item in items reversed %}{{ forloop.first }} {{ item.name }}\n{% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        let expected = "true {{ items.2.name }}\nfalse {{ items.1.name }}\nfalse {{ items.0.name }}\n";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_for_loop_integer_range() {
+        let variables = HashMap::new();
+
+        let template = "{% for i in (1..5) %}{{ i }} {% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        assert_eq!(result, "1 2 3 4 5 ");
+    }
+
+    #[test]
+    fn test_for_loop_integer_range_with_variable_bounds() {
+        let mut variables = HashMap::new();
+        variables.insert("start".to_string(), "2".to_string());
+        variables.insert("end".to_string(), "4".to_string());
+
+        let template = "{% for i in (start..end) %}{{ i }} {% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        assert_eq!(result, "2 3 4 ");
+    }
+
+    #[test]
+    fn test_for_loop_integer_range_reversed_with_limit() {
+        let variables = HashMap::new();
+
+        let template = "{% for i in (1..5) reversed limit:2 %}{{ forloop.index }}: {{ i }}\n{% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        let expected = "1: 2\n2: 1\n";
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_for_loop_else_branch_renders_when_collection_empty() {
+        let variables = HashMap::new();
+
+        let template = "{% for item in items %}{{ item.name }}{% else %}No items{% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        assert_eq!(result, "No items");
+    }
+
+    #[test]
+    fn test_for_loop_else_branch_is_skipped_when_collection_has_items() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0.name".to_string(), "A".to_string());
+
+        let template = "{% for item in items %}{{ item.name }}{% else %}No items{% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        assert_eq!(result, "{{ items.0.name }}");
+    }
+
+    #[test]
+    fn test_for_loop_else_branch_ignores_nested_for_else() {
+        let mut variables = HashMap::new();
+        variables.insert("groups.0.members.0.name".to_string(), "Alice".to_string());
+
+        // The inner for loop's nonexistent `{% else %}` here is plain text
+        // inside an `{% if %}`-free nested for body, so this mainly checks
+        // that the nested `{% for %}`/`{% endfor %}` pair doesn't get
+        // mistaken for the outer loop's closing tag by the depth tracking.
+        let template = "{% for group in groups %}{% for member in group.members %}{{ member.name }}{% endfor %}{% else %}No groups{% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        assert!(result.contains("{{ groups.0.members.0.name }}"));
+    }
+
     #[test]
     fn test_for_loop_forloop_first_and_index0() {
         let mut variables = HashMap::new();
@@ -406,6 +803,26 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_for_loop_containing_if_block() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0.name".to_string(), "A".to_string());
+        variables.insert("items.0.active".to_string(), "true".to_string());
+        variables.insert("items.1.name".to_string(), "B".to_string());
+        variables.insert("items.1.active".to_string(), "false".to_string());
+
+        // The for-loop pass rewrites `{% if item. %}` references to the
+        // indexed collection variable; the conditional pass that runs
+        // afterwards then evaluates each expanded `{% if %}` on its own.
+        let template = "{% for item in items %}{% if item.active == \"true\" %}{{ item.name }} is active\n{% endif %}{% endfor %}";
+        let result = process_liquid_for_loops(template, &variables).unwrap();
+
+        assert_eq!(
+            result,
+            "{% if items.0.active == \"true\" %}{{ items.0.name }} is active\n{% endif %}{% if items.1.active == \"true\" %}{{ items.1.name }} is active\n{% endif %}"
+        );
+    }
+
     #[test]
     fn test_forloop_vars_not_replaced_inside_nested_loops() {
         let mut variables = HashMap::new();
@@ -421,4 +838,66 @@ mod tests {
         let expected = "(1)(2)(1)";
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_for_tags_renders_items_and_forloop_metadata() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0.name".to_string(), "A".to_string());
+        variables.insert("items.1.name".to_string(), "B".to_string());
+
+        let template = "{% for item in items %}{{ forloop.index1 }}/{{ forloop.length }}: {{ item.name }}\n{% endfor %}";
+        let result = process_liquid_for_tags(template, &variables).unwrap();
+
+        assert_eq!(result, "1/2: A\n2/2: B\n");
+    }
+
+    #[test]
+    fn test_for_tags_unless_forloop_last_resolves_per_iteration() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0.name".to_string(), "A".to_string());
+        variables.insert("items.1.name".to_string(), "B".to_string());
+        variables.insert("items.2.name".to_string(), "C".to_string());
+
+        let template =
+            "{% for item in items %}{{ item.name }}{% unless forloop.last %}, {% endunless %}{% endfor %}";
+        let result = process_liquid_for_tags(template, &variables).unwrap();
+
+        assert_eq!(result, "A, B, C");
+    }
+
+    #[test]
+    fn test_for_tags_if_on_item_variable() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0.name".to_string(), "A".to_string());
+        variables.insert("items.0.active".to_string(), "true".to_string());
+        variables.insert("items.1.name".to_string(), "B".to_string());
+        variables.insert("items.1.active".to_string(), "false".to_string());
+
+        let template = "{% for item in items %}{% if item.active == \"true\" %}{{ item.name }} is active\n{% endif %}{% endfor %}";
+        let result = process_liquid_for_tags(template, &variables).unwrap();
+
+        assert_eq!(result, "A is active\n");
+    }
+
+    #[test]
+    fn test_for_tags_else_branch_renders_when_collection_empty() {
+        let variables = HashMap::new();
+
+        let template = "{% for item in items %}{{ item.name }}{% else %}No items{% endfor %}";
+        let result = process_liquid_for_tags(template, &variables).unwrap();
+
+        assert_eq!(result, "No items");
+    }
+
+    #[test]
+    fn test_for_tags_else_branch_ignores_nested_if_else() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0.name".to_string(), "A".to_string());
+
+        let template = "{% for item in items %}{% if item.name %}{{ item.name }}{% else %}unnamed{% endif %}{% else %}No items{% endfor %}";
+        let result = process_liquid_for_tags(template, &variables).unwrap();
+
+        assert_eq!(result, "A");
+    }
+
 }
@@ -0,0 +1,204 @@
+use super::parse_include_tag::parse_liquid_include_tag;
+use super::replace_variables::replace_template_variables;
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::fs;
+
+/// Supplies the raw content of a named partial for `{% include %}` tags.
+///
+/// Implemented for `HashMap<String, String>` so the in-memory template map
+/// already built by `load_liquid_includes` can be used directly as a
+/// resolver; [`FilesystemIncludeResolver`] is provided for callers that want
+/// to resolve partials straight from disk instead.
+pub trait IncludeResolver {
+    fn resolve(&self, name: &str) -> Option<String>;
+}
+
+impl IncludeResolver for HashMap<String, String> {
+    fn resolve(&self, name: &str) -> Option<String> {
+        self.get(name).cloned()
+    }
+}
+
+/// Resolves partials by reading `<base_dir>/<name>.liquid` from disk.
+pub struct FilesystemIncludeResolver {
+    base_dir: String,
+}
+
+impl FilesystemIncludeResolver {
+    pub fn new(base_dir: impl Into<String>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+        }
+    }
+}
+
+impl IncludeResolver for FilesystemIncludeResolver {
+    fn resolve(&self, name: &str) -> Option<String> {
+        fs::read_to_string(format!("{}/{name}.liquid", self.base_dir)).ok()
+    }
+}
+
+/// Processes `{% include name key:"value" ... %}` tags.
+///
+/// Each tag is resolved through `resolver`, its key:value params are merged
+/// into a copy of `variables` scoped to that partial, and the partial's own
+/// `{{ }}` variables are substituted before it's spliced back in. A partial
+/// can itself include others - those are expanded recursively - and a name
+/// that re-enters its own expansion (directly or via a longer cycle) is
+/// rejected with the offending chain rather than recursing forever. A name
+/// the resolver doesn't know about is left in the output untouched, matching
+/// how `{% render %}` handles a missing template.
+pub fn process_liquid_includes(
+    template: &str,
+    resolver: &dyn IncludeResolver,
+    variables: &HashMap<String, String>,
+) -> Result<String> {
+    expand_includes(template, resolver, variables, &mut Vec::new())
+}
+
+fn expand_includes(
+    template: &str,
+    resolver: &dyn IncludeResolver,
+    variables: &HashMap<String, String>,
+    in_progress: &mut Vec<String>,
+) -> Result<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(tag_start) = rest.find("{% include") {
+        result.push_str(&rest[..tag_start]);
+
+        let Some(close_rel) = rest[tag_start..].find("%}") else {
+            result.push_str(&rest[tag_start..]);
+            rest = "";
+            break;
+        };
+        let tag_end = tag_start + close_rel + 2;
+        let tag = &rest[tag_start..tag_end];
+
+        let Some((name, params)) = parse_liquid_include_tag(tag) else {
+            result.push_str(tag);
+            rest = &rest[tag_end..];
+            continue;
+        };
+
+        // Templates are keyed without their `.liquid` extension (see
+        // `load_liquid_includes`'s `normalize_template_key`), same as `{% render %}`.
+        let name = name.strip_suffix(".liquid").unwrap_or(&name).to_string();
+
+        if in_progress.contains(&name) {
+            let mut chain = in_progress.clone();
+            chain.push(name);
+            return Err(Error::Liquid(format!(
+                "Include cycle detected: {}",
+                chain.join(" -> ")
+            )));
+        }
+
+        let Some(partial) = resolver.resolve(&name) else {
+            result.push_str(tag);
+            rest = &rest[tag_end..];
+            continue;
+        };
+
+        let mut scoped_variables = variables.clone();
+        scoped_variables.extend(params);
+        let substituted = replace_template_variables(&partial, &scoped_variables)?;
+
+        in_progress.push(name);
+        let expanded = expand_includes(&substituted, resolver, &scoped_variables, in_progress)?;
+        in_progress.pop();
+
+        result.push_str(&expanded);
+        rest = &rest[tag_end..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_include_renders_partial_with_own_variables() {
+        let mut templates = HashMap::new();
+        templates.insert("greeting".to_string(), "Hello, {{ name }}!".to_string());
+
+        let variables = HashMap::new();
+        let input = "{% include greeting.liquid name:\"World\" %}";
+        let result = process_liquid_includes(input, &templates, &variables).unwrap();
+
+        assert_eq!(result, "Hello, World!");
+    }
+
+    #[test]
+    fn test_include_params_fall_back_to_outer_variables() {
+        let mut templates = HashMap::new();
+        templates.insert("greeting".to_string(), "Hello, {{ name }}!".to_string());
+
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Outer".to_string());
+
+        let input = "{% include greeting.liquid %}";
+        let result = process_liquid_includes(input, &templates, &variables).unwrap();
+
+        assert_eq!(result, "Hello, Outer!");
+    }
+
+    #[test]
+    fn test_include_recursively_expands_nested_includes() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "page".to_string(),
+            "{% include header.liquid %} body".to_string(),
+        );
+        templates.insert("header".to_string(), "[{{ title }}]".to_string());
+
+        let mut variables = HashMap::new();
+        variables.insert("title".to_string(), "Home".to_string());
+
+        let input = "{% include page.liquid %}";
+        let result = process_liquid_includes(input, &templates, &variables).unwrap();
+
+        assert_eq!(result, "[Home] body");
+    }
+
+    #[test]
+    fn test_include_direct_self_inclusion_errors() {
+        let mut templates = HashMap::new();
+        templates.insert("loop".to_string(), "{% include loop.liquid %}".to_string());
+
+        let variables = HashMap::new();
+        let input = "{% include loop.liquid %}";
+        let result = process_liquid_includes(input, &templates, &variables);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_include_indirect_cycle_errors() {
+        let mut templates = HashMap::new();
+        templates.insert("a".to_string(), "{% include b.liquid %}".to_string());
+        templates.insert("b".to_string(), "{% include a.liquid %}".to_string());
+
+        let variables = HashMap::new();
+        let input = "{% include a.liquid %}";
+        let result = process_liquid_includes(input, &templates, &variables);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_include_unknown_partial_left_unchanged() {
+        let templates = HashMap::new();
+        let variables = HashMap::new();
+
+        let input = "{% include missing.liquid %} and more";
+        let result = process_liquid_includes(input, &templates, &variables).unwrap();
+
+        assert_eq!(result, "{% include missing.liquid %} and more");
+    }
+}
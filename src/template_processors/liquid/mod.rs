@@ -1,24 +1,40 @@
 /// Liquid template processing module
 ///
 /// This module provides functionality for processing Liquid-style templates,
-/// including conditional tags, renders, for loops, assign tags, unless tags, and variable substitution.
+/// including conditional tags, renders, includes, for loops, assign tags, unless tags,
+/// layout inheritance (`{% extends %}` / `{% block %}`), and variable substitution.
+/// [`compile`] offers a one-pass tokenize-then-AST alternative that can be
+/// rendered repeatedly against different variable scopes.
 mod _if;
 mod assign;
+mod extends;
+mod filters;
 mod for_loop;
+mod include;
 mod nested_access;
+mod parse_include_tag;
 mod parse_render_tag;
 mod process_renders;
 mod processor;
+mod registry;
 mod remove;
+mod render_options;
 mod replace_variables;
+mod template;
 mod unless;
 mod utils;
 mod validation;
 
 pub use _if::process_liquid_conditional_tags;
 pub use assign::process_liquid_assign_tags;
-pub use for_loop::process_liquid_for_loops;
-pub use processor::process_liquid_tags_with_assigns;
+pub use extends::process_liquid_extends;
+pub use for_loop::{process_liquid_for_loops, process_liquid_for_tags};
+pub use include::{process_liquid_includes, FilesystemIncludeResolver, IncludeResolver};
+pub use process_renders::{process_liquid_renders, process_liquid_renders_with_options};
+pub use processor::{process_liquid_tags_with_assigns, process_liquid_tags_with_registry};
+pub use registry::LiquidTagRegistry;
 pub use remove::remove_liquid_variables;
-pub use replace_variables::replace_template_variables;
+pub use render_options::RenderOptions;
+pub use replace_variables::{replace_template_variables, replace_template_variables_with_options};
+pub use template::{compile, Template};
 pub use unless::process_liquid_unless_tags;
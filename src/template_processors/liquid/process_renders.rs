@@ -1,10 +1,21 @@
 use super::parse_render_tag::parse_liquid_render_tag;
+use super::render_options::RenderOptions;
 use super::replace_variables::replace_template_variables;
-use crate::error::Result;
+use super::utils::find_tag_block;
+use crate::error::{liquid_error_at, Error, Result};
 use std::collections::HashMap;
 
 /// Processes all liquid render tags in the input string and replaces them with template content.
 ///
+/// Expansion is recursive: once a partial's variables are substituted, its
+/// own `{% render %}` tags are expanded too, so partials can render other
+/// partials (see [`expand_renders`]).
+///
+/// Equivalent to [`process_liquid_renders_with_options`] with the default,
+/// non-strict [`RenderOptions`]: an unknown template name or a malformed or
+/// unclosed tag is left verbatim in the output rather than failing the
+/// render.
+///
 /// # Arguments
 /// * `input` - The input string containing liquid render tags
 /// * `templates` - A `HashMap` containing template names and their content
@@ -12,12 +23,96 @@ use std::collections::HashMap;
 /// # Returns
 /// * `Result<String>` - The processed string with renders replaced or an error if processing fails
 pub fn process_liquid_renders(input: &str, templates: &HashMap<String, String>) -> Result<String> {
+    process_liquid_renders_with_options(input, templates, &RenderOptions::default())
+}
+
+/// Same as [`process_liquid_renders`], but takes a [`RenderOptions`]. With
+/// `options.strict`, an unknown template name, a malformed render tag, or
+/// an unclosed block is returned as an `Err` (carrying the offending tag's
+/// position, via [`liquid_error_at`]) instead of being passed through
+/// unchanged.
+pub fn process_liquid_renders_with_options(
+    input: &str,
+    templates: &HashMap<String, String>,
+    options: &RenderOptions,
+) -> Result<String> {
+    let mut chain = Vec::new();
+    expand_renders(input, templates, &mut chain, options)
+}
+
+/// Recursively expands `{% render %}` tags, in both their non-block form
+/// (`{% render 'name' %}`) and their block form (`{% render 'name' %}...{%
+/// endrender %}`, which captures the inner markup and hands it to the
+/// partial as a `content` parameter, the same way a Handlebars partial
+/// block fills its `{{> @partial-block}}` slot). `chain` tracks the stack
+/// of template names currently being expanded - if a partial (directly or
+/// through another partial) ends up rendering itself, that name is already
+/// on `chain` and expansion stops with an error instead of recursing
+/// forever.
+///
+/// Block pairing is done with [`find_tag_block`], the same non-nesting
+/// first-match scan used for `{% unless %}`: a render tag pairs with the
+/// nearest `{% endrender %}` that follows it, so a non-block render that
+/// happens to precede an unrelated block-form render's `{% endrender %}`
+/// should be kept out of that gap.
+fn expand_renders(
+    input: &str,
+    templates: &HashMap<String, String>,
+    chain: &mut Vec<String>,
+    options: &RenderOptions,
+) -> Result<String> {
     let mut result = input.to_owned();
     let mut start = 0;
 
     while let Some(start_index) = result[start..].find("{% render") {
         let tag_start = start + start_index;
+
+        // A render tag immediately followed (elsewhere in the template) by
+        // an `{% endrender %}` is block-form: its inner content becomes the
+        // `content` parameter passed to the partial.
+        if let Some(block) = find_tag_block(&result, "{% render", "{% endrender %}", tag_start) {
+            if block.start == tag_start {
+                if let Some((template_name, mut params)) =
+                    parse_liquid_render_tag(&format!("{{% render {} %}}", block.tag_content))
+                {
+                    if let Some(template_content) = templates.get(&template_name) {
+                        if chain.contains(&template_name) {
+                            return Err(Error::Liquid(format!(
+                                "recursive render detected: {template_name}"
+                            )));
+                        }
+
+                        let slot_content =
+                            expand_renders(&block.inner_content, templates, chain, options)?;
+                        params.insert("content".to_string(), slot_content);
+
+                        let substituted = replace_template_variables(template_content, &params)?;
+
+                        chain.push(template_name);
+                        let processed_content = expand_renders(&substituted, templates, chain, options);
+                        chain.pop();
+                        let processed_content = processed_content?;
+
+                        result.replace_range(block.start..block.end, &processed_content);
+                        start = block.start + processed_content.len();
+                        continue;
+                    } else if options.strict {
+                        return Err(liquid_error_at(
+                            &result,
+                            block.start,
+                            &format!("unknown render template: {template_name}"),
+                        ));
+                    }
+                } else if options.strict {
+                    return Err(liquid_error_at(&result, block.start, "malformed render tag"));
+                }
+            }
+        }
+
         let Some(end_index) = result[tag_start..].find("%}") else {
+            if options.strict {
+                return Err(liquid_error_at(&result, tag_start, "unclosed render tag"));
+            }
             break;
         };
 
@@ -26,14 +121,33 @@ pub fn process_liquid_renders(input: &str, templates: &HashMap<String, String>)
 
         if let Some((template_name, params)) = parse_liquid_render_tag(tag) {
             if let Some(template_content) = templates.get(&template_name) {
-                let processed_content = replace_template_variables(template_content, &params)?;
-                result.replace_range(tag_start..tag_end, &processed_content);
+                if chain.contains(&template_name) {
+                    return Err(Error::Liquid(format!(
+                        "recursive render detected: {template_name}"
+                    )));
+                }
 
+                let substituted = replace_template_variables(template_content, &params)?;
+
+                chain.push(template_name);
+                let processed_content = expand_renders(&substituted, templates, chain, options);
+                chain.pop();
+                let processed_content = processed_content?;
+
+                result.replace_range(tag_start..tag_end, &processed_content);
                 start = tag_start + processed_content.len();
+            } else if options.strict {
+                return Err(liquid_error_at(
+                    &result,
+                    tag_start,
+                    &format!("unknown render template: {template_name}"),
+                ));
             } else {
                 // Move start to just after the current tag if the template was not found
                 start = tag_end;
             }
+        } else if options.strict {
+            return Err(liquid_error_at(&result, tag_start, "malformed render tag"));
         } else {
             // Move start to just after the current tag if parsing failed
             start = tag_end;
@@ -215,4 +329,164 @@ mod tests {
         let result = process_liquid_renders(input, &templates).unwrap();
         assert_eq!(result, "<h1>Welcome</h1> <footer>2024</footer>");
     }
+
+    #[test]
+    fn test_process_liquid_renders_expands_nested_render_tags() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "body".to_string(),
+            "<body>{% render 'body_detail' %}</body>".to_string(),
+        );
+        templates.insert("body_detail".to_string(), "<p>Detail</p>".to_string());
+
+        let input = "{% render 'body' %}";
+        let result = process_liquid_renders(input, &templates).unwrap();
+        assert_eq!(result, "<body><p>Detail</p></body>");
+    }
+
+    #[test]
+    fn test_process_liquid_renders_expands_multiple_levels() {
+        let mut templates = HashMap::new();
+        templates.insert("a".to_string(), "A[{% render 'b' %}]".to_string());
+        templates.insert("b".to_string(), "B[{% render 'c' %}]".to_string());
+        templates.insert("c".to_string(), "C".to_string());
+
+        let input = "{% render 'a' %}";
+        let result = process_liquid_renders(input, &templates).unwrap();
+        assert_eq!(result, "A[B[C]]");
+    }
+
+    #[test]
+    fn test_process_liquid_renders_detects_direct_recursion() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "recursively_nested_template".to_string(),
+            "{% render 'recursively_nested_template' %}".to_string(),
+        );
+
+        let input = "{% render 'recursively_nested_template' %}";
+        let result = process_liquid_renders(input, &templates);
+        let err = result.unwrap_err();
+        if let Error::Liquid(msg) = err {
+            assert!(
+                msg.contains("recursive render detected"),
+                "unexpected error message: {msg}"
+            );
+            assert!(msg.contains("recursively_nested_template"));
+        } else {
+            panic!("expected Error::Liquid, got {err:?}");
+        }
+    }
+
+    #[test]
+    fn test_process_liquid_renders_detects_indirect_recursion() {
+        let mut templates = HashMap::new();
+        templates.insert("a".to_string(), "{% render 'b' %}".to_string());
+        templates.insert("b".to_string(), "{% render 'a' %}".to_string());
+
+        let input = "{% render 'a' %}";
+        let result = process_liquid_renders(input, &templates);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_process_liquid_renders_block_form_passes_content_slot() {
+        let mut templates = HashMap::new();
+        templates.insert(
+            "layout".to_string(),
+            "<div class=\"layout\"><h1>{{ title }}</h1>{{ content }}</div>".to_string(),
+        );
+
+        let input = "{% render 'layout' title:\"Hi\" %}<p>Body</p>{% endrender %}";
+        let result = process_liquid_renders(input, &templates).unwrap();
+        assert_eq!(result, "<div class=\"layout\"><h1>Hi</h1><p>Body</p></div>");
+    }
+
+    #[test]
+    fn test_process_liquid_renders_block_form_content_is_itself_expanded() {
+        let mut templates = HashMap::new();
+        templates.insert("layout".to_string(), "<main>{{ content }}</main>".to_string());
+        templates.insert("icon".to_string(), "<i>star</i>".to_string());
+
+        let input = "{% render 'layout' %}{% render 'icon' %}{% endrender %}";
+        let result = process_liquid_renders(input, &templates).unwrap();
+        assert_eq!(result, "<main><i>star</i></main>");
+    }
+
+    #[test]
+    fn test_process_liquid_renders_block_form_followed_by_non_block_render() {
+        let mut templates = HashMap::new();
+        templates.insert("header".to_string(), "HEADER".to_string());
+        templates.insert(
+            "layout".to_string(),
+            "<main>{{ content }}</main>".to_string(),
+        );
+
+        let input = "{% render 'layout' %}<p>Body</p>{% endrender %}{% render 'header' %}";
+        let result = process_liquid_renders(input, &templates).unwrap();
+        assert_eq!(result, "<main><p>Body</p></main>HEADER");
+    }
+
+    #[test]
+    fn test_strict_mode_errors_on_unknown_template() {
+        let templates = HashMap::new();
+        let options = RenderOptions { strict: true };
+
+        let input = "Before {% render 'missing' %} After";
+        let result = process_liquid_renders_with_options(input, &templates, &options);
+
+        let Err(Error::Liquid(msg)) = result else {
+            panic!("expected Error::Liquid, got {result:?}");
+        };
+        assert!(msg.contains("unknown render template: missing"), "{msg}");
+        assert!(msg.contains("1:8"), "{msg}");
+    }
+
+    #[test]
+    fn test_strict_mode_errors_on_unknown_block_form_template() {
+        let templates = HashMap::new();
+        let options = RenderOptions { strict: true };
+
+        let input = "{% render 'missing' %}Body{% endrender %}";
+        let result = process_liquid_renders_with_options(input, &templates, &options);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_strict_mode_errors_on_malformed_tag() {
+        let templates = HashMap::new();
+        let options = RenderOptions { strict: true };
+
+        let input = "{% render %}";
+        let result = process_liquid_renders_with_options(input, &templates, &options);
+
+        let Err(Error::Liquid(msg)) = result else {
+            panic!("expected Error::Liquid, got {result:?}");
+        };
+        assert!(msg.contains("malformed render tag"), "{msg}");
+    }
+
+    #[test]
+    fn test_strict_mode_errors_on_unclosed_tag() {
+        let templates = HashMap::new();
+        let options = RenderOptions { strict: true };
+
+        let input = "{% render unclosed";
+        let result = process_liquid_renders_with_options(input, &templates, &options);
+
+        let Err(Error::Liquid(msg)) = result else {
+            panic!("expected Error::Liquid, got {result:?}");
+        };
+        assert!(msg.contains("unclosed render tag"), "{msg}");
+    }
+
+    #[test]
+    fn test_non_strict_mode_unchanged_by_default() {
+        let templates = HashMap::new();
+        let input = "{% render 'missing' %}";
+        let result = process_liquid_renders_with_options(input, &templates, &RenderOptions::default())
+            .unwrap();
+        assert_eq!(result, "{% render 'missing' %}");
+    }
 }
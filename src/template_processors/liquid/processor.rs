@@ -1,32 +1,56 @@
 use super::_if::process_liquid_conditional_tags;
 use super::assign::process_liquid_assign_tags;
+use super::extends::process_liquid_extends;
 use super::for_loop::process_liquid_for_loops;
-use super::process_includes::process_liquid_includes;
+use super::include::process_liquid_includes;
+use super::process_renders::process_liquid_renders;
+use super::registry::LiquidTagRegistry;
 use super::unless::process_liquid_unless_tags;
 use crate::error::Result;
 use std::collections::HashMap;
 
 /// Process all Liquid tags in a template string, including assign tags
 ///
-/// This function processes conditional tags, assign tags, for loops, unless tags, and includes
-/// in the correct order. Assign tags can modify the variables map.
+/// This function processes layout inheritance, conditional tags, assign tags, for loops,
+/// unless tags, renders, and includes in the correct order. Assign tags can modify the
+/// variables map.
 ///
 /// # Arguments
 /// * `template` - The template string to process
 /// * `conditions` - List of condition names that should evaluate to true (deprecated)
-/// * `templates` - Map of template names to their content for includes
+/// * `templates` - Map of template names to their content for `{% extends %}`/`{% render %}`/`{% include %}`
 /// * `variables` - Mutable variables map for assign tags and for loop processing
 ///
 /// # Returns
 /// The processed template with all liquid tags evaluated
 pub fn process_liquid_tags_with_assigns(
+    template: &str,
+    conditions: &[String],
+    templates: &HashMap<String, String>,
+    variables: &mut HashMap<String, String>,
+) -> Result<String> {
+    process_liquid_tags_with_registry(template, conditions, templates, variables, None)
+}
+
+/// Same as [`process_liquid_tags_with_assigns`], but also runs a
+/// [`LiquidTagRegistry`] of project-specific custom tags after the built-in
+/// passes, so downstream users can add their own tags without editing this
+/// crate.
+///
+/// # Arguments
+/// * `registry` - Custom tag handlers to run after the built-in passes; `None` skips this step
+pub fn process_liquid_tags_with_registry(
     template: &str,
     _conditions: &[String],
     templates: &HashMap<String, String>,
     variables: &mut HashMap<String, String>,
+    registry: Option<&LiquidTagRegistry>,
 ) -> Result<String> {
+    // Resolve layout inheritance first so the merged layout flows through the rest of the pipeline
+    let processed_extends = process_liquid_extends(template, templates)?;
+
     // Process assigns first so variables are available to subsequent steps
-    let processed_assigns = process_liquid_assign_tags(template, variables)?;
+    let processed_assigns = process_liquid_assign_tags(&processed_extends, variables)?;
 
     // Expand for loops next so that any item-scoped references are transformed
     let processed_for_loops = process_liquid_for_loops(&processed_assigns, variables)?;
@@ -37,8 +61,17 @@ pub fn process_liquid_tags_with_assigns(
     // Process if-conditionals after loop expansion using variables for truthiness
     let processed_conditionals = process_liquid_conditional_tags(&processed_unless, variables)?;
 
-    // Finally, resolve includes
-    process_liquid_includes(&processed_conditionals, templates)
+    // Resolve renders
+    let processed_renders = process_liquid_renders(&processed_conditionals, templates)?;
+
+    // Resolve includes, which may themselves recursively include other partials
+    let processed_includes = process_liquid_includes(&processed_renders, templates, variables)?;
+
+    // Finally, let any registered custom tags splice themselves in
+    match registry {
+        Some(registry) => registry.process(&processed_includes, variables),
+        None => Ok(processed_includes),
+    }
 }
 
 #[cfg(test)]
@@ -68,6 +101,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_liquid_tags_with_registry_splices_custom_tag() {
+        let mut registry = LiquidTagRegistry::new();
+        registry.register_inline_tag("shout", |args, _vars| Ok(args.to_uppercase()));
+
+        let mut variables = HashMap::new();
+        let templates = HashMap::new();
+        let conditions = Vec::new();
+
+        let input = "{% shout hello %}";
+        let result = process_liquid_tags_with_registry(
+            input,
+            &conditions,
+            &templates,
+            &mut variables,
+            Some(&registry),
+        )
+        .unwrap();
+
+        assert_eq!(result, "HELLO");
+    }
+
     #[test]
     fn test_for_loop_with_unless_forloop_last() {
         let mut variables = HashMap::new();
@@ -0,0 +1,151 @@
+use crate::error::Result;
+use std::collections::HashMap;
+
+type InlineHandler = Box<dyn Fn(&str, &mut HashMap<String, String>) -> Result<String>>;
+type BlockHandler = Box<dyn Fn(&str, &str, &mut HashMap<String, String>) -> Result<String>>;
+
+/// Registry of custom Liquid tag handlers for project-specific tags the core
+/// crate doesn't know about (date formatting, asset hashing, SVG inlining, ...).
+///
+/// Inline tags look like `{% name args %}`; block tags look like
+/// `{% name args %}...{% endname %}`. The registry is consulted after the
+/// built-in passes, so custom tags see already-resolved assigns, loops and
+/// conditionals.
+#[derive(Default)]
+pub struct LiquidTagRegistry {
+    inline_tags: HashMap<String, InlineHandler>,
+    block_tags: HashMap<String, BlockHandler>,
+}
+
+impl LiquidTagRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a handler for an inline tag `{% name args %}`. The handler
+    /// receives the raw argument string and the variables map, and returns
+    /// the string to splice in its place.
+    pub fn register_inline_tag<F>(&mut self, name: impl Into<String>, handler: F)
+    where
+        F: Fn(&str, &mut HashMap<String, String>) -> Result<String> + 'static,
+    {
+        self.inline_tags.insert(name.into(), Box::new(handler));
+    }
+
+    /// Registers a handler for a paired block tag `{% name args %}body{% endname %}`.
+    /// The handler receives the raw argument string, the captured inner body,
+    /// and the variables map.
+    pub fn register_block_tag<F>(&mut self, name: impl Into<String>, handler: F)
+    where
+        F: Fn(&str, &str, &mut HashMap<String, String>) -> Result<String> + 'static,
+    {
+        self.block_tags.insert(name.into(), Box::new(handler));
+    }
+
+    /// True if no custom tags have been registered.
+    pub fn is_empty(&self) -> bool {
+        self.inline_tags.is_empty() && self.block_tags.is_empty()
+    }
+
+    /// Scans `template` for tags whose name matches a registered handler and
+    /// splices in its returned string. Tags that match no registered name are
+    /// left untouched.
+    pub fn process(&self, template: &str, variables: &mut HashMap<String, String>) -> Result<String> {
+        if self.is_empty() {
+            return Ok(template.to_string());
+        }
+
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(tag_start) = rest.find("{%") {
+            result.push_str(&rest[..tag_start]);
+
+            let Some(close_rel) = rest[tag_start..].find("%}") else {
+                result.push_str(&rest[tag_start..]);
+                rest = "";
+                break;
+            };
+            let tag_close = tag_start + close_rel + 2;
+            let tag_inner = rest[tag_start + 2..tag_close - 2].trim();
+            let (word, args) = match tag_inner.split_once(char::is_whitespace) {
+                Some((w, a)) => (w, a.trim()),
+                None => (tag_inner, ""),
+            };
+
+            if let Some(handler) = self.block_tags.get(word) {
+                let end_tag = format!("{{% end{word} %}}");
+                if let Some(end_rel) = rest[tag_close..].find(&end_tag) {
+                    let body = &rest[tag_close..tag_close + end_rel];
+                    let replacement = handler(args, body, variables)?;
+                    result.push_str(&replacement);
+                    rest = &rest[tag_close + end_rel + end_tag.len()..];
+                    continue;
+                }
+            } else if let Some(handler) = self.inline_tags.get(word) {
+                let replacement = handler(args, variables)?;
+                result.push_str(&replacement);
+                rest = &rest[tag_close..];
+                continue;
+            }
+
+            // Not a registered tag: keep it verbatim and keep scanning past it.
+            result.push_str(&rest[tag_start..tag_close]);
+            rest = &rest[tag_close..];
+        }
+
+        result.push_str(rest);
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_registry_leaves_template_unchanged() {
+        let registry = LiquidTagRegistry::new();
+        let mut variables = HashMap::new();
+        let input = "{% unknown_tag %}";
+        assert_eq!(registry.process(input, &mut variables).unwrap(), input);
+    }
+
+    #[test]
+    fn test_inline_tag_handler_splices_result() {
+        let mut registry = LiquidTagRegistry::new();
+        registry.register_inline_tag("shout", |args, _vars| Ok(args.to_uppercase()));
+
+        let mut variables = HashMap::new();
+        let result = registry
+            .process("Say {% shout hello %}!", &mut variables)
+            .unwrap();
+        assert_eq!(result, "Say HELLO!");
+    }
+
+    #[test]
+    fn test_block_tag_handler_receives_body() {
+        let mut registry = LiquidTagRegistry::new();
+        registry.register_block_tag("reverse", |_args, body, _vars| {
+            Ok(body.chars().rev().collect())
+        });
+
+        let mut variables = HashMap::new();
+        let result = registry
+            .process("{% reverse %}abc{% endreverse %}", &mut variables)
+            .unwrap();
+        assert_eq!(result, "cba");
+    }
+
+    #[test]
+    fn test_unregistered_tags_are_left_alone() {
+        let mut registry = LiquidTagRegistry::new();
+        registry.register_inline_tag("known", |_args, _vars| Ok("X".to_string()));
+
+        let mut variables = HashMap::new();
+        let input = "{% if foo %}bar{% endif %} {% known %}";
+        let result = registry.process(input, &mut variables).unwrap();
+        assert_eq!(result, "{% if foo %}bar{% endif %} X");
+    }
+}
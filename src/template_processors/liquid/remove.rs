@@ -1,4 +1,4 @@
-use crate::error::{Error, Result};
+use crate::error::{line_col, Error, Result};
 
 /// Removes Liquid variables from the input string.
 /// This function will remove any content between {{ and }} including the braces.
@@ -10,30 +10,31 @@ use crate::error::{Error, Result};
 /// * `Result<String>` - The string with variables removed or an error if malformed
 pub fn remove_liquid_variables(input: &str) -> Result<String> {
     let mut result = String::with_capacity(input.len());
-    let mut chars = input.chars().peekable();
-    let mut in_variable = false;
+    let mut chars = input.char_indices().peekable();
+    let mut variable_start: Option<usize> = None;
 
-    while let Some(current) = chars.next() {
-        if current == '{' && chars.peek() == Some(&'{') {
-            if in_variable {
-                return Err(Error::Liquid(
-                    "Nested opening braces '{{' found inside a variable".to_string(),
-                ));
+    while let Some((idx, current)) = chars.next() {
+        if current == '{' && chars.peek().map(|&(_, c)| c) == Some('{') {
+            if let Some(start) = variable_start {
+                let (line, col) = line_col(input, start);
+                return Err(Error::Liquid(format!(
+                    "Nested opening braces '{{{{' found inside a variable at {line}:{col}"
+                )));
             }
-            in_variable = true;
+            variable_start = Some(idx);
             // Skip the second '{'
             chars.next();
 
             // Skip whitespace after '{{'
-            while let Some(&c) = chars.peek() {
+            while let Some(&(_, c)) = chars.peek() {
                 if !c.is_whitespace() {
                     break;
                 }
                 chars.next();
             }
-        } else if in_variable {
-            if current == '}' && chars.peek() == Some(&'}') {
-                in_variable = false;
+        } else if variable_start.is_some() {
+            if current == '}' && chars.peek().map(|&(_, c)| c) == Some('}') {
+                variable_start = None;
                 chars.next(); // Skip the second '}'
             }
         } else {
@@ -41,8 +42,11 @@ pub fn remove_liquid_variables(input: &str) -> Result<String> {
         }
     }
 
-    if in_variable {
-        return Err(Error::Liquid("Unclosed Liquid variable".to_string()));
+    if let Some(start) = variable_start {
+        let (line, col) = line_col(input, start);
+        return Err(Error::Liquid(format!(
+            "Unclosed Liquid variable at {line}:{col}"
+        )));
     }
 
     Ok(result)
@@ -0,0 +1,34 @@
+/// Options controlling how permissive and how safe the Liquid rendering
+/// passes are.
+///
+/// The defaults (`strict: false`, `escape_html: false`) preserve each pass's
+/// historical behavior: leaving an unresolved tag verbatim in the output
+/// rather than failing the whole render, and inserting a resolved variable's
+/// value verbatim rather than HTML-escaping it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions {
+    /// When `true`, an unknown template name, a malformed tag, or an
+    /// unclosed block is returned as an `Err` instead of being passed
+    /// through unchanged.
+    pub strict: bool,
+    /// When `true`, a variable's resolved value is HTML-escaped before being
+    /// inserted, unless the template opts it out with triple braces
+    /// (`{{{ name }}}`) or a trailing `| raw` filter. See
+    /// [`super::replace_variables::replace_template_variables_with_options`].
+    pub escape_html: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_options_default_is_not_strict() {
+        assert!(!RenderOptions::default().strict);
+    }
+
+    #[test]
+    fn test_render_options_default_does_not_escape_html() {
+        assert!(!RenderOptions::default().escape_html);
+    }
+}
@@ -1,11 +1,20 @@
+use super::filters::{apply_filter_chain, escape_html, filter_invocation_name, split_filter_chain};
 use super::nested_access::resolve_nested_path;
+use super::render_options::RenderOptions;
+use super::utils::advance_past_whitespace;
 use super::validation::is_valid_variable_name;
 use crate::error::{Error, Result};
 use std::collections::HashMap;
 use std::fmt::Write;
 
 /// Replaces all Liquid variables in a template with their corresponding values.
-/// Now supports nested object access with dot notation and array indexing.
+///
+/// This is a thin wrapper over [`replace_template_variables_with_options`]
+/// with HTML-escaping left off, preserving this function's historical
+/// behavior of inserting a resolved value verbatim - callers such as
+/// [`crate::layout::insert_body_into_layout`] interpolate already-rendered
+/// HTML (a page's `body`) through this exact function, so turning escaping
+/// on here by default would corrupt every page's output.
 ///
 /// # Arguments
 /// * `template` - The template string containing Liquid variables
@@ -16,6 +25,41 @@ use std::fmt::Write;
 pub fn replace_template_variables(
     template: &str,
     variables: &HashMap<String, String>,
+) -> Result<String> {
+    replace_template_variables_with_options(template, variables, &RenderOptions::default())
+}
+
+/// Replaces all Liquid variables in a template with their corresponding values.
+/// Supports nested object access with dot notation and array indexing, as well
+/// as a chain of filters (e.g. `{{ name | upcase | truncate: 5 }}`) applied
+/// left-to-right to the resolved value.
+///
+/// When `options.escape_html` is set, a resolved value is HTML-escaped before
+/// being inserted, unless the variable opts out by being wrapped in triple
+/// braces (`{{{ name }}}`, Handlebars-style), its filter chain ends in `|
+/// raw`, or it already ends in `| escape` or `| json` (both of which produce
+/// their own, differently-escaped output that auto-escaping would otherwise
+/// mangle). The triple-brace and `| raw` syntax is recognized regardless of
+/// `options.escape_html` so a template doesn't silently change meaning if
+/// escaping is later turned on for it.
+///
+/// Also honors Liquid's whitespace-control markers: a leading `-` right
+/// after the opening braces (`{{- name }}`) strips trailing whitespace
+/// already written to the output, and a trailing `-` right before the
+/// closing braces (`{{ name -}}`) skips whitespace immediately following
+/// the tag in the template.
+///
+/// # Arguments
+/// * `template` - The template string containing Liquid variables
+/// * `variables` - A `HashMap` containing variable names and their values
+/// * `options` - Controls whether resolved values are HTML-escaped
+///
+/// # Returns
+/// * `Result<String>` - The template with all variables replaced or an error if malformed
+pub fn replace_template_variables_with_options(
+    template: &str,
+    variables: &HashMap<String, String>,
+    options: &RenderOptions,
 ) -> Result<String> {
     let mut result = String::with_capacity(template.len());
     let mut chars = template.chars().peekable();
@@ -25,20 +69,68 @@ pub fn replace_template_variables(
             // consume second '{'
             chars.next();
 
-            // Read entire variable content up to '}}'
-            let content = super::utils::read_liquid_variable_content(&mut chars)?;
-            let var_name = content.trim().to_string();
+            // A leading '-' immediately inside the braces (`{{-`) is a
+            // whitespace-control marker asking us to strip trailing
+            // whitespace already written to `result`.
+            let strip_leading = chars.peek() == Some(&'-');
+            if strip_leading {
+                chars.next();
+                let trimmed_len = result.trim_end().len();
+                result.truncate(trimmed_len);
+            }
+
+            // A third '{' opts the variable out of auto-escaping; it must be
+            // balanced by a third '}' on the way out.
+            let triple_braced = chars.peek() == Some(&'{');
+            if triple_braced {
+                chars.next();
+            }
+
+            // Read entire variable content up to '}}' (or '-}}')
+            let (content, strip_trailing) =
+                super::utils::read_liquid_variable_content(&mut chars)?;
+            if triple_braced && chars.next() != Some('}') {
+                return Err(Error::Liquid("Unclosed variable in template".to_string()));
+            }
+
+            let mut segments = split_filter_chain(&content);
+            let var_name = segments.remove(0);
 
             if !is_valid_variable_name(&var_name) {
                 return Err(Error::Liquid(format!("Invalid variable name: {var_name}")));
             }
 
-            // Try to resolve the variable using nested access
-            if let Some(value) = resolve_nested_path(&var_name, variables) {
-                result.push_str(&value);
-            } else {
-                // Variable not found, keep the original placeholder
-                write!(result, "{{{{ {var_name} }}}}").unwrap();
+            let raw_filter =
+                segments.last().map(|s| filter_invocation_name(s)).as_deref() == Some("raw");
+            if raw_filter {
+                segments.pop();
+            }
+            let skip_escaping = !options.escape_html
+                || triple_braced
+                || raw_filter
+                || matches!(
+                    segments.last().map(|s| filter_invocation_name(s)).as_deref(),
+                    Some("escape") | Some("json")
+                );
+
+            // Try to resolve the variable using nested access, then run the result
+            // (or its absence) through the filter chain.
+            let resolved = resolve_nested_path(&var_name, variables);
+            match apply_filter_chain(resolved, &segments)? {
+                Some(value) if skip_escaping => result.push_str(&value),
+                Some(value) => result.push_str(&escape_html(&value)),
+                None => {
+                    // Variable not found and no filter supplied a default, keep the
+                    // original placeholder.
+                    write!(result, "{{{{ {var_name} }}}}").unwrap();
+                }
+            }
+
+            // A trailing '-' immediately before the closing braces (`-}}`) is
+            // a whitespace-control marker asking us to skip whitespace
+            // immediately following the tag in the input.
+            if strip_trailing {
+                advance_past_whitespace(&mut chars);
             }
         } else {
             result.push(current);
@@ -148,4 +240,254 @@ mod tests {
         let result = replace_template_variables(template, &variables);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_replace_variables_with_single_filter() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = "Hello {{ name | upcase }}!";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "Hello ALICE!");
+    }
+
+    #[test]
+    fn test_replace_variables_with_chained_filters() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "  Alice  ".to_string());
+
+        let template = "Hello {{ name | downcase | truncate: 3 }}!";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "Hello   a!");
+    }
+
+    #[test]
+    fn test_replace_variables_default_filter_used_when_missing() {
+        let variables = HashMap::new();
+
+        let template = "Hello {{ name | default: \"stranger\" }}!";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "Hello stranger!");
+    }
+
+    #[test]
+    fn test_replace_variables_default_filter_ignored_when_present() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = "Hello {{ name | default: \"stranger\" }}!";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "Hello Alice!");
+    }
+
+    #[test]
+    fn test_replace_variables_unknown_filter_errors() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = "Hello {{ name | shout }}!";
+        let result = replace_template_variables(template, &variables);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_replace_variables_json_filter() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice \"A\"".to_string());
+
+        let template = "{{ name | json }}";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "\"Alice \\\"A\\\"\"");
+    }
+
+    #[test]
+    fn test_replace_variables_filter_chain_still_validates_variable_name() {
+        let variables = HashMap::new();
+
+        let template = "Hello {{ invalid-name | upcase }}!";
+        let result = replace_template_variables(template, &variables);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_replace_variables_does_not_escape_html_by_default() {
+        // `body`/`content`-style variables carry already-rendered HTML through
+        // this exact function (see `insert_body_into_layout`), so the
+        // zero-options default must keep passing it through verbatim.
+        let mut variables = HashMap::new();
+        variables.insert("body".to_string(), "<p>Hello & welcome</p>".to_string());
+
+        let template = "<main>{{ body }}</main>";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "<main><p>Hello & welcome</p></main>");
+    }
+
+    #[test]
+    fn test_replace_variables_escapes_html_when_enabled() {
+        let mut variables = HashMap::new();
+        variables.insert("bio".to_string(), "<b>Alice</b> & \"friends\"".to_string());
+
+        let options = RenderOptions {
+            escape_html: true,
+            ..Default::default()
+        };
+        let template = "Bio: {{ bio }}";
+        let result = replace_template_variables_with_options(template, &variables, &options)
+            .unwrap();
+        assert_eq!(result, "Bio: &lt;b&gt;Alice&lt;/b&gt; &amp; &quot;friends&quot;");
+    }
+
+    #[test]
+    fn test_replace_variables_escapes_after_filters_are_applied() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "alice <script>".to_string());
+
+        let options = RenderOptions {
+            escape_html: true,
+            ..Default::default()
+        };
+        let template = "{{ name | upcase }}";
+        let result = replace_template_variables_with_options(template, &variables, &options)
+            .unwrap();
+        assert_eq!(result, "ALICE &lt;SCRIPT&gt;");
+    }
+
+    #[test]
+    fn test_replace_variables_triple_braces_opt_out_of_escaping() {
+        let mut variables = HashMap::new();
+        variables.insert("bio".to_string(), "<b>Alice</b>".to_string());
+
+        let options = RenderOptions {
+            escape_html: true,
+            ..Default::default()
+        };
+        let template = "Bio: {{{ bio }}}";
+        let result = replace_template_variables_with_options(template, &variables, &options)
+            .unwrap();
+        assert_eq!(result, "Bio: <b>Alice</b>");
+    }
+
+    #[test]
+    fn test_replace_variables_unclosed_triple_brace_errors() {
+        let mut variables = HashMap::new();
+        variables.insert("bio".to_string(), "<b>Alice</b>".to_string());
+
+        let template = "Bio: {{{ bio }}";
+        let result = replace_template_variables(template, &variables);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_replace_variables_raw_filter_opts_out_of_escaping() {
+        let mut variables = HashMap::new();
+        variables.insert("bio".to_string(), "<b>Alice</b>".to_string());
+
+        let options = RenderOptions {
+            escape_html: true,
+            ..Default::default()
+        };
+        let template = "Bio: {{ bio | raw }}";
+        let result = replace_template_variables_with_options(template, &variables, &options)
+            .unwrap();
+        assert_eq!(result, "Bio: <b>Alice</b>");
+    }
+
+    #[test]
+    fn test_replace_variables_raw_filter_combines_with_other_filters() {
+        let mut variables = HashMap::new();
+        variables.insert("bio".to_string(), "<b>alice</b>".to_string());
+
+        let options = RenderOptions {
+            escape_html: true,
+            ..Default::default()
+        };
+        let template = "Bio: {{ bio | upcase | raw }}";
+        let result = replace_template_variables_with_options(template, &variables, &options)
+            .unwrap();
+        assert_eq!(result, "Bio: <B>ALICE</B>");
+    }
+
+    #[test]
+    fn test_replace_variables_explicit_escape_filter_is_not_double_escaped() {
+        let mut variables = HashMap::new();
+        variables.insert("bio".to_string(), "<b>Alice</b>".to_string());
+
+        let options = RenderOptions {
+            escape_html: true,
+            ..Default::default()
+        };
+        let template = "Bio: {{ bio | escape }}";
+        let result = replace_template_variables_with_options(template, &variables, &options)
+            .unwrap();
+        assert_eq!(result, "Bio: &lt;b&gt;Alice&lt;/b&gt;");
+    }
+
+    #[test]
+    fn test_replace_variables_json_filter_is_not_escaped() {
+        let mut variables = HashMap::new();
+        variables.insert("bio".to_string(), "<b>Alice</b>".to_string());
+
+        let options = RenderOptions {
+            escape_html: true,
+            ..Default::default()
+        };
+        let template = "{{ bio | json }}";
+        let result = replace_template_variables_with_options(template, &variables, &options)
+            .unwrap();
+        assert_eq!(result, "\"<b>Alice</b>\"");
+    }
+
+    #[test]
+    fn test_replace_variables_not_found_placeholder_is_not_escaped() {
+        let variables = HashMap::new();
+
+        let options = RenderOptions {
+            escape_html: true,
+            ..Default::default()
+        };
+        let template = "Hello {{{ missing.variable }}}!";
+        let result = replace_template_variables_with_options(template, &variables, &options)
+            .unwrap();
+        assert_eq!(result, "Hello {{ missing.variable }}!");
+    }
+
+    #[test]
+    fn test_replace_variables_leading_whitespace_control_strips_preceding_whitespace() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = "Hello   \n\t {{- name }}!";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "HelloAlice!");
+    }
+
+    #[test]
+    fn test_replace_variables_trailing_whitespace_control_skips_following_whitespace() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = "{{ name -}}   \n\t  !";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "Alice!");
+    }
+
+    #[test]
+    fn test_replace_variables_whitespace_control_on_both_sides() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = "Hello   {{- name -}}  \n  !";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "HelloAlice!");
+    }
+
+    #[test]
+    fn test_replace_variables_whitespace_control_does_not_affect_plain_variables() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = "Hello   {{ name }}  !";
+        let result = replace_template_variables(template, &variables).unwrap();
+        assert_eq!(result, "Hello   Alice  !");
+    }
 }
@@ -0,0 +1,1018 @@
+use super::_if::evaluate_condition;
+use super::filters::{apply_filter_chain, split_filter_chain};
+use super::include::IncludeResolver;
+use super::nested_access::resolve_nested_path;
+use super::parse_include_tag::parse_liquid_include_tag;
+use super::validation::is_valid_variable_name;
+use super::utils::{find_collection_size, parse_space_separated_key_value_params};
+use crate::error::{line_col, Error, Result};
+use std::collections::HashMap;
+
+/// A single lexeme produced by [`tokenize`]: either a run of plain text, the
+/// (unparsed, untrimmed) content of a `{{ ... }}` expression, or the
+/// (unparsed, untrimmed) content of a `{% ... %}` tag. Keeping the content
+/// untrimmed lets a caller that doesn't understand a given tag reconstruct
+/// it byte-for-byte.
+enum Token {
+    Lit(String),
+    Expr(String),
+    Tag(String),
+}
+
+/// Splits `template` into a flat stream of tokens in a single left-to-right
+/// pass, each paired with its starting byte offset. This replaces the
+/// repeated `find`/re-scan approach used by the older tag-by-tag processors:
+/// the whole template is walked once here, and the resulting tokens are
+/// handed to a recursive-descent parser rather than re-searched per nesting
+/// level.
+fn tokenize(template: &str) -> Vec<(Token, usize)> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    let mut offset = 0usize;
+
+    loop {
+        let next_expr = rest.find("{{");
+        let next_tag = rest.find("{%");
+
+        let start = match (next_expr, next_tag) {
+            (Some(e), Some(t)) => e.min(t),
+            (Some(e), None) => e,
+            (None, Some(t)) => t,
+            (None, None) => {
+                if !rest.is_empty() {
+                    tokens.push((Token::Lit(rest.to_string()), offset));
+                }
+                return tokens;
+            }
+        };
+
+        if start > 0 {
+            tokens.push((Token::Lit(rest[..start].to_string()), offset));
+        }
+
+        let is_expr = rest[start..].starts_with("{{");
+        let close = if is_expr { "}}" } else { "%}" };
+        let content_start = start + 2;
+
+        let Some(close_rel) = rest[content_start..].find(close) else {
+            // An unterminated `{{`/`{%` has no matching close - keep it (and
+            // everything after it) as plain text so the caller sees the
+            // original, unconsumed markers rather than losing them.
+            tokens.push((Token::Lit(rest[start..].to_string()), offset + start));
+            return tokens;
+        };
+
+        let content_end = content_start + close_rel;
+        let content = rest[content_start..content_end].to_string();
+        let tag_offset = offset + start;
+
+        tokens.push((
+            if is_expr { Token::Expr(content) } else { Token::Tag(content) },
+            tag_offset,
+        ));
+
+        let consumed = content_end + close.len();
+        offset += consumed;
+        rest = &rest[consumed..];
+    }
+}
+
+/// A single `if`/`elsif`/`else` branch: its condition (`None` for `else`)
+/// and its body.
+type Branch = (Option<String>, Vec<Node>);
+
+/// A node of the compiled template AST produced by [`compile`].
+#[derive(Debug)]
+enum Node {
+    Lit(String),
+    Expr(String),
+    Cond(Vec<Branch>),
+    /// `{% unless condition %}...{% endunless %}`: the inverse of a single
+    /// `{% if %}` branch, with no `else`/`elsif` counterpart - matching
+    /// [`super::unless::process_liquid_unless_tags`]'s grammar.
+    Unless(String, Vec<Node>),
+    Loop {
+        item_var: String,
+        collection: String,
+        limit: Option<usize>,
+        offset: usize,
+        reversed: bool,
+        body: Vec<Node>,
+        /// Rendered instead of `body` when the collection has no items.
+        else_body: Vec<Node>,
+    },
+    Include {
+        name: String,
+        params: HashMap<String, String>,
+        raw: String,
+    },
+}
+
+/// A template compiled once by [`compile`] and rendered as many times as
+/// needed against different variable scopes - useful when the same layout
+/// is applied across hundreds of pages, since the tokenizing/parsing work
+/// happens only once.
+#[derive(Debug)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+/// Compiles `template` into a [`Template`]. Tokenizes the whole template in
+/// one pass and parses it into a nested AST using a block stack (tracked via
+/// the recursion of [`parse_nodes`]/the `if`/`for` handlers below) that
+/// enforces correct open/close pairing - an unmatched closing tag, or a
+/// block left open at the end of the template, is reported with the
+/// `line:column` location of the offending tag.
+pub fn compile(template: &str) -> Result<Template> {
+    let tokens = tokenize(template);
+    let mut pos = 0;
+    let nodes = parse_nodes(&tokens, &mut pos, template)?;
+
+    if pos != tokens.len() {
+        let (token, offset) = &tokens[pos];
+        let label = match token {
+            Token::Tag(content) => format!("{{% {} %}}", content.trim()),
+            Token::Expr(content) => format!("{{{{ {} }}}}", content.trim()),
+            Token::Lit(_) => unreachable!("a literal token never stops node parsing"),
+        };
+        let (line, col) = line_col(template, *offset);
+        return Err(Error::Liquid(format!(
+            "Unexpected {label} at {line}:{col} with no matching opening tag"
+        )));
+    }
+
+    Ok(Template { nodes })
+}
+
+impl Template {
+    /// Renders the compiled template against `variables`. `{% include %}`
+    /// tags are left untouched unless a resolver is supplied via
+    /// [`Template::render_with_includes`], matching the production pipeline
+    /// where includes are resolved in their own later pass.
+    pub fn render(&self, variables: &HashMap<String, String>) -> Result<String> {
+        self.render_with_includes(variables, None)
+    }
+
+    /// Same as [`Template::render`], but also resolves `{% include %}` tags
+    /// through `includes`, recursively expanding any includes a partial
+    /// itself contains (rejecting a partial that re-enters its own
+    /// expansion, the same cycle guard as [`super::include::process_liquid_includes`]).
+    pub fn render_with_includes(
+        &self,
+        variables: &HashMap<String, String>,
+        includes: Option<&dyn IncludeResolver>,
+    ) -> Result<String> {
+        render_nodes(&self.nodes, variables, includes, &mut Vec::new())
+    }
+}
+
+/// Parses as many nodes as possible starting at `*pos`, stopping (without
+/// consuming) at any token that can only belong to an enclosing block -
+/// `{% else %}`, `{% elsif %}`, `{% endif %}`, `{% endfor %}`. The calling
+/// construct (the `if`/`for` handlers below) is responsible for checking
+/// which of those is legal next.
+fn parse_nodes(tokens: &[(Token, usize)], pos: &mut usize, source: &str) -> Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+
+    while *pos < tokens.len() {
+        let (token, offset) = &tokens[*pos];
+
+        match token {
+            Token::Lit(text) => {
+                nodes.push(Node::Lit(text.clone()));
+                *pos += 1;
+            }
+            Token::Expr(content) => {
+                nodes.push(Node::Expr(content.clone()));
+                *pos += 1;
+            }
+            Token::Tag(content) => {
+                let trimmed = content.trim();
+
+                if trimmed == "else"
+                    || trimmed == "endif"
+                    || trimmed == "endfor"
+                    || trimmed == "endunless"
+                    || trimmed == "elsif"
+                    || trimmed.starts_with("elsif ")
+                {
+                    break;
+                }
+
+                if trimmed == "if" || trimmed.starts_with("if ") {
+                    nodes.push(parse_if(tokens, pos, source)?);
+                } else if trimmed == "unless" || trimmed.starts_with("unless ") {
+                    nodes.push(parse_unless(tokens, pos, source)?);
+                } else if trimmed.starts_with("for ") {
+                    nodes.push(parse_for(tokens, pos, *offset, source)?);
+                } else if trimmed == "include" || trimmed.starts_with("include ") {
+                    nodes.push(parse_include(content));
+                    *pos += 1;
+                } else {
+                    // A tag this AST doesn't interpret (e.g. `assign`,
+                    // `unless`, `render`) is kept as literal text so it
+                    // survives untouched for whichever pass does understand
+                    // it.
+                    nodes.push(Node::Lit(format!("{{%{content}%}}")));
+                    *pos += 1;
+                }
+            }
+        }
+    }
+
+    Ok(nodes)
+}
+
+fn parse_if(tokens: &[(Token, usize)], pos: &mut usize, source: &str) -> Result<Node> {
+    let if_offset = tokens[*pos].1;
+    let Token::Tag(content) = &tokens[*pos].0 else {
+        unreachable!("parse_if is only called at an if tag")
+    };
+    let condition = content.trim().strip_prefix("if").unwrap_or("").trim().to_string();
+    *pos += 1;
+
+    let mut branches = vec![(Some(condition), parse_nodes(tokens, pos, source)?)];
+
+    loop {
+        let Some((token, _)) = tokens.get(*pos) else {
+            let (line, col) = line_col(source, if_offset);
+            return Err(Error::Liquid(format!(
+                "Unclosed {{% if %}} tag at {line}:{col} - missing {{% endif %}}"
+            )));
+        };
+
+        let Token::Tag(content) = token else {
+            unreachable!("parse_nodes only stops at a Tag token")
+        };
+        let trimmed = content.trim();
+
+        if trimmed == "else" {
+            *pos += 1;
+            branches.push((None, parse_nodes(tokens, pos, source)?));
+        } else if trimmed == "elsif" || trimmed.starts_with("elsif ") {
+            let condition = trimmed.strip_prefix("elsif").unwrap_or("").trim().to_string();
+            *pos += 1;
+            branches.push((Some(condition), parse_nodes(tokens, pos, source)?));
+        } else if trimmed == "endif" {
+            *pos += 1;
+            break;
+        } else {
+            let (line, col) = line_col(source, if_offset);
+            return Err(Error::Liquid(format!(
+                "Unclosed {{% if %}} tag at {line}:{col} - missing {{% endif %}}"
+            )));
+        }
+    }
+
+    Ok(Node::Cond(branches))
+}
+
+fn parse_unless(tokens: &[(Token, usize)], pos: &mut usize, source: &str) -> Result<Node> {
+    let unless_offset = tokens[*pos].1;
+    let Token::Tag(content) = &tokens[*pos].0 else {
+        unreachable!("parse_unless is only called at an unless tag")
+    };
+    let condition = content.trim().strip_prefix("unless").unwrap_or("").trim().to_string();
+    *pos += 1;
+
+    let body = parse_nodes(tokens, pos, source)?;
+
+    match tokens.get(*pos) {
+        Some((Token::Tag(content), _)) if content.trim() == "endunless" => {
+            *pos += 1;
+        }
+        _ => {
+            let (line, col) = line_col(source, unless_offset);
+            return Err(Error::Liquid(format!(
+                "Unclosed {{% unless %}} tag at {line}:{col} - missing {{% endunless %}}"
+            )));
+        }
+    }
+
+    Ok(Node::Unless(condition, body))
+}
+
+fn parse_for(
+    tokens: &[(Token, usize)],
+    pos: &mut usize,
+    for_offset: usize,
+    source: &str,
+) -> Result<Node> {
+    let Token::Tag(content) = &tokens[*pos].0 else {
+        unreachable!("parse_for is only called at a for tag")
+    };
+    let for_content = content.trim().strip_prefix("for ").unwrap_or("").trim();
+    let parts: Vec<&str> = for_content.splitn(2, " in ").collect();
+    if parts.len() != 2 {
+        return Err(Error::Liquid("Invalid for loop syntax".to_string()));
+    }
+    let item_var = parts[0].trim().to_string();
+
+    let rhs = parts[1].trim();
+    let mut rhs_iter = rhs.split_whitespace();
+    let collection = rhs_iter
+        .next()
+        .ok_or_else(|| Error::Liquid("Invalid for loop syntax".to_string()))?
+        .to_string();
+
+    let mut limit = None;
+    let mut offset = 0usize;
+    let mut reversed = false;
+    let params_str = rhs_iter.collect::<Vec<_>>().join(" ");
+    if !params_str.is_empty() {
+        let params = parse_space_separated_key_value_params(&params_str);
+        if let Some(limit_str) = params.get("limit") {
+            limit = limit_str.parse::<usize>().ok();
+        }
+        if let Some(offset_str) = params.get("offset") {
+            offset = offset_str.parse::<usize>().unwrap_or(0);
+        }
+        reversed = params_str.split_whitespace().any(|token| token == "reversed");
+    }
+
+    *pos += 1;
+    let body = parse_nodes(tokens, pos, source)?;
+
+    let else_body = match tokens.get(*pos) {
+        Some((Token::Tag(content), _)) if content.trim() == "else" => {
+            *pos += 1;
+            parse_nodes(tokens, pos, source)?
+        }
+        _ => Vec::new(),
+    };
+
+    match tokens.get(*pos) {
+        Some((Token::Tag(content), _)) if content.trim() == "endfor" => {
+            *pos += 1;
+        }
+        _ => {
+            let (line, col) = line_col(source, for_offset);
+            return Err(Error::Liquid(format!(
+                "Unclosed {{% for %}} tag at {line}:{col} - missing {{% endfor %}}"
+            )));
+        }
+    }
+
+    Ok(Node::Loop {
+        item_var,
+        collection,
+        limit,
+        offset,
+        reversed,
+        body,
+        else_body,
+    })
+}
+
+fn parse_include(raw_content: &str) -> Node {
+    let raw = format!("{{%{raw_content}%}}");
+
+    match parse_liquid_include_tag(&raw) {
+        Some((name, params)) => Node::Include { name, params, raw },
+        // Malformed include tags are left untouched rather than failing the
+        // whole compile - the same leniency `process_liquid_includes` gives
+        // a tag it can't parse.
+        None => Node::Lit(raw),
+    }
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    variables: &HashMap<String, String>,
+    includes: Option<&dyn IncludeResolver>,
+    in_progress: &mut Vec<String>,
+) -> Result<String> {
+    let mut out = String::new();
+
+    for node in nodes {
+        match node {
+            Node::Lit(text) => out.push_str(text),
+            Node::Expr(content) => out.push_str(&render_expr(content, variables)?),
+            Node::Cond(branches) => {
+                let matched = branches.iter().find_map(|(condition, body)| {
+                    let matches = match condition {
+                        Some(cond) => evaluate_condition(cond.trim(), variables),
+                        None => true,
+                    };
+                    matches.then_some(body)
+                });
+
+                if let Some(body) = matched {
+                    out.push_str(&render_nodes(body, variables, includes, in_progress)?);
+                }
+            }
+            Node::Unless(condition, body) => {
+                if !evaluate_condition(condition.trim(), variables) {
+                    out.push_str(&render_nodes(body, variables, includes, in_progress)?);
+                }
+            }
+            Node::Loop {
+                item_var,
+                collection,
+                limit,
+                offset,
+                reversed,
+                body,
+                else_body,
+            } => {
+                out.push_str(&render_loop(
+                    item_var,
+                    collection,
+                    *limit,
+                    *offset,
+                    *reversed,
+                    body,
+                    else_body,
+                    variables,
+                    includes,
+                    in_progress,
+                )?);
+            }
+            Node::Include { name, params, raw } => {
+                out.push_str(&render_include(name, params, raw, variables, includes, in_progress)?);
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+fn render_expr(content: &str, variables: &HashMap<String, String>) -> Result<String> {
+    let mut segments = split_filter_chain(content);
+    let var_name = segments.remove(0);
+
+    if !is_valid_variable_name(&var_name) {
+        return Err(Error::Liquid(format!("Invalid variable name: {var_name}")));
+    }
+
+    let resolved = resolve_nested_path(&var_name, variables);
+    match apply_filter_chain(resolved, &segments)? {
+        Some(value) => Ok(value),
+        None => Ok(format!("{{{{ {var_name} }}}}")),
+    }
+}
+
+/// Applies `offset`, then `limit`, then `reversed` (in that order) to the
+/// collection positions `0..total_size`, matching the iteration order used
+/// by the string-substitution `{% for %}` pass's `build_iteration_sequence`.
+fn build_loop_sequence(total_size: usize, limit: Option<usize>, offset: usize, reversed: bool) -> Vec<usize> {
+    let mut sequence: Vec<usize> = (offset.min(total_size)..total_size).collect();
+    if let Some(lim) = limit {
+        sequence.truncate(lim);
+    }
+    if reversed {
+        sequence.reverse();
+    }
+    sequence
+}
+
+fn parse_loop_range(collection: &str, variables: &HashMap<String, String>) -> Option<(i64, i64)> {
+    let inner = collection.strip_prefix('(')?.strip_suffix(')')?;
+    let (start_str, end_str) = inner.split_once("..")?;
+    let start = resolve_range_bound(start_str.trim(), variables)?;
+    let end = resolve_range_bound(end_str.trim(), variables)?;
+    Some((start, end))
+}
+
+fn resolve_range_bound(s: &str, variables: &HashMap<String, String>) -> Option<i64> {
+    s.parse::<i64>().ok().or_else(|| variables.get(s)?.parse::<i64>().ok())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_loop(
+    item_var: &str,
+    collection: &str,
+    limit: Option<usize>,
+    offset: usize,
+    reversed: bool,
+    body: &[Node],
+    else_body: &[Node],
+    variables: &HashMap<String, String>,
+    includes: Option<&dyn IncludeResolver>,
+    in_progress: &mut Vec<String>,
+) -> Result<String> {
+    if let Some((start, end)) = parse_loop_range(collection, variables) {
+        return render_range_loop(
+            item_var, start, end, limit, offset, reversed, body, variables, includes, in_progress,
+        );
+    }
+
+    let total_size = find_collection_size(collection, variables);
+    if total_size == 0 {
+        return render_nodes(else_body, variables, includes, in_progress);
+    }
+
+    let sequence = build_loop_sequence(total_size, limit, offset, reversed);
+    let loop_len = sequence.len();
+
+    let mut out = String::new();
+
+    for (pos, &i) in sequence.iter().enumerate() {
+        // Each iteration gets its own scope, built fresh from the outer
+        // variables - unlike the string-substitution approach used by the
+        // existing `{% for %}` pass, this means a nested loop's `forloop.*`
+        // bindings can never leak into an outer loop's body or vice versa.
+        let mut scoped = variables.clone();
+
+        let item_prefix = format!("{collection}.{i}.");
+        for (key, value) in variables {
+            if let Some(rest) = key.strip_prefix(&item_prefix) {
+                scoped.insert(format!("{item_var}.{rest}"), value.clone());
+            }
+        }
+        if let Some(value) = variables.get(&format!("{collection}.{i}")) {
+            scoped.insert(item_var.to_string(), value.clone());
+        }
+
+        scoped.insert("forloop.index".to_string(), (pos + 1).to_string());
+        scoped.insert("forloop.index0".to_string(), pos.to_string());
+        scoped.insert("forloop.first".to_string(), (pos == 0).to_string());
+        scoped.insert("forloop.last".to_string(), (pos + 1 == loop_len).to_string());
+        scoped.insert("forloop.length".to_string(), loop_len.to_string());
+
+        out.push_str(&render_nodes(body, &scoped, includes, in_progress)?);
+    }
+
+    Ok(out)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_range_loop(
+    item_var: &str,
+    start: i64,
+    end: i64,
+    limit: Option<usize>,
+    offset: usize,
+    reversed: bool,
+    body: &[Node],
+    variables: &HashMap<String, String>,
+    includes: Option<&dyn IncludeResolver>,
+    in_progress: &mut Vec<String>,
+) -> Result<String> {
+    let step: i64 = if end >= start { 1 } else { -1 };
+    let total_size = start.abs_diff(end) as usize + 1;
+
+    let sequence = build_loop_sequence(total_size, limit, offset, reversed);
+    let loop_len = sequence.len();
+
+    let mut out = String::new();
+
+    for (pos, &i) in sequence.iter().enumerate() {
+        let mut scoped = variables.clone();
+        let value = start + step * i as i64;
+        scoped.insert(item_var.to_string(), value.to_string());
+
+        scoped.insert("forloop.index".to_string(), (pos + 1).to_string());
+        scoped.insert("forloop.index0".to_string(), pos.to_string());
+        scoped.insert("forloop.first".to_string(), (pos == 0).to_string());
+        scoped.insert("forloop.last".to_string(), (pos + 1 == loop_len).to_string());
+        scoped.insert("forloop.length".to_string(), loop_len.to_string());
+
+        out.push_str(&render_nodes(body, &scoped, includes, in_progress)?);
+    }
+
+    Ok(out)
+}
+
+fn render_include(
+    name: &str,
+    params: &HashMap<String, String>,
+    raw: &str,
+    variables: &HashMap<String, String>,
+    includes: Option<&dyn IncludeResolver>,
+    in_progress: &mut Vec<String>,
+) -> Result<String> {
+    let Some(resolver) = includes else {
+        return Ok(raw.to_string());
+    };
+
+    // Templates are keyed without their `.liquid` extension, same as
+    // `{% render %}` and `process_liquid_includes`.
+    let name = name.strip_suffix(".liquid").unwrap_or(name).to_string();
+
+    if in_progress.contains(&name) {
+        let mut chain = in_progress.clone();
+        chain.push(name);
+        return Err(Error::Liquid(format!(
+            "Include cycle detected: {}",
+            chain.join(" -> ")
+        )));
+    }
+
+    let Some(partial) = resolver.resolve(&name) else {
+        return Ok(raw.to_string());
+    };
+
+    let mut scoped_variables = variables.clone();
+    scoped_variables.extend(params.clone());
+
+    let partial_template = compile(&partial)?;
+    in_progress.push(name);
+    let rendered = render_nodes(&partial_template.nodes, &scoped_variables, includes, in_progress)?;
+    in_progress.pop();
+
+    Ok(rendered)
+}
+
+/// A reduced AST used only by [`render_conditionals`]: everything other
+/// than `{% if/elsif/else/endif %}` - including `{{ expr }}`, `{% for %}`,
+/// and `{% include %}` - is opaque literal text at this stage, since this
+/// grammar backs [`super::_if::process_liquid_conditional_tags`], a single
+/// step in a pipeline where those other tags are resolved in earlier or
+/// later passes.
+enum CondNode {
+    Lit(String),
+    Cond(Vec<(Option<String>, Vec<CondNode>)>),
+}
+
+/// Tokenizes and parses `template` into the conditionals-only grammar, then
+/// renders it against `variables`. This is the single-pass replacement for
+/// the old `find_nested_if_block`/`read_nested_if_content` pair: the whole
+/// template is tokenized once up front instead of being re-scanned with
+/// `find` at every nesting level.
+pub(super) fn render_conditionals(template: &str, variables: &HashMap<String, String>) -> Result<String> {
+    let tokens = tokenize(template);
+    let mut pos = 0;
+    let nodes = parse_cond_nodes(&tokens, &mut pos)?;
+
+    if pos != tokens.len() {
+        // An `{% endif %}`/`{% else %}`/`{% elsif %}` with no enclosing
+        // `{% if %}` to belong to.
+        return Err(Error::Liquid("Missing {% endif %} tag".to_string()));
+    }
+
+    render_cond_nodes(&nodes, variables)
+}
+
+fn parse_cond_nodes(tokens: &[(Token, usize)], pos: &mut usize) -> Result<Vec<CondNode>> {
+    let mut nodes = Vec::new();
+
+    while *pos < tokens.len() {
+        let (token, _) = &tokens[*pos];
+
+        match token {
+            Token::Lit(text) => {
+                nodes.push(CondNode::Lit(text.clone()));
+                *pos += 1;
+            }
+            Token::Expr(content) => {
+                nodes.push(CondNode::Lit(format!("{{{{{content}}}}}")));
+                *pos += 1;
+            }
+            Token::Tag(content) => {
+                let trimmed = content.trim();
+
+                if trimmed == "else"
+                    || trimmed == "endif"
+                    || trimmed == "elsif"
+                    || trimmed.starts_with("elsif ")
+                {
+                    break;
+                }
+
+                if trimmed == "if" || trimmed.starts_with("if ") {
+                    nodes.push(parse_cond_if(tokens, pos)?);
+                } else {
+                    nodes.push(CondNode::Lit(format!("{{%{content}%}}")));
+                    *pos += 1;
+                }
+            }
+        }
+    }
+
+    Ok(nodes)
+}
+
+fn parse_cond_if(tokens: &[(Token, usize)], pos: &mut usize) -> Result<CondNode> {
+    let Token::Tag(content) = &tokens[*pos].0 else {
+        unreachable!("parse_cond_if is only called at an if tag")
+    };
+    let condition = content.trim().strip_prefix("if").unwrap_or("").trim().to_string();
+    *pos += 1;
+
+    let mut branches = vec![(Some(condition), parse_cond_nodes(tokens, pos)?)];
+
+    loop {
+        let Some((Token::Tag(content), _)) = tokens.get(*pos) else {
+            return Err(Error::Liquid(
+                "Unclosed block - missing {% endif %}".to_string(),
+            ));
+        };
+        let trimmed = content.trim();
+
+        if trimmed == "else" {
+            *pos += 1;
+            branches.push((None, parse_cond_nodes(tokens, pos)?));
+        } else if trimmed == "elsif" || trimmed.starts_with("elsif ") {
+            let condition = trimmed.strip_prefix("elsif").unwrap_or("").trim().to_string();
+            *pos += 1;
+            branches.push((Some(condition), parse_cond_nodes(tokens, pos)?));
+        } else if trimmed == "endif" {
+            *pos += 1;
+            break;
+        } else {
+            return Err(Error::Liquid(
+                "Unclosed block - missing {% endif %}".to_string(),
+            ));
+        }
+    }
+
+    Ok(CondNode::Cond(branches))
+}
+
+fn render_cond_nodes(nodes: &[CondNode], variables: &HashMap<String, String>) -> Result<String> {
+    let mut out = String::new();
+
+    for node in nodes {
+        match node {
+            CondNode::Lit(text) => out.push_str(text),
+            CondNode::Cond(branches) => {
+                let matched = branches.iter().find_map(|(condition, body)| {
+                    let matches = match condition {
+                        Some(cond) => evaluate_condition(cond.trim(), variables),
+                        None => true,
+                    };
+                    matches.then_some(body)
+                });
+
+                if let Some(body) = matched {
+                    out.push_str(&render_cond_nodes(body, variables)?);
+                }
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_render_literal_only() {
+        let variables = HashMap::new();
+        let template = compile("Hello, world!").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "Hello, world!");
+    }
+
+    #[test]
+    fn test_compile_render_expr() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = compile("Hello, {{ name }}!").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "Hello, Alice!");
+    }
+
+    #[test]
+    fn test_compile_render_expr_with_filter() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Alice".to_string());
+
+        let template = compile("{{ name | upcase }}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "ALICE");
+    }
+
+    #[test]
+    fn test_compile_render_can_be_reused_across_variable_scopes() {
+        let template = compile("Hello, {{ name }}!").unwrap();
+
+        let mut first = HashMap::new();
+        first.insert("name".to_string(), "Alice".to_string());
+        let mut second = HashMap::new();
+        second.insert("name".to_string(), "Bob".to_string());
+
+        assert_eq!(template.render(&first).unwrap(), "Hello, Alice!");
+        assert_eq!(template.render(&second).unwrap(), "Hello, Bob!");
+    }
+
+    #[test]
+    fn test_compile_render_if_else() {
+        let mut variables = HashMap::new();
+        variables.insert("published".to_string(), "true".to_string());
+
+        let template = compile("{% if published %}Live{% else %}Draft{% endif %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "Live");
+
+        variables.insert("published".to_string(), "false".to_string());
+        assert_eq!(template.render(&variables).unwrap(), "Draft");
+    }
+
+    #[test]
+    fn test_compile_render_nested_if() {
+        let mut variables = HashMap::new();
+        variables.insert("outer".to_string(), "true".to_string());
+        variables.insert("inner".to_string(), "true".to_string());
+
+        let template =
+            compile("{% if outer %}Outer {% if inner %}Inner{% endif %}{% endif %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "Outer Inner");
+    }
+
+    #[test]
+    fn test_compile_render_for_loop() {
+        let mut variables = HashMap::new();
+        variables.insert("people.0.name".to_string(), "Alice".to_string());
+        variables.insert("people.1.name".to_string(), "Bob".to_string());
+
+        let template = compile("{% for person in people %}{{ person.name }} {% endfor %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "Alice Bob ");
+    }
+
+    #[test]
+    fn test_compile_render_for_loop_forloop_metadata() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0".to_string(), "a".to_string());
+        variables.insert("items.1".to_string(), "b".to_string());
+
+        let template =
+            compile("{% for item in items %}{{ forloop.index }}:{{ item }}{% unless forloop.last %}, {% endunless %}{% endfor %}")
+                .unwrap();
+
+        assert_eq!(template.render(&variables).unwrap(), "1:a, 2:b");
+    }
+
+    #[test]
+    fn test_compile_render_nested_for_loops_scope_independently() {
+        let mut variables = HashMap::new();
+        variables.insert("groups.0.members.0".to_string(), "Alice".to_string());
+        variables.insert("groups.0.members.1".to_string(), "Bob".to_string());
+        variables.insert("groups.1.members.0".to_string(), "Carol".to_string());
+
+        let template = compile(
+            "{% for group in groups %}[{% for member in group.members %}{{ forloop.index }}:{{ member }} {% endfor %}]{% endfor %}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            template.render(&variables).unwrap(),
+            "[1:Alice 2:Bob ][1:Carol ]"
+        );
+    }
+
+    #[test]
+    fn test_compile_render_unless() {
+        let mut variables = HashMap::new();
+        variables.insert("published".to_string(), "false".to_string());
+
+        let template = compile("{% unless published %}Draft{% endunless %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "Draft");
+
+        variables.insert("published".to_string(), "true".to_string());
+        assert_eq!(template.render(&variables).unwrap(), "");
+    }
+
+    #[test]
+    fn test_compile_unclosed_unless_errors_with_location() {
+        let result = compile("before {% unless outer %}content");
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("endunless"));
+        assert!(error_msg.contains("1:8"));
+    }
+
+    #[test]
+    fn test_compile_render_for_loop_with_offset_and_limit() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0".to_string(), "a".to_string());
+        variables.insert("items.1".to_string(), "b".to_string());
+        variables.insert("items.2".to_string(), "c".to_string());
+        variables.insert("items.3".to_string(), "d".to_string());
+
+        let template =
+            compile("{% for item in items offset:1 limit:2 %}{{ item }} {% endfor %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "b c ");
+    }
+
+    #[test]
+    fn test_compile_render_for_loop_reversed() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0".to_string(), "a".to_string());
+        variables.insert("items.1".to_string(), "b".to_string());
+        variables.insert("items.2".to_string(), "c".to_string());
+
+        let template = compile("{% for item in items reversed %}{{ item }} {% endfor %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "c b a ");
+    }
+
+    #[test]
+    fn test_compile_render_for_loop_else_branch_renders_when_empty() {
+        let variables = HashMap::new();
+        let template =
+            compile("{% for item in items %}{{ item }}{% else %}No items{% endfor %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "No items");
+    }
+
+    #[test]
+    fn test_compile_render_for_loop_else_branch_skipped_when_not_empty() {
+        let mut variables = HashMap::new();
+        variables.insert("items.0".to_string(), "a".to_string());
+
+        let template =
+            compile("{% for item in items %}{{ item }}{% else %}No items{% endfor %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_compile_render_for_loop_integer_range() {
+        let variables = HashMap::new();
+        let template = compile("{% for i in (1..3) %}{{ i }} {% endfor %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "1 2 3 ");
+    }
+
+    #[test]
+    fn test_compile_render_for_loop_integer_range_reversed_with_limit() {
+        let variables = HashMap::new();
+        let template =
+            compile("{% for i in (1..5) limit:2 reversed %}{{ i }} {% endfor %}").unwrap();
+        assert_eq!(template.render(&variables).unwrap(), "2 1 ");
+    }
+
+    #[test]
+    fn test_compile_render_include_without_resolver_is_left_untouched() {
+        let variables = HashMap::new();
+        let template = compile("{% include greeting.liquid name:\"World\" %}").unwrap();
+        assert_eq!(
+            template.render(&variables).unwrap(),
+            "{% include greeting.liquid name:\"World\" %}"
+        );
+    }
+
+    #[test]
+    fn test_compile_render_include_with_resolver() {
+        let mut templates = HashMap::new();
+        templates.insert("greeting".to_string(), "Hello, {{ name }}!".to_string());
+
+        let variables = HashMap::new();
+        let template = compile("{% include greeting.liquid name:\"World\" %}").unwrap();
+        assert_eq!(
+            template
+                .render_with_includes(&variables, Some(&templates as &dyn IncludeResolver))
+                .unwrap(),
+            "Hello, World!"
+        );
+    }
+
+    #[test]
+    fn test_compile_render_include_cycle_errors() {
+        let mut templates = HashMap::new();
+        templates.insert("loop".to_string(), "{% include loop.liquid %}".to_string());
+
+        let variables = HashMap::new();
+        let template = compile("{% include loop.liquid %}").unwrap();
+        let result = template.render_with_includes(&variables, Some(&templates as &dyn IncludeResolver));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_compile_unclosed_if_errors_with_location() {
+        let result = compile("before {% if outer %}content");
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("1:8"));
+    }
+
+    #[test]
+    fn test_compile_unclosed_if_reports_line_past_newlines() {
+        let result = compile("line one\n{% if x %}\nmore");
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("2:1"));
+    }
+
+    #[test]
+    fn test_compile_stray_endif_errors_with_location() {
+        let result = compile("content {% endif %}");
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("endif"));
+        assert!(error_msg.contains("1:9"));
+    }
+
+    #[test]
+    fn test_compile_unclosed_for_errors() {
+        let result = compile("{% for item in items %}{{ item }}");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_render_conditionals_matches_process_liquid_conditional_tags_behavior() {
+        let input = "{% if something %}lorem ipsum{% endif %} and {% if another %}stays{% endif %}";
+        let mut variables = HashMap::new();
+        variables.insert("another".to_string(), "true".to_string());
+
+        assert_eq!(
+            render_conditionals(input, &variables).unwrap(),
+            " and stays"
+        );
+    }
+
+    #[test]
+    fn test_render_conditionals_leaves_other_tags_and_expressions_untouched() {
+        let input = "{% if show %}Hello {{ name }}{% endif %}{% include header.liquid %}";
+        let mut variables = HashMap::new();
+        variables.insert("show".to_string(), "true".to_string());
+
+        assert_eq!(
+            render_conditionals(input, &variables).unwrap(),
+            "Hello {{ name }}{% include header.liquid %}"
+        );
+    }
+}
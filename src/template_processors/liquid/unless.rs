@@ -1,12 +1,18 @@
+use super::_if::evaluate_condition;
 use super::utils::find_tag_block;
-use crate::error::{Error, Result};
+use crate::error::{liquid_error_at, Result};
 use std::collections::HashMap;
 
 /// Processes Liquid unless tags in a template string.
 ///
 /// This function handles {% unless condition %}content{% endunless %} tags by:
-/// - Removing the content if the condition is true (variable exists and equals "true")
-/// - Keeping the content if the condition is false (variable doesn't exist or doesn't equal "true")
+/// - Removing the content if the condition is true
+/// - Keeping the content if the condition is false
+///
+/// The condition supports the same grammar as `{% if %}`'s: `==`, `!=`, `<`,
+/// `>`, `<=`, `>=` comparisons and `and`/`or`/`not` (see
+/// [`evaluate_condition`]), falling back to a bare identifier's truthiness
+/// when no comparison is present.
 ///
 /// # Arguments
 /// * `template` - The template string containing unless tags
@@ -31,7 +37,7 @@ pub fn process_liquid_unless_tags(
         let condition = tag_block.tag_content.trim();
 
         // Evaluate condition
-        let condition_is_true = variables.get(condition).map_or(false, |v| v == "true");
+        let condition_is_true = evaluate_condition(condition, variables);
 
         let replacement = if condition_is_true {
             String::new() // Remove content if condition is true
@@ -39,7 +45,21 @@ pub fn process_liquid_unless_tags(
             tag_block.inner_content // Keep content if condition is false
         };
 
-        replacements.push((tag_block.start, tag_block.end, replacement));
+        // A `{%-` opening delimiter trims trailing whitespace from the text
+        // before the block; a `-%}` closing delimiter trims leading
+        // whitespace from the text that follows.
+        let start = if tag_block.trim_left {
+            result[..tag_block.start].trim_end().len()
+        } else {
+            tag_block.start
+        };
+        let end = if tag_block.trim_right {
+            result.len() - result[tag_block.end..].trim_start().len()
+        } else {
+            tag_block.end
+        };
+
+        replacements.push((start, end, replacement));
         current_pos = tag_block.end;
     }
 
@@ -47,8 +67,12 @@ pub fn process_liquid_unless_tags(
     super::utils::apply_replacements_in_reverse(&mut result, &replacements);
 
     // Check if there are any unclosed unless tags
-    if result.contains("{% unless") {
-        return Err(Error::Liquid("Missing {% endunless %} tag".to_string()));
+    if let Some(unclosed_at) = result.find("{% unless") {
+        return Err(liquid_error_at(
+            &result,
+            unclosed_at,
+            "Missing {% endunless %} tag",
+        ));
     }
 
     Ok(result)
@@ -102,6 +126,17 @@ mod tests {
         assert_eq!(result, "ABYC");
     }
 
+    #[test]
+    fn test_unless_trim_markers_remove_surrounding_whitespace() {
+        let mut variables = HashMap::new();
+        variables.insert("forloop.last".to_string(), "true".to_string());
+
+        let template = "A  \n  {%- unless forloop.last -%}  , {%- endunless -%}  \n  B";
+        let result = process_liquid_unless_tags(template, &variables).unwrap();
+
+        assert_eq!(result, "AB");
+    }
+
     #[test]
     fn test_unclosed_unless_tag() {
         let variables = HashMap::new();
@@ -111,4 +146,60 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_unclosed_unless_tag_error_has_position_and_snippet() {
+        use crate::error::Error;
+
+        let variables = HashMap::new();
+
+        let template = "A{% unless condition %}B";
+        let Err(Error::Liquid(msg)) = process_liquid_unless_tags(template, &variables) else {
+            panic!("expected Error::Liquid");
+        };
+
+        assert!(msg.contains("1:2"), "{msg}");
+        assert!(msg.contains("{% unless condition %}"), "{msg}");
+    }
+
+    #[test]
+    fn test_unless_comparison_operators() {
+        let mut variables = HashMap::new();
+        variables.insert("count".to_string(), "5".to_string());
+
+        let template = "{% unless count > 3 %}low{% endunless %}";
+        assert_eq!(process_liquid_unless_tags(template, &variables).unwrap(), "");
+
+        let template = "{% unless count > 10 %}low{% endunless %}";
+        assert_eq!(
+            process_liquid_unless_tags(template, &variables).unwrap(),
+            "low"
+        );
+
+        let template = "{% unless count == 5 %}different{% endunless %}";
+        assert_eq!(
+            process_liquid_unless_tags(template, &variables).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_unless_and_or_logic() {
+        let mut variables = HashMap::new();
+        variables.insert("role".to_string(), "admin".to_string());
+        variables.insert("active".to_string(), "true".to_string());
+
+        let template = "{% unless role == \"admin\" and active %}hidden{% endunless %}";
+        assert_eq!(process_liquid_unless_tags(template, &variables).unwrap(), "");
+
+        let template = "{% unless role == \"guest\" or active %}hidden{% endunless %}";
+        assert_eq!(process_liquid_unless_tags(template, &variables).unwrap(), "");
+
+        variables.insert("active".to_string(), "false".to_string());
+        let template = "{% unless role == \"guest\" or active %}hidden{% endunless %}";
+        assert_eq!(
+            process_liquid_unless_tags(template, &variables).unwrap(),
+            "hidden"
+        );
+    }
 }
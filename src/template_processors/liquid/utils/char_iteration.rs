@@ -59,13 +59,27 @@ pub fn read_until_endunless(chars: &mut Peekable<Chars>) -> String {
     content
 }
 
-/// Reads a liquid tag's content and returns it along with whether the closing tag was found
-pub fn read_liquid_tag_content(chars: &mut Peekable<Chars>) -> (String, bool) {
+/// Reads a liquid tag's content and returns it along with whether the
+/// closing tag was found and whether it carried a whitespace-control marker
+/// (`-%}` instead of `%}`), which asks the caller to skip whitespace
+/// immediately following the tag in the input via [`advance_past_whitespace`].
+pub fn read_liquid_tag_content(chars: &mut Peekable<Chars>) -> (String, bool, bool) {
     let mut tag_content = String::new();
     let mut found_closing = false;
+    let mut strip_trailing = false;
 
-    // Collect tag content until we find %}
+    // Collect tag content until we find %} or -%}
     while let Some(c) = chars.next() {
+        if c == '-' {
+            let mut lookahead = chars.clone();
+            if lookahead.next() == Some('%') && lookahead.next() == Some('}') {
+                chars.next(); // skip '%'
+                chars.next(); // skip '}'
+                found_closing = true;
+                strip_trailing = true;
+                break;
+            }
+        }
         if c == '%' && chars.peek() == Some(&'}') {
             chars.next(); // Skip '}'
             found_closing = true;
@@ -74,7 +88,7 @@ pub fn read_liquid_tag_content(chars: &mut Peekable<Chars>) -> (String, bool) {
         tag_content.push(c);
     }
 
-    (tag_content, found_closing)
+    (tag_content, found_closing, strip_trailing)
 }
 
 /// Advances the character iterator to skip whitespace characters
@@ -89,9 +103,14 @@ pub fn advance_past_whitespace(chars: &mut Peekable<Chars>) {
 
 use crate::error::{Error, Result};
 
-/// Detects and consumes a liquid variable start `{{`.
-/// Returns true and advances the iterator past `{{` if present; otherwise returns false and leaves iterator unchanged.
-pub fn detect_variable_start(chars: &mut Peekable<Chars>) -> bool {
+/// Detects and consumes a liquid variable start `{{`, along with an optional
+/// whitespace-control marker (`{{-`) asking the caller to strip trailing
+/// whitespace from whatever it has already emitted.
+///
+/// Returns `Some(strip_leading)` and advances the iterator past `{{` (and the
+/// `-`, if present) when a variable start is found; otherwise returns `None`
+/// and leaves the iterator unchanged.
+pub fn detect_variable_start(chars: &mut Peekable<Chars>) -> Option<bool> {
     if let Some(&'{') = chars.peek() {
         let mut temp = chars.clone();
         temp.next();
@@ -99,21 +118,36 @@ pub fn detect_variable_start(chars: &mut Peekable<Chars>) -> bool {
             // consume both '{'
             chars.next();
             chars.next();
-            return true;
+            let strip_leading = chars.peek() == Some(&'-');
+            if strip_leading {
+                chars.next();
+            }
+            return Some(strip_leading);
         }
     }
-    false
+    None
 }
 
-/// Reads the content of a liquid variable until the closing `}}`.
+/// Reads the content of a liquid variable until the closing `}}`, along with
+/// whether it carried a whitespace-control marker (`-}}` instead of `}}`),
+/// which asks the caller to skip whitespace immediately following the
+/// variable in the input via [`advance_past_whitespace`].
 /// Returns an error if the variable is unclosed.
-pub fn read_liquid_variable_content(chars: &mut Peekable<Chars>) -> Result<String> {
+pub fn read_liquid_variable_content(chars: &mut Peekable<Chars>) -> Result<(String, bool)> {
     let mut content = String::new();
 
     while let Some(c) = chars.next() {
+        if c == '-' {
+            let mut lookahead = chars.clone();
+            if lookahead.next() == Some('}') && lookahead.next() == Some('}') {
+                chars.next(); // skip first '}'
+                chars.next(); // skip second '}'
+                return Ok((content, true));
+            }
+        }
         if c == '}' && chars.peek() == Some(&'}') {
             chars.next(); // consume second '}'
-            return Ok(content);
+            return Ok((content, false));
         }
         content.push(c);
     }
@@ -151,19 +185,33 @@ mod tests {
     #[test]
     fn test_read_liquid_tag_content() {
         let mut chars = " if condition %}".chars().peekable();
-        let (content, found_closing) = read_liquid_tag_content(&mut chars);
+        let (content, found_closing, strip_trailing) = read_liquid_tag_content(&mut chars);
 
         assert_eq!(content, " if condition ");
         assert!(found_closing);
+        assert!(!strip_trailing);
     }
 
     #[test]
     fn test_read_liquid_tag_content_unclosed() {
         let mut chars = " if condition".chars().peekable();
-        let (content, found_closing) = read_liquid_tag_content(&mut chars);
+        let (content, found_closing, strip_trailing) = read_liquid_tag_content(&mut chars);
 
         assert_eq!(content, " if condition");
         assert!(!found_closing);
+        assert!(!strip_trailing);
+    }
+
+    #[test]
+    fn test_read_liquid_tag_content_with_trailing_whitespace_control() {
+        let mut chars = " if condition -%}\n  rest".chars().peekable();
+        let (content, found_closing, strip_trailing) = read_liquid_tag_content(&mut chars);
+
+        assert_eq!(content, " if condition ");
+        assert!(found_closing);
+        assert!(strip_trailing);
+        let remaining: String = chars.collect();
+        assert_eq!(remaining, "\n  rest");
     }
 
     #[test]
@@ -173,20 +221,54 @@ mod tests {
         assert_eq!(chars.next(), Some('h'));
     }
 
+    #[test]
+    fn test_advance_past_whitespace_skips_newlines_and_tabs() {
+        let mut chars = " \n\t \t hello".chars().peekable();
+        advance_past_whitespace(&mut chars);
+        assert_eq!(chars.next(), Some('h'));
+    }
+
     #[test]
     fn test_detect_variable_start_and_read_variable_content() {
         let mut chars = "{{  user.name  }} rest".chars().peekable();
-        assert!(detect_variable_start(&mut chars));
-        let content = read_liquid_variable_content(&mut chars).unwrap();
+        assert_eq!(detect_variable_start(&mut chars), Some(false));
+        let (content, strip_trailing) = read_liquid_variable_content(&mut chars).unwrap();
         assert_eq!(content, "  user.name  ");
+        assert!(!strip_trailing);
         let remaining: String = chars.collect();
         assert_eq!(remaining, " rest");
     }
 
+    #[test]
+    fn test_detect_variable_start_consumes_leading_whitespace_control_marker() {
+        let mut chars = "{{- user.name }} rest".chars().peekable();
+        assert_eq!(detect_variable_start(&mut chars), Some(true));
+        let (content, strip_trailing) = read_liquid_variable_content(&mut chars).unwrap();
+        assert_eq!(content, " user.name ");
+        assert!(!strip_trailing);
+    }
+
+    #[test]
+    fn test_read_liquid_variable_content_detects_trailing_whitespace_control_marker() {
+        let mut chars = "{{ user.name -}}\n  rest".chars().peekable();
+        assert_eq!(detect_variable_start(&mut chars), Some(false));
+        let (content, strip_trailing) = read_liquid_variable_content(&mut chars).unwrap();
+        assert_eq!(content, " user.name ");
+        assert!(strip_trailing);
+        let remaining: String = chars.collect();
+        assert_eq!(remaining, "\n  rest");
+    }
+
+    #[test]
+    fn test_detect_variable_start_returns_none_without_double_brace() {
+        let mut chars = "{ user.name }".chars().peekable();
+        assert_eq!(detect_variable_start(&mut chars), None);
+    }
+
     #[test]
     fn test_read_liquid_variable_content_unclosed_error() {
         let mut chars = "{{ unclosed".chars().peekable();
-        assert!(detect_variable_start(&mut chars));
+        assert_eq!(detect_variable_start(&mut chars), Some(false));
         let err = read_liquid_variable_content(&mut chars).unwrap_err();
         match err {
             crate::error::Error::Liquid(msg) => assert!(msg.contains("Unclosed")),
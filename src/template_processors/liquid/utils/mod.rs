@@ -1,5 +1,6 @@
 pub mod char_iteration;
 pub mod find_byte;
+pub mod find_equal;
 pub mod quote_utils;
 pub mod string_utils;
 pub mod tag_parsing;
@@ -4,8 +4,19 @@ use core::arch::global_asm;
 #[cfg(target_arch = "x86_64")]
 global_asm!(include_str!("split_quotes_x86_64.s"));
 
+/// Sentinel [`split_quotes_scan`] returns when the input has more comma
+/// splits than fit in the caller's buffer, so the caller can retry with a
+/// bigger one instead of mistaking "buffer full" for "found exactly
+/// `max_splits` splits" and losing everything past it.
+#[cfg(target_arch = "x86_64")]
+const SPLIT_OVERFLOW: usize = usize::MAX;
+
 #[cfg(target_arch = "x86_64")]
 extern "C" {
+    /// Scans `ptr[..len]` for comma split positions, writing up to
+    /// `max_splits` of them into `splits`. Returns the number written, or
+    /// [`SPLIT_OVERFLOW`] if the input has more splits than `max_splits`
+    /// could hold.
     fn split_quotes_scan(
         ptr: *const u8,
         len: usize,
@@ -14,27 +25,66 @@ extern "C" {
     ) -> usize;
 }
 
-/// Splits a string on commas while respecting quotes - x86_64 assembly optimized version
+/// Splits a string on commas while respecting quotes - x86_64 assembly
+/// optimized version. Tries a small stack buffer first so the common case
+/// never pays for a heap allocation, then falls back to a heap buffer that
+/// doubles in size until every split fits, so inputs with arbitrarily many
+/// comma-separated parts scan correctly instead of being silently truncated.
 #[cfg(target_arch = "x86_64")]
 pub fn split_respecting_quotes(input: &str) -> Vec<String> {
     let input_bytes = input.as_bytes();
-    let mut splits = [0usize; 32]; // Support up to 32 parts
 
-    let split_count = unsafe {
+    let mut stack_splits = [0usize; 32];
+    let stack_count = unsafe {
         split_quotes_scan(
             input_bytes.as_ptr(),
             input_bytes.len(),
-            splits.as_mut_ptr(),
-            32,
+            stack_splits.as_mut_ptr(),
+            stack_splits.len(),
         )
     };
 
+    let splits = if stack_count == SPLIT_OVERFLOW {
+        scan_with_growing_buffer(input_bytes)
+    } else {
+        stack_splits[..stack_count].to_vec()
+    };
+
+    build_parts(input, &splits)
+}
+
+/// Re-scans `input_bytes` with a heap-allocated buffer that doubles in size
+/// until every comma split position fits, for inputs that overflowed the
+/// stack buffer in [`split_respecting_quotes`].
+#[cfg(target_arch = "x86_64")]
+fn scan_with_growing_buffer(input_bytes: &[u8]) -> Vec<usize> {
+    let mut capacity = 64;
+    loop {
+        let mut buffer = vec![0usize; capacity];
+        let split_count = unsafe {
+            split_quotes_scan(
+                input_bytes.as_ptr(),
+                input_bytes.len(),
+                buffer.as_mut_ptr(),
+                capacity,
+            )
+        };
+        if split_count == SPLIT_OVERFLOW {
+            capacity *= 2;
+            continue;
+        }
+        buffer.truncate(split_count);
+        return buffer;
+    }
+}
+
+/// Turns comma split positions into trimmed, non-empty parts of `input`.
+#[cfg(target_arch = "x86_64")]
+fn build_parts(input: &str, splits: &[usize]) -> Vec<String> {
     let mut parts = Vec::new();
     let mut start = 0;
 
-    // Process each split position
-    for i in 0..split_count {
-        let comma_pos = splits[i];
+    for &comma_pos in splits {
         if comma_pos > start {
             let part = &input[start..comma_pos];
             let trimmed = part.trim();
@@ -30,8 +30,238 @@ pub fn parse_key_value_pair(pair: &str) -> Option<(String, String)> {
     Some((key, value))
 }
 
-/// Splits a string on commas while respecting quotes
+/// Splits a string on commas while respecting quotes. A thin wrapper over
+/// [`split_respecting_quotes_with`] for the common comma-separated case.
 pub fn split_respecting_quotes(input: &str) -> Vec<String> {
+    split_respecting_quotes_with(input, ',')
+}
+
+/// Splits a string on `delim` while respecting quotes, so the same
+/// quote-aware splitter can parse comma-separated filter arguments as well
+/// as colon- or space-delimited parameter lists. Matches shlex escaping
+/// semantics: inside a double-quoted region a backslash escapes the
+/// following character (`\"` doesn't toggle quote state, `\\` doesn't start
+/// an escape of its own), while inside single quotes a backslash is
+/// ordinary literal text. Escaped text is kept in the output verbatim -
+/// pass a part through [`unescape_quoted_value`] to resolve it.
+pub fn split_respecting_quotes_with(input: &str, delim: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut quote_char = '"';
+    let mut prev_was_backslash = false;
+
+    for ch in input.chars() {
+        match ch {
+            '"' | '\'' if !in_quotes && !prev_was_backslash => {
+                in_quotes = true;
+                quote_char = ch;
+                current.push(ch);
+            }
+            c if in_quotes && c == quote_char && !prev_was_backslash => {
+                in_quotes = false;
+                current.push(c);
+            }
+            c if c == delim && !in_quotes => {
+                parts.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => {
+                current.push(ch);
+            }
+        }
+        prev_was_backslash = in_quotes && quote_char == '"' && ch == '\\' && !prev_was_backslash;
+    }
+
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    parts
+}
+
+/// Why [`try_split_respecting_quotes_with`] rejected an input outright,
+/// instead of silently folding the rest of the string into one part the way
+/// [`split_respecting_quotes_with`] leniently does.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SplitError {
+    /// The scan reached the end of the input while still inside a quoted
+    /// region; `byte_offset` is where that region's opening quote is.
+    UnterminatedQuote { byte_offset: usize },
+    /// The scan reached the end of the input right after a backslash inside
+    /// a double-quoted region, at `byte_offset`, with no character left to
+    /// escape.
+    UnterminatedEscape { byte_offset: usize },
+}
+
+/// Splits a string on commas while respecting quotes, rejecting malformed
+/// input. A thin wrapper over [`try_split_respecting_quotes_with`] for the
+/// common comma-separated case, mirroring [`split_respecting_quotes`].
+pub fn try_split_respecting_quotes(input: &str) -> Result<Vec<String>, SplitError> {
+    try_split_respecting_quotes_with(input, ',')
+}
+
+/// Like [`split_respecting_quotes_with`], but reports an unclosed quote or a
+/// trailing escape with nothing to escape as a [`SplitError`] instead of
+/// silently swallowing the rest of the input into one part.
+pub fn try_split_respecting_quotes_with(
+    input: &str,
+    delim: char,
+) -> Result<Vec<String>, SplitError> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut quote_char = '"';
+    let mut quote_start = 0;
+    let mut prev_was_backslash = false;
+    let mut backslash_offset = 0;
+
+    for (offset, ch) in input.char_indices() {
+        match ch {
+            '"' | '\'' if !in_quotes && !prev_was_backslash => {
+                in_quotes = true;
+                quote_char = ch;
+                quote_start = offset;
+                current.push(ch);
+            }
+            c if in_quotes && c == quote_char && !prev_was_backslash => {
+                in_quotes = false;
+                current.push(c);
+            }
+            c if c == delim && !in_quotes => {
+                parts.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => {
+                current.push(ch);
+            }
+        }
+
+        let escape_starts_here =
+            in_quotes && quote_char == '"' && ch == '\\' && !prev_was_backslash;
+        if escape_starts_here {
+            backslash_offset = offset;
+        }
+        prev_was_backslash = escape_starts_here;
+    }
+
+    if prev_was_backslash {
+        return Err(SplitError::UnterminatedEscape {
+            byte_offset: backslash_offset,
+        });
+    }
+    if in_quotes {
+        return Err(SplitError::UnterminatedQuote {
+            byte_offset: quote_start,
+        });
+    }
+
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    Ok(parts)
+}
+
+/// Unescapes a part produced by [`split_respecting_quotes_with`] or
+/// [`split_whitespace_respecting_quotes`], shlex style: inside a
+/// double-quoted region a backslash is dropped and the following character
+/// kept literally (so `\"` becomes `"` and `\\` becomes `\`), while inside
+/// single quotes - and outside any quotes - a backslash passes through
+/// unchanged. The surrounding quote characters themselves are left in place;
+/// combine with [`trim_quotes`] to strip those too.
+pub fn unescape_quoted_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut in_quotes = false;
+    let mut quote_char = '"';
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' | '\'' if !in_quotes => {
+                in_quotes = true;
+                quote_char = ch;
+                result.push(ch);
+            }
+            c if in_quotes && c == quote_char => {
+                in_quotes = false;
+                result.push(c);
+            }
+            '\\' if in_quotes && quote_char == '"' => {
+                if let Some(next) = chars.next() {
+                    result.push(next);
+                } else {
+                    result.push('\\');
+                }
+            }
+            _ => result.push(ch),
+        }
+    }
+
+    result
+}
+
+/// Splits a string on commas while respecting quotes, like
+/// [`split_respecting_quotes`], but returns the *logical* value of each part:
+/// surrounding quotes stripped and interior escapes resolved, e.g.
+/// `'hello, world', foo` -> `["hello, world", "foo"]`. This is what callers
+/// parsing template-tag arguments usually want; reach for
+/// [`split_respecting_quotes`] instead when the raw, still-quoted substring
+/// is needed.
+pub fn split_respecting_quotes_unescaped(input: &str) -> Vec<String> {
+    split_respecting_quotes(input)
+        .into_iter()
+        .map(|part| trim_quotes(&unescape_quoted_value(&part)).to_string())
+        .collect()
+}
+
+/// Splits `input` on runs of whitespace while treating a quoted region as
+/// atomic, so `'podman > 1.4' installed` stays two parts instead of four.
+/// Consecutive spaces/tabs collapse into a single separator, and empty parts
+/// are dropped after trimming - the same conventions
+/// [`split_respecting_quotes_with`] follows for a fixed delimiter.
+pub fn split_whitespace_respecting_quotes(input: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut quote_char = '"';
+    let mut prev_was_backslash = false;
+
+    for ch in input.chars() {
+        match ch {
+            '"' | '\'' if !in_quotes && !prev_was_backslash => {
+                in_quotes = true;
+                quote_char = ch;
+                current.push(ch);
+            }
+            c if in_quotes && c == quote_char && !prev_was_backslash => {
+                in_quotes = false;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.trim().is_empty() {
+                    parts.push(current.trim().to_string());
+                }
+                current.clear();
+            }
+            _ => {
+                current.push(ch);
+            }
+        }
+        prev_was_backslash = in_quotes && quote_char == '"' && ch == '\\' && !prev_was_backslash;
+    }
+
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    parts
+}
+
+/// Splits a filter pipeline expression on `|` while respecting quotes, e.g.
+/// `data.users | where: "active", true | first` ->
+/// `["data.users", "where: \"active\", true", "first"]`.
+pub fn split_pipeline(input: &str) -> Vec<String> {
     let mut parts = Vec::new();
     let mut current = String::new();
     let mut in_quotes = false;
@@ -48,7 +278,7 @@ pub fn split_respecting_quotes(input: &str) -> Vec<String> {
                 in_quotes = false;
                 current.push(ch);
             }
-            ',' if !in_quotes => {
+            '|' if !in_quotes => {
                 parts.push(current.trim().to_string());
                 current.clear();
             }
@@ -65,6 +295,71 @@ pub fn split_respecting_quotes(input: &str) -> Vec<String> {
     parts
 }
 
+/// A [`split_csv_fields`] scan position: either between fields, inside a
+/// quoted field, or just past a `"` inside a quoted field - where a second
+/// `"` means an escaped literal quote, and anything else closes the field.
+enum CsvParseState {
+    Outside,
+    InQuotedField,
+    SawQuoteInField,
+}
+
+/// Splits `input` on commas using RFC 4180 CSV quoting, e.g. for values
+/// copied from a spreadsheet into front matter: a field may be wrapped in
+/// double quotes, and a literal double quote inside one is written as two
+/// consecutive double quotes (`""`). Unlike [`split_respecting_quotes`], the
+/// returned fields have their surrounding quotes already stripped and `""`
+/// already collapsed to `"` - no separate [`trim_quotes`]/
+/// [`unescape_quoted_value`] pass is needed. A field is only treated as
+/// quoted when the `"` is the very first character of the field; anything
+/// else (an unterminated quote, stray text after a closing quote) is kept
+/// literally rather than rejected, matching this module's other lenient
+/// splitters.
+pub fn split_csv_fields(input: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut state = CsvParseState::Outside;
+
+    for ch in input.chars() {
+        match state {
+            CsvParseState::Outside => match ch {
+                '"' if current.is_empty() => state = CsvParseState::InQuotedField,
+                ',' => {
+                    fields.push(current.trim().to_string());
+                    current.clear();
+                }
+                _ => current.push(ch),
+            },
+            CsvParseState::InQuotedField => match ch {
+                '"' => state = CsvParseState::SawQuoteInField,
+                _ => current.push(ch),
+            },
+            CsvParseState::SawQuoteInField => match ch {
+                '"' => {
+                    current.push('"');
+                    state = CsvParseState::InQuotedField;
+                }
+                ',' => {
+                    fields.push(current.clone());
+                    current.clear();
+                    state = CsvParseState::Outside;
+                }
+                _ => {
+                    current.push(ch);
+                    state = CsvParseState::Outside;
+                }
+            },
+        }
+    }
+
+    fields.push(match state {
+        CsvParseState::Outside => current.trim().to_string(),
+        CsvParseState::InQuotedField | CsvParseState::SawQuoteInField => current,
+    });
+
+    fields
+}
+
 use std::collections::HashMap;
 
 /// Parses a space-separated list of key:value pairs with optional quoted values into a HashMap.
@@ -218,6 +513,99 @@ mod tests {
         assert_eq!(result, vec!["simple", "values"]);
     }
 
+    #[test]
+    fn test_split_respecting_quotes_handles_escaped_quotes() {
+        let result = split_respecting_quotes(r#""she said \"hi\"", next"#);
+        assert_eq!(result, vec![r#""she said \"hi\"""#, "next"]);
+    }
+
+    #[test]
+    fn test_split_respecting_quotes_with_custom_delimiter() {
+        let result = split_respecting_quotes_with(r#"name:"Alice":age:"30""#, ':');
+        assert_eq!(result, vec!["name", "\"Alice\"", "age", "\"30\""]);
+    }
+
+    #[test]
+    fn test_split_respecting_quotes_single_quotes_do_not_support_backslash_escaping() {
+        // Inside single quotes a backslash has no special meaning (shlex
+        // semantics), so the quote right after it still closes normally
+        // instead of being swallowed as an "escaped" quote.
+        let result = split_respecting_quotes_with(r#"'\', next"#, ',');
+        assert_eq!(result, vec![r#"'\'"#, "next"]);
+    }
+
+    #[test]
+    fn test_try_split_respecting_quotes_matches_lenient_output_for_well_formed_input() {
+        let result = try_split_respecting_quotes(r#"active, true, "quoted, value""#).unwrap();
+        assert_eq!(result, vec!["active", "true", r#""quoted, value""#]);
+    }
+
+    #[test]
+    fn test_try_split_respecting_quotes_reports_unterminated_quote() {
+        let result = try_split_respecting_quotes(r#""foo, bar"#);
+        assert_eq!(
+            result,
+            Err(SplitError::UnterminatedQuote { byte_offset: 0 })
+        );
+    }
+
+    #[test]
+    fn test_try_split_respecting_quotes_reports_unterminated_escape() {
+        let result = try_split_respecting_quotes(r#""foo\"#);
+        assert_eq!(
+            result,
+            Err(SplitError::UnterminatedEscape { byte_offset: 4 })
+        );
+    }
+
+    #[test]
+    fn test_unescape_quoted_value_resolves_double_quote_escapes() {
+        assert_eq!(
+            unescape_quoted_value(r#""she said \"hi\"""#),
+            r#""she said "hi"""#
+        );
+    }
+
+    #[test]
+    fn test_unescape_quoted_value_resolves_escaped_backslash() {
+        assert_eq!(unescape_quoted_value(r#""a\\b""#), r#""a\b""#);
+    }
+
+    #[test]
+    fn test_unescape_quoted_value_leaves_single_quoted_backslashes_literal() {
+        assert_eq!(unescape_quoted_value(r#"'a\b'"#), r#"'a\b'"#);
+    }
+
+    #[test]
+    fn test_split_respecting_quotes_unescaped_strips_quotes_and_resolves_escapes() {
+        let result = split_respecting_quotes_unescaped(r#"'hello, world', foo"#);
+        assert_eq!(result, vec!["hello, world", "foo"]);
+    }
+
+    #[test]
+    fn test_split_respecting_quotes_unescaped_resolves_nested_double_quote_escapes() {
+        let result = split_respecting_quotes_unescaped(r#""she said \"hi\"", next"#);
+        assert_eq!(result, vec![r#"she said "hi""#, "next"]);
+    }
+
+    #[test]
+    fn test_split_whitespace_respecting_quotes_keeps_quoted_region_atomic() {
+        let result = split_whitespace_respecting_quotes("'podman > 1.4' installed");
+        assert_eq!(result, vec!["'podman > 1.4'", "installed"]);
+    }
+
+    #[test]
+    fn test_split_whitespace_respecting_quotes_collapses_whitespace_runs() {
+        let result = split_whitespace_respecting_quotes("a  \t b\tc");
+        assert_eq!(result, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_split_whitespace_respecting_quotes_trims_and_drops_empty_parts() {
+        let result = split_whitespace_respecting_quotes("  leading and trailing  ");
+        assert_eq!(result, vec!["leading", "and", "trailing"]);
+    }
+
     #[test]
     fn test_parse_space_separated_key_value_params() {
         let map = parse_space_separated_key_value_params(
@@ -237,6 +625,39 @@ mod tests {
         assert_eq!(p[3], "{ item }");
     }
 
+    #[test]
+    fn test_split_pipeline() {
+        let result = split_pipeline(r#"data.users | where: "active", true | first"#);
+        assert_eq!(
+            result,
+            vec!["data.users", r#"where: "active", true"#, "first"]
+        );
+
+        let result = split_pipeline("items");
+        assert_eq!(result, vec!["items"]);
+
+        let result = split_pipeline(r#"items | where: "a | b", true"#);
+        assert_eq!(result, vec!["items", r#"where: "a | b", true"#]);
+    }
+
+    #[test]
+    fn test_split_csv_fields_unwraps_and_collapses_doubled_quotes() {
+        let result = split_csv_fields(r#"foo,"she said ""hi""",bar"#);
+        assert_eq!(result, vec!["foo", r#"she said "hi""#, "bar"]);
+    }
+
+    #[test]
+    fn test_split_csv_fields_handles_unquoted_fields() {
+        let result = split_csv_fields("simple,fields");
+        assert_eq!(result, vec!["simple", "fields"]);
+    }
+
+    #[test]
+    fn test_split_csv_fields_preserves_commas_inside_quoted_field() {
+        let result = split_csv_fields(r#""a, b",c"#);
+        assert_eq!(result, vec!["a, b", "c"]);
+    }
+
     #[test]
     fn test_parse_filter_invocation() {
         let parsed = parse_filter_invocation("where: 'a', 'b'").unwrap();
@@ -1,3 +1,4 @@
+use crate::template_processors::liquid::utils::find_byte::find_byte_index;
 use crate::template_processors::liquid::utils::find_equal::find_equal_index;
 
 use crate::error::{Error, Result};
@@ -11,6 +12,72 @@ pub struct TagBlock {
     pub end: usize,
     pub tag_content: String,
     pub inner_content: String,
+    /// `true` if the opening delimiter was written `{%-`, requesting that
+    /// the text before `start` have its trailing whitespace trimmed.
+    pub trim_left: bool,
+    /// `true` if the closing delimiter was written `-%}`, requesting that
+    /// the text after `end` have its leading whitespace trimmed.
+    pub trim_right: bool,
+}
+
+/// Finds the next occurrence of a two-byte ASCII delimiter (`{%`, `%}`,
+/// `{{`, or `}}`) in `bytes`, the same way a `memchr2` search would: jump to
+/// the next candidate first byte, then confirm the second byte follows it,
+/// rather than decoding the input one `char` at a time. Delimiters are
+/// always ASCII, so every match position is a valid `char` boundary to
+/// slice the original `&str` at.
+pub fn find_delimiter(bytes: &[u8], delim: [u8; 2]) -> Option<usize> {
+    let mut from = 0;
+    while let Some(rel) = find_byte_index(&bytes[from..], delim[0]) {
+        let pos = from + rel;
+        if bytes.get(pos + 1) == Some(&delim[1]) {
+            return Some(pos);
+        }
+        from = pos + 1;
+    }
+    None
+}
+
+/// Finds `start_tag` from `from`, also matching the whitespace-control
+/// spelling `{%-` in place of `{%`. Returns the match position, its byte
+/// length, and whether the dashed variant was the one found.
+fn find_start_tag(template: &str, from: usize, start_tag: &str) -> Option<(usize, usize, bool)> {
+    let dashed = format!("{{%-{}", &start_tag[2..]);
+    match (
+        template[from..].find(start_tag),
+        template[from..].find(&dashed),
+    ) {
+        (Some(a), Some(b)) if b < a => Some((from + b, dashed.len(), true)),
+        (Some(a), _) => Some((from + a, start_tag.len(), false)),
+        (None, Some(b)) => Some((from + b, dashed.len(), true)),
+        (None, None) => None,
+    }
+}
+
+/// Finds `end_tag` from `from`, also matching its whitespace-control
+/// spellings (`{%-` for the opening delimiter and/or `-%}` for the closing
+/// one). Returns the match position, its byte length, and whether the
+/// trailing `-%}` variant was the one found - that's the half that governs
+/// trimming the text after the block.
+fn find_end_tag(template: &str, from: usize, end_tag: &str) -> Option<(usize, usize, bool)> {
+    // `end_tag` is always written "{% keyword %}" - pull out "keyword" so
+    // the four dash combinations can be rebuilt around it.
+    let inner = &end_tag[3..end_tag.len() - 3];
+    let variants = [
+        (format!("{{% {inner} %}}"), false),
+        (format!("{{%- {inner} %}}"), false),
+        (format!("{{% {inner} -%}}"), true),
+        (format!("{{%- {inner} -%}}"), true),
+    ];
+
+    variants
+        .iter()
+        .filter_map(|(variant, trim_right)| {
+            template[from..]
+                .find(variant.as_str())
+                .map(|rel| (from + rel, variant.len(), *trim_right))
+        })
+        .min_by_key(|(pos, ..)| *pos)
 }
 
 /// Finds a complete tag block (e.g., {% if %}...{% endif %}) starting from a position
@@ -20,35 +87,38 @@ pub fn find_tag_block(
     end_tag: &str,
     start_pos: usize,
 ) -> Option<TagBlock> {
-    let tag_start = template[start_pos..]
-        .find(start_tag)
-        .map(|pos| start_pos + pos)?;
+    let (tag_start, start_len, trim_left) = find_start_tag(template, start_pos, start_tag)?;
 
     // Find where the opening tag ends
-    let opening_tag_end = template[tag_start..]
-        .find("%}")
+    let opening_tag_end = find_delimiter(template[tag_start..].as_bytes(), *b"%}")
         .map(|pos| tag_start + pos + 2)?;
 
     // Find the closing tag
-    let tag_end = template[opening_tag_end..]
-        .find(end_tag)
-        .map(|pos| opening_tag_end + pos + end_tag.len())?;
-
-    // Extract tag content (the condition/parameters in the opening tag)
-    let tag_content_start = tag_start + start_tag.len();
+    let (end_tag_start, end_len, trim_right) = find_end_tag(template, opening_tag_end, end_tag)?;
+    let tag_end = end_tag_start + end_len;
+
+    // Extract tag content (the condition/parameters in the opening tag). A
+    // stray trailing "-" left by a `-%}` on the opening tag itself isn't
+    // tracked as a TagBlock flag, but is still stripped here so it doesn't
+    // leak into the condition/parameter text.
+    let tag_content_start = tag_start + start_len;
     let tag_content_end = opening_tag_end - 2; // Before "%}"
     let tag_content = template[tag_content_start..tag_content_end]
         .trim()
+        .trim_end_matches('-')
+        .trim_end()
         .to_string();
 
     // Extract inner content
-    let inner_content = template[opening_tag_end..tag_end - end_tag.len()].to_string();
+    let inner_content = template[opening_tag_end..end_tag_start].to_string();
 
     Some(TagBlock {
         start: tag_start,
         end: tag_end,
         tag_content,
         inner_content,
+        trim_left,
+        trim_right,
     })
 }
 
@@ -88,25 +158,25 @@ extern "C" {
     fn is_ascii_whitespace_scan(byte: u8) -> u8;
 }
 
-/// Reads content until finding a closing liquid tag pattern
-pub fn read_until_closing_tag(chars: &mut Peekable<Chars>) -> Result<String> {
-    let mut content = String::new();
-    let mut found_closing = false;
+/// Reads `template[pos..]` up to the next closing `%}` delimiter (or its
+/// whitespace-control spelling `-%}`), returning its content, whether the
+/// closing delimiter was dashed, and the byte position just past it.
+pub fn read_until_closing_tag(template: &str, pos: usize) -> Result<(String, bool, usize)> {
+    let close = pos
+        + find_delimiter(template[pos..].as_bytes(), *b"%}")
+            .ok_or_else(|| Error::Liquid("Unclosed liquid tag".to_string()))?;
 
-    while let Some(c) = chars.next() {
-        if c == '%' && chars.peek() == Some(&'}') {
-            chars.next(); // Skip '}'
-            found_closing = true;
-            break;
-        }
-        content.push(c);
-    }
+    let trim_right = close > pos && template.as_bytes()[close - 1] == b'-';
+    let content_end = if trim_right { close - 1 } else { close };
 
-    if !found_closing {
-        return Err(Error::Liquid("Unclosed liquid tag".to_string()));
-    }
+    Ok((template[pos..content_end].to_string(), trim_right, close + 2))
+}
 
-    Ok(content)
+/// Advances `pos` past any leading whitespace in `template`, used to apply
+/// the trim requested by a whitespace-control closing delimiter (`-%}`).
+pub fn skip_leading_whitespace_from(template: &str, pos: usize) -> usize {
+    let trimmed = template[pos..].trim_start();
+    pos + (template[pos..].len() - trimmed.len())
 }
 
 /// Parses an assignment expression (variable = value)
@@ -137,64 +207,68 @@ pub fn extract_tag_parameter(tag_content: &str, tag_type: &str) -> Option<String
 
 /// Extracts the inner content of a full liquid tag string for a given tag name.
 /// Example: given "{% include header.liquid %}", `tag_name` "include" -> returns Some("header.liquid").
+/// Also recognizes the whitespace-control spellings `{%-`/`-%}`.
 pub fn extract_tag_inner<'a>(full_tag: &'a str, tag_name: &str) -> Option<&'a str> {
     let trimmed = full_tag.trim();
-    let prefix = format!("{{% {tag_name}");
-    if !trimmed.starts_with(&prefix) || !trimmed.ends_with("%}") {
+
+    let after_open = trimmed
+        .strip_prefix("{%-")
+        .or_else(|| trimmed.strip_prefix("{%"))?
+        .strip_prefix(' ')?
+        .strip_prefix(tag_name)?;
+
+    let before_close = trimmed
+        .strip_suffix("-%}")
+        .or_else(|| trimmed.strip_suffix("%}"))?;
+
+    let inner_start = trimmed.len() - after_open.len();
+    let inner_end = before_close.len();
+    if inner_start > inner_end {
         return None;
     }
-    Some(trimmed[prefix.len()..trimmed.len() - 2].trim())
+    Some(trimmed[inner_start..inner_end].trim())
 }
 
-/// Reads a nested balanced block for arbitrary start/end keywords using a character iterator.
-/// Increments depth when encountering `{% <start_keyword> ... %}` and decrements on `{% <end_keyword> %}`.
-/// Returns the collected inner content (excluding the closing end tag) at depth 0.
+/// Reads a nested balanced block for arbitrary start/end keywords, jumping
+/// delimiter-to-delimiter (`{%`/`%}`) rather than walking `template` one
+/// `char` at a time. Increments depth when encountering
+/// `{% <start_keyword> ... %}` and decrements on `{% <end_keyword> %}`.
+/// Returns the collected inner content (excluding the closing end tag) and
+/// the byte position just past it.
 pub fn read_nested_block(
-    chars: &mut Peekable<Chars>,
+    template: &str,
+    pos: usize,
     start_keyword: &str,
     end_keyword: &str,
-) -> Result<String> {
+) -> Result<(String, usize)> {
+    let bytes = template.as_bytes();
     let mut content = String::new();
     let mut depth: i32 = 1;
+    let mut cursor = pos;
+
+    loop {
+        let unclosed = || Error::Liquid(format!("Unclosed block - missing {{% {end_keyword} %}}"));
+        let open = cursor + find_delimiter(&bytes[cursor..], *b"{%").ok_or_else(unclosed)?;
+        content.push_str(&template[cursor..open]);
 
-    while depth > 0 {
-        let Some(c) = chars.next() else {
-            return Err(Error::Liquid(format!(
-                "Unclosed block - missing {{% {end_keyword} %}}"
-            )));
-        };
-
-        if c == '{' && chars.peek() == Some(&'%') {
-            chars.next(); // consume '%'
-            let mut inner_tag = String::new();
-
-            // Read tag content until %}
-            while let Some(tc) = chars.next() {
-                if tc == '%' && chars.peek() == Some(&'}') {
-                    chars.next(); // consume '}'
-                    break;
-                }
-                inner_tag.push(tc);
-            }
-
-            let trimmed = inner_tag.trim();
-            if trimmed.starts_with(start_keyword) {
-                depth += 1;
-            } else if trimmed == end_keyword {
-                depth -= 1;
-            }
-
-            if depth > 0 {
-                content.push_str("{% ");
-                content.push_str(trimmed);
-                content.push_str(" %}");
-            }
-        } else if depth > 0 {
-            content.push(c);
+        let close = open + find_delimiter(&bytes[open..], *b"%}").ok_or_else(unclosed)?;
+        let trimmed = template[open + 2..close].trim();
+
+        if trimmed.starts_with(start_keyword) {
+            depth += 1;
+        } else if trimmed == end_keyword {
+            depth -= 1;
+        }
+
+        cursor = close + 2;
+        if depth == 0 {
+            return Ok((content, cursor));
         }
-    }
 
-    Ok(content)
+        content.push_str("{% ");
+        content.push_str(trimmed);
+        content.push_str(" %}");
+    }
 }
 
 #[cfg(test)]
@@ -210,18 +284,34 @@ mod tests {
 
     #[test]
     fn test_read_until_closing_tag() {
-        let mut chars = " if condition %}".chars().peekable();
-        let content = read_until_closing_tag(&mut chars).unwrap();
+        let template = " if condition %} after";
+        let (content, trim_right, pos) = read_until_closing_tag(template, 0).unwrap();
+        assert_eq!(content, " if condition ");
+        assert!(!trim_right);
+        assert_eq!(&template[pos..], " after");
+    }
+
+    #[test]
+    fn test_read_until_closing_tag_trims_right() {
+        let template = " if condition -%} after";
+        let (content, trim_right, pos) = read_until_closing_tag(template, 0).unwrap();
         assert_eq!(content, " if condition ");
+        assert!(trim_right);
+        assert_eq!(&template[pos..], " after");
     }
 
     #[test]
     fn test_read_until_closing_tag_unclosed() {
-        let mut chars = " if condition".chars().peekable();
-        let result = read_until_closing_tag(&mut chars);
+        let result = read_until_closing_tag(" if condition", 0);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_skip_leading_whitespace_from() {
+        let template = "   \n  rest";
+        assert_eq!(skip_leading_whitespace_from(template, 0), 7);
+    }
+
     #[test]
     fn test_find_tag_block() {
         let template = "before {% if condition %}content{% endif %} after";
@@ -231,6 +321,18 @@ mod tests {
         assert_eq!(result.end, 43);
         assert_eq!(result.tag_content, "condition");
         assert_eq!(result.inner_content, "content");
+        assert!(!result.trim_left);
+        assert!(!result.trim_right);
+    }
+
+    #[test]
+    fn test_find_tag_block_with_trim_markers() {
+        let template = "before   {%- if condition -%}   content   {%- endif -%}   after";
+        let result = find_tag_block(template, "{% if", "{% endif %}", 0).unwrap();
+
+        assert_eq!(result.tag_content, "condition");
+        assert!(result.trim_left);
+        assert!(result.trim_right);
     }
 
     #[test]
@@ -268,15 +370,36 @@ mod tests {
         assert_eq!(extract_tag_inner("not a tag", "include"), None);
     }
 
+    #[test]
+    fn test_extract_tag_inner_with_trim_markers() {
+        assert_eq!(
+            extract_tag_inner("{%- include header -%}", "include"),
+            Some("header")
+        );
+        assert_eq!(
+            extract_tag_inner("{%- include header %}", "include"),
+            Some("header")
+        );
+    }
+
     #[test]
     fn test_read_nested_block_for_endfor() {
-        let mut chars = " inner {% for x in y %} nested {% endfor %} tail {% endfor %} after"
-            .chars()
-            .peekable();
+        let template = " inner {% for x in y %} nested {% endfor %} tail {% endfor %} after";
         // simulate that we've already consumed the outer start tag, so depth starts at 1
-        let content = read_nested_block(&mut chars, "for ", "endfor").unwrap();
+        let (content, pos) = read_nested_block(template, 0, "for ", "endfor").unwrap();
         assert_eq!(content, " inner {% for x in y %} nested {% endfor %} tail ");
-        let remaining: String = chars.collect();
-        assert_eq!(remaining, " after");
+        assert_eq!(&template[pos..], " after");
+    }
+
+    #[test]
+    fn test_read_nested_block_unclosed() {
+        let template = " inner {% for x in y %} nested";
+        assert!(read_nested_block(template, 0, "for ", "endfor").is_err());
+    }
+
+    #[test]
+    fn test_find_delimiter_confirms_second_byte() {
+        let bytes = b"a { b {% c";
+        assert_eq!(find_delimiter(bytes, *b"{%"), Some(6));
     }
 }
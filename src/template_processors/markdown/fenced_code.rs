@@ -0,0 +1,146 @@
+//! Fenced code block detection and rendering.
+//!
+//! Recognizes ` ``` ` and `~~~` fences (CommonMark style: three or more of
+//! the same character opens a fence, and a closing fence must use the same
+//! character and be at least as long). The text inside a fence is emitted
+//! verbatim as `<pre><code>`, HTML-escaped but with its line breaks kept
+//! literal, unlike the `<br />`-stripping applied to regular paragraphs.
+
+use super::lang_string::LangString;
+
+/// An open fence, tracked until its matching close is found.
+pub struct OpenFence {
+    fence_char: char,
+    fence_len: usize,
+    pub lang_string: LangString,
+}
+
+/// If `line` opens a fenced code block, returns the fence to track until its
+/// close and the parsed info string from the rest of the line.
+pub fn try_parse_fence_open(line: &str) -> Option<OpenFence> {
+    let trimmed = line.trim_start();
+    let fence_char = trimmed.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let fence_len = trimmed.chars().take_while(|&c| c == fence_char).count();
+    if fence_len < 3 {
+        return None;
+    }
+
+    let info = trimmed[fence_len..].trim();
+    Some(OpenFence {
+        fence_char,
+        fence_len,
+        lang_string: LangString::parse(info),
+    })
+}
+
+/// Returns true if `line` closes `fence`: the same character repeated at
+/// least as many times, with nothing else on the line.
+pub fn is_fence_close(line: &str, fence: &OpenFence) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty()
+        && trimmed.len() >= fence.fence_len
+        && trimmed.chars().all(|c| c == fence.fence_char)
+}
+
+/// Escapes `&`, `<`, `>`, `"`, and `'` for safe inclusion in HTML text.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders a fenced code block's collected lines as
+/// `<pre><code class="language-...">...</code></pre>`, HTML-escaping the
+/// code but keeping its line breaks literal.
+pub fn render_code_block(fence: &OpenFence, lines: &[&str]) -> String {
+    let mut code_tag = String::from("<code");
+    if let Some(lang) = &fence.lang_string.lang {
+        code_tag.push_str(&format!(" class=\"language-{lang}\""));
+    }
+    for flag in fence.lang_string.data_attributes() {
+        code_tag.push_str(&format!(" data-{flag}"));
+    }
+    code_tag.push('>');
+
+    let escaped_lines: Vec<String> = lines.iter().map(|line| escape_html(line)).collect();
+
+    format!("<pre>{code_tag}{}</code></pre>", escaped_lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_parse_fence_open_backtick() {
+        let fence = try_parse_fence_open("```rust").unwrap();
+        assert_eq!(fence.fence_char, '`');
+        assert_eq!(fence.fence_len, 3);
+        assert_eq!(fence.lang_string.lang, Some("rust".to_string()));
+    }
+
+    #[test]
+    fn test_try_parse_fence_open_tilde() {
+        let fence = try_parse_fence_open("~~~").unwrap();
+        assert_eq!(fence.fence_char, '~');
+        assert_eq!(fence.lang_string.lang, None);
+    }
+
+    #[test]
+    fn test_try_parse_fence_open_rejects_short_run() {
+        assert!(try_parse_fence_open("``rust").is_none());
+    }
+
+    #[test]
+    fn test_try_parse_fence_open_rejects_non_fence_line() {
+        assert!(try_parse_fence_open("Some text").is_none());
+    }
+
+    #[test]
+    fn test_is_fence_close_matches_same_or_longer_run() {
+        let fence = try_parse_fence_open("```rust").unwrap();
+        assert!(is_fence_close("```", &fence));
+        assert!(is_fence_close("````", &fence));
+        assert!(!is_fence_close("``", &fence));
+    }
+
+    #[test]
+    fn test_is_fence_close_rejects_mismatched_fence_char() {
+        let fence = try_parse_fence_open("```rust").unwrap();
+        assert!(!is_fence_close("~~~", &fence));
+    }
+
+    #[test]
+    fn test_is_fence_close_rejects_trailing_content() {
+        let fence = try_parse_fence_open("```rust").unwrap();
+        assert!(!is_fence_close("``` oops", &fence));
+    }
+
+    #[test]
+    fn test_render_code_block_escapes_and_keeps_line_breaks() {
+        let fence = try_parse_fence_open("```rust,should_panic").unwrap();
+        let lines = vec!["fn main() {", "    panic!(\"<boom> & 'go'\");", "}"];
+        let result = render_code_block(&fence, &lines);
+        assert_eq!(
+            result,
+            "<pre><code class=\"language-rust\" data-should_panic>fn main() {\n    \
+             panic!(&quot;&lt;boom&gt; &amp; &#39;go&#39;&quot;);\n}</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_render_code_block_without_language() {
+        let fence = try_parse_fence_open("```").unwrap();
+        let result = render_code_block(&fence, &["plain text"]);
+        assert_eq!(result, "<pre><code>plain text</code></pre>");
+    }
+}
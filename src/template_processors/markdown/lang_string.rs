@@ -0,0 +1,100 @@
+//! Parses the info string on a fenced code block's opening fence, e.g.
+//! ```` ```rust,should_panic ````, the same way rustdoc's `LangString` does:
+//! the info string is split on commas and whitespace, the first bare token
+//! is taken as the language name, and a small set of known flags are
+//! recognized and stripped out of what would otherwise be the language.
+
+/// The parsed form of a fenced code block's info string.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LangString {
+    pub lang: Option<String>,
+    pub ignore: bool,
+    pub no_run: bool,
+    pub should_panic: bool,
+}
+
+impl LangString {
+    /// Parses an info string such as `rust,should_panic` or `ignore`.
+    pub fn parse(info: &str) -> Self {
+        let mut lang_string = LangString::default();
+
+        for (index, token) in info
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .enumerate()
+        {
+            match token {
+                "ignore" => lang_string.ignore = true,
+                "no_run" => lang_string.no_run = true,
+                "should_panic" => lang_string.should_panic = true,
+                token if index == 0 => lang_string.lang = Some(token.to_string()),
+                _ => {}
+            }
+        }
+
+        lang_string
+    }
+
+    /// Flags that should be stripped from the `class` attribute but kept as
+    /// `data-*` attributes.
+    pub fn data_attributes(&self) -> Vec<&'static str> {
+        let mut attrs = Vec::new();
+        if self.ignore {
+            attrs.push("ignore");
+        }
+        if self.no_run {
+            attrs.push("no_run");
+        }
+        if self.should_panic {
+            attrs.push("should_panic");
+        }
+        attrs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_language() {
+        let lang_string = LangString::parse("rust");
+        assert_eq!(lang_string.lang, Some("rust".to_string()));
+        assert!(lang_string.data_attributes().is_empty());
+    }
+
+    #[test]
+    fn test_parse_language_with_comma_separated_flags() {
+        let lang_string = LangString::parse("rust,should_panic,no_run");
+        assert_eq!(lang_string.lang, Some("rust".to_string()));
+        assert_eq!(lang_string.data_attributes(), vec!["no_run", "should_panic"]);
+    }
+
+    #[test]
+    fn test_parse_language_with_space_separated_flags() {
+        let lang_string = LangString::parse("rust ignore");
+        assert_eq!(lang_string.lang, Some("rust".to_string()));
+        assert_eq!(lang_string.data_attributes(), vec!["ignore"]);
+    }
+
+    #[test]
+    fn test_parse_flag_only_info_string_has_no_language() {
+        let lang_string = LangString::parse("ignore");
+        assert_eq!(lang_string.lang, None);
+        assert_eq!(lang_string.data_attributes(), vec!["ignore"]);
+    }
+
+    #[test]
+    fn test_parse_empty_info_string() {
+        let lang_string = LangString::parse("");
+        assert_eq!(lang_string.lang, None);
+        assert!(lang_string.data_attributes().is_empty());
+    }
+
+    #[test]
+    fn test_parse_unknown_trailing_token_is_ignored() {
+        let lang_string = LangString::parse("rust,editable");
+        assert_eq!(lang_string.lang, Some("rust".to_string()));
+        assert!(lang_string.data_attributes().is_empty());
+    }
+}
@@ -0,0 +1,498 @@
+//! Markdown to HTML conversion.
+//!
+//! Headings are converted ATX-style: `#` through `######` lines become
+//! `<hN id="slug">...</hN>`, with the id generated by [`slugify`] and
+//! de-duplicated by [`HeadingIdMap`] the same way rustdoc's `IdMap` keeps
+//! anchors unique across a page. Fenced code blocks (see
+//! [`fenced_code`]) are passed through as `<pre><code>`, HTML-escaped with
+//! their line breaks kept literal. Line breaks between other, non-list
+//! lines are stripped entirely; list lines (`-`, `*`, or `1.`-style) keep
+//! their own line break.
+
+mod fenced_code;
+mod lang_string;
+
+use crate::types::Variables;
+use fenced_code::{is_fence_close, render_code_block, try_parse_fence_open, OpenFence};
+use std::collections::HashMap;
+
+/// A single heading collected while converting markdown, used to build a
+/// table of contents once the whole page has been walked.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadingEntry {
+    pub level: usize,
+    pub text: String,
+    pub id: String,
+}
+
+/// Tracks how many times each slug has been used on a page, so repeated
+/// headings with the same text still get unique ids.
+///
+/// Modeled on rustdoc's `IdMap`: the first occurrence of a slug is used
+/// as-is, and every subsequent occurrence appends `-1`, `-2`, etc.
+#[derive(Debug, Default)]
+struct HeadingIdMap {
+    counts: HashMap<String, usize>,
+}
+
+impl HeadingIdMap {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn unique_id(&mut self, slug: String) -> String {
+        let count = self.counts.entry(slug.clone()).or_insert(0);
+        let id = if *count == 0 {
+            slug
+        } else {
+            format!("{slug}-{count}")
+        };
+        *count += 1;
+        id
+    }
+}
+
+/// Slugifies heading text for use as an HTML id: lowercases ASCII
+/// alphanumeric characters, collapses every run of other characters into a
+/// single `-`, and trims leading/trailing `-`.
+fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut pending_dash = false;
+
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            if pending_dash && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_dash = false;
+            slug.push(c.to_ascii_lowercase());
+        } else {
+            pending_dash = true;
+        }
+    }
+
+    slug
+}
+
+/// Returns `(heading_level, heading_text)` if `line` is an ATX heading
+/// (1-6 leading `#` characters followed by a space or end of line).
+fn parse_atx_heading(line: &str) -> Option<(usize, &str)> {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    if hashes == 0 || hashes > 6 {
+        return None;
+    }
+
+    let rest = &line[hashes..];
+    if rest.is_empty() {
+        return Some((hashes, ""));
+    }
+
+    let mut chars = rest.chars();
+    if chars.next() != Some(' ') {
+        return None;
+    }
+
+    Some((hashes, chars.as_str().trim()))
+}
+
+/// Returns true if `line` starts a markdown list item (`-`, `*`, or
+/// `1.`-style ordered list markers).
+fn is_list_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("- ") || trimmed.starts_with("* ") {
+        return true;
+    }
+
+    let digits: String = trimmed.chars().take_while(char::is_ascii_digit).collect();
+    !digits.is_empty() && trimmed[digits.len()..].starts_with(". ")
+}
+
+/// Converts markdown to HTML, discarding the collected heading data.
+///
+/// Use [`markdown_to_html_with_headings`] instead when the headings are
+/// needed, e.g. to build a table of contents.
+pub fn markdown_to_html(input: &str) -> String {
+    markdown_to_html_with_headings(input).0
+}
+
+/// Converts markdown to HTML, also returning every heading encountered (in
+/// document order) so a table of contents can be built from them.
+pub fn markdown_to_html_with_headings(input: &str) -> (String, Vec<HeadingEntry>) {
+    let mut output = String::with_capacity(input.len());
+    let mut headings = Vec::new();
+    let mut id_map = HeadingIdMap::new();
+
+    let mut open_fence: Option<OpenFence> = None;
+    let mut code_lines: Vec<&str> = Vec::new();
+
+    for line in input.lines() {
+        if let Some(fence) = &open_fence {
+            if is_fence_close(line, fence) {
+                output.push_str(&render_code_block(fence, &code_lines));
+                open_fence = None;
+                code_lines.clear();
+            } else {
+                code_lines.push(line);
+            }
+            continue;
+        }
+
+        if let Some(fence) = try_parse_fence_open(line) {
+            open_fence = Some(fence);
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some((level, text)) = parse_atx_heading(line) {
+            let id = id_map.unique_id(slugify(text));
+            output.push_str(&format!("<h{level} id=\"{id}\">{text}</h{level}>"));
+            headings.push(HeadingEntry {
+                level,
+                text: text.to_string(),
+                id,
+            });
+        } else if is_list_line(line) {
+            output.push_str(line);
+            output.push('\n');
+        } else {
+            output.push_str(line);
+        }
+    }
+
+    // An unterminated fence still renders the code collected so far.
+    if let Some(fence) = &open_fence {
+        output.push_str(&render_code_block(fence, &code_lines));
+    }
+
+    (output, headings)
+}
+
+/// Builds a nested `<ul>` table of contents from headings collected by
+/// [`markdown_to_html_with_headings`], using a level stack: a deeper
+/// heading opens a new nested `<ul>`, a shallower one closes back out to
+/// the matching level.
+pub fn build_table_of_contents(headings: &[HeadingEntry]) -> String {
+    if headings.is_empty() {
+        return String::new();
+    }
+
+    let mut toc = String::new();
+    let mut level_stack: Vec<usize> = Vec::new();
+
+    toc.push_str("<ul>");
+    level_stack.push(headings[0].level);
+
+    for (index, heading) in headings.iter().enumerate() {
+        if index > 0 {
+            let previous_level = *level_stack.last().unwrap();
+            if heading.level > previous_level {
+                toc.push_str("<ul>");
+                level_stack.push(heading.level);
+            } else {
+                while level_stack.len() > 1 && heading.level < *level_stack.last().unwrap() {
+                    toc.push_str("</ul>");
+                    level_stack.pop();
+                }
+            }
+        }
+
+        toc.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a></li>",
+            heading.id, heading.text
+        ));
+    }
+
+    for _ in 0..level_stack.len() {
+        toc.push_str("</ul>");
+    }
+
+    toc
+}
+
+/// One node of the nested table-of-contents tree built by
+/// [`build_table_of_contents_tree`]: a heading of level N becomes a child of
+/// the nearest preceding heading with a lower level.
+#[derive(Debug, Clone, PartialEq)]
+struct TocNode {
+    level: usize,
+    title: String,
+    id: String,
+    children: Vec<TocNode>,
+}
+
+/// Groups `headings` into a nested tree: a heading nests under the nearest
+/// preceding heading with a lower level, siblings at the same level stay
+/// flat. `min_level` is the level a heading must exceed to be consumed as a
+/// child at the current recursion depth; `pos` advances past every heading
+/// consumed, including nested ones, so the caller can resume after them.
+fn build_toc_children(headings: &[HeadingEntry], pos: &mut usize, min_level: usize) -> Vec<TocNode> {
+    let mut nodes = Vec::new();
+
+    while *pos < headings.len() && headings[*pos].level > min_level {
+        let heading = &headings[*pos];
+        let level = heading.level;
+        let title = heading.text.clone();
+        let id = heading.id.clone();
+        *pos += 1;
+
+        let children = build_toc_children(headings, pos, level);
+        nodes.push(TocNode {
+            level,
+            title,
+            id,
+            children,
+        });
+    }
+
+    nodes
+}
+
+fn flatten_toc_nodes(nodes: &[TocNode], prefix: &str, variables: &mut Variables) {
+    for (index, node) in nodes.iter().enumerate() {
+        let node_prefix = format!("{prefix}.{index}");
+        variables.insert(format!("{node_prefix}.level"), node.level.to_string());
+        variables.insert(format!("{node_prefix}.title"), node.title.clone());
+        variables.insert(format!("{node_prefix}.id"), node.id.clone());
+        if !node.children.is_empty() {
+            flatten_toc_nodes(&node.children, &format!("{node_prefix}.children"), variables);
+        }
+    }
+}
+
+/// Flattens `headings` into `toc.N.*` variables a template can walk with
+/// `{% for item in toc %}`, mirroring how
+/// [`crate::pagination::add_posts_collection_to_variables`] flattens a
+/// collection. A heading nested under a shallower one shows up as
+/// `toc.N.children.M.*` instead of a top-level `toc` entry, so
+/// `{% for child in toc.0.children %}` walks its subsections.
+pub fn flatten_table_of_contents(headings: &[HeadingEntry], variables: &mut Variables) {
+    let mut pos = 0;
+    let tree = build_toc_children(headings, &mut pos, 0);
+    flatten_toc_nodes(&tree, "toc", variables);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_lowercases_and_collapses_punctuation() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+    }
+
+    #[test]
+    fn test_slugify_trims_leading_and_trailing_dashes() {
+        assert_eq!(slugify("  Getting Started  "), "getting-started");
+    }
+
+    #[test]
+    fn test_heading_id_map_deduplicates_collisions() {
+        let mut id_map = HeadingIdMap::new();
+        assert_eq!(id_map.unique_id("intro".to_string()), "intro");
+        assert_eq!(id_map.unique_id("intro".to_string()), "intro-1");
+        assert_eq!(id_map.unique_id("intro".to_string()), "intro-2");
+    }
+
+    #[test]
+    fn test_markdown_to_html_converts_heading_with_id() {
+        let result = markdown_to_html("# Test Heading");
+        assert_eq!(result, "<h1 id=\"test-heading\">Test Heading</h1>");
+    }
+
+    #[test]
+    fn test_markdown_to_html_strips_breaks_between_non_list_lines() {
+        let result = markdown_to_html("# Test Heading\n\nThis is a paragraph.");
+        assert_eq!(
+            result,
+            "<h1 id=\"test-heading\">Test Heading</h1>This is a paragraph."
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_html_preserves_list_line_breaks() {
+        let result = markdown_to_html("- one\n- two\n- three");
+        assert_eq!(result, "- one\n- two\n- three\n");
+    }
+
+    #[test]
+    fn test_markdown_to_html_ignores_hash_without_following_space() {
+        let result = markdown_to_html("#no-space");
+        assert_eq!(result, "#no-space");
+    }
+
+    #[test]
+    fn test_markdown_to_html_with_headings_collects_heading_entries() {
+        let (_, headings) = markdown_to_html_with_headings("# One\n\n## Two\n\n## Two");
+        assert_eq!(
+            headings,
+            vec![
+                HeadingEntry {
+                    level: 1,
+                    text: "One".to_string(),
+                    id: "one".to_string()
+                },
+                HeadingEntry {
+                    level: 2,
+                    text: "Two".to_string(),
+                    id: "two".to_string()
+                },
+                HeadingEntry {
+                    level: 2,
+                    text: "Two".to_string(),
+                    id: "two-1".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_table_of_contents_nests_deeper_headings() {
+        let headings = vec![
+            HeadingEntry {
+                level: 1,
+                text: "One".to_string(),
+                id: "one".to_string(),
+            },
+            HeadingEntry {
+                level: 2,
+                text: "Two".to_string(),
+                id: "two".to_string(),
+            },
+            HeadingEntry {
+                level: 1,
+                text: "Three".to_string(),
+                id: "three".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            build_table_of_contents(&headings),
+            "<ul><li><a href=\"#one\">One</a></li><ul><li><a href=\"#two\">Two</a></li></ul><li><a href=\"#three\">Three</a></li></ul>"
+        );
+    }
+
+    #[test]
+    fn test_build_table_of_contents_empty_for_no_headings() {
+        assert_eq!(build_table_of_contents(&[]), "");
+    }
+
+    #[test]
+    fn test_flatten_table_of_contents_top_level_headings() {
+        let headings = vec![
+            HeadingEntry {
+                level: 1,
+                text: "One".to_string(),
+                id: "one".to_string(),
+            },
+            HeadingEntry {
+                level: 1,
+                text: "Two".to_string(),
+                id: "two".to_string(),
+            },
+        ];
+
+        let mut variables = Variables::new();
+        flatten_table_of_contents(&headings, &mut variables);
+
+        assert_eq!(variables.get("toc.0.level"), Some(&"1".to_string()));
+        assert_eq!(variables.get("toc.0.title"), Some(&"One".to_string()));
+        assert_eq!(variables.get("toc.0.id"), Some(&"one".to_string()));
+        assert_eq!(variables.get("toc.1.title"), Some(&"Two".to_string()));
+        assert_eq!(variables.get("toc.0.children.0.title"), None);
+    }
+
+    #[test]
+    fn test_flatten_table_of_contents_nests_deeper_headings_as_children() {
+        let headings = vec![
+            HeadingEntry {
+                level: 1,
+                text: "One".to_string(),
+                id: "one".to_string(),
+            },
+            HeadingEntry {
+                level: 2,
+                text: "One A".to_string(),
+                id: "one-a".to_string(),
+            },
+            HeadingEntry {
+                level: 2,
+                text: "One B".to_string(),
+                id: "one-b".to_string(),
+            },
+            HeadingEntry {
+                level: 1,
+                text: "Two".to_string(),
+                id: "two".to_string(),
+            },
+        ];
+
+        let mut variables = Variables::new();
+        flatten_table_of_contents(&headings, &mut variables);
+
+        assert_eq!(variables.get("toc.0.title"), Some(&"One".to_string()));
+        assert_eq!(
+            variables.get("toc.0.children.0.title"),
+            Some(&"One A".to_string())
+        );
+        assert_eq!(
+            variables.get("toc.0.children.1.title"),
+            Some(&"One B".to_string())
+        );
+        assert_eq!(variables.get("toc.1.title"), Some(&"Two".to_string()));
+        assert_eq!(variables.get("toc.1.children.0.title"), None);
+    }
+
+    #[test]
+    fn test_flatten_table_of_contents_empty_for_no_headings() {
+        let mut variables = Variables::new();
+        flatten_table_of_contents(&[], &mut variables);
+        assert!(variables.is_empty());
+    }
+
+    #[test]
+    fn test_markdown_to_html_renders_fenced_code_block() {
+        let result = markdown_to_html("```rust\nfn main() {}\n```");
+        assert_eq!(
+            result,
+            "<pre><code class=\"language-rust\">fn main() {}</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_html_fenced_code_preserves_line_breaks_and_escapes() {
+        let result = markdown_to_html("```html\n<p>hi & bye</p>\n\nstill code\n```");
+        assert_eq!(
+            result,
+            "<pre><code class=\"language-html\">&lt;p&gt;hi &amp; bye&lt;/p&gt;\n\n\
+             still code</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_html_fenced_code_with_flags_becomes_data_attributes() {
+        let result = markdown_to_html("```rust,should_panic\npanic!();\n```");
+        assert_eq!(
+            result,
+            "<pre><code class=\"language-rust\" data-should_panic>panic!();</code></pre>"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_html_tilde_fence_is_recognized() {
+        let result = markdown_to_html("~~~\nraw text\n~~~");
+        assert_eq!(result, "<pre><code>raw text</code></pre>");
+    }
+
+    #[test]
+    fn test_markdown_to_html_headings_and_code_blocks_together() {
+        let result = markdown_to_html("# Title\n\n```rust\nlet x = 1;\n```\n\nDone.");
+        assert_eq!(
+            result,
+            "<h1 id=\"title\">Title</h1>\
+             <pre><code class=\"language-rust\">let x = 1;</code></pre>Done."
+        );
+    }
+}
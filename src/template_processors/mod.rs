@@ -4,5 +4,7 @@
 pub mod liquid;
 pub mod markdown;
 mod processor;
+mod shortcodes;
 
-pub use processor::DefaultTemplateProcessor;
+pub use processor::process_template_tags;
+pub use shortcodes::process_shortcodes;
@@ -1,10 +1,13 @@
+use crate::converters::org::org_to_html;
 use crate::error::Result;
 use crate::template_processors::liquid::{
     process_liquid_assign_tags, process_liquid_conditional_tags, process_liquid_for_loops,
     process_liquid_tags_with_assigns, process_liquid_unless_tags, remove_liquid_variables,
     replace_template_variables,
 };
-use crate::template_processors::markdown::markdown_to_html;
+use crate::template_processors::markdown::{
+    build_table_of_contents, flatten_table_of_contents, markdown_to_html_with_headings,
+};
 use crate::types::{ContentItem, TemplateIncludes};
 use std::collections::HashMap;
 
@@ -14,6 +17,7 @@ use std::collections::HashMap;
 /// - Liquid conditionals (always)
 /// - Liquid includes (when includes are provided)
 /// - Markdown to HTML conversion (when content_item with markdown file_type is provided)
+/// - Org-mode to HTML conversion (when content_item has file_type "org")
 /// - Liquid variables (always)
 ///
 /// # Arguments
@@ -56,11 +60,34 @@ pub fn process_template_tags(
         process_liquid_conditional_tags(&processed_unless, &combined_variables)?
     };
 
-    // Step 2: Convert markdown to HTML if content_item indicates markdown
+    // Step 2: Convert markdown or Org content to HTML, collecting headings
+    // along the way (markdown only) so a table of contents can be injected
+    // when the content item or content requests one. The same request also
+    // flattens the heading tree into `toc.N.*` variables for direct access
+    // in step 3.
     if let Some(item) = content_item {
-        let is_markdown = item.get("file_type").is_none_or(|ft| ft == "md");
-        if is_markdown {
-            result = markdown_to_html(&result);
+        match item.get("file_type").map(String::as_str) {
+            Some("org") => {
+                result = org_to_html(&result);
+            }
+            None | Some("md") => {
+                let (html, headings) = markdown_to_html_with_headings(&result);
+                result = html;
+
+                let has_toc_marker = result.contains("{% toc %}");
+                let wants_toc =
+                    item.get("toc").is_some_and(|toc| toc == "true") || has_toc_marker;
+                if wants_toc {
+                    flatten_table_of_contents(&headings, &mut combined_variables);
+                    let toc = build_table_of_contents(&headings);
+                    result = if has_toc_marker {
+                        result.replace("{% toc %}", &toc)
+                    } else {
+                        format!("{toc}{result}")
+                    };
+                }
+            }
+            _ => {}
         }
     }
 
@@ -90,7 +117,8 @@ mod tests {
     #[test]
     fn test_process_template_tags_with_includes() {
         let mut includes = HashMap::new();
-        includes.insert("test.liquid".to_string(), "Hello {{ name }}!".to_string());
+        // Templates are keyed without their `.liquid` extension, same as `{% render %}`.
+        includes.insert("test".to_string(), "Hello {{ name }}!".to_string());
 
         let mut variables = HashMap::new();
         variables.insert("name".to_string(), "World".to_string());
@@ -111,8 +139,80 @@ mod tests {
         let result =
             process_template_tags(content, &variables, Some(&includes), Some(&content_item))
                 .unwrap();
-        // The markdown processor strips line breaks between non-list lines
-        assert_eq!(result, "# Test HeadingThis is a paragraph.");
+        // Headings gain an anchor id, and line breaks between non-list
+        // lines are still stripped.
+        assert_eq!(
+            result,
+            "<h1 id=\"test-heading\">Test Heading</h1>This is a paragraph."
+        );
+    }
+
+    #[test]
+    fn test_process_template_tags_with_toc_param() {
+        let includes = HashMap::new();
+        let mut content_item = HashMap::new();
+        content_item.insert("file_type".to_string(), "md".to_string());
+        content_item.insert("toc".to_string(), "true".to_string());
+        let variables = HashMap::new();
+
+        let content = "# One\n\n## Two\n\nSome text.";
+        let result =
+            process_template_tags(content, &variables, Some(&includes), Some(&content_item))
+                .unwrap();
+        assert_eq!(
+            result,
+            "<ul><li><a href=\"#one\">One</a></li><ul><li><a href=\"#two\">Two</a></li></ul></ul>\
+<h1 id=\"one\">One</h1><h2 id=\"two\">Two</h2>Some text."
+        );
+    }
+
+    #[test]
+    fn test_process_template_tags_with_toc_param_exposes_flattened_variables() {
+        let includes = HashMap::new();
+        let mut content_item = HashMap::new();
+        content_item.insert("file_type".to_string(), "md".to_string());
+        content_item.insert("toc".to_string(), "true".to_string());
+        let variables = HashMap::new();
+
+        let content = "# One\n\n## Two\n\n{{ toc.0.title }}/{{ toc.0.children.0.title }}";
+        let result =
+            process_template_tags(content, &variables, Some(&includes), Some(&content_item))
+                .unwrap();
+        assert!(result.ends_with("One/Two"));
+    }
+
+    #[test]
+    fn test_process_template_tags_with_toc_marker() {
+        let includes = HashMap::new();
+        let mut content_item = HashMap::new();
+        content_item.insert("file_type".to_string(), "md".to_string());
+        let variables = HashMap::new();
+
+        let content = "{% toc %}\n\n# One\n\nSome text.";
+        let result =
+            process_template_tags(content, &variables, Some(&includes), Some(&content_item))
+                .unwrap();
+        assert_eq!(
+            result,
+            "<ul><li><a href=\"#one\">One</a></li></ul><h1 id=\"one\">One</h1>Some text."
+        );
+    }
+
+    #[test]
+    fn test_process_template_tags_with_org() {
+        let includes = HashMap::new();
+        let mut content_item = HashMap::new();
+        content_item.insert("file_type".to_string(), "org".to_string());
+        let variables = HashMap::new();
+
+        let content = "* Test Heading\n\n- an item";
+        let result =
+            process_template_tags(content, &variables, Some(&includes), Some(&content_item))
+                .unwrap();
+        assert_eq!(
+            result,
+            "<h1>Test Heading</h1><ul><li>an item</li></ul>"
+        );
     }
 
     #[test]
@@ -0,0 +1,306 @@
+use super::liquid::IncludeResolver;
+use super::process_template_tags;
+use crate::error::Result;
+use std::collections::HashMap;
+
+/// Parses a shortcode call's parenthesized argument list, e.g.
+/// `id="abc", count=3, autoplay=true` -> `{"id": "abc", "count": "3",
+/// "autoplay": "true"}`. Accepts comma- or whitespace-separated
+/// `key="value"`/`key='value'` pairs plus bare (unquoted) numbers and
+/// booleans, the same permissiveness `parse_space_separated_key_value_params`
+/// gives `{% include %}` parameters - just with `=` instead of `:` and
+/// commas allowed between pairs, matching function-call syntax.
+fn parse_shortcode_args(args: &str) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    let chars: Vec<char> = args.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        while i < chars.len() && (chars[i].is_whitespace() || chars[i] == ',') {
+            i += 1;
+        }
+        if i >= chars.len() {
+            break;
+        }
+
+        let mut key = String::new();
+        while i < chars.len() && chars[i] != '=' && !chars[i].is_whitespace() {
+            key.push(chars[i]);
+            i += 1;
+        }
+
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if i >= chars.len() || chars[i] != '=' {
+            // malformed; continue scanning forward
+            continue;
+        }
+        i += 1; // skip '='
+
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+
+        let mut value = String::new();
+        if i < chars.len() && (chars[i] == '"' || chars[i] == '\'') {
+            let quote_char = chars[i];
+            i += 1;
+            while i < chars.len() && chars[i] != quote_char {
+                value.push(chars[i]);
+                i += 1;
+            }
+            if i < chars.len() {
+                i += 1; // skip closing quote
+            }
+        } else {
+            while i < chars.len() && chars[i] != ',' && !chars[i].is_whitespace() {
+                value.push(chars[i]);
+                i += 1;
+            }
+        }
+
+        if !key.is_empty() {
+            properties.insert(key.trim().to_string(), value);
+        }
+    }
+
+    properties
+}
+
+/// Splits a `name(args)` shortcode call into its name and raw argument
+/// string, e.g. `figure(src="x")` -> `Some(("figure", "src=\"x\""))`.
+/// Returns `None` for anything that isn't a bare identifier immediately
+/// followed by a parenthesized argument list, so a plain `{{ variable }}`
+/// or an existing `{% if %}`/`{% for %}`/`{% include %}`-style tag (none of
+/// which use parens) falls through untouched.
+fn parse_shortcode_call(content: &str) -> Option<(&str, &str)> {
+    let open = content.find('(')?;
+    let name = &content[..open];
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    content
+        .strip_prefix(name)?
+        .strip_prefix('(')?
+        .strip_suffix(')')
+        .map(|args| (name, args))
+}
+
+/// Expands author-friendly shortcode tags embedded in content body text:
+/// inline `{{ name(arg="value") }}` calls and block
+/// `{% name(arg="value") %}...{% endname %}` calls.
+///
+/// Each call is resolved against `resolver` - the same per-site registry
+/// `{% include %}` partials use, since a shortcode is just a named template
+/// invoked with function-call syntax instead of `{% include %}`'s
+/// space-separated params. Its arguments (and, for the block form, the
+/// captured inner body as a `body` variable) are merged into a copy of
+/// `variables`, and the resolved template is run through
+/// [`process_template_tags`] so it can use `{{ }}`/`{% if %}`/etc. itself.
+///
+/// A name the resolver doesn't know about, or a block form missing its
+/// closing tag, is left in the output untouched - the same graceful
+/// degradation `{% include %}` uses for an unknown partial.
+pub fn process_shortcodes(
+    template: &str,
+    resolver: &dyn IncludeResolver,
+    variables: &HashMap<String, String>,
+) -> Result<String> {
+    let after_blocks = expand_block_shortcodes(template, resolver, variables)?;
+    expand_inline_shortcodes(&after_blocks, resolver, variables)
+}
+
+fn expand_block_shortcodes(
+    template: &str,
+    resolver: &dyn IncludeResolver,
+    variables: &HashMap<String, String>,
+) -> Result<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(tag_start) = rest.find("{%") {
+        let Some(close_rel) = rest[tag_start..].find("%}") else {
+            result.push_str(rest);
+            return Ok(result);
+        };
+        let tag_end = tag_start + close_rel + 2;
+        let tag_content = rest[tag_start + 2..tag_start + close_rel].trim();
+
+        let Some((name, args)) = parse_shortcode_call(tag_content) else {
+            result.push_str(&rest[..tag_end]);
+            rest = &rest[tag_end..];
+            continue;
+        };
+
+        let end_tag = format!("{{% end{name} %}}");
+        let Some(end_rel) = rest[tag_end..].find(&end_tag) else {
+            result.push_str(&rest[..tag_end]);
+            rest = &rest[tag_end..];
+            continue;
+        };
+        let body = &rest[tag_end..tag_end + end_rel];
+        let after_end = tag_end + end_rel + end_tag.len();
+
+        result.push_str(&rest[..tag_start]);
+        match resolver.resolve(name) {
+            Some(shortcode_template) => {
+                let mut scoped_variables = variables.clone();
+                scoped_variables.extend(parse_shortcode_args(args));
+                scoped_variables.insert("body".to_string(), body.to_string());
+                result.push_str(&process_template_tags(
+                    &shortcode_template,
+                    &scoped_variables,
+                    None,
+                    None,
+                )?);
+            }
+            None => {
+                result.push_str(&rest[tag_start..after_end]);
+            }
+        }
+
+        rest = &rest[after_end..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn expand_inline_shortcodes(
+    template: &str,
+    resolver: &dyn IncludeResolver,
+    variables: &HashMap<String, String>,
+) -> Result<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(tag_start) = rest.find("{{") {
+        let Some(close_rel) = rest[tag_start..].find("}}") else {
+            result.push_str(rest);
+            return Ok(result);
+        };
+        let tag_end = tag_start + close_rel + 2;
+        let tag_content = rest[tag_start + 2..tag_start + close_rel].trim();
+
+        let Some((name, args)) = parse_shortcode_call(tag_content) else {
+            result.push_str(&rest[..tag_end]);
+            rest = &rest[tag_end..];
+            continue;
+        };
+
+        result.push_str(&rest[..tag_start]);
+        match resolver.resolve(name) {
+            Some(shortcode_template) => {
+                let mut scoped_variables = variables.clone();
+                scoped_variables.extend(parse_shortcode_args(args));
+                result.push_str(&process_template_tags(
+                    &shortcode_template,
+                    &scoped_variables,
+                    None,
+                    None,
+                )?);
+            }
+            None => {
+                result.push_str(&rest[tag_start..tag_end]);
+            }
+        }
+
+        rest = &rest[tag_end..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_shortcodes_expands_inline_call() {
+        let mut registry = HashMap::new();
+        registry.insert(
+            "youtube".to_string(),
+            "<iframe src=\"https://youtube.com/{{ id }}\"></iframe>".to_string(),
+        );
+        let variables = HashMap::new();
+
+        let input = "Before {{ youtube(id=\"abc\") }} After";
+        let result = process_shortcodes(input, &registry, &variables).unwrap();
+
+        assert_eq!(
+            result,
+            "Before <iframe src=\"https://youtube.com/abc\"></iframe> After"
+        );
+    }
+
+    #[test]
+    fn test_process_shortcodes_expands_block_call_with_body_variable() {
+        let mut registry = HashMap::new();
+        registry.insert(
+            "figure".to_string(),
+            "<figure><img src=\"{{ src }}\"><figcaption>{{ body }}</figcaption></figure>"
+                .to_string(),
+        );
+        let variables = HashMap::new();
+
+        let input = "{% figure(src=\"x.png\") %}A caption{% endfigure %}";
+        let result = process_shortcodes(input, &registry, &variables).unwrap();
+
+        assert_eq!(
+            result,
+            "<figure><img src=\"x.png\"><figcaption>A caption</figcaption></figure>"
+        );
+    }
+
+    #[test]
+    fn test_process_shortcodes_accepts_bare_numbers_and_booleans() {
+        let mut registry = HashMap::new();
+        registry.insert(
+            "gallery".to_string(),
+            "{{ columns }}/{{ autoplay }}".to_string(),
+        );
+        let variables = HashMap::new();
+
+        let input = "{{ gallery(columns=3, autoplay=true) }}";
+        let result = process_shortcodes(input, &registry, &variables).unwrap();
+
+        assert_eq!(result, "3/true");
+    }
+
+    #[test]
+    fn test_process_shortcodes_unknown_name_left_unchanged() {
+        let registry: HashMap<String, String> = HashMap::new();
+        let variables = HashMap::new();
+
+        let input = "{{ missing(id=\"x\") }}";
+        let result = process_shortcodes(input, &registry, &variables).unwrap();
+
+        assert_eq!(result, "{{ missing(id=\"x\") }}");
+    }
+
+    #[test]
+    fn test_process_shortcodes_unclosed_block_left_unchanged() {
+        let mut registry = HashMap::new();
+        registry.insert("figure".to_string(), "<figure>{{ body }}</figure>".to_string());
+        let variables = HashMap::new();
+
+        let input = "{% figure(src=\"x\") %}caption, no closing tag";
+        let result = process_shortcodes(input, &registry, &variables).unwrap();
+
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn test_process_shortcodes_ignores_plain_variables_and_existing_tags() {
+        let registry: HashMap<String, String> = HashMap::new();
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "World".to_string());
+
+        let input = "{% if name %}Hello {{ name }}{% endif %}";
+        let result = process_shortcodes(input, &registry, &variables).unwrap();
+
+        assert_eq!(result, input);
+    }
+}